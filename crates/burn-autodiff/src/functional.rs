@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+
+use burn_tensor::{Tensor, backend::AutodiffBackend};
+
+/// The backend two levels under a nested autodiff backend, e.g. `B` for `Autodiff<Autodiff<B>>`.
+pub type NestedInnerBackend<B> =
+    <<B as AutodiffBackend>::InnerBackend as AutodiffBackend>::InnerBackend;
+
+/// Computes the output of `f` at `x` and the vector-Jacobian product `vᵀ J` with the given
+/// cotangent `v`, which has the shape of the output.
+///
+/// A single backward pass is needed. The function can return any tensor, the product is
+/// obtained by differentiating `sum(f(x) * v)` with respect to `x`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// type B = Autodiff<NdArray>;
+///
+/// let (y, grad) = vjp::<B, 1, 1>(|x| x.powf_scalar(2.0), x, v); // y = x², grad = 2 x v
+/// ```
+pub fn vjp<B, const D: usize, const D2: usize>(
+    f: impl FnOnce(Tensor<B, D>) -> Tensor<B, D2>,
+    x: Tensor<B::InnerBackend, D>,
+    v: Tensor<B::InnerBackend, D2>,
+) -> (Tensor<B::InnerBackend, D2>, Tensor<B::InnerBackend, D>)
+where
+    B: AutodiffBackend,
+{
+    let x = Tensor::<B, D>::from_inner(x).require_grad();
+    let output = f(x.clone());
+    let grad = pullback(x, output.clone(), v);
+
+    (output.inner(), grad)
+}
+
+/// Differentiates `sum(output * v)` with respect to `x`, which must be tracked.
+fn pullback<B, const D: usize, const D2: usize>(
+    x: Tensor<B, D>,
+    output: Tensor<B, D2>,
+    v: Tensor<B::InnerBackend, D2>,
+) -> Tensor<B::InnerBackend, D>
+where
+    B: AutodiffBackend,
+{
+    let grads = output.mul(Tensor::from_inner(v)).sum().backward();
+
+    x.grad(&grads)
+        .unwrap_or_else(|| x.clone().inner().zeros_like())
+}
+
+/// Computes the gradient of the scalar function `f` at `x`.
+///
+/// The returned gradient is tracked by the inner backend when `x` is, so calls can be nested to
+/// compute higher-order derivatives with a nested autodiff backend.
+///
+/// # Panics
+///
+/// If the output of `f` doesn't have a single element.
+pub fn grad<B, const D: usize>(
+    f: impl FnOnce(Tensor<B, D>) -> Tensor<B, 1>,
+    x: Tensor<B::InnerBackend, D>,
+) -> Tensor<B::InnerBackend, D>
+where
+    B: AutodiffBackend,
+{
+    let x = Tensor::<B, D>::from_inner(x).require_grad();
+    let output = f(x.clone());
+    assert_eq!(
+        output.dims(),
+        [1],
+        "The function should return a tensor with a single element"
+    );
+
+    let grads = output.backward();
+
+    x.grad(&grads)
+        .unwrap_or_else(|| x.clone().inner().zeros_like())
+}
+
+/// Computes the Jacobian of `f` at `x`.
+///
+/// The Jacobian is returned as a matrix of shape `[n_outputs, n_inputs]` where each dimension is
+/// the number of elements of the flattened output and input, so row `i` is the gradient of the
+/// `i`-th output element. It requires one forward and one backward pass per output element.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// type B = Autodiff<NdArray>;
+///
+/// let jacobian = jacobian::<B, 1, 1>(|x| x.clone() * x, x); // diag(2x)
+/// ```
+pub fn jacobian<B, const D: usize, const D2: usize>(
+    f: impl Fn(Tensor<B, D>) -> Tensor<B, D2>,
+    x: Tensor<B::InnerBackend, D>,
+) -> Tensor<B::InnerBackend, 2>
+where
+    B: AutodiffBackend,
+{
+    let device = x.device();
+    let num_inputs = x.shape().num_elements();
+
+    // The first pass gives the shape of the output, and the gradient of its first element.
+    let tracked = Tensor::<B, D>::from_inner(x.clone()).require_grad();
+    let output = f(tracked.clone());
+    let output_shape = output.shape();
+    let num_outputs = output_shape.num_elements();
+
+    let basis = Tensor::<B::InnerBackend, 2>::eye(num_outputs, &device);
+    let cotangent = |i| basis.clone().narrow(0, i, 1).reshape(output_shape.clone());
+
+    let mut rows = Vec::with_capacity(num_outputs);
+    rows.push(pullback(tracked, output, cotangent(0)).reshape([1, num_inputs]));
+    for i in 1..num_outputs {
+        let (_, row) = vjp(&f, x.clone(), cotangent(i));
+        rows.push(row.reshape([1, num_inputs]));
+    }
+
+    Tensor::cat(rows, 0)
+}
+
+/// Computes the Hessian of the scalar function `f` at `x`.
+///
+/// The Hessian is returned as a matrix of shape `[n_inputs, n_inputs]` where `n_inputs` is the
+/// number of elements of the flattened input. It is the [Jacobian](jacobian) of the
+/// [gradient](grad), so the backend must be a nested autodiff backend, e.g.
+/// `Autodiff<Autodiff<B>>`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// type B = Autodiff<Autodiff<NdArray>>;
+///
+/// let hessian = hessian::<B, 1>(|x| x.powf_scalar(3.0).sum(), x); // diag(6x)
+/// ```
+pub fn hessian<B, const D: usize>(
+    f: impl Fn(Tensor<B, D>) -> Tensor<B, 1>,
+    x: Tensor<NestedInnerBackend<B>, D>,
+) -> Tensor<NestedInnerBackend<B>, 2>
+where
+    B: AutodiffBackend,
+    B::InnerBackend: AutodiffBackend,
+{
+    jacobian::<B::InnerBackend, D, D>(|x| grad(&f, x), x)
+}
@@ -16,6 +16,12 @@ extern crate alloc;
 
 /// Checkpoint module.
 pub mod checkpoint;
+/// Functional transforms: vector-Jacobian products, gradients, Jacobians and Hessians.
+///
+/// Only reverse-mode transforms are provided. The backend has no forward mode to compute
+/// Jacobian-vector products with dual numbers, and no vectorizing map to batch a per-example
+/// function over a leading dimension.
+pub mod functional;
 /// Gradients module.
 pub mod grads;
 /// Operation module.
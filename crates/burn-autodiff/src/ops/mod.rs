@@ -9,6 +9,7 @@ mod tensor;
 mod transaction;
 
 pub(crate) mod maxmin;
+pub(crate) mod scan;
 pub(crate) mod sort;
 
 pub use backward::*;
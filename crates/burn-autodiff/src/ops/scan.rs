@@ -0,0 +1,65 @@
+use super::{Backward, Ops};
+use crate::{checkpoint::base::Checkpointer, grads::Gradients};
+use alloc::vec;
+use alloc::vec::Vec;
+use burn_tensor::{TensorMetadata, backend::Backend, ops::FloatTensor};
+
+/// Backward of the linear scan `h_t = a_t * h_{t-1} + b_t`.
+///
+/// The adjoint `λ_t = g_t + a_{t+1} * λ_{t+1}` is itself a linear scan running backward along
+/// the sequence, so it reuses the backend kernel on flipped tensors. The gradients are then
+/// `λ` for `b` and `λ_t * h_{t-1}` for `a`.
+#[derive(Debug)]
+pub(crate) struct LinearScan;
+
+impl<B: Backend> Backward<B, 2> for LinearScan {
+    type State = (FloatTensor<B>, FloatTensor<B>, usize);
+
+    fn backward(
+        self,
+        ops: Ops<Self::State, 2>,
+        grads: &mut Gradients,
+        _checkpointer: &mut Checkpointer,
+    ) {
+        let (a, output, dim) = ops.state;
+        let [node_a, node_b] = ops.parents;
+        let grad = grads.consume::<B>(&ops.node);
+
+        let coefficients = shift_right::<B>(B::float_flip(a, &[dim]), dim);
+        let adjoint = B::float_linear_scan(coefficients, B::float_flip(grad, &[dim]), dim);
+        let adjoint = B::float_flip(adjoint, &[dim]);
+
+        if let Some(node) = node_a {
+            let output_prev = shift_right::<B>(output, dim);
+            grads.register::<B>(node.id, B::float_mul(adjoint.clone(), output_prev));
+        }
+
+        if let Some(node) = node_b {
+            grads.register::<B>(node.id, adjoint);
+        }
+    }
+}
+
+/// Shifts the tensor by one position along `dim`, filling the first position with zeros.
+fn shift_right<B: Backend>(tensor: FloatTensor<B>, dim: usize) -> FloatTensor<B> {
+    let shape = tensor.shape();
+    let length = shape.dims[dim];
+
+    if length == 0 {
+        return tensor;
+    }
+
+    let mut zeros_shape = shape.clone();
+    zeros_shape.dims[dim] = 1;
+    let mut ranges: Vec<_> = shape.dims.iter().map(|&size| 0..size).collect();
+    ranges[dim] = 0..length - 1;
+
+    let zeros = B::float_zeros(
+        zeros_shape,
+        &B::float_device(&tensor),
+        tensor.dtype().into(),
+    );
+    let shifted = B::float_slice(tensor, &ranges);
+
+    B::float_cat(vec![zeros, shifted], dim)
+}
@@ -32,17 +32,21 @@ impl AutodiffClient for MutexClient {
         *server = Some(server_new);
     }
     fn backward<B: Backend>(&self, root: AutodiffTensor<B>) -> Gradients {
-        let mut server = SERVER.lock();
         let node_id = root.node.id;
         let grads = Gradients::new::<B>(root.node, root.primitive);
 
-        if let Some(server) = server.as_mut() {
-            return server.backward(grads, node_id);
-        }
-
-        let mut server_new = AutodiffServer::default();
-        let gradients = server_new.backward(grads, node_id);
-        *server = Some(server_new);
+        // The steps are executed without holding the lock, since they register new nodes when
+        // the inner backend is itself an autodiff backend.
+        let (tape, checkpointer) = SERVER
+            .lock()
+            .get_or_insert_with(AutodiffServer::default)
+            .tape(node_id);
+        let gradients = AutodiffServer::execute_steps(tape, grads, checkpointer);
+
+        SERVER
+            .lock()
+            .get_or_insert_with(AutodiffServer::default)
+            .free_unavailable_nodes();
 
         gradients
     }
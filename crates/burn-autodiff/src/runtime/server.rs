@@ -31,24 +31,36 @@ impl AutodiffServer {
     }
 
     pub fn backward(&mut self, grads: Gradients, node_id: NodeID) -> Gradients {
+        let (tape, checkpointer) = self.tape(node_id);
+        let gradients = Self::execute_steps(tape, grads, checkpointer);
+
+        self.free_unavailable_nodes();
+
+        gradients
+    }
+
+    /// Removes the steps needed to compute the gradients of the given node from the graph.
+    ///
+    /// The steps can be executed with [execute_steps](Self::execute_steps) without access to the
+    /// server, which is required when the inner backend is itself an autodiff backend since the
+    /// steps then register new nodes.
+    pub fn tape(&mut self, node_id: NodeID) -> (Vec<Vec<StepBoxed>>, Checkpointer) {
         let step = self.steps.remove(&node_id).expect(
             "Node should have a step registered, did you forget to call \
              `Tensor::register_grad` on the tensor where you need gradients?",
         );
         let builder = self.actions_builder.remove(&node_id).unwrap();
 
-        let (tape, checkpointer) = self.build_tape(node_id, step, builder);
-
-        let gradients = Self::execute_steps(tape, grads, checkpointer);
+        self.build_tape(node_id, step, builder)
+    }
 
-        // Cleanup
+    /// Frees the nodes that can't be used by a backward pass anymore.
+    pub fn free_unavailable_nodes(&mut self) {
         self.memory_management
             .free_unavailable_nodes(|node_id: &NodeID| {
                 self.steps.remove(node_id);
                 self.actions_builder.remove(node_id);
             });
-
-        gradients
     }
 
     fn build_tape(
@@ -88,7 +100,7 @@ impl AutodiffServer {
         (tape, checkpointer)
     }
 
-    fn execute_steps(
+    pub fn execute_steps(
         tape: Vec<Vec<StepBoxed>>,
         mut grads: Gradients,
         mut checkpointer: Checkpointer,
@@ -1,7 +1,7 @@
 #[burn_tensor_testgen::testgen(bridge)]
 mod tests {
     use super::*;
-    use burn_tensor::{DType, Distribution, Tensor, backend::Backend};
+    use burn_tensor::{DType, Distribution, Tensor, TensorData, Tolerance, backend::Backend};
 
     #[test]
     fn test_full_precision() {
@@ -27,4 +27,24 @@ mod tests {
         assert!(x1_grad.is_some());
         assert!(x2_grad.is_some());
     }
+
+    #[test]
+    fn test_cast_backward() {
+        let device = Default::default();
+        let x =
+            Tensor::<TestAutodiffBackend, 1>::from_data([1.0, -2.0, 3.0], &device).require_grad();
+        let dtype = x.dtype();
+
+        let x_full = x.clone().cast(DType::F32);
+        let y = (x_full.clone() * x_full).sum().cast(dtype);
+
+        let grads = y.backward();
+        let grad = x.grad(&grads).unwrap();
+
+        assert_eq!(grad.dtype(), dtype);
+        grad.into_data().assert_approx_eq::<FloatType>(
+            &TensorData::from([2.0, -4.0, 6.0]),
+            Tolerance::default(),
+        );
+    }
 }
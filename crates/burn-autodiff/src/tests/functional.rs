@@ -0,0 +1,87 @@
+#[burn_tensor_testgen::testgen(functional)]
+mod tests {
+    use super::*;
+    use burn_autodiff::functional::{grad, hessian, jacobian, vjp};
+    use burn_tensor::{Tensor, TensorData, Tolerance, ops::FloatElem};
+    type FT = FloatElem<TestBackend>;
+
+    type TestNestedAutodiffBackend = burn_autodiff::Autodiff<TestAutodiffBackend>;
+
+    #[test]
+    fn should_compute_vjp() {
+        let device = Default::default();
+        let x = Tensor::<TestBackend, 1>::from_data([1.0, 2.0, 3.0], &device);
+        let v = Tensor::<TestBackend, 1>::from_data([1.0, 0.5, 2.0], &device);
+
+        let (output, grad) = vjp::<TestAutodiffBackend, 1, 1>(|x| x.powf_scalar(2.0), x, v);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([1.0, 4.0, 9.0]), Tolerance::default());
+        grad.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([2.0, 2.0, 12.0]), Tolerance::default());
+    }
+
+    #[test]
+    fn should_compute_jacobian() {
+        let device = Default::default();
+        let x = Tensor::<TestBackend, 1>::from_data([1.0, 2.0], &device);
+
+        // f(x) = x sum(x), so J = sum(x) I + x 1^T.
+        let jacobian = jacobian::<TestAutodiffBackend, 1, 1>(|x| x.clone().mul(x.sum()), x);
+
+        jacobian.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[4.0, 1.0], [2.0, 5.0]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn should_compute_grad() {
+        let device = Default::default();
+        let x = Tensor::<TestBackend, 1>::from_data([1.0, -2.0], &device);
+
+        let grad = grad::<TestAutodiffBackend, 1>(|x| x.powf_scalar(3.0).sum(), x);
+
+        grad.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([3.0, 12.0]), Tolerance::default());
+    }
+
+    #[test]
+    fn jacobian_should_call_the_function_once_per_output_element() {
+        let device = Default::default();
+        let x = Tensor::<TestBackend, 1>::from_data([1.0, 2.0, 3.0], &device);
+        let calls = core::cell::Cell::new(0);
+
+        let jacobian = jacobian::<TestAutodiffBackend, 1, 2>(
+            |x| {
+                calls.set(calls.get() + 1);
+                x.reshape([1, 3]).narrow(1, 1, 2).exp()
+            },
+            x,
+        );
+
+        assert_eq!(calls.get(), 2);
+        jacobian.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[0.0, 7.389056, 0.0], [0.0, 0.0, 20.085537]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn should_compute_hessian() {
+        let device = Default::default();
+        let x = Tensor::<TestBackend, 1>::from_data([1.0, 2.0], &device);
+
+        // f(x) = sum(x^3) + sum(x)^2, so H = diag(6x) + 2.
+        let hessian = hessian::<TestNestedAutodiffBackend, 1>(
+            |x| x.clone().powf_scalar(3.0).sum() + x.sum().powf_scalar(2.0),
+            x,
+        );
+
+        hessian.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[8.0, 2.0], [2.0, 14.0]]),
+            Tolerance::default(),
+        );
+    }
+}
@@ -0,0 +1,80 @@
+#[burn_tensor_testgen::testgen(higher_order)]
+mod tests {
+    use super::*;
+    use burn_tensor::{Tensor, TensorData, Tolerance, ops::FloatElem};
+    type FT = FloatElem<TestBackend>;
+
+    type TestNestedAutodiffBackend = burn_autodiff::Autodiff<TestAutodiffBackend>;
+
+    #[test]
+    fn should_diff_twice_powf_scalar() {
+        let device = Default::default();
+        let x = Tensor::<TestNestedAutodiffBackend, 1>::from_data([1.0, 2.0, -3.0], &device)
+            .require_nested_grad();
+
+        let grads = x.clone().powf_scalar(3.0).sum().backward();
+        let dx = x.grad(&grads).unwrap();
+
+        dx.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([3.0, 12.0, 27.0]), Tolerance::default());
+
+        let grads = dx.sum().backward();
+        let dx2 = x.inner().grad(&grads).unwrap();
+
+        dx2.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([6.0, 12.0, -18.0]), Tolerance::default());
+    }
+
+    #[test]
+    fn should_diff_twice_composed_ops() {
+        let device = Default::default();
+        let values = [0.5, 1.0, 2.0];
+        let x = Tensor::<TestNestedAutodiffBackend, 1>::from_data(values, &device)
+            .require_nested_grad();
+
+        // f(x) = x sin(x), f'(x) = sin(x) + x cos(x), f''(x) = 2 cos(x) - x sin(x)
+        let grads = x.clone().mul(x.clone().sin()).sum().backward();
+        let dx = x.grad(&grads).unwrap();
+        let grads = dx.sum().backward();
+        let dx2 = x.inner().grad(&grads).unwrap();
+
+        let expected = values.map(|x: f64| 2.0 * x.cos() - x * x.sin());
+        let tolerance = Tolerance::default().set_half_precision_relative(2e-3);
+        dx2.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from(expected), tolerance);
+    }
+
+    #[test]
+    fn should_diff_gradient_penalty() {
+        let device = Default::default();
+        let x = Tensor::<TestNestedAutodiffBackend, 2>::from_data([[1.0, 2.0]], &device);
+        let w = Tensor::<TestNestedAutodiffBackend, 2>::from_data([[3.0], [4.0]], &device)
+            .require_nested_grad();
+
+        // f(w) = (xw)^2, so df/dw = 2 (xw) x^T.
+        let grads = x.matmul(w.clone()).powf_scalar(2.0).sum().backward();
+        let grad = w.grad(&grads).unwrap();
+
+        grad.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[22.0], [44.0]]), Tolerance::default());
+
+        // The penalty ||df/dw||^2 has the gradient 4 x^T (x df/dw).
+        let grads = grad.powf_scalar(2.0).sum().backward();
+        let grad = w.inner().grad(&grads).unwrap();
+
+        grad.to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[440.0], [880.0]]), Tolerance::default());
+    }
+
+    #[test]
+    fn should_not_track_gradients_without_nested_grad() {
+        let device = Default::default();
+        let x =
+            Tensor::<TestNestedAutodiffBackend, 1>::from_data([1.0, 2.0], &device).require_grad();
+
+        let grads = x.clone().powf_scalar(3.0).sum().backward();
+        let dx = x.grad(&grads).unwrap();
+
+        assert!(!dx.is_require_grad());
+    }
+}
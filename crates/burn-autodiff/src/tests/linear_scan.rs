@@ -0,0 +1,78 @@
+#[burn_tensor_testgen::testgen(ad_linear_scan)]
+mod tests {
+    use super::*;
+    use burn_tensor::{TensorData, Tolerance, scan};
+
+    #[test]
+    fn should_diff_linear_scan() {
+        let device = Default::default();
+        let a = TestAutodiffTensor::<2>::from_data(
+            TensorData::from([[0.9, 0.1, 0.5, -1.0], [1.0, 0.7, -0.4, 0.6]]),
+            &device,
+        )
+        .require_grad();
+        let b = TestAutodiffTensor::<2>::from_data(
+            TensorData::from([[1.0, -1.0, 0.5, 2.0], [0.2, 0.4, 1.5, -0.5]]),
+            &device,
+        )
+        .require_grad();
+        let weights = TestAutodiffTensor::<2>::from_data(
+            TensorData::from([[1.0, 2.0, -1.0, 0.5], [0.3, -2.0, 1.0, 1.5]]),
+            &device,
+        );
+
+        let output = scan::linear_scan(a.clone(), b.clone(), 1);
+        let grads = (output * weights.clone()).sum().backward();
+        let grad_a = a.grad(&grads).unwrap();
+        let grad_b = b.grad(&grads).unwrap();
+
+        let a_ref = a.clone().detach().require_grad();
+        let b_ref = b.clone().detach().require_grad();
+        let output_ref = sequential_scan(a_ref.clone(), b_ref.clone());
+        let grads_ref = (output_ref * weights).sum().backward();
+
+        let tolerance = Tolerance::default().set_half_precision_relative(1e-3);
+        grad_a
+            .into_data()
+            .assert_approx_eq::<FloatType>(&a_ref.grad(&grads_ref).unwrap().into_data(), tolerance);
+        grad_b
+            .into_data()
+            .assert_approx_eq::<FloatType>(&b_ref.grad(&grads_ref).unwrap().into_data(), tolerance);
+    }
+
+    #[test]
+    fn should_diff_linear_scan_with_untracked_coefficients() {
+        let device = Default::default();
+        let a = TestAutodiffTensor::<1>::from_data(TensorData::from([0.5, 2.0, -1.0]), &device);
+        let b = TestAutodiffTensor::<1>::from_data(TensorData::from([1.0, 2.0, 3.0]), &device)
+            .require_grad();
+
+        let grads = scan::linear_scan(a, b.clone(), 0).sum().backward();
+
+        // λ_2 = 1, λ_1 = 1 + a_2 * λ_2 = 0, λ_0 = 1 + a_1 * λ_1 = 1.
+        b.grad(&grads)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FloatType>(
+                &TensorData::from([1.0, 0.0, 1.0]),
+                Tolerance::default(),
+            );
+    }
+
+    fn sequential_scan(
+        a: TestAutodiffTensor<2>,
+        b: TestAutodiffTensor<2>,
+    ) -> TestAutodiffTensor<2> {
+        let [batch, length] = a.dims();
+        let mut state = TestAutodiffTensor::<2>::zeros([batch, 1], &a.device());
+        let mut outputs = Vec::with_capacity(length);
+
+        for t in 0..length {
+            state = a.clone().slice([0..batch, t..t + 1]) * state
+                + b.clone().slice([0..batch, t..t + 1]);
+            outputs.push(state.clone());
+        }
+
+        TestAutodiffTensor::cat(outputs, 1)
+    }
+}
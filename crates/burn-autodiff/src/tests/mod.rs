@@ -29,9 +29,12 @@ mod exp;
 mod expand;
 mod flip;
 mod floor;
+mod functional;
 mod gather_scatter;
 mod gelu;
 mod gradients;
+mod higher_order;
+mod linear_scan;
 mod log;
 mod log1p;
 mod log_sigmoid;
@@ -159,6 +162,8 @@ macro_rules! testgen_with_float_param {
         burn_autodiff::testgen_bridge!();
         burn_autodiff::testgen_checkpoint!();
         burn_autodiff::testgen_memory_management!();
+        burn_autodiff::testgen_higher_order!();
+        burn_autodiff::testgen_functional!();
 
         // Activation
         burn_autodiff::testgen_ad_relu!();
@@ -226,5 +231,6 @@ macro_rules! testgen_with_float_param {
         burn_autodiff::testgen_ad_expand!();
         burn_autodiff::testgen_ad_sort!();
         burn_autodiff::testgen_ad_repeat_dim!();
+        burn_autodiff::testgen_ad_linear_scan!();
     };
 }
@@ -23,6 +23,9 @@ pub mod network;
 /// Parallel utilities.
 pub mod parallel;
 
+/// Named scopes of the module forward passes, to attribute the operations to the modules.
+pub mod scope;
+
 /// Tensor utilities.
 pub mod tensor {
     use alloc::vec;
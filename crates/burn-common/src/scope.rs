@@ -0,0 +1,94 @@
+use alloc::string::String;
+
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static SCOPES: core::cell::RefCell<Vec<String>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// A named scope of a module forward pass, entered on the current thread until the scope is
+/// dropped.
+///
+/// Scopes are nested, so a model can enter a scope named after each of its fields in its forward
+/// pass to get paths such as `encoder.layers.0.attention`.
+///
+/// # Example
+///
+/// ```ignore
+/// pub fn forward(&self, x: Tensor<B, 3>) -> Tensor<B, 3> {
+///     let _scope = ForwardScope::enter("attention");
+///     self.attention.forward(x)
+/// }
+/// ```
+///
+/// The stock attention and transformer modules enter the scopes of their submodules.
+///
+/// Scopes require the `std` feature, they are ignored otherwise.
+#[must_use = "The scope is exited when dropped"]
+pub struct ForwardScope {
+    _private: (),
+}
+
+impl ForwardScope {
+    /// Enter the scope with the given name.
+    pub fn enter(name: &str) -> Self {
+        #[cfg(feature = "std")]
+        SCOPES.with(|scopes| scopes.borrow_mut().push(String::from(name)));
+        #[cfg(not(feature = "std"))]
+        let _ = name;
+
+        Self { _private: () }
+    }
+
+    /// Run the given function in the scope with the given name.
+    pub fn run<T, F: FnOnce() -> T>(name: &str, func: F) -> T {
+        let _scope = Self::enter(name);
+        func()
+    }
+}
+
+impl Drop for ForwardScope {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+/// The path of the scopes entered on the current thread, separated by dots, which is empty
+/// outside of any scope.
+pub fn scope_path() -> String {
+    #[cfg(feature = "std")]
+    {
+        SCOPES.with(|scopes| scopes.borrow().join("."))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        String::new()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_scopes() {
+        assert_eq!(scope_path(), "");
+        {
+            let _encoder = ForwardScope::enter("encoder");
+            let _layer = ForwardScope::enter("layers.0");
+            assert_eq!(scope_path(), "encoder.layers.0");
+        }
+        let _decoder = ForwardScope::enter("decoder");
+        assert_eq!(scope_path(), "decoder");
+    }
+
+    #[test]
+    fn test_run_in_scope() {
+        let path = ForwardScope::run("encoder", || ForwardScope::run("norm", scope_path));
+        assert_eq!(path, "encoder.norm");
+        assert_eq!(scope_path(), "");
+    }
+}
@@ -0,0 +1,364 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::tensor::{DType, FloatDType, Tensor, backend::Backend};
+
+/// The reduced precision float types of mixed precision training.
+#[derive(Config, Debug, PartialEq)]
+pub enum HalfPrecision {
+    /// Half precision floats.
+    F16,
+    /// Brain floats, with the range of `f32` and a reduced precision.
+    BF16,
+}
+
+impl From<HalfPrecision> for FloatDType {
+    fn from(value: HalfPrecision) -> Self {
+        match value {
+            HalfPrecision::F16 => FloatDType::F16,
+            HalfPrecision::BF16 => FloatDType::BF16,
+        }
+    }
+}
+
+/// The operations that can be kept in full precision when autocasting, since they are
+/// numerically sensitive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FullPrecisionOp {
+    /// The softmax of the attention scores.
+    Softmax,
+    /// The normalization layers.
+    Norm,
+    /// The loss functions.
+    Loss,
+}
+
+/// The policy of the autocasting: the float type of the operations run in reduced precision,
+/// e.g. the linear and convolution layers, and the operations kept in full precision.
+#[derive(Clone, Copy, Debug)]
+pub struct AutocastPolicy {
+    dtype: FloatDType,
+    softmax: bool,
+    norm: bool,
+    loss: bool,
+}
+
+impl AutocastPolicy {
+    /// Create a new policy running the reduced precision operations with the given float type,
+    /// and keeping the softmax, normalization and loss operations in full precision.
+    pub fn new(dtype: impl Into<FloatDType>) -> Self {
+        Self {
+            dtype: dtype.into(),
+            softmax: true,
+            norm: true,
+            loss: true,
+        }
+    }
+
+    /// Keep the given operation in full precision, or run it with the type of its inputs.
+    pub fn with_full_precision(mut self, op: FullPrecisionOp, full_precision: bool) -> Self {
+        match op {
+            FullPrecisionOp::Softmax => self.softmax = full_precision,
+            FullPrecisionOp::Norm => self.norm = full_precision,
+            FullPrecisionOp::Loss => self.loss = full_precision,
+        }
+        self
+    }
+
+    /// The float type of the operations run in reduced precision.
+    pub fn dtype(&self) -> FloatDType {
+        self.dtype
+    }
+
+    /// Whether the given operation is kept in full precision.
+    pub fn is_full_precision(&self, op: FullPrecisionOp) -> bool {
+        match op {
+            FullPrecisionOp::Softmax => self.softmax,
+            FullPrecisionOp::Norm => self.norm,
+            FullPrecisionOp::Loss => self.loss,
+        }
+    }
+
+    /// Enable autocasting with this policy on the current thread until the returned guard is
+    /// dropped.
+    #[cfg(feature = "std")]
+    pub fn enable(self) -> AutocastGuard {
+        let previous = AUTOCAST.with(|autocast| autocast.replace(Some(self)));
+        AutocastGuard { previous }
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static AUTOCAST: core::cell::Cell<Option<AutocastPolicy>> = const { core::cell::Cell::new(None) };
+}
+
+/// Restores the previous autocasting policy of the thread when dropped.
+#[cfg(feature = "std")]
+#[must_use = "Autocasting is disabled when the guard is dropped"]
+pub struct AutocastGuard {
+    previous: Option<AutocastPolicy>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for AutocastGuard {
+    fn drop(&mut self) {
+        AUTOCAST.with(|autocast| autocast.set(self.previous));
+    }
+}
+
+/// The autocasting policy enabled on the current thread, if any.
+///
+/// Autocasting requires the `std` feature, it is never enabled otherwise.
+pub fn autocast_policy() -> Option<AutocastPolicy> {
+    #[cfg(feature = "std")]
+    {
+        AUTOCAST.with(|autocast| autocast.get())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        None
+    }
+}
+
+/// The float type of the operations run in reduced precision when autocasting is enabled on the
+/// current thread.
+pub fn autocast_dtype() -> Option<FloatDType> {
+    autocast_policy().map(|policy| policy.dtype())
+}
+
+/// Cast the tensor to the reduced precision type when autocasting is enabled on the current
+/// thread, returning it unchanged otherwise.
+pub fn autocast<B: Backend, const D: usize>(tensor: Tensor<B, D>) -> Tensor<B, D> {
+    match autocast_dtype() {
+        Some(dtype) => cast_to(tensor, dtype.into()),
+        None => tensor,
+    }
+}
+
+/// Cast the tensor to `f32` when autocasting is enabled and the given operation is kept in full
+/// precision, returning it unchanged otherwise.
+///
+/// The output of the operation should be cast back to the type of its input with [cast_to].
+pub fn full_precision<B: Backend, const D: usize>(
+    op: FullPrecisionOp,
+    tensor: Tensor<B, D>,
+) -> Tensor<B, D> {
+    match autocast_policy() {
+        Some(policy)
+            if policy.is_full_precision(op)
+                && !matches!(tensor.dtype(), DType::F32 | DType::F64) =>
+        {
+            tensor.cast(FloatDType::F32)
+        }
+        _ => tensor,
+    }
+}
+
+/// Cast the tensor to the given type if it has another one.
+pub fn cast_to<B: Backend, const D: usize>(tensor: Tensor<B, D>, dtype: DType) -> Tensor<B, D> {
+    if tensor.dtype() == dtype {
+        return tensor;
+    }
+
+    tensor.cast(dtype)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::conv::{
+        Conv1dConfig, Conv2dConfig, Conv3dConfig, ConvTranspose1dConfig, ConvTranspose2dConfig,
+        ConvTranspose3dConfig,
+    };
+    use crate::nn::transformer::{TransformerEncoderConfig, TransformerEncoderInput};
+    use crate::nn::{LayerNormConfig, LinearConfig, RotaryEncodingConfig};
+    use crate::tensor::Distribution;
+    use crate::{TestAutodiffBackend, TestBackend};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    #[cfg(any(
+        feature = "test-cuda",
+        feature = "test-rocm",
+        feature = "test-tch",
+        feature = "test-vulkan",
+        feature = "test-metal"
+    ))]
+    use {crate::nn::loss::CrossEntropyLossConfig, crate::tensor::Int};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_guard_restores_policy() {
+        assert!(autocast_policy().is_none());
+
+        {
+            let _guard = AutocastPolicy::new(HalfPrecision::BF16).enable();
+            assert!(matches!(autocast_dtype(), Some(FloatDType::BF16)));
+
+            {
+                let _guard = AutocastPolicy::new(FloatDType::F64).enable();
+                assert!(matches!(autocast_dtype(), Some(FloatDType::F64)));
+            }
+
+            assert!(matches!(autocast_dtype(), Some(FloatDType::BF16)));
+        }
+
+        assert!(autocast_policy().is_none());
+    }
+
+    #[test]
+    fn test_full_precision_policy() {
+        let policy = AutocastPolicy::new(FloatDType::F64)
+            .with_full_precision(FullPrecisionOp::Softmax, false);
+
+        assert!(!policy.is_full_precision(FullPrecisionOp::Softmax));
+        assert!(policy.is_full_precision(FullPrecisionOp::Norm));
+        assert!(policy.is_full_precision(FullPrecisionOp::Loss));
+    }
+
+    #[test]
+    fn test_full_precision_keeps_full_precision_inputs() {
+        let device = Default::default();
+        let tensor = Tensor::<TestBackend, 1>::from_floats([1.0, 2.0], &device);
+        let dtype = tensor.dtype();
+
+        let _guard = AutocastPolicy::new(FloatDType::F64).enable();
+        let tensor = full_precision(FullPrecisionOp::Norm, tensor);
+
+        assert_eq!(tensor.dtype(), dtype);
+    }
+
+    #[test]
+    fn test_autocast_linear_outputs_reduced_type() {
+        let device = Default::default();
+        let linear = LinearConfig::new(2, 1).init::<TestAutodiffBackend>(&device);
+        let input = Tensor::<TestAutodiffBackend, 2>::from_floats([[1.0, 2.0]], &device);
+        let dtype = input.dtype();
+
+        let output = {
+            let _guard = AutocastPolicy::new(FloatDType::F64).enable();
+            linear.forward(input)
+        };
+        assert_eq!(output.dtype(), DType::F64);
+
+        // The gradients of the parameters have the type of the parameters.
+        let grads = output.sum().backward();
+        let grad = linear.weight.grad(&grads).unwrap();
+        assert_eq!(grad.dtype(), dtype);
+    }
+
+    #[test]
+    fn test_autocast_convolutions_output_reduced_type() {
+        let device = Default::default();
+        let _guard = AutocastPolicy::new(FloatDType::F64).enable();
+
+        let input = Tensor::<TestBackend, 3>::ones([1, 2, 4], &device);
+        let output = Conv1dConfig::new(2, 3, 3)
+            .init(&device)
+            .forward(input.clone());
+        assert_eq!(output.dtype(), DType::F64);
+        let output = ConvTranspose1dConfig::new([2, 3], 3)
+            .init(&device)
+            .forward(input);
+        assert_eq!(output.dtype(), DType::F64);
+
+        let input = Tensor::<TestBackend, 4>::ones([1, 2, 4, 4], &device);
+        let output = Conv2dConfig::new([2, 3], [3, 3])
+            .init(&device)
+            .forward(input.clone());
+        assert_eq!(output.dtype(), DType::F64);
+        let output = ConvTranspose2dConfig::new([2, 3], [3, 3])
+            .init(&device)
+            .forward(input);
+        assert_eq!(output.dtype(), DType::F64);
+
+        let input = Tensor::<TestBackend, 5>::ones([1, 2, 4, 4, 4], &device);
+        let output = Conv3dConfig::new([2, 3], [3, 3, 3])
+            .init(&device)
+            .forward(input.clone());
+        assert_eq!(output.dtype(), DType::F64);
+        let output = ConvTranspose3dConfig::new([2, 3], [3, 3, 3])
+            .init(&device)
+            .forward(input);
+        assert_eq!(output.dtype(), DType::F64);
+    }
+
+    #[test]
+    fn test_autocast_norm_without_full_precision_keeps_reduced_type() {
+        let device = Default::default();
+        let norm = LayerNormConfig::new(2).init::<TestBackend>(&device);
+        let input = Tensor::<TestBackend, 2>::from_floats([[1.0, 3.0]], &device);
+        let expected = norm.forward(input.clone());
+
+        let _guard = AutocastPolicy::new(FloatDType::F64)
+            .with_full_precision(FullPrecisionOp::Norm, false)
+            .enable();
+        let output = norm.forward(input.cast(FloatDType::F64));
+
+        assert_eq!(output.dtype(), DType::F64);
+        output
+            .cast(expected.dtype())
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_autocast_transformer_keeps_the_type_of_the_residual_stream() {
+        let device = Default::default();
+        let encoder = TransformerEncoderConfig::new(4, 8, 2, 2)
+            .with_alibi(true)
+            .with_rope(Some(RotaryEncodingConfig::new(16, 4)))
+            .init::<TestBackend>(&device);
+        let input = Tensor::<TestBackend, 3>::random([2, 3, 4], Distribution::Default, &device);
+        let dtype = input.dtype();
+        let expected = encoder.forward(TransformerEncoderInput::new(input.clone()));
+
+        let _guard = AutocastPolicy::new(FloatDType::F64).enable();
+        let output = encoder.forward(TransformerEncoderInput::new(input));
+
+        assert_eq!(output.dtype(), dtype);
+        output
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[cfg(any(
+        feature = "test-cuda",
+        feature = "test-rocm",
+        feature = "test-tch",
+        feature = "test-vulkan",
+        feature = "test-metal"
+    ))]
+    #[test]
+    fn test_autocast_half_precision_norm_and_loss_run_in_f32() {
+        let device = Default::default();
+        let norm = LayerNormConfig::new(4).init::<TestBackend>(&device);
+        let logits = Tensor::<TestBackend, 2>::from_floats(
+            [[1.0, -2.0, 0.5, 3.0], [0.25, 2.0, -1.0, 0.0]],
+            &device,
+        );
+        let targets = Tensor::<TestBackend, 1, Int>::from_ints([3, 1], &device);
+        let loss = CrossEntropyLossConfig::new().init(&device);
+        let expected_norm = norm.forward(logits.clone());
+        let expected_loss = loss.forward(logits.clone(), targets.clone());
+
+        let _guard = AutocastPolicy::new(HalfPrecision::F16).enable();
+        let half = logits.cast(FloatDType::F16);
+
+        // The norm is computed in `f32` and returns the type of its input.
+        let output = norm.forward(half.clone());
+        assert_eq!(output.dtype(), DType::F16);
+        output
+            .cast(FloatDType::F32)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected_norm.into_data(), Tolerance::rel_abs(1e-2, 1e-2));
+
+        // The loss is computed and returned in `f32`.
+        let output = loss.forward(half, targets);
+        assert_eq!(output.dtype(), DType::F32);
+        output
+            .into_data()
+            .assert_approx_eq::<FT>(&expected_loss.into_data(), Tolerance::rel_abs(1e-2, 1e-2));
+    }
+}
@@ -0,0 +1,44 @@
+use crate as burn;
+
+use super::{AutocastPolicy, FullPrecisionOp, GradScalerConfig, HalfPrecision};
+use crate::config::Config;
+
+/// Configuration of automatic mixed precision (AMP) training.
+///
+/// The parameters of the model are kept in full precision, and the linear and convolution layers
+/// cast their inputs and weights to the reduced precision type when autocasting is enabled, and
+/// return outputs of that type. The operations kept in full precision return the type of their
+/// input, except the losses which stay in `f32`, and the transformer layers add the output of
+/// their sublayers to the residual stream with the type of the stream. The deformable convolution
+/// isn't autocast, since the positions it samples are sensitive to the precision of its offsets.
+///
+/// The gradients flow back to the full precision parameters, and are scaled by a
+/// [gradient scaler](super::GradScaler) to avoid their underflow.
+#[derive(Config, Debug)]
+pub struct MixedPrecisionConfig {
+    /// The reduced precision float type of the forward and backward passes.
+    #[config(default = "HalfPrecision::F16")]
+    pub dtype: HalfPrecision,
+    /// Keep the softmax of the attention in full precision.
+    #[config(default = true)]
+    pub softmax_f32: bool,
+    /// Keep the normalization layers in full precision.
+    #[config(default = true)]
+    pub norm_f32: bool,
+    /// Keep the loss functions in full precision.
+    #[config(default = true)]
+    pub loss_f32: bool,
+    /// The configuration of the dynamic loss scaling.
+    #[config(default = "GradScalerConfig::new()")]
+    pub scaler: GradScalerConfig,
+}
+
+impl MixedPrecisionConfig {
+    /// The autocasting policy of the configuration.
+    pub fn policy(&self) -> AutocastPolicy {
+        AutocastPolicy::new(self.dtype.clone())
+            .with_full_precision(FullPrecisionOp::Softmax, self.softmax_f32)
+            .with_full_precision(FullPrecisionOp::Norm, self.norm_f32)
+            .with_full_precision(FullPrecisionOp::Loss, self.loss_f32)
+    }
+}
@@ -0,0 +1,7 @@
+mod autocast;
+mod base;
+mod scaler;
+
+pub use autocast::*;
+pub use base::*;
+pub use scaler::*;
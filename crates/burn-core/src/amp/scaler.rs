@@ -0,0 +1,431 @@
+use crate as burn;
+
+use crate::LearningRate;
+use crate::config::Config;
+use crate::module::{AutodiffModule, ModuleVisitor, ParamId};
+use crate::optim::{GradientsParams, Optimizer};
+use crate::tensor::{Bool, Tensor, backend::AutodiffBackend, backend::Backend, cast::ToElement};
+use serde::{Deserialize, Serialize};
+
+/// Configuration to create a [gradient scaler](GradScaler).
+#[derive(Config, Debug)]
+pub struct GradScalerConfig {
+    /// The initial scale of the loss.
+    #[config(default = 65536.0)]
+    pub init_scale: f32,
+    /// The factor multiplying the scale after `growth_interval` steps with finite gradients.
+    #[config(default = 2.0)]
+    pub growth_factor: f32,
+    /// The factor multiplying the scale when the gradients aren't finite.
+    #[config(default = 0.5)]
+    pub backoff_factor: f32,
+    /// The number of consecutive steps with finite gradients before growing the scale.
+    #[config(default = 2000)]
+    pub growth_interval: usize,
+}
+
+impl GradScalerConfig {
+    /// Initialize the gradient scaler.
+    ///
+    /// # Panics
+    ///
+    /// If the initial scale isn't positive, the growth factor isn't greater than 1 or the backoff
+    /// factor isn't between 0 and 1.
+    pub fn init(&self) -> GradScaler {
+        assert!(
+            self.init_scale > 0.0,
+            "The initial scale of the loss should be positive"
+        );
+        assert!(
+            self.growth_factor > 1.0,
+            "The growth factor of the scale should be greater than 1"
+        );
+        assert!(
+            self.backoff_factor > 0.0 && self.backoff_factor < 1.0,
+            "The backoff factor of the scale should be between 0 and 1"
+        );
+
+        GradScaler {
+            scale: self.init_scale,
+            growth_factor: self.growth_factor,
+            backoff_factor: self.backoff_factor,
+            growth_interval: self.growth_interval,
+            num_finite_steps: 0,
+        }
+    }
+}
+
+/// Dynamic loss scaling for mixed precision training.
+///
+/// Small gradients underflow in half precision, so the loss is multiplied by a large scale before
+/// the backward pass and the gradients are divided by it before the optimizer step. When the
+/// gradients overflow, the step is skipped and the scale is reduced, and it grows again after a
+/// number of steps without overflow.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let loss = model.forward(batch);
+/// let grads = scaler.scale(loss).backward();
+/// let grads = GradientsParams::from_grads(grads, &model);
+/// model = scaler.step(lr, &mut optim, model, grads);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GradScaler {
+    scale: f32,
+    growth_factor: f32,
+    backoff_factor: f32,
+    growth_interval: usize,
+    num_finite_steps: usize,
+}
+
+/// The state of a [gradient scaler](GradScaler) changing during the training, to be saved with
+/// the checkpoints.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GradScalerState {
+    /// The current scale of the loss.
+    pub scale: f32,
+    /// The number of consecutive steps with finite gradients since the scale last changed.
+    pub num_finite_steps: usize,
+}
+
+impl GradScaler {
+    /// The current scale of the loss.
+    pub fn scale_value(&self) -> f32 {
+        self.scale
+    }
+
+    /// The state of the scaler, to be restored with [load_state](Self::load_state).
+    pub fn state(&self) -> GradScalerState {
+        GradScalerState {
+            scale: self.scale,
+            num_finite_steps: self.num_finite_steps,
+        }
+    }
+
+    /// Restore the state of the scaler.
+    pub fn load_state(&mut self, state: &GradScalerState) {
+        self.scale = state.scale;
+        self.num_finite_steps = state.num_finite_steps;
+    }
+
+    /// Multiply the loss by the current scale.
+    pub fn scale<B: Backend, const D: usize>(&self, loss: Tensor<B, D>) -> Tensor<B, D> {
+        loss.mul_scalar(self.scale)
+    }
+
+    /// Divide the gradients of the module by the current scale.
+    ///
+    /// # Returns
+    ///
+    /// The unscaled gradients, or `None` if any of them isn't finite.
+    pub fn unscale<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+        module: &M,
+        mut grads: GradientsParams,
+    ) -> Option<GradientsParams> {
+        let mut visitor = GradientsUnscaler::<B> {
+            grads: &mut grads,
+            inv_scale: 1.0 / self.scale,
+            finite: None,
+        };
+        module.visit(&mut visitor);
+
+        let finite = visitor
+            .finite
+            .take()
+            .is_none_or(|finite| finite.into_scalar().to_bool());
+
+        finite.then_some(grads)
+    }
+
+    /// Update the scale after a step, reducing it if the gradients weren't finite and growing it
+    /// after `growth_interval` consecutive steps with finite gradients.
+    pub fn update(&mut self, finite: bool) {
+        if !finite {
+            self.scale *= self.backoff_factor;
+            self.num_finite_steps = 0;
+            return;
+        }
+
+        self.num_finite_steps += 1;
+
+        if self.num_finite_steps >= self.growth_interval {
+            let scale = self.scale * self.growth_factor;
+            // Stop growing instead of overflowing the scale itself.
+            if scale.is_finite() {
+                self.scale = scale;
+            }
+            self.num_finite_steps = 0;
+        }
+    }
+
+    /// Unscale the gradients and update the module with the optimizer, or skip the step if the
+    /// gradients aren't finite, then update the scale.
+    pub fn step<B, M, O>(
+        &mut self,
+        lr: LearningRate,
+        optim: &mut O,
+        module: M,
+        grads: GradientsParams,
+    ) -> M
+    where
+        B: AutodiffBackend,
+        M: AutodiffModule<B>,
+        O: Optimizer<M, B>,
+    {
+        match self.unscale::<B, M>(&module, grads) {
+            Some(grads) => {
+                self.update(true);
+                optim.step(lr, module, grads)
+            }
+            None => {
+                self.update(false);
+                module
+            }
+        }
+    }
+
+    /// Make the current scale available to [scale_loss] on the current thread until the
+    /// returned guard is dropped.
+    #[cfg(feature = "std")]
+    pub fn enable(&self) -> LossScaleGuard {
+        let scale = LossScale {
+            scale: self.scale,
+            applied: false,
+        };
+        let previous = LOSS_SCALE.with(|current| current.replace(Some(scale)));
+        LossScaleGuard { previous }
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+struct LossScale {
+    scale: f32,
+    // Whether a loss was scaled since the scale was enabled.
+    applied: bool,
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static LOSS_SCALE: core::cell::Cell<Option<LossScale>> = const { core::cell::Cell::new(None) };
+}
+
+/// Restores the previous loss scale of the thread when dropped.
+#[cfg(feature = "std")]
+#[must_use = "The loss scale is disabled when the guard is dropped"]
+pub struct LossScaleGuard {
+    previous: Option<LossScale>,
+}
+
+#[cfg(feature = "std")]
+impl LossScaleGuard {
+    /// Whether a loss was scaled with [scale_loss] on the current thread since the guard was
+    /// created.
+    ///
+    /// The gradients should only be unscaled when it's the case, since they are computed from
+    /// the unscaled loss otherwise.
+    pub fn is_applied(&self) -> bool {
+        LOSS_SCALE.with(|current| current.get().is_some_and(|scale| scale.applied))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for LossScaleGuard {
+    fn drop(&mut self) {
+        LOSS_SCALE.with(|current| current.set(self.previous));
+    }
+}
+
+/// Multiply the loss by the scale of the [gradient scaler](GradScaler) enabled on the current
+/// thread, returning it unchanged if there is none.
+///
+/// The learner enables its scaler around each training step when mixed precision is used, and
+/// only unscales the gradients of the steps that [applied](LossScaleGuard::is_applied) it, so
+/// training steps should compute their gradients from the scaled loss to benefit from loss
+/// scaling:
+///
+/// ```rust,ignore
+/// let grads = burn::amp::scale_loss(loss.clone()).backward();
+/// ```
+pub fn scale_loss<B: Backend, const D: usize>(loss: Tensor<B, D>) -> Tensor<B, D> {
+    #[cfg(feature = "std")]
+    if let Some(mut scale) = LOSS_SCALE.with(|current| current.get()) {
+        scale.applied = true;
+        LOSS_SCALE.with(|current| current.set(Some(scale)));
+        return loss.mul_scalar(scale.scale);
+    }
+
+    loss
+}
+
+struct GradientsUnscaler<'a, B: AutodiffBackend> {
+    grads: &'a mut GradientsParams,
+    inv_scale: f32,
+    // Whether all the gradients visited so far are finite, on the device of the first gradient.
+    finite: Option<Tensor<B::InnerBackend, 1, Bool>>,
+}
+
+impl<B: AutodiffBackend> ModuleVisitor<B> for GradientsUnscaler<'_, B> {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) else {
+            return;
+        };
+
+        let grad = grad.mul_scalar(self.inv_scale);
+        let finite = grad.clone().is_finite().all();
+
+        self.finite = Some(match self.finite.take() {
+            Some(all_finite) => {
+                let finite = finite.to_device(&all_finite.device());
+                all_finite.bool_and(finite)
+            }
+            None => finite,
+        });
+
+        self.grads.register::<B::InnerBackend, D>(id, grad);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::SgdConfig;
+    use crate::tensor::TensorData;
+    use crate::{TestAutodiffBackend, TestBackend};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn linear() -> Linear<TestAutodiffBackend> {
+        let device = Default::default();
+        let mut linear = LinearConfig::new(2, 1)
+            .with_bias(false)
+            .init::<TestAutodiffBackend>(&device);
+        linear.weight = linear
+            .weight
+            .map(|_| Tensor::from_floats([[1.0], [1.0]], &device).require_grad());
+        linear
+    }
+
+    fn gradients(linear: &Linear<TestAutodiffBackend>, grad: [[f32; 1]; 2]) -> GradientsParams {
+        let mut grads = GradientsParams::new();
+        grads.register::<TestBackend, 2>(
+            linear.weight.id,
+            Tensor::from_floats(grad, &Default::default()),
+        );
+        grads
+    }
+
+    #[test]
+    fn test_scale_and_unscale() {
+        let device = Default::default();
+        let linear = linear();
+        let scaler = GradScalerConfig::new().with_init_scale(4.0).init();
+        let inputs = Tensor::<TestAutodiffBackend, 2>::from_floats([[1.0, 2.0]], &device);
+
+        let loss = linear.forward(inputs).sum();
+        let grads = scaler.scale(loss).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+
+        let mut grads = scaler.unscale(&linear, grads).unwrap();
+        grads
+            .remove::<TestBackend, 2>(linear.weight.id)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[1.0], [2.0]]), Tolerance::default());
+    }
+
+    #[test]
+    fn test_skip_step_and_backoff() {
+        let linear = linear();
+        let mut optim = SgdConfig::new().init();
+        let mut scaler = GradScalerConfig::new().with_init_scale(4.0).init();
+
+        let grads = gradients(&linear, [[f32::INFINITY], [1.0]]);
+        let linear = scaler.step(1.0, &mut optim, linear, grads);
+
+        linear
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[1.0], [1.0]]), Tolerance::default());
+        assert_eq!(scaler.scale_value(), 2.0);
+
+        let grads = gradients(&linear, [[f32::NAN], [1.0]]);
+        let linear = scaler.step(1.0, &mut optim, linear, grads);
+        assert_eq!(scaler.scale_value(), 1.0);
+
+        let grads = gradients(&linear, [[2.0], [4.0]]);
+        let linear = scaler.step(1.0, &mut optim, linear, grads);
+
+        // The gradients are divided by the scale.
+        linear
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[-1.0], [-3.0]]), Tolerance::default());
+    }
+
+    #[test]
+    fn test_growth_after_interval() {
+        let mut scaler = GradScalerConfig::new()
+            .with_init_scale(4.0)
+            .with_growth_interval(2)
+            .init();
+
+        scaler.update(true);
+        assert_eq!(scaler.scale_value(), 4.0);
+        scaler.update(true);
+        assert_eq!(scaler.scale_value(), 8.0);
+        scaler.update(true);
+        scaler.update(false);
+        scaler.update(true);
+        assert_eq!(scaler.scale_value(), 4.0);
+    }
+
+    #[test]
+    fn test_scale_loss_with_enabled_scaler() {
+        let device = Default::default();
+        let loss = Tensor::<TestBackend, 1>::from_floats([3.0], &device);
+        let scaler = GradScalerConfig::new().with_init_scale(8.0).init();
+
+        let unscaled = scale_loss(loss.clone());
+        let scaled = {
+            let guard = scaler.enable();
+            assert!(!guard.is_applied());
+            let scaled = scale_loss(loss);
+            assert!(guard.is_applied());
+            scaled
+        };
+
+        unscaled
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([3.0]), Tolerance::default());
+        scaled
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([24.0]), Tolerance::default());
+    }
+
+    #[test]
+    fn test_load_state() {
+        let mut scaler = GradScalerConfig::new()
+            .with_init_scale(4.0)
+            .with_growth_interval(2)
+            .init();
+        scaler.update(false);
+        scaler.update(true);
+
+        let mut restored = GradScalerConfig::new()
+            .with_init_scale(4.0)
+            .with_growth_interval(2)
+            .init();
+        restored.load_state(&scaler.state());
+
+        assert_eq!(restored.scale_value(), 2.0);
+        restored.update(true);
+        assert_eq!(restored.scale_value(), 4.0);
+    }
+}
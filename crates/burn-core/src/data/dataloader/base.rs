@@ -1,6 +1,8 @@
 use burn_tensor::backend::Backend;
+use serde::{Deserialize, Serialize};
 
 pub use crate::data::dataset::{Dataset, DatasetIterator};
+use crate::record::{PrecisionSettings, Record};
 use core::iter::Iterator;
 use std::sync::Arc;
 
@@ -14,10 +16,39 @@ pub struct Progress {
     pub items_total: usize,
 }
 
+/// The position of a data loader iterator, used to resume an interrupted iteration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataLoaderState {
+    /// The number of iterators created by the data loader, including the one the state was taken
+    /// from, which determines how the dataset was shuffled.
+    pub iteration: usize,
+
+    /// The number of items loaded by each worker of the data loader.
+    pub cursors: Vec<usize>,
+}
+
+impl<B: Backend> Record<B> for DataLoaderState {
+    type Item<S: PrecisionSettings> = Self;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        self
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, _device: &B::Device) -> Self {
+        item
+    }
+}
+
 /// A data loader iterator that can be used to iterate over a data loader.
 pub trait DataLoaderIterator<O>: Iterator<Item = O> {
     /// Returns the progress of the data loader.
     fn progress(&self) -> Progress;
+
+    /// Returns the state of the iterator after the last returned item, if it can be
+    /// [resumed](DataLoader::resume).
+    fn state(&self) -> Option<DataLoaderState> {
+        None
+    }
 }
 
 /// A data loader that can be used to iterate over a dataset.
@@ -25,6 +56,16 @@ pub trait DataLoader<B: Backend, O>: Send + Sync {
     /// Returns a boxed [iterator](DataLoaderIterator) to iterate over the data loader.
     fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a>;
 
+    /// Returns a boxed [iterator](DataLoaderIterator) resuming the iteration at the given
+    /// [state](DataLoaderIterator::state), yielding the items that weren't returned yet with the
+    /// same shuffling.
+    ///
+    /// Data loaders that can't be resumed start a new iteration instead.
+    fn resume<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        log::warn!("The data loader can't be resumed at {state:?}, starting a new iteration.");
+        self.iter()
+    }
+
     /// The number of items (not the number of batches nor the number of iterations),
     /// corresponding to the items_total of the progress returned by the iterator.
     fn num_items(&self) -> usize;
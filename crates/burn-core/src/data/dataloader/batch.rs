@@ -1,10 +1,13 @@
-use super::{BatchStrategy, DataLoader, DataLoaderIterator, Progress, batcher::Batcher};
+use super::{
+    BatchStrategy, DataLoader, DataLoaderIterator, DataLoaderState, Progress, batcher::Batcher,
+};
 use burn_dataset::{
     Dataset,
     transform::{PartialDataset, ShuffledDataset},
 };
 use burn_tensor::backend::Backend;
-use std::ops::DerefMut;
+use rand::RngCore;
+use rand::rngs::StdRng;
 use std::sync::Arc;
 
 /// A data loader that can be used to iterate over a dataset in batches.
@@ -13,7 +16,52 @@ pub struct BatchDataLoader<B: Backend, I, O> {
     dataset: Arc<dyn Dataset<I>>,
     batcher: Arc<dyn Batcher<B, I, O>>,
     device: B::Device,
-    rng: Option<Arc<spin::Mutex<rand::rngs::StdRng>>>,
+    iterations: Arc<spin::Mutex<Iterations>>,
+}
+
+/// The number of iterations started by a data loader, with the rng shuffling the dataset of each
+/// one.
+#[derive(Clone)]
+struct Iterations {
+    count: usize,
+    rng: Option<StdRng>,
+    initial_rng: Option<StdRng>,
+}
+
+impl Iterations {
+    /// Starts a new iteration, shuffling the dataset if the data loader has an rng.
+    fn next<I: Clone + Send + Sync + 'static>(
+        &mut self,
+        dataset: Arc<dyn Dataset<I>>,
+    ) -> Arc<dyn Dataset<I>> {
+        self.count += 1;
+
+        // Advancing the current rng ensures that each new iteration shuffles the dataset
+        // differently.
+        match &mut self.rng {
+            Some(rng) => Arc::new(ShuffledDataset::new(dataset, rng)),
+            None => dataset,
+        }
+    }
+
+    /// Starts the given iteration again, shuffling the dataset the same way it was the first time.
+    fn rewind<I: Clone + Send + Sync + 'static>(
+        &mut self,
+        iteration: usize,
+        dataset: Arc<dyn Dataset<I>>,
+    ) -> Arc<dyn Dataset<I>> {
+        self.count = iteration - 1;
+        self.rng = self.initial_rng.clone();
+
+        // Each shuffle advances the rng once.
+        if let Some(rng) = &mut self.rng {
+            for _ in 0..self.count {
+                rng.next_u64();
+            }
+        }
+
+        self.next(dataset)
+    }
 }
 
 impl<B: Backend, I, O> Clone for BatchDataLoader<B, I, O> {
@@ -23,7 +71,7 @@ impl<B: Backend, I, O> Clone for BatchDataLoader<B, I, O> {
             dataset: self.dataset.clone(),
             batcher: self.batcher.clone(),
             device: self.device.clone(),
-            rng: self.rng.clone(),
+            iterations: self.iterations.clone(),
         }
     }
 }
@@ -48,20 +96,37 @@ impl<B: Backend, I, O> BatchDataLoader<B, I, O> {
         dataset: Arc<dyn Dataset<I>>,
         batcher: Arc<dyn Batcher<B, I, O>>,
         device: B::Device,
-        rng: Option<rand::rngs::StdRng>,
+        rng: Option<StdRng>,
+    ) -> Self {
+        let iterations = Iterations {
+            count: 0,
+            initial_rng: rng.clone(),
+            rng,
+        };
+
+        Self::with_iterations(strategy, dataset, batcher, device, iterations)
+    }
+
+    fn with_iterations(
+        strategy: Box<dyn BatchStrategy<I>>,
+        dataset: Arc<dyn Dataset<I>>,
+        batcher: Arc<dyn Batcher<B, I, O>>,
+        device: B::Device,
+        iterations: Iterations,
     ) -> Self {
         Self {
             strategy,
             dataset,
             batcher,
             device,
-            rng: rng.map(|rng| Arc::new(spin::Mutex::new(rng))),
+            iterations: Arc::new(spin::Mutex::new(iterations)),
         }
     }
 }
 
 /// A data loader iterator that can be used to iterate over a data loader.
 struct BatchDataloaderIterator<B: Backend, I, O> {
+    iteration: usize,
     current_index: usize,
     strategy: Box<dyn BatchStrategy<I>>,
     dataset: Arc<dyn Dataset<I>>,
@@ -76,21 +141,39 @@ where
     O: Send + 'static,
 {
     fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
-        // When starting a new iteration, we first check if the dataloader was created with an rng,
-        // implying that we should shuffle the dataset beforehand, while advancing the current
-        // rng to ensure that each new iteration shuffles the dataset differently.
-        let dataset = match &self.rng {
-            Some(rng) => Arc::new(ShuffledDataset::new(
-                self.dataset.clone(),
-                rng.lock().deref_mut(),
-            )),
-            None => self.dataset.clone(),
-        };
+        let mut iterations = self.iterations.lock();
+        let dataset = iterations.next(self.dataset.clone());
+
         Box::new(BatchDataloaderIterator::new(
             self.strategy.clone_dyn(),
             dataset,
             self.batcher.clone(),
             self.device.clone(),
+            iterations.count,
+            0,
+        ))
+    }
+
+    fn resume<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        assert_eq!(
+            state.cursors.len(),
+            1,
+            "The state of a batch data loader should have a single cursor"
+        );
+        if state.iteration == 0 {
+            return self.iter();
+        }
+
+        let mut iterations = self.iterations.lock();
+        let dataset = iterations.rewind(state.iteration, self.dataset.clone());
+
+        Box::new(BatchDataloaderIterator::new(
+            self.strategy.clone_dyn(),
+            dataset,
+            self.batcher.clone(),
+            self.device.clone(),
+            iterations.count,
+            state.cursors[0],
         ))
     }
 
@@ -99,30 +182,24 @@ where
     }
 
     fn to_device(&self, device: &B::Device) -> Arc<dyn DataLoader<B, O>> {
-        let rng = self.rng.as_ref().map(|rng| {
-            let rng = rng.lock();
-            rng.clone()
-        });
-        Arc::new(Self::new(
+        let iterations = self.iterations.lock().clone();
+        Arc::new(Self::with_iterations(
             self.strategy.clone_dyn(),
             self.dataset.clone(),
             self.batcher.clone(),
             device.clone(),
-            rng,
+            iterations,
         ))
     }
 
     fn slice(&self, start: usize, end: usize) -> Arc<dyn DataLoader<B, O>> {
-        let rng = self.rng.as_ref().map(|rng| {
-            let rng = rng.lock();
-            rng.clone()
-        });
-        let dataloader = Self::new(
+        let iterations = self.iterations.lock().clone();
+        let dataloader = Self::with_iterations(
             self.strategy.clone_dyn(),
             Arc::new(PartialDataset::new(self.dataset.clone(), start, end)),
             self.batcher.clone(),
             self.device.clone(),
-            rng,
+            iterations,
         );
         Arc::new(dataloader)
     }
@@ -137,6 +214,8 @@ impl<B: Backend, I, O> BatchDataloaderIterator<B, I, O> {
     /// * `dataset` - The dataset.
     /// * `batcher` - The batcher.
     /// * `device`  - The device to use when loading a batch.
+    /// * `iteration` - The number of iterators created by the data loader, including this one.
+    /// * `current_index` - The index of the first item to load.
     ///
     /// # Returns
     ///
@@ -146,9 +225,12 @@ impl<B: Backend, I, O> BatchDataloaderIterator<B, I, O> {
         dataset: Arc<dyn Dataset<I>>,
         batcher: Arc<dyn Batcher<B, I, O>>,
         device: B::Device,
+        iteration: usize,
+        current_index: usize,
     ) -> Self {
         BatchDataloaderIterator {
-            current_index: 0,
+            iteration,
+            current_index,
             strategy,
             dataset,
             batcher,
@@ -182,6 +264,13 @@ impl<B: Backend, I, O> DataLoaderIterator<O> for BatchDataloaderIterator<B, I, O
     fn progress(&self) -> Progress {
         Progress::new(self.current_index, self.dataset.len())
     }
+
+    fn state(&self) -> Option<DataLoaderState> {
+        Some(DataLoaderState {
+            iteration: self.iteration,
+            cursors: vec![self.current_index],
+        })
+    }
 }
 
 #[cfg(test)]
@@ -192,6 +281,7 @@ mod tests {
     use crate::data::dataloader::FixBatchStrategy;
     use crate::data::dataloader::batcher::TestBatcher;
     use crate::data::dataset::FakeDataset;
+    use rand::SeedableRng;
 
     #[test]
     fn test_batch_dataloader() {
@@ -255,4 +345,41 @@ mod tests {
 
         assert_eq!(items_dataloader, items_dataloader_slice);
     }
+
+    #[test]
+    fn test_batch_dataloader_resume() {
+        let batcher = Arc::new(TestBatcher::new());
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let dataloader = BatchDataLoader::new(
+            Box::new(FixBatchStrategy::new(5)),
+            dataset,
+            batcher,
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        );
+
+        // Interrupt the second iteration after two batches.
+        let _first: Vec<_> = dataloader.iter().collect();
+        let mut iterator = dataloader.iter();
+        let _ = iterator.next();
+        let _ = iterator.next();
+        let state = iterator.state().unwrap();
+        let remaining: Vec<_> = iterator.collect();
+        let next: Vec<_> = dataloader.iter().collect();
+
+        assert_eq!(
+            state,
+            DataLoaderState {
+                iteration: 2,
+                cursors: vec![10]
+            }
+        );
+
+        let resumed: Vec<_> = dataloader.resume(&state).collect();
+        assert_eq!(resumed, remaining);
+
+        // The following iterations are shuffled the same way.
+        let resumed_next: Vec<_> = dataloader.iter().collect();
+        assert_eq!(resumed_next, next);
+    }
 }
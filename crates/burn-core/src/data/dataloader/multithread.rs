@@ -6,7 +6,10 @@ use rand::distr::{Distribution, StandardUniform};
 use rand::rngs::StdRng;
 
 use super::batcher::Batcher;
-use super::{BatchDataLoader, BatchStrategy, DataLoader, DataLoaderIterator, Progress};
+use super::{
+    BatchDataLoader, BatchStrategy, DataLoader, DataLoaderIterator, DataLoaderState, Progress,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, OnceLock, mpsc};
 use std::thread;
 
@@ -24,6 +27,8 @@ pub struct MultiThreadDataLoader<B: Backend, I, O> {
 
     // The lazily initialized data loaders
     dataloaders: OnceLock<Vec<BatchDataLoader<B, I, O>>>,
+    // The number of iterators created
+    iterations: AtomicUsize,
 }
 
 /// A message that can be sent between threads.
@@ -37,6 +42,7 @@ pub enum Message<O> {
 }
 
 struct MultiThreadsDataloaderIterator<O> {
+    iteration: usize,
     num_done: usize,
     workers: Vec<thread::JoinHandle<()>>,
     receiver: mpsc::Receiver<Message<O>>,
@@ -79,6 +85,7 @@ where
             device,
             rng,
             dataloaders: OnceLock::new(),
+            iterations: AtomicUsize::new(0),
         }
     }
 
@@ -128,14 +135,17 @@ where
             })
             .as_ref()
     }
-}
 
-impl<B: Backend, I, O> DataLoader<B, O> for MultiThreadDataLoader<B, I, O>
-where
-    I: Send + Sync + Clone + 'static,
-    O: Send + 'static + std::fmt::Debug,
-{
-    fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
+    /// Spawns a thread per data loader, each one sending the items of a new iterator, or of one
+    /// resumed at the given cursors.
+    fn spawn_workers(
+        &self,
+        iteration: usize,
+        cursors: Option<&[usize]>,
+    ) -> Box<dyn DataLoaderIterator<O> + '_>
+    where
+        O: std::fmt::Debug,
+    {
         // This will initialize the loader if it hasn't been initialized yet
         let dataloaders = self.initialize();
 
@@ -149,10 +159,20 @@ where
             .map(|(index, dataloader)| {
                 let dataloader_cloned = dataloader.clone();
                 let sender_cloned = sender.clone();
-                progresses.push(Progress::new(0, dataloader_cloned.num_items()));
+                let cursor = cursors.map(|cursors| cursors[index]);
+                progresses.push(Progress::new(
+                    cursor.unwrap_or(0),
+                    dataloader_cloned.num_items(),
+                ));
 
                 thread::spawn(move || {
-                    let mut iterator = dataloader_cloned.iter();
+                    let mut iterator = match cursor {
+                        Some(cursor) => dataloader_cloned.resume(&DataLoaderState {
+                            iteration,
+                            cursors: vec![cursor],
+                        }),
+                        None => dataloader_cloned.iter(),
+                    };
                     while let Some(item) = iterator.next() {
                         let progress = iterator.progress();
 
@@ -170,9 +190,35 @@ where
             .collect();
 
         Box::new(MultiThreadsDataloaderIterator::new(
-            receiver, handlers, progresses,
+            iteration, receiver, handlers, progresses,
         ))
     }
+}
+
+impl<B: Backend, I, O> DataLoader<B, O> for MultiThreadDataLoader<B, I, O>
+where
+    I: Send + Sync + Clone + 'static,
+    O: Send + 'static + std::fmt::Debug,
+{
+    fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        let iteration = self.iterations.fetch_add(1, Ordering::Relaxed) + 1;
+
+        self.spawn_workers(iteration, None)
+    }
+
+    fn resume<'a>(&'a self, state: &DataLoaderState) -> Box<dyn DataLoaderIterator<O> + 'a> {
+        if state.iteration == 0 {
+            return self.iter();
+        }
+        assert_eq!(
+            state.cursors.len(),
+            self.num_threads,
+            "The state of a multi-threaded data loader should have a cursor per thread"
+        );
+        self.iterations.store(state.iteration, Ordering::Relaxed);
+
+        self.spawn_workers(state.iteration, Some(&state.cursors))
+    }
 
     fn num_items(&self) -> usize {
         // For num_items, we can directly use the dataset size without
@@ -206,11 +252,13 @@ where
 
 impl<O> MultiThreadsDataloaderIterator<O> {
     pub fn new(
+        iteration: usize,
         receiver: mpsc::Receiver<Message<O>>,
         workers: Vec<thread::JoinHandle<()>>,
         progresses: Vec<Progress>,
     ) -> Self {
         MultiThreadsDataloaderIterator {
+            iteration,
             num_done: 0,
             workers,
             receiver,
@@ -230,6 +278,18 @@ impl<O: std::fmt::Debug> DataLoaderIterator<O> for MultiThreadsDataloaderIterato
 
         Progress::new(items_processed, items_total)
     }
+
+    fn state(&self) -> Option<DataLoaderState> {
+        // Items queued by the workers but not returned yet are loaded again when resuming.
+        Some(DataLoaderState {
+            iteration: self.iteration,
+            cursors: self
+                .progresses
+                .iter()
+                .map(|progress| progress.items_processed)
+                .collect(),
+        })
+    }
 }
 
 impl<O: std::fmt::Debug> Iterator for MultiThreadsDataloaderIterator<O> {
@@ -416,4 +476,34 @@ mod tests {
         assert_eq!(single_thread_cnt, multi_thread_cnt);
         assert_eq!(items_single_thread, items_multi_thread);
     }
+
+    #[test]
+    fn test_multi_thread_batch_dataloader_resume() {
+        let batcher = Arc::new(TestBatcher::new());
+        let dataset = Arc::new(FakeDataset::<String>::new(27));
+        let dataloader = MultiThreadDataLoader::new(
+            Box::new(FixBatchStrategy::new(5)),
+            dataset,
+            batcher,
+            2,
+            Default::default(),
+            Some(StdRng::seed_from_u64(42)),
+        );
+
+        let mut iterator = dataloader.iter();
+        let first = iterator.next().unwrap();
+        let state = iterator.state().unwrap();
+        let mut remaining: Vec<_> = iterator.collect();
+
+        assert_eq!(state.iteration, 1);
+        assert_eq!(state.cursors.iter().sum::<usize>(), 5);
+
+        // The workers are resumed at their cursors, only the order of their batches may differ.
+        let mut resumed: Vec<_> = dataloader.resume(&state).collect();
+        remaining.sort();
+        resumed.sort();
+
+        assert!(!resumed.contains(&first));
+        assert_eq!(resumed, remaining);
+    }
 }
@@ -1,7 +1,12 @@
 use crate as burn;
 
-use crate::{config::Config, tensor::Tensor};
-use burn_tensor::backend::Backend;
+use crate::{
+    config::Config,
+    module::{AutodiffModule, ModuleVisitor, ParamId},
+    optim::GradientsParams,
+    tensor::Tensor,
+};
+use burn_tensor::backend::{AutodiffBackend, Backend};
 
 /// Gradient Clipping provides a way to mitigate exploding gradients
 #[derive(Config, Debug)]
@@ -11,6 +16,12 @@ pub enum GradientClippingConfig {
 
     /// Clip the gradient by norm.
     Norm(f32),
+
+    /// Clip the gradients by the norm of all the gradients of the module.
+    GlobalNorm(f32),
+
+    /// Clip the gradients by unit-wise ratio to the norm of the weights.
+    Adaptive(f32),
 }
 
 impl GradientClippingConfig {
@@ -23,6 +34,8 @@ impl GradientClippingConfig {
         match self {
             GradientClippingConfig::Value(val) => GradientClipping::Value(*val),
             GradientClippingConfig::Norm(val) => GradientClipping::Norm(*val),
+            GradientClippingConfig::GlobalNorm(val) => GradientClipping::GlobalNorm(*val),
+            GradientClippingConfig::Adaptive(val) => GradientClipping::Adaptive(*val),
         }
     }
 }
@@ -37,8 +50,26 @@ pub enum GradientClipping {
 
     /// Clip the gradient by norm.
     Norm(f32),
+
+    /// Clip the gradients by the L2 norm of all the gradients of the module, as if they were
+    /// concatenated into a single vector, scaling every gradient by the same factor.
+    GlobalNorm(f32),
+
+    /// Adaptive gradient clipping (AGC), from [High-Performance Large-Scale Image Recognition
+    /// Without Normalization](https://arxiv.org/abs/2102.06171).
+    ///
+    /// Each unit of a gradient is scaled down when its norm exceeds the given factor times the
+    /// norm of the same unit of the weight. Units are the columns of matrices, matching the
+    /// `[d_input, d_output]` layout of [linear](crate::nn::Linear) weights, and the slices along
+    /// the first dimension of higher rank tensors, the output channels of convolutions. Vectors
+    /// are clipped as a whole.
+    Adaptive(f32),
 }
 
+/// The minimum weight norm used by [adaptive clipping](GradientClipping::Adaptive), so that
+/// gradients of weights initialized to zero can still grow.
+const AGC_EPSILON: f32 = 1e-3;
+
 impl GradientClipping {
     /// Clip the gradient.
     ///
@@ -49,13 +80,64 @@ impl GradientClipping {
     /// # Returns
     ///
     /// The clipped gradient.
+    ///
+    /// # Notes
+    ///
+    /// Alone, a gradient is clipped by [global norm](GradientClipping::GlobalNorm) the same way
+    /// it is by [norm](GradientClipping::Norm). [Adaptive clipping](GradientClipping::Adaptive)
+    /// requires the weights, see [clip_gradients](Self::clip_gradients).
     pub fn clip_gradient<B: Backend, const D: usize>(&self, grad: Tensor<B, D>) -> Tensor<B, D> {
         match self {
             GradientClipping::Value(threshold) => self.clip_by_value(grad, *threshold),
-            GradientClipping::Norm(max_norm) => self.clip_by_norm(grad, *max_norm),
+            GradientClipping::Norm(max_norm) | GradientClipping::GlobalNorm(max_norm) => {
+                self.clip_by_norm(grad, *max_norm)
+            }
+            GradientClipping::Adaptive(_) => {
+                panic!("Adaptive gradient clipping requires the weights of the gradient")
+            }
         }
     }
 
+    /// Clip all the gradients of a module.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module the gradients were computed for.
+    /// * `grads` - The gradients to clip.
+    ///
+    /// # Returns
+    ///
+    /// The clipped gradients.
+    pub fn clip_gradients<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+        module: &M,
+        mut grads: GradientsParams,
+    ) -> GradientsParams {
+        let scale = match self {
+            GradientClipping::GlobalNorm(max_norm) => {
+                let Some(squared_norm) = grads.squared_l2_norm::<B, M>(module) else {
+                    return grads;
+                };
+                let clip_coef = squared_norm
+                    .sqrt()
+                    .add_scalar(1e-6)
+                    .recip()
+                    .mul_scalar(*max_norm);
+                Some(clip_coef.clamp_max(1.0))
+            }
+            _ => None,
+        };
+
+        let mut visitor = GradientsClipper::<B> {
+            clipping: self,
+            grads: &mut grads,
+            scale,
+        };
+        module.visit(&mut visitor);
+
+        grads
+    }
+
     fn clip_by_value<B: Backend, const D: usize>(
         &self,
         grad: Tensor<B, D>,
@@ -80,6 +162,35 @@ impl GradientClipping {
         grad.mul(clip_coef_clamped.unsqueeze())
     }
 
+    fn clip_adaptive<B: Backend, const D: usize>(
+        &self,
+        param: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        clipping: f32,
+    ) -> Tensor<B, D> {
+        let param_norm = Self::unit_wise_norm(param).clamp_min(AGC_EPSILON);
+        let grad_norm = Self::unit_wise_norm(grad.clone());
+        let max_norm = param_norm.mul_scalar(clipping);
+
+        let clip_coef = max_norm.div(grad_norm.add_scalar(1e-6)).clamp_max(1.0);
+        grad.mul(clip_coef)
+    }
+
+    /// The L2 norm of each unit of the tensor, keeping the reduced dimensions for broadcasting.
+    fn unit_wise_norm<B: Backend, const D: usize>(tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let unit_dim = match D {
+            1 => None,
+            2 => Some(1),
+            _ => Some(0),
+        };
+
+        let mut squared = tensor.powi_scalar(2);
+        for dim in (0..D).filter(|dim| Some(*dim) != unit_dim) {
+            squared = squared.sum_dim(dim);
+        }
+        squared.sqrt()
+    }
+
     fn l2_norm<B: Backend, const D: usize>(tensor: Tensor<B, D>) -> Tensor<B, 1> {
         let squared = tensor.powi_scalar(2);
         let sum = squared.sum();
@@ -87,11 +198,44 @@ impl GradientClipping {
     }
 }
 
+struct GradientsClipper<'a, B: AutodiffBackend> {
+    clipping: &'a GradientClipping,
+    grads: &'a mut GradientsParams,
+    // The factor applied to every gradient with global norm clipping.
+    scale: Option<Tensor<B::InnerBackend, 1>>,
+}
+
+impl<B: AutodiffBackend> ModuleVisitor<B> for GradientsClipper<'_, B> {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) else {
+            return;
+        };
+
+        let grad = match (self.clipping, &self.scale) {
+            (GradientClipping::GlobalNorm(_), Some(scale)) => {
+                let scale = scale.clone().to_device(&grad.device());
+                grad.mul(scale.unsqueeze())
+            }
+            (GradientClipping::Adaptive(clipping), _) => {
+                let param = tensor.clone().inner().to_device(&grad.device());
+                self.clipping.clip_adaptive(param, grad, *clipping)
+            }
+            _ => self.clipping.clip_gradient(grad),
+        };
+
+        self.grads.register::<B::InnerBackend, D>(id, grad);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::TestBackend;
-    use crate::tensor::Tensor;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::tensor::{Tensor, TensorData};
+    use crate::{TestAutodiffBackend, TestBackend};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
 
     #[test]
     fn test_clip_by_value() {
@@ -141,4 +285,86 @@ mod tests {
             .into_data()
             .assert_eq(&gradient.into_data(), true);
     }
+
+    fn linear_with_weight(weight: [[f32; 2]; 2]) -> Linear<TestAutodiffBackend> {
+        let device = Default::default();
+        let mut linear = LinearConfig::new(2, 2).init::<TestAutodiffBackend>(&device);
+        linear.weight = linear
+            .weight
+            .map(|_| Tensor::from_floats(weight, &device).require_grad());
+        linear
+    }
+
+    #[test]
+    fn test_clip_by_global_norm() {
+        let device = Default::default();
+        let linear = linear_with_weight([[1.0, 0.0], [0.0, 1.0]]);
+        let mut grads = GradientsParams::new();
+        grads.register::<TestBackend, 2>(
+            linear.weight.id,
+            Tensor::from_floats([[3.0, 0.0], [0.0, 0.0]], &device),
+        );
+        grads.register::<TestBackend, 1>(
+            linear.bias.as_ref().unwrap().id,
+            Tensor::from_floats([4.0, 0.0], &device),
+        );
+
+        assert!((grads.l2_norm::<TestAutodiffBackend, _>(&linear) - 5.0).abs() < 1e-5);
+
+        let mut grads = GradientClipping::GlobalNorm(1.0).clip_gradients(&linear, grads);
+
+        grads
+            .remove::<TestBackend, 2>(linear.weight.id)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &TensorData::from([[0.6, 0.0], [0.0, 0.0]]),
+                Tolerance::default(),
+            );
+        grads
+            .remove::<TestBackend, 1>(linear.bias.as_ref().unwrap().id)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([0.8, 0.0]), Tolerance::default());
+    }
+
+    #[test]
+    fn test_clip_by_global_norm_no_clipping() {
+        let device = Default::default();
+        let linear = linear_with_weight([[1.0, 0.0], [0.0, 1.0]]);
+        let gradient = Tensor::<TestBackend, 2>::from_floats([[0.3, 0.4], [0.0, 0.0]], &device);
+        let mut grads = GradientsParams::new();
+        grads.register(linear.weight.id, gradient.clone());
+
+        let mut grads = GradientClipping::GlobalNorm(1.0).clip_gradients(&linear, grads);
+
+        grads
+            .remove::<TestBackend, 2>(linear.weight.id)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FT>(&gradient.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_clip_adaptive() {
+        let device = Default::default();
+        let linear = linear_with_weight([[1.0, 0.0], [0.0, 2.0]]);
+        let mut grads = GradientsParams::new();
+        grads.register::<TestBackend, 2>(
+            linear.weight.id,
+            Tensor::from_floats([[3.0, 0.0], [0.0, 0.1]], &device),
+        );
+
+        let mut grads = GradientClipping::Adaptive(0.5).clip_gradients(&linear, grads);
+
+        // The first column is clipped to half the weight norm, the second one is kept.
+        grads
+            .remove::<TestBackend, 2>(linear.weight.id)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &TensorData::from([[0.5, 0.0], [0.0, 0.1]]),
+                Tolerance::default(),
+            );
+    }
 }
@@ -28,6 +28,9 @@ pub mod lr_scheduler;
 /// Gradient clipping module.
 pub mod grad_clipping;
 
+/// Automatic mixed precision module.
+pub mod amp;
+
 /// Module for the neural network module.
 pub mod module;
 
@@ -2,7 +2,8 @@ pub(super) use alloc::string::String;
 
 use burn_tensor::backend::Backend;
 
-use crate::{LearningRate, record::Record};
+use crate as burn;
+use crate::{LearningRate, config::Config, record::Record};
 
 /// Learning rate scheduler defines how the learning rate will evolve during training.
 pub trait LrScheduler: Clone + Send + Sync {
@@ -13,6 +14,19 @@ pub trait LrScheduler: Clone + Send + Sync {
     /// learning rate.
     fn step(&mut self) -> LearningRate;
 
+    /// The metric the scheduler adapts the learning rate to, if any.
+    ///
+    /// When a metric is returned, the learner aggregates it at the end of each epoch and provides
+    /// its value to [observe](LrScheduler::observe).
+    fn observed_metric(&self) -> Option<SchedulerMetric> {
+        None
+    }
+
+    /// Observe the value of the [observed metric](LrScheduler::observed_metric) aggregated over
+    /// the last epoch, potentially updating the learning rate returned by the next steps.
+    #[allow(unused_variables)]
+    fn observe(&mut self, value: f64) {}
+
     /// Get the current state of the scheduler as a [record](Record).
     fn to_record<B: Backend>(&self) -> Self::Record<B>;
 
@@ -20,6 +34,25 @@ pub trait LrScheduler: Clone + Send + Sync {
     fn load_record<B: Backend>(self, record: Self::Record<B>) -> Self;
 }
 
+/// A metric aggregated over an epoch, observed by a [learning rate scheduler](LrScheduler).
+#[derive(Config, Debug)]
+pub struct SchedulerMetric {
+    /// The name of the metric, e.g. `Loss`.
+    pub name: String,
+    /// The split the metric is collected on.
+    #[config(default = "MetricSplit::Valid")]
+    pub split: MetricSplit,
+}
+
+/// The split on which a [scheduler metric](SchedulerMetric) is collected.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum MetricSplit {
+    /// The training split.
+    Train,
+    /// The validation split.
+    Valid,
+}
+
 #[cfg(test)]
 pub(super) mod test_utils {
     use super::*;
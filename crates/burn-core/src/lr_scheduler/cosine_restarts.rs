@@ -0,0 +1,181 @@
+use super::{LrScheduler, String};
+use crate as burn;
+use crate::{LearningRate, config::Config};
+use burn_tensor::backend::Backend;
+
+/// The configuration for creating a [cosine annealing learning rate scheduler with warm
+/// restarts](CosineAnnealingWarmRestarts).
+///
+/// This scheduler returns the learning rate `initial_lr` at the first step, then decreases it by
+/// following a cosine function for `num_iters` iterations, after which the learning rate is reset
+/// to `initial_lr`. The length of each cycle is the length of the previous one multiplied by
+/// `cycle_mult`.
+#[derive(Config, Debug)]
+pub struct CosineAnnealingWarmRestartsConfig {
+    // The initial learning rate.
+    initial_lr: LearningRate,
+    // The number of iterations of the first cycle.
+    num_iters: usize,
+    /// The factor by which the number of iterations is multiplied after each restart. Default: 1.
+    #[config(default = 1)]
+    cycle_mult: usize,
+    /// The final learning rate of each cycle. Default: 0.
+    #[config(default = 0.0)]
+    min_lr: LearningRate,
+}
+
+impl CosineAnnealingWarmRestartsConfig {
+    /// Initializes a [cosine annealing learning rate scheduler with warm
+    /// restarts](CosineAnnealingWarmRestarts).
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if any of the following conditions is true:
+    ///
+    /// * `initial_lr` is out of range (0.0, 1.0]
+    /// * `min_lr` is out of range [0.0, `initial_lr`]
+    /// * `num_iters` is 0
+    /// * `cycle_mult` is 0
+    pub fn init(&self) -> Result<CosineAnnealingWarmRestarts, String> {
+        if self.initial_lr <= 0. || self.initial_lr > 1. {
+            return Err("Initial learning rate must be greater than 0 and at most 1".into());
+        }
+        if self.min_lr < 0.0 || self.min_lr > self.initial_lr {
+            return Err(
+                "Minimum learning rate must be at least 0 and at most equal to the initial \
+                 learning rate"
+                    .into(),
+            );
+        }
+        if self.num_iters == 0 {
+            return Err("Number of iterations must be at least 1".into());
+        }
+        if self.cycle_mult == 0 {
+            return Err("Cycle multiplier must be at least 1".into());
+        }
+
+        Ok(CosineAnnealingWarmRestarts {
+            min_lr: self.min_lr,
+            max_lr: self.initial_lr,
+            cycle_mult: self.cycle_mult,
+            cycle_iter: 0,
+            cycle_len: self.num_iters,
+        })
+    }
+}
+
+/// A cosine annealing learning rate scheduler with warm restarts and growing cycles.
+///
+/// This scheduler is described in [SGDR: Stochastic Gradient Descent with Warm
+/// Restarts](https://arxiv.org/abs/1608.03983). Unlike the [cosine annealing
+/// scheduler](super::cosine::CosineAnnealingLrScheduler), the minimum learning rate is never
+/// reached, the next cycle starts right after the last iteration of the current one. See
+/// [CosineAnnealingWarmRestartsConfig] for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct CosineAnnealingWarmRestarts {
+    min_lr: LearningRate,
+    max_lr: LearningRate,
+    cycle_mult: usize,
+    // The index of the next iteration in the current cycle.
+    cycle_iter: usize,
+    // The number of iterations of the current cycle.
+    cycle_len: usize,
+}
+
+impl LrScheduler for CosineAnnealingWarmRestarts {
+    type Record<B: Backend> = (usize, usize);
+
+    fn step(&mut self) -> LearningRate {
+        let progress = self.cycle_iter as f64 / self.cycle_len as f64;
+        let lr = self.min_lr
+            + 0.5 * (self.max_lr - self.min_lr) * (1.0 + (progress * core::f64::consts::PI).cos());
+
+        self.cycle_iter += 1;
+        if self.cycle_iter >= self.cycle_len {
+            self.cycle_iter = 0;
+            self.cycle_len = self.cycle_len.saturating_mul(self.cycle_mult);
+        }
+
+        lr
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        (self.cycle_iter, self.cycle_len)
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        (self.cycle_iter, self.cycle_len) = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    #[test]
+    fn config_num_iters_too_low() {
+        let r = CosineAnnealingWarmRestartsConfig::new(0.5, 0).init();
+        assert!(r.is_err(), "Should return an error");
+        assert_eq!(
+            r.unwrap_err(),
+            "Number of iterations must be at least 1",
+            "Error messages should match",
+        );
+    }
+
+    #[test]
+    fn config_cycle_mult_too_low() {
+        let r = CosineAnnealingWarmRestartsConfig::new(0.5, 2)
+            .with_cycle_mult(0)
+            .init();
+        assert!(r.is_err(), "Should return an error");
+    }
+
+    #[test]
+    fn test_lr_change() {
+        const INITIAL_LR: LearningRate = 0.5;
+        const MIN_LR: LearningRate = 0.1;
+
+        let scheduler = CosineAnnealingWarmRestartsConfig::new(INITIAL_LR, 2)
+            .with_min_lr(MIN_LR)
+            .init()
+            .unwrap();
+        let expected_lrs = [
+            INITIAL_LR,                  // cos(0)
+            (INITIAL_LR + MIN_LR) * 0.5, // cos(PI/2)
+            INITIAL_LR,                  // restart
+            (INITIAL_LR + MIN_LR) * 0.5, // cos(PI/2)
+        ];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_with_growing_cycles() {
+        let scheduler = CosineAnnealingWarmRestartsConfig::new(1.0, 2)
+            .with_cycle_mult(2)
+            .init()
+            .unwrap();
+        let quarter = 0.5 * (1.0 + core::f64::consts::FRAC_1_SQRT_2);
+        let expected_lrs = [
+            1.0,           // cos(0)
+            0.5,           // cos(PI/2)
+            1.0,           // restart, with a cycle of 4 iterations
+            quarter,       // cos(PI/4)
+            0.5,           // cos(PI/2)
+            1.0 - quarter, // cos(3PI/4)
+            1.0,           // restart
+        ];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let scheduler = CosineAnnealingWarmRestartsConfig::new(1.0, 3)
+            .with_cycle_mult(2)
+            .init()
+            .unwrap();
+        test_utils::check_save_load(scheduler, 5);
+    }
+}
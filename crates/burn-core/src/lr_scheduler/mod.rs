@@ -19,6 +19,18 @@ pub mod cosine;
 /// Step learning rate scheduler
 pub mod step;
 
+/// Reduce on plateau learning rate scheduler
+pub mod plateau;
+
+/// One cycle learning rate scheduler
+pub mod one_cycle;
+
+/// Cosine learning rate scheduler with warm restarts
+pub mod cosine_restarts;
+
+/// Polynomial learning rate scheduler
+pub mod polynomial;
+
 mod base;
 
 pub use base::*;
@@ -0,0 +1,254 @@
+use super::{LrScheduler, String};
+use crate as burn;
+use crate::{LearningRate, config::Config};
+use burn_tensor::backend::Backend;
+
+/// The configuration for creating a [one cycle learning rate scheduler](OneCycleLr).
+///
+/// This scheduler starts at `max_lr / div_factor`, increases the learning rate up to `max_lr`
+/// during the first `pct_start * total_steps` iterations, then anneals it down to
+/// `max_lr / (div_factor * final_div_factor)` at iteration `total_steps`. The learning rate stays
+/// at its final value when the scheduler is stepped beyond `total_steps` iterations.
+///
+/// With `three_phase`, the learning rate first goes back to its initial value symmetrically to
+/// the warmup, and only then anneals down to the final value.
+#[derive(Config, Debug)]
+pub struct OneCycleLrConfig {
+    // The peak learning rate.
+    max_lr: LearningRate,
+    // The total number of iterations of the cycle.
+    total_steps: usize,
+    /// The fraction of the cycle spent increasing the learning rate. Default: 0.3.
+    #[config(default = 0.3)]
+    pct_start: f64,
+    /// The function used to move from one learning rate to the next. Default: Cos.
+    #[config(default = "AnnealStrategy::Cos")]
+    anneal_strategy: AnnealStrategy,
+    /// The initial learning rate is `max_lr / div_factor`. Default: 25.
+    #[config(default = 25.0)]
+    div_factor: f64,
+    /// The final learning rate is the initial learning rate divided by `final_div_factor`.
+    /// Default: 1e4.
+    #[config(default = 1e4)]
+    final_div_factor: f64,
+    /// Whether to use a third phase going back to the initial learning rate before annealing.
+    /// Default: false.
+    #[config(default = false)]
+    three_phase: bool,
+}
+
+/// Defines how a [one cycle scheduler](OneCycleLr) moves between two learning rates.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum AnnealStrategy {
+    /// Cosine annealing.
+    Cos,
+    /// Linear annealing.
+    Linear,
+}
+
+impl OneCycleLrConfig {
+    /// Initializes a [one cycle learning rate scheduler](OneCycleLr).
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if any of the following conditions is true:
+    ///
+    /// * `max_lr` is out of range (0.0, 1.0]
+    /// * `total_steps` is 0
+    /// * `pct_start` is out of range (0.0, 1.0), or (0.0, 0.5) with `three_phase`
+    /// * `div_factor` or `final_div_factor` is not greater than 0
+    pub fn init(&self) -> Result<OneCycleLr, String> {
+        if self.max_lr <= 0. || self.max_lr > 1. {
+            return Err("Maximum learning rate must be greater than 0 and at most 1".into());
+        }
+        if self.total_steps == 0 {
+            return Err("Total number of steps must be at least 1".into());
+        }
+        let max_pct = if self.three_phase { 0.5 } else { 1.0 };
+        if self.pct_start <= 0. || self.pct_start >= max_pct {
+            return Err(format!(
+                "Percentage of the cycle spent increasing the learning rate must be greater than \
+                 0 and less than {max_pct}"
+            ));
+        }
+        if self.div_factor <= 0. || self.final_div_factor <= 0. {
+            return Err("Division factors must be greater than 0".into());
+        }
+
+        let initial_lr = self.max_lr / self.div_factor;
+        let final_lr = initial_lr / self.final_div_factor;
+        let warmup_end = self.pct_start * self.total_steps as f64 - 1.0;
+        let last_step = (self.total_steps - 1) as f64;
+
+        let phases = if self.three_phase {
+            vec![
+                Phase::new(warmup_end, initial_lr, self.max_lr),
+                Phase::new(2.0 * warmup_end, self.max_lr, initial_lr),
+                Phase::new(last_step, initial_lr, final_lr),
+            ]
+        } else {
+            vec![
+                Phase::new(warmup_end, initial_lr, self.max_lr),
+                Phase::new(last_step, self.max_lr, final_lr),
+            ]
+        };
+
+        Ok(OneCycleLr {
+            phases,
+            anneal_strategy: self.anneal_strategy,
+            total_steps: self.total_steps,
+            current_step: 0,
+        })
+    }
+}
+
+/// A one cycle learning rate scheduler.
+///
+/// This scheduler is described in [Super-Convergence: Very Fast Training of Neural Networks Using
+/// Large Learning Rates](https://arxiv.org/abs/1708.07120). See [OneCycleLrConfig] for more
+/// information.
+#[derive(Clone, Debug)]
+pub struct OneCycleLr {
+    phases: Vec<Phase>,
+    anneal_strategy: AnnealStrategy,
+    total_steps: usize,
+    // The number of steps taken so far.
+    current_step: usize,
+}
+
+#[derive(Clone, Debug, new)]
+struct Phase {
+    // The iteration at which the phase reaches its end learning rate.
+    end_step: f64,
+    start_lr: LearningRate,
+    end_lr: LearningRate,
+}
+
+impl AnnealStrategy {
+    fn anneal(&self, start: LearningRate, end: LearningRate, pct: f64) -> LearningRate {
+        match self {
+            AnnealStrategy::Cos => {
+                end + (start - end) / 2.0 * ((core::f64::consts::PI * pct).cos() + 1.0)
+            }
+            AnnealStrategy::Linear => start + (end - start) * pct,
+        }
+    }
+}
+
+impl LrScheduler for OneCycleLr {
+    type Record<B: Backend> = usize;
+
+    fn step(&mut self) -> LearningRate {
+        let step = usize::min(self.current_step, self.total_steps - 1) as f64;
+        self.current_step = self.current_step.saturating_add(1);
+
+        let mut start_step = 0.0;
+        for (i, phase) in self.phases.iter().enumerate() {
+            if step <= phase.end_step || i == self.phases.len() - 1 {
+                let pct = if phase.end_step > start_step {
+                    ((step - start_step) / (phase.end_step - start_step)).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+                return self
+                    .anneal_strategy
+                    .anneal(phase.start_lr, phase.end_lr, pct);
+            }
+            start_step = phase.end_step;
+        }
+
+        unreachable!("The last phase always returns a learning rate")
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        self.current_step
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        self.current_step = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    #[test]
+    fn config_max_lr_too_high() {
+        let r = OneCycleLrConfig::new(1.5, 10).init();
+        assert!(r.is_err(), "Should return an error");
+        assert_eq!(
+            r.unwrap_err(),
+            "Maximum learning rate must be greater than 0 and at most 1",
+            "Error messages should match",
+        );
+    }
+
+    #[test]
+    fn config_total_steps_zero() {
+        let r = OneCycleLrConfig::new(0.5, 0).init();
+        assert!(r.is_err(), "Should return an error");
+    }
+
+    #[test]
+    fn config_pct_start_too_high_with_three_phase() {
+        let r = OneCycleLrConfig::new(0.5, 10)
+            .with_pct_start(0.6)
+            .with_three_phase(true)
+            .init();
+        assert!(r.is_err(), "Should return an error");
+        assert_eq!(
+            r.unwrap_err(),
+            "Percentage of the cycle spent increasing the learning rate must be greater than 0 \
+             and less than 0.5",
+            "Error messages should match",
+        );
+    }
+
+    #[test]
+    fn test_lr_change() {
+        let scheduler = OneCycleLrConfig::new(0.5, 10).init().unwrap();
+        let expected_lrs = [
+            0.02, // max_lr / div_factor
+            0.26, // cos(PI/2)
+            0.5,  // max_lr
+            0.5 - (0.5 - 2e-6) * (1.0 - (core::f64::consts::PI / 7.0).cos()) / 2.0,
+        ];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_stays_at_final_value() {
+        let mut scheduler = OneCycleLrConfig::new(0.5, 10)
+            .with_anneal_strategy(AnnealStrategy::Linear)
+            .init()
+            .unwrap();
+        let lrs: Vec<_> = (0..12).map(|_| scheduler.step()).collect();
+
+        assert!((lrs[1] - 0.26).abs() < 1e-10);
+        assert!((lrs[9] - 2e-6).abs() < 1e-10);
+        assert!((lrs[11] - 2e-6).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_three_phase() {
+        let mut scheduler = OneCycleLrConfig::new(0.5, 10)
+            .with_three_phase(true)
+            .init()
+            .unwrap();
+        let lrs: Vec<_> = (0..10).map(|_| scheduler.step()).collect();
+
+        assert!((lrs[2] - 0.5).abs() < 1e-10);
+        assert!((lrs[3] - 0.26).abs() < 1e-10);
+        assert!((lrs[4] - 0.02).abs() < 1e-10);
+        assert!((lrs[9] - 2e-6).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let scheduler = OneCycleLrConfig::new(0.5, 20).init().unwrap();
+        test_utils::check_save_load(scheduler, 7);
+    }
+}
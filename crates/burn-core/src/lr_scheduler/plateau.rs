@@ -0,0 +1,303 @@
+use super::{LrScheduler, SchedulerMetric, String};
+use crate as burn;
+use crate::{LearningRate, config::Config};
+use burn_tensor::backend::Backend;
+
+/// The configuration for creating a [reduce on plateau learning rate
+/// scheduler](ReduceLrOnPlateau).
+///
+/// This scheduler returns the learning rate `initial_lr` until the observed metric stops
+/// improving for more than `patience` epochs, then multiplies the learning rate by `factor`.
+/// After each reduction, the metric is ignored for `cooldown` epochs. The learning rate never
+/// goes below `min_lr`.
+#[derive(Config, Debug)]
+pub struct ReduceLrOnPlateauConfig {
+    // The initial learning rate.
+    initial_lr: LearningRate,
+    // The metric to monitor.
+    metric: SchedulerMetric,
+    /// Whether the metric improves when it decreases or when it increases. Default: Min.
+    #[config(default = "PlateauMode::Min")]
+    mode: PlateauMode,
+    /// The factor by which the learning rate is multiplied with each reduction. Default: 0.1.
+    #[config(default = 0.1)]
+    factor: f64,
+    /// The number of epochs without improvement tolerated before reducing the learning rate.
+    /// Default: 10.
+    #[config(default = 10)]
+    patience: usize,
+    /// The minimal change of the metric considered as an improvement. Default: 1e-4.
+    #[config(default = 1e-4)]
+    threshold: f64,
+    /// Whether the threshold is relative to the best value or absolute. Default: Relative.
+    #[config(default = "ThresholdMode::Relative")]
+    threshold_mode: ThresholdMode,
+    /// The number of epochs to wait after a reduction before resuming normal operation.
+    /// Default: 0.
+    #[config(default = 0)]
+    cooldown: usize,
+    /// The lower bound of the learning rate. Default: 0.
+    #[config(default = 0.0)]
+    min_lr: LearningRate,
+}
+
+/// Defines whether the metric observed by a [reduce on plateau scheduler](ReduceLrOnPlateau)
+/// should be minimized or maximized.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum PlateauMode {
+    /// Lower is better.
+    Min,
+    /// Higher is better.
+    Max,
+}
+
+/// Defines how the threshold of a [reduce on plateau scheduler](ReduceLrOnPlateau) is compared
+/// to the best value.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum ThresholdMode {
+    /// The metric must improve by `best * threshold`.
+    Relative,
+    /// The metric must improve by `threshold`.
+    Absolute,
+}
+
+impl ReduceLrOnPlateauConfig {
+    /// Initializes a [reduce on plateau learning rate scheduler](ReduceLrOnPlateau).
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if any of the following conditions is true:
+    ///
+    /// * `initial_lr` is out of range (0.0, 1.0]
+    /// * `factor` is out of range (0.0, 1.0)
+    /// * `min_lr` is out of range [0.0, `initial_lr`]
+    /// * `threshold` is negative
+    pub fn init(&self) -> Result<ReduceLrOnPlateau, String> {
+        if self.initial_lr <= 0. || self.initial_lr > 1. {
+            return Err("Initial learning rate must be greater than 0 and at most 1".into());
+        }
+        if self.factor <= 0. || self.factor >= 1. {
+            return Err("Factor must be greater than 0 and less than 1".into());
+        }
+        if self.min_lr < 0.0 || self.min_lr > self.initial_lr {
+            return Err(
+                "Minimum learning rate must be at least 0 and at most equal to the initial \
+                 learning rate"
+                    .into(),
+            );
+        }
+        if self.threshold < 0.0 {
+            return Err("Threshold must be at least 0".into());
+        }
+
+        Ok(ReduceLrOnPlateau {
+            metric: self.metric.clone(),
+            mode: self.mode,
+            factor: self.factor,
+            patience: self.patience,
+            threshold: self.threshold,
+            threshold_mode: self.threshold_mode,
+            cooldown: self.cooldown,
+            min_lr: self.min_lr,
+            current_lr: self.initial_lr,
+            best: None,
+            num_bad_epochs: 0,
+            cooldown_counter: 0,
+        })
+    }
+}
+
+/// A learning rate scheduler reducing the learning rate when a metric has stopped improving.
+///
+/// The metric is provided at the end of each epoch through [observe](LrScheduler::observe). See
+/// [ReduceLrOnPlateauConfig] for more information.
+#[derive(Clone, Debug)]
+pub struct ReduceLrOnPlateau {
+    metric: SchedulerMetric,
+    mode: PlateauMode,
+    factor: f64,
+    patience: usize,
+    threshold: f64,
+    threshold_mode: ThresholdMode,
+    cooldown: usize,
+    min_lr: LearningRate,
+    current_lr: LearningRate,
+    // The best value observed so far, none before the first observation.
+    best: Option<f64>,
+    num_bad_epochs: usize,
+    cooldown_counter: usize,
+}
+
+impl ReduceLrOnPlateau {
+    fn is_better(&self, value: f64, best: f64) -> bool {
+        let margin = match self.threshold_mode {
+            ThresholdMode::Relative => best.abs() * self.threshold,
+            ThresholdMode::Absolute => self.threshold,
+        };
+
+        match self.mode {
+            PlateauMode::Min => value < best - margin,
+            PlateauMode::Max => value > best + margin,
+        }
+    }
+}
+
+impl LrScheduler for ReduceLrOnPlateau {
+    type Record<B: Backend> = (LearningRate, Option<f64>, usize, usize);
+
+    fn step(&mut self) -> LearningRate {
+        self.current_lr
+    }
+
+    fn observed_metric(&self) -> Option<SchedulerMetric> {
+        Some(self.metric.clone())
+    }
+
+    fn observe(&mut self, value: f64) {
+        match self.best {
+            Some(best) if !self.is_better(value, best) => self.num_bad_epochs += 1,
+            _ => {
+                self.best = Some(value);
+                self.num_bad_epochs = 0;
+            }
+        }
+
+        if self.cooldown_counter > 0 {
+            self.cooldown_counter -= 1;
+            self.num_bad_epochs = 0;
+        }
+
+        if self.num_bad_epochs > self.patience {
+            let lr = f64::max(self.current_lr * self.factor, self.min_lr);
+            if lr < self.current_lr {
+                log::info!(
+                    "Reducing the learning rate from {} to {lr}, no improvement of {} since {} \
+                     epochs",
+                    self.current_lr,
+                    self.metric.name,
+                    self.num_bad_epochs
+                );
+                self.current_lr = lr;
+            }
+            self.cooldown_counter = self.cooldown;
+            self.num_bad_epochs = 0;
+        }
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        (
+            self.current_lr,
+            self.best,
+            self.num_bad_epochs,
+            self.cooldown_counter,
+        )
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        (
+            self.current_lr,
+            self.best,
+            self.num_bad_epochs,
+            self.cooldown_counter,
+        ) = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn config() -> ReduceLrOnPlateauConfig {
+        ReduceLrOnPlateauConfig::new(0.1, SchedulerMetric::new("Loss".into()))
+    }
+
+    fn observe_all(scheduler: &mut ReduceLrOnPlateau, values: &[f64]) -> Vec<LearningRate> {
+        values
+            .iter()
+            .map(|value| {
+                scheduler.observe(*value);
+                scheduler.step()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn config_factor_out_of_range() {
+        let r = config().with_factor(1.0).init();
+        assert!(r.is_err(), "Should return an error");
+        assert_eq!(
+            r.unwrap_err(),
+            "Factor must be greater than 0 and less than 1",
+            "Error messages should match",
+        );
+    }
+
+    #[test]
+    fn config_min_lr_too_high() {
+        let r = config().with_min_lr(0.2).init();
+        assert!(r.is_err(), "Should return an error");
+    }
+
+    #[test]
+    fn test_lr_unchanged_without_observation() {
+        let mut scheduler = config().init().unwrap();
+        assert_eq!(scheduler.observed_metric().unwrap().name, "Loss");
+        assert_eq!(scheduler.step(), 0.1);
+        assert_eq!(scheduler.step(), 0.1);
+    }
+
+    #[test]
+    fn test_lr_reduced_after_patience() {
+        let mut scheduler = config().with_patience(2).init().unwrap();
+
+        let lrs = observe_all(&mut scheduler, &[1.0, 0.9, 0.95, 0.92, 0.91, 0.93, 0.94]);
+
+        let expected = [0.1, 0.1, 0.1, 0.1, 0.01, 0.01, 0.01];
+        for (lr, expected) in lrs.into_iter().zip(expected) {
+            assert!((lr - expected).abs() < 1e-12, "{lr} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_lr_max_mode_with_absolute_threshold_and_cooldown() {
+        let mut scheduler = config()
+            .with_mode(PlateauMode::Max)
+            .with_threshold_mode(ThresholdMode::Absolute)
+            .with_threshold(0.1)
+            .with_patience(0)
+            .with_cooldown(1)
+            .with_min_lr(0.005)
+            .init()
+            .unwrap();
+
+        // 0.55 doesn't improve on 0.5 by more than the absolute threshold.
+        let lrs = observe_all(&mut scheduler, &[0.5, 0.55, 0.56, 0.57, 0.58]);
+
+        let expected = [0.1, 0.01, 0.01, 0.005, 0.005];
+        for (lr, expected) in lrs.into_iter().zip(expected) {
+            assert!((lr - expected).abs() < 1e-12, "{lr} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut scheduler = config().with_patience(1).init().unwrap();
+        observe_all(&mut scheduler, &[1.0, 1.0]);
+
+        let record = scheduler.to_record::<TestBackend>();
+        let mut loaded = config()
+            .with_patience(1)
+            .init()
+            .unwrap()
+            .load_record::<TestBackend>(record);
+
+        // The second epoch without improvement reduces the learning rate of both schedulers.
+        assert_eq!(
+            observe_all(&mut scheduler, &[1.0]),
+            observe_all(&mut loaded, &[1.0])
+        );
+        assert!((loaded.step() - 0.01).abs() < 1e-12);
+    }
+}
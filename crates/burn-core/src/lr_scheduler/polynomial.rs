@@ -0,0 +1,150 @@
+use super::{LrScheduler, String};
+use crate as burn;
+use crate::{LearningRate, config::Config};
+use burn_tensor::backend::Backend;
+
+/// The configuration for creating a [polynomial decay learning rate scheduler](PolynomialLr).
+///
+/// This scheduler returns the learning rate `initial_lr` at the first step, then decays it to
+/// `final_lr` over `num_iters` iterations. At any iteration `i` (which starts from 0), the
+/// learning rate is given by
+/// `(initial_lr - final_lr) * (1 - min(i, num_iters) / num_iters)^power + final_lr`.
+#[derive(Config, Debug)]
+pub struct PolynomialLrConfig {
+    // The initial learning rate.
+    initial_lr: LearningRate,
+    // The number of iterations before the final learning rate is reached.
+    num_iters: usize,
+    /// The power of the polynomial. Default: 1, which is a linear decay.
+    #[config(default = 1.0)]
+    power: f64,
+    /// The final learning rate. Default: 0.
+    #[config(default = 0.0)]
+    final_lr: LearningRate,
+}
+
+impl PolynomialLrConfig {
+    /// Initializes a [polynomial decay learning rate scheduler](PolynomialLr).
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned if any of the following conditions is true:
+    ///
+    /// * `initial_lr` is out of range (0.0, 1.0]
+    /// * `final_lr` is out of range [0.0, `initial_lr`]
+    /// * `num_iters` is 0
+    /// * `power` is not greater than 0
+    pub fn init(&self) -> Result<PolynomialLr, String> {
+        if self.initial_lr <= 0. || self.initial_lr > 1. {
+            return Err("Initial learning rate must be greater than 0 and at most 1".into());
+        }
+        if self.final_lr < 0.0 || self.final_lr > self.initial_lr {
+            return Err(
+                "Final learning rate must be at least 0 and at most equal to the initial \
+                 learning rate"
+                    .into(),
+            );
+        }
+        if self.num_iters == 0 {
+            return Err("Number of iterations must be at least 1".into());
+        }
+        if self.power <= 0. {
+            return Err("Power must be greater than 0".into());
+        }
+
+        Ok(PolynomialLr {
+            initial_lr: self.initial_lr,
+            final_lr: self.final_lr,
+            num_iters: self.num_iters,
+            power: self.power,
+            current_iter: 0,
+        })
+    }
+}
+
+/// A polynomial decay learning rate scheduler.
+///
+/// See [PolynomialLrConfig] for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct PolynomialLr {
+    initial_lr: LearningRate,
+    final_lr: LearningRate,
+    num_iters: usize,
+    power: f64,
+    // The index of the next iteration.
+    current_iter: usize,
+}
+
+impl LrScheduler for PolynomialLr {
+    type Record<B: Backend> = usize;
+
+    fn step(&mut self) -> LearningRate {
+        let iter = usize::min(self.current_iter, self.num_iters);
+        self.current_iter = self.current_iter.saturating_add(1);
+
+        let remaining = 1.0 - iter as f64 / self.num_iters as f64;
+        (self.initial_lr - self.final_lr) * remaining.powf(self.power) + self.final_lr
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        self.current_iter
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        self.current_iter = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    #[test]
+    fn config_final_lr_too_high() {
+        let r = PolynomialLrConfig::new(0.5, 10).with_final_lr(0.6).init();
+        assert!(r.is_err(), "Should return an error");
+        assert_eq!(
+            r.unwrap_err(),
+            "Final learning rate must be at least 0 and at most equal to the initial learning \
+             rate",
+            "Error messages should match",
+        );
+    }
+
+    #[test]
+    fn config_power_invalid() {
+        let r = PolynomialLrConfig::new(0.5, 10).with_power(0.0).init();
+        assert!(r.is_err(), "Should return an error");
+    }
+
+    #[test]
+    fn test_lr_change_linear() {
+        let scheduler = PolynomialLrConfig::new(0.5, 4)
+            .with_final_lr(0.1)
+            .init()
+            .unwrap();
+        let expected_lrs = [0.5, 0.4, 0.3, 0.2, 0.1, 0.1];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_quadratic() {
+        let scheduler = PolynomialLrConfig::new(0.8, 4)
+            .with_power(2.0)
+            .init()
+            .unwrap();
+        let expected_lrs = [0.8, 0.45, 0.2, 0.05, 0.0];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let scheduler = PolynomialLrConfig::new(1.0, 10)
+            .with_power(2.0)
+            .init()
+            .unwrap();
+        test_utils::check_save_load(scheduler, 6);
+    }
+}
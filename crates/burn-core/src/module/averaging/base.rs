@@ -0,0 +1,56 @@
+use core::marker::PhantomData;
+
+use burn_tensor::container::TensorContainer;
+
+use crate::module::{Module, ModuleMapper, ModuleVisitor, ParamId};
+use crate::tensor::{Tensor, backend::Backend};
+
+/// Interpolates the float tensors of `module` towards the ones of `target` with the same
+/// parameter ids, `module + weight * (target - module)`.
+///
+/// The tensors of `target` are detached and moved to the devices of `module`.
+pub(crate) fn interpolate<B: Backend, M: Module<B>>(module: M, target: &M, weight: f64) -> M {
+    let mut collector = FloatCollector::default();
+    target.visit(&mut collector);
+
+    let mut mapper = InterpolateMapper::<B> {
+        tensors: collector.tensors,
+        weight,
+        _backend: PhantomData,
+    };
+    module.map(&mut mapper)
+}
+
+#[derive(Default)]
+struct FloatCollector {
+    tensors: TensorContainer<ParamId>,
+}
+
+impl<B: Backend> ModuleVisitor<B> for FloatCollector {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        self.tensors
+            .register::<B>(id, tensor.clone().detach().into_primitive());
+    }
+}
+
+struct InterpolateMapper<B: Backend> {
+    tensors: TensorContainer<ParamId>,
+    weight: f64,
+    _backend: PhantomData<B>,
+}
+
+impl<B: Backend> ModuleMapper<B> for InterpolateMapper<B> {
+    fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let Some(target) = self.tensors.remove::<B>(&id) else {
+            return tensor;
+        };
+        let target = Tensor::<B, D>::from_primitive(target).to_device(&tensor.device());
+
+        if self.weight >= 1.0 {
+            return target;
+        }
+
+        let delta = target.sub(tensor.clone()).mul_scalar(self.weight);
+        tensor.add(delta)
+    }
+}
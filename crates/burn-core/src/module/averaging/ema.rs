@@ -0,0 +1,240 @@
+use core::marker::PhantomData;
+
+use super::base::interpolate;
+use crate as burn;
+use crate::config::Config;
+use crate::module::Module;
+use crate::record::Record;
+use crate::tensor::backend::Backend;
+
+/// Configuration to create an [exponential moving average](EmaModule) of a module.
+#[derive(Config, Debug)]
+pub struct EmaConfig {
+    /// The weight of the current average at each update. Default: 0.999
+    #[config(default = 0.999)]
+    pub decay: f64,
+    /// Whether the decay of the update `t` is warmed up as `min(decay, (1 + t) / (10 + t))`, so
+    /// the average quickly moves away from the initial weights. Default: false
+    #[config(default = false)]
+    pub warmup: bool,
+    /// The number of calls to [update](EmaModule::update) for each update of the average.
+    /// Default: 1
+    #[config(default = 1)]
+    pub update_every: usize,
+}
+
+impl EmaConfig {
+    /// Initializes an [exponential moving average](EmaModule) starting from the given module.
+    ///
+    /// The average lives on the devices of the module, use [to_device](EmaModule::to_device) to
+    /// keep it elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// If `decay` is out of range [0.0, 1.0] or `update_every` is 0.
+    pub fn init<B: Backend, M: Module<B>>(&self, module: &M) -> EmaModule<B, M> {
+        assert!(
+            (0.0..=1.0).contains(&self.decay),
+            "The decay should be between 0 and 1"
+        );
+        assert!(
+            self.update_every > 0,
+            "The number of steps between updates should be positive"
+        );
+
+        EmaModule {
+            module: module.clone().no_grad(),
+            decay: self.decay,
+            warmup: self.warmup,
+            update_every: self.update_every,
+            num_steps: 0,
+            num_updates: 0,
+            _backend: PhantomData,
+        }
+    }
+}
+
+/// Exponential moving average (EMA) of the weights of a module.
+///
+/// A shadow copy of the module is updated from the trained module with
+/// `average = decay * average + (1 - decay) * weight`, matching the tensors by
+/// [parameter id](crate::module::ParamId). All float tensors are averaged, including running
+/// statistics such as the ones of [batch normalization](crate::nn::BatchNorm), while integer and
+/// boolean tensors are kept from the initial module.
+///
+/// Should be created using [EmaConfig].
+#[derive(Clone, Debug)]
+pub struct EmaModule<B: Backend, M: Module<B>> {
+    module: M,
+    decay: f64,
+    warmup: bool,
+    update_every: usize,
+    num_steps: usize,
+    num_updates: usize,
+    _backend: PhantomData<B>,
+}
+
+/// Record of an [exponential moving average](EmaModule).
+#[derive(Record)]
+pub struct EmaRecord<B: Backend, M: Module<B>> {
+    /// The averaged module.
+    pub module: <M as Module<B>>::Record,
+    /// The number of calls to [update](EmaModule::update).
+    pub num_steps: usize,
+    /// The number of updates of the average.
+    pub num_updates: usize,
+}
+
+impl<B: Backend, M: Module<B>> EmaModule<B, M> {
+    /// Updates the average with the weights of the given module, usually called after each
+    /// optimizer step.
+    ///
+    /// The tensors of the module are detached and moved to the devices of the average.
+    pub fn update(&mut self, module: &M) {
+        self.num_steps += 1;
+        if self.num_steps % self.update_every != 0 {
+            return;
+        }
+
+        let decay = self.decay();
+        self.module = interpolate(self.module.clone(), module, 1.0 - decay);
+        self.num_updates += 1;
+    }
+
+    /// The decay used by the next update of the average.
+    pub fn decay(&self) -> f64 {
+        if !self.warmup {
+            return self.decay;
+        }
+
+        let num_updates = self.num_updates as f64;
+        f64::min(self.decay, (1.0 + num_updates) / (10.0 + num_updates))
+    }
+
+    /// The number of updates of the average.
+    pub fn num_updates(&self) -> usize {
+        self.num_updates
+    }
+
+    /// The averaged module.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Consumes the average, returning the averaged module.
+    pub fn into_module(self) -> M {
+        self.module
+    }
+
+    /// Moves the average to the given device.
+    pub fn to_device(mut self, device: &B::Device) -> Self {
+        self.module = self.module.to_device(device);
+        self
+    }
+
+    /// Get the current state of the average as a [record](EmaRecord).
+    pub fn into_record(self) -> EmaRecord<B, M> {
+        EmaRecord {
+            module: self.module.into_record(),
+            num_steps: self.num_steps,
+            num_updates: self.num_updates,
+        }
+    }
+
+    /// Load the state of the average from a [record](EmaRecord).
+    pub fn load_record(mut self, record: EmaRecord<B, M>) -> Self {
+        self.module = self.module.load_record(record.module);
+        self.num_steps = record.num_steps;
+        self.num_updates = record.num_updates;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::tensor::Tensor;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn shifted(module: &Linear<TestBackend>, shift: f32) -> Linear<TestBackend> {
+        let mut module = module.clone();
+        module.weight = module.weight.map(|weight| weight.add_scalar(shift));
+        module
+    }
+
+    fn assert_shifted(module: &Linear<TestBackend>, initial: &Linear<TestBackend>, shift: f32) {
+        let expected: Tensor<TestBackend, 2> = initial.weight.val().add_scalar(shift);
+        module
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn ema_should_average_weights() {
+        let device = Default::default();
+        let module = LinearConfig::new(2, 3).init::<TestBackend>(&device);
+        let mut ema = EmaConfig::new().with_decay(0.5).init(&module);
+
+        ema.update(&shifted(&module, 1.0));
+        assert_shifted(ema.module(), &module, 0.5);
+
+        ema.update(&shifted(&module, 1.0));
+        assert_shifted(ema.module(), &module, 0.75);
+        assert_eq!(ema.num_updates(), 2);
+    }
+
+    #[test]
+    fn ema_should_update_every_n_steps() {
+        let device = Default::default();
+        let module = LinearConfig::new(2, 3).init::<TestBackend>(&device);
+        let mut ema = EmaConfig::new()
+            .with_decay(0.5)
+            .with_update_every(2)
+            .init(&module);
+
+        ema.update(&shifted(&module, 1.0));
+        assert_shifted(ema.module(), &module, 0.0);
+
+        ema.update(&shifted(&module, 1.0));
+        assert_shifted(ema.module(), &module, 0.5);
+    }
+
+    #[test]
+    fn ema_decay_warmup() {
+        let device = Default::default();
+        let module = LinearConfig::new(2, 3).init::<TestBackend>(&device);
+        let mut ema = EmaConfig::new()
+            .with_decay(0.9)
+            .with_warmup(true)
+            .init(&module);
+
+        assert_eq!(ema.decay(), 0.1);
+        ema.update(&shifted(&module, 1.0));
+        assert_shifted(ema.module(), &module, 0.9);
+        assert_eq!(ema.decay(), 2.0 / 11.0);
+
+        (0..100).for_each(|_| ema.update(&module));
+        assert_eq!(ema.decay(), 0.9);
+    }
+
+    #[test]
+    fn ema_record_should_restore_counters() {
+        let device = Default::default();
+        let module = LinearConfig::new(2, 3).init::<TestBackend>(&device);
+        let config = EmaConfig::new().with_decay(0.5).with_warmup(true);
+        let mut ema = config.init(&module);
+        ema.update(&shifted(&module, 1.0));
+
+        let loaded = config.init(&module).load_record(ema.clone().into_record());
+
+        assert_eq!(loaded.num_updates(), 1);
+        assert_eq!(loaded.decay(), ema.decay());
+        assert_shifted(loaded.module(), &module, 0.9);
+    }
+}
@@ -0,0 +1,6 @@
+mod base;
+mod ema;
+mod swa;
+
+pub use ema::*;
+pub use swa::*;
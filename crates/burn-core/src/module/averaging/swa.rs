@@ -0,0 +1,330 @@
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+use burn_tensor::container::TensorContainer;
+
+use super::base::interpolate;
+use crate as burn;
+use crate::module::{AutodiffModule, Module, ModuleMapper, ModuleVisitor, ParamId};
+use crate::record::Record;
+use crate::tensor::{
+    Tensor,
+    backend::{AutodiffBackend, Backend},
+};
+
+/// Stochastic weight averaging (SWA) of a module.
+///
+/// Keeps the equal-weight average of all the modules given to [update](SwaModule::update),
+/// usually snapshots taken at the end of each epoch once the learning rate is high and constant,
+/// as described in [Averaging Weights Leads to Wider Optima and Better
+/// Generalization](https://arxiv.org/abs/1803.05407).
+///
+/// Averaging the running statistics of [batch normalization](crate::nn::BatchNorm) does not give
+/// the statistics of the averaged weights, they should be recomputed with
+/// [recompute_batch_norm] before using the averaged module.
+#[derive(Clone, Debug)]
+pub struct SwaModule<B: Backend, M: Module<B>> {
+    module: M,
+    num_averaged: usize,
+    _backend: PhantomData<B>,
+}
+
+/// Record of a [stochastic weight average](SwaModule).
+#[derive(Record)]
+pub struct SwaRecord<B: Backend, M: Module<B>> {
+    /// The averaged module.
+    pub module: <M as Module<B>>::Record,
+    /// The number of modules in the average.
+    pub num_averaged: usize,
+}
+
+impl<B: Backend, M: Module<B>> SwaModule<B, M> {
+    /// Creates an average containing only the given module.
+    pub fn new(module: &M) -> Self {
+        Self {
+            module: module.clone().no_grad(),
+            num_averaged: 1,
+            _backend: PhantomData,
+        }
+    }
+
+    /// Adds the weights of the given module to the average.
+    pub fn update(&mut self, module: &M) {
+        let weight = 1.0 / (self.num_averaged + 1) as f64;
+        self.module = interpolate(self.module.clone(), module, weight);
+        self.num_averaged += 1;
+    }
+
+    /// The number of modules in the average.
+    pub fn num_averaged(&self) -> usize {
+        self.num_averaged
+    }
+
+    /// The averaged module.
+    pub fn module(&self) -> &M {
+        &self.module
+    }
+
+    /// Consumes the average, returning the averaged module.
+    pub fn into_module(self) -> M {
+        self.module
+    }
+
+    /// Get the current state of the average as a [record](SwaRecord).
+    pub fn into_record(self) -> SwaRecord<B, M> {
+        SwaRecord {
+            module: self.module.into_record(),
+            num_averaged: self.num_averaged,
+        }
+    }
+
+    /// Load the state of the average from a [record](SwaRecord).
+    pub fn load_record(mut self, record: SwaRecord<B, M>) -> Self {
+        self.module = self.module.load_record(record.module);
+        self.num_averaged = record.num_averaged;
+        self
+    }
+}
+
+/// Recomputes the running statistics of all the [batch normalization](crate::nn::BatchNorm)
+/// layers of a module as the average of the statistics of each batch.
+///
+/// The `forward` function is called once per batch in training mode, plus once more on the first
+/// batch to measure the momentum of each layer. It must be deterministic up to the batch
+/// normalization layers, so dropout placed before them should be disabled, and the momentum of
+/// each layer must be greater than 0.
+pub fn recompute_batch_norm<B, M, T>(
+    module: M,
+    batches: impl IntoIterator<Item = T>,
+    mut forward: impl FnMut(&M, T),
+) -> M
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    T: Clone,
+{
+    let mut batches = batches.into_iter();
+    let Some(first) = batches.next() else {
+        return module;
+    };
+
+    // Pending updates of the running statistics would overwrite the filled values.
+    let _ = module.valid();
+
+    // Starting from 1 gives `1 - momentum + momentum * stat` after a step, and starting from 0
+    // gives `momentum * stat`.
+    let mut module = module.map(&mut FillRunningStats::new(1.0));
+    forward(&module, first.clone());
+    let ones = collect_running_stats::<B, M>(&module, RunningStatsCollector::default());
+
+    module = module.map(&mut FillRunningStats::new(0.0));
+    forward(&module, first);
+    let first = collect_running_stats::<B, M>(&module, RunningStatsCollector::default());
+
+    let mut rest = RunningStatsCollector::default();
+    let mut num_batches = 1;
+    for batch in batches {
+        module = module.map(&mut FillRunningStats::new(0.0));
+        forward(&module, batch);
+        rest = collect_running_stats::<B, M>(&module, rest);
+        num_batches += 1;
+    }
+
+    module.map(&mut AverageRunningStats::<B> {
+        scope: RunningStatsScope::default(),
+        ones: ones.tensors,
+        first: first.tensors,
+        rest: rest.tensors,
+        num_batches,
+        _backend: PhantomData,
+    })
+}
+
+fn collect_running_stats<B: AutodiffBackend, M: AutodiffModule<B>>(
+    module: &M,
+    mut collector: RunningStatsCollector,
+) -> RunningStatsCollector {
+    // The inner module is synchronized with the statistics updated by the forward pass.
+    module.valid().visit(&mut collector);
+    collector
+}
+
+/// Tracks whether the visited tensor is a running statistic of a batch normalization layer.
+#[derive(Default)]
+struct RunningStatsScope {
+    stack: Vec<bool>,
+}
+
+impl RunningStatsScope {
+    fn enter(&mut self, name: &str, container_type: &str) {
+        self.stack
+            .push(container_type == "BatchNorm" && name.starts_with("running_"));
+    }
+
+    fn exit(&mut self) {
+        self.stack.pop();
+    }
+
+    fn is_running_stat(&self) -> bool {
+        self.stack.last().copied().unwrap_or(false)
+    }
+}
+
+struct FillRunningStats {
+    scope: RunningStatsScope,
+    value: f64,
+}
+
+impl FillRunningStats {
+    fn new(value: f64) -> Self {
+        Self {
+            scope: RunningStatsScope::default(),
+            value,
+        }
+    }
+}
+
+impl<B: Backend> ModuleMapper<B> for FillRunningStats {
+    fn enter_module(&mut self, name: &str, container_type: &str) {
+        self.scope.enter(name, container_type);
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        self.scope.exit();
+    }
+
+    fn map_float<const D: usize>(&mut self, _id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        match self.scope.is_running_stat() {
+            true => tensor.full_like(self.value),
+            false => tensor,
+        }
+    }
+}
+
+#[derive(Default)]
+struct RunningStatsCollector {
+    scope: RunningStatsScope,
+    tensors: TensorContainer<ParamId>,
+}
+
+impl<B: Backend> ModuleVisitor<B> for RunningStatsCollector {
+    fn enter_module(&mut self, name: &str, container_type: &str) {
+        self.scope.enter(name, container_type);
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        self.scope.exit();
+    }
+
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        if !self.scope.is_running_stat() {
+            return;
+        }
+
+        let tensor = match self.tensors.remove::<B>(&id) {
+            Some(sum) => Tensor::<B, D>::from_primitive(sum).add(tensor.clone()),
+            None => tensor.clone(),
+        };
+        self.tensors.register::<B>(id, tensor.into_primitive());
+    }
+}
+
+struct AverageRunningStats<B: AutodiffBackend> {
+    scope: RunningStatsScope,
+    ones: TensorContainer<ParamId>,
+    first: TensorContainer<ParamId>,
+    rest: TensorContainer<ParamId>,
+    num_batches: usize,
+    _backend: PhantomData<B>,
+}
+
+impl<B: AutodiffBackend> ModuleMapper<B> for AverageRunningStats<B> {
+    fn enter_module(&mut self, name: &str, container_type: &str) {
+        self.scope.enter(name, container_type);
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        self.scope.exit();
+    }
+
+    fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        if !self.scope.is_running_stat() {
+            return tensor;
+        }
+        let (Some(ones), Some(first)) = (
+            self.ones.remove::<B::InnerBackend>(&id),
+            self.first.remove::<B::InnerBackend>(&id),
+        ) else {
+            return tensor;
+        };
+
+        let ones = Tensor::<B::InnerBackend, D>::from_primitive(ones);
+        let first = Tensor::<B::InnerBackend, D>::from_primitive(first);
+        let momentum = first.clone().sub(ones).add_scalar(1.0);
+        let sum = match self.rest.remove::<B::InnerBackend>(&id) {
+            Some(rest) => first.add(Tensor::from_primitive(rest)),
+            None => first,
+        };
+        let average = sum.div(momentum.mul_scalar(self.num_batches as f64));
+
+        Tensor::from_inner(average).to_device(&tensor.device())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{BatchNorm, BatchNormConfig, LinearConfig};
+    use crate::{TestAutodiffBackend, TestBackend};
+    use burn_tensor::{TensorData, Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn swa_should_average_weights_equally() {
+        let device = Default::default();
+        let module = LinearConfig::new(2, 3).init::<TestBackend>(&device);
+        let mut swa = SwaModule::new(&module);
+
+        for shift in [1.0, 2.0] {
+            let mut shifted = module.clone();
+            shifted.weight = shifted.weight.map(|weight| weight.add_scalar(shift));
+            swa.update(&shifted);
+        }
+
+        assert_eq!(swa.num_averaged(), 3);
+        swa.module()
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &module.weight.val().add_scalar(1.0).into_data(),
+                Tolerance::default(),
+            );
+    }
+
+    #[test]
+    fn recompute_batch_norm_should_average_batch_statistics() {
+        let device = Default::default();
+        let module: BatchNorm<TestAutodiffBackend> = BatchNormConfig::new(1).init(&device);
+        let batches = [
+            Tensor::<TestAutodiffBackend, 2>::from_floats([[1.0], [2.0], [3.0], [4.0]], &device),
+            Tensor::from_floats([[5.0], [7.0]], &device),
+        ];
+
+        let module = recompute_batch_norm(module, batches, |module, batch| {
+            module.forward(batch);
+        });
+
+        module
+            .running_mean
+            .value()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([4.25]), Tolerance::default());
+        module
+            .running_var
+            .value()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([1.125]), Tolerance::default());
+    }
+}
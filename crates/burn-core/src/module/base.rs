@@ -1,5 +1,9 @@
-use super::{ParamId, Quantizer};
+use super::{
+    ParamId, Quantizer,
+    freeze::{RequireGradMapper, TrainableParamsCounter},
+};
 use crate::{
+    optim::ParamPathFilter,
     record::Record,
     tensor::backend::{AutodiffBackend, Backend},
 };
@@ -136,6 +140,34 @@ pub trait Module<B: Backend>: Clone + Send + core::fmt::Debug {
             init = || 0
         )
     }
+    /// Get the number of parameters requiring gradients, i.e. the ones updated during training.
+    ///
+    /// Only [autodiff](crate::tensor::backend::AutodiffBackend) modules track gradients, the count
+    /// is zero on other backends.
+    fn num_trainable_params(&self) -> usize {
+        let mut counter = TrainableParamsCounter::default();
+        self.visit(&mut counter);
+        counter.num_params
+    }
+
+    /// Freeze the parameters whose module path is accepted by the filter, so they no longer
+    /// require gradients.
+    ///
+    /// Frozen parameters don't get gradients from the backward pass, so they are skipped by
+    /// [GradientsParams](crate::optim::GradientsParams) and left untouched by the optimizers.
+    /// Filters like [GlobFilter](crate::module::GlobFilter), or the `PathFilter` of `burn-store`
+    /// for regular expressions, match the dot-separated path of each parameter, e.g.
+    /// `encoder.layers.0.weight`.
+    fn freeze<F: ParamPathFilter + ?Sized>(self, filter: &F) -> Self {
+        self.map(&mut RequireGradMapper::new(filter, false))
+    }
+
+    /// Unfreeze the parameters whose module path is accepted by the filter, so they require
+    /// gradients again. See [freeze](Module::freeze).
+    fn unfreeze<F: ParamPathFilter + ?Sized>(self, filter: &F) -> Self {
+        self.map(&mut RequireGradMapper::new(filter, true))
+    }
+
     /// Visit each tensor parameter in the module with a [visitor](ModuleVisitor).
     fn visit<Visitor: ModuleVisitor<B>>(&self, visitor: &mut Visitor);
 
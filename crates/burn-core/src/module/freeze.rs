@@ -0,0 +1,228 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{ModuleMapper, ModuleVisitor, ParamId};
+use crate::optim::ParamPathFilter;
+use crate::tensor::{Tensor, backend::Backend};
+
+/// [Path filter](ParamPathFilter) matching module paths against glob patterns.
+///
+/// Paths are dot-separated field names (e.g. `encoder.layers.0.attn.query.weight`). In a
+/// pattern, `*` matches any part of a single path segment, `?` matches a single character of a
+/// segment, and `**` matches any number of segments.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Fine-tune only the last layer of the encoder and the head.
+/// let model = model
+///     .freeze(&GlobFilter::new(["encoder.**"]))
+///     .unfreeze(&GlobFilter::new(["encoder.layers.11.**"]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlobFilter {
+    patterns: Vec<String>,
+}
+
+impl GlobFilter {
+    /// Creates a filter matching the paths accepted by any of the given patterns.
+    pub fn new<I, S>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Adds a pattern to the filter.
+    pub fn with_pattern<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+}
+
+impl ParamPathFilter for GlobFilter {
+    fn matches(&self, path: &str, _container_path: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern.as_bytes(), path.as_bytes()))
+    }
+}
+
+fn glob_matches(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            // `**.` can also match no segment at all.
+            if let [b'.', after_dot @ ..] = rest
+                && glob_matches(after_dot, path)
+            {
+                return true;
+            }
+            (0..=path.len()).any(|start| glob_matches(rest, &path[start..]))
+        }
+        [b'*', rest @ ..] => {
+            let segment_len = path.iter().position(|c| *c == b'.').unwrap_or(path.len());
+            (0..=segment_len).any(|start| glob_matches(rest, &path[start..]))
+        }
+        [b'?', rest @ ..] => match path {
+            [c, path @ ..] if *c != b'.' => glob_matches(rest, path),
+            _ => false,
+        },
+        [c, rest @ ..] => match path {
+            [p, path @ ..] if p == c => glob_matches(rest, path),
+            _ => false,
+        },
+    }
+}
+
+/// Sets whether the float parameters selected by a path filter require gradients.
+pub(crate) struct RequireGradMapper<'a, F: ParamPathFilter + ?Sized> {
+    filter: &'a F,
+    require_grad: bool,
+    path: Vec<String>,
+    container_path: Vec<String>,
+}
+
+impl<'a, F: ParamPathFilter + ?Sized> RequireGradMapper<'a, F> {
+    pub(crate) fn new(filter: &'a F, require_grad: bool) -> Self {
+        Self {
+            filter,
+            require_grad,
+            path: Vec::new(),
+            container_path: Vec::new(),
+        }
+    }
+}
+
+impl<B: Backend, F: ParamPathFilter + ?Sized> ModuleMapper<B> for RequireGradMapper<'_, F> {
+    fn enter_module(&mut self, name: &str, container_type: &str) {
+        self.path.push(name.to_string());
+        self.container_path.push(container_type.to_string());
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        self.path.pop();
+        self.container_path.pop();
+    }
+
+    fn map_float<const D: usize>(&mut self, _id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let path = self.path.join(".");
+        let container_path = self.container_path.join(".");
+
+        match self.filter.matches(&path, &container_path) {
+            true => tensor.set_require_grad(self.require_grad),
+            false => tensor,
+        }
+    }
+}
+
+/// Counts the elements of the float tensors requiring gradients.
+#[derive(Default)]
+pub(crate) struct TrainableParamsCounter {
+    pub(crate) num_params: usize,
+}
+
+impl<B: Backend> ModuleVisitor<B> for TrainableParamsCounter {
+    fn visit_float<const D: usize>(&mut self, _id: ParamId, tensor: &Tensor<B, D>) {
+        if tensor.is_require_grad() {
+            self.num_params += tensor.shape().num_elements();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as burn;
+    use crate::TestAutodiffBackend;
+    use crate::module::Module;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::{AdamConfig, GradientsParams, Optimizer};
+    use crate::tensor::{Distribution, backend::Backend};
+
+    #[derive(Module, Debug)]
+    struct Model<B: Backend> {
+        encoder: Linear<B>,
+        head: Linear<B>,
+    }
+
+    impl<B: Backend> Model<B> {
+        fn new(device: &B::Device) -> Self {
+            Self {
+                encoder: LinearConfig::new(4, 4).init(device),
+                head: LinearConfig::new(4, 2).init(device),
+            }
+        }
+
+        fn forward(&self, input: Tensor<B, 2>) -> Tensor<B, 2> {
+            self.head.forward(self.encoder.forward(input))
+        }
+    }
+
+    #[test]
+    fn glob_should_match_paths() {
+        let matches = |pattern: &str, path: &str| glob_matches(pattern.as_bytes(), path.as_bytes());
+
+        assert!(matches("encoder.weight", "encoder.weight"));
+        assert!(matches("encoder.*", "encoder.weight"));
+        assert!(!matches("encoder.*", "encoder.layers.0.weight"));
+        assert!(matches("encoder.**", "encoder.layers.0.weight"));
+        assert!(matches("**.bias", "encoder.layers.0.bias"));
+        assert!(matches("**.bias", "bias"));
+        assert!(matches("encoder.layers.?.*", "encoder.layers.3.weight"));
+        assert!(!matches("encoder.layers.?.*", "encoder.layers.10.weight"));
+        assert!(matches("*_norm.*", "encoder_norm.gamma"));
+        assert!(!matches("encoder.**", "encoder_norm.gamma"));
+    }
+
+    #[test]
+    fn freeze_should_only_affect_selected_params() {
+        let device = Default::default();
+        let model = Model::<TestAutodiffBackend>::new(&device);
+
+        let model = model.freeze(&GlobFilter::new(["encoder.**"]));
+
+        assert!(!model.encoder.weight.is_require_grad());
+        assert!(!model.encoder.bias.as_ref().unwrap().is_require_grad());
+        assert!(model.head.weight.is_require_grad());
+        assert_eq!(model.num_params(), 4 * 4 + 4 + 4 * 2 + 2);
+        assert_eq!(model.num_trainable_params(), 4 * 2 + 2);
+
+        let model = model.unfreeze(&GlobFilter::new(["encoder.bias"]));
+
+        assert!(!model.encoder.weight.is_require_grad());
+        assert!(model.encoder.bias.as_ref().unwrap().is_require_grad());
+        assert_eq!(model.num_trainable_params(), 4 + 4 * 2 + 2);
+    }
+
+    #[test]
+    fn frozen_params_should_not_be_optimized() {
+        let device = Default::default();
+        let model =
+            Model::<TestAutodiffBackend>::new(&device).freeze(&GlobFilter::new(["encoder.*"]));
+        let mut optim = AdamConfig::new().init();
+        let encoder_weight = model.encoder.weight.val();
+        let input = Tensor::random([3, 4], Distribution::Default, &device);
+
+        let grads = model.forward(input).sum().backward();
+        let grads = GradientsParams::from_grads(grads, &model);
+        assert_eq!(grads.len(), 2);
+
+        let model = optim.step(0.1, model, grads);
+
+        model
+            .encoder
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&encoder_weight.into_data(), true);
+        assert!(!model.encoder.weight.is_require_grad());
+        assert!(model.head.weight.is_require_grad());
+        assert_eq!(optim.to_record().states.len(), 2);
+    }
+}
@@ -1,14 +1,20 @@
+mod averaging;
 mod base;
 mod display;
+mod freeze;
 mod param;
 mod quantize;
 #[cfg(feature = "std")]
 mod reinit;
 
+pub use averaging::*;
 pub use base::*;
 pub use display::*;
+pub use freeze::GlobFilter;
 pub use param::*;
 pub use quantize::*;
 
+pub use burn_common::scope::{ForwardScope, scope_path};
+
 #[cfg(feature = "std")]
 pub use reinit::*;
@@ -1,7 +1,7 @@
 use crate as burn;
 
 use crate::config::Config;
-use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::module::{Content, DisplaySettings, ForwardScope, Module, ModuleDisplay};
 use crate::tensor::activation::silu;
 use crate::tensor::{Tensor, backend::Backend};
 
@@ -87,9 +87,11 @@ impl<B: Backend> SwiGlu<B> {
     /// - input: `[batch_size, seq_length, d_input]`
     /// - output: `[batch_size, seq_length, d_output]`
     pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
-        let x = self.linear_inner.forward(input.clone());
+        let x = ForwardScope::run("linear_inner", || self.linear_inner.forward(input.clone()));
         let x = silu(x);
-        x.mul(self.linear_outer.forward(input))
+        x.mul(ForwardScope::run("linear_outer", || {
+            self.linear_outer.forward(input)
+        }))
     }
 }
 
@@ -3,6 +3,10 @@ use burn_tensor::ops::IntElem;
 
 use crate::tensor::{Bool, ElementConversion, Int, Shape, Tensor, TensorData, backend::Backend};
 
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
 /// Generate an autoregressive attention mask.
 ///
 /// The mask can be used in Transformer modules to train models to generate tensors sequentially.
@@ -15,6 +19,71 @@ pub fn generate_autoregressive_mask<B: Backend>(
     mask.expand([batch_size, seq_length, seq_length])
 }
 
+/// Generate the attention linear biases (ALiBi) added to the attention scores.
+///
+/// Each head `h` penalizes the attention between a query at position `i` and a key at position
+/// `j` by `-m_h * |i - j|`, where `m_h` is a head-specific slope following a geometric sequence.
+/// When there are fewer queries than keys, the queries are assumed to be the last positions of
+/// the sequence, which is the case during autoregressive decoding.
+///
+/// Introduced in the paper: [Train Short, Test Long: Attention with Linear Biases Enables Input Length Extrapolation](https://arxiv.org/abs/2108.12409)
+///
+/// # Shapes
+///
+/// - output: `[1, n_heads, seq_length_query, seq_length_key]`
+pub fn generate_alibi_bias<B: Backend>(
+    n_heads: usize,
+    seq_length_query: usize,
+    seq_length_key: usize,
+    device: &B::Device,
+) -> Tensor<B, 4> {
+    assert!(
+        seq_length_query <= seq_length_key,
+        "The query sequence length must not exceed the key sequence length"
+    );
+
+    let offset = (seq_length_key - seq_length_query) as i64;
+    let query_pos = Tensor::<B, 1, Int>::arange(offset..offset + seq_length_query as i64, device);
+    let key_pos = Tensor::<B, 1, Int>::arange(0..seq_length_key as i64, device);
+
+    // Relative distance `|i - j|` of shape [seq_length_query, seq_length_key].
+    let distance = (key_pos.unsqueeze_dim::<2>(0) - query_pos.unsqueeze_dim::<2>(1))
+        .abs()
+        .float();
+
+    let slopes = Tensor::<B, 1>::from_floats(alibi_slopes(n_heads).as_slice(), device)
+        .reshape([1, n_heads, 1, 1]);
+
+    distance.unsqueeze::<4>().neg() * slopes
+}
+
+/// Compute the ALiBi head slopes.
+///
+/// For a number of heads `n` that is a power of two, the slopes are `2^(-8i/n)` for `i` in
+/// `[1, n]`. Otherwise, the slopes of the closest lower power of two are interleaved with the
+/// odd slopes of the next power of two, as in the reference implementation.
+fn alibi_slopes(n_heads: usize) -> Vec<f32> {
+    let slopes_power_of_two = |n: usize| {
+        let start = 2.0f64.powf(-8.0 / n as f64);
+        (1..=n).map(move |i| start.powi(i as i32) as f32)
+    };
+
+    let closest = if n_heads.is_power_of_two() {
+        n_heads
+    } else {
+        n_heads.next_power_of_two() / 2
+    };
+
+    let mut slopes: Vec<f32> = slopes_power_of_two(closest).collect();
+    slopes.extend(
+        slopes_power_of_two(2 * closest)
+            .step_by(2)
+            .take(n_heads - closest),
+    );
+
+    slopes
+}
+
 /// Generate a padding attention mask.
 pub struct GeneratePaddingMask<B: Backend> {
     /// The generated tensor.
@@ -79,6 +148,8 @@ mod tests {
     use crate::TestBackend;
     use crate::tensor::TensorData;
     use alloc::vec;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+    type FT = FloatElem<TestBackend>;
 
     #[test]
     fn test_generate_autoregressive_mask() {
@@ -103,6 +174,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_alibi_bias() {
+        let device = <TestBackend as Backend>::Device::default();
+
+        let bias = generate_alibi_bias::<TestBackend>(2, 3, 3, &device);
+
+        // Slopes for 2 heads: [2^-4, 2^-8].
+        bias.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([[
+                [
+                    [0.0, -0.0625, -0.125],
+                    [-0.0625, 0.0, -0.0625],
+                    [-0.125, -0.0625, 0.0],
+                ],
+                [
+                    [0.0, -0.00390625, -0.0078125],
+                    [-0.00390625, 0.0, -0.00390625],
+                    [-0.0078125, -0.00390625, 0.0],
+                ],
+            ]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn test_alibi_slopes_non_power_of_two() {
+        let slopes = alibi_slopes(3);
+
+        // Slopes of 2 heads [2^-4, 2^-8] followed by the first odd slope of 4 heads [2^-2].
+        assert_eq!(slopes, vec![0.0625, 0.00390625, 0.25]);
+    }
+
     #[test]
     fn test_generate_padding_mask() {
         let device = <TestBackend as Backend>::Device::default();
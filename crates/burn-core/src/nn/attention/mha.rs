@@ -1,9 +1,10 @@
 use crate as burn;
 
-use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::amp::{FullPrecisionOp, autocast, cast_to, full_precision};
+use crate::module::{Content, DisplaySettings, ForwardScope, Module, ModuleDisplay};
 use crate::nn::activation::Gelu;
 use crate::nn::cache::TensorCache;
-use crate::nn::{Dropout, DropoutConfig, Initializer, Linear, LinearConfig};
+use crate::nn::{Dropout, DropoutConfig, Initializer, Linear, LinearConfig, RotaryEncoding};
 use crate::{
     config::Config,
     tensor::{Bool, Tensor, backend::Backend},
@@ -111,6 +112,9 @@ pub struct MhaInput<B: Backend> {
     value: Tensor<B, 3>,
     mask_pad: Option<Tensor<B, 2, Bool>>,
     mask_attn: Option<Tensor<B, 3, Bool>>,
+    /// Shape `[batch_size or 1, n_heads or 1, seq_length_1, seq_length_2]`
+    attn_bias: Option<Tensor<B, 4>>,
+    rope: Option<RotaryEncoding<B>>,
 }
 
 impl MultiHeadAttentionConfig {
@@ -151,6 +155,8 @@ impl<B: Backend> MhaInput<B> {
             value: tensor,
             mask_pad: None,
             mask_attn: None,
+            attn_bias: None,
+            rope: None,
         }
     }
 
@@ -162,6 +168,8 @@ impl<B: Backend> MhaInput<B> {
             value,
             mask_pad: None,
             mask_attn: None,
+            attn_bias: None,
+            rope: None,
         }
     }
 
@@ -176,6 +184,24 @@ impl<B: Backend> MhaInput<B> {
         self.mask_attn = Some(mask_attn);
         self
     }
+
+    /// Register an additive bias applied to the attention scores before the softmax,
+    /// such as the one produced by [generate_alibi_bias](super::generate_alibi_bias).
+    ///
+    /// # Shape
+    /// - attn_bias: `[batch_size or 1, n_heads or 1, seq_length_1, seq_length_2]`
+    pub fn attn_bias(mut self, attn_bias: Tensor<B, 4>) -> Self {
+        self.attn_bias = Some(attn_bias);
+        self
+    }
+
+    /// Register a rotary positional encoding applied to the query and key of each head.
+    ///
+    /// The encoding dimension must match the size of each head (`d_model / n_heads`).
+    pub fn rope(mut self, rope: RotaryEncoding<B>) -> Self {
+        self.rope = Some(rope);
+        self
+    }
 }
 
 /// [Multihead attention](MultiHeadAttention) outputs.
@@ -199,24 +225,58 @@ impl<B: Backend> MultiHeadAttention<B> {
     /// - value: `[batch_size, seq_length_2, d_model]`
     /// - output: `[batch_size, seq_length_1, d_model]`
     pub fn forward(&self, input: MhaInput<B>) -> MhaOutput<B> {
+        self.forward_projected(input, |projection, x| {
+            self.projection(projection).forward(x)
+        })
+    }
+
+    /// Applies the forward pass, computing the query, key, value and output projections of the
+    /// inputs with the given function, in the [scope](ForwardScope) of each projection.
+    pub(crate) fn forward_projected<F>(&self, input: MhaInput<B>, project: F) -> MhaOutput<B>
+    where
+        F: Fn(MhaProjection, Tensor<B, 3>) -> Tensor<B, 3>,
+    {
         let [batch_size, seq_length_1, d_model] = input.query.dims();
+        let project = |projection: MhaProjection, x: Tensor<B, 3>| {
+            ForwardScope::run(projection.name(), || project(projection, x))
+        };
 
-        let query = self.attention_linear(input.query, &self.query);
-        let key = self.attention_linear(input.key, &self.key);
-        let value = self.attention_linear(input.value, &self.value);
+        let query = self.split_heads(project(MhaProjection::Query, input.query));
+        let key = self.split_heads(project(MhaProjection::Key, input.key));
+        let value = self.split_heads(project(MhaProjection::Value, input.value));
+
+        let (query, key) = match &input.rope {
+            Some(rope) => (rope.apply(query, 0), rope.apply(key, 0)),
+            None => (query, key),
+        };
 
         let attn_scores = self.attn_scores(query, key);
-        let weights = self.attn_weights(attn_scores, input.mask_pad, input.mask_attn);
+        let weights = self.attn_weights(
+            attn_scores,
+            input.attn_bias,
+            input.mask_pad,
+            input.mask_attn,
+        );
 
         let context = weights.clone().matmul(value);
         let context = context
             .swap_dims(1, 2)
             .reshape([batch_size, seq_length_1, d_model]);
-        let context = self.output.forward(context);
+        let context = project(MhaProjection::Output, context);
 
         MhaOutput { weights, context }
     }
 
+    /// The linear layer of the given projection.
+    pub(crate) fn projection(&self, projection: MhaProjection) -> &Linear<B> {
+        match projection {
+            MhaProjection::Query => &self.query,
+            MhaProjection::Key => &self.key,
+            MhaProjection::Value => &self.value,
+            MhaProjection::Output => &self.output,
+        }
+    }
+
     /// Applies the forward pass using a cache.
     ///
     /// # Shapes
@@ -227,33 +287,46 @@ impl<B: Backend> MultiHeadAttention<B> {
     /// - output: `[batch_size, seq_length_1, d_model]`
     pub fn forward_cache(&self, input: MhaInput<B>, cache: &mut MhaCache<B>) -> MhaOutput<B> {
         let [batch_size, seq_length_1, d_model] = input.query.dims();
-
-        let query = cache
-            .query
-            .forward(input.query, |t| self.attention_linear(t, &self.query));
-        let key = cache
-            .key
-            .forward(input.key, |t| self.attention_linear(t, &self.key));
-        let value = cache
-            .value
-            .forward(input.value, |t| self.attention_linear(t, &self.value));
+        let seq_length_2 = input.key.dims()[1];
+        let rope = input.rope.as_ref();
+
+        // Only the new positions are projected when the cache is filled, so the rotary encoding
+        // starts at the first position not yet cached.
+        let query = cache.query.forward(input.query, |t| {
+            let start = seq_length_1 - t.dims()[1];
+            self.attention_linear_rope(t, MhaProjection::Query, rope, start)
+        });
+        let key = cache.key.forward(input.key, |t| {
+            let start = seq_length_2 - t.dims()[1];
+            self.attention_linear_rope(t, MhaProjection::Key, rope, start)
+        });
+        let value = cache.value.forward(input.value, |t| {
+            self.attention_linear(t, MhaProjection::Value)
+        });
 
         let attn_scores = self.attn_scores(query, key);
-        let weights = self.attn_weights(attn_scores, input.mask_pad, input.mask_attn);
+        let weights = self.attn_weights(
+            attn_scores,
+            input.attn_bias,
+            input.mask_pad,
+            input.mask_attn,
+        );
 
         let context = weights.clone().matmul(value);
         let context = context
             .swap_dims(1, 2)
             .reshape([batch_size, seq_length_1, d_model]);
 
-        let context = cache.output.forward(context, |t| self.output.forward(t));
+        let context = cache
+            .output
+            .forward(context, |t| self.project(MhaProjection::Output, t));
 
         MhaOutput { weights, context }
     }
 
     fn attn_scores(&self, query: Tensor<B, 4>, key: Tensor<B, 4>) -> Tensor<B, 4> {
-        let attn_scores = query
-            .matmul(key.transpose())
+        let attn_scores = autocast(query)
+            .matmul(autocast(key).transpose())
             .div_scalar((self.d_k as f32).sqrt());
 
         self.dropout.forward(attn_scores)
@@ -262,9 +335,15 @@ impl<B: Backend> MultiHeadAttention<B> {
     fn attn_weights(
         &self,
         mut attn_scores: Tensor<B, 4>,
+        attn_bias: Option<Tensor<B, 4>>,
         mask_pad: Option<Tensor<B, 2, Bool>>,
         mask_attn: Option<Tensor<B, 3, Bool>>,
     ) -> Tensor<B, 4> {
+        if let Some(attn_bias) = attn_bias {
+            let attn_bias = cast_to(attn_bias, attn_scores.dtype());
+            attn_scores = attn_scores + attn_bias;
+        }
+
         if let Some(mask_pad) = mask_pad {
             let [batch_size, seq_length] = mask_pad.dims();
 
@@ -283,20 +362,66 @@ impl<B: Backend> MultiHeadAttention<B> {
             );
         }
 
-        if self.quiet_softmax {
+        let dtype = attn_scores.dtype();
+        let attn_scores = full_precision(FullPrecisionOp::Softmax, attn_scores);
+
+        let weights = if self.quiet_softmax {
             quiet_softmax(attn_scores, 3)
         } else {
             softmax(attn_scores, 3)
-        }
+        };
+        cast_to(weights, dtype)
     }
 
-    fn attention_linear(&self, x: Tensor<B, 3>, linear: &Linear<B>) -> Tensor<B, 4> {
+    fn project(&self, projection: MhaProjection, x: Tensor<B, 3>) -> Tensor<B, 3> {
+        ForwardScope::run(projection.name(), || self.projection(projection).forward(x))
+    }
+
+    fn attention_linear(&self, x: Tensor<B, 3>, projection: MhaProjection) -> Tensor<B, 4> {
+        self.split_heads(self.project(projection, x))
+    }
+
+    fn split_heads(&self, x: Tensor<B, 3>) -> Tensor<B, 4> {
         let [batch_size, seq_length, _d_model] = x.dims();
-        linear
-            .forward(x)
-            .reshape([batch_size, seq_length, self.n_heads, self.d_k])
+        x.reshape([batch_size, seq_length, self.n_heads, self.d_k])
             .swap_dims(1, 2)
     }
+
+    fn attention_linear_rope(
+        &self,
+        x: Tensor<B, 3>,
+        projection: MhaProjection,
+        rope: Option<&RotaryEncoding<B>>,
+        start: usize,
+    ) -> Tensor<B, 4> {
+        let x = self.attention_linear(x, projection);
+
+        match rope {
+            Some(rope) => rope.apply(x, start),
+            None => x,
+        }
+    }
+}
+
+/// The projections of a [Multi Head Attention](MultiHeadAttention) layer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MhaProjection {
+    Query,
+    Key,
+    Value,
+    Output,
+}
+
+impl MhaProjection {
+    /// The name of the field of the projection, used as its [scope](ForwardScope).
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            MhaProjection::Query => "query",
+            MhaProjection::Key => "key",
+            MhaProjection::Value => "value",
+            MhaProjection::Output => "output",
+        }
+    }
 }
 
 /// Cache for the [Multi Head Attention](MultiHeadAttention) layer.
@@ -355,9 +480,11 @@ impl<B: Backend, const D: usize> MhaLinearCache<B, D> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TestBackend;
+    use crate::nn::RotaryEncodingConfig;
+    use crate::nn::attention::{generate_alibi_bias, generate_autoregressive_mask};
     use crate::tensor::Int;
     use crate::tensor::{Distribution, Shape};
-    use crate::{TestBackend, nn::attention::generate_autoregressive_mask};
     use alloc::vec::Vec;
     use burn_tensor::Tolerance;
     use burn_tensor::ops::FloatElem;
@@ -519,6 +646,54 @@ mod tests {
             );
     }
 
+    #[test]
+    fn test_rope_and_alibi_should_have_same_output_as_autoregressive_decoding() {
+        let [batch_size, seq_length, d_model, n_heads] = [3, 4, 12, 2];
+        let device = Default::default();
+        let mha = MultiHeadAttentionConfig::new(d_model, n_heads).init::<TestBackend>(&device);
+        let rope = RotaryEncodingConfig::new(seq_length, d_model / n_heads).init(&device);
+
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Default,
+            &device,
+        );
+        let mask_attn = generate_autoregressive_mask(batch_size, seq_length, &device);
+        let input = MhaInput::self_attn(tensor.clone())
+            .mask_attn(mask_attn)
+            .attn_bias(generate_alibi_bias(
+                n_heads, seq_length, seq_length, &device,
+            ))
+            .rope(rope.clone());
+
+        let output_1 = mha.forward(input);
+        let mut output_2 = Vec::new();
+        let mut cache = MhaCache::autoregressive();
+
+        for i in 1..seq_length + 1 {
+            let tensor = tensor.clone().slice([0..batch_size, 0..i, 0..d_model]);
+            let input = MhaInput::self_attn(tensor)
+                .attn_bias(generate_alibi_bias(n_heads, i, i, &device))
+                .rope(rope.clone());
+            let next_tok = mha.forward_cache(input, &mut cache).context.slice([
+                0..batch_size,
+                i - 1..i,
+                0..d_model,
+            ]);
+            output_2.push(next_tok);
+        }
+
+        let output_2 = Tensor::cat(output_2, 1);
+
+        output_1
+            .context
+            .into_data()
+            .assert_approx_eq::<FloatElem<TestBackend>>(
+                &output_2.into_data(),
+                Tolerance::default(),
+            );
+    }
+
     #[test]
     fn display() {
         let config = MultiHeadAttentionConfig::new(2, 4);
@@ -0,0 +1,124 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::tensor::Tensor;
+use crate::tensor::backend::Backend;
+
+/// Configuration to create a [ChannelShuffle](ChannelShuffle) layer using the [init function](ChannelShuffleConfig::init).
+#[derive(Config, Debug)]
+pub struct ChannelShuffleConfig {
+    /// The number of groups to divide the channels in.
+    pub groups: usize,
+}
+
+/// Shuffles the channels of the input tensor, so that the channels of each group are
+/// interleaved with the channels of the other groups.
+///
+/// The channels are viewed as `[groups, channels / groups]`, transposed and flattened back.
+///
+/// Introduced in the paper: [ShuffleNet: An Extremely Efficient Convolutional Neural Network for
+/// Mobile Devices](https://arxiv.org/abs/1707.01083).
+///
+/// Should be created using [ChannelShuffleConfig].
+#[derive(Module, Clone, Debug)]
+#[module(custom_display)]
+pub struct ChannelShuffle {
+    /// The number of groups to divide the channels in.
+    pub groups: usize,
+}
+
+impl ChannelShuffleConfig {
+    /// Initialize a new [channel shuffle](ChannelShuffle) module.
+    pub fn init(&self) -> ChannelShuffle {
+        assert!(self.groups > 0, "The number of groups must be positive");
+
+        ChannelShuffle {
+            groups: self.groups,
+        }
+    }
+}
+
+impl ChannelShuffle {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [ChannelShuffle](ChannelShuffle) for more information.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, channels, *]`
+    /// - output: `[batch_size, channels, *]`
+    pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        assert!(
+            D >= 2,
+            "ChannelShuffle expects an input of shape [batch_size, channels, *]"
+        );
+
+        let shape = input.dims();
+        let [batch_size, channels] = [shape[0], shape[1]];
+        assert!(
+            channels % self.groups == 0,
+            "The number of channels ({channels}) must be divisible by the number of groups ({})",
+            self.groups
+        );
+
+        let spatial = shape[2..].iter().product::<usize>();
+
+        input
+            .reshape([batch_size, self.groups, channels / self.groups, spatial])
+            .swap_dims(1, 2)
+            .reshape(shape)
+    }
+}
+
+impl ModuleDisplay for ChannelShuffle {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        content.add("groups", &self.groups).optional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+
+    #[test]
+    fn channel_shuffle_forward() {
+        let device = Default::default();
+        let module = ChannelShuffleConfig::new(2).init();
+        let input = Tensor::<TestBackend, 3>::from_data(
+            TensorData::from([[[0.0], [1.0], [2.0], [3.0], [4.0], [5.0]]]),
+            &device,
+        );
+
+        let output = module.forward(input);
+
+        output.into_data().assert_eq(
+            &TensorData::from([[[0.0], [3.0], [1.0], [4.0], [2.0], [5.0]]]),
+            false,
+        );
+    }
+
+    #[test]
+    #[should_panic = "must be divisible by the number of groups"]
+    fn channel_shuffle_invalid_groups() {
+        let module = ChannelShuffleConfig::new(4).init();
+        let input = Tensor::<TestBackend, 3>::zeros([1, 6, 2], &Default::default());
+
+        let _output = module.forward(input);
+    }
+
+    #[test]
+    fn display() {
+        let layer = ChannelShuffleConfig::new(3).init();
+
+        assert_eq!(alloc::format!("{layer}"), "ChannelShuffle {groups: 3}");
+    }
+}
@@ -2,6 +2,7 @@ use alloc::format;
 
 use crate as burn;
 
+use crate::amp::autocast;
 use crate::{
     config::Config,
     module::{Content, DisplaySettings, Ignored, Module, ModuleDisplay, Param},
@@ -155,9 +156,9 @@ impl<B: Backend> Conv1d<B> {
             .calculate_padding_1d(length, self.kernel_size, self.stride);
 
         conv1d(
-            input,
-            self.weight.val(),
-            self.bias.as_ref().map(|bias| bias.val()),
+            autocast(input),
+            autocast(self.weight.val()),
+            self.bias.as_ref().map(|bias| autocast(bias.val())),
             ConvOptions::new([self.stride], [padding], [self.dilation], self.groups),
         )
     }
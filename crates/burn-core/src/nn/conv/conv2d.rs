@@ -2,6 +2,7 @@ use alloc::format;
 
 use crate as burn;
 
+use crate::amp::autocast;
 use crate::config::Config;
 use crate::module::{Content, DisplaySettings, Ignored, Module, ModuleDisplay, Param};
 use crate::nn::Initializer;
@@ -173,9 +174,9 @@ impl<B: Backend> Conv2d<B> {
             self.padding
                 .calculate_padding_2d(height_in, width_in, &self.kernel_size, &self.stride);
         conv2d(
-            input,
-            self.weight.val(),
-            self.bias.as_ref().map(|bias| bias.val()),
+            autocast(input),
+            autocast(self.weight.val()),
+            self.bias.as_ref().map(|bias| autocast(bias.val())),
             ConvOptions::new(self.stride, padding, self.dilation, self.groups),
         )
     }
@@ -2,6 +2,7 @@ use alloc::format;
 
 use crate as burn;
 
+use crate::amp::autocast;
 use crate::config::Config;
 use crate::module::{Content, DisplaySettings, Ignored, Module, ModuleDisplay, Param};
 use crate::nn::Initializer;
@@ -163,9 +164,9 @@ impl<B: Backend> Conv3d<B> {
             &self.stride,
         );
         conv3d(
-            input,
-            self.weight.val(),
-            self.bias.as_ref().map(|bias| bias.val()),
+            autocast(input),
+            autocast(self.weight.val()),
+            self.bias.as_ref().map(|bias| autocast(bias.val())),
             ConvOptions::new(self.stride, padding, self.dilation, self.groups),
         )
     }
@@ -2,6 +2,7 @@ use alloc::format;
 
 use crate as burn;
 
+use crate::amp::autocast;
 use crate::config::Config;
 use crate::module::Content;
 use crate::module::DisplaySettings;
@@ -141,9 +142,9 @@ impl<B: Backend> ConvTranspose1d<B> {
     /// - output: `[batch_size, channels_out, length_out]`
     pub fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
         conv_transpose1d(
-            input,
-            self.weight.val(),
-            self.bias.as_ref().map(|bias| bias.val()),
+            autocast(input),
+            autocast(self.weight.val()),
+            self.bias.as_ref().map(|bias| autocast(bias.val())),
             ConvTransposeOptions::new(
                 [self.stride],
                 [self.padding],
@@ -8,6 +8,7 @@ use crate::module::{Content, DisplaySettings, ModuleDisplay};
 use crate::tensor::Int;
 use crate::tensor::Tensor;
 use crate::tensor::backend::Backend;
+use crate::tensor::s;
 
 use crate::tensor::module::embedding;
 
@@ -21,10 +22,20 @@ pub struct EmbeddingConfig {
     /// The type of function used to initialize neural network parameters
     #[config(default = "Initializer::Normal{mean:0.0, std:1.0}")]
     pub initializer: Initializer,
+    /// If specified, the vector at this index is initialized to zeros, and looking it up always
+    /// returns zeros, so it doesn't contribute to the gradient.
+    pub padding_idx: Option<usize>,
+    /// If specified, the looked up vectors with a norm larger than `max_norm` are rescaled to
+    /// have a norm of `max_norm`.
+    pub max_norm: Option<f64>,
 }
 
 /// Lookup table to store a fix number of vectors.
 ///
+/// The gradient of the weights only depends on the looked up rows, it can be computed in
+/// row-indexed form with [embedding_backward_sparse](crate::tensor::module::embedding_backward_sparse)
+/// without materializing a `[n_embedding, d_model]` tensor.
+///
 /// Should be created with [EmbeddingConfig].
 #[derive(Module, Debug)]
 #[module(custom_display)]
@@ -32,6 +43,10 @@ pub struct Embedding<B: Backend> {
     /// The learnable weights of the module of shape `[n_embedding, d_model]` initialized
     /// from a normal distribution `N(0, 1)`.
     pub weight: Param<Tensor<B, 2>>,
+    /// The index of the padding vector, always looked up as zeros.
+    pub padding_idx: Option<usize>,
+    /// The maximum norm of the looked up vectors.
+    pub max_norm: Option<f64>,
 }
 
 impl<B: Backend> ModuleDisplay for Embedding<B> {
@@ -53,14 +68,75 @@ impl<B: Backend> ModuleDisplay for Embedding<B> {
 impl EmbeddingConfig {
     /// Initialize a new [embedding](Embedding) module.
     pub fn init<B: Backend>(&self, device: &B::Device) -> Embedding<B> {
-        let weight = self
-            .initializer
-            .init([self.n_embedding, self.d_model], device);
+        let weight = init_weight(
+            &self.initializer,
+            [self.n_embedding, self.d_model],
+            self.padding_idx,
+            device,
+        );
+
+        Embedding {
+            weight,
+            padding_idx: self.padding_idx,
+            max_norm: self.max_norm,
+        }
+    }
+}
+
+/// Initializes an embedding table, with a zero vector at the padding index.
+pub(crate) fn init_weight<B: Backend>(
+    initializer: &Initializer,
+    shape: [usize; 2],
+    padding_idx: Option<usize>,
+    device: &B::Device,
+) -> Param<Tensor<B, 2>> {
+    let weight = initializer.init(shape, device);
 
-        Embedding { weight }
+    match padding_idx {
+        Some(padding_idx) => {
+            assert!(
+                padding_idx < shape[0],
+                "The padding index ({padding_idx}) must be lower than the number of embeddings ({})",
+                shape[0]
+            );
+            weight.init_mapper(move |tensor| tensor.slice_fill(s![padding_idx, ..], 0.0))
+        }
+        None => weight,
     }
 }
 
+/// Looks up the embedding vectors, applying the padding index and max norm options.
+pub(crate) fn lookup<B: Backend>(
+    weight: Tensor<B, 2>,
+    indices: Tensor<B, 2, Int>,
+    padding_idx: Option<usize>,
+    max_norm: Option<f64>,
+) -> Tensor<B, 3> {
+    let mut output = embedding(weight, indices.clone());
+
+    if let Some(max_norm) = max_norm {
+        let norm = output.clone().powi_scalar(2).sum_dim(2).sqrt();
+        // Like the in-place renormalization of the weights, the scale isn't differentiated.
+        let scale = norm
+            .add_scalar(1e-7)
+            .recip()
+            .mul_scalar(max_norm)
+            .clamp_max(1.0)
+            .detach();
+        output = output * scale;
+    }
+
+    if let Some(padding_idx) = padding_idx {
+        let mask = indices
+            .not_equal_elem(padding_idx as i64)
+            .float()
+            .unsqueeze_dim(2);
+        output = output * mask;
+    }
+
+    output
+}
+
 impl<B: Backend> Embedding<B> {
     /// Applies the forward pass on the input tensor.
     ///
@@ -71,7 +147,7 @@ impl<B: Backend> Embedding<B> {
     /// - input: `[batch_size, seq_length]`
     /// - output: `[batch_size, seq_length, d_model]`
     pub fn forward(&self, input: Tensor<B, 2, Int>) -> Tensor<B, 3> {
-        embedding(self.weight.val(), input)
+        lookup(self.weight.val(), input, self.padding_idx, self.max_norm)
     }
 }
 
@@ -80,6 +156,9 @@ mod tests {
     use super::*;
     use crate::TestBackend;
     use crate::tensor::TensorData;
+
+    #[cfg(feature = "std")]
+    use crate::TestAutodiffBackend;
     use burn_tensor::{Tolerance, ops::FloatElem};
     type FT = FloatElem<TestBackend>;
 
@@ -98,6 +177,92 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn padding_idx_is_zero_without_gradient() {
+        let device = Default::default();
+        let embed = EmbeddingConfig::new(4, 3)
+            .with_padding_idx(Some(1))
+            .init::<TestAutodiffBackend>(&device);
+
+        embed
+            .weight
+            .val()
+            .slice(s![1, ..])
+            .into_data()
+            .assert_eq(&TensorData::zeros::<f32, _>([1, 3]), false);
+
+        let input = Tensor::<TestAutodiffBackend, 2, Int>::from_data([[1, 2], [1, 1]], &device);
+        let output = embed.forward(input);
+        output
+            .clone()
+            .slice(s![.., 0])
+            .into_data()
+            .assert_eq(&TensorData::zeros::<f32, _>([2, 1, 3]), false);
+
+        let grads = output.sum().backward();
+        let grad = embed.weight.grad(&grads).unwrap();
+
+        grad.to_data().assert_eq(
+            &TensorData::from([
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0],
+                [1.0, 1.0, 1.0],
+                [0.0, 0.0, 0.0],
+            ]),
+            false,
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sparse_gradient_only_has_looked_up_rows() {
+        use crate::tensor::module::embedding_backward_sparse;
+
+        let device = Default::default();
+        let embed = EmbeddingConfig::new(5, 2).init::<TestAutodiffBackend>(&device);
+        let input = Tensor::<TestAutodiffBackend, 2, Int>::from_data([[0, 3], [3, 4]], &device);
+        let output_grad = Tensor::<TestAutodiffBackend, 3>::from_data(
+            [[[1.0, 2.0], [3.0, 4.0]], [[5.0, 6.0], [7.0, 8.0]]],
+            &device,
+        );
+
+        let output = embed.forward(input.clone());
+        let grads = (output * output_grad.clone()).sum().backward();
+        let grad = embed.weight.grad(&grads).unwrap();
+
+        let (indices, values) = embedding_backward_sparse(output_grad.inner(), input.inner());
+
+        indices
+            .into_data()
+            .assert_eq(&TensorData::from([0, 3, 3, 4]), false);
+        values.into_data().assert_eq(
+            &TensorData::from([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0], [7.0, 8.0]]),
+            false,
+        );
+        grad.into_data().assert_eq(
+            &TensorData::from([[1.0, 2.0], [0.0, 0.0], [0.0, 0.0], [8.0, 10.0], [7.0, 8.0]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn max_norm_rescales_looked_up_vectors() {
+        let device = Default::default();
+        let mut embed = EmbeddingConfig::new(2, 2)
+            .with_max_norm(Some(1.0))
+            .init::<TestBackend>(&device);
+        embed.weight = Param::from_data([[3.0, 4.0], [0.3, 0.4]], &device);
+
+        let input = Tensor::<TestBackend, 2, Int>::from_data([[0, 1]], &device);
+        let output = embed.forward(input);
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[[0.6, 0.8], [0.3, 0.4]]]),
+            Tolerance::default(),
+        );
+    }
+
     #[test]
     fn display() {
         let config = EmbeddingConfig::new(100, 10);
@@ -0,0 +1,355 @@
+use crate as burn;
+
+use alloc::vec;
+
+use super::Initializer;
+use super::embedding::{init_weight, lookup};
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Ignored, Module, ModuleDisplay, Param};
+use crate::tensor::backend::Backend;
+use crate::tensor::{Bool, Int, Tensor};
+
+/// The reduction applied to the vectors of each bag of an [EmbeddingBag].
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum EmbeddingBagMode {
+    /// Sum of the vectors, optionally weighted by per-sample weights.
+    Sum,
+    /// Mean of the vectors.
+    Mean,
+    /// Element-wise maximum of the vectors.
+    Max,
+}
+
+/// Configuration to create an [EmbeddingBag](EmbeddingBag) layer using the [init function](EmbeddingBagConfig::init).
+#[derive(Config, Debug)]
+pub struct EmbeddingBagConfig {
+    /// The number of embedding vectors.
+    pub n_embedding: usize,
+    /// The size of each vector.
+    pub d_model: usize,
+    /// The reduction applied to the vectors of each bag. Default: mean
+    #[config(default = "EmbeddingBagMode::Mean")]
+    pub mode: EmbeddingBagMode,
+    /// The type of function used to initialize neural network parameters
+    #[config(default = "Initializer::Normal{mean:0.0, std:1.0}")]
+    pub initializer: Initializer,
+    /// If specified, the vector at this index is initialized to zeros and is excluded from the
+    /// reduction of the bags.
+    pub padding_idx: Option<usize>,
+    /// If specified, the looked up vectors with a norm larger than `max_norm` are rescaled to
+    /// have a norm of `max_norm`.
+    pub max_norm: Option<f64>,
+}
+
+/// Computes the sums, means or maxima of bags of embedding vectors.
+///
+/// Bags are either given as a 2D tensor where each row is a bag of the same size, or as a
+/// flat tensor of indices with the offsets at which each bag starts (ragged bags).
+///
+/// Should be created with [EmbeddingBagConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct EmbeddingBag<B: Backend> {
+    /// The learnable weights of the module of shape `[n_embedding, d_model]` initialized
+    /// from a normal distribution `N(0, 1)`.
+    pub weight: Param<Tensor<B, 2>>,
+    /// The reduction applied to the vectors of each bag.
+    pub mode: Ignored<EmbeddingBagMode>,
+    /// The index of the padding vector, excluded from the bags.
+    pub padding_idx: Option<usize>,
+    /// The maximum norm of the looked up vectors.
+    pub max_norm: Option<f64>,
+}
+
+impl<B: Backend> ModuleDisplay for EmbeddingBag<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [n_embedding, d_model] = self.weight.shape().dims();
+        content
+            .add("n_embedding", &n_embedding)
+            .add("d_model", &d_model)
+            .add("mode", &self.mode)
+            .optional()
+    }
+}
+
+impl EmbeddingBagConfig {
+    /// Initialize a new [embedding bag](EmbeddingBag) module.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> EmbeddingBag<B> {
+        let weight = init_weight(
+            &self.initializer,
+            [self.n_embedding, self.d_model],
+            self.padding_idx,
+            device,
+        );
+
+        EmbeddingBag {
+            weight,
+            mode: Ignored(self.mode),
+            padding_idx: self.padding_idx,
+            max_norm: self.max_norm,
+        }
+    }
+}
+
+impl<B: Backend> EmbeddingBag<B> {
+    /// Applies the forward pass on bags of the same size.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[num_bags, bag_size]`
+    /// - per_sample_weights: `[num_bags, bag_size]`, only supported with [sum](EmbeddingBagMode::Sum).
+    /// - output: `[num_bags, d_model]`
+    pub fn forward(
+        &self,
+        input: Tensor<B, 2, Int>,
+        per_sample_weights: Option<Tensor<B, 2>>,
+    ) -> Tensor<B, 2> {
+        let embeddings = lookup(self.weight.val(), input.clone(), None, self.max_norm);
+        let [num_bags, bag_size, d_model] = embeddings.dims();
+        let mask = self.padding_mask(input);
+
+        match self.mode.0 {
+            EmbeddingBagMode::Max => {
+                self.check_no_weights(per_sample_weights.is_some());
+                match mask {
+                    Some(mask) => max_reduce(embeddings, mask, [num_bags, bag_size, d_model]),
+                    None => embeddings.max_dim(1).squeeze(1),
+                }
+            }
+            mode => {
+                let weights = match mask {
+                    Some(mask) => mask.float(),
+                    None => Tensor::ones([num_bags, bag_size], &embeddings.device()),
+                };
+                let weights = self.weights(mode, weights, per_sample_weights);
+                // [num_bags, 1, bag_size] @ [num_bags, bag_size, d_model]
+                weights.unsqueeze_dim::<3>(1).matmul(embeddings).squeeze(1)
+            }
+        }
+    }
+
+    /// Applies the forward pass on bags of different sizes.
+    ///
+    /// The indices of all bags are concatenated in `input`, and `offsets` holds the position at
+    /// which each bag starts. Bag `i` spans `input[offsets[i]..offsets[i + 1]]`, the last bag
+    /// ends with the input. Empty bags are reduced to zeros.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[num_indices]`
+    /// - offsets: `[num_bags]`
+    /// - per_sample_weights: `[num_indices]`, only supported with [sum](EmbeddingBagMode::Sum).
+    /// - output: `[num_bags, d_model]`
+    pub fn forward_offsets(
+        &self,
+        input: Tensor<B, 1, Int>,
+        offsets: Tensor<B, 1, Int>,
+        per_sample_weights: Option<Tensor<B, 1>>,
+    ) -> Tensor<B, 2> {
+        let device = input.device();
+        let [num_indices] = input.dims();
+        let [num_bags] = offsets.dims();
+        let shape = [num_bags, num_indices];
+
+        let input = input.unsqueeze::<2>();
+        let embeddings =
+            lookup(self.weight.val(), input.clone(), None, self.max_norm).squeeze::<2>(0);
+        let [_, d_model] = embeddings.dims();
+
+        // Bag `i` ends where bag `i + 1` starts, the last one ends with the input.
+        let mut ends = vec![Tensor::from_ints([num_indices as i64], &device)];
+        if num_bags > 1 {
+            ends.insert(0, offsets.clone().narrow(0, 1, num_bags - 1));
+        }
+        let ends = Tensor::cat(ends, 0);
+
+        // Membership of each index to each bag, with shape [num_bags, num_indices].
+        let positions = Tensor::<B, 1, Int>::arange(0..num_indices as i64, &device)
+            .unsqueeze::<2>()
+            .expand(shape);
+        let mut mask = positions
+            .clone()
+            .greater_equal(offsets.unsqueeze_dim(1).expand(shape))
+            .bool_and(positions.lower(ends.unsqueeze_dim(1).expand(shape)));
+        if let Some(padding_mask) = self.padding_mask(input) {
+            mask = mask.bool_and(padding_mask.expand(shape));
+        }
+
+        match self.mode.0 {
+            EmbeddingBagMode::Max => {
+                self.check_no_weights(per_sample_weights.is_some());
+                let shape = [num_bags, num_indices, d_model];
+                max_reduce(embeddings.unsqueeze::<3>().expand(shape), mask, shape)
+            }
+            mode => {
+                let per_sample_weights = per_sample_weights.map(|weights| weights.unsqueeze());
+                let weights = self.weights(mode, mask.float(), per_sample_weights);
+                // [num_bags, num_indices] @ [num_indices, d_model]
+                weights.matmul(embeddings)
+            }
+        }
+    }
+
+    /// The mask of the indices that aren't the padding index.
+    fn padding_mask(&self, input: Tensor<B, 2, Int>) -> Option<Tensor<B, 2, Bool>> {
+        self.padding_idx
+            .map(|padding_idx| input.not_equal_elem(padding_idx as i64))
+    }
+
+    /// The weights of the indices of each bag for the sum and mean reductions, from the weights
+    /// of the indices that are part of each bag.
+    fn weights(
+        &self,
+        mode: EmbeddingBagMode,
+        weights: Tensor<B, 2>,
+        per_sample_weights: Option<Tensor<B, 2>>,
+    ) -> Tensor<B, 2> {
+        match mode {
+            EmbeddingBagMode::Sum => match per_sample_weights {
+                Some(per_sample_weights) => weights * per_sample_weights,
+                None => weights,
+            },
+            _ => {
+                self.check_no_weights(per_sample_weights.is_some());
+                let count = weights.clone().sum_dim(1).clamp_min(1.0);
+                weights / count
+            }
+        }
+    }
+
+    fn check_no_weights(&self, has_weights: bool) {
+        assert!(
+            !has_weights,
+            "Per-sample weights are only supported with the sum mode, got {:?}",
+            self.mode.0
+        );
+    }
+}
+
+/// Element-wise maximum of the masked vectors of each bag, empty bags are reduced to zeros.
+fn max_reduce<B: Backend>(
+    embeddings: Tensor<B, 3>,
+    mask: Tensor<B, 2, Bool>,
+    shape: [usize; 3],
+) -> Tensor<B, 2> {
+    let empty = mask.clone().any_dim(1).bool_not();
+    let ignored = mask.bool_not().unsqueeze_dim::<3>(2).expand(shape);
+
+    embeddings
+        .mask_fill(ignored, f32::NEG_INFINITY)
+        .max_dim(1)
+        .squeeze::<2>(1)
+        .mask_fill(empty.expand([shape[0], shape[2]]), 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn embedding_bag(
+        mode: EmbeddingBagMode,
+        padding_idx: Option<usize>,
+    ) -> EmbeddingBag<TestBackend> {
+        let device = Default::default();
+        let mut module = EmbeddingBagConfig::new(4, 2)
+            .with_mode(mode)
+            .with_padding_idx(padding_idx)
+            .init::<TestBackend>(&device);
+        module.weight =
+            Param::from_data([[1.0, -1.0], [2.0, 4.0], [3.0, 0.0], [-2.0, 5.0]], &device);
+        module
+    }
+
+    #[test]
+    fn forward_sum_with_per_sample_weights() {
+        let device = Default::default();
+        let module = embedding_bag(EmbeddingBagMode::Sum, None);
+        let input = Tensor::<TestBackend, 2, Int>::from_data([[0, 1], [2, 2]], &device);
+        let weights = Tensor::<TestBackend, 2>::from_data([[1.0, 0.5], [2.0, -1.0]], &device);
+
+        let output = module.forward(input, Some(weights));
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[2.0, 1.0], [3.0, 0.0]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn forward_mean_excludes_padding() {
+        let device = Default::default();
+        let module = embedding_bag(EmbeddingBagMode::Mean, Some(3));
+        let input = Tensor::<TestBackend, 2, Int>::from_data([[0, 1, 3], [3, 3, 3]], &device);
+
+        let output = module.forward(input, None);
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[1.5, 1.5], [0.0, 0.0]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn forward_offsets_max() {
+        let device = Default::default();
+        let module = embedding_bag(EmbeddingBagMode::Max, None);
+        let input = Tensor::<TestBackend, 1, Int>::from_data([0, 1, 2, 3, 1], &device);
+        let offsets = Tensor::<TestBackend, 1, Int>::from_data([0, 3, 3], &device);
+
+        let output = module.forward_offsets(input, offsets, None);
+
+        // Bags: [0, 1, 2], [] and [3, 1]
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[3.0, 4.0], [0.0, 0.0], [2.0, 5.0]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn forward_offsets_should_match_forward() {
+        let device = Default::default();
+        let module = embedding_bag(EmbeddingBagMode::Mean, None);
+        let input = Tensor::<TestBackend, 2, Int>::from_data([[0, 1], [2, 3], [1, 1]], &device);
+        let offsets = Tensor::<TestBackend, 1, Int>::from_data([0, 2, 4], &device);
+
+        let expected = module.forward(input.clone(), None);
+        let output = module.forward_offsets(input.reshape([6]), offsets, None);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    #[should_panic = "Per-sample weights are only supported with the sum mode"]
+    fn per_sample_weights_require_sum_mode() {
+        let device = Default::default();
+        let module = embedding_bag(EmbeddingBagMode::Mean, None);
+        let input = Tensor::<TestBackend, 2, Int>::from_data([[0, 1]], &device);
+        let weights = Tensor::<TestBackend, 2>::ones([1, 2], &device);
+
+        let _output = module.forward(input, Some(weights));
+    }
+
+    #[test]
+    fn display() {
+        let config = EmbeddingBagConfig::new(100, 10);
+        let embed = config.init::<TestBackend>(&Default::default());
+
+        assert_eq!(
+            alloc::format!("{embed}"),
+            "EmbeddingBag {n_embedding: 100, d_model: 10, mode: Mean, params: 1000}"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use burn_tensor::module::linear;
 
 use crate as burn;
 
+use crate::amp::autocast;
 use crate::config::Config;
 use crate::module::Param;
 use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
@@ -124,12 +125,13 @@ impl<B: Backend> Linear<B> {
     ///
     /// # Returns
     ///
-    /// The transformed tensor of shape `[..., d_output]`.
+    /// The transformed tensor of shape `[..., d_output]`, with the reduced precision type when
+    /// [autocasting](crate::amp::autocast) is enabled.
     pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
         linear(
-            input,
-            self.weight.val(),
-            self.bias.as_ref().map(|b| b.val()),
+            autocast(input),
+            autocast(self.weight.val()),
+            self.bias.as_ref().map(|b| autocast(b.val())),
         )
     }
 }
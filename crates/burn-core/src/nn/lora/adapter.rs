@@ -0,0 +1,139 @@
+use burn_tensor::module::linear;
+
+use crate as burn;
+
+use crate::amp::autocast;
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay, Param};
+use crate::nn::{Dropout, DropoutConfig, Initializer};
+use crate::tensor::{Tensor, backend::Backend};
+
+/// Configuration to create a [LoRA adapter](LoraAdapter), either directly with the
+/// [init function](LoraConfig::init) or by wrapping the [linear layers](crate::nn::Linear) of a
+/// module in [adapted layers](super::LoraLinear).
+#[derive(Config, Debug)]
+pub struct LoraConfig {
+    /// The rank of the low-rank update.
+    pub rank: usize,
+    /// The scaling numerator, the update is multiplied by `alpha / rank`.
+    pub alpha: f64,
+    /// The probability of dropping elements of the input of the adapter. Default: 0.0
+    #[config(default = 0.0)]
+    pub dropout: f64,
+    /// The initializer of the down projection, the up projection always starts at zero so the
+    /// adapted layer initially computes the same output as the base layer.
+    #[config(
+        default = "Initializer::KaimingUniform{gain:1.0/num_traits::Float::sqrt(3.0), fan_out_only:false}"
+    )]
+    pub initializer: Initializer,
+}
+
+/// Low-rank adaptation (LoRA) of a linear transformation, as described in
+/// [LoRA: Low-Rank Adaptation of Large Language Models](https://arxiv.org/abs/2106.09685).
+///
+/// The adapter computes the update `scaling * dropout(X) A B` added to the output of the base
+/// layer, where `A` has shape `[d_input, rank]` and `B` has shape `[rank, d_output]`.
+///
+/// Should be created with [LoraConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct LoraAdapter<B: Backend> {
+    /// Down projection of shape `[d_input, rank]`.
+    pub lora_a: Param<Tensor<B, 2>>,
+    /// Up projection of shape `[rank, d_output]`.
+    pub lora_b: Param<Tensor<B, 2>>,
+    /// Dropout applied to the input of the adapter.
+    pub dropout: Dropout,
+    /// The factor `alpha / rank` applied to the update.
+    pub scaling: f64,
+    /// Whether the update is currently merged into the weight of the base layer.
+    pub merged: bool,
+}
+
+impl LoraConfig {
+    /// Initialize a new [LoRA adapter](LoraAdapter) for a linear transformation from `d_input`
+    /// to `d_output` features.
+    ///
+    /// # Panics
+    ///
+    /// If the rank is 0.
+    pub fn init<B: Backend>(
+        &self,
+        d_input: usize,
+        d_output: usize,
+        device: &B::Device,
+    ) -> LoraAdapter<B> {
+        assert!(self.rank > 0, "The rank should be positive");
+
+        let lora_a = self.initializer.init_with(
+            [d_input, self.rank],
+            Some(d_input),
+            Some(self.rank),
+            device,
+        );
+        let lora_b = Initializer::Zeros.init([self.rank, d_output], device);
+
+        LoraAdapter {
+            lora_a,
+            lora_b,
+            dropout: DropoutConfig::new(self.dropout).init(),
+            scaling: self.alpha / self.rank as f64,
+            merged: false,
+        }
+    }
+}
+
+impl<B: Backend> LoraAdapter<B> {
+    /// Computes the update added to the output of the base layer.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., d_input]`
+    /// - output: `[..., d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let input = self.dropout.forward(input);
+        let hidden = linear(autocast(input), autocast(self.lora_a.val()), None);
+
+        linear(hidden, autocast(self.lora_b.val()), None).mul_scalar(self.scaling)
+    }
+
+    /// The update of the weight of the base layer, `scaling * A B` of shape
+    /// `[d_input, d_output]`.
+    pub fn delta_weight(&self) -> Tensor<B, 2> {
+        self.lora_a
+            .val()
+            .matmul(self.lora_b.val())
+            .mul_scalar(self.scaling)
+    }
+
+    /// The rank of the update.
+    pub fn rank(&self) -> usize {
+        self.lora_a.dims()[1]
+    }
+}
+
+impl<B: Backend> ModuleDisplay for LoraAdapter<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        content
+            .add("rank", &self.rank())
+            .add("scaling", &self.scaling)
+            .add("dropout", &self.dropout.prob)
+            .add("merged", &self.merged)
+            .optional()
+    }
+}
+
+/// Whether a tensor belongs to a [LoRA adapter](LoraAdapter), given its path and the path of the
+/// types of its containers.
+///
+/// Can be used as a predicate of the path filters of `burn-store` to save or load the adapters of
+/// a module without the weights of the base layers.
+pub fn is_adapter_tensor(_path: &str, container_path: &str) -> bool {
+    container_path.rsplit('.').next() == Some("LoraAdapter")
+}
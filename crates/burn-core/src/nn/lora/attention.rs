@@ -0,0 +1,109 @@
+use crate as burn;
+
+use super::linear::{adapted_forward, set_merged};
+use super::{LoraAdapter, LoraConfig};
+use crate::module::Module;
+use crate::nn::attention::{MhaInput, MhaOutput, MhaProjection, MultiHeadAttention};
+use crate::tensor::backend::Backend;
+
+/// A [multi-head attention](MultiHeadAttention) layer with optional [LoRA adapters](LoraAdapter)
+/// on its query, key, value and output projections.
+///
+/// Should be created with [LoraConfig::init_attention], or by [injecting](LoraConfig::inject)
+/// adapters in a module.
+#[derive(Module, Debug)]
+pub struct LoraMultiHeadAttention<B: Backend> {
+    /// The base layer.
+    pub base: MultiHeadAttention<B>,
+    /// The adapter of the query projection, if any.
+    pub query: Option<LoraAdapter<B>>,
+    /// The adapter of the key projection, if any.
+    pub key: Option<LoraAdapter<B>>,
+    /// The adapter of the value projection, if any.
+    pub value: Option<LoraAdapter<B>>,
+    /// The adapter of the output projection, if any.
+    pub output: Option<LoraAdapter<B>>,
+}
+
+impl LoraConfig {
+    /// Wraps the multi-head attention layer with a new [LoRA adapter](LoraAdapter) on each of its
+    /// projections.
+    ///
+    /// Use [inject](LoraConfig::inject) to only adapt some of the projections.
+    pub fn init_attention<B: Backend>(
+        &self,
+        base: MultiHeadAttention<B>,
+    ) -> LoraMultiHeadAttention<B> {
+        LoraMultiHeadAttention {
+            query: Some(self.init_adapter(&base.query)),
+            key: Some(self.init_adapter(&base.key)),
+            value: Some(self.init_adapter(&base.value)),
+            output: Some(self.init_adapter(&base.output)),
+            base,
+        }
+    }
+}
+
+impl<B: Backend> LoraMultiHeadAttention<B> {
+    /// Applies the forward pass on the input tensors.
+    ///
+    /// See [MultiHeadAttention::forward] for the shapes of the tensors. The cached forward pass
+    /// isn't supported, the layer should be converted with
+    /// [into_attention](Self::into_attention) for inference.
+    pub fn forward(&self, input: MhaInput<B>) -> MhaOutput<B> {
+        self.base.forward_projected(input, |projection, x| {
+            adapted_forward(
+                self.base.projection(projection),
+                self.adapter(projection),
+                x,
+            )
+        })
+    }
+
+    /// Merges the update of the adapters into the weights of the projections.
+    ///
+    /// See [LoraLinear::merge](super::LoraLinear::merge).
+    pub fn merge(self) -> Self {
+        self.set_merged(true)
+    }
+
+    /// Removes the update of the merged adapters from the weights of the projections.
+    pub fn unmerge(self) -> Self {
+        self.set_merged(false)
+    }
+
+    /// Merges the adapters into the projections, and returns the base layer.
+    pub fn into_attention(self) -> MultiHeadAttention<B> {
+        self.merge().base
+    }
+
+    fn adapter(&self, projection: MhaProjection) -> Option<&LoraAdapter<B>> {
+        match projection {
+            MhaProjection::Query => self.query.as_ref(),
+            MhaProjection::Key => self.key.as_ref(),
+            MhaProjection::Value => self.value.as_ref(),
+            MhaProjection::Output => self.output.as_ref(),
+        }
+    }
+
+    fn set_merged(self, merged: bool) -> Self {
+        let mut base = self.base;
+
+        let (query, query_adapter) = set_merged(base.query, self.query, merged);
+        let (key, key_adapter) = set_merged(base.key, self.key, merged);
+        let (value, value_adapter) = set_merged(base.value, self.value, merged);
+        let (output, output_adapter) = set_merged(base.output, self.output, merged);
+        base.query = query;
+        base.key = key;
+        base.value = value;
+        base.output = output;
+
+        Self {
+            base,
+            query: query_adapter,
+            key: key_adapter,
+            value: value_adapter,
+            output: output_adapter,
+        }
+    }
+}
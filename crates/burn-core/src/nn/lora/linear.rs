@@ -0,0 +1,210 @@
+use crate as burn;
+
+use super::{LoraAdapter, LoraConfig};
+use crate::module::{Module, Param};
+use crate::nn::Linear;
+use crate::tensor::{Tensor, backend::Backend};
+
+/// A [linear layer](Linear) with an optional [LoRA adapter](LoraAdapter) whose update is added
+/// to the output of the layer.
+///
+/// Should be created with [LoraConfig::init_linear], or by [injecting](LoraConfig::inject)
+/// adapters in a module.
+#[derive(Module, Debug)]
+pub struct LoraLinear<B: Backend> {
+    /// The base layer.
+    pub base: Linear<B>,
+    /// The adapter of the base layer, if any.
+    pub adapter: Option<LoraAdapter<B>>,
+}
+
+impl LoraConfig {
+    /// Wraps the linear layer with a new [LoRA adapter](LoraAdapter).
+    ///
+    /// The parameters of the base layer keep requiring gradients, they should be
+    /// [frozen](Module::freeze) to only train the adapter.
+    pub fn init_linear<B: Backend>(&self, base: Linear<B>) -> LoraLinear<B> {
+        let adapter = self.init_adapter(&base);
+
+        LoraLinear::new(base, Some(adapter))
+    }
+
+    /// Initialize a new [LoRA adapter](LoraAdapter) matching the shape of the linear layer.
+    pub(crate) fn init_adapter<B: Backend>(&self, linear: &Linear<B>) -> LoraAdapter<B> {
+        let [d_input, d_output] = linear.weight.dims();
+
+        self.init(d_input, d_output, &linear.weight.device())
+    }
+}
+
+impl<B: Backend> LoraLinear<B> {
+    /// Creates a new layer from its base layer and its adapter, if any.
+    pub fn new(base: Linear<B>, adapter: Option<LoraAdapter<B>>) -> Self {
+        Self { base, adapter }
+    }
+
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., d_input]`
+    /// - output: `[..., d_output]`
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        adapted_forward(&self.base, self.adapter.as_ref(), input)
+    }
+
+    /// Merges the update of the adapter into the weight of the base layer, so the layer computes
+    /// its adapted output without the cost of the adapter.
+    ///
+    /// The adapter is kept, and its update can be removed from the weight with
+    /// [unmerge](Self::unmerge). Since whether an adapter is merged isn't part of the records, a
+    /// merged layer should be saved without its adapter, or converted with
+    /// [into_linear](Self::into_linear).
+    pub fn merge(self) -> Self {
+        let (base, adapter) = set_merged(self.base, self.adapter, true);
+
+        Self { base, adapter }
+    }
+
+    /// Removes the update of the adapter from the weight of the base layer if it was
+    /// [merged](Self::merge).
+    pub fn unmerge(self) -> Self {
+        let (base, adapter) = set_merged(self.base, self.adapter, false);
+
+        Self { base, adapter }
+    }
+
+    /// Merges the adapter into the base layer, and returns the base layer.
+    pub fn into_linear(self) -> Linear<B> {
+        self.merge().base
+    }
+}
+
+/// Applies the base layer, adding the update of the adapter unless it's merged.
+pub(crate) fn adapted_forward<B: Backend, const D: usize>(
+    base: &Linear<B>,
+    adapter: Option<&LoraAdapter<B>>,
+    input: Tensor<B, D>,
+) -> Tensor<B, D> {
+    match adapter.filter(|adapter| !adapter.merged) {
+        Some(adapter) => base.forward(input.clone()) + adapter.forward(input),
+        None => base.forward(input),
+    }
+}
+
+/// Sets the merge state of the adapter, shifting the weight of the base layer by the update of the
+/// adapter when the state changes.
+pub(crate) fn set_merged<B: Backend>(
+    mut base: Linear<B>,
+    adapter: Option<LoraAdapter<B>>,
+    merged: bool,
+) -> (Linear<B>, Option<LoraAdapter<B>>) {
+    let Some(mut adapter) = adapter else {
+        return (base, None);
+    };
+
+    if adapter.merged != merged {
+        let delta = adapter.delta_weight().detach();
+        let delta = if merged { delta } else { delta.neg() };
+
+        base.weight = shift_weight(base.weight, delta);
+        adapter.merged = merged;
+    }
+
+    (base, Some(adapter))
+}
+
+fn shift_weight<B: Backend>(
+    weight: Param<Tensor<B, 2>>,
+    delta: Tensor<B, 2>,
+) -> Param<Tensor<B, 2>> {
+    weight.map(|weight| {
+        let require_grad = weight.is_require_grad();
+        let delta = delta.to_device(&weight.device());
+
+        weight.add(delta).detach().set_require_grad(require_grad)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::nn::LinearConfig;
+    use crate::tensor::Distribution;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn randomize(mut layer: LoraLinear<TestBackend>) -> LoraLinear<TestBackend> {
+        layer.adapter = layer.adapter.map(|mut adapter| {
+            adapter.lora_b = adapter
+                .lora_b
+                .map(|tensor| tensor.random_like(Distribution::Default));
+            adapter
+        });
+        layer
+    }
+
+    #[test]
+    fn init_linear_should_keep_the_output_unchanged() {
+        let device = Default::default();
+        let base = LinearConfig::new(8, 4).init::<TestBackend>(&device);
+        let input = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let expected = base.forward(input.clone());
+
+        let layer = LoraConfig::new(2, 4.0).init_linear(base);
+
+        assert_eq!(layer.adapter.as_ref().unwrap().rank(), 2);
+        layer
+            .forward(input)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn merge_should_keep_the_output_and_unmerge_should_restore_weights() {
+        let device = Default::default();
+        let base = LinearConfig::new(8, 4).init::<TestBackend>(&device);
+        let weight = base.weight.val();
+        let layer = randomize(LoraConfig::new(2, 4.0).init_linear(base));
+        let input = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let expected = layer.forward(input.clone());
+
+        let layer = layer.merge();
+
+        assert!(layer.adapter.as_ref().unwrap().merged);
+        layer.base.weight.val().into_data().assert_approx_eq::<FT>(
+            &weight
+                .clone()
+                .add(layer.adapter.as_ref().unwrap().delta_weight())
+                .into_data(),
+            Tolerance::default(),
+        );
+        layer
+            .forward(input.clone())
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.clone().into_data(), Tolerance::default());
+
+        // Merging twice doesn't add the update again.
+        let layer = layer.merge().unmerge();
+
+        assert!(!layer.adapter.as_ref().unwrap().merged);
+        layer
+            .base
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(&weight.into_data(), Tolerance::default());
+        layer
+            .forward(input.clone())
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.clone().into_data(), Tolerance::default());
+
+        layer
+            .into_linear()
+            .forward(input)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+}
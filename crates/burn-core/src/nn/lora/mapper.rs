@@ -0,0 +1,329 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{LoraAdapter, LoraConfig, LoraLinear, LoraMultiHeadAttention};
+use crate::module::Module;
+use crate::nn::Linear;
+use crate::nn::attention::MultiHeadAttention;
+use crate::optim::ParamPathFilter;
+use crate::tensor::backend::Backend;
+
+/// Module whose [linear layers](Linear) can be wrapped with [LoRA adapters](super::LoraAdapter)
+/// by a [LoRA mapper](LoraMapper).
+///
+/// The trait is implemented for the linear and [multi-head attention](MultiHeadAttention) layers,
+/// and for the [transformer](crate::nn::transformer) modules. It should be implemented for the
+/// modules to fine-tune by mapping each of their fields with [LoraMapper::map].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// impl<B: Backend> LoraModule<B> for Encoder<B> {
+///     type Adapted = LoraEncoder<B>;
+///
+///     fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+///         LoraEncoder {
+///             attn: mapper.map("attn", "Encoder", self.attn),
+///             norm: self.norm,
+///         }
+///     }
+/// }
+/// ```
+pub trait LoraModule<B: Backend>: Module<B> {
+    /// The module with its linear layers wrapped with adapters.
+    type Adapted: Module<B>;
+
+    /// Wraps the linear layers of the module, adding an adapter to the layers selected by the
+    /// mapper.
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted;
+}
+
+/// Maps the modules implementing [LoraModule], adding a new [LoRA adapter](super::LoraAdapter)
+/// to the linear layers whose path is selected by a [path filter](ParamPathFilter).
+///
+/// The paths are built the same way as the paths of the parameters, from the names of the fields
+/// given to [map](Self::map) (e.g. `encoder.layers.0.attn.query`).
+pub struct LoraMapper<'a, F: ParamPathFilter + ?Sized> {
+    config: &'a LoraConfig,
+    filter: &'a F,
+    path: Vec<String>,
+    container_path: Vec<String>,
+}
+
+impl<'a, F: ParamPathFilter + ?Sized> LoraMapper<'a, F> {
+    /// Creates a new mapper creating the adapters with the given configuration.
+    pub fn new(config: &'a LoraConfig, filter: &'a F) -> Self {
+        Self {
+            config,
+            filter,
+            path: Vec::new(),
+            container_path: Vec::new(),
+        }
+    }
+
+    /// Maps the field `name` of a module of type `container_type`.
+    pub fn map<B, M>(&mut self, name: &str, container_type: &str, module: M) -> M::Adapted
+    where
+        B: Backend,
+        M: LoraModule<B>,
+    {
+        self.enter(name, container_type);
+        let module = module.map_lora(self);
+        self.exit();
+
+        module
+    }
+
+    /// Wraps the linear layer at the current path, adding an adapter if the path is selected.
+    pub fn map_linear<B: Backend>(&mut self, linear: Linear<B>) -> LoraLinear<B> {
+        let adapter = self
+            .is_selected()
+            .then(|| self.config.init_adapter(&linear));
+
+        LoraLinear::new(linear, adapter)
+    }
+
+    /// Creates an adapter for the linear layer in the field `name` of a module of type
+    /// `container_type`, if its path is selected.
+    pub(super) fn adapter<B: Backend>(
+        &mut self,
+        name: &str,
+        container_type: &str,
+        linear: &Linear<B>,
+    ) -> Option<LoraAdapter<B>> {
+        self.enter(name, container_type);
+        let adapter = self.is_selected().then(|| self.config.init_adapter(linear));
+        self.exit();
+
+        adapter
+    }
+
+    pub(super) fn enter(&mut self, name: &str, container_type: &str) {
+        self.path.push(name.to_string());
+        self.container_path.push(container_type.to_string());
+    }
+
+    pub(super) fn exit(&mut self) {
+        self.path.pop();
+        self.container_path.pop();
+    }
+
+    /// Whether the current path is selected by the filter.
+    pub fn is_selected(&self) -> bool {
+        self.filter
+            .matches(&self.path.join("."), &self.container_path.join("."))
+    }
+}
+
+impl LoraConfig {
+    /// Wraps the linear layers of the module, adding a new adapter to the layers whose path is
+    /// selected by the filter.
+    ///
+    /// The filter receives the dot-separated path of the linear layers (e.g.
+    /// `encoder.layers.0.attn.query`). The parameters of the base layers keep requiring
+    /// gradients, they should be [frozen](Module::freeze) to only train the adapters.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let model = model.freeze(&GlobFilter::new(["**"]));
+    /// let model = LoraConfig::new(8, 16.0).inject(model, &GlobFilter::new(["**.query", "**.value"]));
+    /// ```
+    pub fn inject<B, M, F>(&self, module: M, filter: &F) -> M::Adapted
+    where
+        B: Backend,
+        M: LoraModule<B>,
+        F: ParamPathFilter + ?Sized,
+    {
+        module.map_lora(&mut LoraMapper::new(self, filter))
+    }
+}
+
+impl<B: Backend> LoraModule<B> for Linear<B> {
+    type Adapted = LoraLinear<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        mapper.map_linear(self)
+    }
+}
+
+impl<B: Backend> LoraModule<B> for MultiHeadAttention<B> {
+    type Adapted = LoraMultiHeadAttention<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        const CONTAINER: &str = "MultiHeadAttention";
+
+        LoraMultiHeadAttention {
+            query: mapper.adapter("query", CONTAINER, &self.query),
+            key: mapper.adapter("key", CONTAINER, &self.key),
+            value: mapper.adapter("value", CONTAINER, &self.value),
+            output: mapper.adapter("output", CONTAINER, &self.output),
+            base: self,
+        }
+    }
+}
+
+impl<B: Backend, M: LoraModule<B>> LoraModule<B> for Option<M> {
+    type Adapted = Option<M::Adapted>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        self.map(|module| module.map_lora(mapper))
+    }
+}
+
+impl<B: Backend, M: LoraModule<B>> LoraModule<B> for Vec<M> {
+    type Adapted = Vec<M::Adapted>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        self.into_iter()
+            .enumerate()
+            .map(|(index, module)| mapper.map(&index.to_string(), "Vec", module))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as burn;
+    use crate::TestBackend;
+    use crate::module::GlobFilter;
+    use crate::nn::LinearConfig;
+    use crate::nn::attention::{MhaInput, MultiHeadAttentionConfig};
+    use crate::nn::lora::is_adapter_tensor;
+    use crate::tensor::{Distribution, Tensor};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[derive(Module, Debug)]
+    struct Model<B: Backend> {
+        attn: MultiHeadAttention<B>,
+        head: Linear<B>,
+    }
+
+    #[derive(Module, Debug)]
+    struct LoraModel<B: Backend> {
+        attn: LoraMultiHeadAttention<B>,
+        head: LoraLinear<B>,
+    }
+
+    impl<B: Backend> LoraModule<B> for Model<B> {
+        type Adapted = LoraModel<B>;
+
+        fn map_lora<F: ParamPathFilter + ?Sized>(
+            self,
+            mapper: &mut LoraMapper<F>,
+        ) -> Self::Adapted {
+            LoraModel {
+                attn: mapper.map("attn", "Model", self.attn),
+                head: mapper.map("head", "Model", self.head),
+            }
+        }
+    }
+
+    impl<B: Backend> Model<B> {
+        fn new(device: &B::Device) -> Self {
+            Self {
+                attn: MultiHeadAttentionConfig::new(8, 2).init(device),
+                head: LinearConfig::new(8, 4).init(device),
+            }
+        }
+
+        fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
+            let context = self.attn.forward(MhaInput::self_attn(input)).context;
+            self.head.forward(context)
+        }
+    }
+
+    impl<B: Backend> LoraModel<B> {
+        fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
+            let context = self.attn.forward(MhaInput::self_attn(input)).context;
+            self.head.forward(context)
+        }
+
+        fn merge(self) -> Self {
+            Self {
+                attn: self.attn.merge(),
+                head: self.head.merge(),
+            }
+        }
+    }
+
+    #[test]
+    fn inject_should_add_adapters_to_selected_layers() {
+        let device = Default::default();
+        let model = Model::<TestBackend>::new(&device);
+        let num_params = model.num_params();
+
+        let model =
+            LoraConfig::new(2, 4.0).inject(model, &GlobFilter::new(["attn.query", "attn.value"]));
+
+        assert_eq!(model.attn.query.as_ref().unwrap().rank(), 2);
+        assert_eq!(model.attn.value.as_ref().unwrap().scaling, 2.0);
+        assert!(model.attn.key.is_none());
+        assert!(model.attn.output.is_none());
+        assert!(model.head.adapter.is_none());
+        assert_eq!(model.num_params(), num_params + 2 * (8 * 2 + 2 * 8));
+    }
+
+    #[test]
+    fn inject_should_keep_the_output_unchanged() {
+        let device = Default::default();
+        let model = Model::<TestBackend>::new(&device);
+        let input = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let expected = model.forward(input.clone());
+
+        let model = LoraConfig::new(4, 4.0).inject(model, &GlobFilter::new(["**"]));
+
+        model
+            .forward(input)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn merged_model_should_keep_the_output() {
+        let device = Default::default();
+        let mut model = LoraConfig::new(2, 4.0)
+            .inject(Model::<TestBackend>::new(&device), &GlobFilter::new(["**"]));
+        model.attn.value = model.attn.value.map(|mut adapter| {
+            adapter.lora_b = adapter
+                .lora_b
+                .map(|tensor| tensor.random_like(Distribution::Default));
+            adapter
+        });
+        let input = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let expected = model.forward(input.clone());
+
+        let model = model.merge();
+
+        model
+            .forward(input.clone())
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.clone().into_data(), Tolerance::default());
+
+        // The merged layers compute the adapted output without their adapters.
+        let attn = model.attn.into_attention();
+        let head = model.head.into_linear();
+        let context = attn.forward(MhaInput::self_attn(input)).context;
+
+        head.forward(context)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn adapter_tensors_should_be_selected_by_predicate() {
+        assert!(is_adapter_tensor(
+            "attn.query.lora_a",
+            "Model.LoraMultiHeadAttention.LoraAdapter"
+        ));
+        assert!(!is_adapter_tensor(
+            "attn.base.query.weight",
+            "Model.LoraMultiHeadAttention.MultiHeadAttention.Linear"
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+//! Low-rank adaptation (LoRA) of the [linear layers](crate::nn::Linear) of a module.
+//!
+//! The linear layers are wrapped in [adapted layers](LoraLinear) by a [LoRA mapper](LoraMapper),
+//! which adds an adapter to the layers selected by their path. The modules to fine-tune implement
+//! [LoraModule] to declare their adapted version, mapping each of their fields explicitly. The
+//! [transformer](crate::nn::transformer) modules are mapped to adapted modules sharing their
+//! forward pass (e.g. [LoraTransformerEncoder]).
+//!
+//! Once trained, the adapters can be [merged](LoraLinear::merge) into the weights of the layers.
+//! Since the adapters are regular parameters of the module, only their tensors can be saved and
+//! loaded with the [adapter tensor](is_adapter_tensor) predicate of the path filters of
+//! `burn-store`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let model = model.freeze(&GlobFilter::new(["**"]));
+//! let model = LoraConfig::new(8, 16.0)
+//!     .with_dropout(0.1)
+//!     .inject(model, &GlobFilter::new(["**.attn.query", "**.attn.value"]));
+//!
+//! // Train the model, then save the adapters only.
+//! let mut store = SafetensorsStore::from_file("adapters.safetensors")
+//!     .with_predicate(lora::is_adapter_tensor);
+//! model.collect_to(&mut store)?;
+//! ```
+
+mod adapter;
+mod attention;
+mod linear;
+mod mapper;
+mod transformer;
+
+pub use adapter::*;
+pub use attention::*;
+pub use linear::*;
+pub use mapper::*;
+pub use transformer::*;
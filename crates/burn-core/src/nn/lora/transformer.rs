@@ -0,0 +1,657 @@
+use crate as burn;
+
+use alloc::{format, vec::Vec};
+
+use super::linear::{adapted_forward, set_merged};
+use super::{LoraAdapter, LoraMapper, LoraModule, LoraMultiHeadAttention};
+use crate::module::{ForwardScope, Module};
+use crate::nn::transformer::{
+    DecoderLayerResiduals, DecoderSelfAttention, EncoderAttention, EncoderLayerResiduals,
+    FeedForwardHidden, PositionWiseFeedForward, TransformerDecoder, TransformerDecoderInput,
+    TransformerDecoderLayer, TransformerEncoder, TransformerEncoderInput, TransformerEncoderLayer,
+};
+use crate::nn::{Dropout, Gelu, RotaryEncoding, norm::Normalization};
+use crate::optim::ParamPathFilter;
+use crate::tensor::{Tensor, activation::silu, backend::Backend};
+
+/// A [position-wise feed-forward](PositionWiseFeedForward) network with optional
+/// [LoRA adapters](LoraAdapter) on its linear layers.
+///
+/// Created by [injecting](super::LoraConfig::inject) adapters in a module.
+#[derive(Module, Debug)]
+pub struct LoraPositionWiseFeedForward<B: Backend> {
+    /// The base network.
+    pub base: PositionWiseFeedForward<B>,
+    /// The adapter of the [GELU](FeedForwardHidden::Gelu) hidden layer, or of the inner layer of
+    /// the [SwiGLU](FeedForwardHidden::SwiGlu) hidden layer, if any.
+    pub hidden: Option<LoraAdapter<B>>,
+    /// The adapter of the outer layer of the [SwiGLU](FeedForwardHidden::SwiGlu) hidden layer, if
+    /// any.
+    pub gate: Option<LoraAdapter<B>>,
+    /// The adapter of the outer linear layer, if any.
+    pub linear_outer: Option<LoraAdapter<B>>,
+}
+
+impl<B: Backend> LoraPositionWiseFeedForward<B> {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [PositionWiseFeedForward::forward] for the shapes of the tensors.
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let x = ForwardScope::run("hidden", || match &self.base.hidden {
+            FeedForwardHidden::Gelu(linear) => {
+                let x = ForwardScope::run("Gelu", || {
+                    adapted_forward(linear, self.hidden.as_ref(), input)
+                });
+                Gelu::new().forward(x)
+            }
+            FeedForwardHidden::SwiGlu(swiglu) => {
+                let _scope = ForwardScope::enter("SwiGlu");
+                let x = ForwardScope::run("linear_inner", || {
+                    adapted_forward(&swiglu.linear_inner, self.hidden.as_ref(), input.clone())
+                });
+                let x = silu(x);
+                x.mul(ForwardScope::run("linear_outer", || {
+                    adapted_forward(&swiglu.linear_outer, self.gate.as_ref(), input)
+                }))
+            }
+        });
+        let x = self.base.dropout.forward(x);
+
+        ForwardScope::run("linear_outer", || {
+            adapted_forward(&self.base.linear_outer, self.linear_outer.as_ref(), x)
+        })
+    }
+
+    /// Merges the update of the adapters into the weights of the linear layers.
+    ///
+    /// See [LoraLinear::merge](super::LoraLinear::merge).
+    pub fn merge(self) -> Self {
+        self.set_merged(true)
+    }
+
+    /// Removes the update of the merged adapters from the weights of the linear layers.
+    pub fn unmerge(self) -> Self {
+        self.set_merged(false)
+    }
+
+    /// Merges the adapters into the linear layers, and returns the base network.
+    pub fn into_feed_forward(self) -> PositionWiseFeedForward<B> {
+        self.merge().base
+    }
+
+    fn set_merged(self, merged: bool) -> Self {
+        let mut base = self.base;
+
+        let (hidden, hidden_adapter, gate_adapter) = match base.hidden {
+            FeedForwardHidden::Gelu(linear) => {
+                let (linear, adapter) = set_merged(linear, self.hidden, merged);
+                (FeedForwardHidden::Gelu(linear), adapter, self.gate)
+            }
+            FeedForwardHidden::SwiGlu(mut swiglu) => {
+                let (inner, hidden_adapter) = set_merged(swiglu.linear_inner, self.hidden, merged);
+                let (outer, gate_adapter) = set_merged(swiglu.linear_outer, self.gate, merged);
+                swiglu.linear_inner = inner;
+                swiglu.linear_outer = outer;
+                (
+                    FeedForwardHidden::SwiGlu(swiglu),
+                    hidden_adapter,
+                    gate_adapter,
+                )
+            }
+        };
+        let (linear_outer, linear_outer_adapter) =
+            set_merged(base.linear_outer, self.linear_outer, merged);
+        base.hidden = hidden;
+        base.linear_outer = linear_outer;
+
+        Self {
+            base,
+            hidden: hidden_adapter,
+            gate: gate_adapter,
+            linear_outer: linear_outer_adapter,
+        }
+    }
+}
+
+/// A [transformer encoder layer](TransformerEncoderLayer) with optional
+/// [LoRA adapters](LoraAdapter) on the linear layers of its sublayers.
+///
+/// Created by [injecting](super::LoraConfig::inject) adapters in a module.
+#[derive(Module, Debug)]
+pub struct LoraTransformerEncoderLayer<B: Backend> {
+    /// The adapted self-attention.
+    pub mha: LoraMultiHeadAttention<B>,
+    /// The adapted feed-forward network.
+    pub pwff: LoraPositionWiseFeedForward<B>,
+    norm_1: Normalization<B>,
+    norm_2: Normalization<B>,
+    dropout: Dropout,
+    norm_first: bool,
+}
+
+impl<B: Backend> LoraTransformerEncoderLayer<B> {
+    /// Merges the update of the adapters into the weights of the linear layers.
+    pub fn merge(self) -> Self {
+        Self {
+            mha: self.mha.merge(),
+            pwff: self.pwff.merge(),
+            ..self
+        }
+    }
+
+    /// Removes the update of the merged adapters from the weights of the linear layers.
+    pub fn unmerge(self) -> Self {
+        Self {
+            mha: self.mha.unmerge(),
+            pwff: self.pwff.unmerge(),
+            ..self
+        }
+    }
+
+    /// Merges the adapters into the linear layers, and returns the base layer.
+    pub fn into_layer(self) -> TransformerEncoderLayer<B> {
+        TransformerEncoderLayer {
+            mha: self.mha.into_attention(),
+            pwff: self.pwff.into_feed_forward(),
+            norm_1: self.norm_1,
+            norm_2: self.norm_2,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+        }
+    }
+
+    fn forward(&self, input: Tensor<B, 3>, attn: &EncoderAttention<B>) -> Tensor<B, 3> {
+        EncoderLayerResiduals {
+            norm_1: &self.norm_1,
+            norm_2: &self.norm_2,
+            dropout: &self.dropout,
+            norm_first: self.norm_first,
+        }
+        .forward(
+            input,
+            attn,
+            |input| ForwardScope::run("mha", || self.mha.forward(input).context),
+            |x| ForwardScope::run("pwff", || self.pwff.forward(x)),
+        )
+    }
+}
+
+/// A [transformer encoder](TransformerEncoder) with optional [LoRA adapters](LoraAdapter) on the
+/// linear layers of its layers.
+///
+/// Created by [injecting](super::LoraConfig::inject) adapters in a module. The cached forward
+/// pass isn't supported, the encoder should be converted with
+/// [into_encoder](Self::into_encoder) for inference.
+#[derive(Module, Debug)]
+pub struct LoraTransformerEncoder<B: Backend> {
+    /// The adapted encoder layers.
+    pub layers: Vec<LoraTransformerEncoderLayer<B>>,
+    d_model: usize,
+    d_ff: usize,
+    n_heads: usize,
+    n_layers: usize,
+    dropout: f64,
+    norm_first: bool,
+    quiet_softmax: bool,
+    rope: Option<RotaryEncoding<B>>,
+    alibi: bool,
+    causal: bool,
+}
+
+impl<B: Backend> LoraTransformerEncoder<B> {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [TransformerEncoder::forward] for the shapes of the tensors.
+    pub fn forward(&self, input: TransformerEncoderInput<B>) -> Tensor<B, 3> {
+        let attn = EncoderAttention::new(
+            &input,
+            self.n_heads,
+            self.causal,
+            self.alibi,
+            self.rope.as_ref(),
+        );
+        let mut x = input.tensor;
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            x = ForwardScope::run(&format!("layers.{i}"), || layer.forward(x, &attn));
+        }
+
+        x
+    }
+
+    /// Merges the update of the adapters into the weights of the linear layers.
+    pub fn merge(self) -> Self {
+        Self {
+            layers: self.layers.into_iter().map(|layer| layer.merge()).collect(),
+            ..self
+        }
+    }
+
+    /// Removes the update of the merged adapters from the weights of the linear layers.
+    pub fn unmerge(self) -> Self {
+        Self {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|layer| layer.unmerge())
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Merges the adapters into the linear layers, and returns the base encoder.
+    pub fn into_encoder(self) -> TransformerEncoder<B> {
+        TransformerEncoder {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|layer| layer.into_layer())
+                .collect(),
+            d_model: self.d_model,
+            d_ff: self.d_ff,
+            n_heads: self.n_heads,
+            n_layers: self.n_layers,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+            quiet_softmax: self.quiet_softmax,
+            rope: self.rope,
+            alibi: self.alibi,
+            causal: self.causal,
+        }
+    }
+}
+
+/// A [transformer decoder layer](TransformerDecoderLayer) with optional
+/// [LoRA adapters](LoraAdapter) on the linear layers of its sublayers.
+///
+/// Created by [injecting](super::LoraConfig::inject) adapters in a module.
+#[derive(Module, Debug)]
+pub struct LoraTransformerDecoderLayer<B: Backend> {
+    /// The adapted cross-attention.
+    pub cross_attn: LoraMultiHeadAttention<B>,
+    /// The adapted self-attention.
+    pub self_attn: LoraMultiHeadAttention<B>,
+    /// The adapted feed-forward network.
+    pub pwff: LoraPositionWiseFeedForward<B>,
+    norm_1: Normalization<B>,
+    norm_2: Normalization<B>,
+    norm_3: Normalization<B>,
+    dropout: Dropout,
+    norm_first: bool,
+}
+
+impl<B: Backend> LoraTransformerDecoderLayer<B> {
+    /// Merges the update of the adapters into the weights of the linear layers.
+    pub fn merge(self) -> Self {
+        Self {
+            cross_attn: self.cross_attn.merge(),
+            self_attn: self.self_attn.merge(),
+            pwff: self.pwff.merge(),
+            ..self
+        }
+    }
+
+    /// Removes the update of the merged adapters from the weights of the linear layers.
+    pub fn unmerge(self) -> Self {
+        Self {
+            cross_attn: self.cross_attn.unmerge(),
+            self_attn: self.self_attn.unmerge(),
+            pwff: self.pwff.unmerge(),
+            ..self
+        }
+    }
+
+    /// Merges the adapters into the linear layers, and returns the base layer.
+    pub fn into_layer(self) -> TransformerDecoderLayer<B> {
+        TransformerDecoderLayer {
+            cross_attn: self.cross_attn.into_attention(),
+            self_attn: self.self_attn.into_attention(),
+            pwff: self.pwff.into_feed_forward(),
+            norm_1: self.norm_1,
+            norm_2: self.norm_2,
+            norm_3: self.norm_3,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+        }
+    }
+
+    fn forward(
+        &self,
+        input: TransformerDecoderInput<B>,
+        positions: &DecoderSelfAttention<B>,
+    ) -> TransformerDecoderInput<B> {
+        DecoderLayerResiduals {
+            norm_1: &self.norm_1,
+            norm_2: &self.norm_2,
+            norm_3: &self.norm_3,
+            dropout: &self.dropout,
+            norm_first: self.norm_first,
+        }
+        .forward(
+            input,
+            positions,
+            |input| ForwardScope::run("self_attn", || self.self_attn.forward(input).context),
+            |input| ForwardScope::run("cross_attn", || self.cross_attn.forward(input).context),
+            |x| ForwardScope::run("pwff", || self.pwff.forward(x)),
+        )
+    }
+}
+
+/// A [transformer decoder](TransformerDecoder) with optional [LoRA adapters](LoraAdapter) on the
+/// linear layers of its layers.
+///
+/// Created by [injecting](super::LoraConfig::inject) adapters in a module. The cached forward
+/// pass isn't supported, the decoder should be converted with
+/// [into_decoder](Self::into_decoder) for inference.
+#[derive(Module, Debug)]
+pub struct LoraTransformerDecoder<B: Backend> {
+    /// The adapted decoder layers.
+    pub layers: Vec<LoraTransformerDecoderLayer<B>>,
+    d_model: usize,
+    d_ff: usize,
+    n_heads: usize,
+    n_layers: usize,
+    dropout: f64,
+    norm_first: bool,
+    quiet_softmax: bool,
+    rope: Option<RotaryEncoding<B>>,
+    alibi: bool,
+    causal: bool,
+}
+
+impl<B: Backend> LoraTransformerDecoder<B> {
+    /// Applies the forward pass.
+    pub fn forward(&self, input: TransformerDecoderInput<B>) -> Tensor<B, 3> {
+        let (mut input, positions) = DecoderSelfAttention::new(
+            input,
+            self.n_heads,
+            self.causal,
+            self.alibi,
+            self.rope.as_ref(),
+        );
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            input = ForwardScope::run(&format!("layers.{i}"), || layer.forward(input, &positions));
+        }
+
+        input.target
+    }
+
+    /// Merges the update of the adapters into the weights of the linear layers.
+    pub fn merge(self) -> Self {
+        Self {
+            layers: self.layers.into_iter().map(|layer| layer.merge()).collect(),
+            ..self
+        }
+    }
+
+    /// Removes the update of the merged adapters from the weights of the linear layers.
+    pub fn unmerge(self) -> Self {
+        Self {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|layer| layer.unmerge())
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Merges the adapters into the linear layers, and returns the base decoder.
+    pub fn into_decoder(self) -> TransformerDecoder<B> {
+        TransformerDecoder {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|layer| layer.into_layer())
+                .collect(),
+            d_model: self.d_model,
+            d_ff: self.d_ff,
+            n_heads: self.n_heads,
+            n_layers: self.n_layers,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+            quiet_softmax: self.quiet_softmax,
+            rope: self.rope,
+            alibi: self.alibi,
+            causal: self.causal,
+        }
+    }
+}
+
+impl<B: Backend> LoraModule<B> for PositionWiseFeedForward<B> {
+    type Adapted = LoraPositionWiseFeedForward<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        const CONTAINER: &str = "PositionWiseFeedForward";
+
+        mapper.enter("hidden", CONTAINER);
+        let (hidden, gate) = match &self.hidden {
+            FeedForwardHidden::Gelu(linear) => {
+                (mapper.adapter("Gelu", "FeedForwardHidden", linear), None)
+            }
+            FeedForwardHidden::SwiGlu(swiglu) => {
+                mapper.enter("SwiGlu", "FeedForwardHidden");
+                let hidden = mapper.adapter("linear_inner", "SwiGlu", &swiglu.linear_inner);
+                let gate = mapper.adapter("linear_outer", "SwiGlu", &swiglu.linear_outer);
+                mapper.exit();
+                (hidden, gate)
+            }
+        };
+        mapper.exit();
+
+        LoraPositionWiseFeedForward {
+            hidden,
+            gate,
+            linear_outer: mapper.adapter("linear_outer", CONTAINER, &self.linear_outer),
+            base: self,
+        }
+    }
+}
+
+impl<B: Backend> LoraModule<B> for TransformerEncoderLayer<B> {
+    type Adapted = LoraTransformerEncoderLayer<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        const CONTAINER: &str = "TransformerEncoderLayer";
+
+        LoraTransformerEncoderLayer {
+            mha: mapper.map("mha", CONTAINER, self.mha),
+            pwff: mapper.map("pwff", CONTAINER, self.pwff),
+            norm_1: self.norm_1,
+            norm_2: self.norm_2,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+        }
+    }
+}
+
+impl<B: Backend> LoraModule<B> for TransformerEncoder<B> {
+    type Adapted = LoraTransformerEncoder<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        LoraTransformerEncoder {
+            layers: mapper.map("layers", "TransformerEncoder", self.layers),
+            d_model: self.d_model,
+            d_ff: self.d_ff,
+            n_heads: self.n_heads,
+            n_layers: self.n_layers,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+            quiet_softmax: self.quiet_softmax,
+            rope: self.rope,
+            alibi: self.alibi,
+            causal: self.causal,
+        }
+    }
+}
+
+impl<B: Backend> LoraModule<B> for TransformerDecoderLayer<B> {
+    type Adapted = LoraTransformerDecoderLayer<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        const CONTAINER: &str = "TransformerDecoderLayer";
+
+        LoraTransformerDecoderLayer {
+            cross_attn: mapper.map("cross_attn", CONTAINER, self.cross_attn),
+            self_attn: mapper.map("self_attn", CONTAINER, self.self_attn),
+            pwff: mapper.map("pwff", CONTAINER, self.pwff),
+            norm_1: self.norm_1,
+            norm_2: self.norm_2,
+            norm_3: self.norm_3,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+        }
+    }
+}
+
+impl<B: Backend> LoraModule<B> for TransformerDecoder<B> {
+    type Adapted = LoraTransformerDecoder<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        LoraTransformerDecoder {
+            layers: mapper.map("layers", "TransformerDecoder", self.layers),
+            d_model: self.d_model,
+            d_ff: self.d_ff,
+            n_heads: self.n_heads,
+            n_layers: self.n_layers,
+            dropout: self.dropout,
+            norm_first: self.norm_first,
+            quiet_softmax: self.quiet_softmax,
+            rope: self.rope,
+            alibi: self.alibi,
+            causal: self.causal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::module::GlobFilter;
+    use crate::nn::lora::LoraConfig;
+    use crate::nn::transformer::{
+        FeedForwardActivation, TransformerDecoderConfig, TransformerEncoderConfig,
+    };
+    use crate::tensor::Distribution;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn randomize(adapter: Option<LoraAdapter<TestBackend>>) -> Option<LoraAdapter<TestBackend>> {
+        adapter.map(|mut adapter| {
+            adapter.lora_b = adapter
+                .lora_b
+                .map(|tensor| tensor.random_like(Distribution::Default));
+            adapter
+        })
+    }
+
+    #[test]
+    fn inject_should_adapt_the_selected_encoder_layers() {
+        let device = Default::default();
+        let encoder = TransformerEncoderConfig::new(8, 16, 2, 2).init::<TestBackend>(&device);
+
+        let encoder = LoraConfig::new(2, 4.0).inject(
+            encoder,
+            &GlobFilter::new([
+                "layers.*.mha.query",
+                "**.pwff.linear_outer",
+                "layers.1.**.Gelu",
+            ]),
+        );
+
+        for layer in encoder.layers.iter() {
+            assert!(layer.mha.query.is_some());
+            assert!(layer.mha.value.is_none());
+            assert!(layer.pwff.linear_outer.is_some());
+            assert!(layer.pwff.gate.is_none());
+        }
+        assert!(encoder.layers[0].pwff.hidden.is_none());
+        assert!(encoder.layers[1].pwff.hidden.is_some());
+    }
+
+    #[test]
+    fn inject_should_keep_the_encoder_output_unchanged() {
+        let device = Default::default();
+        let encoder = TransformerEncoderConfig::new(8, 16, 2, 2)
+            .with_feed_forward(FeedForwardActivation::SwiGlu)
+            .init::<TestBackend>(&device);
+        let input = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let expected = encoder.forward(TransformerEncoderInput::new(input.clone()));
+
+        let encoder = LoraConfig::new(4, 4.0).inject(encoder, &GlobFilter::new(["**"]));
+
+        assert!(encoder.layers[0].pwff.gate.is_some());
+        encoder
+            .forward(TransformerEncoderInput::new(input))
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn merged_encoder_should_keep_the_output() {
+        let device = Default::default();
+        let encoder = TransformerEncoderConfig::new(8, 16, 2, 2)
+            .with_feed_forward(FeedForwardActivation::SwiGlu)
+            .init::<TestBackend>(&device);
+        let mut encoder = LoraConfig::new(2, 4.0).inject(encoder, &GlobFilter::new(["**"]));
+        for layer in encoder.layers.iter_mut() {
+            layer.mha.value = randomize(layer.mha.value.take());
+            layer.pwff.hidden = randomize(layer.pwff.hidden.take());
+            layer.pwff.gate = randomize(layer.pwff.gate.take());
+            layer.pwff.linear_outer = randomize(layer.pwff.linear_outer.take());
+        }
+        let input = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let expected = encoder.forward(TransformerEncoderInput::new(input.clone()));
+
+        let encoder = encoder.merge();
+        encoder
+            .forward(TransformerEncoderInput::new(input.clone()))
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.clone().into_data(), Tolerance::default());
+
+        encoder
+            .into_encoder()
+            .forward(TransformerEncoderInput::new(input))
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn merged_decoder_should_keep_the_output() {
+        let device = Default::default();
+        let decoder = TransformerDecoderConfig::new(8, 16, 2, 2)
+            .with_causal(true)
+            .init::<TestBackend>(&device);
+        let target = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        let memory = Tensor::random([2, 4, 8], Distribution::Default, &device);
+        let base = decoder.forward(TransformerDecoderInput::new(target.clone(), memory.clone()));
+
+        let mut decoder = LoraConfig::new(2, 4.0).inject(
+            decoder,
+            &GlobFilter::new(["**.self_attn.query", "**.cross_attn.value", "**.pwff.**"]),
+        );
+        decoder
+            .forward(TransformerDecoderInput::new(target.clone(), memory.clone()))
+            .into_data()
+            .assert_approx_eq::<FT>(&base.into_data(), Tolerance::default());
+
+        for layer in decoder.layers.iter_mut() {
+            assert!(layer.self_attn.key.is_none());
+            assert!(layer.cross_attn.query.is_none());
+            layer.self_attn.query = randomize(layer.self_attn.query.take());
+            layer.cross_attn.value = randomize(layer.cross_attn.value.take());
+            layer.pwff.hidden = randomize(layer.pwff.hidden.take());
+        }
+        let expected =
+            decoder.forward(TransformerDecoderInput::new(target.clone(), memory.clone()));
+
+        decoder
+            .into_decoder()
+            .forward(TransformerDecoderInput::new(target, memory))
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
+}
@@ -1,4 +1,5 @@
 use crate as burn;
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::module::{Content, DisplaySettings, ModuleDisplay};
 
 use crate::tensor::activation::log_sigmoid;
@@ -103,8 +104,9 @@ impl<B: Backend> BinaryCrossEntropyLoss<B> {
         targets: Tensor<B, D, Int>,
     ) -> Tensor<B, 1> {
         self.assertions(&logits, &targets);
+        let logits = full_precision(FullPrecisionOp::Loss, logits);
 
-        let mut targets_float = targets.clone().float();
+        let mut targets_float = cast_to(targets.clone().float(), logits.dtype());
         let shape = targets.dims();
 
         if let Some(alpha) = self.smoothing {
@@ -123,15 +125,13 @@ impl<B: Backend> BinaryCrossEntropyLoss<B> {
         };
 
         if let Some(weights) = &self.weights {
+            let weights = cast_to(weights.clone(), loss.dtype());
             let weights = if D > 1 {
-                weights.clone().expand(shape)
+                weights.expand(shape)
             } else {
                 // Flatten targets and expand resulting weights to make it compatible with
                 // Tensor<B, D> for binary 1-D case
-                weights
-                    .clone()
-                    .gather(0, targets.flatten(0, 0))
-                    .expand(shape)
+                weights.gather(0, targets.flatten(0, 0)).expand(shape)
             };
             loss = loss * weights;
         }
@@ -1,5 +1,6 @@
 use crate as burn;
 
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::module::{Content, DisplaySettings, ModuleDisplay};
 use crate::tensor::activation::log_softmax;
 use crate::tensor::{Bool, Int, Tensor, backend::Backend};
@@ -123,6 +124,7 @@ impl<B: Backend> CrossEntropyLoss<B> {
     /// - targets: `[batch_size]`
     pub fn forward(&self, logits: Tensor<B, 2>, targets: Tensor<B, 1, Int>) -> Tensor<B, 1> {
         Self::assertions(logits.clone(), targets.clone());
+        let logits = full_precision(FullPrecisionOp::Loss, logits);
         match self.smoothing {
             Some(alpha) => self.forward_smoothed(logits, targets, alpha),
             _ => self.forward_default(logits, targets),
@@ -142,11 +144,14 @@ impl<B: Backend> CrossEntropyLoss<B> {
             logits.log()
         };
         let [batch_size, nr_classes] = tensor.dims();
-        let tensor = tensor
-            * Self::compute_smoothed_targets([batch_size, nr_classes], targets.clone(), alpha);
+        let smoothed_targets =
+            Self::compute_smoothed_targets([batch_size, nr_classes], targets.clone(), alpha);
+        let smoothed_targets = cast_to(smoothed_targets, tensor.dtype());
+        let tensor = tensor * smoothed_targets;
 
         match &self.weights {
             Some(weights) => {
+                let weights = cast_to(weights.clone(), tensor.dtype());
                 let tensor = tensor
                     * weights
                         .clone()
@@ -172,7 +177,7 @@ impl<B: Backend> CrossEntropyLoss<B> {
 
         match &self.weights {
             Some(weights) => {
-                let weights = weights.clone().gather(0, targets);
+                let weights = cast_to(weights.clone(), tensor.dtype()).gather(0, targets);
                 let tensor = tensor.reshape([batch_size]) * weights.clone();
                 let tensor = Self::apply_mask_1d(tensor, mask);
                 tensor.sum().neg() / weights.sum()
@@ -1,5 +1,6 @@
 use crate as burn;
 
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::nn::loss::reduction::Reduction;
 
 use crate::module::Module;
@@ -46,6 +47,8 @@ impl MseLoss {
         logits: Tensor<B, D>,
         targets: Tensor<B, D>,
     ) -> Tensor<B, D> {
+        let logits = full_precision(FullPrecisionOp::Loss, logits);
+        let targets = cast_to(targets, logits.dtype());
         logits.sub(targets).powi_scalar(2)
     }
 }
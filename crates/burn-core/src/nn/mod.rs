@@ -10,9 +10,15 @@ pub mod conv;
 /// Loss module
 pub mod loss;
 
+/// Low-rank adaptation module
+pub mod lora;
+
 /// Pooling module
 pub mod pool;
 
+/// State-space model module
+pub mod ssm;
+
 /// Transformer module
 pub mod transformer;
 
@@ -25,24 +31,30 @@ pub use activation::{
     tanh::*,
 };
 
+mod channel_shuffle;
 mod dropout;
 mod embedding;
+mod embedding_bag;
 mod initializer;
 mod linear;
 mod padding;
+mod pixel_shuffle;
 mod pos_encoding;
 mod rnn;
 mod rope_encoding;
 mod unfold;
 
 pub mod norm;
-pub use norm::{batch::*, group::*, instance::*, layer::*, rms::*};
+pub use norm::{batch::*, group::*, instance::*, layer::*, local_response::*, lp::*, rms::*};
 
+pub use channel_shuffle::*;
 pub use dropout::*;
 pub use embedding::*;
+pub use embedding_bag::*;
 pub use initializer::*;
 pub use linear::*;
 pub use padding::*;
+pub use pixel_shuffle::*;
 pub use pos_encoding::*;
 pub use rnn::*;
 pub use rope_encoding::*;
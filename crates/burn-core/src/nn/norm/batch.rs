@@ -1,4 +1,5 @@
 use crate as burn;
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::module::{Content, DisplaySettings, ModuleDisplay};
 
 use crate::nn::Initializer;
@@ -100,17 +101,21 @@ impl<B: Backend> BatchNorm<B> {
             );
         }
 
-        match B::ad_enabled() {
+        let dtype = input.dtype();
+        let input = full_precision(FullPrecisionOp::Norm, input);
+
+        let output = match B::ad_enabled() {
             true => self.forward_train(input),
             false => self.forward_inference(input),
-        }
+        };
+        cast_to(output, dtype)
     }
 
     fn forward_inference<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
         let device = input.device();
         let channels = input.dims()[1];
-        let mean = self.running_mean.value().to_device(&device);
-        let var = self.running_var.value().to_device(&device);
+        let mean = cast_to(self.running_mean.value().to_device(&device), input.dtype());
+        let var = cast_to(self.running_var.value().to_device(&device), input.dtype());
 
         let mut shape = [1; D];
         shape[1] = channels;
@@ -151,18 +156,22 @@ impl<B: Backend> BatchNorm<B> {
         let running_mean = self.running_mean.value_sync().to_device(&device);
         let running_var = self.running_var.value_sync().to_device(&device);
 
-        let running_mean = running_mean.mul_scalar(1.0 - self.momentum).add(
+        // The statistics are accumulated with the type of the running statistics.
+        let dtype = running_mean.dtype();
+        let running_mean = running_mean.mul_scalar(1.0 - self.momentum).add(cast_to(
             mean.clone()
                 .detach()
                 .mul_scalar(self.momentum)
                 .reshape([channels]),
-        );
-        let running_var = running_var.mul_scalar(1.0 - self.momentum).add(
+            dtype,
+        ));
+        let running_var = running_var.mul_scalar(1.0 - self.momentum).add(cast_to(
             var.clone()
                 .detach()
                 .mul_scalar(self.momentum)
                 .reshape([channels]),
-        );
+            dtype,
+        ));
 
         self.running_mean.update(running_mean.detach());
         self.running_var.update(running_var.detach());
@@ -185,9 +194,10 @@ impl<B: Backend> BatchNorm<B> {
         let x = x.sub(mean);
         let x = x.div(std);
 
-        let x = x.mul(self.gamma.val().reshape(shape));
+        let dtype = x.dtype();
+        let x = x.mul(cast_to(self.gamma.val(), dtype).reshape(shape));
 
-        x.add(self.beta.val().reshape(shape))
+        x.add(cast_to(self.beta.val(), dtype).reshape(shape))
     }
 }
 
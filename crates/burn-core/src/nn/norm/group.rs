@@ -1,4 +1,5 @@
 use crate as burn;
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::nn::Initializer;
 
 use crate::config::Config;
@@ -117,17 +118,20 @@ impl<B: Backend> GroupNorm<B> {
             );
         }
 
-        let gamma = self.gamma.as_ref().map(|x| x.val());
-        let beta = self.beta.as_ref().map(|x| x.val());
+        let dtype = input.dtype();
+        let input = full_precision(FullPrecisionOp::Norm, input);
+        let gamma = self.gamma.as_ref().map(|x| cast_to(x.val(), input.dtype()));
+        let beta = self.beta.as_ref().map(|x| cast_to(x.val(), input.dtype()));
 
-        group_norm(
+        let output = group_norm(
             input,
             gamma,
             beta,
             self.num_groups,
             self.epsilon,
             self.affine,
-        )
+        );
+        cast_to(output, dtype)
     }
 }
 
@@ -1,4 +1,5 @@
 use crate as burn;
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 
 use crate::config::Config;
 use crate::module::{Content, DisplaySettings, ModuleDisplay};
@@ -91,10 +92,13 @@ impl<B: Backend> InstanceNorm<B> {
         // Instance norm is equivalent to group norm when the number of groups is equal to the number of channels.
         let num_groups = self.num_channels;
 
-        let gamma = self.gamma.as_ref().map(|x| x.val());
-        let beta = self.beta.as_ref().map(|x| x.val());
+        let dtype = input.dtype();
+        let input = full_precision(FullPrecisionOp::Norm, input);
+        let gamma = self.gamma.as_ref().map(|x| cast_to(x.val(), input.dtype()));
+        let beta = self.beta.as_ref().map(|x| cast_to(x.val(), input.dtype()));
 
-        group_norm(input, gamma, beta, num_groups, self.epsilon, self.affine)
+        let output = group_norm(input, gamma, beta, num_groups, self.epsilon, self.affine);
+        cast_to(output, dtype)
     }
 }
 
@@ -1,4 +1,5 @@
 use crate as burn;
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::config::Config;
 use crate::module::Content;
 use crate::module::DisplaySettings;
@@ -65,13 +66,17 @@ impl<B: Backend> LayerNorm<B> {
     /// - input: `[..., any, d_model]`
     /// - output: `[..., any, d_model]`
     pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        let dtype = input.dtype();
+        let input = full_precision(FullPrecisionOp::Norm, input);
+        let input_dtype = input.dtype();
         let (var, mean) = input.clone().var_mean_bias(D - 1);
 
         let input_normalized = input.sub(mean).div(var.add_scalar(self.epsilon).sqrt());
 
-        input_normalized
-            .mul(self.gamma.val().unsqueeze())
-            .add(self.beta.val().unsqueeze())
+        let output = input_normalized
+            .mul(cast_to(self.gamma.val(), input_dtype).unsqueeze())
+            .add(cast_to(self.beta.val(), input_dtype).unsqueeze());
+        cast_to(output, dtype)
     }
 }
 
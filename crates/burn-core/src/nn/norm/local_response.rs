@@ -0,0 +1,195 @@
+use crate as burn;
+
+use alloc::vec;
+
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::tensor::Tensor;
+use crate::tensor::backend::Backend;
+
+/// Configuration to create a [LocalResponseNorm](LocalResponseNorm) layer using the [init function](LocalResponseNormConfig::init).
+#[derive(Config, Debug)]
+pub struct LocalResponseNormConfig {
+    /// The number of neighbouring channels used for normalization.
+    pub size: usize,
+    /// The multiplicative factor. Default: 1e-4
+    #[config(default = 1e-4)]
+    pub alpha: f64,
+    /// The exponent. Default: 0.75
+    #[config(default = 0.75)]
+    pub beta: f64,
+    /// The additive factor. Default: 1.0
+    #[config(default = 1.0)]
+    pub k: f64,
+}
+
+/// Applies Local Response Normalization over the channels of an input tensor.
+///
+/// `Y = X / (k + alpha / size * sum(X^2))^beta`
+///
+/// where the sum is computed over the `size` channels centered around each channel, as used
+/// in AlexNet and GoogLeNet.
+///
+/// Introduced in the paper: [ImageNet Classification with Deep Convolutional Neural
+/// Networks](https://papers.nips.cc/paper/4824-imagenet-classification-with-deep-convolutional-neural-networks).
+///
+/// Should be created using [LocalResponseNormConfig].
+#[derive(Module, Clone, Debug)]
+#[module(custom_display)]
+pub struct LocalResponseNorm {
+    /// The number of neighbouring channels used for normalization.
+    pub size: usize,
+    /// The multiplicative factor.
+    pub alpha: f64,
+    /// The exponent.
+    pub beta: f64,
+    /// The additive factor.
+    pub k: f64,
+}
+
+impl LocalResponseNormConfig {
+    /// Initialize a new [local response norm](LocalResponseNorm) module.
+    pub fn init(&self) -> LocalResponseNorm {
+        assert!(
+            self.size > 0,
+            "The size of a LocalResponseNorm must be positive"
+        );
+
+        LocalResponseNorm {
+            size: self.size,
+            alpha: self.alpha,
+            beta: self.beta,
+            k: self.k,
+        }
+    }
+}
+
+impl LocalResponseNorm {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [LocalResponseNorm](LocalResponseNorm) for more information.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, channels, *]`
+    /// - output: `[batch_size, channels, *]`
+    pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        assert!(
+            D >= 3,
+            "LocalResponseNorm expects an input of shape [batch_size, channels, *]"
+        );
+
+        let device = input.device();
+        let mut shape = input.dims();
+        let channels = shape[1];
+
+        // The window of channel `c` spans `[c - size / 2, c + (size - 1) / 2]`.
+        let before = self.size / 2;
+        let after = (self.size - 1) / 2;
+
+        let mut padded = vec![input.clone().powi_scalar(2)];
+        if before > 0 {
+            shape[1] = before;
+            padded.insert(0, Tensor::zeros(shape, &device));
+        }
+        if after > 0 {
+            shape[1] = after;
+            padded.push(Tensor::zeros(shape, &device));
+        }
+        let padded = Tensor::cat(padded, 1);
+
+        let sum = (1..self.size).fold(padded.clone().narrow(1, 0, channels), |sum, offset| {
+            sum + padded.clone().narrow(1, offset, channels)
+        });
+
+        let div = sum
+            .mul_scalar(self.alpha / self.size as f64)
+            .add_scalar(self.k)
+            .powf_scalar(self.beta);
+
+        input / div
+    }
+}
+
+impl ModuleDisplay for LocalResponseNorm {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        content
+            .add("size", &self.size)
+            .add("alpha", &self.alpha)
+            .add("beta", &self.beta)
+            .add("k", &self.k)
+            .optional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn local_response_norm_forward() {
+        let device = Default::default();
+        let module = LocalResponseNormConfig::new(3)
+            .with_alpha(3.0)
+            .with_beta(1.0)
+            .init();
+        let input = Tensor::<TestBackend, 4>::from_data(
+            TensorData::from([[[[1.0]], [[2.0]], [[3.0]], [[4.0]]]]),
+            &device,
+        );
+
+        let output = module.forward(input);
+
+        // div(c) = 1 + (x(c-1)^2 + x(c)^2 + x(c+1)^2)
+        let expected = TensorData::from([[
+            [[1.0 / 6.0]],
+            [[2.0 / 15.0]],
+            [[3.0 / 30.0]],
+            [[4.0 / 26.0]],
+        ]]);
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+
+    #[test]
+    fn local_response_norm_even_size() {
+        let device = Default::default();
+        let module = LocalResponseNormConfig::new(2)
+            .with_alpha(2.0)
+            .with_beta(1.0)
+            .init();
+        let input =
+            Tensor::<TestBackend, 3>::from_data(TensorData::from([[[1.0], [2.0], [3.0]]]), &device);
+
+        let output = module.forward(input);
+
+        // The window of channel `c` is `[c - 1, c]`.
+        let expected = TensorData::from([[[1.0 / 2.0], [2.0 / 6.0], [3.0 / 14.0]]]);
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+
+    #[test]
+    fn display() {
+        let config = LocalResponseNormConfig::new(5);
+        let layer = config.init();
+
+        assert_eq!(
+            alloc::format!("{layer}"),
+            "LocalResponseNorm {size: 5, alpha: 0.0001, beta: 0.75, k: 1}"
+        );
+    }
+}
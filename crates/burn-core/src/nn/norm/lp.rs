@@ -0,0 +1,132 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::tensor::backend::Backend;
+use crate::tensor::{Tensor, linalg};
+
+/// Configuration to create a [LpNormalization](LpNormalization) layer using the [init function](LpNormalizationConfig::init).
+#[derive(Config, Debug)]
+pub struct LpNormalizationConfig {
+    /// The exponent of the norm. Default: 2.0
+    #[config(default = 2.0)]
+    pub p: f64,
+    /// The dimension to normalize over. Default: 1
+    #[config(default = 1)]
+    pub dim: usize,
+    /// A value required for numerical stability. Default: 1e-12
+    #[config(default = 1e-12)]
+    pub epsilon: f64,
+}
+
+/// Normalizes the input tensor by its Lp norm along a dimension.
+///
+/// `Y = X / max(||X||_p, epsilon)`
+///
+/// Should be created using [LpNormalizationConfig].
+#[derive(Module, Clone, Debug)]
+#[module(custom_display)]
+pub struct LpNormalization {
+    /// The exponent of the norm.
+    pub p: f64,
+    /// The dimension to normalize over.
+    pub dim: usize,
+    /// A value required for numerical stability.
+    pub epsilon: f64,
+}
+
+impl LpNormalizationConfig {
+    /// Initialize a new [Lp normalization](LpNormalization) module.
+    pub fn init(&self) -> LpNormalization {
+        LpNormalization {
+            p: self.p,
+            dim: self.dim,
+            epsilon: self.epsilon,
+        }
+    }
+}
+
+impl LpNormalization {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [LpNormalization](LpNormalization) for more information.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[..., any]`
+    /// - output: `[..., any]`
+    pub fn forward<B: Backend, const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        linalg::vector_normalize(input, self.p, self.dim, self.epsilon)
+    }
+}
+
+impl ModuleDisplay for LpNormalization {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        content
+            .add("p", &self.p)
+            .add("dim", &self.dim)
+            .add("epsilon", &self.epsilon)
+            .optional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn lp_normalization_l2() {
+        let device = Default::default();
+        let module = LpNormalizationConfig::new().init();
+        let input = Tensor::<TestBackend, 2>::from_data(
+            TensorData::from([[3.0, 4.0], [0.0, 0.0]]),
+            &device,
+        );
+
+        let output = module.forward(input);
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[0.6, 0.8], [0.0, 0.0]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn lp_normalization_l1_dim_0() {
+        let device = Default::default();
+        let module = LpNormalizationConfig::new().with_p(1.0).with_dim(0).init();
+        let input = Tensor::<TestBackend, 2>::from_data(
+            TensorData::from([[1.0, -2.0], [3.0, 2.0]]),
+            &device,
+        );
+
+        let output = module.forward(input);
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[0.25, -0.5], [0.75, 0.5]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn display() {
+        let config = LpNormalizationConfig::new();
+        let layer = config.init();
+
+        assert_eq!(
+            alloc::format!("{layer}"),
+            "LpNormalization {p: 2, dim: 1, epsilon: 0.000000000001}"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use burn_tensor::DType;
 
 use crate as burn;
 
+use crate::amp::{FullPrecisionOp, cast_to, full_precision};
 use crate::config::Config;
 use crate::module::Module;
 use crate::module::Param;
@@ -71,8 +72,11 @@ impl<B: Backend> RmsNorm<B> {
     pub fn forward<const D: usize>(&self, x: Tensor<B, D>) -> Tensor<B, D> {
         // Calculate the root-mean-square norm of the input tensor along the last dimension
         let dtype = x.dtype();
+        let x = full_precision(FullPrecisionOp::Norm, x);
+        let x_dtype = x.dtype();
         let rms = (x.clone().cast(DType::F32).powi_scalar(2).mean_dim(D - 1) + self.epsilon).sqrt();
-        (x / rms.cast(dtype)) * self.gamma.val().unsqueeze()
+        let output = (x / rms.cast(x_dtype)) * cast_to(self.gamma.val(), x_dtype).unsqueeze();
+        cast_to(output, dtype)
     }
 }
 
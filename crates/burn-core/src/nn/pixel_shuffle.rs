@@ -0,0 +1,217 @@
+use crate as burn;
+
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::tensor::Tensor;
+use crate::tensor::backend::Backend;
+
+/// Configuration to create a [PixelShuffle](PixelShuffle) layer using the [init function](PixelShuffleConfig::init).
+#[derive(Config, Debug)]
+pub struct PixelShuffleConfig {
+    /// The factor to increase the spatial resolution by.
+    pub upscale_factor: usize,
+}
+
+/// Rearranges the elements of a tensor of shape `[batch_size, channels * r * r, height, width]`
+/// to a tensor of shape `[batch_size, channels, height * r, width * r]`, where `r` is the
+/// upscale factor.
+///
+/// Introduced in the paper: [Real-Time Single Image and Video Super-Resolution Using an Efficient
+/// Sub-Pixel Convolutional Neural Network](https://arxiv.org/abs/1609.05158).
+///
+/// Should be created using [PixelShuffleConfig].
+#[derive(Module, Clone, Debug)]
+#[module(custom_display)]
+pub struct PixelShuffle {
+    /// The factor to increase the spatial resolution by.
+    pub upscale_factor: usize,
+}
+
+/// Configuration to create a [PixelUnshuffle](PixelUnshuffle) layer using the [init function](PixelUnshuffleConfig::init).
+#[derive(Config, Debug)]
+pub struct PixelUnshuffleConfig {
+    /// The factor to decrease the spatial resolution by.
+    pub downscale_factor: usize,
+}
+
+/// Reverses the [PixelShuffle](PixelShuffle) operation, rearranging the elements of a tensor of
+/// shape `[batch_size, channels, height * r, width * r]` to a tensor of shape
+/// `[batch_size, channels * r * r, height, width]`, where `r` is the downscale factor.
+///
+/// Should be created using [PixelUnshuffleConfig].
+#[derive(Module, Clone, Debug)]
+#[module(custom_display)]
+pub struct PixelUnshuffle {
+    /// The factor to decrease the spatial resolution by.
+    pub downscale_factor: usize,
+}
+
+impl PixelShuffleConfig {
+    /// Initialize a new [pixel shuffle](PixelShuffle) module.
+    pub fn init(&self) -> PixelShuffle {
+        assert!(
+            self.upscale_factor > 0,
+            "The upscale factor must be positive"
+        );
+
+        PixelShuffle {
+            upscale_factor: self.upscale_factor,
+        }
+    }
+}
+
+impl PixelUnshuffleConfig {
+    /// Initialize a new [pixel unshuffle](PixelUnshuffle) module.
+    pub fn init(&self) -> PixelUnshuffle {
+        assert!(
+            self.downscale_factor > 0,
+            "The downscale factor must be positive"
+        );
+
+        PixelUnshuffle {
+            downscale_factor: self.downscale_factor,
+        }
+    }
+}
+
+impl PixelShuffle {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [PixelShuffle](PixelShuffle) for more information.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, channels * upscale_factor^2, height, width]`
+    /// - output: `[batch_size, channels, height * upscale_factor, width * upscale_factor]`
+    pub fn forward<B: Backend>(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
+        let [batch_size, channels, height, width] = input.dims();
+        let r = self.upscale_factor;
+        assert!(
+            channels % (r * r) == 0,
+            "The number of channels ({channels}) must be divisible by the square of the upscale factor ({r})"
+        );
+        let channels_out = channels / (r * r);
+
+        input
+            .reshape([batch_size, channels_out, r, r, height, width])
+            .permute([0, 1, 4, 2, 5, 3])
+            .reshape([batch_size, channels_out, height * r, width * r])
+    }
+}
+
+impl PixelUnshuffle {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// See [PixelUnshuffle](PixelUnshuffle) for more information.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, channels, height * downscale_factor, width * downscale_factor]`
+    /// - output: `[batch_size, channels * downscale_factor^2, height, width]`
+    pub fn forward<B: Backend>(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
+        let [batch_size, channels, height, width] = input.dims();
+        let r = self.downscale_factor;
+        assert!(
+            height % r == 0 && width % r == 0,
+            "The spatial dimensions ({height}, {width}) must be divisible by the downscale factor ({r})"
+        );
+        let [height_out, width_out] = [height / r, width / r];
+
+        input
+            .reshape([batch_size, channels, height_out, r, width_out, r])
+            .permute([0, 1, 3, 5, 2, 4])
+            .reshape([batch_size, channels * r * r, height_out, width_out])
+    }
+}
+
+impl ModuleDisplay for PixelShuffle {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        content
+            .add("upscale_factor", &self.upscale_factor)
+            .optional()
+    }
+}
+
+impl ModuleDisplay for PixelUnshuffle {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        content
+            .add("downscale_factor", &self.downscale_factor)
+            .optional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::{Int, TensorData};
+
+    fn input(shape: [usize; 4]) -> Tensor<TestBackend, 4> {
+        let numel = shape.iter().product::<usize>() as i64;
+        Tensor::<TestBackend, 1, Int>::arange(0..numel, &Default::default())
+            .float()
+            .reshape(shape)
+    }
+
+    #[test]
+    fn pixel_shuffle_forward() {
+        let module = PixelShuffleConfig::new(2).init();
+
+        let output = module.forward(input([1, 4, 1, 2]));
+
+        output.into_data().assert_eq(
+            &TensorData::from([[[[0.0, 2.0, 1.0, 3.0], [4.0, 6.0, 5.0, 7.0]]]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn pixel_unshuffle_forward() {
+        let module = PixelUnshuffleConfig::new(2).init();
+
+        let output = module.forward(input([1, 1, 2, 4]));
+
+        output.into_data().assert_eq(
+            &TensorData::from([[[[0.0, 2.0]], [[1.0, 3.0]], [[4.0, 6.0]], [[5.0, 7.0]]]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn pixel_unshuffle_inverts_pixel_shuffle() {
+        let shuffle = PixelShuffleConfig::new(3).init();
+        let unshuffle = PixelUnshuffleConfig::new(3).init();
+        let input = input([2, 18, 2, 3]);
+
+        let output = unshuffle.forward(shuffle.forward(input.clone()));
+
+        output.into_data().assert_eq(&input.into_data(), false);
+    }
+
+    #[test]
+    fn display() {
+        let shuffle = PixelShuffleConfig::new(2).init();
+        let unshuffle = PixelUnshuffleConfig::new(2).init();
+
+        assert_eq!(
+            alloc::format!("{shuffle}"),
+            "PixelShuffle {upscale_factor: 2}"
+        );
+        assert_eq!(
+            alloc::format!("{unshuffle}"),
+            "PixelUnshuffle {downscale_factor: 2}"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use crate as burn;
+
+use alloc::vec::Vec;
+
+use super::gate_controller::GateController;
+use super::sequence::{masked_output, masked_update, timestep_mask, unstack_state};
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Ignored, Module, ModuleDisplay};
+use crate::nn::{Dropout, DropoutConfig, Initializer};
+use crate::tensor::activation;
+use crate::tensor::backend::Backend;
+use crate::tensor::{Int, Tensor};
+
+/// The non-linearity applied to the hidden state of a [Rnn].
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum RnnActivation {
+    /// Hyperbolic tangent.
+    Tanh,
+    /// Rectified linear unit.
+    Relu,
+}
+
+/// Configuration to create a [rnn](Rnn) module using the [init function](RnnConfig::init).
+#[derive(Config, Debug)]
+pub struct RnnConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+    /// If a bias should be applied during the Rnn transformation.
+    pub bias: bool,
+    /// The non-linearity applied to the hidden state. Default: tanh
+    #[config(default = "RnnActivation::Tanh")]
+    pub activation: RnnActivation,
+    /// Rnn initializer
+    #[config(default = "Initializer::XavierNormal{gain:1.0}")]
+    pub initializer: Initializer,
+    /// The number of stacked layers, each layer takes the hidden states of the previous one as
+    /// input. Default: 1
+    #[config(default = 1)]
+    pub num_layers: usize,
+    /// The dropout rate applied to the outputs of each layer except the last one. Default: 0.0
+    #[config(default = 0.0)]
+    pub dropout: f64,
+}
+
+/// The Elman recurrent neural network module. This implementation is for a unidirectional,
+/// stateless, Rnn.
+///
+/// `h(t) = activation(W_ih * x(t) + b_ih + W_hh * h(t-1) + b_hh)`
+///
+/// Introduced in the paper: [Finding Structure in Time](https://doi.org/10.1207/s15516709cog1402_1).
+///
+/// Should be created with [RnnConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct Rnn<B: Backend> {
+    /// The gate controller of each layer, computing its hidden state from its input and previous
+    /// hidden state. The layers after the first one take the hidden states of the previous
+    /// layer as input.
+    pub layers: Vec<GateController<B>>,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+    /// The non-linearity applied to the hidden state.
+    pub activation: Ignored<RnnActivation>,
+    /// The dropout applied to the input of each layer after the first one.
+    pub dropout: Dropout,
+}
+
+impl<B: Backend> ModuleDisplay for Rnn<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_input, _] = self.layers[0].input_transform.weight.shape().dims();
+        let bias = self.layers[0].input_transform.bias.is_some();
+
+        content
+            .add("d_input", &d_input)
+            .add("d_hidden", &self.d_hidden)
+            .add("bias", &bias)
+            .add("activation", &self.activation)
+            .optional()
+    }
+}
+
+impl RnnConfig {
+    /// Initialize a new [rnn](Rnn) module.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Rnn<B> {
+        assert!(self.num_layers > 0, "A Rnn must have at least one layer");
+
+        let layers = (0..self.num_layers)
+            .map(|i| {
+                let d_input = if i == 0 { self.d_input } else { self.d_hidden };
+                GateController::new(
+                    d_input,
+                    self.d_hidden,
+                    self.bias,
+                    self.initializer.clone(),
+                    device,
+                )
+            })
+            .collect();
+
+        Rnn {
+            layers,
+            d_hidden: self.d_hidden,
+            activation: Ignored(self.activation),
+            dropout: DropoutConfig::new(self.dropout).init(),
+        }
+    }
+}
+
+impl<B: Backend> Rnn<B> {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Parameters
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing an initial hidden state with dimensions
+    ///   `[batch_size, hidden_size]`. If none is provided, an empty state will be used.
+    ///
+    /// # Returns
+    /// - output: `[batch_size, sequence_length, hidden_size]`
+    ///
+    /// With stacked layers, the initial state is the one of the first layer, the other layers
+    /// start from an empty state, and the output of the last layer is returned. Use
+    /// [forward_layers](Rnn::forward_layers) to provide or retrieve the state of every layer.
+    pub fn forward(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+    ) -> Tensor<B, 3> {
+        let mut states = (1..self.layers.len()).map(|_| None).collect::<Vec<_>>();
+        states.insert(0, state);
+
+        let (output, _) = self.forward_states(batched_input, states, None);
+        output
+    }
+
+    /// Applies the forward pass on the input tensor through all the stacked layers, optionally
+    /// with variable-length sequences.
+    ///
+    /// # Parameters
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state of each layer with
+    ///   dimensions `[num_layers, batch_size, hidden_size]`. If none is provided, an empty state
+    ///   will be used.
+    /// - lengths: An optional tensor of shape `[batch_size]` with the length of each sequence.
+    ///   Timesteps past the length of a sequence are treated as padding: they don't update the
+    ///   state and their output is zero.
+    ///
+    /// # Returns
+    /// - output: The hidden states of the last layer `[batch_size, sequence_length, hidden_size]`
+    /// - state: The final hidden state of each layer `[num_layers, batch_size, hidden_size]`
+    pub fn forward_layers(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+        lengths: Option<Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Tensor<B, 3>) {
+        let states = unstack_state(state, self.layers.len());
+        let (output, final_states) = self.forward_states(batched_input, states, lengths.as_ref());
+
+        (output, Tensor::stack(final_states, 0))
+    }
+
+    fn forward_states(
+        &self,
+        batched_input: Tensor<B, 3>,
+        states: Vec<Option<Tensor<B, 2>>>,
+        lengths: Option<&Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Vec<Tensor<B, 2>>) {
+        let mut output = batched_input;
+        let mut final_states = Vec::with_capacity(states.len());
+
+        for (i, (gate, state)) in self.layers.iter().zip(states).enumerate() {
+            if i > 0 {
+                output = self.dropout.forward(output);
+            }
+
+            let (layer_output, final_state) = self.forward_layer(gate, output, state, lengths);
+
+            output = layer_output;
+            final_states.push(final_state);
+        }
+
+        (output, final_states)
+    }
+
+    fn forward_layer(
+        &self,
+        gate: &GateController<B>,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+        lengths: Option<&Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Tensor<B, 2>) {
+        let device = batched_input.device();
+        let [batch_size, seq_length, _] = batched_input.dims();
+
+        let mut batched_hidden_state =
+            Tensor::empty([batch_size, seq_length, self.d_hidden], &device);
+
+        let mut hidden_t = match state {
+            Some(state) => state,
+            None => Tensor::zeros([batch_size, self.d_hidden], &device),
+        };
+
+        for (t, input_t) in batched_input.iter_dim(1).enumerate() {
+            let input_t = input_t.squeeze(1);
+            let mask = lengths.map(|lengths| timestep_mask(lengths, t, self.d_hidden));
+
+            let biased_input_sum = gate.gate_product(input_t, hidden_t.clone());
+            let next_hidden_t = match self.activation.0 {
+                RnnActivation::Tanh => biased_input_sum.tanh(),
+                RnnActivation::Relu => activation::relu(biased_input_sum),
+            };
+
+            // padded timesteps keep the previous state
+            hidden_t = masked_update(hidden_t, next_hidden_t, mask.as_ref());
+
+            let unsqueezed_hidden_state =
+                masked_output(hidden_t.clone(), mask.as_ref()).unsqueeze_dim(1);
+
+            batched_hidden_state = batched_hidden_state.slice_assign(
+                [0..batch_size, t..(t + 1), 0..self.d_hidden],
+                unsqueezed_hidden_state,
+            );
+        }
+
+        (batched_hidden_state, hidden_t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Distribution, TensorData};
+    use crate::{TestBackend, module::Param, nn::LinearRecord};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn init_rnn(
+        activation: RnnActivation,
+        device: &<TestBackend as Backend>::Device,
+    ) -> Rnn<TestBackend> {
+        let mut rnn = RnnConfig::new(1, 1, true)
+            .with_activation(activation)
+            .init::<TestBackend>(device);
+
+        let input_record = LinearRecord {
+            weight: Param::from_data(TensorData::from([[0.5]]), device),
+            bias: Some(Param::from_data(TensorData::from([0.1]), device)),
+        };
+        let hidden_record = LinearRecord {
+            weight: Param::from_data(TensorData::from([[-0.8]]), device),
+            bias: Some(Param::from_data(TensorData::from([-0.2]), device)),
+        };
+        rnn.layers[0] = GateController::create_with_weights(
+            1,
+            1,
+            true,
+            Initializer::XavierNormal { gain: 1.0 },
+            input_record,
+            hidden_record,
+        );
+        rnn
+    }
+
+    /// h_1 = tanh(0.5*1.0 + 0.1 - 0.8*0 - 0.2) = tanh(0.4) = 0.3799
+    /// h_2 = tanh(0.5*2.0 + 0.1 - 0.8*0.3799 - 0.2) = tanh(0.5961) = 0.5342
+    #[test]
+    fn test_forward_tanh() {
+        let device = Default::default();
+        let rnn = init_rnn(RnnActivation::Tanh, &device);
+
+        let input = Tensor::<TestBackend, 3>::from_data([[[1.0], [2.0]]], &device);
+        let output = rnn.forward(input, None);
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[[0.3799], [0.5342]]]),
+            Tolerance::default(),
+        );
+    }
+
+    /// h_1 = relu(0.5*-1.0 + 0.1 - 0.8*0 - 0.2) = relu(-0.6) = 0.0
+    /// h_2 = relu(0.5*2.0 + 0.1 - 0.8*0 - 0.2) = relu(0.9) = 0.9
+    #[test]
+    fn test_forward_relu() {
+        let device = Default::default();
+        let rnn = init_rnn(RnnActivation::Relu, &device);
+
+        let input = Tensor::<TestBackend, 3>::from_data([[[-1.0], [2.0]]], &device);
+        let output = rnn.forward(input, None);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[[0.0], [0.9]]]), Tolerance::default());
+    }
+
+    #[test]
+    fn test_stacked_variable_lengths() {
+        let device = Default::default();
+        let rnn = RnnConfig::new(4, 8, true)
+            .with_num_layers(2)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let lengths = Tensor::<TestBackend, 1, Int>::from_ints([5, 3], &device);
+
+        let (output, state) = rnn.forward_layers(batched_input.clone(), None, Some(lengths));
+
+        assert_eq!(output.dims(), [2, 5, 8]);
+        assert_eq!(state.dims(), [2, 2, 8]);
+
+        let unpadded = batched_input.slice([1..2, 0..3]);
+        let (expected_output, expected_state) = rnn.forward_layers(unpadded, None, None);
+
+        output
+            .slice([1..2, 0..3])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_output.to_data(), Tolerance::default());
+        state
+            .slice([0..2, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn display() {
+        let config = RnnConfig::new(2, 8, true);
+
+        let layer = config.init::<TestBackend>(&Default::default());
+
+        assert_eq!(
+            alloc::format!("{layer}"),
+            "Rnn {d_input: 2, d_hidden: 8, bias: true, activation: Tanh, params: 96}"
+        );
+    }
+}
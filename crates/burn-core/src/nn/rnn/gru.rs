@@ -1,13 +1,16 @@
 use crate as burn;
 
+use alloc::vec::Vec;
+
 use super::gate_controller::GateController;
+use super::sequence::{masked_output, masked_update, timestep_mask, unstack_state};
 use crate::config::Config;
 use crate::module::Module;
 use crate::module::{Content, DisplaySettings, ModuleDisplay};
-use crate::nn::Initializer;
-use crate::tensor::Tensor;
+use crate::nn::{Dropout, DropoutConfig, Initializer};
 use crate::tensor::activation;
 use crate::tensor::backend::Backend;
+use crate::tensor::{Int, Tensor};
 
 /// Configuration to create a [gru](Gru) module using the [init function](GruConfig::init).
 #[derive(Config, Debug)]
@@ -36,6 +39,13 @@ pub struct GruConfig {
     /// Gru initializer
     #[config(default = "Initializer::XavierNormal{gain:1.0}")]
     pub initializer: Initializer,
+    /// The number of stacked layers, each layer takes the hidden states of the previous one as
+    /// input. Default: 1
+    #[config(default = 1)]
+    pub num_layers: usize,
+    /// The dropout rate applied to the outputs of each layer except the last one. Default: 0.0
+    #[config(default = 0.0)]
+    pub dropout: f64,
 }
 
 /// The Gru (Gated recurrent unit) module. This implementation is for a unidirectional, stateless, Gru.
@@ -56,6 +66,30 @@ pub struct Gru<B: Backend> {
     pub d_hidden: usize,
     /// If reset gate should be applied after weight multiplication.
     pub reset_after: bool,
+    /// The layers stacked on top of the first one.
+    pub stacked: Vec<GruLayer<B>>,
+    /// The dropout applied to the input of each stacked layer.
+    pub dropout: Dropout,
+}
+
+/// A layer of a [stacked](GruConfig::num_layers) [Gru], on top of the first one.
+#[derive(Module, Debug)]
+pub struct GruLayer<B: Backend> {
+    /// The update gate controller.
+    pub update_gate: GateController<B>,
+    /// The reset gate controller.
+    pub reset_gate: GateController<B>,
+    /// The new gate controller.
+    pub new_gate: GateController<B>,
+}
+
+/// The gates of a layer of a [Gru].
+struct GruGates<'a, B: Backend> {
+    update_gate: &'a GateController<B>,
+    reset_gate: &'a GateController<B>,
+    new_gate: &'a GateController<B>,
+    d_hidden: usize,
+    reset_after: bool,
 }
 
 impl<B: Backend> ModuleDisplay for Gru<B> {
@@ -81,36 +115,39 @@ impl<B: Backend> ModuleDisplay for Gru<B> {
 impl GruConfig {
     /// Initialize a new [gru](Gru) module.
     pub fn init<B: Backend>(&self, device: &B::Device) -> Gru<B> {
+        self.init_stacked(self.d_hidden, device)
+    }
+
+    /// Initialize the module with `d_stacked` input features for the stacked layers.
+    fn init_stacked<B: Backend>(&self, d_stacked: usize, device: &B::Device) -> Gru<B> {
+        assert!(self.num_layers > 0, "A Gru must have at least one layer");
+
         let d_output = self.d_hidden;
 
-        let update_gate = GateController::new(
-            self.d_input,
-            d_output,
-            self.bias,
-            self.initializer.clone(),
-            device,
-        );
-        let reset_gate = GateController::new(
-            self.d_input,
-            d_output,
-            self.bias,
-            self.initializer.clone(),
-            device,
-        );
-        let new_gate = GateController::new(
-            self.d_input,
-            d_output,
-            self.bias,
-            self.initializer.clone(),
-            device,
-        );
+        let new_gate = |d_input| {
+            GateController::new(
+                d_input,
+                d_output,
+                self.bias,
+                self.initializer.clone(),
+                device,
+            )
+        };
 
         Gru {
-            update_gate,
-            reset_gate,
-            new_gate,
+            update_gate: new_gate(self.d_input),
+            reset_gate: new_gate(self.d_input),
+            new_gate: new_gate(self.d_input),
             d_hidden: self.d_hidden,
             reset_after: self.reset_after,
+            stacked: (1..self.num_layers)
+                .map(|_| GruLayer {
+                    update_gate: new_gate(d_stacked),
+                    reset_gate: new_gate(d_stacked),
+                    new_gate: new_gate(d_stacked),
+                })
+                .collect(),
+            dropout: DropoutConfig::new(self.dropout).init(),
         }
     }
 }
@@ -126,51 +163,155 @@ impl<B: Backend> Gru<B> {
     ///
     /// # Returns
     /// - output: `[batch_size, sequence_length, hidden_size]`
+    ///
+    /// With stacked layers, the initial state is the one of the first layer, the other layers
+    /// start from an empty state, and the output of the last layer is returned. Use
+    /// [forward_layers](Gru::forward_layers) to provide or retrieve the state of every layer.
     pub fn forward(
         &self,
         batched_input: Tensor<B, 3>,
         state: Option<Tensor<B, 2>>,
     ) -> Tensor<B, 3> {
-        let device = batched_input.device();
-        let [batch_size, seq_length, _] = batched_input.shape().dims();
+        let mut states = (0..self.stacked.len()).map(|_| None).collect::<Vec<_>>();
+        states.insert(0, state);
+
+        let (output, _) = self.forward_states(batched_input, states, None);
+        output
+    }
 
+    /// Applies the forward pass on the input tensor through all the stacked layers, optionally
+    /// with variable-length sequences.
+    ///
+    /// # Parameters
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state of each layer with
+    ///   dimensions `[num_layers, batch_size, hidden_size]`. If none is provided, an empty state
+    ///   will be used.
+    /// - lengths: An optional tensor of shape `[batch_size]` with the length of each sequence.
+    ///   Timesteps past the length of a sequence are treated as padding: they don't update the
+    ///   state and their output is zero.
+    ///
+    /// # Returns
+    /// - output: The hidden states of the last layer `[batch_size, sequence_length, hidden_size]`
+    /// - state: The final hidden state of each layer `[num_layers, batch_size, hidden_size]`
+    pub fn forward_layers(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+        lengths: Option<Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Tensor<B, 3>) {
+        let states = unstack_state(state, self.stacked.len() + 1);
+        let (output, final_states) = self.forward_states(batched_input, states, lengths.as_ref());
+
+        (output, Tensor::stack(final_states, 0))
+    }
+
+    fn forward_states(
+        &self,
+        batched_input: Tensor<B, 3>,
+        states: Vec<Option<Tensor<B, 2>>>,
+        lengths: Option<&Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Vec<Tensor<B, 2>>) {
+        let mut output = batched_input;
+        let mut final_states = Vec::with_capacity(states.len());
+
+        for (i, (gates, state)) in self.layers().zip(states).enumerate() {
+            if i > 0 {
+                output = self.dropout.forward(output);
+            }
+
+            let device = output.device();
+            let [batch_size, seq_length, _] = output.dims();
+
+            let (layer_output, final_state) = gates.forward_iter(
+                output.iter_dim(1).zip(0..seq_length),
+                state,
+                lengths,
+                batch_size,
+                seq_length,
+                &device,
+            );
+
+            output = layer_output;
+            final_states.push(final_state);
+        }
+
+        (output, final_states)
+    }
+
+    /// The gates of each layer, starting with the first one.
+    fn layers(&self) -> impl Iterator<Item = GruGates<'_, B>> {
+        let first = GruGates {
+            update_gate: &self.update_gate,
+            reset_gate: &self.reset_gate,
+            new_gate: &self.new_gate,
+            d_hidden: self.d_hidden,
+            reset_after: self.reset_after,
+        };
+        let stacked = self.stacked.iter().map(|layer| GruGates {
+            update_gate: &layer.update_gate,
+            reset_gate: &layer.reset_gate,
+            new_gate: &layer.new_gate,
+            d_hidden: self.d_hidden,
+            reset_after: self.reset_after,
+        });
+
+        core::iter::once(first).chain(stacked)
+    }
+}
+
+impl<B: Backend> GruGates<'_, B> {
+    fn forward_iter<I: Iterator<Item = (Tensor<B, 3>, usize)>>(
+        &self,
+        input_timestep_iter: I,
+        state: Option<Tensor<B, 2>>,
+        lengths: Option<&Tensor<B, 1, Int>>,
+        batch_size: usize,
+        seq_length: usize,
+        device: &B::Device,
+    ) -> (Tensor<B, 3>, Tensor<B, 2>) {
         let mut batched_hidden_state =
-            Tensor::empty([batch_size, seq_length, self.d_hidden], &device);
+            Tensor::empty([batch_size, seq_length, self.d_hidden], device);
 
         let mut hidden_t = match state {
             Some(state) => state,
-            None => Tensor::zeros([batch_size, self.d_hidden], &device),
+            None => Tensor::zeros([batch_size, self.d_hidden], device),
         };
 
-        for (t, input_t) in batched_input.iter_dim(1).enumerate() {
+        for (input_t, t) in input_timestep_iter {
             let input_t = input_t.squeeze(1);
+            let mask = lengths.map(|lengths| timestep_mask(lengths, t, self.d_hidden));
+
             // u(pdate)g(ate) tensors
             let biased_ug_input_sum =
-                self.gate_product(&input_t, &hidden_t, None, &self.update_gate);
+                self.gate_product(&input_t, &hidden_t, None, self.update_gate);
             let update_values = activation::sigmoid(biased_ug_input_sum); // Colloquially referred to as z(t)
 
             // r(eset)g(ate) tensors
-            let biased_rg_input_sum =
-                self.gate_product(&input_t, &hidden_t, None, &self.reset_gate);
+            let biased_rg_input_sum = self.gate_product(&input_t, &hidden_t, None, self.reset_gate);
             let reset_values = activation::sigmoid(biased_rg_input_sum); // Colloquially referred to as r(t)
 
             // n(ew)g(ate) tensor
             let biased_ng_input_sum = if self.reset_after {
-                self.gate_product(&input_t, &hidden_t, Some(&reset_values), &self.new_gate)
+                self.gate_product(&input_t, &hidden_t, Some(&reset_values), self.new_gate)
             } else {
                 let reset_t = hidden_t.clone().mul(reset_values); // Passed as input to new_gate
-                self.gate_product(&input_t, &reset_t, None, &self.new_gate)
+                self.gate_product(&input_t, &reset_t, None, self.new_gate)
             };
             let candidate_state = biased_ng_input_sum.tanh(); // Colloquially referred to as g(t)
 
             // calculate linear interpolation between previous hidden state and candidate state:
             // g(t) * (1 - z(t)) + z(t) * hidden_t
-            hidden_t = candidate_state
+            let next_hidden_t = candidate_state
                 .clone()
                 .mul(update_values.clone().sub_scalar(1).mul_scalar(-1)) // (1 - z(t)) = -(z(t) - 1)
-                + update_values.clone().mul(hidden_t);
+                + update_values.clone().mul(hidden_t.clone());
 
-            let unsqueezed_hidden_state = hidden_t.clone().unsqueeze_dim(1);
+            // padded timesteps keep the previous state
+            hidden_t = masked_update(hidden_t, next_hidden_t, mask.as_ref());
+
+            let unsqueezed_hidden_state =
+                masked_output(hidden_t.clone(), mask.as_ref()).unsqueeze_dim(1);
 
             batched_hidden_state = batched_hidden_state.slice_assign(
                 [0..batch_size, t..(t + 1), 0..self.d_hidden],
@@ -178,7 +319,7 @@ impl<B: Backend> Gru<B> {
             );
         }
 
-        batched_hidden_state
+        (batched_hidden_state, hidden_t)
     }
 
     /// Helper function for performing weighted matrix product for a gate and adds
@@ -239,6 +380,175 @@ impl<B: Backend> Gru<B> {
     }
 }
 
+/// Configuration to create a [BiGru](BiGru) module using the [init function](BiGruConfig::init).
+#[derive(Config, Debug)]
+pub struct BiGruConfig {
+    /// The size of the input features.
+    pub d_input: usize,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+    /// If a bias should be applied during the BiGru transformation.
+    pub bias: bool,
+    /// If reset gate should be applied after weight multiplication.
+    ///
+    /// See [GruConfig::reset_after] for more details.
+    #[config(default = "true")]
+    pub reset_after: bool,
+    /// BiGru initializer
+    #[config(default = "Initializer::XavierNormal{gain:1.0}")]
+    pub initializer: Initializer,
+    /// The number of stacked layers, each layer takes the concatenated forward and reverse hidden
+    /// states of the previous one as input. Default: 1
+    #[config(default = 1)]
+    pub num_layers: usize,
+    /// The dropout rate applied to the outputs of each layer except the last one. Default: 0.0
+    #[config(default = 0.0)]
+    pub dropout: f64,
+}
+
+/// The BiGru module. This implementation is for Bidirectional GRU.
+///
+/// The input sequence is processed by a [Gru] in the forward direction and another one in the
+/// reverse direction, and their hidden states are concatenated.
+///
+/// With stacked layers, the [stacked](Gru::stacked) layers of each direction take the
+/// concatenated forward and reverse hidden states of the previous layer as input, after the
+/// [dropout](Gru::dropout) of their direction.
+///
+/// Should be created with [BiGruConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct BiGru<B: Backend> {
+    /// GRU for the forward direction.
+    pub forward: Gru<B>,
+    /// GRU for the reverse direction.
+    pub reverse: Gru<B>,
+    /// The size of the hidden state.
+    pub d_hidden: usize,
+}
+
+impl<B: Backend> ModuleDisplay for BiGru<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_input, _] = self
+            .forward
+            .update_gate
+            .input_transform
+            .weight
+            .shape()
+            .dims();
+        let bias = self.forward.update_gate.input_transform.bias.is_some();
+
+        content
+            .add("d_input", &d_input)
+            .add("d_hidden", &self.d_hidden)
+            .add("bias", &bias)
+            .add("reset_after", &self.forward.reset_after)
+            .optional()
+    }
+}
+
+impl BiGruConfig {
+    /// Initialize a new [Bidirectional GRU](BiGru) module.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> BiGru<B> {
+        assert!(self.num_layers > 0, "A BiGru must have at least one layer");
+
+        let config = GruConfig::new(self.d_input, self.d_hidden, self.bias)
+            .with_reset_after(self.reset_after)
+            .with_initializer(self.initializer.clone())
+            .with_num_layers(self.num_layers)
+            .with_dropout(self.dropout);
+
+        BiGru {
+            forward: config.init_stacked(2 * self.d_hidden, device),
+            reverse: config.init_stacked(2 * self.d_hidden, device),
+            d_hidden: self.d_hidden,
+        }
+    }
+}
+
+impl<B: Backend> BiGru<B> {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Parameters
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state with dimensions
+    ///   `[2 * num_layers, batch_size, hidden_size]`, where the forward and reverse states of
+    ///   layer `l` are at index `2 * l` and `2 * l + 1`. If none is provided, an empty state
+    ///   will be used.
+    ///
+    /// # Returns
+    /// - output: `[batch_size, sequence_length, hidden_size * 2]`
+    /// - state: The final forward and reverse hidden states `[2 * num_layers, batch_size, hidden_size]`
+    pub fn forward(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+    ) -> (Tensor<B, 3>, Tensor<B, 3>) {
+        self.forward_layers(batched_input, state, None)
+    }
+
+    /// Applies the forward pass on the input tensor with variable-length sequences.
+    ///
+    /// Same as [forward](BiGru::forward), with an optional `lengths` tensor of shape
+    /// `[batch_size]`. Timesteps past the length of a sequence are treated as padding: they
+    /// don't update the state and their output is zero. The reverse direction of each sequence
+    /// starts at its last valid timestep.
+    pub fn forward_layers(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 3>>,
+        lengths: Option<Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Tensor<B, 3>) {
+        let num_layers = self.forward.stacked.len() + 1;
+        let mut states = unstack_state(state, 2 * num_layers).into_iter();
+
+        let mut output = batched_input;
+        let mut final_states = Vec::with_capacity(2 * num_layers);
+
+        let layers = self.forward.layers().zip(self.reverse.layers());
+        for (i, (gates_forward, gates_reverse)) in layers.enumerate() {
+            let device = output.device();
+            let [batch_size, seq_length, _] = output.dims();
+            let (input_forward, input_reverse) = match i {
+                0 => (output.clone(), output),
+                _ => (
+                    self.forward.dropout.forward(output.clone()),
+                    self.reverse.dropout.forward(output),
+                ),
+            };
+
+            let (output_forward, final_forward) = gates_forward.forward_iter(
+                input_forward.iter_dim(1).zip(0..seq_length),
+                states.next().flatten(),
+                lengths.as_ref(),
+                batch_size,
+                seq_length,
+                &device,
+            );
+            let (output_reverse, final_reverse) = gates_reverse.forward_iter(
+                input_reverse.iter_dim(1).rev().zip((0..seq_length).rev()),
+                states.next().flatten(),
+                lengths.as_ref(),
+                batch_size,
+                seq_length,
+                &device,
+            );
+
+            output = Tensor::cat([output_forward, output_reverse].to_vec(), 2);
+            final_states.push(final_forward);
+            final_states.push(final_reverse);
+        }
+
+        (output, Tensor::stack(final_states, 0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,6 +706,140 @@ mod tests {
         assert_eq!(hidden_state.shape().dims, [8, 10, 1024]);
     }
 
+    #[test]
+    fn test_stacked_layers() {
+        let device = Default::default();
+        let gru = GruConfig::new(4, 8, true)
+            .with_num_layers(2)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+
+        let (output, state) = gru.forward_layers(batched_input.clone(), None, None);
+
+        assert_eq!(output.dims(), [2, 5, 8]);
+        assert_eq!(state.dims(), [2, 2, 8]);
+
+        // Each layer takes the output of the previous one as input.
+        let mut expected = batched_input.clone();
+        for gates in gru.layers() {
+            let (layer_output, _) =
+                gates.forward_iter(expected.iter_dim(1).zip(0..5), None, None, 2, 5, &device);
+            expected = layer_output;
+        }
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected.to_data(), Tolerance::default());
+        gru.forward(batched_input, None)
+            .to_data()
+            .assert_approx_eq::<FT>(&expected.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_stacked_layers_with_initial_state() {
+        let device = Default::default();
+        let gru = GruConfig::new(4, 8, true)
+            .with_num_layers(2)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let state = Tensor::<TestBackend, 2>::random([2, 8], Distribution::Default, &device);
+
+        // The initial state is the one of the first layer.
+        let output = gru.forward(batched_input.clone(), Some(state.clone()));
+        let states = Tensor::stack(vec![state.clone(), state.zeros_like()], 0);
+        let (expected, _) = gru.forward_layers(batched_input, Some(states), None);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_variable_lengths_should_ignore_padding() {
+        let device = Default::default();
+        let gru = GruConfig::new(4, 8, true).init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let lengths = Tensor::<TestBackend, 1, Int>::from_ints([5, 3], &device);
+
+        let (output, state) = gru.forward_layers(batched_input.clone(), None, Some(lengths));
+
+        // The second sequence without its padding.
+        let unpadded = batched_input.slice([1..2, 0..3]);
+        let (expected_output, expected_state) = gru.forward_layers(unpadded, None, None);
+
+        output
+            .clone()
+            .slice([1..2, 0..3])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_output.to_data(), Tolerance::default());
+        output.slice([1..2, 3..5]).to_data().assert_approx_eq::<FT>(
+            &TensorData::zeros::<f32, _>([1, 2, 8]),
+            Tolerance::default(),
+        );
+        state
+            .slice([0..1, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_bidirectional() {
+        let device = Default::default();
+        let gru = BiGruConfig::new(4, 8, true)
+            .with_num_layers(2)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+
+        let (output, state) = gru.forward(batched_input.clone(), None);
+
+        assert_eq!(output.dims(), [2, 5, 16]);
+        assert_eq!(state.dims(), [4, 2, 8]);
+
+        // The reverse direction of the first layer processes the reversed sequence.
+        let reverse = gru.reverse.layers().next().unwrap();
+        let (_, expected_reverse) = reverse.forward_iter(
+            batched_input.flip([1]).iter_dim(1).zip(0..5),
+            None,
+            None,
+            2,
+            5,
+            &device,
+        );
+
+        state
+            .slice([1..2])
+            .squeeze::<2>(0)
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_reverse.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_bidirectional_variable_lengths_should_ignore_padding() {
+        let device = Default::default();
+        let gru = BiGruConfig::new(4, 8, true).init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let lengths = Tensor::<TestBackend, 1, Int>::from_ints([5, 3], &device);
+
+        let (output, state) = gru.forward_layers(batched_input.clone(), None, Some(lengths));
+
+        let unpadded = batched_input.slice([1..2, 0..3]);
+        let (expected_output, expected_state) = gru.forward(unpadded, None);
+
+        output
+            .slice([1..2, 0..3])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_output.to_data(), Tolerance::default());
+        state
+            .slice([0..2, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.to_data(), Tolerance::default());
+    }
+
     #[test]
     fn display() {
         let config = GruConfig::new(2, 8, true);
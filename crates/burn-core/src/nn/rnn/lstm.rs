@@ -1,13 +1,16 @@
 use crate as burn;
 
+use alloc::vec::Vec;
+
+use super::sequence::{masked_output, masked_update, timestep_mask, unstack_state};
 use crate::config::Config;
 use crate::module::Module;
 use crate::module::{Content, DisplaySettings, ModuleDisplay};
-use crate::nn::Initializer;
 use crate::nn::rnn::gate_controller::GateController;
-use crate::tensor::Tensor;
+use crate::nn::{Dropout, DropoutConfig, Initializer};
 use crate::tensor::activation;
 use crate::tensor::backend::Backend;
+use crate::tensor::{Int, Tensor};
 
 /// A LstmState is used to store cell state and hidden state in LSTM.
 pub struct LstmState<B: Backend, const D: usize> {
@@ -24,6 +27,35 @@ impl<B: Backend, const D: usize> LstmState<B, D> {
     }
 }
 
+impl<B: Backend> LstmState<B, 3> {
+    /// Split a stacked state of shape `[num_states, batch_size, hidden_size]`.
+    fn unstack(state: Option<Self>, num_states: usize) -> Vec<Option<LstmState<B, 2>>> {
+        let (cells, hiddens) = match state {
+            Some(state) => (Some(state.cell), Some(state.hidden)),
+            None => (None, None),
+        };
+
+        unstack_state(cells, num_states)
+            .into_iter()
+            .zip(unstack_state(hiddens, num_states))
+            .map(|(cell, hidden)| match (cell, hidden) {
+                (Some(cell), Some(hidden)) => Some(LstmState::new(cell, hidden)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Stack the states of each layer (and direction) along the first dimension.
+    fn stack(states: Vec<LstmState<B, 2>>) -> Self {
+        let (cells, hiddens): (Vec<_>, Vec<_>) = states
+            .into_iter()
+            .map(|state| (state.cell, state.hidden))
+            .unzip();
+
+        Self::new(Tensor::stack(cells, 0), Tensor::stack(hiddens, 0))
+    }
+}
+
 /// Configuration to create a [Lstm](Lstm) module using the [init function](LstmConfig::init).
 #[derive(Config, Debug)]
 pub struct LstmConfig {
@@ -36,6 +68,13 @@ pub struct LstmConfig {
     /// Lstm initializer
     #[config(default = "Initializer::XavierNormal{gain:1.0}")]
     pub initializer: Initializer,
+    /// The number of stacked layers, each layer takes the hidden states of the previous one as
+    /// input. Default: 1
+    #[config(default = 1)]
+    pub num_layers: usize,
+    /// The dropout rate applied to the outputs of each layer except the last one. Default: 0.0
+    #[config(default = 0.0)]
+    pub dropout: f64,
 }
 
 /// The Lstm module. This implementation is for a unidirectional, stateless, Lstm.
@@ -56,6 +95,32 @@ pub struct Lstm<B: Backend> {
     pub cell_gate: GateController<B>,
     /// The hidden state of the LSTM.
     pub d_hidden: usize,
+    /// The layers stacked on top of the first one.
+    pub stacked: Vec<LstmLayer<B>>,
+    /// The dropout applied to the input of each stacked layer.
+    pub dropout: Dropout,
+}
+
+/// A layer of a [stacked](LstmConfig::num_layers) [Lstm], on top of the first one.
+#[derive(Module, Debug)]
+pub struct LstmLayer<B: Backend> {
+    /// The input gate.
+    pub input_gate: GateController<B>,
+    /// The forget gate.
+    pub forget_gate: GateController<B>,
+    /// The output gate.
+    pub output_gate: GateController<B>,
+    /// The cell gate.
+    pub cell_gate: GateController<B>,
+}
+
+/// The gates of a layer of an [Lstm].
+struct LstmGates<'a, B: Backend> {
+    input_gate: &'a GateController<B>,
+    forget_gate: &'a GateController<B>,
+    output_gate: &'a GateController<B>,
+    cell_gate: &'a GateController<B>,
+    d_hidden: usize,
 }
 
 impl<B: Backend> ModuleDisplay for Lstm<B> {
@@ -80,11 +145,18 @@ impl<B: Backend> ModuleDisplay for Lstm<B> {
 impl LstmConfig {
     /// Initialize a new [lstm](Lstm) module.
     pub fn init<B: Backend>(&self, device: &B::Device) -> Lstm<B> {
+        self.init_stacked(self.d_hidden, device)
+    }
+
+    /// Initialize the module with `d_stacked` input features for the stacked layers.
+    fn init_stacked<B: Backend>(&self, d_stacked: usize, device: &B::Device) -> Lstm<B> {
+        assert!(self.num_layers > 0, "An Lstm must have at least one layer");
+
         let d_output = self.d_hidden;
 
-        let new_gate = || {
+        let new_gate = |d_input| {
             GateController::new(
-                self.d_input,
+                d_input,
                 d_output,
                 self.bias,
                 self.initializer.clone(),
@@ -93,11 +165,20 @@ impl LstmConfig {
         };
 
         Lstm {
-            input_gate: new_gate(),
-            forget_gate: new_gate(),
-            output_gate: new_gate(),
-            cell_gate: new_gate(),
+            input_gate: new_gate(self.d_input),
+            forget_gate: new_gate(self.d_input),
+            output_gate: new_gate(self.d_input),
+            cell_gate: new_gate(self.d_input),
             d_hidden: self.d_hidden,
+            stacked: (1..self.num_layers)
+                .map(|_| LstmLayer {
+                    input_gate: new_gate(d_stacked),
+                    forget_gate: new_gate(d_stacked),
+                    output_gate: new_gate(d_stacked),
+                    cell_gate: new_gate(d_stacked),
+                })
+                .collect(),
+            dropout: DropoutConfig::new(self.dropout).init(),
         }
     }
 }
@@ -116,27 +197,112 @@ impl<B: Backend> Lstm<B> {
     /// - output: A tensor represents the output features of LSTM. Shape: `[batch_size, sequence_length, hidden_size]`
     /// - state: A `LstmState` represents the final states. Both `state.cell` and `state.hidden` have the shape
     ///   `[batch_size, hidden_size]`.
+    ///
+    /// With stacked layers, the initial state is the one of the first layer, the other layers
+    /// start from zeros, and the final state of the last layer is returned. Use
+    /// [forward_layers](Lstm::forward_layers) to provide or retrieve the state of every layer.
     pub fn forward(
         &self,
         batched_input: Tensor<B, 3>,
         state: Option<LstmState<B, 2>>,
     ) -> (Tensor<B, 3>, LstmState<B, 2>) {
-        let device = batched_input.device();
-        let [batch_size, seq_length, _] = batched_input.dims();
-
-        self.forward_iter(
-            batched_input.iter_dim(1).zip(0..seq_length),
-            state,
-            batch_size,
-            seq_length,
-            &device,
-        )
+        let mut states = (0..self.stacked.len()).map(|_| None).collect::<Vec<_>>();
+        states.insert(0, state);
+
+        let (output, mut final_states) = self.forward_states(batched_input, states, None);
+        let state = final_states.pop().expect("An Lstm has at least one layer");
+
+        (output, state)
     }
 
+    /// Applies the forward pass on the input tensor through all the stacked layers, optionally
+    /// with variable-length sequences.
+    ///
+    /// ## Parameters:
+    /// - batched_input: The input tensor of shape `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional `LstmState` representing the initial cell state and hidden state of
+    ///   each layer. Each state tensor has shape `[num_layers, batch_size, hidden_size]`.
+    ///   If no initial state is provided, these tensors are initialized to zeros.
+    /// - lengths: An optional tensor of shape `[batch_size]` with the length of each sequence.
+    ///   Timesteps past the length of a sequence are treated as padding: they don't update the
+    ///   state and their output is zero.
+    ///
+    /// ## Returns:
+    /// - output: The output features of the last layer. Shape: `[batch_size, sequence_length, hidden_size]`
+    /// - state: The final states of each layer. Both `state.cell` and `state.hidden` have the shape
+    ///   `[num_layers, batch_size, hidden_size]`.
+    pub fn forward_layers(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<LstmState<B, 3>>,
+        lengths: Option<Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, LstmState<B, 3>) {
+        let states = LstmState::unstack(state, self.stacked.len() + 1);
+        let (output, final_states) = self.forward_states(batched_input, states, lengths.as_ref());
+
+        (output, LstmState::stack(final_states))
+    }
+
+    fn forward_states(
+        &self,
+        batched_input: Tensor<B, 3>,
+        states: Vec<Option<LstmState<B, 2>>>,
+        lengths: Option<&Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, Vec<LstmState<B, 2>>) {
+        let mut output = batched_input;
+        let mut final_states = Vec::with_capacity(states.len());
+
+        for (i, (gates, state)) in self.layers().zip(states).enumerate() {
+            if i > 0 {
+                output = self.dropout.forward(output);
+            }
+
+            let device = output.device();
+            let [batch_size, seq_length, _] = output.dims();
+
+            let (layer_output, final_state) = gates.forward_iter(
+                output.iter_dim(1).zip(0..seq_length),
+                state,
+                lengths,
+                batch_size,
+                seq_length,
+                &device,
+            );
+
+            output = layer_output;
+            final_states.push(final_state);
+        }
+
+        (output, final_states)
+    }
+
+    /// The gates of each layer, starting with the first one.
+    fn layers(&self) -> impl Iterator<Item = LstmGates<'_, B>> {
+        let first = LstmGates {
+            input_gate: &self.input_gate,
+            forget_gate: &self.forget_gate,
+            output_gate: &self.output_gate,
+            cell_gate: &self.cell_gate,
+            d_hidden: self.d_hidden,
+        };
+        let stacked = self.stacked.iter().map(|layer| LstmGates {
+            input_gate: &layer.input_gate,
+            forget_gate: &layer.forget_gate,
+            output_gate: &layer.output_gate,
+            cell_gate: &layer.cell_gate,
+            d_hidden: self.d_hidden,
+        });
+
+        core::iter::once(first).chain(stacked)
+    }
+}
+
+impl<B: Backend> LstmGates<'_, B> {
     fn forward_iter<I: Iterator<Item = (Tensor<B, 3>, usize)>>(
         &self,
         input_timestep_iter: I,
         state: Option<LstmState<B, 2>>,
+        lengths: Option<&Tensor<B, 1, Int>>,
         batch_size: usize,
         seq_length: usize,
         device: &B::Device,
@@ -154,6 +320,8 @@ impl<B: Backend> Lstm<B> {
 
         for (input_t, t) in input_timestep_iter {
             let input_t = input_t.squeeze(1);
+            let mask = lengths.map(|lengths| timestep_mask(lengths, t, self.d_hidden));
+
             // f(orget)g(ate) tensors
             let biased_fg_input_sum = self
                 .forget_gate
@@ -178,10 +346,16 @@ impl<B: Backend> Lstm<B> {
                 .gate_product(input_t.clone(), hidden_state.clone());
             let candidate_cell_values = biased_cg_input_sum.tanh();
 
-            cell_state = forget_values * cell_state.clone() + add_values * candidate_cell_values;
-            hidden_state = output_values * cell_state.clone().tanh();
+            let next_cell_state =
+                forget_values * cell_state.clone() + add_values * candidate_cell_values;
+            let next_hidden_state = output_values * next_cell_state.clone().tanh();
+
+            // padded timesteps keep the previous state
+            cell_state = masked_update(cell_state, next_cell_state, mask.as_ref());
+            hidden_state = masked_update(hidden_state, next_hidden_state, mask.as_ref());
 
-            let unsqueezed_hidden_state = hidden_state.clone().unsqueeze_dim(1);
+            let unsqueezed_hidden_state =
+                masked_output(hidden_state.clone(), mask.as_ref()).unsqueeze_dim(1);
 
             // store the hidden state for this timestep
             batched_hidden_state = batched_hidden_state.slice_assign(
@@ -209,12 +383,23 @@ pub struct BiLstmConfig {
     /// BiLstm initializer
     #[config(default = "Initializer::XavierNormal{gain:1.0}")]
     pub initializer: Initializer,
+    /// The number of stacked layers, each layer takes the concatenated forward and reverse hidden
+    /// states of the previous one as input. Default: 1
+    #[config(default = 1)]
+    pub num_layers: usize,
+    /// The dropout rate applied to the outputs of each layer except the last one. Default: 0.0
+    #[config(default = 0.0)]
+    pub dropout: f64,
 }
 
 /// The BiLstm module. This implementation is for Bidirectional LSTM.
 ///
 /// Introduced in the paper: [Framewise phoneme classification with bidirectional LSTM and other neural network architectures](https://www.cs.toronto.edu/~graves/ijcnn_2005.pdf).
 ///
+/// With stacked layers, the [stacked](Lstm::stacked) layers of each direction take the
+/// concatenated forward and reverse hidden states of the previous layer as input, after the
+/// [dropout](Lstm::dropout) of their direction.
+///
 /// Should be created with [BiLstmConfig].
 #[derive(Module, Debug)]
 #[module(custom_display)]
@@ -255,13 +440,16 @@ impl<B: Backend> ModuleDisplay for BiLstm<B> {
 impl BiLstmConfig {
     /// Initialize a new [Bidirectional LSTM](BiLstm) module.
     pub fn init<B: Backend>(&self, device: &B::Device) -> BiLstm<B> {
+        assert!(self.num_layers > 0, "A BiLstm must have at least one layer");
+
+        let config = LstmConfig::new(self.d_input, self.d_hidden, self.bias)
+            .with_initializer(self.initializer.clone())
+            .with_num_layers(self.num_layers)
+            .with_dropout(self.dropout);
+
         BiLstm {
-            forward: LstmConfig::new(self.d_input, self.d_hidden, self.bias)
-                .with_initializer(self.initializer.clone())
-                .init(device),
-            reverse: LstmConfig::new(self.d_input, self.d_hidden, self.bias)
-                .with_initializer(self.initializer.clone())
-                .init(device),
+            forward: config.init_stacked(2 * self.d_hidden, device),
+            reverse: config.init_stacked(2 * self.d_hidden, device),
             d_hidden: self.d_hidden,
         }
     }
@@ -274,81 +462,81 @@ impl<B: Backend> BiLstm<B> {
     /// ## Parameters:
     /// - batched_input: The input tensor of shape `[batch_size, sequence_length, input_size]`.
     /// - state: An optional `LstmState` representing the initial cell state and hidden state.
-    ///   Each state tensor has shape `[2, batch_size, hidden_size]`.
+    ///   Each state tensor has shape `[2 * num_layers, batch_size, hidden_size]`, where the
+    ///   forward and reverse states of layer `l` are at index `2 * l` and `2 * l + 1`.
     ///   If no initial state is provided, these tensors are initialized to zeros.
     ///
     /// ## Returns:
     /// - output: A tensor represents the output features of LSTM. Shape: `[batch_size, sequence_length, hidden_size * 2]`
     /// - state: A `LstmState` represents the final forward and reverse states. Both `state.cell` and
-    ///   `state.hidden` have the shape `[2, batch_size, hidden_size]`.
+    ///   `state.hidden` have the shape `[2 * num_layers, batch_size, hidden_size]`.
     pub fn forward(
         &self,
         batched_input: Tensor<B, 3>,
         state: Option<LstmState<B, 3>>,
     ) -> (Tensor<B, 3>, LstmState<B, 3>) {
-        let device = batched_input.clone().device();
-        let [batch_size, seq_length, _] = batched_input.shape().dims();
-
-        let [init_state_forward, init_state_reverse] = match state {
-            Some(state) => {
-                let cell_state_forward = state
-                    .cell
-                    .clone()
-                    .slice([0..1, 0..batch_size, 0..self.d_hidden])
-                    .squeeze(0);
-                let hidden_state_forward = state
-                    .hidden
-                    .clone()
-                    .slice([0..1, 0..batch_size, 0..self.d_hidden])
-                    .squeeze(0);
-                let cell_state_reverse = state
-                    .cell
-                    .slice([1..2, 0..batch_size, 0..self.d_hidden])
-                    .squeeze(0);
-                let hidden_state_reverse = state
-                    .hidden
-                    .slice([1..2, 0..batch_size, 0..self.d_hidden])
-                    .squeeze(0);
-
-                [
-                    Some(LstmState::new(cell_state_forward, hidden_state_forward)),
-                    Some(LstmState::new(cell_state_reverse, hidden_state_reverse)),
-                ]
-            }
-            None => [None, None],
-        };
+        self.forward_layers(batched_input, state, None)
+    }
 
-        // forward direction
-        let (batched_hidden_state_forward, final_state_forward) = self
-            .forward
-            .forward(batched_input.clone(), init_state_forward);
-
-        // reverse direction
-        let (batched_hidden_state_reverse, final_state_reverse) = self.reverse.forward_iter(
-            batched_input.iter_dim(1).rev().zip((0..seq_length).rev()),
-            init_state_reverse,
-            batch_size,
-            seq_length,
-            &device,
-        );
+    /// Applies the forward pass on the input tensor with variable-length sequences.
+    ///
+    /// Same as [forward](BiLstm::forward), with an optional `lengths` tensor of shape
+    /// `[batch_size]`. Timesteps past the length of a sequence are treated as padding: they
+    /// don't update the state and their output is zero. The reverse direction of each sequence
+    /// starts at its last valid timestep.
+    pub fn forward_layers(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<LstmState<B, 3>>,
+        lengths: Option<Tensor<B, 1, Int>>,
+    ) -> (Tensor<B, 3>, LstmState<B, 3>) {
+        let num_layers = self.forward.stacked.len() + 1;
+        let mut states = LstmState::unstack(state, 2 * num_layers).into_iter();
+
+        let mut output = batched_input;
+        let mut final_states = Vec::with_capacity(2 * num_layers);
+
+        let layers = self.forward.layers().zip(self.reverse.layers());
+        for (i, (gates_forward, gates_reverse)) in layers.enumerate() {
+            let device = output.device();
+            let [batch_size, seq_length, _] = output.dims();
+            let (input_forward, input_reverse) = match i {
+                0 => (output.clone(), output),
+                _ => (
+                    self.forward.dropout.forward(output.clone()),
+                    self.reverse.dropout.forward(output),
+                ),
+            };
 
-        let output = Tensor::cat(
-            [batched_hidden_state_forward, batched_hidden_state_reverse].to_vec(),
-            2,
-        );
+            // forward direction
+            let (batched_hidden_state_forward, final_state_forward) = gates_forward.forward_iter(
+                input_forward.iter_dim(1).zip(0..seq_length),
+                states.next().flatten(),
+                lengths.as_ref(),
+                batch_size,
+                seq_length,
+                &device,
+            );
 
-        let state = LstmState::new(
-            Tensor::stack(
-                [final_state_forward.cell, final_state_reverse.cell].to_vec(),
-                0,
-            ),
-            Tensor::stack(
-                [final_state_forward.hidden, final_state_reverse.hidden].to_vec(),
-                0,
-            ),
-        );
+            // reverse direction
+            let (batched_hidden_state_reverse, final_state_reverse) = gates_reverse.forward_iter(
+                input_reverse.iter_dim(1).rev().zip((0..seq_length).rev()),
+                states.next().flatten(),
+                lengths.as_ref(),
+                batch_size,
+                seq_length,
+                &device,
+            );
 
-        (output, state)
+            output = Tensor::cat(
+                [batched_hidden_state_forward, batched_hidden_state_reverse].to_vec(),
+                2,
+            );
+            final_states.push(final_state_forward);
+            final_states.push(final_state_reverse);
+        }
+
+        (output, LstmState::stack(final_states))
     }
 }
 
@@ -757,6 +945,132 @@ mod tests {
             .assert_approx_eq::<FT>(&expected_cn_without_init_state, tolerance);
     }
 
+    #[test]
+    fn test_stacked_layers() {
+        let device = Default::default();
+        let lstm = LstmConfig::new(4, 8, true)
+            .with_num_layers(3)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+
+        let (output, state) = lstm.forward_layers(batched_input.clone(), None, None);
+
+        assert_eq!(output.dims(), [2, 5, 8]);
+        assert_eq!(state.cell.dims(), [3, 2, 8]);
+        assert_eq!(state.hidden.dims(), [3, 2, 8]);
+
+        // Each layer takes the output of the previous one as input.
+        let mut expected = batched_input.clone();
+        let mut last_state = None;
+        for gates in lstm.layers() {
+            let (layer_output, state) =
+                gates.forward_iter(expected.iter_dim(1).zip(0..5), None, None, 2, 5, &device);
+            expected = layer_output;
+            last_state = Some(state);
+        }
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected.to_data(), Tolerance::default());
+
+        let (_, state) = lstm.forward(batched_input, None);
+        state
+            .hidden
+            .to_data()
+            .assert_approx_eq::<FT>(&last_state.unwrap().hidden.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_variable_lengths_should_ignore_padding() {
+        let device = Default::default();
+        let lstm = LstmConfig::new(4, 8, true)
+            .with_num_layers(2)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let lengths = Tensor::<TestBackend, 1, Int>::from_ints([5, 3], &device);
+
+        let (output, state) = lstm.forward_layers(batched_input.clone(), None, Some(lengths));
+
+        // The second sequence without its padding.
+        let unpadded = batched_input.slice([1..2, 0..3]);
+        let (expected_output, expected_state) = lstm.forward_layers(unpadded, None, None);
+
+        output
+            .clone()
+            .slice([1..2, 0..3])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_output.to_data(), Tolerance::default());
+        output.slice([1..2, 3..5]).to_data().assert_approx_eq::<FT>(
+            &TensorData::zeros::<f32, _>([1, 2, 8]),
+            Tolerance::default(),
+        );
+        state
+            .hidden
+            .slice([0..2, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.hidden.to_data(), Tolerance::default());
+        state
+            .cell
+            .slice([0..2, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.cell.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_variable_lengths_should_ignore_non_finite_padding() {
+        let device = Default::default();
+        let lstm = LstmConfig::new(4, 8, true).init::<TestBackend>(&device);
+        let input = Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let padding = Tensor::full([1, 2, 4], f32::NAN, &device);
+        let batched_input = input.clone().slice_assign([1..2, 3..5, 0..4], padding);
+        let lengths = Tensor::<TestBackend, 1, Int>::from_ints([5, 3], &device);
+
+        let (output, state) = lstm.forward_layers(batched_input, None, Some(lengths));
+
+        let (_, expected_state) = lstm.forward_layers(input.slice([1..2, 0..3]), None, None);
+        output.slice([1..2, 3..5]).to_data().assert_approx_eq::<FT>(
+            &TensorData::zeros::<f32, _>([1, 2, 8]),
+            Tolerance::default(),
+        );
+        state
+            .cell
+            .slice([0..1, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.cell.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_bidirectional_variable_lengths_should_ignore_padding() {
+        let device = Default::default();
+        let lstm = BiLstmConfig::new(4, 8, true)
+            .with_num_layers(2)
+            .init::<TestBackend>(&device);
+        let batched_input =
+            Tensor::<TestBackend, 3>::random([2, 5, 4], Distribution::Default, &device);
+        let lengths = Tensor::<TestBackend, 1, Int>::from_ints([5, 3], &device);
+
+        let (output, state) = lstm.forward_layers(batched_input.clone(), None, Some(lengths));
+
+        assert_eq!(output.dims(), [2, 5, 16]);
+        assert_eq!(state.hidden.dims(), [4, 2, 8]);
+
+        // The reverse direction should start at the last valid timestep of the sequence.
+        let unpadded = batched_input.slice([1..2, 0..3]);
+        let (expected_output, expected_state) = lstm.forward(unpadded, None);
+
+        output
+            .slice([1..2, 0..3])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_output.to_data(), Tolerance::default());
+        state
+            .hidden
+            .slice([0..4, 1..2])
+            .to_data()
+            .assert_approx_eq::<FT>(&expected_state.hidden.to_data(), Tolerance::default());
+    }
+
     #[test]
     fn display_lstm() {
         let config = LstmConfig::new(2, 3, true);
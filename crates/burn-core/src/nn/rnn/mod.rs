@@ -1,4 +1,8 @@
 mod gate_controller;
+mod sequence;
+
+/// Elman recurrent neural network module.
+pub mod elman;
 
 /// Gated Recurrent Unit module.
 pub mod gru;
@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+
+use crate::tensor::{Bool, Int, Tensor, backend::Backend};
+
+/// Mask of the sequences still active at timestep `t`, with shape `[batch_size, d_hidden]`.
+///
+/// A sequence of length `l` is active for timesteps `0..l`, values are `true` for active
+/// sequences and `false` for padded ones.
+pub(crate) fn timestep_mask<B: Backend>(
+    lengths: &Tensor<B, 1, Int>,
+    t: usize,
+    d_hidden: usize,
+) -> Tensor<B, 2, Bool> {
+    let [batch_size] = lengths.dims();
+
+    lengths
+        .clone()
+        .greater_elem(t as i64)
+        .unsqueeze_dim::<2>(1)
+        .expand([batch_size, d_hidden])
+}
+
+/// Only update the state of the active sequences, padded sequences keep their previous state.
+///
+/// This gives the same semantics as packed sequences: the padding never pollutes the hidden
+/// state, even when it produces non-finite values, and the final state of each sequence is the
+/// one at its last valid timestep.
+pub(crate) fn masked_update<B: Backend>(
+    previous: Tensor<B, 2>,
+    next: Tensor<B, 2>,
+    mask: Option<&Tensor<B, 2, Bool>>,
+) -> Tensor<B, 2> {
+    match mask {
+        Some(mask) => previous.mask_where(mask.clone(), next),
+        None => next,
+    }
+}
+
+/// Zero the outputs of padded timesteps.
+pub(crate) fn masked_output<B: Backend>(
+    output: Tensor<B, 2>,
+    mask: Option<&Tensor<B, 2, Bool>>,
+) -> Tensor<B, 2> {
+    match mask {
+        Some(mask) => output.mask_fill(mask.clone().bool_not(), 0.0),
+        None => output,
+    }
+}
+
+/// Split a stacked state of shape `[num_states, batch_size, d_hidden]` into one state per layer
+/// (and direction).
+pub(crate) fn unstack_state<B: Backend>(
+    state: Option<Tensor<B, 3>>,
+    num_states: usize,
+) -> Vec<Option<Tensor<B, 2>>> {
+    match state {
+        Some(state) => {
+            assert_eq!(
+                state.dims()[0],
+                num_states,
+                "The initial state should contain one entry per layer and direction"
+            );
+            state
+                .iter_dim(0)
+                .map(|state| Some(state.squeeze(0)))
+                .collect()
+        }
+        None => (0..num_states).map(|_| None).collect(),
+    }
+}
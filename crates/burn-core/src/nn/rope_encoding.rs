@@ -1,6 +1,7 @@
 use core::ops::Range;
 
 use crate as burn;
+use crate::amp::cast_to;
 use crate::config::Config;
 use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
 use crate::tensor::Int;
@@ -170,6 +171,8 @@ impl<B: Backend> RotaryEncoding<B> {
 
         let device = x.device();
         let input_shape = x.shape();
+        // The frequencies are cast to the type of the input, which is reduced when autocasting.
+        let x_dtype = x.dtype();
 
         // Extract the sequence length and embedding dimension, other dimensions are kept generic
         // to allow both 3D and 4D tensors i.e. batch_size or (batch_size, num_heads)
@@ -180,17 +183,18 @@ impl<B: Backend> RotaryEncoding<B> {
         // [[cos, -sin], [sin, cos]]
         let sign_tensor =
             Tensor::<B, 2>::from_floats([[1.0, 0.0, 0.0, 1.0], [0.0, -1.0, 1.0, 0.0]], &device);
+        let sign_tensor = cast_to(sign_tensor, x_dtype);
 
         // Rotate input using the frequency tensor. Slice the frequencies till input sequence length
         let out: Tensor<B, 4> = x
             .reshape([dummy_dim_size, seq_len, d_model / 2, 2])
             .matmul(sign_tensor.unsqueeze())
             .reshape([dummy_dim_size, seq_len, d_model, 2])
-            * self
-                .freq_complex
-                .clone()
-                .slice([start..start + seq_len])
-                .unsqueeze();
+            * cast_to(
+                self.freq_complex.clone().slice([start..start + seq_len]),
+                x_dtype,
+            )
+            .unsqueeze();
 
         // Sum the real and imaginary components to get output tensor and reshape to original shape
         out.sum_dim(D - 1).reshape(input_shape)
@@ -0,0 +1,188 @@
+use crate as burn;
+
+use super::{s4d_real_log_a, selective_scan};
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay, Param};
+use crate::nn::Initializer;
+use crate::tensor::Tensor;
+use crate::tensor::backend::Backend;
+
+/// Configuration to create a [diagonal state-space](DiagonalSsm) layer using the
+/// [init function](DiagonalSsmConfig::init).
+#[derive(Config, Debug)]
+pub struct DiagonalSsmConfig {
+    /// The size of the input and output features.
+    pub d_model: usize,
+    /// The size of the state of each feature. Default: 16
+    #[config(default = 16)]
+    pub d_state: usize,
+    /// The minimum initial step size. Default: 0.001
+    #[config(default = 0.001)]
+    pub dt_min: f64,
+    /// The maximum initial step size. Default: 0.1
+    #[config(default = 0.1)]
+    pub dt_max: f64,
+}
+
+/// A diagonal, time-invariant, state-space layer (S4D).
+///
+/// Each feature is processed by an independent linear state-space model with a diagonal state
+/// matrix, discretized with a learned step size:
+///
+/// `h(t) = exp(dt * A) * h(t-1) + dt * B * x(t)`, `y(t) = C * h(t) + D * x(t)`
+///
+/// The recurrence is computed with a [selective scan](selective_scan).
+///
+/// Introduced in the paper: [On the Parameterization and Initialization of Diagonal State Space
+/// Models](https://arxiv.org/abs/2206.11893).
+///
+/// Should be created with [DiagonalSsmConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct DiagonalSsm<B: Backend> {
+    /// The log of the negated state matrix of shape `[d_model, d_state]`, `A = -exp(a_log)`.
+    pub a_log: Param<Tensor<B, 2>>,
+    /// The input matrix of shape `[d_model, d_state]`.
+    pub b: Param<Tensor<B, 2>>,
+    /// The output matrix of shape `[d_model, d_state]`.
+    pub c: Param<Tensor<B, 2>>,
+    /// The skip connection of shape `[d_model]`.
+    pub d: Param<Tensor<B, 1>>,
+    /// The log of the step size of each feature, of shape `[d_model]`.
+    pub dt_log: Param<Tensor<B, 1>>,
+}
+
+impl<B: Backend> ModuleDisplay for DiagonalSsm<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_model, d_state] = self.a_log.shape().dims();
+
+        content
+            .add("d_model", &d_model)
+            .add("d_state", &d_state)
+            .optional()
+    }
+}
+
+impl DiagonalSsmConfig {
+    /// Initialize a new [diagonal state-space](DiagonalSsm) layer.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> DiagonalSsm<B> {
+        let shape = [self.d_model, self.d_state];
+        let dt_log = Initializer::Uniform {
+            min: num_traits::Float::ln(self.dt_min),
+            max: num_traits::Float::ln(self.dt_max),
+        };
+
+        DiagonalSsm {
+            a_log: s4d_real_log_a(self.d_model, self.d_state, device),
+            b: Initializer::Ones.init(shape, device),
+            c: Initializer::Normal {
+                mean: 0.0,
+                std: 1.0,
+            }
+            .init(shape, device),
+            d: Initializer::Ones.init([self.d_model], device),
+            dt_log: dt_log.init([self.d_model], device),
+        }
+    }
+}
+
+impl<B: Backend> DiagonalSsm<B> {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, seq_length, d_model]`
+    /// - output: `[batch_size, seq_length, d_model]`
+    pub fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
+        let [batch_size, seq_length, d_model] = input.dims();
+
+        let delta = self
+            .dt_log
+            .val()
+            .exp()
+            .reshape([1, 1, d_model])
+            .expand([batch_size, seq_length, d_model]);
+        let a = self.a_log.val().exp().neg();
+
+        selective_scan(
+            input,
+            delta,
+            a,
+            self.b.val().unsqueeze(),
+            self.c.val().unsqueeze(),
+            self.d.val(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::{Distribution, TensorData};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    /// With `dt = ln(2)` and `A = -1`, the state decays by `0.5` at every step:
+    /// h_1 = ln(2) * 1 = 0.6931
+    /// h_2 = 0.5 * 0.6931 + ln(2) * 2 = 1.7329
+    /// h_3 = 0.5 * 1.7329 + ln(2) * 3 = 2.9459
+    #[test]
+    fn test_forward() {
+        let device = Default::default();
+        let mut ssm = DiagonalSsmConfig::new(1)
+            .with_d_state(1)
+            .init::<TestBackend>(&device);
+
+        ssm.a_log = Param::from_data(TensorData::from([[0.0]]), &device);
+        ssm.b = Param::from_data(TensorData::from([[1.0]]), &device);
+        ssm.c = Param::from_data(TensorData::from([[1.0]]), &device);
+        ssm.d = Param::from_data(TensorData::from([0.0]), &device);
+        ssm.dt_log = Param::from_data(
+            TensorData::from([num_traits::Float::ln(core::f32::consts::LN_2)]),
+            &device,
+        );
+
+        let input = Tensor::<TestBackend, 3>::from_data([[[1.0], [2.0], [3.0]]], &device);
+        let output = ssm.forward(input);
+
+        output.to_data().assert_approx_eq::<FT>(
+            &TensorData::from([[[0.6931], [1.7329], [2.9459]]]),
+            Tolerance::default(),
+        );
+    }
+
+    #[test]
+    fn test_forward_is_causal() {
+        let device = Default::default();
+        let ssm = DiagonalSsmConfig::new(4).init::<TestBackend>(&device);
+        let input = Tensor::<TestBackend, 3>::random([2, 6, 4], Distribution::Default, &device);
+
+        let output = ssm.forward(input.clone());
+        let output_prefix = ssm.forward(input.narrow(1, 0, 3));
+
+        assert_eq!(output.dims(), [2, 6, 4]);
+        output
+            .narrow(1, 0, 3)
+            .to_data()
+            .assert_approx_eq::<FT>(&output_prefix.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn display() {
+        let config = DiagonalSsmConfig::new(4).with_d_state(2);
+        let layer = config.init::<TestBackend>(&Default::default());
+
+        assert_eq!(
+            alloc::format!("{layer}"),
+            "DiagonalSsm {d_model: 4, d_state: 2, params: 32}"
+        );
+    }
+}
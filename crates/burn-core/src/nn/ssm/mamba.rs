@@ -0,0 +1,257 @@
+use crate as burn;
+
+use super::{s4d_real_log_a, selective_scan};
+use crate::config::Config;
+use crate::module::{Content, DisplaySettings, Module, ModuleDisplay, Param, ParamId};
+use crate::nn::conv::{Conv1d, Conv1dConfig};
+use crate::nn::{Initializer, Linear, LinearConfig, PaddingConfig1d};
+use crate::tensor::Tensor;
+use crate::tensor::activation::{silu, softplus};
+use crate::tensor::backend::Backend;
+
+/// Configuration to create a [Mamba](Mamba) block using the [init function](MambaConfig::init).
+#[derive(Config, Debug)]
+pub struct MambaConfig {
+    /// The size of the input and output features.
+    pub d_model: usize,
+    /// The size of the state of each inner feature. Default: 16
+    #[config(default = 16)]
+    pub d_state: usize,
+    /// The kernel size of the depthwise causal convolution. Default: 4
+    #[config(default = 4)]
+    pub d_conv: usize,
+    /// The expansion factor of the inner features, `d_inner = expand * d_model`. Default: 2
+    #[config(default = 2)]
+    pub expand: usize,
+    /// The rank of the step size projection. Default: `ceil(d_model / 16)`
+    pub dt_rank: Option<usize>,
+    /// The minimum initial step size. Default: 0.001
+    #[config(default = 0.001)]
+    pub dt_min: f64,
+    /// The maximum initial step size. Default: 0.1
+    #[config(default = 0.1)]
+    pub dt_max: f64,
+    /// If a bias should be applied by the input and output projections. Default: false
+    #[config(default = false)]
+    pub bias: bool,
+    /// If a bias should be applied by the convolution. Default: true
+    #[config(default = true)]
+    pub conv_bias: bool,
+}
+
+/// The Mamba block, a gated selective state-space model.
+///
+/// The input is projected into two branches. The first one goes through a depthwise causal
+/// convolution and a [selective scan](selective_scan) whose step size `Δ` and matrices `B` and
+/// `C` depend on the input, the second one gates the output of the scan. Unlike the fused kernel
+/// of the paper, the discretized state is materialized before being scanned.
+///
+/// Introduced in the paper: [Mamba: Linear-Time Sequence Modeling with Selective State
+/// Spaces](https://arxiv.org/abs/2312.00752).
+///
+/// Should be created with [MambaConfig].
+#[derive(Module, Debug)]
+#[module(custom_display)]
+pub struct Mamba<B: Backend> {
+    /// Projects the input into the scanned and gating branches.
+    pub in_proj: Linear<B>,
+    /// Depthwise causal convolution applied before the scan.
+    pub conv1d: Conv1d<B>,
+    /// Projects the scanned branch into the input-dependent `Δ` (low rank), `B` and `C`.
+    pub x_proj: Linear<B>,
+    /// Projects the low rank `Δ` to the inner features.
+    pub dt_proj: Linear<B>,
+    /// The log of the negated state matrix of shape `[d_inner, d_state]`, `A = -exp(a_log)`.
+    pub a_log: Param<Tensor<B, 2>>,
+    /// The skip connection of shape `[d_inner]`.
+    pub d: Param<Tensor<B, 1>>,
+    /// Projects the gated output back to the model features.
+    pub out_proj: Linear<B>,
+}
+
+impl<B: Backend> ModuleDisplay for Mamba<B> {
+    fn custom_settings(&self) -> Option<DisplaySettings> {
+        DisplaySettings::new()
+            .with_new_line_after_attribute(false)
+            .optional()
+    }
+
+    fn custom_content(&self, content: Content) -> Option<Content> {
+        let [d_inner, d_model] = self.out_proj.weight.shape().dims();
+        let [_, d_state] = self.a_log.shape().dims();
+        let [dt_rank, _] = self.dt_proj.weight.shape().dims();
+
+        content
+            .add("d_model", &d_model)
+            .add("d_inner", &d_inner)
+            .add("d_state", &d_state)
+            .add("d_conv", &self.conv1d.kernel_size)
+            .add("dt_rank", &dt_rank)
+            .optional()
+    }
+}
+
+impl MambaConfig {
+    /// Initialize a new [Mamba](Mamba) block.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Mamba<B> {
+        let d_inner = self.expand * self.d_model;
+        let dt_rank = self.dt_rank.unwrap_or(self.d_model.div_ceil(16));
+
+        let in_proj = LinearConfig::new(self.d_model, 2 * d_inner)
+            .with_bias(self.bias)
+            .init(device);
+        let conv1d = Conv1dConfig::new(d_inner, d_inner, self.d_conv)
+            .with_groups(d_inner)
+            .with_padding(PaddingConfig1d::Explicit(self.d_conv - 1))
+            .with_bias(self.conv_bias)
+            .init(device);
+        let x_proj = LinearConfig::new(d_inner, dt_rank + 2 * self.d_state)
+            .with_bias(false)
+            .init(device);
+        let mut dt_proj = LinearConfig::new(dt_rank, d_inner).init(device);
+        dt_proj.bias = Some(self.dt_bias(d_inner, device));
+        let out_proj = LinearConfig::new(d_inner, self.d_model)
+            .with_bias(self.bias)
+            .init(device);
+
+        Mamba {
+            in_proj,
+            conv1d,
+            x_proj,
+            dt_proj,
+            a_log: s4d_real_log_a(d_inner, self.d_state, device),
+            d: Initializer::Ones.init([d_inner], device),
+            out_proj,
+        }
+    }
+
+    /// The bias of the step size projection is initialized so that `softplus(bias)` is
+    /// log-uniformly distributed in `[dt_min, dt_max]`.
+    fn dt_bias<B: Backend>(&self, d_inner: usize, device: &B::Device) -> Param<Tensor<B, 1>> {
+        let dt_log = Initializer::Uniform {
+            min: num_traits::Float::ln(self.dt_min),
+            max: num_traits::Float::ln(self.dt_max),
+        };
+
+        Param::uninitialized(
+            ParamId::new(),
+            move |device, require_grad| {
+                let dt = dt_log.init::<B, 1, _>([d_inner], device).val().exp();
+                // Inverse of softplus: dt + log(1 - exp(-dt))
+                let mut tensor = dt.clone() + (dt.neg().exp().neg() + 1).log();
+
+                if require_grad {
+                    tensor = tensor.require_grad();
+                }
+
+                tensor
+            },
+            device.clone(),
+            true,
+        )
+    }
+}
+
+impl<B: Backend> Mamba<B> {
+    /// Applies the forward pass on the input tensor.
+    ///
+    /// # Shapes
+    ///
+    /// - input: `[batch_size, seq_length, d_model]`
+    /// - output: `[batch_size, seq_length, d_model]`
+    pub fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
+        let [_, seq_length, _] = input.dims();
+        let [_, d_state] = self.a_log.shape().dims();
+        let [dt_rank, _] = self.dt_proj.weight.shape().dims();
+
+        let [x, z] = self
+            .in_proj
+            .forward(input)
+            .chunk(2, 2)
+            .try_into()
+            .expect("The input projection should have two branches");
+
+        // Only keep the first `seq_length` outputs of the padded convolution so that each
+        // timestep only sees the past.
+        let x = self
+            .conv1d
+            .forward(x.swap_dims(1, 2))
+            .narrow(2, 0, seq_length)
+            .swap_dims(1, 2);
+        let x = silu(x);
+
+        let x_proj = self.x_proj.forward(x.clone());
+        let delta = x_proj.clone().narrow(2, 0, dt_rank);
+        let b = x_proj.clone().narrow(2, dt_rank, d_state);
+        let c = x_proj.narrow(2, dt_rank + d_state, d_state);
+
+        let delta = softplus(self.dt_proj.forward(delta), 1.0);
+        let a = self.a_log.val().exp().neg();
+
+        let y = selective_scan(
+            x,
+            delta,
+            a,
+            b.unsqueeze_dim(2),
+            c.unsqueeze_dim(2),
+            self.d.val(),
+        );
+
+        self.out_proj.forward(y * silu(z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::{Distribution, ElementConversion};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_forward_is_causal() {
+        let device = Default::default();
+        let mamba = MambaConfig::new(8)
+            .with_d_state(4)
+            .init::<TestBackend>(&device);
+        let input = Tensor::<TestBackend, 3>::random([2, 7, 8], Distribution::Default, &device);
+
+        let output = mamba.forward(input.clone());
+        let output_prefix = mamba.forward(input.narrow(1, 0, 4));
+
+        assert_eq!(output.dims(), [2, 7, 8]);
+        output
+            .narrow(1, 0, 4)
+            .to_data()
+            .assert_approx_eq::<FT>(&output_prefix.to_data(), Tolerance::default());
+    }
+
+    #[test]
+    fn test_dt_bias_inverts_softplus() {
+        let device = Default::default();
+        let config = MambaConfig::new(4).with_dt_min(0.01).with_dt_max(0.05);
+        let mamba = config.init::<TestBackend>(&device);
+
+        let dt = softplus(mamba.dt_proj.bias.unwrap().val(), 1.0);
+        let min = dt.clone().min().into_scalar().elem::<f32>();
+        let max = dt.max().into_scalar().elem::<f32>();
+
+        assert!(min >= 0.0099 && max <= 0.0501);
+    }
+
+    #[test]
+    fn display() {
+        let config = MambaConfig::new(4)
+            .with_d_state(2)
+            .with_d_conv(2)
+            .with_dt_rank(Some(1));
+        let layer = config.init::<TestBackend>(&Default::default());
+
+        assert_eq!(
+            alloc::format!("{layer}"),
+            "Mamba {d_model: 4, d_inner: 8, d_state: 2, d_conv: 2, dt_rank: 1, params: 200}"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod diagonal;
+mod mamba;
+mod selective_scan;
+
+pub use diagonal::*;
+pub use mamba::*;
+pub use selective_scan::*;
@@ -0,0 +1,73 @@
+use crate::module::{Param, ParamId};
+use crate::tensor::backend::Backend;
+use crate::tensor::{Int, Tensor, scan};
+
+/// Applies the discretized state-space recurrence of a diagonal state-space model.
+///
+/// For each channel `d` and state `n`, the continuous parameters are discretized with the step
+/// size `delta` (zero-order hold on `A`, Euler on `B`):
+///
+/// ```text
+/// h(t) = exp(delta(t) * A) * h(t-1) + delta(t) * B(t) * x(t)
+/// y(t) = C(t) * h(t) + D * x(t)
+/// ```
+///
+/// The recurrence is computed with a [linear scan](crate::tensor::scan::linear_scan), which does
+/// `O(L)` work for a sequence of length `L` on backends with a scan kernel.
+///
+/// # Shapes
+///
+/// - x: `[batch_size, seq_length, d_model]`
+/// - delta: `[batch_size, seq_length, d_model]`
+/// - a: `[d_model, d_state]`
+/// - b: `[batch_size, seq_length, 1, d_state]` when input-dependent (selective), or
+///   `[1, 1, d_model, d_state]` when time-invariant.
+/// - c: same as `b`.
+/// - d: `[d_model]`
+/// - output: `[batch_size, seq_length, d_model]`
+pub fn selective_scan<B: Backend>(
+    x: Tensor<B, 3>,
+    delta: Tensor<B, 3>,
+    a: Tensor<B, 2>,
+    b: Tensor<B, 4>,
+    c: Tensor<B, 4>,
+    d: Tensor<B, 1>,
+) -> Tensor<B, 3> {
+    let delta = delta.unsqueeze_dim::<4>(3);
+
+    // [batch_size, seq_length, d_model, d_state]
+    let a_bar = delta.clone().mul(a.unsqueeze::<4>()).exp();
+    let b_x = delta.mul(b).mul(x.clone().unsqueeze_dim(3));
+
+    let states = scan::linear_scan(a_bar, b_x, 1);
+    let y = states.mul(c).sum_dim(3).squeeze::<3>(3);
+
+    y + x.mul(d.unsqueeze())
+}
+
+/// Initializes the log of the (negated) state matrix with the S4D-Real initialization,
+/// `A[d, n] = -(n + 1)`, with shape `[d_model, d_state]`.
+pub(crate) fn s4d_real_log_a<B: Backend>(
+    d_model: usize,
+    d_state: usize,
+    device: &B::Device,
+) -> Param<Tensor<B, 2>> {
+    Param::uninitialized(
+        ParamId::new(),
+        move |device, require_grad| {
+            let mut tensor = Tensor::<B, 1, Int>::arange(1..(d_state as i64 + 1), device)
+                .float()
+                .log()
+                .unsqueeze::<2>()
+                .repeat_dim(0, d_model);
+
+            if require_grad {
+                tensor = tensor.require_grad();
+            }
+
+            tensor
+        },
+        device.clone(),
+        true,
+    )
+}
@@ -1,8 +1,12 @@
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 
-use super::{PositionWiseFeedForward, PositionWiseFeedForwardConfig};
+use super::{
+    FeedForwardActivation, PositionWiseFeedForward, PositionWiseFeedForwardConfig,
+    check_sequence_norm,
+};
 
-use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use crate::amp::cast_to;
+use crate::module::{Content, DisplaySettings, ForwardScope, Module, ModuleDisplay};
 use crate::tensor::Bool;
 use crate::{
     self as burn,
@@ -11,8 +15,12 @@ use crate::{
 use crate::{
     config::Config,
     nn::{
-        Dropout, DropoutConfig, LayerNorm, LayerNormConfig,
-        attention::{MhaInput, MultiHeadAttention, MultiHeadAttentionConfig},
+        Dropout, DropoutConfig, LayerNormConfig, RotaryEncoding, RotaryEncodingConfig,
+        attention::{
+            MhaInput, MultiHeadAttention, MultiHeadAttentionConfig, generate_alibi_bias,
+            generate_autoregressive_mask,
+        },
+        norm::{Normalization, NormalizationConfig},
     },
     tensor::{Tensor, backend::Backend},
 };
@@ -47,6 +55,28 @@ pub struct TransformerDecoderConfig {
         default = "Initializer::KaimingUniform{gain:1.0/num_traits::Float::sqrt(3.0), fan_out_only:false}"
     )]
     pub initializer: Initializer,
+    /// The normalization layer applied around each sublayer, the number of features is set to
+    /// `d_model`. Only layer and RMS norms are supported, since they normalize the features of
+    /// each position. Default: [LayerNorm](crate::nn::LayerNorm)
+    ///
+    /// The records saved when the normalization was always a layer norm are loaded with the
+    /// [legacy records](super::LayerNormTransformerDecoderRecord).
+    #[config(default = "NormalizationConfig::Layer(LayerNormConfig::new(0))")]
+    pub norm: NormalizationConfig,
+    /// The activation of the position-wise feed-forward network. Default: GELU
+    #[config(default = "FeedForwardActivation::Gelu")]
+    pub feed_forward: FeedForwardActivation,
+    /// Rotary positional encoding applied to the queries and keys of each self-attention head.
+    ///
+    /// The `d_model` of the encoding is set to the size of each head (`d_model / n_heads`).
+    #[config(default = "None")]
+    pub rope: Option<RotaryEncodingConfig>,
+    /// Add attention linear biases ([ALiBi](generate_alibi_bias)) to the self-attention scores.
+    #[config(default = false)]
+    pub alibi: bool,
+    /// Prevent each target position from attending to subsequent target positions.
+    #[config(default = false)]
+    pub causal: bool,
 }
 
 /// The transformer decoder module as describe in the paper [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
@@ -82,6 +112,15 @@ pub struct TransformerDecoder<B: Backend> {
 
     /// Use "quiet softmax" instead of regular softmax.
     pub quiet_softmax: bool,
+
+    /// Rotary positional encoding shared by the self-attention heads of all layers.
+    pub rope: Option<RotaryEncoding<B>>,
+
+    /// Add attention linear biases to the self-attention scores.
+    pub alibi: bool,
+
+    /// Prevent each target position from attending to subsequent target positions.
+    pub causal: bool,
 }
 
 impl<B: Backend> ModuleDisplay for TransformerDecoder<B> {
@@ -106,11 +145,25 @@ impl<B: Backend> ModuleDisplay for TransformerDecoder<B> {
 
 impl TransformerDecoderConfig {
     /// Initialize a new [Transformer Decoder](TransformerDecoder) module.
+    ///
+    /// # Panics
+    ///
+    /// If the [normalization](Self::norm) isn't a layer norm or an RMS norm.
     pub fn init<B: Backend>(&self, device: &B::Device) -> TransformerDecoder<B> {
+        check_sequence_norm(&self.norm);
+
         let layers = (0..self.n_layers)
             .map(|_| TransformerDecoderLayer::new(self, device))
             .collect::<Vec<_>>();
 
+        let rope = self.rope.as_ref().map(|config| {
+            RotaryEncodingConfig {
+                d_model: self.d_model / self.n_heads,
+                ..config.clone()
+            }
+            .init(device)
+        });
+
         TransformerDecoder {
             layers,
             d_model: self.d_model,
@@ -120,6 +173,9 @@ impl TransformerDecoderConfig {
             dropout: self.dropout,
             norm_first: self.norm_first,
             quiet_softmax: self.quiet_softmax,
+            rope,
+            alibi: self.alibi,
+            causal: self.causal,
         }
     }
 }
@@ -127,7 +183,7 @@ impl TransformerDecoderConfig {
 /// [Transformer Decoder](TransformerDecoder) forward pass input argument.
 #[derive(Debug)]
 pub struct TransformerDecoderInput<B: Backend> {
-    target: Tensor<B, 3>,
+    pub(crate) target: Tensor<B, 3>,
     target_mask_pad: Option<Tensor<B, 2, Bool>>,
     target_mask_attn: Option<Tensor<B, 3, Bool>>,
     memory: Tensor<B, 3>,
@@ -176,14 +232,73 @@ impl<B: Backend> TransformerDecoderInput<B> {
 /// [Transformer Decoder](TransformerDecoder) layer module.
 #[derive(Module, Debug)]
 pub struct TransformerDecoderLayer<B: Backend> {
-    cross_attn: MultiHeadAttention<B>,
-    self_attn: MultiHeadAttention<B>,
-    pwff: PositionWiseFeedForward<B>,
-    norm_1: LayerNorm<B>,
-    norm_2: LayerNorm<B>,
-    norm_3: LayerNorm<B>,
-    dropout: Dropout,
-    norm_first: bool,
+    pub(crate) cross_attn: MultiHeadAttention<B>,
+    pub(crate) self_attn: MultiHeadAttention<B>,
+    pub(crate) pwff: PositionWiseFeedForward<B>,
+    pub(crate) norm_1: Normalization<B>,
+    pub(crate) norm_2: Normalization<B>,
+    pub(crate) norm_3: Normalization<B>,
+    pub(crate) dropout: Dropout,
+    pub(crate) norm_first: bool,
+}
+
+/// The normalizations and dropout of a [decoder layer](TransformerDecoderLayer), applied around
+/// its sublayers, so the forward pass is shared with the [adapted layers](crate::nn::lora).
+pub(crate) struct DecoderLayerResiduals<'a, B: Backend> {
+    pub(crate) norm_1: &'a Normalization<B>,
+    pub(crate) norm_2: &'a Normalization<B>,
+    pub(crate) norm_3: &'a Normalization<B>,
+    pub(crate) dropout: &'a Dropout,
+    pub(crate) norm_first: bool,
+}
+
+/// Position biases applied to the self-attention of each decoder layer.
+pub(crate) struct DecoderSelfAttention<B: Backend> {
+    attn_bias: Option<Tensor<B, 4>>,
+    rope: Option<RotaryEncoding<B>>,
+}
+
+impl<B: Backend> DecoderSelfAttention<B> {
+    /// Apply the causal mask to the target and gather the position biases shared by the
+    /// self-attention of every layer.
+    pub(crate) fn new(
+        mut input: TransformerDecoderInput<B>,
+        n_heads: usize,
+        causal: bool,
+        alibi: bool,
+        rope: Option<&RotaryEncoding<B>>,
+    ) -> (TransformerDecoderInput<B>, Self) {
+        let [batch_size, seq_length, _] = input.target.dims();
+        let device = input.target.device();
+
+        if causal {
+            let mask_causal = generate_autoregressive_mask(batch_size, seq_length, &device);
+            input.target_mask_attn = Some(match input.target_mask_attn {
+                Some(mask_attn) => mask_attn.bool_or(mask_causal),
+                None => mask_causal,
+            });
+        }
+
+        let attn_bias =
+            alibi.then(|| generate_alibi_bias(n_heads, seq_length, seq_length, &device));
+
+        let positions = Self {
+            attn_bias,
+            rope: rope.cloned(),
+        };
+
+        (input, positions)
+    }
+
+    fn apply(&self, mut input: MhaInput<B>) -> MhaInput<B> {
+        if let Some(attn_bias) = &self.attn_bias {
+            input = input.attn_bias(attn_bias.clone());
+        }
+        if let Some(rope) = &self.rope {
+            input = input.rope(rope.clone());
+        }
+        input
+    }
 }
 
 struct TransformerDecoderLayerAutoregressiveCache<B: Backend> {
@@ -238,12 +353,14 @@ impl<B: Backend> TransformerDecoderLayer<B> {
             .with_dropout(config.dropout)
             .with_quiet_softmax(config.quiet_softmax)
             .init(device);
-        let norm_1 = LayerNormConfig::new(config.d_model).init(device);
-        let norm_2 = LayerNormConfig::new(config.d_model).init(device);
-        let norm_3 = LayerNormConfig::new(config.d_model).init(device);
+        let norm = config.norm.clone().with_num_features(config.d_model);
+        let norm_1 = norm.init(device);
+        let norm_2 = norm.init(device);
+        let norm_3 = norm.init(device);
         let dropout = DropoutConfig::new(config.dropout).init();
         let pwff = PositionWiseFeedForwardConfig::new(config.d_model, config.d_ff)
             .with_dropout(config.dropout)
+            .with_activation(config.feed_forward)
             .init(device);
 
         Self {
@@ -259,14 +376,45 @@ impl<B: Backend> TransformerDecoderLayer<B> {
     }
 
     /// Applies the TransformerDecoder forward pass to the input tensor.
-    fn forward(&self, mut input: TransformerDecoderInput<B>) -> TransformerDecoderInput<B> {
+    fn forward(
+        &self,
+        input: TransformerDecoderInput<B>,
+        positions: &DecoderSelfAttention<B>,
+    ) -> TransformerDecoderInput<B> {
+        self.residuals().forward(
+            input,
+            positions,
+            |input| ForwardScope::run("self_attn", || self.self_attn.forward(input).context),
+            |input| ForwardScope::run("cross_attn", || self.cross_attn.forward(input).context),
+            |x| ForwardScope::run("pwff", || self.pwff.forward(x)),
+        )
+    }
+
+    fn residuals(&self) -> DecoderLayerResiduals<'_, B> {
+        DecoderLayerResiduals {
+            norm_1: &self.norm_1,
+            norm_2: &self.norm_2,
+            norm_3: &self.norm_3,
+            dropout: &self.dropout,
+            norm_first: self.norm_first,
+        }
+    }
+
+    fn forward_autoregressive_inference(
+        &self,
+        mut input: TransformerDecoderInput<B>,
+        positions: &DecoderSelfAttention<B>,
+        cache: &mut TransformerDecoderLayerAutoregressiveCache<B>,
+    ) -> TransformerDecoderInput<B> {
         // Self attention residual path.
         let x = input.target;
         let mut residual_path = x.clone();
 
         // Normalize.
         if self.norm_first {
-            residual_path = self.norm_3.forward(residual_path);
+            residual_path = cache.norm_3.forward_autoregressive(residual_path, 1, |x| {
+                ForwardScope::run("norm_3", || self.norm_3.forward(x))
+            });
         }
 
         // Self attention.
@@ -277,17 +425,27 @@ impl<B: Backend> TransformerDecoderLayer<B> {
         if let Some(mask_attn) = &input.target_mask_attn {
             self_attn_input = self_attn_input.mask_attn(mask_attn.clone());
         }
-        let residual_path = self.self_attn.forward(self_attn_input).context;
+        let self_attn_input = positions.apply(self_attn_input);
+        let residual_path = ForwardScope::run("self_attn", || {
+            self.self_attn
+                .forward_cache(self_attn_input, &mut cache.self_attn)
+                .context
+        });
 
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Cross attention residual path.
         // Normalize.
         let residual_path = if self.norm_first {
-            self.norm_1.forward(x.clone())
+            cache.norm_1.forward_autoregressive(x.clone(), 1, |x| {
+                ForwardScope::run("norm_1", || self.norm_1.forward(x))
+            })
         } else {
-            x = self.norm_1.forward(x);
+            x = cache.norm_1.forward_autoregressive(x, 1, |x| {
+                ForwardScope::run("norm_1", || self.norm_1.forward(x))
+            });
             x.clone()
         };
 
@@ -300,38 +458,59 @@ impl<B: Backend> TransformerDecoderLayer<B> {
         if let Some(mask_attn) = &input.memory_mask_attn {
             cross_attn_input = cross_attn_input.mask_attn(mask_attn.clone());
         }
-        let residual_path = self.cross_attn.forward(cross_attn_input).context;
+        let residual_path = ForwardScope::run("cross_attn", || {
+            self.cross_attn
+                .forward_cache(cross_attn_input, &mut cache.cross_attn)
+                .context
+        });
 
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Feed forward residual path.
         // Normalize.
         let residual_path = if self.norm_first {
-            self.norm_2.forward(x.clone())
+            cache.norm_2.forward_autoregressive(x.clone(), 1, |x| {
+                ForwardScope::run("norm_2", || self.norm_2.forward(x))
+            })
         } else {
-            x = self.norm_2.forward(x);
+            x = cache.norm_2.forward_autoregressive(x, 1, |x| {
+                ForwardScope::run("norm_2", || self.norm_2.forward(x))
+            });
             x.clone()
         };
 
-        let residual_path = self.pwff.forward(residual_path);
+        let residual_path = cache.pwff.forward_autoregressive(residual_path, 1, |x| {
+            ForwardScope::run("pwff", || self.pwff.forward(x))
+        });
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Main path.
         // Normalize.
         if !self.norm_first {
-            x = self.norm_3.forward(x)
+            x = cache.norm_3.forward_autoregressive(x, 1, |x| {
+                ForwardScope::run("norm_3", || self.norm_3.forward(x))
+            })
         }
 
         input.target = x;
         input
     }
+}
 
-    fn forward_autoregressive_inference(
+impl<B: Backend> DecoderLayerResiduals<'_, B> {
+    /// Applies the residual paths around the given self-attention, cross-attention and
+    /// feed-forward sublayers.
+    pub(crate) fn forward(
         &self,
         mut input: TransformerDecoderInput<B>,
-        cache: &mut TransformerDecoderLayerAutoregressiveCache<B>,
+        positions: &DecoderSelfAttention<B>,
+        self_attn: impl Fn(MhaInput<B>) -> Tensor<B, 3>,
+        cross_attn: impl Fn(MhaInput<B>) -> Tensor<B, 3>,
+        pwff: impl Fn(Tensor<B, 3>) -> Tensor<B, 3>,
     ) -> TransformerDecoderInput<B> {
         // Self attention residual path.
         let x = input.target;
@@ -339,9 +518,7 @@ impl<B: Backend> TransformerDecoderLayer<B> {
 
         // Normalize.
         if self.norm_first {
-            residual_path = cache
-                .norm_3
-                .forward_autoregressive(residual_path, 1, |x| self.norm_3.forward(x));
+            residual_path = ForwardScope::run("norm_3", || self.norm_3.forward(residual_path));
         }
 
         // Self attention.
@@ -352,24 +529,19 @@ impl<B: Backend> TransformerDecoderLayer<B> {
         if let Some(mask_attn) = &input.target_mask_attn {
             self_attn_input = self_attn_input.mask_attn(mask_attn.clone());
         }
-        let residual_path = self
-            .self_attn
-            .forward_cache(self_attn_input, &mut cache.self_attn)
-            .context;
+        let self_attn_input = positions.apply(self_attn_input);
+        let residual_path = self_attn(self_attn_input);
 
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Cross attention residual path.
         // Normalize.
         let residual_path = if self.norm_first {
-            cache
-                .norm_1
-                .forward_autoregressive(x.clone(), 1, |x| self.norm_1.forward(x))
+            ForwardScope::run("norm_1", || self.norm_1.forward(x.clone()))
         } else {
-            x = cache
-                .norm_1
-                .forward_autoregressive(x, 1, |x| self.norm_1.forward(x));
+            x = ForwardScope::run("norm_1", || self.norm_1.forward(x));
             x.clone()
         };
 
@@ -382,39 +554,30 @@ impl<B: Backend> TransformerDecoderLayer<B> {
         if let Some(mask_attn) = &input.memory_mask_attn {
             cross_attn_input = cross_attn_input.mask_attn(mask_attn.clone());
         }
-        let residual_path = self
-            .cross_attn
-            .forward_cache(cross_attn_input, &mut cache.cross_attn)
-            .context;
+        let residual_path = cross_attn(cross_attn_input);
 
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Feed forward residual path.
         // Normalize.
         let residual_path = if self.norm_first {
-            cache
-                .norm_2
-                .forward_autoregressive(x.clone(), 1, |x| self.norm_2.forward(x))
+            ForwardScope::run("norm_2", || self.norm_2.forward(x.clone()))
         } else {
-            x = cache
-                .norm_2
-                .forward_autoregressive(x, 1, |x| self.norm_2.forward(x));
+            x = ForwardScope::run("norm_2", || self.norm_2.forward(x));
             x.clone()
         };
 
-        let residual_path = cache
-            .pwff
-            .forward_autoregressive(residual_path, 1, |x| self.pwff.forward(x));
+        let residual_path = pwff(residual_path);
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Main path.
         // Normalize.
         if !self.norm_first {
-            x = cache
-                .norm_3
-                .forward_autoregressive(x, 1, |x| self.norm_3.forward(x))
+            x = ForwardScope::run("norm_3", || self.norm_3.forward(x))
         }
 
         input.target = x;
@@ -424,9 +587,11 @@ impl<B: Backend> TransformerDecoderLayer<B> {
 
 impl<B: Backend> TransformerDecoder<B> {
     /// Applies the forward pass.
-    pub fn forward(&self, mut input: TransformerDecoderInput<B>) -> Tensor<B, 3> {
-        for layer in self.layers.iter() {
-            input = layer.forward(input);
+    pub fn forward(&self, input: TransformerDecoderInput<B>) -> Tensor<B, 3> {
+        let (mut input, positions) = self.self_attention_input(input);
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            input = ForwardScope::run(&format!("layers.{i}"), || layer.forward(input, &positions));
         }
 
         input.target
@@ -435,18 +600,36 @@ impl<B: Backend> TransformerDecoder<B> {
     /// Applies the forward pass on the input using autoregressive cache.
     pub fn forward_autoregressive_inference(
         &self,
-        mut input: TransformerDecoderInput<B>,
+        input: TransformerDecoderInput<B>,
         cache: &mut TransformerDecoderAutoregressiveCache<B>,
     ) -> Tensor<B, 3> {
+        let (mut input, positions) = self.self_attention_input(input);
+
         for i in 0..self.layers.len() {
             let layer = self.layers.get(i).unwrap();
             let cache = cache.layers.get_mut(i).unwrap();
 
-            input = layer.forward_autoregressive_inference(input, cache);
+            input = ForwardScope::run(&format!("layers.{i}"), || {
+                layer.forward_autoregressive_inference(input, &positions, cache)
+            });
         }
 
         input.target
     }
+
+    fn self_attention_input(
+        &self,
+        input: TransformerDecoderInput<B>,
+    ) -> (TransformerDecoderInput<B>, DecoderSelfAttention<B>) {
+        DecoderSelfAttention::new(
+            input,
+            self.n_heads,
+            self.causal,
+            self.alibi,
+            self.rope.as_ref(),
+        )
+    }
+
     /// Create an empty autoregressive cache.
     pub fn new_autoregressive_cache(&self) -> TransformerDecoderAutoregressiveCache<B> {
         TransformerDecoderAutoregressiveCache::empty(self.layers.len())
@@ -458,6 +641,7 @@ mod tests {
     use burn_tensor::Device;
 
     use super::*;
+    use crate::nn::RmsNormConfig;
     use crate::{TestBackend, nn::attention::generate_autoregressive_mask};
 
     use burn_tensor::{Tolerance, ops::FloatElem};
@@ -486,6 +670,43 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_autoregressive_llama_style() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let device = Default::default();
+        TestBackend::seed(&device, 0);
+
+        test_autoregressive(
+            TransformerDecoderConfig::new(d_model, d_ff, n_heads, num_layers)
+                .with_norm_first(true)
+                .with_norm(RmsNormConfig::new(0).into())
+                .with_feed_forward(FeedForwardActivation::SwiGlu)
+                .with_rope(Some(RotaryEncodingConfig::new(16, 0)))
+                .with_causal(true),
+        )
+    }
+
+    #[test]
+    #[should_panic = "should be a layer norm or an RMS norm"]
+    fn test_group_norm_should_be_rejected() {
+        TransformerDecoderConfig::new(12, 24, 2, 1)
+            .with_norm(crate::nn::GroupNormConfig::new(2, 0).into())
+            .init::<TestBackend>(&Default::default());
+    }
+
+    #[test]
+    fn test_autoregressive_alibi() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        let device = Default::default();
+        TestBackend::seed(&device, 0);
+
+        test_autoregressive(
+            TransformerDecoderConfig::new(d_model, d_ff, n_heads, num_layers)
+                .with_norm_first(true)
+                .with_alibi(true),
+        )
+    }
+
     fn test_autoregressive(config: TransformerDecoderConfig) {
         let device: Device<TestBackend> = Default::default();
         let [batch_size, seq_length, d_model] = [3, 4, config.d_model];
@@ -1,8 +1,9 @@
 use crate::tensor::Bool;
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 
-use super::{PositionWiseFeedForward, PositionWiseFeedForwardConfig};
-use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
+use super::{FeedForwardActivation, PositionWiseFeedForward, PositionWiseFeedForwardConfig};
+use crate::amp::cast_to;
+use crate::module::{Content, DisplaySettings, ForwardScope, Module, ModuleDisplay};
 use crate::{
     self as burn,
     nn::{Initializer, attention::MhaCache, cache::TensorCache},
@@ -10,8 +11,12 @@ use crate::{
 use crate::{
     config::Config,
     nn::{
-        Dropout, DropoutConfig, LayerNorm, LayerNormConfig,
-        attention::{MhaInput, MultiHeadAttention, MultiHeadAttentionConfig},
+        Dropout, DropoutConfig, LayerNormConfig, RotaryEncoding, RotaryEncodingConfig,
+        attention::{
+            MhaInput, MultiHeadAttention, MultiHeadAttentionConfig, generate_alibi_bias,
+            generate_autoregressive_mask,
+        },
+        norm::{Normalization, NormalizationConfig},
     },
     tensor::{Tensor, backend::Backend},
 };
@@ -46,6 +51,28 @@ pub struct TransformerEncoderConfig {
         default = "Initializer::KaimingUniform{gain:1.0/num_traits::Float::sqrt(3.0), fan_out_only:false}"
     )]
     pub initializer: Initializer,
+    /// The normalization layer applied around each sublayer, the number of features is set to
+    /// `d_model`. Only layer and RMS norms are supported, since they normalize the features of
+    /// each position. Default: [LayerNorm](crate::nn::LayerNorm)
+    ///
+    /// The records saved when the normalization was always a layer norm are loaded with the
+    /// [legacy records](super::LayerNormTransformerEncoderRecord).
+    #[config(default = "NormalizationConfig::Layer(LayerNormConfig::new(0))")]
+    pub norm: NormalizationConfig,
+    /// The activation of the position-wise feed-forward network. Default: GELU
+    #[config(default = "FeedForwardActivation::Gelu")]
+    pub feed_forward: FeedForwardActivation,
+    /// Rotary positional encoding applied to the queries and keys of each attention head.
+    ///
+    /// The `d_model` of the encoding is set to the size of each head (`d_model / n_heads`).
+    #[config(default = "None")]
+    pub rope: Option<RotaryEncodingConfig>,
+    /// Add attention linear biases ([ALiBi](generate_alibi_bias)) to the attention scores.
+    #[config(default = false)]
+    pub alibi: bool,
+    /// Prevent each position from attending to subsequent positions.
+    #[config(default = false)]
+    pub causal: bool,
 }
 
 /// The transformer encoder module as describe in the paper [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
@@ -81,6 +108,15 @@ pub struct TransformerEncoder<B: Backend> {
 
     /// Use "quiet softmax" instead of regular softmax.
     pub quiet_softmax: bool,
+
+    /// Rotary positional encoding shared by the attention heads of all layers.
+    pub rope: Option<RotaryEncoding<B>>,
+
+    /// Add attention linear biases to the attention scores.
+    pub alibi: bool,
+
+    /// Prevent each position from attending to subsequent positions.
+    pub causal: bool,
 }
 
 impl<B: Backend> ModuleDisplay for TransformerEncoder<B> {
@@ -106,7 +142,7 @@ impl<B: Backend> ModuleDisplay for TransformerEncoder<B> {
 /// [Transformer Encoder](TransformerEncoder) forward pass input argument.
 #[derive(Debug)]
 pub struct TransformerEncoderInput<B: Backend> {
-    tensor: Tensor<B, 3>,
+    pub(crate) tensor: Tensor<B, 3>,
     mask_pad: Option<Tensor<B, 2, Bool>>,
     mask_attn: Option<Tensor<B, 3, Bool>>,
 }
@@ -135,11 +171,25 @@ impl<B: Backend> TransformerEncoderInput<B> {
 }
 impl TransformerEncoderConfig {
     /// Initialize a new [transformer encoder](TransformerEncoder) module.
+    ///
+    /// # Panics
+    ///
+    /// If the [normalization](Self::norm) isn't a layer norm or an RMS norm.
     pub fn init<B: Backend>(&self, device: &B::Device) -> TransformerEncoder<B> {
+        check_sequence_norm(&self.norm);
+
         let layers = (0..self.n_layers)
             .map(|_| TransformerEncoderLayer::new(self, device))
             .collect::<Vec<_>>();
 
+        let rope = self.rope.as_ref().map(|config| {
+            RotaryEncodingConfig {
+                d_model: self.d_model / self.n_heads,
+                ..config.clone()
+            }
+            .init(device)
+        });
+
         TransformerEncoder {
             layers,
             d_model: self.d_model,
@@ -149,6 +199,9 @@ impl TransformerEncoderConfig {
             dropout: self.dropout,
             norm_first: self.norm_first,
             quiet_softmax: self.quiet_softmax,
+            rope,
+            alibi: self.alibi,
+            causal: self.causal,
         }
     }
 }
@@ -161,10 +214,11 @@ impl<B: Backend> TransformerEncoder<B> {
     /// - tensor: `[batch_size, seq_length, d_model]`
     /// - output: `[batch_size, seq_length, d_model]`
     pub fn forward(&self, input: TransformerEncoderInput<B>) -> Tensor<B, 3> {
+        let attn = self.attention_input(&input);
         let mut x = input.tensor;
 
-        for layer in self.layers.iter() {
-            x = layer.forward(x, input.mask_pad.clone(), input.mask_attn.clone());
+        for (i, layer) in self.layers.iter().enumerate() {
+            x = ForwardScope::run(&format!("layers.{i}"), || layer.forward(x, &attn));
         }
 
         x
@@ -180,38 +234,126 @@ impl<B: Backend> TransformerEncoder<B> {
         input: TransformerEncoderInput<B>,
         cache: &mut TransformerEncoderAutoregressiveCache<B>,
     ) -> Tensor<B, 3> {
+        let attn = self.attention_input(&input);
         let mut x = input.tensor;
 
         for i in 0..self.layers.len() {
             let layer = self.layers.get(i).unwrap();
             let cache = cache.layers.get_mut(i).unwrap();
 
-            x = layer.forward_autoregressive_inference(
-                x,
-                input.mask_pad.clone(),
-                input.mask_attn.clone(),
-                cache,
-            );
+            x = ForwardScope::run(&format!("layers.{i}"), || {
+                layer.forward_autoregressive_inference(x, &attn, cache)
+            });
         }
 
         x
     }
 
+    fn attention_input(&self, input: &TransformerEncoderInput<B>) -> EncoderAttention<B> {
+        EncoderAttention::new(
+            input,
+            self.n_heads,
+            self.causal,
+            self.alibi,
+            self.rope.as_ref(),
+        )
+    }
+
     /// Create an empty autoregressive cache.
     pub fn new_autoregressive_cache(&self) -> TransformerEncoderAutoregressiveCache<B> {
         TransformerEncoderAutoregressiveCache::empty(self.layers.len())
     }
 }
 
+/// Masks and position biases applied to the self-attention of each encoder layer.
+pub(crate) struct EncoderAttention<B: Backend> {
+    mask_pad: Option<Tensor<B, 2, Bool>>,
+    mask_attn: Option<Tensor<B, 3, Bool>>,
+    attn_bias: Option<Tensor<B, 4>>,
+    rope: Option<RotaryEncoding<B>>,
+}
+
+impl<B: Backend> EncoderAttention<B> {
+    /// Gather the masks and position biases shared by the self-attention of every layer.
+    pub(crate) fn new(
+        input: &TransformerEncoderInput<B>,
+        n_heads: usize,
+        causal: bool,
+        alibi: bool,
+        rope: Option<&RotaryEncoding<B>>,
+    ) -> Self {
+        let [batch_size, seq_length, _] = input.tensor.dims();
+        let device = input.tensor.device();
+
+        let mut mask_attn = input.mask_attn.clone();
+        if causal {
+            let mask_causal = generate_autoregressive_mask(batch_size, seq_length, &device);
+            mask_attn = Some(match mask_attn {
+                Some(mask_attn) => mask_attn.bool_or(mask_causal),
+                None => mask_causal,
+            });
+        }
+
+        let attn_bias =
+            alibi.then(|| generate_alibi_bias(n_heads, seq_length, seq_length, &device));
+
+        Self {
+            mask_pad: input.mask_pad.clone(),
+            mask_attn,
+            attn_bias,
+            rope: rope.cloned(),
+        }
+    }
+
+    fn mha_input(&self, tensor: Tensor<B, 3>) -> MhaInput<B> {
+        let mut input = MhaInput::self_attn(tensor);
+        if let Some(mask_pad) = &self.mask_pad {
+            input = input.mask_pad(mask_pad.clone());
+        }
+        if let Some(mask_attn) = &self.mask_attn {
+            input = input.mask_attn(mask_attn.clone());
+        }
+        if let Some(attn_bias) = &self.attn_bias {
+            input = input.attn_bias(attn_bias.clone());
+        }
+        if let Some(rope) = &self.rope {
+            input = input.rope(rope.clone());
+        }
+        input
+    }
+}
+
+/// Panics if the normalization doesn't normalize the features of each position of `[B, L, D]`
+/// sequences, which batch, group and instance norms don't, since they expect the channels in the
+/// second dimension.
+pub(crate) fn check_sequence_norm(norm: &NormalizationConfig) {
+    assert!(
+        matches!(
+            norm,
+            NormalizationConfig::Layer(_) | NormalizationConfig::Rms(_)
+        ),
+        "The normalization of a transformer should be a layer norm or an RMS norm, got {norm:?}"
+    );
+}
+
 /// Transformer encoder layer module.
 #[derive(Module, Debug)]
 pub struct TransformerEncoderLayer<B: Backend> {
-    mha: MultiHeadAttention<B>,
-    pwff: PositionWiseFeedForward<B>,
-    norm_1: LayerNorm<B>,
-    norm_2: LayerNorm<B>,
-    dropout: Dropout,
-    norm_first: bool,
+    pub(crate) mha: MultiHeadAttention<B>,
+    pub(crate) pwff: PositionWiseFeedForward<B>,
+    pub(crate) norm_1: Normalization<B>,
+    pub(crate) norm_2: Normalization<B>,
+    pub(crate) dropout: Dropout,
+    pub(crate) norm_first: bool,
+}
+
+/// The normalizations and dropout of an [encoder layer](TransformerEncoderLayer), applied around
+/// its sublayers, so the forward pass is shared with the [adapted layers](crate::nn::lora).
+pub(crate) struct EncoderLayerResiduals<'a, B: Backend> {
+    pub(crate) norm_1: &'a Normalization<B>,
+    pub(crate) norm_2: &'a Normalization<B>,
+    pub(crate) dropout: &'a Dropout,
+    pub(crate) norm_first: bool,
 }
 
 impl<B: Backend> TransformerEncoderLayer<B> {
@@ -221,12 +363,14 @@ impl<B: Backend> TransformerEncoderLayer<B> {
             .with_dropout(config.dropout)
             .with_quiet_softmax(config.quiet_softmax)
             .init(device);
-        let norm_1 = LayerNormConfig::new(config.d_model).init(device);
-        let norm_2 = LayerNormConfig::new(config.d_model).init(device);
+        let norm = config.norm.clone().with_num_features(config.d_model);
+        let norm_1 = norm.init(device);
+        let norm_2 = norm.init(device);
         let dropout = DropoutConfig::new(config.dropout).init();
         let pwff = PositionWiseFeedForwardConfig::new(config.d_model, config.d_ff)
             .with_initializer(config.initializer.clone())
             .with_dropout(config.dropout)
+            .with_activation(config.feed_forward)
             .init(device);
 
         Self {
@@ -239,11 +383,29 @@ impl<B: Backend> TransformerEncoderLayer<B> {
         }
     }
 
-    fn forward(
+    fn forward(&self, input: Tensor<B, 3>, attn: &EncoderAttention<B>) -> Tensor<B, 3> {
+        self.residuals().forward(
+            input,
+            attn,
+            |input| ForwardScope::run("mha", || self.mha.forward(input).context),
+            |x| ForwardScope::run("pwff", || self.pwff.forward(x)),
+        )
+    }
+
+    fn residuals(&self) -> EncoderLayerResiduals<'_, B> {
+        EncoderLayerResiduals {
+            norm_1: &self.norm_1,
+            norm_2: &self.norm_2,
+            dropout: &self.dropout,
+            norm_first: self.norm_first,
+        }
+    }
+
+    fn forward_autoregressive_inference(
         &self,
         input: Tensor<B, 3>,
-        mask_pad: Option<Tensor<B, 2, Bool>>,
-        mask_attn: Option<Tensor<B, 3, Bool>>,
+        attn: &EncoderAttention<B>,
+        cache: &mut TransformerEncoderLayerAutoregressiveCache<B>,
     ) -> Tensor<B, 3> {
         // Multi-head attention residual path.
         let x = input;
@@ -251,51 +413,62 @@ impl<B: Backend> TransformerEncoderLayer<B> {
 
         // Normalize.
         if self.norm_first {
-            residual_path = self.norm_2.forward(residual_path)
+            residual_path = cache.norm_2.forward_autoregressive(residual_path, 1, |x| {
+                ForwardScope::run("norm_2", || self.norm_2.forward(x))
+            })
         }
 
         // Multi-head attention.
-        let mut input_mhs = MhaInput::self_attn(residual_path);
-        if let Some(mask_pad) = mask_pad {
-            input_mhs = input_mhs.mask_pad(mask_pad);
-        }
-        if let Some(mask_attn) = mask_attn {
-            input_mhs = input_mhs.mask_attn(mask_attn);
-        }
-        let residual_path = self.mha.forward(input_mhs).context;
+        let input_mhs = attn.mha_input(residual_path);
+        let residual_path = ForwardScope::run("mha", || {
+            self.mha.forward_cache(input_mhs, &mut cache.mha).context
+        });
 
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Feed forward residual path.
         // Normalize.
         let residual_path = if self.norm_first {
-            self.norm_1.forward(x.clone())
+            cache.norm_1.forward_autoregressive(x.clone(), 1, |x| {
+                ForwardScope::run("norm_1", || self.norm_1.forward(x))
+            })
         } else {
-            x = self.norm_1.forward(x);
+            x = cache.norm_1.forward_autoregressive(x, 1, |x| {
+                ForwardScope::run("norm_1", || self.norm_1.forward(x))
+            });
             x.clone()
         };
 
         // Feed forward.
-        let residual_path = self.pwff.forward(residual_path);
+        let residual_path = cache.pwff.forward_autoregressive(residual_path, 1, |x| {
+            ForwardScope::run("pwff", || self.pwff.forward(x))
+        });
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Main path.
         // Normalize.
         if !self.norm_first {
-            x = self.norm_2.forward(x)
+            x = cache.norm_2.forward_autoregressive(x, 1, |x| {
+                ForwardScope::run("norm_2", || self.norm_2.forward(x))
+            })
         }
 
         x
     }
+}
 
-    fn forward_autoregressive_inference(
+impl<B: Backend> EncoderLayerResiduals<'_, B> {
+    /// Applies the residual paths around the given self-attention and feed-forward sublayers.
+    pub(crate) fn forward(
         &self,
         input: Tensor<B, 3>,
-        mask_pad: Option<Tensor<B, 2, Bool>>,
-        mask_attn: Option<Tensor<B, 3, Bool>>,
-        cache: &mut TransformerEncoderLayerAutoregressiveCache<B>,
+        attn: &EncoderAttention<B>,
+        mha: impl Fn(MhaInput<B>) -> Tensor<B, 3>,
+        pwff: impl Fn(Tensor<B, 3>) -> Tensor<B, 3>,
     ) -> Tensor<B, 3> {
         // Multi-head attention residual path.
         let x = input;
@@ -303,50 +476,36 @@ impl<B: Backend> TransformerEncoderLayer<B> {
 
         // Normalize.
         if self.norm_first {
-            residual_path = cache
-                .norm_2
-                .forward_autoregressive(residual_path, 1, |x| self.norm_2.forward(x))
+            residual_path = ForwardScope::run("norm_2", || self.norm_2.forward(residual_path))
         }
 
         // Multi-head attention.
-        let mut input_mhs = MhaInput::self_attn(residual_path);
-        if let Some(mask_pad) = mask_pad {
-            input_mhs = input_mhs.mask_pad(mask_pad);
-        }
-        if let Some(mask_attn) = mask_attn {
-            input_mhs = input_mhs.mask_attn(mask_attn);
-        }
-        let residual_path = self.mha.forward_cache(input_mhs, &mut cache.mha).context;
+        let input_mhs = attn.mha_input(residual_path);
+        let residual_path = mha(input_mhs);
 
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Feed forward residual path.
         // Normalize.
         let residual_path = if self.norm_first {
-            cache
-                .norm_1
-                .forward_autoregressive(x.clone(), 1, |x| self.norm_1.forward(x))
+            ForwardScope::run("norm_1", || self.norm_1.forward(x.clone()))
         } else {
-            x = cache
-                .norm_1
-                .forward_autoregressive(x, 1, |x| self.norm_1.forward(x));
+            x = ForwardScope::run("norm_1", || self.norm_1.forward(x));
             x.clone()
         };
 
         // Feed forward.
-        let residual_path = cache
-            .pwff
-            .forward_autoregressive(residual_path, 1, |x| self.pwff.forward(x));
+        let residual_path = pwff(residual_path);
         let residual_path = self.dropout.forward(residual_path);
+        let residual_path = cast_to(residual_path, x.dtype());
         let mut x = x + residual_path;
 
         // Main path.
         // Normalize.
         if !self.norm_first {
-            x = cache
-                .norm_2
-                .forward_autoregressive(x, 1, |x| self.norm_2.forward(x))
+            x = ForwardScope::run("norm_2", || self.norm_2.forward(x))
         }
 
         x
@@ -391,6 +550,7 @@ impl<B: Backend> TransformerEncoderAutoregressiveCache<B> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::nn::RmsNormConfig;
     use crate::tensor::Distribution;
     use crate::{TestBackend, nn::attention::generate_autoregressive_mask};
     use burn_tensor::{Tolerance, ops::FloatElem};
@@ -413,6 +573,60 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_autoregressive_llama_style() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 2, 3];
+        test_autoregressive(
+            TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+                .with_norm_first(true)
+                .with_norm(RmsNormConfig::new(0).into())
+                .with_feed_forward(FeedForwardActivation::SwiGlu)
+                .with_rope(Some(RotaryEncodingConfig::new(16, 0))),
+        )
+    }
+
+    #[test]
+    #[should_panic = "should be a layer norm or an RMS norm"]
+    fn test_batch_norm_should_be_rejected() {
+        TransformerEncoderConfig::new(12, 24, 2, 1)
+            .with_norm(crate::nn::BatchNormConfig::new(0).into())
+            .init::<TestBackend>(&Default::default());
+    }
+
+    #[test]
+    fn test_autoregressive_alibi() {
+        let [d_model, d_ff, n_heads, num_layers] = [12, 24, 3, 2];
+        test_autoregressive(
+            TransformerEncoderConfig::new(d_model, d_ff, n_heads, num_layers)
+                .with_norm_first(true)
+                .with_alibi(true),
+        )
+    }
+
+    #[test]
+    fn test_causal_should_match_autoregressive_mask() {
+        let [batch_size, seq_length, d_model] = [2, 5, 12];
+        let device = Default::default();
+        let transformer = TransformerEncoderConfig::new(d_model, 24, 2, 2)
+            .with_causal(true)
+            .init::<TestBackend>(&device);
+
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Default,
+            &device,
+        );
+        let mask_attn = generate_autoregressive_mask(batch_size, seq_length, &device);
+
+        let output_causal = transformer.forward(TransformerEncoderInput::new(tensor.clone()));
+        let output_masked =
+            transformer.forward(TransformerEncoderInput::new(tensor).mask_attn(mask_attn));
+
+        output_causal
+            .into_data()
+            .assert_approx_eq::<FT>(&output_masked.into_data(), Tolerance::default());
+    }
+
     fn test_autoregressive(config: TransformerEncoderConfig) {
         let [batch_size, seq_length, d_model] = [3, 4, config.d_model];
         let device = Default::default();
@@ -446,6 +660,53 @@ mod tests {
             .assert_approx_eq::<FT>(&output_2.into_data(), Tolerance::permissive());
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_profile_operations_in_the_scopes_of_the_layers() {
+        use burn_router::{Profiler, Router};
+        type RouterBackend = Router<(burn_ndarray::NdArray, burn_ndarray::NdArray)>;
+
+        let device = Default::default();
+        let transformer = TransformerEncoderConfig::new(8, 16, 2, 2).init::<RouterBackend>(&device);
+        let tensor = Tensor::<RouterBackend, 3>::random([1, 3, 8], Distribution::Default, &device);
+        let mut cache = transformer.new_autoregressive_cache();
+
+        let profiler = Profiler::new().start();
+        ForwardScope::run("encoder", || {
+            transformer.forward(TransformerEncoderInput::new(tensor.clone()))
+        })
+        .into_data();
+        ForwardScope::run("cached", || {
+            transformer
+                .forward_autoregressive_inference(TransformerEncoderInput::new(tensor), &mut cache)
+        })
+        .into_data();
+        let profile = profiler.finish();
+
+        let scopes = profile
+            .events
+            .iter()
+            .map(|event| event.scope.as_str())
+            .collect::<Vec<_>>();
+        for scope in [
+            "encoder.layers.0.norm_1",
+            "encoder.layers.0.mha.query",
+            "encoder.layers.1.mha.output",
+            "encoder.layers.1.pwff.hidden.Gelu",
+            "encoder.layers.1.pwff.linear_outer",
+            "cached.layers.1.mha.value",
+            "cached.layers.0.pwff.linear_outer",
+        ] {
+            assert!(
+                scopes.contains(&scope),
+                "No operation in {scope}: {scopes:?}"
+            );
+        }
+        assert!(profile.events.iter().any(
+            |event| event.scope == "encoder.layers.0.mha.query" && event.name == "float.matmul"
+        ));
+    }
+
     #[test]
     fn display() {
         let config = TransformerEncoderConfig::new(2, 4, 2, 3);
@@ -0,0 +1,279 @@
+//! Records of the transformer modules saved before the normalization and the feed-forward
+//! network were selectable, when every layer used [LayerNorm](crate::nn::LayerNorm) and a GELU
+//! feed-forward network.
+//!
+//! Load them with the same recorder, then convert them into the current records to load them in
+//! modules initialized with the default [norm](super::TransformerEncoderConfig::norm) and
+//! [feed_forward](super::TransformerEncoderConfig::feed_forward) options:
+//!
+//! ```rust, ignore
+//! let record: LayerNormTransformerEncoderRecord<B> = recorder.load(path, &device)?;
+//! let encoder = config.init::<B>(&device).load_record(record.into());
+//! ```
+
+use alloc::vec::Vec;
+
+use super::{
+    FeedForwardHiddenRecord, PositionWiseFeedForwardRecord, TransformerDecoderLayerRecord,
+    TransformerDecoderRecord, TransformerEncoderLayerRecord, TransformerEncoderRecord,
+};
+use crate as burn;
+use crate::module::{ConstantRecord, Module};
+use crate::nn::attention::MultiHeadAttentionRecord;
+use crate::nn::norm::NormalizationRecord;
+use crate::nn::{Dropout, Gelu, LayerNormRecord, LinearRecord};
+use crate::record::Record;
+use crate::tensor::backend::Backend;
+
+/// The record of a [position-wise feed-forward](super::PositionWiseFeedForward) network with a
+/// GELU activation.
+#[derive(Record)]
+pub struct GeluPositionWiseFeedForwardRecord<B: Backend> {
+    /// The record of the inner linear layer.
+    pub linear_inner: LinearRecord<B>,
+    /// The record of the outer linear layer.
+    pub linear_outer: LinearRecord<B>,
+    /// The record of the dropout.
+    pub dropout: <Dropout as Module<B>>::Record,
+    /// The record of the activation.
+    pub gelu: <Gelu as Module<B>>::Record,
+}
+
+impl<B: Backend> From<GeluPositionWiseFeedForwardRecord<B>> for PositionWiseFeedForwardRecord<B> {
+    fn from(record: GeluPositionWiseFeedForwardRecord<B>) -> Self {
+        Self {
+            hidden: FeedForwardHiddenRecord::Gelu(record.linear_inner),
+            linear_outer: record.linear_outer,
+            dropout: record.dropout,
+        }
+    }
+}
+
+/// The record of a [transformer encoder layer](super::TransformerEncoderLayer) with
+/// [LayerNorm](crate::nn::LayerNorm) layers.
+#[derive(Record)]
+pub struct LayerNormTransformerEncoderLayerRecord<B: Backend> {
+    /// The record of the self-attention.
+    pub mha: MultiHeadAttentionRecord<B>,
+    /// The record of the feed-forward network.
+    pub pwff: GeluPositionWiseFeedForwardRecord<B>,
+    /// The record of the first normalization layer.
+    pub norm_1: LayerNormRecord<B>,
+    /// The record of the second normalization layer.
+    pub norm_2: LayerNormRecord<B>,
+    /// The record of the dropout.
+    pub dropout: <Dropout as Module<B>>::Record,
+    /// The record of the `norm_first` flag.
+    pub norm_first: ConstantRecord,
+}
+
+impl<B: Backend> From<LayerNormTransformerEncoderLayerRecord<B>>
+    for TransformerEncoderLayerRecord<B>
+{
+    fn from(record: LayerNormTransformerEncoderLayerRecord<B>) -> Self {
+        Self {
+            mha: record.mha,
+            pwff: record.pwff.into(),
+            norm_1: NormalizationRecord::Layer(record.norm_1),
+            norm_2: NormalizationRecord::Layer(record.norm_2),
+            dropout: record.dropout,
+            norm_first: record.norm_first,
+        }
+    }
+}
+
+/// The record of a [transformer encoder](super::TransformerEncoder) with
+/// [LayerNorm](crate::nn::LayerNorm) layers.
+#[derive(Record)]
+pub struct LayerNormTransformerEncoderRecord<B: Backend> {
+    /// The records of the layers.
+    pub layers: Vec<LayerNormTransformerEncoderLayerRecord<B>>,
+    /// The record of `d_model`.
+    pub d_model: ConstantRecord,
+    /// The record of `d_ff`.
+    pub d_ff: ConstantRecord,
+    /// The record of `n_heads`.
+    pub n_heads: ConstantRecord,
+    /// The record of `n_layers`.
+    pub n_layers: ConstantRecord,
+    /// The record of `dropout`.
+    pub dropout: ConstantRecord,
+    /// The record of `norm_first`.
+    pub norm_first: ConstantRecord,
+    /// The record of `quiet_softmax`.
+    pub quiet_softmax: ConstantRecord,
+}
+
+impl<B: Backend> From<LayerNormTransformerEncoderRecord<B>> for TransformerEncoderRecord<B> {
+    fn from(record: LayerNormTransformerEncoderRecord<B>) -> Self {
+        Self {
+            layers: record.layers.into_iter().map(Into::into).collect(),
+            d_model: record.d_model,
+            d_ff: record.d_ff,
+            n_heads: record.n_heads,
+            n_layers: record.n_layers,
+            dropout: record.dropout,
+            norm_first: record.norm_first,
+            quiet_softmax: record.quiet_softmax,
+            rope: None,
+            alibi: ConstantRecord::new(),
+            causal: ConstantRecord::new(),
+        }
+    }
+}
+
+/// The record of a [transformer decoder layer](super::TransformerDecoderLayer) with
+/// [LayerNorm](crate::nn::LayerNorm) layers.
+#[derive(Record)]
+pub struct LayerNormTransformerDecoderLayerRecord<B: Backend> {
+    /// The record of the cross-attention.
+    pub cross_attn: MultiHeadAttentionRecord<B>,
+    /// The record of the self-attention.
+    pub self_attn: MultiHeadAttentionRecord<B>,
+    /// The record of the feed-forward network.
+    pub pwff: GeluPositionWiseFeedForwardRecord<B>,
+    /// The record of the first normalization layer.
+    pub norm_1: LayerNormRecord<B>,
+    /// The record of the second normalization layer.
+    pub norm_2: LayerNormRecord<B>,
+    /// The record of the third normalization layer.
+    pub norm_3: LayerNormRecord<B>,
+    /// The record of the dropout.
+    pub dropout: <Dropout as Module<B>>::Record,
+    /// The record of the `norm_first` flag.
+    pub norm_first: ConstantRecord,
+}
+
+impl<B: Backend> From<LayerNormTransformerDecoderLayerRecord<B>>
+    for TransformerDecoderLayerRecord<B>
+{
+    fn from(record: LayerNormTransformerDecoderLayerRecord<B>) -> Self {
+        Self {
+            cross_attn: record.cross_attn,
+            self_attn: record.self_attn,
+            pwff: record.pwff.into(),
+            norm_1: NormalizationRecord::Layer(record.norm_1),
+            norm_2: NormalizationRecord::Layer(record.norm_2),
+            norm_3: NormalizationRecord::Layer(record.norm_3),
+            dropout: record.dropout,
+            norm_first: record.norm_first,
+        }
+    }
+}
+
+/// The record of a [transformer decoder](super::TransformerDecoder) with
+/// [LayerNorm](crate::nn::LayerNorm) layers.
+#[derive(Record)]
+pub struct LayerNormTransformerDecoderRecord<B: Backend> {
+    /// The records of the layers.
+    pub layers: Vec<LayerNormTransformerDecoderLayerRecord<B>>,
+    /// The record of `d_model`.
+    pub d_model: ConstantRecord,
+    /// The record of `d_ff`.
+    pub d_ff: ConstantRecord,
+    /// The record of `n_heads`.
+    pub n_heads: ConstantRecord,
+    /// The record of `n_layers`.
+    pub n_layers: ConstantRecord,
+    /// The record of `dropout`.
+    pub dropout: ConstantRecord,
+    /// The record of `norm_first`.
+    pub norm_first: ConstantRecord,
+    /// The record of `quiet_softmax`.
+    pub quiet_softmax: ConstantRecord,
+}
+
+impl<B: Backend> From<LayerNormTransformerDecoderRecord<B>> for TransformerDecoderRecord<B> {
+    fn from(record: LayerNormTransformerDecoderRecord<B>) -> Self {
+        Self {
+            layers: record.layers.into_iter().map(Into::into).collect(),
+            d_model: record.d_model,
+            d_ff: record.d_ff,
+            n_heads: record.n_heads,
+            n_layers: record.n_layers,
+            dropout: record.dropout,
+            norm_first: record.norm_first,
+            quiet_softmax: record.quiet_softmax,
+            rope: None,
+            alibi: ConstantRecord::new(),
+            causal: ConstantRecord::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::nn::transformer::{
+        TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput,
+    };
+    use crate::record::{BinBytesRecorder, FullPrecisionSettings, Recorder};
+    use crate::tensor::{Distribution, Tensor};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_load_layer_norm_encoder_record() {
+        let device = Default::default();
+        let config = TransformerEncoderConfig::new(8, 16, 2, 2).with_dropout(0.0);
+        let encoder: TransformerEncoder<TestBackend> = config.init(&device);
+
+        // Save the record with the layout of the encoder before the normalization was selectable.
+        let record = encoder.clone().into_record();
+        let legacy = LayerNormTransformerEncoderRecord::<TestBackend> {
+            layers: record
+                .layers
+                .into_iter()
+                .map(|layer| LayerNormTransformerEncoderLayerRecord {
+                    mha: layer.mha,
+                    pwff: GeluPositionWiseFeedForwardRecord {
+                        linear_inner: match layer.pwff.hidden {
+                            FeedForwardHiddenRecord::Gelu(linear) => linear,
+                            _ => unreachable!(),
+                        },
+                        linear_outer: layer.pwff.linear_outer,
+                        dropout: layer.pwff.dropout,
+                        gelu: Module::<TestBackend>::into_record(Gelu::new()),
+                    },
+                    norm_1: match layer.norm_1 {
+                        NormalizationRecord::Layer(norm) => norm,
+                        _ => unreachable!(),
+                    },
+                    norm_2: match layer.norm_2 {
+                        NormalizationRecord::Layer(norm) => norm,
+                        _ => unreachable!(),
+                    },
+                    dropout: layer.dropout,
+                    norm_first: layer.norm_first,
+                })
+                .collect(),
+            d_model: record.d_model,
+            d_ff: record.d_ff,
+            n_heads: record.n_heads,
+            n_layers: record.n_layers,
+            dropout: record.dropout,
+            norm_first: record.norm_first,
+            quiet_softmax: record.quiet_softmax,
+        };
+        let recorder = BinBytesRecorder::<FullPrecisionSettings>::default();
+        let bytes = recorder.record(legacy, ()).unwrap();
+
+        let legacy: LayerNormTransformerEncoderRecord<TestBackend> =
+            recorder.load(bytes, &device).unwrap();
+        let loaded = config
+            .init::<TestBackend>(&device)
+            .load_record(legacy.into());
+
+        let tensor = Tensor::random([2, 3, 8], Distribution::Default, &device);
+        loaded
+            .forward(TransformerEncoderInput::new(tensor.clone()))
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &encoder
+                    .forward(TransformerEncoderInput::new(tensor))
+                    .into_data(),
+                Tolerance::default(),
+            );
+    }
+}
@@ -1,7 +1,9 @@
 mod decoder;
 mod encoder;
+mod legacy;
 mod pwff;
 
 pub use decoder::*;
 pub use encoder::*;
+pub use legacy::*;
 pub use pwff::*;
@@ -1,12 +1,29 @@
 use crate as burn;
 
-use crate::module::{Content, DisplaySettings, Module, ModuleDisplay};
-use crate::nn::{Dropout, DropoutConfig, Gelu, Initializer, Linear, LinearConfig};
+use crate::module::{Content, DisplaySettings, ForwardScope, Module, ModuleDisplay};
+use crate::nn::{
+    Dropout, DropoutConfig, Gelu, Initializer, Linear, LinearConfig, SwiGlu, SwiGluConfig,
+};
 use crate::{
     config::Config,
     tensor::{Tensor, backend::Backend},
 };
 
+/// The activation used by the [position-wise feed-forward](PositionWiseFeedForward) network.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum FeedForwardActivation {
+    /// Two layers perceptron with a [GELU](Gelu) activation.
+    ///
+    /// `FFN(x) = GELU(xW1 + b1)W2 + b2`
+    Gelu,
+    /// [Swish gated linear unit](SwiGlu) without biases, as used by Llama and Mistral.
+    ///
+    /// `FFN(x) = (Swish(xW1) * xV)W2 + b2`
+    ///
+    /// Reference: <https://arxiv.org/abs/2002.05202>
+    SwiGlu,
+}
+
 /// Configuration to create a [position-wise feed-forward](PositionWiseFeedForward) layer using the [init function](PositionWiseFeedForwardConfig::init).
 #[derive(Config, Debug)]
 pub struct PositionWiseFeedForwardConfig {
@@ -22,13 +39,43 @@ pub struct PositionWiseFeedForwardConfig {
         default = "Initializer::KaimingUniform{gain:1.0/num_traits::Float::sqrt(3.0), fan_out_only:false}"
     )]
     pub initializer: Initializer,
+    /// The activation applied to the hidden features. Default: GELU
+    #[config(default = "FeedForwardActivation::Gelu")]
+    pub activation: FeedForwardActivation,
+}
+
+/// The hidden layer of the [position-wise feed-forward](PositionWiseFeedForward) network, which
+/// depends on its [activation](FeedForwardActivation).
+#[derive(Module, Debug)]
+pub enum FeedForwardHidden<B: Backend> {
+    /// Linear layer with `d_model` input features and `d_ff` output features, followed by a
+    /// [GELU](Gelu) activation.
+    Gelu(Linear<B>),
+    /// [Gated linear unit](SwiGlu) with `d_model` input features and `d_ff` output features.
+    SwiGlu(SwiGlu<B>),
+}
+
+impl<B: Backend> FeedForwardHidden<B> {
+    /// Applies the hidden layer and its activation on the input tensor.
+    pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
+        match self {
+            FeedForwardHidden::Gelu(linear) => {
+                let x = ForwardScope::run("Gelu", || linear.forward(input));
+                Gelu::new().forward(x)
+            }
+            FeedForwardHidden::SwiGlu(swiglu) => {
+                ForwardScope::run("SwiGlu", || swiglu.forward(input))
+            }
+        }
+    }
 }
 
 /// Applies the position-wise feed-forward network to the input tensor from the paper [Attention Is All You Need](https://arxiv.org/pdf/1706.03762v7).
 ///
 /// # Params
 ///
-/// - linear inner: Linear layer with `d_model` input features and `d_ff` output features.
+/// - hidden: [Hidden layer](FeedForwardHidden) with `d_model` input features and `d_ff` output
+///   features.
 /// - linear outer: Linear layer with `d_ff` input features and `d_model` output features.
 ///
 /// `FFN(x) = max(0, xW1 + b1)W2 + b2`
@@ -37,14 +84,12 @@ pub struct PositionWiseFeedForwardConfig {
 #[derive(Module, Debug)]
 #[module(custom_display)]
 pub struct PositionWiseFeedForward<B: Backend> {
-    /// Linear layer with `d_model` input features and `d_ff` output features.
-    pub linear_inner: Linear<B>,
+    /// The hidden layer with its activation.
+    pub hidden: FeedForwardHidden<B>,
     /// Linear layer with `d_ff` input features and `d_model` output features.
     pub linear_outer: Linear<B>,
     /// Dropout layer.
     pub dropout: Dropout,
-    /// GELU activation function.
-    pub gelu: Gelu,
 }
 
 impl<B: Backend> ModuleDisplay for PositionWiseFeedForward<B> {
@@ -55,7 +100,7 @@ impl<B: Backend> ModuleDisplay for PositionWiseFeedForward<B> {
     }
 
     fn custom_content(&self, content: Content) -> Option<Content> {
-        let [d_model, dff] = self.linear_inner.weight.shape().dims();
+        let [dff, d_model] = self.linear_outer.weight.shape().dims();
 
         content
             .add("d_model", &d_model)
@@ -68,15 +113,25 @@ impl<B: Backend> ModuleDisplay for PositionWiseFeedForward<B> {
 impl PositionWiseFeedForwardConfig {
     /// Initialize a new [position-wise feed-forward](PositionWiseFeedForward) module.
     pub fn init<B: Backend>(&self, device: &B::Device) -> PositionWiseFeedForward<B> {
+        let hidden = match self.activation {
+            FeedForwardActivation::Gelu => FeedForwardHidden::Gelu(
+                LinearConfig::new(self.d_model, self.d_ff)
+                    .with_initializer(self.initializer.clone())
+                    .init(device),
+            ),
+            FeedForwardActivation::SwiGlu => FeedForwardHidden::SwiGlu(
+                SwiGluConfig::new(self.d_model, self.d_ff)
+                    .with_initializer(self.initializer.clone())
+                    .init(device),
+            ),
+        };
+
         PositionWiseFeedForward {
-            linear_inner: LinearConfig::new(self.d_model, self.d_ff)
-                .with_initializer(self.initializer.clone())
-                .init(device),
+            hidden,
             linear_outer: LinearConfig::new(self.d_ff, self.d_model)
                 .with_initializer(self.initializer.clone())
                 .init(device),
             dropout: DropoutConfig::new(self.dropout).init(),
-            gelu: Gelu::new(),
         }
     }
 }
@@ -89,11 +144,10 @@ impl<B: Backend> PositionWiseFeedForward<B> {
     /// - tensor: `[batch_size, seq_length, d_model]`
     /// - output: `[batch_size, seq_length, d_model]`
     pub fn forward<const D: usize>(&self, input: Tensor<B, D>) -> Tensor<B, D> {
-        let x = self.linear_inner.forward(input);
-        let x = self.gelu.forward(x);
+        let x = ForwardScope::run("hidden", || self.hidden.forward(input));
         let x = self.dropout.forward(x);
 
-        self.linear_outer.forward(x)
+        ForwardScope::run("linear_outer", || self.linear_outer.forward(x))
     }
 }
 
@@ -101,6 +155,8 @@ impl<B: Backend> PositionWiseFeedForward<B> {
 mod tests {
     use super::*;
     use crate::TestBackend;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+    type FT = FloatElem<TestBackend>;
 
     #[test]
     fn display() {
@@ -112,4 +168,24 @@ mod tests {
             "PositionWiseFeedForward {d_model: 2, d_ff: 4, prob: 0.1, params: 22}"
         );
     }
+
+    #[test]
+    fn test_swiglu_activation() {
+        let device = Default::default();
+        let pwff = PositionWiseFeedForwardConfig::new(3, 4)
+            .with_dropout(0.0)
+            .with_activation(FeedForwardActivation::SwiGlu)
+            .init::<TestBackend>(&device);
+        let input =
+            Tensor::<TestBackend, 2>::from_data([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]], &device);
+
+        let FeedForwardHidden::SwiGlu(swiglu) = &pwff.hidden else {
+            panic!("SwiGLU should be initialized");
+        };
+        let expected = pwff.linear_outer.forward(swiglu.forward(input.clone()));
+
+        pwff.forward(input)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected.into_data(), Tolerance::default());
+    }
 }
@@ -0,0 +1,272 @@
+use crate::{
+    self as burn, LearningRate, grad_clipping::GradientClippingConfig, module::AutodiffModule,
+    record::Record,
+};
+
+use super::SimpleOptimizer;
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{Tensor, backend::AutodiffBackend};
+use burn_tensor::{backend::Backend, ops::Device};
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+/// Adafactor configuration.
+#[derive(Config, Debug)]
+pub struct AdafactorConfig {
+    /// Regularization constant added to the squared gradient.
+    #[config(default = 1e-30)]
+    epsilon_1: f32,
+    /// Lower bound of the parameter scale when `scale_parameter` is enabled.
+    #[config(default = 1e-3)]
+    epsilon_2: f32,
+    /// Threshold of the root mean square of the update, above which the update is scaled down.
+    #[config(default = 1.0)]
+    clip_threshold: f32,
+    /// Exponent of the second moment decay, `beta_2 = 1 - t^decay_rate` at step `t`.
+    #[config(default = -0.8)]
+    decay_rate: f32,
+    /// Coefficient of the first moment, which isn't tracked when not provided.
+    beta_1: Option<f32>,
+    /// Decoupled weight decay coefficient.
+    #[config(default = 0.0)]
+    weight_decay: f32,
+    /// Whether the learning rate is scaled by the root mean square of the parameter.
+    #[config(default = true)]
+    scale_parameter: bool,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+}
+
+/// Adafactor optimizer as described in the paper
+/// [Adafactor: Adaptive Learning Rates with Sublinear Memory Cost](https://arxiv.org/abs/1804.04235).
+///
+/// For parameters with at least two dimensions, the second moment is factored into running
+/// averages over the rows and the columns of the last two dimensions, so the state only grows
+/// with the sum of the dimensions instead of their product. The learning rate is provided by the
+/// caller, so relative step sizes are expressed with a [learning rate scheduler](crate::lr_scheduler).
+#[derive(Clone)]
+pub struct Adafactor {
+    epsilon_1: f32,
+    epsilon_2: f32,
+    clip_threshold: f32,
+    decay_rate: f32,
+    beta_1: Option<f32>,
+    weight_decay: f32,
+    scale_parameter: bool,
+}
+
+/// Adafactor state.
+#[derive(Record, Clone, new)]
+pub struct AdafactorState<B: Backend, const D: usize> {
+    /// The number of iterations aggregated.
+    pub time: usize,
+    /// The running average of the squared gradient over the last dimension, when factored.
+    pub row: Option<Tensor<B, D>>,
+    /// The running average of the squared gradient over the second to last dimension, when factored.
+    pub col: Option<Tensor<B, D>>,
+    /// The running average of the squared gradient, when not factored.
+    pub second_moment: Option<Tensor<B, D>>,
+    /// The first moment of the update, when `beta_1` is set.
+    pub moment_1: Option<Tensor<B, D>>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Adafactor {
+    type State<const D: usize> = AdafactorState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        let (time, row, col, second_moment, moment_1) = match state {
+            Some(state) => (
+                state.time + 1,
+                state.row,
+                state.col,
+                state.second_moment,
+                state.moment_1,
+            ),
+            None => (1, None, None, None, None),
+        };
+
+        let beta_2 = 1.0 - (time as f32).powf(self.decay_rate);
+        let grad_sq = grad.clone().powi_scalar(2).add_scalar(self.epsilon_1);
+
+        let (variance, row, col, second_moment) = if D >= 2 {
+            let row = moving_average(row, grad_sq.clone().mean_dim(D - 1), beta_2);
+            let col = moving_average(col, grad_sq.mean_dim(D - 2), beta_2);
+            let variance = row
+                .clone()
+                .div(row.clone().mean_dim(D - 2))
+                .mul(col.clone());
+            (variance, Some(row), Some(col), None)
+        } else {
+            let second_moment = moving_average(second_moment, grad_sq, beta_2);
+            (second_moment.clone(), None, None, Some(second_moment))
+        };
+
+        let update = grad.div(variance.sqrt());
+        let update = update.clone().div(
+            root_mean_square(&update)
+                .div_scalar(self.clip_threshold)
+                .clamp_min(1.0),
+        );
+
+        let scale = self
+            .scale_parameter
+            .then(|| root_mean_square(&tensor).clamp_min(self.epsilon_2));
+        let scale_lr = |value: Tensor<B, D>| match &scale {
+            Some(scale) => value.mul(scale.clone()).mul_scalar(lr),
+            None => value.mul_scalar(lr),
+        };
+        let update = scale_lr(update);
+
+        let (update, moment_1) = match self.beta_1 {
+            Some(beta_1) => {
+                let moment_1 = moving_average(moment_1, update, beta_1);
+                (moment_1.clone(), Some(moment_1))
+            }
+            None => (update, None),
+        };
+
+        let decay = scale_lr(tensor.clone()).mul_scalar(self.weight_decay);
+        let tensor = tensor - decay - update;
+
+        let state = AdafactorState::new(time, row, col, second_moment, moment_1);
+        (tensor, Some(state))
+    }
+
+    fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
+        state.row = state.row.map(|tensor| tensor.to_device(device));
+        state.col = state.col.map(|tensor| tensor.to_device(device));
+        state.second_moment = state.second_moment.map(|tensor| tensor.to_device(device));
+        state.moment_1 = state.moment_1.map(|tensor| tensor.to_device(device));
+        state
+    }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        Some(optim)
+    }
+}
+
+impl AdafactorConfig {
+    /// Initialize Adafactor optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+    ) -> OptimizerAdaptor<Adafactor, M, B> {
+        let optim = Adafactor {
+            epsilon_1: self.epsilon_1,
+            epsilon_2: self.epsilon_2,
+            clip_threshold: self.clip_threshold,
+            decay_rate: self.decay_rate,
+            beta_1: self.beta_1,
+            weight_decay: self.weight_decay,
+            scale_parameter: self.scale_parameter,
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        optim
+    }
+}
+
+/// Exponential moving average `beta * average + (1 - beta) * value`, starting from zero.
+fn moving_average<B: Backend, const D: usize>(
+    average: Option<Tensor<B, D>>,
+    value: Tensor<B, D>,
+    beta: f32,
+) -> Tensor<B, D> {
+    let value = value.mul_scalar(1.0 - beta);
+    match average {
+        Some(average) => average.mul_scalar(beta).add(value),
+        None => value,
+    }
+}
+
+/// Root mean square of all the elements, with a single element broadcastable to the tensor.
+fn root_mean_square<B: Backend, const D: usize>(tensor: &Tensor<B, D>) -> Tensor<B, D> {
+    tensor.clone().powi_scalar(2).mean().sqrt().unsqueeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_adafactor_optimizer_with_numbers() {
+        let device = Default::default();
+        let optim = Adafactor {
+            epsilon_1: 1e-30,
+            epsilon_2: 1e-3,
+            clip_threshold: 1.0,
+            decay_rate: -0.8,
+            beta_1: Some(0.9),
+            weight_decay: 0.01,
+            scale_parameter: true,
+        };
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(0.01, tensor, grad_1, None);
+        let (tensor, state) = optim.step(0.01, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.500482, -0.998228, 1.996140],
+                [0.297490, -0.198897, 1.497855],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+
+        let state = state.unwrap();
+        assert_eq!(state.row.unwrap().dims(), [2, 1]);
+        assert_eq!(state.col.unwrap().dims(), [1, 3]);
+        assert!(state.second_moment.is_none());
+    }
+
+    #[test]
+    fn test_adafactor_optimizer_not_factored() {
+        let device = Default::default();
+        let optim = Adafactor {
+            epsilon_1: 1e-30,
+            epsilon_2: 1e-3,
+            clip_threshold: 1.0,
+            decay_rate: -0.8,
+            beta_1: None,
+            weight_decay: 0.0,
+            scale_parameter: false,
+        };
+        let tensor = Tensor::<TestBackend, 1>::from_floats([0.5, -1.0, 2.0], &device);
+        let grad_1 = Tensor::from_floats([0.1, -0.2, 0.3], &device);
+        let grad_2 = Tensor::from_floats([-0.3, 0.1, 0.2], &device);
+
+        let (tensor, state) = optim.step(0.01, tensor, grad_1, None);
+        let (tensor, state) = optim.step(0.01, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([0.502683, -0.996627, 1.981921]),
+            Tolerance::absolute(1e-5),
+        );
+        assert!(state.unwrap().row.is_none());
+    }
+}
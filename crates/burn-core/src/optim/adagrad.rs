@@ -69,6 +69,13 @@ impl<B: Backend> SimpleOptimizer<B> for AdaGrad {
         state.lr_decay = state.lr_decay.to_device(device);
         state
     }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay =
+            (penalty != 0.0).then(|| WeightDecay::new(&WeightDecayConfig::new(penalty)));
+        Some(optim)
+    }
 }
 
 impl AdaGradConfig {
@@ -28,6 +28,15 @@ pub struct AdamConfig {
     /// A value required for numerical stability.
     #[config(default = 1e-5)]
     epsilon: f32,
+    /// Whether to use the AMSGrad variant from the paper
+    /// [On the Convergence of Adam and Beyond](https://openreview.net/forum?id=ryQu7f-RZ),
+    /// which normalizes by the maximum of the second moment estimates.
+    #[config(default = false)]
+    amsgrad: bool,
+    /// Whether the weight decay is decoupled from the gradient and applied directly to the
+    /// parameters, as in [AdamW](crate::optim::AdamW).
+    #[config(default = false)]
+    decoupled_weight_decay: bool,
     /// [Weight decay](WeightDecayConfig) config.
     weight_decay: Option<WeightDecayConfig>,
     /// [Gradient Clipping](GradientClippingConfig) config.
@@ -39,6 +48,7 @@ pub struct AdamConfig {
 pub struct Adam {
     momentum: AdaptiveMomentum,
     weight_decay: Option<WeightDecay>,
+    decoupled_weight_decay: bool,
 }
 
 /// Adam state.
@@ -46,6 +56,8 @@ pub struct Adam {
 pub struct AdamState<B: Backend, const D: usize> {
     /// The current adaptive momentum.
     pub momentum: AdaptiveMomentumState<B, D>,
+    /// The maximum of the second order momentum, when using AMSGrad.
+    pub max_moment_2: Option<Tensor<B, D>>,
 }
 
 impl<B: Backend> SimpleOptimizer<B> for Adam {
@@ -54,23 +66,30 @@ impl<B: Backend> SimpleOptimizer<B> for Adam {
     fn step<const D: usize>(
         &self,
         lr: LearningRate,
-        tensor: Tensor<B, D>,
+        mut tensor: Tensor<B, D>,
         mut grad: Tensor<B, D>,
         state: Option<Self::State<D>>,
     ) -> (Tensor<B, D>, Option<Self::State<D>>) {
         let mut state_momentum = None;
+        let mut max_moment_2 = None;
 
         if let Some(state) = state {
             state_momentum = Some(state.momentum);
+            max_moment_2 = state.max_moment_2;
         }
 
         if let Some(weight_decay) = &self.weight_decay {
-            grad = weight_decay.transform(grad, tensor.clone());
+            if self.decoupled_weight_decay {
+                tensor = weight_decay.decay(tensor, lr);
+            } else {
+                grad = weight_decay.transform(grad, tensor.clone());
+            }
         }
 
-        let (grad, state_momentum) = self.momentum.transform(grad, state_momentum);
+        let (grad, state_momentum, max_moment_2) =
+            self.momentum.transform(grad, state_momentum, max_moment_2);
 
-        let state = AdamState::new(state_momentum);
+        let state = AdamState::new(state_momentum, max_moment_2);
         let delta = grad.mul_scalar(lr);
 
         (tensor - delta, Some(state))
@@ -78,8 +97,16 @@ impl<B: Backend> SimpleOptimizer<B> for Adam {
 
     fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
         state.momentum = state.momentum.to_device(device);
+        state.max_moment_2 = state.max_moment_2.map(|moment| moment.to_device(device));
         state
     }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay =
+            (penalty != 0.0).then(|| WeightDecay::new(&WeightDecayConfig::new(penalty)));
+        Some(optim)
+    }
 }
 
 impl AdamConfig {
@@ -94,8 +121,10 @@ impl AdamConfig {
                 beta_1: self.beta_1,
                 beta_2: self.beta_2,
                 epsilon: self.epsilon,
+                amsgrad: self.amsgrad,
             },
             weight_decay: self.weight_decay.as_ref().map(WeightDecay::new),
+            decoupled_weight_decay: self.decoupled_weight_decay,
         };
 
         let mut optim = OptimizerAdaptor::from(optim);
@@ -118,10 +147,11 @@ pub struct AdaptiveMomentumState<B: Backend, const D: usize> {
 }
 
 #[derive(Clone)]
-struct AdaptiveMomentum {
-    beta_1: f32,
-    beta_2: f32,
-    epsilon: f32,
+pub(crate) struct AdaptiveMomentum {
+    pub(crate) beta_1: f32,
+    pub(crate) beta_2: f32,
+    pub(crate) epsilon: f32,
+    pub(crate) amsgrad: bool,
 }
 
 impl AdaptiveMomentum {
@@ -129,7 +159,12 @@ impl AdaptiveMomentum {
         &self,
         grad: Tensor<B, D>,
         momentum_state: Option<AdaptiveMomentumState<B, D>>,
-    ) -> (Tensor<B, D>, AdaptiveMomentumState<B, D>) {
+        max_moment_2: Option<Tensor<B, D>>,
+    ) -> (
+        Tensor<B, D>,
+        AdaptiveMomentumState<B, D>,
+        Option<Tensor<B, D>>,
+    ) {
         let state = if let Some(mut state) = momentum_state {
             let factor = 1.0 - self.beta_1;
             state.moment_1 = state
@@ -156,19 +191,25 @@ impl AdaptiveMomentum {
             AdaptiveMomentumState::new(1, moment_1, moment_2)
         };
 
+        let max_moment_2 = match (self.amsgrad, max_moment_2) {
+            (true, Some(max_moment_2)) => Some(max_moment_2.max_pair(state.moment_2.clone())),
+            (true, None) => Some(state.moment_2.clone()),
+            (false, _) => None,
+        };
+
         let time = state.time as i32;
         let moment_1_corrected = state
             .moment_1
             .clone()
             .div_scalar(1f32 - self.beta_1.powi(time));
-        let moment_2_corrected = state
-            .moment_2
+        let moment_2_corrected = max_moment_2
             .clone()
+            .unwrap_or_else(|| state.moment_2.clone())
             .div_scalar(1f32 - self.beta_2.powi(time));
 
         let grad = moment_1_corrected.div(moment_2_corrected.sqrt().add_scalar(self.epsilon));
 
-        (grad, state)
+        (grad, state, max_moment_2)
     }
 }
 
@@ -195,11 +236,13 @@ mod tests {
     use burn_tensor::ops::FloatElem;
 
     use super::*;
-    use crate::TestAutodiffBackend;
     use crate::module::{Module, Param};
     use crate::nn::{Linear, LinearConfig, LinearRecord};
     use crate::optim::{GradientsParams, Optimizer};
     use crate::tensor::{Distribution, Tensor, TensorData};
+    use crate::{TestAutodiffBackend, TestBackend};
+
+    type FT = FloatElem<TestAutodiffBackend>;
 
     const LEARNING_RATE: LearningRate = 0.01;
 
@@ -315,7 +358,6 @@ mod tests {
             state_updated.bias.unwrap().to_data(),
         );
 
-        type FT = FloatElem<TestAutodiffBackend>;
         let tolerance = Tolerance::absolute(1e-2);
         bias_updated.assert_approx_eq::<FT>(&bias_expected, tolerance);
         weight_updated.assert_approx_eq::<FT>(&weights_expected, tolerance);
@@ -363,6 +405,67 @@ mod tests {
         assert!(!state_updated.weight.to_data().as_slice::<f32>().unwrap()[0].is_nan());
     }
 
+    #[test]
+    fn test_adam_amsgrad_should_use_max_second_moment() {
+        let optim = Adam {
+            momentum: AdaptiveMomentum {
+                beta_1: 0.9,
+                beta_2: 0.5,
+                epsilon: 1e-8,
+                amsgrad: true,
+            },
+            weight_decay: None,
+            decoupled_weight_decay: false,
+        };
+
+        let tensor = two_steps(&optim);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.494392, -0.987422, 1.979901],
+                [0.283393, -0.189396, 1.485273],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+    }
+
+    #[test]
+    fn test_adam_decoupled_weight_decay() {
+        let optim = Adam {
+            momentum: AdaptiveMomentum {
+                beta_1: 0.9,
+                beta_2: 0.999,
+                epsilon: 1e-8,
+                amsgrad: false,
+            },
+            weight_decay: Some(WeightDecay::new(&WeightDecayConfig::new(0.5))),
+            decoupled_weight_decay: true,
+        };
+
+        let tensor = two_steps(&optim);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.490004, -0.977412, 1.960396],
+                [0.279485, -0.187474, 1.469702],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+    }
+
+    fn two_steps(optim: &Adam) -> Tensor<TestBackend, 2> {
+        let device = Default::default();
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(LEARNING_RATE, tensor, grad_1, None);
+        let (tensor, _state) = optim.step(LEARNING_RATE, tensor, grad_2, state);
+
+        tensor
+    }
+
     fn given_linear_layer(weight: TensorData, bias: TensorData) -> Linear<TestAutodiffBackend> {
         let device = Default::default();
         let record = LinearRecord {
@@ -380,8 +483,10 @@ mod tests {
                 beta_1: config.beta_1,
                 beta_2: config.beta_2,
                 epsilon: config.epsilon,
+                amsgrad: config.amsgrad,
             },
             weight_decay: config.weight_decay.as_ref().map(WeightDecay::new),
+            decoupled_weight_decay: config.decoupled_weight_decay,
         }
         .into()
     }
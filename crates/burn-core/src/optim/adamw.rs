@@ -75,6 +75,12 @@ impl<B: Backend> SimpleOptimizer<B> for AdamW {
         state.momentum = state.momentum.to_device(device);
         state
     }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        Some(optim)
+    }
 }
 
 impl AdamWConfig {
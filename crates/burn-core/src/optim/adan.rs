@@ -0,0 +1,203 @@
+use crate::{
+    self as burn, LearningRate, grad_clipping::GradientClippingConfig, module::AutodiffModule,
+    record::Record,
+};
+
+use super::SimpleOptimizer;
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{Tensor, backend::AutodiffBackend};
+use burn_tensor::{backend::Backend, ops::Device};
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+/// Adan configuration.
+#[derive(Config, Debug)]
+pub struct AdanConfig {
+    /// Coefficient of the gradient moving average.
+    #[config(default = 0.98)]
+    beta_1: f32,
+    /// Coefficient of the gradient difference moving average.
+    #[config(default = 0.92)]
+    beta_2: f32,
+    /// Coefficient of the second moment moving average.
+    #[config(default = 0.99)]
+    beta_3: f32,
+    /// A value required for numerical stability.
+    #[config(default = 1e-8)]
+    epsilon: f32,
+    /// Decoupled weight decay coefficient.
+    #[config(default = 0.0)]
+    weight_decay: f32,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+}
+
+/// Adan (Adaptive Nesterov momentum) optimizer as described in the paper
+/// [Adan: Adaptive Nesterov Momentum Algorithm for Faster Optimizing Deep Models](https://arxiv.org/abs/2208.06677).
+#[derive(Clone)]
+pub struct Adan {
+    beta_1: f32,
+    beta_2: f32,
+    beta_3: f32,
+    epsilon: f32,
+    weight_decay: f32,
+}
+
+/// Adan state.
+#[derive(Record, Clone, new)]
+pub struct AdanState<B: Backend, const D: usize> {
+    /// The number of iterations aggregated.
+    pub time: usize,
+    /// The moving average of the gradient.
+    pub moment_1: Tensor<B, D>,
+    /// The moving average of the gradient difference.
+    pub moment_diff: Tensor<B, D>,
+    /// The moving average of the squared Nesterov gradient.
+    pub moment_2: Tensor<B, D>,
+    /// The gradient of the previous iteration.
+    pub grad_prev: Tensor<B, D>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Adan {
+    type State<const D: usize> = AdanState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        let (time, moment_1, moment_diff, moment_2, grad_diff) = match state {
+            Some(state) => (
+                state.time + 1,
+                state.moment_1,
+                state.moment_diff,
+                state.moment_2,
+                grad.clone() - state.grad_prev,
+            ),
+            None => (
+                1,
+                grad.zeros_like(),
+                grad.zeros_like(),
+                grad.zeros_like(),
+                grad.zeros_like(),
+            ),
+        };
+
+        let moment_1 = moment_1
+            .mul_scalar(self.beta_1)
+            .add(grad.clone().mul_scalar(1.0 - self.beta_1));
+        let moment_diff = moment_diff
+            .mul_scalar(self.beta_2)
+            .add(grad_diff.clone().mul_scalar(1.0 - self.beta_2));
+        let nesterov_grad = grad.clone().add(grad_diff.mul_scalar(self.beta_2));
+        let moment_2 = moment_2
+            .mul_scalar(self.beta_3)
+            .add(nesterov_grad.powi_scalar(2).mul_scalar(1.0 - self.beta_3));
+
+        let time_i32 = time as i32;
+        let bias_correction_1 = 1.0 - self.beta_1.powi(time_i32);
+        let bias_correction_2 = 1.0 - self.beta_2.powi(time_i32);
+        let bias_correction_3 = 1.0 - self.beta_3.powi(time_i32);
+
+        let denominator = moment_2
+            .clone()
+            .div_scalar(bias_correction_3)
+            .sqrt()
+            .add_scalar(self.epsilon);
+        let update = moment_1
+            .clone()
+            .div_scalar(bias_correction_1)
+            .add(
+                moment_diff
+                    .clone()
+                    .div_scalar(bias_correction_2)
+                    .mul_scalar(self.beta_2),
+            )
+            .div(denominator);
+
+        let tensor = tensor.clone() - tensor.mul_scalar(lr).mul_scalar(self.weight_decay);
+        let tensor = tensor - update.mul_scalar(lr);
+
+        let state = AdanState::new(time, moment_1, moment_diff, moment_2, grad);
+        (tensor, Some(state))
+    }
+
+    fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
+        state.moment_1 = state.moment_1.to_device(device);
+        state.moment_diff = state.moment_diff.to_device(device);
+        state.moment_2 = state.moment_2.to_device(device);
+        state.grad_prev = state.grad_prev.to_device(device);
+        state
+    }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        Some(optim)
+    }
+}
+
+impl AdanConfig {
+    /// Initialize Adan optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(&self) -> OptimizerAdaptor<Adan, M, B> {
+        let optim = Adan {
+            beta_1: self.beta_1,
+            beta_2: self.beta_2,
+            beta_3: self.beta_3,
+            epsilon: self.epsilon,
+            weight_decay: self.weight_decay,
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        optim
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_adan_optimizer_with_numbers() {
+        let device = Default::default();
+        let optim = Adan {
+            beta_1: 0.98,
+            beta_2: 0.92,
+            beta_3: 0.99,
+            epsilon: 1e-8,
+            weight_decay: 0.02,
+        };
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(0.01, tensor, grad_1, None);
+        let (tensor, _state) = optim.step(0.01, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.495936, -0.992761, 1.980244],
+                [0.288257, -0.194030, 1.490718],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use burn_tensor::backend::Backend;
 
-use crate as burn;
 use crate::record::Record;
+use crate::{self as burn, LearningRate};
 
 use crate::config::Config;
 use crate::tensor::Tensor;
@@ -50,6 +50,24 @@ impl WeightDecay {
     ) -> Tensor<B, D> {
         tensor.mul_scalar(self.penalty).add(grad)
     }
+
+    /// Applies the weight decay directly to the parameter, decoupled from the gradient.
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor` - Tensor param of the last iteration.
+    /// * `lr` - The learning rate of the current step.
+    ///
+    /// # Returns
+    ///
+    /// * `tensor` - Decayed tensor.
+    pub fn decay<B: Backend, const D: usize>(
+        &self,
+        tensor: Tensor<B, D>,
+        lr: LearningRate,
+    ) -> Tensor<B, D> {
+        tensor.clone() - tensor.mul_scalar(lr).mul_scalar(self.penalty)
+    }
 }
 
 impl<B: Backend, const D: usize> WeightDecayState<B, D> {
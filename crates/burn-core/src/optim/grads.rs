@@ -2,14 +2,16 @@
 use burn_collective::{CollectiveError, PeerId, ReduceOperation, all_reduce};
 
 use burn_tensor::{
-    Tensor,
+    ElementConversion, Tensor,
     backend::{AutodiffBackend, Backend},
     container::TensorContainer,
 };
 
 use crate::module::{AutodiffModule, ParamId};
 
-use super::visitor::{GradientsParamsChangeDevice, GradientsParamsConverter};
+use super::visitor::{
+    GradientsParamsChangeDevice, GradientsParamsConverter, GradientsParamsSquaredNorm,
+};
 
 /// Data type that contains gradients for parameters.
 #[derive(Default, Debug)]
@@ -113,6 +115,30 @@ impl GradientsParams {
         self
     }
 
+    /// The L2 norm of all the gradients registered for the given [module](AutodiffModule), as if
+    /// they were concatenated into a single vector.
+    ///
+    /// # Notes
+    ///
+    /// This reads the norm back from the device, use it for monitoring rather than on every
+    /// parameter.
+    pub fn l2_norm<B: AutodiffBackend, M: AutodiffModule<B>>(&self, module: &M) -> f64 {
+        self.squared_l2_norm::<B, M>(module)
+            .map(|squared_norm| squared_norm.sqrt().into_scalar().elem())
+            .unwrap_or(0.0)
+    }
+
+    /// The squared L2 norm of all the gradients registered for the given module, on the device of
+    /// the first gradient, or `None` if there is no gradient.
+    pub(crate) fn squared_l2_norm<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+        module: &M,
+    ) -> Option<Tensor<B::InnerBackend, 1>> {
+        let mut visitor = GradientsParamsSquaredNorm::<M, B>::new(self);
+        module.visit(&mut visitor);
+        visitor.squared_norm
+    }
+
     /// Syncs the gradient params with the other peers in the collective.
     #[cfg(feature = "collective")]
     pub fn all_reduce<B: Backend>(
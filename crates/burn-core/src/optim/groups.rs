@@ -0,0 +1,233 @@
+use crate::module::ParamId;
+use alloc::{string::String, vec::Vec};
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+#[cfg(not(target_has_atomic = "ptr"))]
+use portable_atomic_util::Arc;
+
+#[cfg(target_has_atomic = "ptr")]
+type SharedFilter = Arc<dyn ParamPathFilter>;
+
+#[cfg(not(target_has_atomic = "ptr"))]
+type SharedFilter = Arc<alloc::boxed::Box<dyn ParamPathFilter>>;
+
+#[cfg(target_has_atomic = "ptr")]
+fn new_filter<F: ParamPathFilter + 'static>(filter: F) -> SharedFilter {
+    Arc::new(filter)
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+fn new_filter<F: ParamPathFilter + 'static>(filter: F) -> SharedFilter {
+    Arc::new(alloc::boxed::Box::new(filter))
+}
+
+/// Filter on the module path of a parameter, used to select the members of a
+/// [parameter group](ParamGroup).
+///
+/// Paths are dot-separated field names (e.g. `encoder.layers.0.attn.query.weight`), and the
+/// container path holds the matching container types (e.g. `Model.Vec.Layer.Linear.Param`).
+///
+/// The `PathFilter` of `burn-store` implements this trait, so the same filters used to load
+/// and save tensors can be used to build parameter groups.
+pub trait ParamPathFilter: Send + Sync {
+    /// Returns true if the parameter at the given path should be selected.
+    fn matches(&self, path: &str, container_path: &str) -> bool;
+}
+
+/// Selects the parameters belonging to a [parameter group](ParamGroup).
+#[derive(Clone)]
+pub enum ParamSelector {
+    /// Parameters with one of the given ids.
+    Ids(Vec<ParamId>),
+    /// Parameters whose module path is, or is nested under, one of the given paths.
+    ///
+    /// For instance, `encoder` selects both `encoder.weight` and `encoder.layers.0.bias`, but not
+    /// `encoder_norm.gamma`.
+    Paths(Vec<String>),
+    /// Parameters accepted by a [path filter](ParamPathFilter).
+    Filter(SharedFilter),
+}
+
+impl core::fmt::Debug for ParamSelector {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Ids(ids) => f.debug_tuple("Ids").field(ids).finish(),
+            Self::Paths(paths) => f.debug_tuple("Paths").field(paths).finish(),
+            Self::Filter(_) => f.write_str("Filter"),
+        }
+    }
+}
+
+impl ParamSelector {
+    /// Returns true if the parameter is selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The parameter id.
+    /// * `path` - The dot-separated module path of the parameter.
+    /// * `container_path` - The dot-separated container types along the path.
+    pub fn matches(&self, id: &ParamId, path: &str, container_path: &str) -> bool {
+        match self {
+            Self::Ids(ids) => ids.contains(id),
+            Self::Paths(paths) => paths.iter().any(|prefix| {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            }),
+            Self::Filter(filter) => filter.matches(path, container_path),
+        }
+    }
+}
+
+/// A group of parameters optimized with their own learning rate multiplier, weight decay and
+/// frozen flag.
+///
+/// Groups are registered on an [optimizer adaptor](crate::optim::adaptor::OptimizerAdaptor)
+/// with [with_param_group](crate::optim::adaptor::OptimizerAdaptor::with_param_group), and are
+/// identified by their unique name. A parameter belongs to the first registered group that
+/// selects it; parameters selected by no group are optimized with the optimizer defaults.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let optim = AdamWConfig::new()
+///     .with_weight_decay(0.05)
+///     .init()
+///     // Fine-tune the pretrained backbone ten times slower than the head.
+///     .with_param_group(ParamGroup::from_paths("backbone", ["backbone"]).with_lr_multiplier(0.1))
+///     // No weight decay on biases.
+///     .with_param_group(ParamGroup::from_filter("biases", BiasFilter).with_weight_decay(0.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParamGroup {
+    name: String,
+    selector: ParamSelector,
+    lr_multiplier: f64,
+    weight_decay: Option<f32>,
+    frozen: bool,
+}
+
+impl ParamGroup {
+    /// Creates a new group with the given name and selector, using the optimizer defaults.
+    pub fn new(name: impl Into<String>, selector: ParamSelector) -> Self {
+        Self {
+            name: name.into(),
+            selector,
+            lr_multiplier: 1.0,
+            weight_decay: None,
+            frozen: false,
+        }
+    }
+
+    /// Creates a new group selecting the parameters with the given ids.
+    pub fn from_ids<I: IntoIterator<Item = ParamId>>(name: impl Into<String>, ids: I) -> Self {
+        Self::new(name, ParamSelector::Ids(ids.into_iter().collect()))
+    }
+
+    /// Creates a new group selecting the parameters under the given module paths.
+    pub fn from_paths<I, S>(name: impl Into<String>, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(
+            name,
+            ParamSelector::Paths(paths.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Creates a new group selecting the parameters accepted by the given path filter.
+    pub fn from_filter<F: ParamPathFilter + 'static>(name: impl Into<String>, filter: F) -> Self {
+        Self::new(name, ParamSelector::Filter(new_filter(filter)))
+    }
+
+    /// Sets the factor applied to the learning rate for the parameters of the group.
+    pub fn with_lr_multiplier(mut self, lr_multiplier: f64) -> Self {
+        self.lr_multiplier = lr_multiplier;
+        self
+    }
+
+    /// Overrides the weight decay penalty of the optimizer for the parameters of the group.
+    ///
+    /// A penalty of zero disables weight decay for the group.
+    pub fn with_weight_decay(mut self, penalty: f32) -> Self {
+        self.weight_decay = Some(penalty);
+        self
+    }
+
+    /// Sets whether the parameters of the group are frozen, i.e. left untouched by the optimizer.
+    pub fn with_frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self
+    }
+
+    /// The name of the group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The parameter selector of the group.
+    pub fn selector(&self) -> &ParamSelector {
+        &self.selector
+    }
+
+    /// The learning rate multiplier of the group.
+    pub fn lr_multiplier(&self) -> f64 {
+        self.lr_multiplier
+    }
+
+    /// The weight decay override of the group, if any.
+    pub fn weight_decay(&self) -> Option<f32> {
+        self.weight_decay
+    }
+
+    /// Whether the parameters of the group are frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SuffixFilter(&'static str);
+
+    impl ParamPathFilter for SuffixFilter {
+        fn matches(&self, path: &str, _container_path: &str) -> bool {
+            path.ends_with(self.0)
+        }
+    }
+
+    #[test]
+    fn path_selector_should_match_nested_paths() {
+        let selector = ParamGroup::from_paths("encoder", ["encoder"])
+            .selector()
+            .clone();
+        let id = ParamId::new();
+
+        assert!(selector.matches(&id, "encoder", ""));
+        assert!(selector.matches(&id, "encoder.layers.0.weight", ""));
+        assert!(!selector.matches(&id, "encoder_norm.gamma", ""));
+        assert!(!selector.matches(&id, "decoder.weight", ""));
+    }
+
+    #[test]
+    fn id_selector_should_match_ids() {
+        let id = ParamId::new();
+        let selector = ParamSelector::Ids(alloc::vec![id]);
+
+        assert!(selector.matches(&id, "weight", ""));
+        assert!(!selector.matches(&ParamId::new(), "weight", ""));
+    }
+
+    #[test]
+    fn filter_selector_should_use_filter() {
+        let group = ParamGroup::from_filter("biases", SuffixFilter(".bias"));
+        let id = ParamId::new();
+
+        assert!(group.selector().matches(&id, "linear.bias", ""));
+        assert!(!group.selector().matches(&id, "linear.weight", ""));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    self as burn, LearningRate, grad_clipping::GradientClippingConfig, module::AutodiffModule,
+    record::Record,
+};
+
+use super::SimpleOptimizer;
+use super::adam::{AdaptiveMomentum, AdaptiveMomentumState};
+use super::lars::trust_ratio;
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{Tensor, backend::AutodiffBackend};
+use burn_tensor::{backend::Backend, ops::Device};
+
+/// LAMB configuration.
+#[derive(Config, Debug)]
+pub struct LambConfig {
+    /// Parameter for LAMB.
+    #[config(default = 0.9)]
+    beta_1: f32,
+    /// Parameter for LAMB.
+    #[config(default = 0.999)]
+    beta_2: f32,
+    /// A value required for numerical stability.
+    #[config(default = 1e-6)]
+    epsilon: f32,
+    /// Decoupled weight decay coefficient, added to the update before computing the trust ratio.
+    #[config(default = 0.0)]
+    weight_decay: f32,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+}
+
+/// LAMB (Layer-wise Adaptive Moments for Batch training) optimizer as described in the paper
+/// [Large Batch Optimization for Deep Learning: Training BERT in 76 minutes](https://arxiv.org/abs/1904.00962).
+///
+/// The Adam update of each parameter tensor is scaled by the ratio between the norm of the
+/// parameter and the norm of the update.
+#[derive(Clone)]
+pub struct Lamb {
+    momentum: AdaptiveMomentum,
+    weight_decay: f32,
+}
+
+/// LAMB state.
+#[derive(Record, Clone, new)]
+pub struct LambState<B: Backend, const D: usize> {
+    /// The current adaptive momentum.
+    pub momentum: AdaptiveMomentumState<B, D>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Lamb {
+    type State<const D: usize> = LambState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        let (update, state_momentum, _) =
+            self.momentum
+                .transform(grad, state.map(|state| state.momentum), None);
+
+        let update = update.add(tensor.clone().mul_scalar(self.weight_decay));
+        let ratio = trust_ratio(&tensor, &update, 1.0, 0.0, 0.0);
+        let tensor = tensor - update.mul(ratio).mul_scalar(lr);
+
+        (tensor, Some(LambState::new(state_momentum)))
+    }
+
+    fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
+        state.momentum = state.momentum.to_device(device);
+        state
+    }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        Some(optim)
+    }
+}
+
+impl LambConfig {
+    /// Initialize LAMB optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(&self) -> OptimizerAdaptor<Lamb, M, B> {
+        let optim = Lamb {
+            momentum: AdaptiveMomentum {
+                beta_1: self.beta_1,
+                beta_2: self.beta_2,
+                epsilon: self.epsilon,
+                amsgrad: false,
+            },
+            weight_decay: self.weight_decay,
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        optim
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_lamb_optimizer_with_numbers() {
+        let device = Default::default();
+        let optim = Lamb {
+            momentum: AdaptiveMomentum {
+                beta_1: 0.9,
+                beta_2: 0.999,
+                epsilon: 1e-6,
+                amsgrad: false,
+            },
+            weight_decay: 0.01,
+        };
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(0.01, tensor, grad_1, None);
+        let (tensor, _state) = optim.step(0.01, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.497841, -0.983593, 1.970244],
+                [0.274701, -0.187691, 1.478398],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    self as burn, LearningRate, grad_clipping::GradientClippingConfig, module::AutodiffModule,
+    record::Record,
+};
+
+use super::SimpleOptimizer;
+use super::momentum::{Momentum, MomentumConfig, MomentumState};
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{Tensor, backend::AutodiffBackend};
+use burn_tensor::{backend::Backend, ops::Device};
+
+/// LARS configuration.
+#[derive(Config, Debug)]
+pub struct LarsConfig {
+    /// Trust coefficient scaling the layer-wise learning rate.
+    #[config(default = 0.001)]
+    trust_coefficient: f32,
+    /// Weight decay coefficient, added to the gradient before computing the trust ratio.
+    #[config(default = 0.0)]
+    weight_decay: f32,
+    /// A value required for numerical stability.
+    #[config(default = 1e-8)]
+    epsilon: f32,
+    /// [Momentum](MomentumConfig) config.
+    momentum: Option<MomentumConfig>,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+}
+
+/// LARS (Layer-wise Adaptive Rate Scaling) optimizer as described in the paper
+/// [Large Batch Training of Convolutional Networks](https://arxiv.org/abs/1708.03888).
+///
+/// Each parameter tensor gets its own learning rate, proportional to the ratio between the norm
+/// of the parameter and the norm of its gradient.
+#[derive(Clone)]
+pub struct Lars<B: Backend> {
+    momentum: Option<Momentum<B>>,
+    trust_coefficient: f32,
+    weight_decay: f32,
+    epsilon: f32,
+}
+
+/// LARS state.
+#[derive(Record, Clone, new)]
+pub struct LarsState<B: Backend, const D: usize> {
+    /// The current state of the momentum (if any).
+    pub momentum: Option<MomentumState<B, D>>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Lars<B> {
+    type State<const D: usize> = LarsState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        let ratio = trust_ratio(
+            &tensor,
+            &grad,
+            self.trust_coefficient,
+            self.weight_decay,
+            self.epsilon,
+        );
+        let mut grad = grad
+            .add(tensor.clone().mul_scalar(self.weight_decay))
+            .mul(ratio);
+
+        let mut state_momentum = state.and_then(|state| state.momentum);
+        if let Some(momentum) = &self.momentum {
+            let (grad_out, state) = momentum.transform(grad, state_momentum);
+            state_momentum = Some(state);
+            grad = grad_out;
+        }
+
+        (
+            tensor - grad.mul_scalar(lr),
+            Some(LarsState::new(state_momentum)),
+        )
+    }
+
+    fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
+        state.momentum = state.momentum.map(|state| state.to_device(device));
+        state
+    }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        Some(optim)
+    }
+}
+
+impl LarsConfig {
+    /// Initialize LARS optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+    ) -> OptimizerAdaptor<Lars<B::InnerBackend>, M, B> {
+        let optim = Lars {
+            momentum: self.momentum.as_ref().map(Momentum::new),
+            trust_coefficient: self.trust_coefficient,
+            weight_decay: self.weight_decay,
+            epsilon: self.epsilon,
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        optim
+    }
+}
+
+/// Computes the layer-wise trust ratio
+/// `coefficient * ||param|| / (||update|| + weight_decay * ||param|| + epsilon)`.
+///
+/// The ratio falls back to one when the norm of the parameter or of the update is zero. The
+/// result has a single element, broadcastable to the shape of the parameter.
+pub(crate) fn trust_ratio<B: Backend, const D: usize>(
+    param: &Tensor<B, D>,
+    update: &Tensor<B, D>,
+    coefficient: f32,
+    weight_decay: f32,
+    epsilon: f32,
+) -> Tensor<B, D> {
+    let param_norm = param.clone().powi_scalar(2).sum().sqrt();
+    let update_norm = update.clone().powi_scalar(2).sum().sqrt();
+
+    let ratio = param_norm.clone().mul_scalar(coefficient).div(
+        update_norm
+            .clone()
+            .add(param_norm.clone().mul_scalar(weight_decay))
+            .add_scalar(epsilon),
+    );
+    let undefined = param_norm
+        .equal_elem(0.0)
+        .bool_or(update_norm.equal_elem(0.0));
+
+    ratio.mask_fill(undefined, 1.0).unsqueeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_lars_optimizer_with_numbers() {
+        let device = Default::default();
+        let optim = Lars {
+            momentum: Some(Momentum::new(&MomentumConfig {
+                momentum: 0.9,
+                dampening: 0.0,
+                nesterov: false,
+            })),
+            trust_coefficient: 0.02,
+            weight_decay: 0.01,
+            epsilon: 1e-8,
+        };
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(1.0, tensor, grad_1, None);
+        let (tensor, _state) = optim.step(1.0, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.516160, -0.985908, 1.945376],
+                [0.252006, -0.183288, 1.442156],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+    }
+
+    #[test]
+    fn test_trust_ratio_should_be_one_for_zero_param() {
+        let device = Default::default();
+        let param = Tensor::<TestBackend, 2>::zeros([2, 3], &device);
+        let update = Tensor::<TestBackend, 2>::ones([2, 3], &device);
+
+        let ratio = trust_ratio(&param, &update, 0.001, 0.0, 1e-8);
+
+        ratio
+            .into_data()
+            .assert_eq(&TensorData::from([[1.0f32]]), false);
+    }
+}
@@ -4,23 +4,42 @@ pub mod decay;
 /// Momentum module for optimizers.
 pub mod momentum;
 
+/// Differential privacy module for optimizers.
+pub mod privacy;
+
+mod adafactor;
 mod adagrad;
 mod adam;
 mod adamw;
+mod adan;
 mod base;
 mod grad_accum;
 mod grads;
+mod groups;
+mod lamb;
+mod lars;
+mod lion;
+mod muon;
 mod rmsprop;
 mod sgd;
 mod simple;
+mod sophia;
 mod visitor;
 
+pub use adafactor::*;
 pub use adagrad::*;
 pub use adam::*;
 pub use adamw::*;
+pub use adan::*;
 pub use base::*;
 pub use grad_accum::*;
 pub use grads::*;
+pub use groups::*;
+pub use lamb::*;
+pub use lars::*;
+pub use lion::*;
+pub use muon::*;
 pub use rmsprop::*;
 pub use sgd::*;
 pub use simple::*;
+pub use sophia::*;
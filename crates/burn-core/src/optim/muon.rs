@@ -0,0 +1,325 @@
+use crate::{
+    self as burn, LearningRate, grad_clipping::GradientClippingConfig, module::AutodiffModule,
+    record::Record,
+};
+
+use super::SimpleOptimizer;
+use super::adam::{AdaptiveMomentum, AdaptiveMomentumState};
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{Tensor, backend::AutodiffBackend};
+use burn_tensor::{backend::Backend, ops::Device};
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+/// Muon configuration.
+#[derive(Config, Debug)]
+pub struct MuonConfig {
+    /// Momentum factor.
+    #[config(default = 0.95)]
+    momentum: f32,
+    /// Whether to use Nesterov momentum.
+    #[config(default = true)]
+    nesterov: bool,
+    /// Number of Newton-Schulz iterations used to orthogonalize the update.
+    #[config(default = 5)]
+    ns_steps: usize,
+    /// A value required for numerical stability of the Newton-Schulz normalization.
+    #[config(default = 1e-7)]
+    epsilon: f32,
+    /// Decoupled weight decay coefficient of matrix parameters.
+    #[config(default = 0.0)]
+    weight_decay: f32,
+    /// Multiplier of the learning rate for the Adam update of parameters with a single dimension.
+    ///
+    /// Adam usually needs a much smaller learning rate than Muon, e.g. `3e-4` against `0.02`.
+    #[config(default = 1.0)]
+    adam_lr_multiplier: f64,
+    /// Decoupled weight decay coefficient of parameters with a single dimension. Biases and norm
+    /// gains are usually not decayed.
+    #[config(default = 0.0)]
+    adam_weight_decay: f32,
+    /// Parameter for the Adam update of parameters with a single dimension.
+    #[config(default = 0.9)]
+    adam_beta_1: f32,
+    /// Parameter for the Adam update of parameters with a single dimension.
+    #[config(default = 0.95)]
+    adam_beta_2: f32,
+    /// A value required for numerical stability of the Adam update.
+    #[config(default = 1e-8)]
+    adam_epsilon: f32,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+}
+
+/// Muon (MomentUm Orthogonalized by Newton-Schulz) optimizer as described in
+/// [Muon: An optimizer for hidden layers in neural networks](https://kellerjordan.github.io/posts/muon/).
+///
+/// The momentum of matrix parameters is replaced by the closest semi-orthogonal matrix, computed
+/// with a quintic Newton-Schulz iteration. Parameters with more than two dimensions, such as
+/// convolution kernels, are flattened to `[dim_0, rest]`. Parameters with a single dimension,
+/// such as biases and norm gains, are updated with [Adam](crate::optim::Adam) instead, with their
+/// own learning rate multiplier and weight decay.
+#[derive(Clone)]
+pub struct Muon {
+    momentum: f32,
+    nesterov: bool,
+    ns_steps: usize,
+    epsilon: f32,
+    weight_decay: f32,
+    adam: AdaptiveMomentum,
+    adam_lr_multiplier: f64,
+    adam_weight_decay: f32,
+}
+
+/// Muon state.
+#[derive(Record, Clone, new)]
+pub struct MuonState<B: Backend, const D: usize> {
+    /// The momentum buffer of matrix parameters.
+    pub momentum: Option<Tensor<B, D>>,
+    /// The adaptive momentum of parameters with a single dimension.
+    pub adaptive: Option<AdaptiveMomentumState<B, D>>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Muon {
+    type State<const D: usize> = MuonState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        let (momentum, adaptive) = match state {
+            Some(state) => (state.momentum, state.adaptive),
+            None => (None, None),
+        };
+
+        if D < 2 {
+            let lr = lr * self.adam_lr_multiplier;
+            let tensor = decay(tensor, lr, self.adam_weight_decay);
+            let (update, adaptive, _) = self.adam.transform(grad, adaptive, None);
+            let state = MuonState::new(None, Some(adaptive));
+            return (tensor - update.mul_scalar(lr), Some(state));
+        }
+
+        let tensor = decay(tensor, lr, self.weight_decay);
+
+        let momentum = match momentum {
+            Some(momentum) => momentum.mul_scalar(self.momentum).add(grad.clone()),
+            None => grad.clone(),
+        };
+        let update = match self.nesterov {
+            true => grad.add(momentum.clone().mul_scalar(self.momentum)),
+            false => momentum.clone(),
+        };
+
+        let shape = update.shape();
+        let rows = shape.dims[0];
+        let cols = shape.num_elements() / rows;
+        let update =
+            newton_schulz(update.reshape([rows, cols]), self.ns_steps, self.epsilon).reshape(shape);
+        let scale = (rows as f64 / cols as f64).max(1.0).sqrt();
+
+        let state = MuonState::new(Some(momentum), None);
+        (tensor - update.mul_scalar(lr * scale), Some(state))
+    }
+
+    fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
+        state.momentum = state.momentum.map(|tensor| tensor.to_device(device));
+        state.adaptive = state.adaptive.map(|state| state.to_device(device));
+        state
+    }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        // The group explicitly requests a weight decay, so it applies to both kinds of parameters.
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        optim.adam_weight_decay = penalty;
+        Some(optim)
+    }
+}
+
+impl MuonConfig {
+    /// Initialize Muon optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(&self) -> OptimizerAdaptor<Muon, M, B> {
+        let optim = Muon {
+            momentum: self.momentum,
+            nesterov: self.nesterov,
+            ns_steps: self.ns_steps,
+            epsilon: self.epsilon,
+            weight_decay: self.weight_decay,
+            adam: AdaptiveMomentum {
+                beta_1: self.adam_beta_1,
+                beta_2: self.adam_beta_2,
+                epsilon: self.adam_epsilon,
+                amsgrad: false,
+            },
+            adam_lr_multiplier: self.adam_lr_multiplier,
+            adam_weight_decay: self.adam_weight_decay,
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        optim
+    }
+}
+
+/// Applies the decoupled weight decay.
+fn decay<B: Backend, const D: usize>(
+    tensor: Tensor<B, D>,
+    lr: LearningRate,
+    weight_decay: f32,
+) -> Tensor<B, D> {
+    if weight_decay == 0.0 {
+        return tensor;
+    }
+
+    tensor.clone() - tensor.mul_scalar(lr).mul_scalar(weight_decay)
+}
+
+/// Approximates the closest semi-orthogonal matrix with a quintic Newton-Schulz iteration, whose
+/// coefficients are tuned to quickly push all singular values close to one.
+fn newton_schulz<B: Backend>(matrix: Tensor<B, 2>, steps: usize, epsilon: f32) -> Tensor<B, 2> {
+    let (a, b, c) = (3.4445, -4.7750, 2.0315);
+    let [rows, cols] = matrix.dims();
+    let transposed = rows > cols;
+
+    let norm = matrix
+        .clone()
+        .powi_scalar(2)
+        .sum()
+        .sqrt()
+        .add_scalar(epsilon)
+        .unsqueeze();
+    let mut x = matrix.div(norm);
+    if transposed {
+        x = x.transpose();
+    }
+
+    for _ in 0..steps {
+        let gram = x.clone().matmul(x.clone().transpose());
+        let poly = gram
+            .clone()
+            .mul_scalar(b)
+            .add(gram.clone().matmul(gram).mul_scalar(c));
+        x = x.clone().mul_scalar(a).add(poly.matmul(x));
+    }
+
+    match transposed {
+        true => x.transpose(),
+        false => x,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn muon() -> Muon {
+        Muon {
+            momentum: 0.95,
+            nesterov: true,
+            ns_steps: 5,
+            epsilon: 1e-7,
+            weight_decay: 0.01,
+            adam: AdaptiveMomentum {
+                beta_1: 0.9,
+                beta_2: 0.95,
+                epsilon: 1e-8,
+                amsgrad: false,
+            },
+            adam_lr_multiplier: 1.0,
+            adam_weight_decay: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_muon_optimizer_with_numbers() {
+        let device = Default::default();
+        let optim = muon();
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(0.02, tensor, grad_1, None);
+        let (tensor, state) = optim.step(0.02, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.524115, -0.998825, 1.968231],
+                [0.276597, -0.200426, 1.485362],
+            ]),
+            Tolerance::absolute(1e-4),
+        );
+        assert!(state.unwrap().adaptive.is_none());
+    }
+
+    #[test]
+    fn test_muon_optimizer_uses_adam_for_vectors() {
+        let device = Default::default();
+        let optim = muon();
+        let tensor = Tensor::<TestBackend, 1>::from_floats([0.5, -1.0, 2.0], &device);
+        let grad = Tensor::from_floats([0.1, -0.2, 0.3], &device);
+
+        let (tensor, state) = optim.step(0.02, tensor, grad, None);
+
+        // The first Adam step moves every element by the learning rate, against its gradient,
+        // and vectors aren't decayed by default.
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([0.48, -0.98, 1.98]),
+            Tolerance::absolute(1e-5),
+        );
+        assert!(state.unwrap().momentum.is_none());
+    }
+
+    #[test]
+    fn test_muon_optimizer_scales_adam_lr_and_decays_vectors_on_request() {
+        let device = Default::default();
+        let optim = Muon {
+            adam_lr_multiplier: 0.5,
+            adam_weight_decay: 0.1,
+            ..muon()
+        };
+        let tensor = Tensor::<TestBackend, 1>::from_floats([0.5, -1.0, 2.0], &device);
+        let grad = Tensor::from_floats([0.1, -0.2, 0.3], &device);
+
+        let (tensor, _) = optim.step(0.02, tensor, grad, None);
+
+        // The effective learning rate is 0.01: x * (1 - 0.01 * 0.1) - 0.01 * sign(grad).
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([0.4895, -0.989, 1.988]),
+            Tolerance::absolute(1e-5),
+        );
+    }
+
+    #[test]
+    fn test_newton_schulz_should_orthogonalize() {
+        let device = Default::default();
+        let matrix = Tensor::<TestBackend, 2>::from_floats([[3.0, 0.0], [0.0, 0.5]], &device);
+
+        let ortho = newton_schulz(matrix, 5, 1e-7);
+
+        // The singular values are pushed towards one, without changing the singular vectors.
+        let data = ortho.into_data();
+        let values = data.as_slice::<FT>().unwrap();
+        assert!(values[1] == 0.0 && values[2] == 0.0);
+        assert!((values[0] - 1.0).abs() < 0.3);
+        assert!((values[3] - 1.0).abs() < 0.3);
+    }
+}
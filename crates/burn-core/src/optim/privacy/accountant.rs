@@ -0,0 +1,217 @@
+use alloc::sync::Arc;
+use num_traits::Float as _;
+
+/// The Rényi divergence orders over which the privacy loss is optimized. Only integer orders are
+/// used, for which the divergence of the subsampled Gaussian mechanism has a closed form.
+fn orders() -> impl Iterator<Item = u32> {
+    (2..=64).chain([80, 96, 128, 256])
+}
+
+/// Rényi differential privacy (RDP) accountant of the subsampled Gaussian mechanism, also known
+/// as the moments accountant.
+///
+/// Tracks the privacy loss of [DP-SGD](super::DpSgd) steps, each one adding Gaussian noise to the
+/// sum of the clipped gradients of examples sampled with the given rate, as analyzed in
+/// [Rényi Differential Privacy of the Sampled Gaussian Mechanism](https://arxiv.org/abs/1908.10530).
+/// The RDP guarantees are converted to an `(ε, δ)` guarantee with the bound of
+/// [Hypothesis Testing Interpretations and Rényi Differential Privacy](https://arxiv.org/abs/1905.09982).
+///
+/// # Notes
+///
+/// The analysis assumes Poisson sampling: each example is independently part of each batch with
+/// the sample rate as probability, so the batches have a random size. Batches of a fixed size,
+/// e.g. from shuffling the dataset and splitting it, don't satisfy this assumption and the
+/// reported budget is then only an approximation of the actual one.
+#[derive(Clone, Debug)]
+pub struct RdpAccountant {
+    noise_multiplier: f64,
+    sample_rate: f64,
+    steps: usize,
+}
+
+impl RdpAccountant {
+    /// Creates a new accountant.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise_multiplier` - The ratio of the standard deviation of the noise to the maximum
+    ///   norm of the gradient of an example.
+    /// * `sample_rate` - The probability of each example to be part of a batch.
+    ///
+    /// # Panics
+    ///
+    /// If the noise multiplier is negative or the sample rate isn't between 0 and 1.
+    pub fn new(noise_multiplier: f64, sample_rate: f64) -> Self {
+        assert!(
+            noise_multiplier >= 0.0,
+            "The noise multiplier should be positive"
+        );
+        assert!(
+            (0.0..=1.0).contains(&sample_rate),
+            "The sample rate should be between 0 and 1"
+        );
+
+        Self {
+            noise_multiplier,
+            sample_rate,
+            steps: 0,
+        }
+    }
+
+    /// Records a step of the mechanism.
+    pub fn step(&mut self) {
+        self.steps += 1;
+    }
+
+    /// The number of recorded steps.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Sets the number of recorded steps, e.g. when resuming a training.
+    pub fn set_steps(&mut self, steps: usize) {
+        self.steps = steps;
+    }
+
+    /// The privacy budget `ε` spent by the recorded steps for the given `δ`.
+    ///
+    /// Returns infinity without noise.
+    pub fn epsilon(&self, delta: f64) -> f64 {
+        if self.steps == 0 || self.sample_rate == 0.0 {
+            return 0.0;
+        }
+        if self.noise_multiplier == 0.0 {
+            return f64::INFINITY;
+        }
+
+        let log_delta = delta.ln();
+
+        orders()
+            .map(|order| {
+                let alpha = order as f64;
+                let rdp = self.steps as f64 * self.rdp(order);
+
+                rdp - (log_delta + alpha.ln()) / (alpha - 1.0) + ((alpha - 1.0) / alpha).ln()
+            })
+            .fold(f64::INFINITY, f64::min)
+            .max(0.0)
+    }
+
+    /// The RDP of a single step at the given integer order.
+    fn rdp(&self, order: u32) -> f64 {
+        let alpha = order as f64;
+        let variance = self.noise_multiplier * self.noise_multiplier;
+
+        if self.sample_rate == 1.0 {
+            return alpha / (2.0 * variance);
+        }
+
+        // log(A_α) with A_α = Σ_k C(α, k) (1 - q)^(α - k) q^k exp((k² - k) / 2σ²)
+        let log_q = self.sample_rate.ln();
+        let log_1mq = (-self.sample_rate).ln_1p();
+        let mut log_binom = 0.0;
+        let mut log_a = f64::NEG_INFINITY;
+
+        for k in 0..=order {
+            if k > 0 {
+                log_binom += ((order - k + 1) as f64).ln() - (k as f64).ln();
+            }
+            let k = k as f64;
+            let term =
+                log_binom + k * log_q + (alpha - k) * log_1mq + (k * k - k) / (2.0 * variance);
+            log_a = log_add(log_a, term);
+        }
+
+        log_a / (alpha - 1.0)
+    }
+}
+
+/// A handle to an [accountant](RdpAccountant) shared by an optimizer and the ones reporting its
+/// privacy budget.
+#[derive(Clone, Debug)]
+pub struct PrivacyAccountant {
+    accountant: Arc<spin::Mutex<RdpAccountant>>,
+}
+
+impl PrivacyAccountant {
+    /// Creates a new handle to the given accountant.
+    pub fn new(accountant: RdpAccountant) -> Self {
+        Self {
+            accountant: Arc::new(spin::Mutex::new(accountant)),
+        }
+    }
+
+    /// Records a step of the mechanism.
+    pub fn step(&self) {
+        self.accountant.lock().step();
+    }
+
+    /// The number of recorded steps.
+    pub fn steps(&self) -> usize {
+        self.accountant.lock().steps()
+    }
+
+    /// Sets the number of recorded steps.
+    pub fn set_steps(&self, steps: usize) {
+        self.accountant.lock().set_steps(steps);
+    }
+
+    /// The privacy budget `ε` spent by the recorded steps for the given `δ`.
+    pub fn epsilon(&self, delta: f64) -> f64 {
+        self.accountant.lock().epsilon(delta)
+    }
+}
+
+/// Computes `log(exp(a) + exp(b))` without overflow.
+fn log_add(a: f64, b: f64) -> f64 {
+    let (max, min) = if a > b { (a, b) } else { (b, a) };
+    if min == f64::NEG_INFINITY {
+        return max;
+    }
+
+    max + (min - max).exp().ln_1p()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epsilon_without_sampling() {
+        let mut accountant = RdpAccountant::new(1.0, 1.0);
+        accountant.step();
+
+        // min over α of α / 2 - (log δ + log α) / (α - 1) + log((α - 1) / α)
+        assert!((accountant.epsilon(1e-5) - 4.7527).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_epsilon_with_sampling() {
+        let mut accountant = RdpAccountant::new(1.0, 0.01);
+        accountant.set_steps(1000);
+
+        assert!((accountant.epsilon(1e-5) - 2.1078).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_epsilon_grows_with_steps() {
+        let mut accountant = RdpAccountant::new(1.1, 256.0 / 60000.0);
+        assert_eq!(accountant.epsilon(1e-5), 0.0);
+
+        accountant.step();
+        let first = accountant.epsilon(1e-5);
+        accountant.set_steps(100);
+        let second = accountant.epsilon(1e-5);
+
+        assert!(first > 0.0);
+        assert!(second > first);
+    }
+
+    #[test]
+    fn test_epsilon_without_noise() {
+        let mut accountant = RdpAccountant::new(0.0, 0.5);
+        accountant.step();
+
+        assert_eq!(accountant.epsilon(1e-5), f64::INFINITY);
+    }
+}
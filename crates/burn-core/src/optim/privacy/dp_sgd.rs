@@ -0,0 +1,388 @@
+use crate as burn;
+
+use super::{PrivacyAccountant, RdpAccountant};
+use crate::LearningRate;
+use crate::config::Config;
+use crate::grad_clipping::GradientClipping;
+use crate::module::{AutodiffModule, ModuleVisitor, ParamId};
+use crate::optim::{GradientsAccumulator, GradientsParams, Optimizer};
+use crate::tensor::{Distribution, Tensor, backend::AutodiffBackend};
+use core::marker::PhantomData;
+
+/// Configuration to create a [DP-SGD](DpSgd) optimizer.
+#[derive(Config, Debug)]
+pub struct DpSgdConfig {
+    /// The maximum L2 norm of the gradients of each example.
+    pub max_grad_norm: f32,
+    /// The ratio of the standard deviation of the Gaussian noise to the maximum gradient norm.
+    pub noise_multiplier: f64,
+    /// The expected number of examples of a batch, which averages the noisy sum of gradients.
+    pub batch_size: usize,
+    /// The number of examples of the dataset, giving the sample rate of the batches.
+    ///
+    /// The [privacy accountant](RdpAccountant) assumes each example is independently part of a
+    /// batch with probability `batch_size / num_examples` (Poisson sampling).
+    pub num_examples: usize,
+}
+
+/// Differentially private stochastic gradient descent (DP-SGD), from
+/// [Deep Learning with Differential Privacy](https://arxiv.org/abs/1607.00133).
+///
+/// Wraps an optimizer, e.g. an [adaptor](crate::optim::adaptor::OptimizerAdaptor), and only
+/// [steps](DpSgd::step) with the [clipped gradients](ClippedGradients) of individual examples
+/// collected by a [clipped gradients accumulator](ClippedGradientsAccumulator). Every step adds
+/// Gaussian noise to the sum of each trainable parameter, divides it by the batch size and records
+/// the step in the [privacy accountant](PrivacyAccountant).
+///
+/// It doesn't implement [Optimizer], whose steps accept any gradients: unclipped gradients would
+/// void the privacy guarantee.
+///
+/// # Notes
+///
+/// The noise is sampled from the random number generator of the backend, which isn't
+/// cryptographically secure. The privacy budget assumes the batches are drawn with Poisson
+/// sampling, see [RdpAccountant].
+#[derive(Clone)]
+pub struct DpSgd<O> {
+    optim: O,
+    max_grad_norm: f32,
+    noise_std: f64,
+    batch_size: usize,
+    accountant: PrivacyAccountant,
+}
+
+impl DpSgdConfig {
+    /// Initialize the DP-SGD optimizer wrapping the given optimizer.
+    ///
+    /// # Panics
+    ///
+    /// If the batch size is 0 or greater than the number of examples.
+    pub fn init<O>(&self, optim: O) -> DpSgd<O> {
+        assert!(
+            self.batch_size > 0 && self.batch_size <= self.num_examples,
+            "The batch size should be positive and at most the number of examples"
+        );
+
+        let sample_rate = self.batch_size as f64 / self.num_examples as f64;
+
+        DpSgd {
+            optim,
+            max_grad_norm: self.max_grad_norm,
+            noise_std: self.noise_multiplier * self.max_grad_norm as f64,
+            batch_size: self.batch_size,
+            accountant: PrivacyAccountant::new(RdpAccountant::new(
+                self.noise_multiplier,
+                sample_rate,
+            )),
+        }
+    }
+}
+
+impl<O> DpSgd<O> {
+    /// Creates an accumulator clipping the gradients of each example to the maximum norm of the
+    /// optimizer.
+    pub fn accumulator<M>(&self) -> ClippedGradientsAccumulator<M> {
+        ClippedGradientsAccumulator::new(self.max_grad_norm)
+    }
+
+    /// The privacy accountant of the optimizer.
+    ///
+    /// The returned handle is shared with the optimizer and its clones, it can be given to the
+    /// privacy budget metric of `burn-train`.
+    pub fn accountant(&self) -> PrivacyAccountant {
+        self.accountant.clone()
+    }
+
+    /// The privacy budget `ε` spent by the steps of the optimizer for the given `δ`.
+    pub fn epsilon(&self, delta: f64) -> f64 {
+        self.accountant.epsilon(delta)
+    }
+
+    /// Perform a step with the clipped gradients of the examples of a batch, adding noise to them
+    /// before updating the module with the wrapped optimizer.
+    pub fn step<M, B>(&mut self, lr: LearningRate, module: M, grads: ClippedGradients) -> M
+    where
+        O: Optimizer<M, B>,
+        M: AutodiffModule<B>,
+        B: AutodiffBackend,
+    {
+        let mut grads = grads.grads;
+        let mut visitor = GradientsNoiser::<B> {
+            grads: &mut grads,
+            noise_std: self.noise_std,
+            batch_size: self.batch_size,
+            phantom: PhantomData,
+        };
+        module.visit(&mut visitor);
+        self.accountant.step();
+
+        self.optim.step(lr, module, grads)
+    }
+
+    /// Get the current state of the optimizer as a [record](crate::record::Record), along with
+    /// the number of steps recorded by the accountant.
+    pub fn to_record<M, B>(&self) -> (O::Record, usize)
+    where
+        O: Optimizer<M, B>,
+        M: AutodiffModule<B>,
+        B: AutodiffBackend,
+    {
+        (self.optim.to_record(), self.accountant.steps())
+    }
+
+    /// Load the state of the optimizer and the number of steps of the accountant from a
+    /// [record](DpSgd::to_record).
+    pub fn load_record<M, B>(mut self, record: (O::Record, usize)) -> Self
+    where
+        O: Optimizer<M, B>,
+        M: AutodiffModule<B>,
+        B: AutodiffBackend,
+    {
+        let (record, steps) = record;
+        self.optim = self.optim.load_record(record);
+        self.accountant.set_steps(steps);
+        self
+    }
+}
+
+/// The sum of the gradients of individual examples, each one clipped to the maximum norm of a
+/// [DP-SGD](DpSgd) optimizer.
+///
+/// Can only be obtained from a [clipped gradients accumulator](ClippedGradientsAccumulator).
+pub struct ClippedGradients {
+    grads: GradientsParams,
+    num_examples: usize,
+}
+
+impl ClippedGradients {
+    /// The number of examples whose gradients are summed.
+    pub fn num_examples(&self) -> usize {
+        self.num_examples
+    }
+}
+
+/// Accumulates the gradients of individual examples, each one clipped to a maximum L2 norm
+/// across all the parameters of the module, for the [DP-SGD](DpSgd) optimizer.
+pub struct ClippedGradientsAccumulator<M> {
+    clipping: GradientClipping,
+    accumulator: GradientsAccumulator<M>,
+    num_examples: usize,
+}
+
+impl<M> ClippedGradientsAccumulator<M> {
+    /// Create a new accumulator clipping the gradients of each example to the given norm.
+    pub fn new(max_grad_norm: f32) -> Self {
+        Self {
+            clipping: GradientClipping::GlobalNorm(max_grad_norm),
+            accumulator: GradientsAccumulator::new(),
+            num_examples: 0,
+        }
+    }
+
+    /// Clip the gradients of a single example and accumulate them.
+    pub fn accumulate<B: AutodiffBackend>(&mut self, module: &M, grads: GradientsParams)
+    where
+        M: AutodiffModule<B>,
+    {
+        let grads = self.clipping.clip_gradients::<B, M>(module, grads);
+        self.accumulator.accumulate(module, grads);
+        self.num_examples += 1;
+    }
+
+    /// Compute, clip and accumulate the gradients of each example, given a function returning the
+    /// loss of the example at an index of the batch.
+    ///
+    /// Each example needs its own forward and backward passes.
+    pub fn accumulate_examples<B, F>(&mut self, module: &M, num_examples: usize, mut loss: F)
+    where
+        B: AutodiffBackend,
+        M: AutodiffModule<B>,
+        F: FnMut(usize) -> Tensor<B, 1>,
+    {
+        for index in 0..num_examples {
+            let grads = GradientsParams::from_grads(loss(index).backward(), module);
+            self.accumulate(module, grads);
+        }
+    }
+
+    /// The number of accumulated examples.
+    pub fn num_examples(&self) -> usize {
+        self.num_examples
+    }
+
+    /// Return the sum of the clipped gradients and reset the accumulator state.
+    pub fn grads(&mut self) -> ClippedGradients {
+        ClippedGradients {
+            grads: self.accumulator.grads(),
+            num_examples: core::mem::take(&mut self.num_examples),
+        }
+    }
+}
+
+struct GradientsNoiser<'a, B: AutodiffBackend> {
+    grads: &'a mut GradientsParams,
+    noise_std: f64,
+    batch_size: usize,
+    phantom: PhantomData<B>,
+}
+
+impl<B: AutodiffBackend> ModuleVisitor<B> for GradientsNoiser<'_, B> {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        if !tensor.is_require_grad() {
+            return;
+        }
+
+        // Parameters without gradients are noised as well, whether a parameter received
+        // gradients shouldn't reveal anything about the batch.
+        let grad = self
+            .grads
+            .remove::<B::InnerBackend, D>(id)
+            .unwrap_or_else(|| tensor.clone().inner().zeros_like());
+        let grad = if self.noise_std > 0.0 {
+            let distribution = Distribution::Normal(0.0, self.noise_std);
+            let noise = Tensor::random(grad.shape(), distribution, &grad.device());
+            grad.add(noise)
+        } else {
+            grad
+        };
+
+        self.grads
+            .register::<B::InnerBackend, D>(id, grad.div_scalar(self.batch_size as f64));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::{Linear, LinearConfig};
+    use crate::optim::SgdConfig;
+    use crate::tensor::TensorData;
+    use crate::{TestAutodiffBackend, TestBackend};
+    use burn_tensor::{ElementConversion, Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    fn clipped(grads: GradientsParams, num_examples: usize) -> ClippedGradients {
+        ClippedGradients {
+            grads,
+            num_examples,
+        }
+    }
+
+    fn linear() -> Linear<TestAutodiffBackend> {
+        let device = Default::default();
+        let mut linear = LinearConfig::new(2, 1).init::<TestAutodiffBackend>(&device);
+        linear.weight = linear
+            .weight
+            .map(|_| Tensor::from_floats([[1.0], [1.0]], &device).require_grad());
+        linear.bias = linear
+            .bias
+            .map(|bias| bias.map(|_| Tensor::from_floats([0.0], &device).require_grad()));
+        linear
+    }
+
+    #[test]
+    fn test_clip_each_example() {
+        let device = Default::default();
+        let linear = linear();
+        let inputs =
+            Tensor::<TestAutodiffBackend, 2>::from_floats([[3.0, 4.0], [0.3, 0.4]], &device);
+        let mut accumulator = ClippedGradientsAccumulator::new(1.0);
+
+        // The gradients of the weight are the inputs, the ones of the bias are 1.
+        accumulator.accumulate_examples(&linear, 2, |index| {
+            linear.forward(inputs.clone().narrow(0, index, 1)).sum()
+        });
+        assert_eq!(accumulator.num_examples(), 2);
+
+        let clipped = accumulator.grads();
+        assert_eq!(clipped.num_examples(), 2);
+        let mut grads = clipped.grads;
+        let norm = (3.0f32 * 3.0 + 4.0 * 4.0 + 1.0).sqrt();
+        let small_norm = (0.3f32 * 0.3 + 0.4 * 0.4 + 1.0).sqrt();
+        let scale = 1.0 / norm;
+        let small_scale = 1.0 / small_norm;
+
+        grads
+            .remove::<TestBackend, 2>(linear.weight.id)
+            .unwrap()
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &TensorData::from([
+                    [3.0 * scale + 0.3 * small_scale],
+                    [4.0 * scale + 0.4 * small_scale],
+                ]),
+                Tolerance::default(),
+            );
+        assert_eq!(accumulator.num_examples(), 0);
+    }
+
+    #[test]
+    fn test_step_without_noise() {
+        let device = Default::default();
+        let linear = linear();
+        let mut optim = DpSgdConfig::new(1.0, 0.0, 2, 10).init(SgdConfig::new().init());
+
+        let mut grads = GradientsParams::new();
+        grads.register::<TestBackend, 2>(
+            linear.weight.id,
+            Tensor::from_floats([[0.4], [0.2]], &device),
+        );
+        grads.register::<TestBackend, 1>(
+            linear.bias.as_ref().unwrap().id,
+            Tensor::from_floats([2.0], &device),
+        );
+
+        let linear = optim.step(1.0, linear, clipped(grads, 2));
+
+        // The sum of the gradients is averaged over the batch size.
+        linear
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([[0.8], [0.9]]), Tolerance::default());
+        linear
+            .bias
+            .unwrap()
+            .val()
+            .into_data()
+            .assert_approx_eq::<FT>(&TensorData::from([-1.0]), Tolerance::default());
+        assert_eq!(optim.accountant().steps(), 1);
+        assert_eq!(optim.epsilon(1e-5), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_noise_all_trainable_params() {
+        let linear = linear();
+        let mut optim = DpSgdConfig::new(1.0, 1.0, 1, 10).init(SgdConfig::new().init());
+
+        let linear = optim.step(1.0, linear, clipped(GradientsParams::new(), 1));
+
+        let change = linear
+            .weight
+            .val()
+            .sub_scalar(1.0)
+            .abs()
+            .sum()
+            .into_scalar();
+        assert!(change.elem::<f32>() > 0.0);
+        assert!(optim.epsilon(1e-5) > 0.0);
+    }
+
+    #[test]
+    fn test_record_steps() {
+        let linear = linear();
+        let config = DpSgdConfig::new(1.0, 1.0, 1, 10);
+        let mut optim = config.init(SgdConfig::new().init());
+
+        let linear = optim.step(1.0, linear, clipped(GradientsParams::new(), 1));
+        let _linear = optim.step(1.0, linear, clipped(GradientsParams::new(), 1));
+        let record = optim.to_record::<Linear<TestAutodiffBackend>, _>();
+
+        let optim = config
+            .init(SgdConfig::new().init())
+            .load_record::<Linear<TestAutodiffBackend>, _>(record);
+
+        assert_eq!(optim.accountant().steps(), 2);
+    }
+}
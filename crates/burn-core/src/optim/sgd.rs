@@ -92,6 +92,13 @@ impl<B: Backend> SimpleOptimizer<B> for Sgd<B> {
         state.momentum = state.momentum.map(|state| state.to_device(device));
         state
     }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay =
+            (penalty != 0.0).then(|| WeightDecay::new(&WeightDecayConfig::new(penalty)));
+        Some(optim)
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,11 @@
 use super::{SimpleOptimizer, record::AdaptorRecord};
 use crate::{
-    LearningRate,
+    self as burn, LearningRate,
     grad_clipping::GradientClipping,
     module::{AutodiffModule, ModuleMapper, ParamId},
-    optim::{GradientsParams, Optimizer},
+    optim::{GradientsParams, Optimizer, ParamGroup},
 };
+use alloc::{string::String, vec::Vec};
 use burn_tensor::{Tensor, backend::AutodiffBackend};
 use core::marker::PhantomData;
 use hashbrown::HashMap;
@@ -22,6 +23,15 @@ where
     records: HashMap<ParamId, AdaptorRecord<O, B>>,
     module: PhantomData<M>,
     grad_clipping: Option<GradientClipping>,
+    groups: Vec<AdaptorParamGroup<O>>,
+    assignments: HashMap<ParamId, usize>,
+}
+
+/// A parameter group along with the optimizer used for its parameters.
+#[derive(Clone)]
+struct AdaptorParamGroup<O> {
+    group: ParamGroup,
+    optim: O,
 }
 
 impl<O, B, M> From<O> for OptimizerAdaptor<O, M, B>
@@ -36,6 +46,8 @@ where
             records: HashMap::new(),
             module: PhantomData,
             grad_clipping: None,
+            groups: Vec::new(),
+            assignments: HashMap::new(),
         }
     }
 }
@@ -60,6 +72,52 @@ where
         self
     }
 
+    /// Adds a parameter group.
+    ///
+    /// A parameter belongs to the first registered group selecting it, and keeps that group
+    /// for the lifetime of the optimizer. Assignments aren't part of the optimizer record: they
+    /// are computed again from the groups after loading a record, so groups must be registered
+    /// before resuming a training.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The parameter group.
+    ///
+    /// # Returns
+    ///
+    /// The optimizer.
+    ///
+    /// # Panics
+    ///
+    /// If a group with the same name was already registered, or if the group overrides the
+    /// weight decay but the optimizer doesn't support weight decay.
+    pub fn with_param_group(mut self, group: ParamGroup) -> Self {
+        assert!(
+            self.groups
+                .iter()
+                .all(|registered| registered.group.name() != group.name()),
+            "A parameter group named `{}` is already registered",
+            group.name()
+        );
+        let optim = match group.weight_decay() {
+            Some(penalty) => self.optim.with_weight_decay(penalty).expect(
+                "The optimizer should support weight decay to override it in a parameter group",
+            ),
+            None => self.optim.clone(),
+        };
+        self.groups.push(AdaptorParamGroup { group, optim });
+        self
+    }
+
+    /// Returns the name of the parameter group the given parameter was assigned to, if any.
+    ///
+    /// Parameters are assigned to their group during the first optimizer step.
+    pub fn param_group_of(&self, id: &ParamId) -> Option<&str> {
+        self.assignments
+            .get(id)
+            .map(|index| self.groups[*index].group.name())
+    }
+
     #[cfg(test)]
     pub(crate) fn has_gradient_clipping(&self) -> bool {
         self.grad_clipping.is_some()
@@ -74,13 +132,18 @@ where
 {
     type Record = HashMap<ParamId, AdaptorRecord<O, B>>;
 
-    fn step(&mut self, lr: LearningRate, module: M, mut grads: GradientsParams) -> M {
+    fn step(&mut self, lr: LearningRate, module: M, grads: GradientsParams) -> M {
+        let mut grads = match &self.grad_clipping {
+            Some(clipping) => clipping.clip_gradients::<B, M>(&module, grads),
+            None => grads,
+        };
         let mut mapper = SimpleOptimizerMapper::<M, B, O>::new(
             &self.optim,
             &mut self.records,
             &mut grads,
             lr,
-            self.grad_clipping.as_ref(),
+            &self.groups,
+            &mut self.assignments,
         );
         module.map(&mut mapper)
     }
@@ -95,7 +158,6 @@ where
     }
 }
 
-#[derive(new)]
 struct SimpleOptimizerMapper<'a, M, B, O>
 where
     M: AutodiffModule<B>,
@@ -107,7 +169,57 @@ where
     grads: &'a mut GradientsParams,
     lr: LearningRate,
     phantom: PhantomData<M>,
-    grad_clipping: Option<&'a GradientClipping>,
+    groups: &'a [AdaptorParamGroup<O>],
+    assignments: &'a mut HashMap<ParamId, usize>,
+    path: Vec<String>,
+    container_path: Vec<String>,
+}
+
+impl<'a, M, B, O> SimpleOptimizerMapper<'a, M, B, O>
+where
+    M: AutodiffModule<B>,
+    B: AutodiffBackend,
+    O: SimpleOptimizer<B::InnerBackend>,
+{
+    fn new(
+        optimizer: &'a O,
+        records: &'a mut HashMap<ParamId, AdaptorRecord<O, B>>,
+        grads: &'a mut GradientsParams,
+        lr: LearningRate,
+        groups: &'a [AdaptorParamGroup<O>],
+        assignments: &'a mut HashMap<ParamId, usize>,
+    ) -> Self {
+        Self {
+            optimizer,
+            records,
+            grads,
+            lr,
+            phantom: PhantomData,
+            groups,
+            assignments,
+            path: Vec::new(),
+            container_path: Vec::new(),
+        }
+    }
+
+    /// Returns the group of the parameter, assigning it to the first matching group if it
+    /// doesn't have one yet.
+    fn group(&mut self, id: ParamId) -> Option<&'a AdaptorParamGroup<O>> {
+        let groups = self.groups;
+
+        if let Some(index) = self.assignments.get(&id) {
+            return groups.get(*index);
+        }
+
+        let path = self.path.join(".");
+        let container_path = self.container_path.join(".");
+        let index = groups
+            .iter()
+            .position(|group| group.group.selector().matches(&id, &path, &container_path))?;
+        self.assignments.insert(id, index);
+
+        groups.get(index)
+    }
 }
 
 impl<M, B, O> ModuleMapper<B> for SimpleOptimizerMapper<'_, M, B, O>
@@ -116,24 +228,44 @@ where
     B: AutodiffBackend,
     O: SimpleOptimizer<B::InnerBackend>,
 {
+    fn enter_module(&mut self, name: &str, container_type: &str) {
+        if !self.groups.is_empty() {
+            self.path.push(name.into());
+            self.container_path.push(container_type.into());
+        }
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        if !self.groups.is_empty() {
+            self.path.pop();
+            self.container_path.pop();
+        }
+    }
+
     fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
         let grad = self.grads.remove(id);
 
+        // Frozen parameters are excluded from the optimizer state.
+        if !tensor.is_require_grad() {
+            self.records.remove(&id);
+            return tensor;
+        }
+
         if let Some(grad) = grad {
+            let (optimizer, lr) = match self.group(id) {
+                Some(group) if group.group.is_frozen() => return tensor,
+                Some(group) => (&group.optim, self.lr * group.group.lr_multiplier()),
+                None => (self.optimizer, self.lr),
+            };
+
             let device = grad.device();
             let is_require_grad = tensor.is_require_grad();
             let (key, record) = self.records.remove_entry(&id).unzip();
 
-            let clipped_grad = if let Some(g_clipping) = self.grad_clipping {
-                g_clipping.clip_gradient(grad)
-            } else {
-                grad
-            };
-
-            let (tensor, state) = self.optimizer.step(
-                self.lr,
+            let (tensor, state) = optimizer.step(
+                lr,
                 tensor.inner(),
-                clipped_grad,
+                grad,
                 record.map(|record| O::to_device(record.into_state(), &device)),
             );
 
@@ -152,3 +284,125 @@ where
         tensor
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        TestAutodiffBackend,
+        nn::{Linear, LinearConfig},
+        optim::{SgdConfig, decay::WeightDecayConfig},
+        tensor::TensorData,
+    };
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestAutodiffBackend>;
+
+    const LEARNING_RATE: LearningRate = 0.1;
+
+    #[test]
+    fn frozen_group_should_not_be_updated() {
+        let layer = layer();
+        let mut optim = SgdConfig::new()
+            .init()
+            .with_param_group(ParamGroup::from_paths("frozen", ["bias"]).with_frozen(true));
+
+        let updated = step(&mut optim, layer.clone(), LEARNING_RATE);
+
+        bias(&updated).assert_eq(&bias(&layer), true);
+        assert_ne!(weight(&updated), weight(&layer));
+    }
+
+    #[test]
+    fn group_lr_multiplier_should_scale_lr() {
+        let layer = layer();
+        let mut optim = SgdConfig::new()
+            .init()
+            .with_param_group(ParamGroup::from_paths("weight", ["weight"]).with_lr_multiplier(0.5));
+        let mut optim_ref = SgdConfig::new().init();
+
+        let updated = step(&mut optim, layer.clone(), LEARNING_RATE);
+        let updated_ref = step(&mut optim_ref, layer.clone(), LEARNING_RATE * 0.5);
+        let updated_default = step(&mut optim_ref, layer, LEARNING_RATE);
+
+        weight(&updated).assert_approx_eq::<FT>(&weight(&updated_ref), Tolerance::default());
+        bias(&updated).assert_approx_eq::<FT>(&bias(&updated_default), Tolerance::default());
+    }
+
+    #[test]
+    fn group_weight_decay_should_override_optimizer() {
+        let layer = layer();
+        let mut optim = SgdConfig::new()
+            .with_weight_decay(Some(WeightDecayConfig::new(0.5)))
+            .init()
+            .with_param_group(ParamGroup::from_paths("no_decay", ["bias"]).with_weight_decay(0.0));
+        let mut optim_ref = SgdConfig::new().init();
+
+        let updated = step(&mut optim, layer.clone(), LEARNING_RATE);
+        let updated_ref = step(&mut optim_ref, layer, LEARNING_RATE);
+
+        bias(&updated).assert_approx_eq::<FT>(&bias(&updated_ref), Tolerance::default());
+        assert_ne!(weight(&updated), weight(&updated_ref));
+    }
+
+    #[test]
+    fn group_assignments_should_be_computed_after_loading_record() {
+        let layer = layer();
+        let weight_id = layer.weight.id;
+        let bias_id = layer.bias.as_ref().unwrap().id;
+        let new_optim = || {
+            SgdConfig::new()
+                .init()
+                .with_param_group(ParamGroup::from_ids("bias", [bias_id]).with_lr_multiplier(0.1))
+                .with_param_group(ParamGroup::from_paths("all", ["weight", "bias"]))
+        };
+        let mut optim = new_optim();
+
+        let layer = step(&mut optim, layer, LEARNING_RATE);
+
+        assert_eq!(optim.param_group_of(&bias_id), Some("bias"));
+        assert_eq!(optim.param_group_of(&weight_id), Some("all"));
+
+        let mut optim = new_optim().load_record(optim.to_record());
+        let _layer = step(&mut optim, layer, LEARNING_RATE);
+
+        assert_eq!(optim.param_group_of(&bias_id), Some("bias"));
+        assert_eq!(optim.param_group_of(&weight_id), Some("all"));
+    }
+
+    #[test]
+    #[should_panic = "already registered"]
+    fn group_names_should_be_unique() {
+        let _optim = SgdConfig::new()
+            .init::<TestAutodiffBackend, Linear<TestAutodiffBackend>>()
+            .with_param_group(ParamGroup::from_paths("head", ["weight"]))
+            .with_param_group(ParamGroup::from_paths("head", ["bias"]));
+    }
+
+    fn step<O: SimpleOptimizer<<TestAutodiffBackend as AutodiffBackend>::InnerBackend>>(
+        optim: &mut OptimizerAdaptor<O, Linear<TestAutodiffBackend>, TestAutodiffBackend>,
+        layer: Linear<TestAutodiffBackend>,
+        lr: LearningRate,
+    ) -> Linear<TestAutodiffBackend> {
+        let x = Tensor::<TestAutodiffBackend, 2>::from_floats(
+            [[0.5, -1.0, 2.0, 0.3], [1.5, 0.2, -0.7, 1.0]],
+            &Default::default(),
+        );
+        let grads = layer.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &layer);
+
+        optim.step(lr, layer, grads)
+    }
+
+    fn layer() -> Linear<TestAutodiffBackend> {
+        LinearConfig::new(4, 3).init(&Default::default())
+    }
+
+    fn weight(layer: &Linear<TestAutodiffBackend>) -> TensorData {
+        layer.weight.val().into_data()
+    }
+
+    fn bias(layer: &Linear<TestAutodiffBackend>) -> TensorData {
+        layer.bias.as_ref().unwrap().val().into_data()
+    }
+}
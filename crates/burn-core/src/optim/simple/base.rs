@@ -30,4 +30,13 @@ where
     /// This function will be called accordingly to have the state on the same device as the
     /// gradient and the tensor when the [step](SimpleOptimizer::step) function is called.
     fn to_device<const D: usize>(state: Self::State<D>, device: &B::Device) -> Self::State<D>;
+
+    /// Returns a copy of the optimizer using the given weight decay penalty, or `None` if the
+    /// optimizer doesn't support weight decay.
+    ///
+    /// This is used by [parameter groups](crate::optim::ParamGroup) overriding the weight decay.
+    #[allow(unused_variables)]
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        None
+    }
 }
@@ -0,0 +1,193 @@
+use crate::{
+    self as burn, LearningRate, grad_clipping::GradientClippingConfig, module::AutodiffModule,
+    record::Record,
+};
+
+use super::SimpleOptimizer;
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{Tensor, backend::AutodiffBackend};
+use burn_tensor::{backend::Backend, ops::Device};
+
+/// Sophia configuration.
+#[derive(Config, Debug)]
+pub struct SophiaConfig {
+    /// Coefficient of the gradient moving average.
+    #[config(default = 0.965)]
+    beta_1: f32,
+    /// Coefficient of the Hessian estimate moving average.
+    #[config(default = 0.99)]
+    beta_2: f32,
+    /// Scale of the Hessian estimate, controlling the size of the pre-conditioned update before
+    /// it gets clipped.
+    #[config(default = 0.04)]
+    rho: f32,
+    /// A value required for numerical stability.
+    #[config(default = 1e-15)]
+    epsilon: f32,
+    /// Decoupled weight decay coefficient.
+    #[config(default = 0.0)]
+    weight_decay: f32,
+    /// Number of steps between two updates of the Hessian estimate.
+    #[config(default = 10)]
+    hessian_update_interval: usize,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+}
+
+/// Sophia (Second-order Clipped Stochastic Optimization) optimizer as described in the paper
+/// [Sophia: A Scalable Stochastic Second-order Optimizer for Language Model Pre-training](https://arxiv.org/abs/2305.14342).
+///
+/// The momentum is pre-conditioned by a moving average of a diagonal Hessian estimate, and each
+/// element of the update is clipped to one. The diagonal Hessian is estimated every
+/// `hessian_update_interval` steps with the Gauss-Newton-Bartlett estimator applied to the
+/// gradient of the step, i.e. the element-wise square of the gradient. Fold the batch size of the
+/// estimator into `rho` when needed.
+#[derive(Clone)]
+pub struct Sophia {
+    beta_1: f32,
+    beta_2: f32,
+    rho: f32,
+    epsilon: f32,
+    weight_decay: f32,
+    hessian_update_interval: usize,
+}
+
+/// Sophia state.
+#[derive(Record, Clone, new)]
+pub struct SophiaState<B: Backend, const D: usize> {
+    /// The number of iterations aggregated.
+    pub time: usize,
+    /// The moving average of the gradient.
+    pub momentum: Tensor<B, D>,
+    /// The moving average of the diagonal Hessian estimate.
+    pub hessian: Tensor<B, D>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Sophia {
+    type State<const D: usize> = SophiaState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        let (time, momentum, hessian) = match state {
+            Some(state) => (state.time + 1, state.momentum, state.hessian),
+            None => (1, grad.zeros_like(), grad.zeros_like()),
+        };
+
+        let momentum = momentum
+            .mul_scalar(self.beta_1)
+            .add(grad.clone().mul_scalar(1.0 - self.beta_1));
+        let hessian = if (time - 1) % self.hessian_update_interval == 0 {
+            hessian
+                .mul_scalar(self.beta_2)
+                .add(grad.powi_scalar(2).mul_scalar(1.0 - self.beta_2))
+        } else {
+            hessian
+        };
+
+        let ratio = momentum
+            .clone()
+            .abs()
+            .div(hessian.clone().mul_scalar(self.rho).clamp_min(self.epsilon))
+            .clamp_max(1.0);
+        let update = momentum.clone().sign().mul(ratio);
+
+        let tensor = tensor.clone() - tensor.mul_scalar(lr).mul_scalar(self.weight_decay);
+        let tensor = tensor - update.mul_scalar(lr);
+
+        (tensor, Some(SophiaState::new(time, momentum, hessian)))
+    }
+
+    fn to_device<const D: usize>(mut state: Self::State<D>, device: &Device<B>) -> Self::State<D> {
+        state.momentum = state.momentum.to_device(device);
+        state.hessian = state.hessian.to_device(device);
+        state
+    }
+
+    fn with_weight_decay(&self, penalty: f32) -> Option<Self> {
+        let mut optim = self.clone();
+        optim.weight_decay = penalty;
+        Some(optim)
+    }
+}
+
+impl SophiaConfig {
+    /// Initialize Sophia optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    ///
+    /// # Panics
+    ///
+    /// If the Hessian update interval is zero.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(&self) -> OptimizerAdaptor<Sophia, M, B> {
+        assert!(
+            self.hessian_update_interval > 0,
+            "The Hessian update interval should be positive"
+        );
+
+        let optim = Sophia {
+            beta_1: self.beta_1,
+            beta_2: self.beta_2,
+            rho: self.rho,
+            epsilon: self.epsilon,
+            weight_decay: self.weight_decay,
+            hessian_update_interval: self.hessian_update_interval,
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        optim
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::tensor::TensorData;
+    use burn_tensor::{Tolerance, ops::FloatElem};
+
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_sophia_optimizer_with_numbers() {
+        let device = Default::default();
+        let optim = Sophia {
+            beta_1: 0.9,
+            beta_2: 0.99,
+            rho: 20.0,
+            epsilon: 1e-15,
+            weight_decay: 0.1,
+            hessian_update_interval: 10,
+        };
+        let tensor =
+            Tensor::<TestBackend, 2>::from_floats([[0.5, -1.0, 2.0], [0.3, -0.2, 1.5]], &device);
+        let grad_1 = Tensor::from_floats([[0.1, -0.2, 0.3], [0.4, -0.5, 0.6]], &device);
+        let grad_2 = Tensor::from_floats([[-0.3, 0.1, 0.2], [0.05, 0.4, -0.1]], &device);
+
+        let (tensor, state) = optim.step(0.01, tensor, grad_1, None);
+        let (tensor, state) = optim.step(0.01, tensor, grad_2, state);
+
+        tensor.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([
+                [0.499011, -0.978011, 1.976012],
+                [0.279410, -0.188610, 1.482565],
+            ]),
+            Tolerance::absolute(1e-5),
+        );
+        // The Hessian estimate is only updated on the first step of each interval.
+        state.unwrap().hessian.into_data().assert_approx_eq::<FT>(
+            &TensorData::from([[0.0001, 0.0004, 0.0009], [0.0016, 0.0025, 0.0036]]),
+            Tolerance::absolute(1e-7),
+        );
+    }
+}
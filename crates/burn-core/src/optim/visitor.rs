@@ -21,6 +21,15 @@ pub struct GradientsParamsChangeDevice<'a, M: AutodiffModule<B>, B: AutodiffBack
     phatom: PhantomData<M>,
 }
 
+/// Accumulates the squared L2 norm of the gradients of a module, on the device of the first one.
+#[derive(new)]
+pub struct GradientsParamsSquaredNorm<'a, M: AutodiffModule<B>, B: AutodiffBackend> {
+    grads: &'a GradientsParams,
+    #[new(default)]
+    pub squared_norm: Option<Tensor<B::InnerBackend, 1>>,
+    phatom: PhantomData<M>,
+}
+
 impl<B, M> ModuleVisitor<B> for GradientsParamsConverter<'_, M, B>
 where
     B: AutodiffBackend,
@@ -33,6 +42,11 @@ where
             return;
         }
 
+        // Frozen parameters don't take part in the optimization.
+        if !tensor.is_require_grad() {
+            return;
+        }
+
         let Some(grad) = tensor.grad_remove(self.grads) else {
             return;
         };
@@ -55,3 +69,24 @@ where
             .register::<B::InnerBackend, D>(id, grad.to_device(self.device));
     }
 }
+
+impl<B, M> ModuleVisitor<B> for GradientsParamsSquaredNorm<'_, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) else {
+            return;
+        };
+        let squared_norm = grad.powi_scalar(2).sum();
+
+        self.squared_norm = Some(match self.squared_norm.take() {
+            Some(total) => {
+                let device = total.device();
+                total.add(squared_norm.to_device(&device))
+            }
+            None => squared_norm,
+        });
+    }
+}
@@ -6,6 +6,7 @@ mod comparison;
 mod contiguous;
 mod index;
 mod mask;
+mod scan;
 mod unary_float;
 mod unary_int;
 mod unary_numeric;
@@ -15,6 +16,7 @@ pub(crate) use binary_int::*;
 pub use cast::*;
 pub use contiguous::*;
 pub use mask::*;
+pub(crate) use scan::*;
 pub(crate) use unary_float::*;
 pub(crate) use unary_int::*;
 pub(crate) use unary_numeric::*;
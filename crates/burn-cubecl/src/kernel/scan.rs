@@ -0,0 +1,58 @@
+use crate::{
+    CubeRuntime, element::CubeElement, kernel::into_contiguous, ops::numeric::empty_device,
+    tensor::CubeTensor,
+};
+use cubecl::{calculate_cube_count_elemwise, prelude::*};
+
+/// Each unit scans one lane along `dim`, so the recurrence is evaluated with `O(L)` work.
+#[cube(launch_unchecked)]
+fn linear_scan_kernel<E: Numeric>(a: &Tensor<E>, b: &Tensor<E>, output: &mut Tensor<E>, dim: u32) {
+    let stride = output.stride(dim);
+    let length = output.shape(dim);
+
+    if ABSOLUTE_POS >= output.len() / length {
+        terminate!();
+    }
+
+    let mut offset = ABSOLUTE_POS / stride * stride * length + ABSOLUTE_POS % stride;
+    let mut state = E::from_int(0);
+
+    for _ in 0..length {
+        state = a[offset] * state + b[offset];
+        output[offset] = state;
+        offset += stride;
+    }
+}
+
+/// Computes `h_t = a_t * h_{t-1} + b_t` along `dim`, with `h_{-1} = 0`.
+pub(crate) fn linear_scan<R: CubeRuntime, E: CubeElement>(
+    a: CubeTensor<R>,
+    b: CubeTensor<R>,
+    dim: usize,
+) -> CubeTensor<R> {
+    let a = into_contiguous(a);
+    let b = into_contiguous(b);
+    let output = empty_device::<R, E>(b.client.clone(), b.device.clone(), b.shape.clone());
+
+    if output.shape.num_elements() == 0 {
+        return output;
+    }
+
+    let num_lanes = output.shape.num_elements() / output.shape.dims[dim];
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_lanes, cube_dim);
+
+    unsafe {
+        linear_scan_kernel::launch_unchecked::<E, R>(
+            &output.client,
+            cube_count,
+            cube_dim,
+            a.as_tensor_arg::<E>(1),
+            b.as_tensor_arg::<E>(1),
+            output.as_tensor_arg::<E>(1),
+            ScalarArg::new(dim as u32),
+        );
+    }
+
+    output
+}
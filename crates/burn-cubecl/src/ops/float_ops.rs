@@ -651,6 +651,14 @@ where
         )
     }
 
+    fn float_linear_scan(
+        a: FloatTensor<Self>,
+        b: FloatTensor<Self>,
+        dim: usize,
+    ) -> FloatTensor<Self> {
+        execute_with_dtype!(float(b.dtype), E, kernel::linear_scan::<R, E>(a, b, dim))
+    }
+
     fn float_cast(mut tensor: FloatTensor<Self>, dtype: FloatDType) -> FloatTensor<Self> {
         match (tensor.dtype, dtype) {
             (DType::F64, FloatDType::F64)
@@ -174,6 +174,9 @@ fn main() {
         .input("tests/linear/linear.onnx")
         .input("tests/log/log.onnx")
         .input("tests/log_softmax/log_softmax.onnx")
+        .input("tests/lp_normalization/lp_normalization.onnx")
+        .input("tests/lp_normalization/lp_normalization_l1.onnx")
+        .input("tests/lrn/lrn.onnx")
         .input("tests/where_op/where_op.onnx")
         .input("tests/where_op/where_op_broadcast.onnx")
         .input("tests/where_op/where_op_scalar_x.onnx")
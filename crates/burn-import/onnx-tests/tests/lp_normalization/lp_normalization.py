@@ -0,0 +1,68 @@
+#!/usr/bin/env python3
+
+# used to generate models:
+#   onnx-tests/tests/lp_normalization/lp_normalization.onnx (p = 2, axis = -1)
+#   onnx-tests/tests/lp_normalization/lp_normalization_l1.onnx (p = 1, axis = 1)
+
+import numpy as np
+import onnx
+import onnxruntime as ort
+from onnx import helper, TensorProto
+from onnx.reference import ReferenceEvaluator
+
+# ONNX opset version to use for model generation
+OPSET_VERSION = 16
+
+
+def export(onnx_name, shape, p, axis, test_input):
+    input_tensor = helper.make_tensor_value_info("input", TensorProto.FLOAT, shape)
+    output_tensor = helper.make_tensor_value_info("output", TensorProto.FLOAT, shape)
+
+    node = helper.make_node(
+        "LpNormalization",
+        inputs=["input"],
+        outputs=["output"],
+        name="/LpNormalization",
+        axis=axis,
+        p=p,
+    )
+
+    graph_def = helper.make_graph([node], "main_graph", [input_tensor], [output_tensor])
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="onnx-tests",
+        opset_imports=[helper.make_operatorsetid("", OPSET_VERSION)],
+    )
+
+    onnx.save(model_def, onnx_name)
+    print(f"Finished exporting model to {onnx_name}")
+    print(f"Test input data: {test_input}")
+
+    session = ort.InferenceSession(onnx_name)
+    output = session.run(None, {"input": test_input})[0]
+    print(f"Test output data: {output}")
+
+    # Check against the reference implementation of the ONNX specification
+    reference = ReferenceEvaluator(onnx_name).run(None, {"input": test_input})[0]
+    np.testing.assert_allclose(output, reference, rtol=1e-5)
+
+
+def main():
+    export(
+        "lp_normalization.onnx",
+        [2, 3, 4],
+        p=2,
+        axis=-1,
+        test_input=((np.arange(24, dtype=np.float32) - 10) / 4).reshape(2, 3, 4),
+    )
+    export(
+        "lp_normalization_l1.onnx",
+        [2, 3, 2],
+        p=1,
+        axis=1,
+        test_input=(np.arange(12, dtype=np.float32) - 5.5).reshape(2, 3, 2),
+    )
+
+
+if __name__ == "__main__":
+    main()
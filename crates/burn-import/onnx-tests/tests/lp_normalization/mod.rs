@@ -0,0 +1,78 @@
+// Import the shared macro
+use crate::include_models;
+include_models!(lp_normalization, lp_normalization_l1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::tensor::{Tensor, TensorData, Tolerance, ops::FloatElem};
+
+    use crate::backend::TestBackend;
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn lp_normalization_l2_last_axis() {
+        let device = Default::default();
+        let model: lp_normalization::Model<TestBackend> = lp_normalization::Model::default();
+
+        let input = Tensor::<TestBackend, 3>::from_floats(
+            [
+                [
+                    [-2.5, -2.25, -2.0, -1.75],
+                    [-1.5, -1.25, -1.0, -0.75],
+                    [-0.5, -0.25, 0.0, 0.25],
+                ],
+                [
+                    [0.5, 0.75, 1.0, 1.25],
+                    [1.5, 1.75, 2.0, 2.25],
+                    [2.5, 2.75, 3.0, 3.25],
+                ],
+            ],
+            &device,
+        );
+        let output = model.forward(input);
+        let expected = TensorData::from([
+            [
+                [-0.583212f32, -0.524891, -0.466569, -0.408248],
+                [-0.646997, -0.539164, -0.431331, -0.323498],
+                [-0.816497, -0.408248, 0.0, 0.408248],
+            ],
+            [
+                [0.272166, 0.408248, 0.544331, 0.680414],
+                [0.395628, 0.461566, 0.527504, 0.593442],
+                [0.432742, 0.476017, 0.519291, 0.562565],
+            ],
+        ]);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+
+    #[test]
+    fn lp_normalization_l1_channel_axis() {
+        let device = Default::default();
+        let model: lp_normalization_l1::Model<TestBackend> = lp_normalization_l1::Model::default();
+
+        let input = Tensor::<TestBackend, 3>::from_floats(
+            [
+                [[-5.5, -4.5], [-3.5, -2.5], [-1.5, -0.5]],
+                [[0.5, 1.5], [2.5, 3.5], [4.5, 5.5]],
+            ],
+            &device,
+        );
+        let output = model.forward(input);
+        let expected = TensorData::from([
+            [
+                [-0.52381f32, -0.6],
+                [-0.333333, -0.333333],
+                [-0.142857, -0.066667],
+            ],
+            [[0.066667, 0.142857], [0.333333, 0.333333], [0.6, 0.52381]],
+        ]);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+}
@@ -0,0 +1,55 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/lrn/lrn.onnx
+
+import numpy as np
+import onnx
+import onnxruntime as ort
+from onnx import helper, TensorProto
+from onnx.reference import ReferenceEvaluator
+
+# ONNX opset version to use for model generation
+OPSET_VERSION = 16
+
+
+def main():
+    input_tensor = helper.make_tensor_value_info("input", TensorProto.FLOAT, [1, 5, 2, 2])
+    output_tensor = helper.make_tensor_value_info("output", TensorProto.FLOAT, [1, 5, 2, 2])
+
+    # Non-default attributes, so that the normalization has a visible effect
+    lrn_node = helper.make_node(
+        "LRN",
+        inputs=["input"],
+        outputs=["output"],
+        name="/LRN",
+        size=3,
+        alpha=0.5,
+        beta=0.75,
+        bias=2.0,
+    )
+
+    graph_def = helper.make_graph([lrn_node], "main_graph", [input_tensor], [output_tensor])
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="onnx-tests",
+        opset_imports=[helper.make_operatorsetid("", OPSET_VERSION)],
+    )
+
+    onnx_name = "lrn.onnx"
+    onnx.save(model_def, onnx_name)
+    print(f"Finished exporting model to {onnx_name}")
+
+    test_input = ((np.arange(20, dtype=np.float32) - 8) / 4).reshape(1, 5, 2, 2)
+    print(f"Test input data: {test_input}")
+
+    session = ort.InferenceSession(onnx_name)
+    output = session.run(None, {"input": test_input})[0]
+    print(f"Test output data: {output}")
+
+    # Check against the reference implementation of the ONNX specification
+    reference = ReferenceEvaluator(onnx_name).run(None, {"input": test_input})[0]
+    np.testing.assert_allclose(output, reference, rtol=1e-5)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,41 @@
+// Import the shared macro
+use crate::include_models;
+include_models!(lrn);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::tensor::{Tensor, TensorData, Tolerance, ops::FloatElem};
+
+    use crate::backend::TestBackend;
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn lrn() {
+        let device = Default::default();
+        let model: lrn::Model<TestBackend> = lrn::Model::default();
+
+        let input = Tensor::<TestBackend, 4>::from_floats(
+            [[
+                [[-2.0, -1.75], [-1.5, -1.25]],
+                [[-1.0, -0.75], [-0.5, -0.25]],
+                [[0.0, 0.25], [0.5, 0.75]],
+                [[1.0, 1.25], [1.5, 1.75]],
+                [[2.0, 2.25], [2.5, 2.75]],
+            ]],
+            &device,
+        );
+        let output = model.forward(input);
+        let expected = TensorData::from([[
+            [[-0.915813f32, -0.853663], [-0.773889, -0.675727]],
+            [[-0.457906, -0.364762], [-0.254677, -0.131106]],
+            [[0.0, 0.131106], [0.254677, 0.364762]],
+            [[0.457906, 0.533163], [0.591481, 0.634908]],
+            [[0.915813, 0.962108], [0.994805, 1.01626]],
+        ]]);
+
+        output
+            .to_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+}
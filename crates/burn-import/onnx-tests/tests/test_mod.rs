@@ -68,6 +68,8 @@ pub mod less_or_equal;
 pub mod linear;
 pub mod log;
 pub mod log_softmax;
+pub mod lp_normalization;
+pub mod lrn;
 pub mod matmul;
 pub mod matmulinteger;
 pub mod max;
@@ -13,8 +13,9 @@ use super::{
     gather::GatherNode, gather_elements::GatherElementsNode, gemm::GemmNode,
     global_avg_pool::GlobalAvgPoolNode, group_norm::GroupNormNode, identity::IdentityNode,
     instance_norm::InstanceNormNode, layer_norm::LayerNormNode, linear::LinearNode,
-    matmul::MatmulNode, matmul_integer::MatMulIntegerNode, max_pool1d::MaxPool1dNode,
-    max_pool2d::MaxPool2dNode, mean::MeanNode, one_hot::OneHotNode, pad::PadNode, prelu::PReluNode,
+    lp_normalization::LpNormalizationNode, lrn::LrnNode, matmul::MatmulNode,
+    matmul_integer::MatMulIntegerNode, max_pool1d::MaxPool1dNode, max_pool2d::MaxPool2dNode,
+    mean::MeanNode, one_hot::OneHotNode, pad::PadNode, prelu::PReluNode,
     random_normal::RandomNormalNode, random_normal_like::RandomNormalLikeNode,
     random_uniform::RandomUniformNode, random_uniform_like::RandomUniformLikeNode,
     range::RangeNode, reduce::ReduceNode, reshape::ReshapeNode, resize::ResizeNode,
@@ -128,6 +129,8 @@ pub enum Node<PS: PrecisionSettings> {
     LayerNorm(LayerNormNode),
     GroupNorm(GroupNormNode),
     Linear(LinearNode),
+    LpNormalization(LpNormalizationNode),
+    Lrn(LrnNode),
     Matmul(MatmulNode),
     MatmulInteger(MatMulIntegerNode),
     MaxPool1d(MaxPool1dNode),
@@ -204,6 +207,8 @@ macro_rules! match_all {
             Node::LayerNorm(node) => $func(node),
             Node::GroupNorm(node) => $func(node),
             Node::Linear(node) => $func(node),
+            Node::LpNormalization(node) => $func(node),
+            Node::Lrn(node) => $func(node),
             Node::MatmulInteger(node) => $func(node),
             Node::Matmul(node) => $func(node),
             Node::MaxPool1d(node) => $func(node),
@@ -288,6 +293,8 @@ impl<PS: PrecisionSettings> Node<PS> {
             Node::LayerNorm(_) => "layer_norm",
             Node::GroupNorm(_) => "group_norm",
             Node::Linear(_) => "linear",
+            Node::LpNormalization(_) => "lp_normalization",
+            Node::Lrn(_) => "lrn",
             Node::MatmulInteger(_) => "matmul_integer",
             Node::Matmul(_) => "matmul",
             Node::MaxPool1d(_) => "max_pool1d",
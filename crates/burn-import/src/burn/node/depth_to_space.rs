@@ -1,5 +1,5 @@
 use super::{Node, NodeCodegen};
-use crate::burn::{Scope, TensorType, Type};
+use crate::burn::{BurnImports, OtherType, Scope, TensorType, Type};
 use burn::record::PrecisionSettings;
 use onnx_ir::node::depth_to_space::{DepthToSpaceConfig, DepthToSpaceMode};
 use proc_macro2::TokenStream;
@@ -7,14 +7,26 @@ use quote::quote;
 
 #[derive(Debug, Clone)]
 pub struct DepthToSpaceNode {
+    pub field: OtherType,
     pub input: TensorType,
     pub output: TensorType,
     pub config: DepthToSpaceConfig,
 }
 
 impl DepthToSpaceNode {
-    pub fn new(input: TensorType, output: TensorType, config: DepthToSpaceConfig) -> Self {
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        input: TensorType,
+        output: TensorType,
+        config: DepthToSpaceConfig,
+    ) -> Self {
         Self {
+            field: OtherType::new(
+                name,
+                quote! {
+                    PixelShuffle
+                },
+            ),
             input,
             output,
             config,
@@ -31,41 +43,60 @@ impl<PS: PrecisionSettings> NodeCodegen<PS> for DepthToSpaceNode {
         vec![Type::Tensor(self.output.clone())]
     }
 
+    fn field_type(&self) -> Option<Type> {
+        Some(Type::Other(self.field.clone()))
+    }
+
+    fn field_init(&self) -> Option<TokenStream> {
+        let name = &self.field.name;
+        let block_size = self.config.block_size;
+        let tokens = quote! {
+            let #name = PixelShuffleConfig::new(#block_size).init();
+        };
+
+        Some(tokens)
+    }
+
     fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
         let input = scope.tensor_use_owned(&self.input, node_position);
         let output = &self.output.name;
+        let field = &self.field.name;
         let block_size = self.config.block_size;
 
-        let output_expr = match self.config.mode {
-            DepthToSpaceMode::DCR => {
-                quote! {
-                    let [b, c, h, w] = #input.shape().dims();
-                    #input
-                        .reshape([b, #block_size, #block_size, c / (#block_size * #block_size), h, w])
-                        .permute([0, 3, 4, 1, 5, 2])
-                        .reshape([b, c / (#block_size * #block_size), h * #block_size, w * #block_size])
-                }
-            }
-            DepthToSpaceMode::CRD => {
-                quote! {
-                    let [b, c, h, w] = #input.shape().dims();
-                    #input
-                        .reshape([b, c / (#block_size * #block_size), #block_size, #block_size, h, w])
-                        .permute([0, 1, 4, 2, 5, 3])
-                        .reshape([b, c / (#block_size * #block_size), h * #block_size, w * #block_size])
-                }
-            }
-        };
-        quote! {
-            let #output = {
-                #output_expr
-            };
+        match self.config.mode {
+            // The column-row-depth layout is the one of the pixel shuffle.
+            DepthToSpaceMode::CRD => quote! {
+                let #output = self.#field.forward(#input);
+            },
+            // The depth-column-row layout stores the blocks first, shuffling the channels
+            // brings them back to the column-row-depth layout.
+            DepthToSpaceMode::DCR => quote! {
+                let #output = {
+                    let input = ChannelShuffleConfig::new(#block_size * #block_size)
+                        .init()
+                        .forward(#input);
+                    self.#field.forward(input)
+                };
+            },
+        }
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::nn::PixelShuffle");
+        imports.register("burn::nn::PixelShuffleConfig");
+
+        if self.config.mode == DepthToSpaceMode::DCR {
+            imports.register("burn::nn::ChannelShuffleConfig");
         }
     }
 
     fn into_node(self) -> Node<PS> {
         Node::DepthToSpace(self)
     }
+
+    fn field_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        S::serialize_none(serializer)
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +110,7 @@ mod tests {
         let mut graph = BurnGraph::<FullPrecisionSettings>::default();
 
         graph.register(DepthToSpaceNode::new(
+            "depth_to_space",
             TensorType::new_float("input", 4),
             TensorType::new_float("output", 4),
             DepthToSpaceConfig::new(DepthToSpaceMode::DCR, 2),
@@ -88,15 +120,23 @@ mod tests {
 
         let expected = quote! {
             use burn::prelude::*;
+            use burn::nn::ChannelShuffleConfig;
+            use burn::nn::PixelShuffle;
+            use burn::nn::PixelShuffleConfig;
+
             #[derive(Module, Debug)]
             pub struct Model<B: Backend> {
+                depth_to_space: PixelShuffle,
                 phantom: core::marker::PhantomData<B>,
                 device: burn::module::Ignored<B::Device>,
             }
             impl<B: Backend> Model<B> {
                 #[allow(unused_variables)]
                 pub fn new(device: &B::Device) -> Self {
+                    let depth_to_space = PixelShuffleConfig::new(2usize).init();
+
                     Self {
+                        depth_to_space,
                         phantom: core::marker::PhantomData,
                         device: burn::module::Ignored(device.clone()),
                     }
@@ -104,11 +144,10 @@ mod tests {
                 #[allow(clippy::let_and_return, clippy::approx_constant)]
                 pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
                     let output = {
-                        let [b, c, h, w] = input.shape().dims();
-                        input
-                            .reshape([b, 2usize, 2usize, c / (2usize * 2usize), h, w])
-                            .permute([0, 3, 4, 1, 5, 2])
-                            .reshape([b, c / (2usize * 2usize), h * 2usize, w * 2usize])
+                        let input = ChannelShuffleConfig::new(2usize * 2usize)
+                            .init()
+                            .forward(input);
+                        self.depth_to_space.forward(input)
                     };
                     output
                 }
@@ -123,6 +162,7 @@ mod tests {
         let mut graph = BurnGraph::<FullPrecisionSettings>::default();
 
         graph.register(DepthToSpaceNode::new(
+            "depth_to_space",
             TensorType::new_float("input", 4),
             TensorType::new_float("output", 4),
             DepthToSpaceConfig::new(DepthToSpaceMode::CRD, 2),
@@ -132,28 +172,30 @@ mod tests {
 
         let expected = quote! {
             use burn::prelude::*;
+            use burn::nn::PixelShuffle;
+            use burn::nn::PixelShuffleConfig;
+
             #[derive(Module, Debug)]
             pub struct Model<B: Backend> {
+                depth_to_space: PixelShuffle,
                 phantom: core::marker::PhantomData<B>,
                 device: burn::module::Ignored<B::Device>,
             }
             impl<B: Backend> Model<B> {
                 #[allow(unused_variables)]
                 pub fn new(device: &B::Device) -> Self {
+                    let depth_to_space = PixelShuffleConfig::new(2usize).init();
+
                     Self {
+                        depth_to_space,
                         phantom: core::marker::PhantomData,
                         device: burn::module::Ignored(device.clone()),
                     }
                 }
                 #[allow(clippy::let_and_return, clippy::approx_constant)]
                 pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
-                    let output = {
-                        let [b, c, h, w] = input.shape().dims();
-                        input
-                            .reshape([b, c / (2usize * 2usize), 2usize, 2usize, h, w])
-                            .permute([0, 1, 4, 2, 5, 3])
-                            .reshape([b, c / (2usize * 2usize), h * 2usize, w * 2usize])
-                    };
+                    let output = self.depth_to_space.forward(input);
+
                     output
                 }
             }
@@ -39,6 +39,8 @@ pub(crate) mod identity;
 pub(crate) mod instance_norm;
 pub(crate) mod layer_norm;
 pub(crate) mod linear;
+pub(crate) mod lp_normalization;
+pub(crate) mod lrn;
 pub(crate) mod matmul;
 pub(crate) mod matmul_integer;
 pub(crate) mod max_pool1d;
@@ -1,19 +1,31 @@
 use super::{Node, NodeCodegen};
-use crate::burn::{Scope, TensorType, Type};
+use crate::burn::{BurnImports, OtherType, Scope, TensorType, Type};
 use burn::record::PrecisionSettings;
 use proc_macro2::TokenStream;
 use quote::quote;
 
 #[derive(Debug, Clone)]
 pub struct SpaceToDepthNode {
+    pub field: OtherType,
     pub input: TensorType,
     pub output: TensorType,
     pub block_size: usize,
 }
 
 impl SpaceToDepthNode {
-    pub fn new(input: TensorType, output: TensorType, block_size: usize) -> Self {
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        input: TensorType,
+        output: TensorType,
+        block_size: usize,
+    ) -> Self {
         Self {
+            field: OtherType::new(
+                name,
+                quote! {
+                    PixelUnshuffle
+                },
+            ),
             input,
             output,
             block_size,
@@ -30,25 +42,49 @@ impl<PS: PrecisionSettings> NodeCodegen<PS> for SpaceToDepthNode {
         vec![Type::Tensor(self.output.clone())]
     }
 
+    fn field_type(&self) -> Option<Type> {
+        Some(Type::Other(self.field.clone()))
+    }
+
+    fn field_init(&self) -> Option<TokenStream> {
+        let name = &self.field.name;
+        let block_size = self.block_size;
+        let tokens = quote! {
+            let #name = PixelUnshuffleConfig::new(#block_size).init();
+        };
+
+        Some(tokens)
+    }
+
     fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
         let input = scope.tensor_use_owned(&self.input, node_position);
         let output = &self.output.name;
-        let block_size = self.block_size;
+        let field = &self.field.name;
 
+        // The pixel unshuffle produces a column-row-depth layout, shuffling the channels with one
+        // group per input channel brings the blocks first as expected by ONNX.
         quote! {
             let #output = {
-                let [b, c, h, w] = #input.shape().dims();
-                #input
-                    .reshape([b, c, h / #block_size, #block_size, w / #block_size, #block_size])
-                    .permute([0, 3, 5, 1, 2, 4])
-                    .reshape([b, c * #block_size * #block_size, h / #block_size, w / #block_size])
+                let [_, channels, _, _] = #input.shape().dims();
+                let output = self.#field.forward(#input);
+                ChannelShuffleConfig::new(channels).init().forward(output)
             };
         }
     }
 
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::nn::ChannelShuffleConfig");
+        imports.register("burn::nn::PixelUnshuffle");
+        imports.register("burn::nn::PixelUnshuffleConfig");
+    }
+
     fn into_node(self) -> Node<PS> {
         Node::SpaceToDepth(self)
     }
+
+    fn field_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        S::serialize_none(serializer)
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +98,7 @@ mod tests {
         let mut graph = BurnGraph::<FullPrecisionSettings>::default();
 
         graph.register(SpaceToDepthNode::new(
+            "space_to_depth",
             TensorType::new_float("input", 4),
             TensorType::new_float("output", 4),
             2,
@@ -71,15 +108,23 @@ mod tests {
 
         let expected = quote! {
             use burn::prelude::*;
+            use burn::nn::ChannelShuffleConfig;
+            use burn::nn::PixelUnshuffle;
+            use burn::nn::PixelUnshuffleConfig;
+
             #[derive(Module, Debug)]
             pub struct Model<B: Backend> {
+                space_to_depth: PixelUnshuffle,
                 phantom: core::marker::PhantomData<B>,
                 device: burn::module::Ignored<B::Device>,
             }
             impl<B: Backend> Model<B> {
                 #[allow(unused_variables)]
                 pub fn new(device: &B::Device) -> Self {
+                    let space_to_depth = PixelUnshuffleConfig::new(2usize).init();
+
                     Self {
+                        space_to_depth,
                         phantom: core::marker::PhantomData,
                         device: burn::module::Ignored(device.clone()),
                     }
@@ -87,11 +132,9 @@ mod tests {
                 #[allow(clippy::let_and_return, clippy::approx_constant)]
                 pub fn forward(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
                     let output = {
-                        let [b, c, h, w] = input.shape().dims();
-                        input
-                            .reshape([b, c, h / 2usize, 2usize, w / 2usize, 2usize])
-                            .permute([0, 3, 5, 1, 2, 4])
-                            .reshape([b, c * 2usize * 2usize, h / 2usize, w / 2usize])
+                        let [_, channels, _, _] = input.shape().dims();
+                        let output = self.space_to_depth.forward(input);
+                        ChannelShuffleConfig::new(channels).init().forward(output)
                     };
                     output
                 }
@@ -57,6 +57,8 @@ use crate::{
             instance_norm::InstanceNormNode,
             layer_norm::LayerNormNode,
             linear::LinearNode,
+            lp_normalization::LpNormalizationNode,
+            lrn::LrnNode,
             matmul::MatmulNode,
             matmul_integer::MatMulIntegerNode,
             max_pool1d::MaxPool1dNode,
@@ -125,6 +127,8 @@ use onnx_ir::{
         leaky_relu::leaky_relu_config,
         linear::linear_config,
         log_softmax::log_softmax_config,
+        lp_normalization::lp_normalization_config,
+        lrn::lrn_config,
         max_pool1d::max_pool1d_config,
         max_pool2d::max_pool2d_config,
         one_hot::one_hot_config,
@@ -421,6 +425,10 @@ impl ParsedOnnxGraph {
                 NodeType::Log => graph.register(Self::log_conversion(node)),
                 NodeType::LeakyRelu => graph.register(Self::leaky_relu_conversion(node)),
                 NodeType::LogSoftmax => graph.register(Self::log_softmax_conversion(node)),
+                NodeType::LpNormalization => {
+                    graph.register(Self::lp_normalization_conversion(node))
+                }
+                NodeType::LRN => graph.register(Self::lrn_conversion(node)),
                 NodeType::Softmax => graph.register(Self::softmax_conversion(node)),
                 NodeType::Sqrt => graph.register(Self::sqrt_conversion(node)),
                 NodeType::Tan => graph.register(Self::tan_conversion(node)),
@@ -1369,7 +1377,7 @@ impl ParsedOnnxGraph {
         let output = TensorType::from(node.outputs.first().unwrap());
         let block_size = space_to_depth_config(&node);
 
-        SpaceToDepthNode::new(input, output, block_size)
+        SpaceToDepthNode::new(&node.name, input, output, block_size)
     }
 
     fn sum_conversion(node: Node) -> SumNode {
@@ -1482,6 +1490,24 @@ impl ParsedOnnxGraph {
         DropoutNode::new(name, input, output, config)
     }
 
+    fn lrn_conversion(node: Node) -> LrnNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = lrn_config(&node);
+
+        LrnNode::new(name, input, output, config)
+    }
+
+    fn lp_normalization_conversion(node: Node) -> LpNormalizationNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = lp_normalization_config(&node);
+
+        LpNormalizationNode::new(name, input, output, config)
+    }
+
     fn batch_norm_conversion<PS: PrecisionSettings>(node: Node) -> BatchNormNode {
         let config = batch_norm_config(&node);
         let input = TensorType::from(node.inputs.first().unwrap());
@@ -1610,7 +1636,7 @@ impl ParsedOnnxGraph {
         let output = TensorType::from(node.outputs.first().unwrap());
         let config = depth_to_space_config(&node);
 
-        DepthToSpaceNode::new(input, output, config)
+        DepthToSpaceNode::new(&node.name, input, output, config)
     }
 
     fn max_pool1d_conversion(node: Node) -> MaxPool1dNode {
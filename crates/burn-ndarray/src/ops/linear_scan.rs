@@ -0,0 +1,25 @@
+use ndarray::{Axis, Zip};
+
+use crate::{FloatNdArrayElement, SharedArray};
+
+/// Computes `h_t = a_t * h_{t-1} + b_t` along `dim`, visiting every element once.
+pub(crate) fn linear_scan<E: FloatNdArrayElement>(
+    a: SharedArray<E>,
+    b: SharedArray<E>,
+    dim: usize,
+) -> SharedArray<E> {
+    let mut output = b.into_owned();
+
+    Zip::from(output.lanes_mut(Axis(dim)))
+        .and(a.lanes(Axis(dim)))
+        .for_each(|mut output, a| {
+            let mut state = E::zero();
+
+            for (output, &a) in output.iter_mut().zip(a.iter()) {
+                state = a * state + *output;
+                *output = state;
+            }
+        });
+
+    output.into_shared()
+}
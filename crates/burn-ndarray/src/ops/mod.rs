@@ -15,6 +15,7 @@ pub(crate) mod conv;
 pub(crate) mod deform_conv;
 pub(crate) mod grid_sample;
 pub(crate) mod interpolate;
+pub(crate) mod linear_scan;
 pub(crate) mod macros;
 pub(crate) mod matmul;
 pub(crate) mod maxpool;
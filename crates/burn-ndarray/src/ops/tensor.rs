@@ -15,7 +15,9 @@ use crate::{
     SharedArray,
     element::{ExpElement, FloatNdArrayElement, IntNdArrayElement, QuantElement},
 };
-use crate::{execute_with_float_dtype, ops::grid_sample::grid_sample_2d};
+use crate::{
+    execute_with_float_dtype, ops::grid_sample::grid_sample_2d, ops::linear_scan::linear_scan,
+};
 
 // Workspace crates
 use crate::rand::get_seeded_rng;
@@ -497,4 +499,12 @@ where
             tensor, grid, method
         ))
     }
+
+    fn float_linear_scan(
+        a: FloatTensor<Self>,
+        b: FloatTensor<Self>,
+        dim: usize,
+    ) -> FloatTensor<Self> {
+        execute_with_float_dtype!((a, b), |a, b| linear_scan(a, b, dim))
+    }
 }
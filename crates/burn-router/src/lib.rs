@@ -10,6 +10,8 @@ mod bridge;
 mod channel;
 mod client;
 mod ops;
+#[cfg(feature = "std")]
+mod profiler;
 mod runner;
 mod tensor;
 mod types;
@@ -18,6 +20,8 @@ pub use backend::*;
 pub use bridge::*;
 pub use channel::*;
 pub use client::*;
+#[cfg(feature = "std")]
+pub use profiler::*;
 pub use runner::*;
 pub use tensor::*;
 pub use types::*;
@@ -0,0 +1,516 @@
+use alloc::{format, string::String, vec::Vec};
+use burn_common::scope::scope_path;
+use burn_ir::{OperationIr, TensorStatus};
+use burn_tensor::DType;
+use core::fmt::{Display, Write};
+use core::time::Duration;
+use hashbrown::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(0);
+
+std::thread_local! {
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+struct Recording {
+    start: Instant,
+    sync: bool,
+    events: Vec<OperationEvent>,
+}
+
+/// Records the [operations](OperationIr) executed by the runners of the
+/// [router backend](crate::BackendRouter), with their timing and the
+/// [scope](burn_common::scope::ForwardScope) of the module that executed them.
+///
+/// # Example
+///
+/// ```ignore
+/// let profiler = Profiler::new().with_sync(true).start();
+/// let output = model.forward(input);
+/// let profile = profiler.finish();
+///
+/// std::fs::write("trace.json", profile.chrome_trace())?;
+/// println!("{}", profile.summary());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Profiler {
+    sync: bool,
+}
+
+impl Profiler {
+    /// Create a new profiler, only measuring the time to launch the operations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for the device to complete each operation, to measure its device time.
+    ///
+    /// This is required to time the operations of asynchronous backends, but prevents the
+    /// execution of the operations to overlap.
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Start recording the operations, until the returned session is finished or dropped.
+    ///
+    /// A single recording is active at a time, so a previous recording is discarded.
+    pub fn start(self) -> ProfilerSession {
+        let mut recording = RECORDING.lock().unwrap();
+        if recording.is_some() {
+            log::warn!("A profiler is already recording, its operations are discarded.");
+        }
+        *recording = Some(Recording {
+            start: Instant::now(),
+            sync: self.sync,
+            events: Vec::new(),
+        });
+        ENABLED.store(true, Ordering::Relaxed);
+
+        ProfilerSession { _private: () }
+    }
+}
+
+/// A recording of the operations started by a [profiler](Profiler).
+#[must_use = "The recording stops when the session is dropped"]
+pub struct ProfilerSession {
+    _private: (),
+}
+
+impl ProfilerSession {
+    /// Stop recording, returning the recorded operations.
+    pub fn finish(self) -> Profile {
+        let recording = stop();
+        core::mem::forget(self);
+
+        Profile {
+            events: recording
+                .map(|recording| recording.events)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Drop for ProfilerSession {
+    fn drop(&mut self) {
+        stop();
+    }
+}
+
+fn stop() -> Option<Recording> {
+    ENABLED.store(false, Ordering::Relaxed);
+    RECORDING.lock().unwrap().take()
+}
+
+/// An operation recorded by the [profiler](Profiler).
+#[derive(Clone, Debug)]
+pub struct OperationEvent {
+    /// The name of the operation, e.g. `float.matmul`.
+    pub name: String,
+    /// The shapes of the input tensors.
+    pub shapes: Vec<Vec<usize>>,
+    /// The data types of the input tensors.
+    pub dtypes: Vec<DType>,
+    /// The path of the [forward scopes](burn_common::scope::ForwardScope) the operation was
+    /// executed in, empty outside of any scope.
+    pub scope: String,
+    /// The index of the thread that executed the operation.
+    pub thread: u64,
+    /// The time the operation started, since the start of the recording.
+    pub start: Duration,
+    /// The time to execute the operation on the host, which only launches it on asynchronous
+    /// backends.
+    pub wall_time: Duration,
+    /// The time until the device completed the operation, when the profiler
+    /// [synchronizes](Profiler::with_sync) the device.
+    pub device_time: Option<Duration>,
+    /// The number of bytes of the output tensors.
+    pub output_bytes: usize,
+}
+
+impl OperationEvent {
+    /// The device time of the operation when measured, otherwise its wall time.
+    pub fn time(&self) -> Duration {
+        self.device_time.unwrap_or(self.wall_time)
+    }
+}
+
+/// An operation being executed while profiling.
+pub(crate) struct OperationRecord {
+    event: OperationEvent,
+    started: Instant,
+    sync: bool,
+}
+
+impl OperationRecord {
+    /// Start recording the operation, when a profiler is recording.
+    ///
+    /// The initialization and the drop of tensors aren't recorded.
+    pub(crate) fn start(op: &OperationIr) -> Option<Self> {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return None;
+        }
+        let name = operation_name(op)?;
+
+        let mut shapes = Vec::new();
+        let mut dtypes = Vec::new();
+        let mut output_bytes = 0;
+        for node in op.nodes() {
+            match node.status {
+                TensorStatus::NotInit => {
+                    output_bytes += node.shape.iter().product::<usize>() * node.dtype.size()
+                }
+                _ => {
+                    shapes.push(node.shape.clone());
+                    dtypes.push(node.dtype);
+                }
+            }
+        }
+
+        let recording = RECORDING.lock().unwrap();
+        let recording = recording.as_ref()?;
+        let started = Instant::now();
+
+        Some(Self {
+            event: OperationEvent {
+                name,
+                shapes,
+                dtypes,
+                scope: scope_path(),
+                thread: THREAD.with(|thread| *thread),
+                start: started.duration_since(recording.start),
+                wall_time: Duration::ZERO,
+                device_time: None,
+                output_bytes,
+            },
+            started,
+            sync: recording.sync,
+        })
+    }
+
+    /// Record the end of the operation, calling the synchronization of the device when the
+    /// profiler measures the device time.
+    pub(crate) fn finish<F: FnOnce()>(mut self, sync: F) {
+        self.event.wall_time = self.started.elapsed();
+        if self.sync {
+            sync();
+            self.event.device_time = Some(self.started.elapsed());
+        }
+
+        if let Some(recording) = RECORDING.lock().unwrap().as_mut() {
+            recording.events.push(self.event);
+        }
+    }
+}
+
+/// Writes the name of an enum variant from its debug representation, stopping at the first
+/// character that isn't part of it.
+struct VariantName(String);
+
+impl Write for VariantName {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            if !c.is_alphanumeric() {
+                return Err(core::fmt::Error);
+            }
+            if c.is_uppercase() && !self.0.is_empty() {
+                self.0.push('_');
+            }
+            self.0.extend(c.to_lowercase());
+        }
+        Ok(())
+    }
+}
+
+fn variant_name<T: core::fmt::Debug>(value: &T) -> String {
+    let mut name = VariantName(String::new());
+    // The formatting is interrupted after the name of the variant.
+    let _ = write!(name, "{value:?}");
+    name.0
+}
+
+/// The name of the operation, prefixed by the kind of its tensors, e.g. `float.matmul`.
+fn operation_name(op: &OperationIr) -> Option<String> {
+    let (kind, name) = match op {
+        OperationIr::BaseFloat(op) => ("float", variant_name(op)),
+        OperationIr::BaseInt(op) => ("int", variant_name(op)),
+        OperationIr::BaseBool(op) => ("bool", variant_name(op)),
+        OperationIr::NumericFloat(_, op) => ("float", variant_name(op)),
+        OperationIr::NumericInt(_, op) => ("int", variant_name(op)),
+        OperationIr::Bool(op) => ("bool", variant_name(op)),
+        OperationIr::Int(op) => ("int", variant_name(op)),
+        OperationIr::Float(_, op) => ("float", variant_name(op)),
+        OperationIr::Module(op) => ("module", variant_name(op)),
+        OperationIr::Custom(op) => ("custom", op.id.clone()),
+        OperationIr::Init(_) | OperationIr::Drop(_) => return None,
+    };
+
+    Some(format!("{kind}.{name}"))
+}
+
+/// The operations recorded by a [profiler](Profiler).
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    /// The recorded operations, in the order they completed.
+    pub events: Vec<OperationEvent>,
+}
+
+impl Profile {
+    /// Export the operations in the Chrome trace event format, which can be opened with
+    /// `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+    pub fn chrome_trace(&self) -> String {
+        let mut trace = String::from("{\"traceEvents\":[");
+
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                trace.push(',');
+            }
+            let shapes = event
+                .shapes
+                .iter()
+                .map(|shape| format!("{shape:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let dtypes = event
+                .dtypes
+                .iter()
+                .map(|dtype| dtype.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let _ = write!(
+                trace,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"pid\":0,\"tid\":{},\"ts\":{},\"dur\":{},\
+                \"args\":{{\"scope\":\"{}\",\"shapes\":\"{}\",\"dtypes\":\"{}\",\"output_bytes\":{},\"wall_us\":{}}}}}",
+                escape(&event.name),
+                event.name.split('.').next().unwrap_or_default(),
+                event.thread,
+                micros(event.start),
+                micros(event.time()),
+                escape(&event.scope),
+                shapes,
+                dtypes,
+                event.output_bytes,
+                micros(event.wall_time),
+            );
+        }
+
+        trace.push_str("]}");
+        trace
+    }
+
+    /// Summarize the operations by name, from the most time consuming.
+    pub fn summary(&self) -> ProfileSummary {
+        self.summarize("Operation", |event| event.name.clone())
+    }
+
+    /// Summarize the operations by the scope they were executed in, from the most time
+    /// consuming.
+    pub fn scope_summary(&self) -> ProfileSummary {
+        self.summarize("Scope", |event| event.scope.clone())
+    }
+
+    fn summarize<F: Fn(&OperationEvent) -> String>(&self, title: &str, key: F) -> ProfileSummary {
+        let mut rows = HashMap::<String, ProfileSummaryRow>::new();
+
+        for event in self.events.iter() {
+            let name = key(event);
+            let row = rows
+                .entry(name.clone())
+                .or_insert_with(|| ProfileSummaryRow {
+                    name,
+                    count: 0,
+                    total: Duration::ZERO,
+                    max: Duration::ZERO,
+                    output_bytes: 0,
+                });
+            row.count += 1;
+            row.total += event.time();
+            row.max = row.max.max(event.time());
+            row.output_bytes += event.output_bytes;
+        }
+
+        let mut rows = rows.into_values().collect::<Vec<_>>();
+        rows.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+
+        ProfileSummary {
+            title: String::from(title),
+            rows,
+        }
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1000.0
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The operations of a [profile](Profile) aggregated by name or by scope.
+#[derive(Clone, Debug)]
+pub struct ProfileSummary {
+    title: String,
+    /// The aggregated operations, from the most time consuming.
+    pub rows: Vec<ProfileSummaryRow>,
+}
+
+/// The aggregate of the operations with the same name or scope.
+#[derive(Clone, Debug)]
+pub struct ProfileSummaryRow {
+    /// The name or the scope of the operations.
+    pub name: String,
+    /// The number of operations.
+    pub count: usize,
+    /// The total time of the operations.
+    pub total: Duration,
+    /// The longest time of an operation.
+    pub max: Duration,
+    /// The total number of bytes of the output tensors.
+    pub output_bytes: usize,
+}
+
+impl ProfileSummaryRow {
+    /// The mean time of the operations.
+    pub fn mean(&self) -> Duration {
+        self.total / self.count as u32
+    }
+}
+
+impl Display for ProfileSummary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let total = self.rows.iter().map(|row| row.total).sum::<Duration>();
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.name.len())
+            .chain([self.title.len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "| {:<width$} | {:>8} | {:>12} | {:>12} | {:>12} | {:>7} | {:>12} |",
+            self.title, "Count", "Total", "Mean", "Max", "%", "Output bytes"
+        )?;
+        writeln!(
+            f,
+            "|{}|{}|{}|{}|{}|{}|{}|",
+            "-".repeat(width + 2),
+            "-".repeat(10),
+            "-".repeat(14),
+            "-".repeat(14),
+            "-".repeat(14),
+            "-".repeat(9),
+            "-".repeat(14),
+        )?;
+
+        for row in self.rows.iter() {
+            let percent = match total.is_zero() {
+                true => 0.0,
+                false => row.total.as_secs_f64() / total.as_secs_f64() * 100.0,
+            };
+            writeln!(
+                f,
+                "| {:<width$} | {:>8} | {:>12} | {:>12} | {:>12} | {:>6.2}% | {:>12} |",
+                row.name,
+                row.count,
+                format!("{:.3?}", row.total),
+                format!("{:.3?}", row.mean()),
+                format!("{:.3?}", row.max),
+                percent,
+                row.output_bytes,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::TestTensor;
+    use burn_common::scope::ForwardScope;
+
+    fn event(name: &str, scope: &str, micros: u64) -> OperationEvent {
+        OperationEvent {
+            name: name.into(),
+            shapes: alloc::vec![alloc::vec![2, 3]],
+            dtypes: alloc::vec![DType::F32],
+            scope: scope.into(),
+            thread: 0,
+            start: Duration::ZERO,
+            wall_time: Duration::from_micros(micros),
+            device_time: None,
+            output_bytes: 24,
+        }
+    }
+
+    #[test]
+    fn test_profile_summary() {
+        let profile = Profile {
+            events: alloc::vec![
+                event("float.add", "encoder", 10),
+                event("float.matmul", "encoder", 50),
+                event("float.add", "decoder", 30),
+            ],
+        };
+
+        let summary = profile.summary();
+        assert_eq!(summary.rows.len(), 2);
+        assert_eq!(summary.rows[0].name, "float.matmul");
+        assert_eq!(summary.rows[1].count, 2);
+        assert_eq!(summary.rows[1].total, Duration::from_micros(40));
+        assert_eq!(summary.rows[1].output_bytes, 48);
+
+        let summary = profile.scope_summary();
+        assert_eq!(summary.rows[0].name, "encoder");
+        assert_eq!(summary.rows[0].total, Duration::from_micros(60));
+
+        let trace = profile.chrome_trace();
+        assert!(trace.starts_with("{\"traceEvents\":[{\"name\":\"float.add\",\"cat\":\"float\""));
+        assert!(trace.contains("\"scope\":\"decoder\",\"shapes\":\"[2, 3]\",\"dtypes\":\"f32\""));
+    }
+
+    #[test]
+    fn test_profiler_records_operations() {
+        let profiler = Profiler::new().with_sync(true).start();
+        let lhs = TestTensor::<2>::from_floats([[1.0, 2.0], [3.0, 4.0]], &Default::default());
+        let output = {
+            let _scope = ForwardScope::enter("linear");
+            lhs.clone().matmul(lhs)
+        };
+        output.into_data();
+        let profile = profiler.finish();
+
+        let matmul = profile
+            .events
+            .iter()
+            .find(|event| event.name == "float.matmul" && event.scope == "linear")
+            .expect("The matmul should be recorded");
+        assert_eq!(
+            matmul.shapes,
+            alloc::vec![alloc::vec![2, 2], alloc::vec![2, 2]]
+        );
+        assert_eq!(matmul.output_bytes, 16);
+        assert!(matmul.device_time.is_some());
+    }
+}
@@ -141,13 +141,9 @@ impl<B: BackendIr> Runner<B> {
     ) -> TensorIr {
         self.register_empty_tensor_desc(shape, dtype.into())
     }
-}
-
-impl<B: BackendIr> RunnerClient for Runner<B> {
-    type Device = B::Device;
 
     /// Execute a tensor operation.
-    fn register(&self, op: OperationIr) {
+    fn execute(&self, op: OperationIr) {
         // Remove unused tensor handles
         let mut ctx = self.context.lock().unwrap();
 
@@ -1229,6 +1225,22 @@ impl<B: BackendIr> RunnerClient for Runner<B> {
             }
         }
     }
+}
+
+impl<B: BackendIr> RunnerClient for Runner<B> {
+    type Device = B::Device;
+
+    /// Execute a tensor operation.
+    fn register(&self, op: OperationIr) {
+        #[cfg(feature = "std")]
+        if let Some(record) = crate::OperationRecord::start(&op) {
+            self.execute(op);
+            record.finish(|| self.sync());
+            return;
+        }
+
+        self.execute(op);
+    }
 
     fn read_tensor(&self, tensor: TensorIr) -> DynFut<TensorData> {
         let mut ctx = self.context.lock().unwrap();
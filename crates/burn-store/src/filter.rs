@@ -313,6 +313,12 @@ impl fmt::Display for PathFilter {
     }
 }
 
+impl burn_core::optim::ParamPathFilter for PathFilter {
+    fn matches(&self, path: &str, container_path: &str) -> bool {
+        self.matches_with_container_path_str(path, container_path)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -620,4 +626,19 @@ mod tests {
         );
         assert!(!combined.matches_with_container_path_str("layer.weight", "Model.Decoder.Linear"));
     }
+
+    #[test]
+    fn param_path_filter() {
+        use burn_core::optim::ParamPathFilter;
+
+        let filter: &dyn ParamPathFilter = &PathFilter::new()
+            .with_full_path("head.bias")
+            .with_predicate(|_path, container_path| {
+                container_path.split('.').next_back() == Some("LayerNorm")
+            });
+
+        assert!(filter.matches("head.bias", "Model.Linear"));
+        assert!(filter.matches("norm.gamma", "Model.LayerNorm"));
+        assert!(!filter.matches("head.weight", "Model.Linear"));
+    }
 }
@@ -0,0 +1,87 @@
+use crate::{ModuleSnapshot, SafetensorsStore};
+use burn_core::module::{GlobFilter, Module};
+use burn_core::nn::lora::{LoraConfig, LoraLinear, LoraMapper, LoraModule, is_adapter_tensor};
+use burn_core::nn::{Linear, LinearConfig};
+use burn_core::optim::ParamPathFilter;
+use burn_tensor::backend::Backend;
+
+type TestBackend = burn_ndarray::NdArray;
+
+#[derive(Module, Debug)]
+struct Model<B: Backend> {
+    projection: Linear<B>,
+    layers: Vec<Linear<B>>,
+}
+
+#[derive(Module, Debug)]
+struct LoraModel<B: Backend> {
+    projection: LoraLinear<B>,
+    layers: Vec<LoraLinear<B>>,
+}
+
+impl<B: Backend> Model<B> {
+    fn new(device: &B::Device) -> Self {
+        Self {
+            projection: LinearConfig::new(4, 2).init(device),
+            layers: vec![
+                LinearConfig::new(3, 4).with_bias(false).init(device),
+                LinearConfig::new(4, 3).init(device),
+            ],
+        }
+    }
+}
+
+impl<B: Backend> LoraModule<B> for Model<B> {
+    type Adapted = LoraModel<B>;
+
+    fn map_lora<F: ParamPathFilter + ?Sized>(self, mapper: &mut LoraMapper<F>) -> Self::Adapted {
+        LoraModel {
+            projection: mapper.map("projection", "Model", self.projection),
+            layers: mapper.map("layers", "Model", self.layers),
+        }
+    }
+}
+
+#[test]
+fn lora_adapters_only_round_trip() {
+    let device = Default::default();
+    let config = LoraConfig::new(2, 4.0);
+    let filter = GlobFilter::new(["projection", "layers.*"]);
+    let module1 = config.inject(Model::<TestBackend>::new(&device), &filter);
+    let mut module2 = config.inject(Model::<TestBackend>::new(&device), &filter);
+
+    // Save the adapters only
+    let mut save_store = SafetensorsStore::from_bytes(None).with_predicate(is_adapter_tensor);
+    module1.collect_to(&mut save_store).unwrap();
+
+    let mut load_store = SafetensorsStore::from_bytes(None).allow_partial(true);
+    if let SafetensorsStore::Memory(ref mut p) = load_store {
+        if let SafetensorsStore::Memory(ref p_save) = save_store {
+            let data = p_save.data().unwrap();
+            let tensors = safetensors::SafeTensors::deserialize(&data).unwrap();
+            assert_eq!(tensors.len(), 6); // lora_a and lora_b of the 3 linear layers
+
+            for (name, _) in tensors.tensors() {
+                assert!(name.ends_with(".adapter.lora_a") || name.ends_with(".adapter.lora_b"));
+            }
+            p.set_data(data.as_ref().clone());
+        }
+    }
+    let result = module2.apply_from(&mut load_store).unwrap();
+
+    assert!(result.is_success());
+    assert_eq!(result.applied.len(), 6);
+
+    let adapter1 = module1.projection.adapter.as_ref().unwrap();
+    let adapter2 = module2.projection.adapter.as_ref().unwrap();
+    adapter2
+        .lora_a
+        .val()
+        .into_data()
+        .assert_eq(&adapter1.lora_a.val().into_data(), true);
+
+    // The base weights are not part of the adapters
+    let weight1 = module1.projection.base.weight.val().into_data();
+    let weight2 = module2.projection.base.weight.val().into_data();
+    assert_ne!(weight1, weight2);
+}
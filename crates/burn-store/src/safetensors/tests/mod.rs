@@ -3,6 +3,7 @@ mod error_handling;
 mod file_io;
 mod filtering;
 mod integration;
+mod lora;
 mod metadata;
 mod mixed_datatypes;
 mod pytorch_import;
@@ -54,6 +54,35 @@ impl<const D: usize, B: AutodiffBackend> Tensor<B, D> {
     }
 }
 
+impl<const D: usize, B> Tensor<B, D>
+where
+    B: AutodiffBackend,
+    B::InnerBackend: AutodiffBackend,
+{
+    /// Mark the tensor as requiring gradients on both levels of a nested autodiff backend, e.g.
+    /// `Autodiff<Autodiff<B>>`.
+    ///
+    /// The backward pass of the outer level is then recorded by the inner level, so the
+    /// gradients returned by [grad](Tensor::grad) are tracked tensors of the inner backend that
+    /// can be differentiated again, as with `create_graph=True` in PyTorch.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// type B = Autodiff<Autodiff<NdArray>>;
+    ///
+    /// let x = Tensor::<B, 1>::from_floats([1.0, 2.0], &device).require_nested_grad();
+    /// let grads = x.clone().powf_scalar(3.0).sum().backward();
+    /// let dx = x.grad(&grads).unwrap(); // 3x^2
+    ///
+    /// let grads = dx.sum().backward();
+    /// let dx2 = x.inner().grad(&grads).unwrap(); // 6x
+    /// ```
+    pub fn require_nested_grad(self) -> Self {
+        Self::from_inner(self.inner().require_grad()).require_grad()
+    }
+}
+
 impl<const D: usize, B: AutodiffBackend, K: BasicAutodiffOps<B>> Tensor<B, D, K> {
     /// Returns the inner tensor without the autodiff information.
     pub fn inner(self) -> Tensor<B::InnerBackend, D, K::InnerKind> {
@@ -45,6 +45,9 @@ pub mod ops;
 /// Tensor quantization module.
 pub mod quantization;
 
+/// The scan module.
+pub mod scan;
+
 #[cfg(feature = "std")]
 pub use report::*;
 
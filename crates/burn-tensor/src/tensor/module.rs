@@ -19,6 +19,28 @@ where
     )))
 }
 
+/// Computes the [sparse gradient](crate::ops::ModuleOps::embedding_backward_sparse) of the
+/// embedding weights.
+///
+/// Returns the looked up `indices` of shape `[batch_size * seq_length]` with their gradient of
+/// shape `[batch_size * seq_length, d_model]`. Indices can be repeated, in which case their
+/// gradients must be accumulated, e.g. with [select_assign](Tensor::select_assign).
+pub fn embedding_backward_sparse<B>(
+    output_grad: Tensor<B, 3>,
+    indices: Tensor<B, 2, Int>,
+) -> (Tensor<B, 1, Int>, Tensor<B, 2>)
+where
+    B: Backend,
+{
+    let (indices, values) =
+        B::embedding_backward_sparse(output_grad.primitive.tensor(), indices.primitive);
+
+    (
+        Tensor::new(indices),
+        Tensor::new(TensorPrimitive::Float(values)),
+    )
+}
+
 /// Applies a [1D convolution](crate::ops::ModuleOps::conv2d).
 pub fn conv1d<B>(
     x: Tensor<B, 3>,
@@ -296,17 +296,42 @@ pub trait ModuleOps<B: Backend> {
         output_grad: FloatTensor<B>,
         indices: IntTensor<B>,
     ) -> FloatTensor<B> {
-        let [batch_size, seq_length] = indices.shape().dims();
         let [n_embeddings, d_model] = weights.shape().dims();
         let device = B::float_device(&weights);
         let dtype = output_grad.dtype();
 
-        let indices = B::int_reshape(indices, Shape::new([batch_size * seq_length]));
-        let output_grad =
-            B::float_reshape(output_grad, Shape::new([batch_size * seq_length, d_model]));
+        let (indices, values) = B::embedding_backward_sparse(output_grad, indices);
         let grad = B::float_zeros(Shape::new([n_embeddings, d_model]), &device, dtype.into());
 
-        B::float_select_assign(grad, 0, indices, output_grad)
+        B::float_select_assign(grad, 0, indices, values)
+    }
+
+    /// Sparse embedding backward operation.
+    ///
+    /// Only computes the gradient of the rows that were looked up, so the cost doesn't depend on
+    /// the number of embeddings in the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_grad` - The output gradient of shape `[batch_size, seq_length, d_model]`.
+    /// * `indices` - The indices tensor of shape `[batch_size, seq_length]`.
+    ///
+    /// # Returns
+    ///
+    /// The gradient in coordinate format `(indices, values)`, where `values` of shape
+    /// `[batch_size * seq_length, d_model]` holds the gradient of the row `indices[i]` at
+    /// position `i`. Indices can be repeated, in which case their values must be accumulated.
+    fn embedding_backward_sparse(
+        output_grad: FloatTensor<B>,
+        indices: IntTensor<B>,
+    ) -> (IntTensor<B>, FloatTensor<B>) {
+        let [batch_size, seq_length] = indices.shape().dims();
+        let [_, _, d_model] = output_grad.shape().dims();
+
+        let indices = B::int_reshape(indices, Shape::new([batch_size * seq_length]));
+        let values = B::float_reshape(output_grad, Shape::new([batch_size * seq_length, d_model]));
+
+        (indices, values)
     }
     /// One dimensional convolution.
     ///
@@ -0,0 +1,59 @@
+use crate::{ElementConversion, TensorMetadata, backend::Backend, ops::FloatTensor};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Default implementation of [float_linear_scan](crate::ops::FloatTensorOps::float_linear_scan).
+///
+/// The recurrence is evaluated with a Hillis-Steele scan over the pairs `(a, b)`, using the
+/// associative operator `(a_1, b_1) ∘ (a_2, b_2) = (a_1 * a_2, a_2 * b_1 + b_2)`. For a sequence
+/// of length `L`, it takes `ceil(log2(L))` steps of element-wise operations over the whole
+/// tensor, which amounts to `O(L log L)` work and allocates intermediate tensors at every step.
+pub fn float_linear_scan_hillis_steele<B: Backend>(
+    a: FloatTensor<B>,
+    b: FloatTensor<B>,
+    dim: usize,
+) -> FloatTensor<B> {
+    let length = a.shape().dims[dim];
+    let mut a = a;
+    let mut b = b;
+    let mut offset = 1;
+
+    while offset < length {
+        // Combine each element with the prefix ending `offset` positions before it; the first
+        // `offset` elements are combined with the identity `(1, 0)`.
+        let a_prev = shift::<B>(a.clone(), dim, offset, 1.0);
+        let b_prev = shift::<B>(b.clone(), dim, offset, 0.0);
+
+        b = B::float_add(b, B::float_mul(a.clone(), b_prev));
+        a = B::float_mul(a, a_prev);
+        offset *= 2;
+    }
+
+    b
+}
+
+/// Shifts the tensor by `offset` positions along `dim`, filling the first positions with `value`.
+fn shift<B: Backend>(
+    tensor: FloatTensor<B>,
+    dim: usize,
+    offset: usize,
+    value: f32,
+) -> FloatTensor<B> {
+    let shape = tensor.shape();
+    let length = shape.dims[dim];
+    let mut fill_shape = shape.clone();
+    fill_shape.dims[dim] = offset;
+
+    let mut ranges: Vec<_> = shape.dims.iter().map(|&size| 0..size).collect();
+    ranges[dim] = 0..length - offset;
+
+    let fill = B::float_full(
+        fill_shape,
+        value.elem(),
+        &B::float_device(&tensor),
+        tensor.dtype().into(),
+    );
+    let shifted = B::float_slice(tensor, &ranges);
+
+    B::float_cat(vec![fill, shifted], dim)
+}
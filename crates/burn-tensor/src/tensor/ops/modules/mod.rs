@@ -14,6 +14,9 @@ pub mod pool;
 /// Module for grid_sample operations
 pub mod grid_sample;
 
+/// Module for linear_scan operations
+pub mod linear_scan;
+
 mod base;
 
 pub use base::*;
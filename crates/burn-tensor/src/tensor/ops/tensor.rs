@@ -1,5 +1,6 @@
 use super::cat::cat_with_slice_assign;
 use super::grid_sample::float_grid_sample_2d_bilinear;
+use super::linear_scan::float_linear_scan_hillis_steele;
 use super::repeat_dim::repeat_with_slice_assign;
 use super::{BoolTensor, Device, FloatElem, FloatTensor, IntElem, IntTensor};
 use crate::ops::InterpolateMode;
@@ -1385,4 +1386,26 @@ pub trait FloatTensorOps<B: Backend> {
             _ => todo!("Default implementation for grid_sample_2d with {method:?} unimplemented"),
         }
     }
+
+    /// Computes the first-order linear recurrence `h[t] = a[t] * h[t - 1] + b[t]` along a
+    /// dimension, with `h[-1] = 0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The multiplicative coefficients.
+    /// * `b` - The additive inputs, with the same shape as `a`.
+    /// * `dim` - The dimension to scan over.
+    ///
+    /// # Returns
+    ///
+    /// The hidden states `h`, with the same shape as the inputs.
+    ///
+    /// # Notes
+    ///
+    /// The default implementation is a [Hillis-Steele scan](float_linear_scan_hillis_steele)
+    /// composed of other tensor operations, with `O(L log L)` work for a sequence of length `L`.
+    /// Backends should override it with a kernel doing `O(L)` work.
+    fn float_linear_scan(a: FloatTensor<B>, b: FloatTensor<B>, dim: usize) -> FloatTensor<B> {
+        float_linear_scan_hillis_steele::<B>(a, b, dim)
+    }
 }
@@ -0,0 +1,57 @@
+use crate::backend::Backend;
+use crate::tensor::{Tensor, TensorPrimitive};
+
+/// Computes the first-order linear recurrence `h[t] = a[t] * h[t - 1] + b[t]` along a dimension,
+/// with `h[-1] = 0`.
+///
+/// The scan is a backend operation: the CPU and CubeCL backends run one sequential pass per
+/// sequence, which amounts to `O(L)` work for a sequence of length `L`, in a single kernel. The
+/// other backends use a [Hillis-Steele scan](crate::ops::linear_scan::float_linear_scan_hillis_steele)
+/// composed of other tensor operations. The gradients are computed with a scan in the reverse
+/// direction.
+///
+/// This is the building block of linear recurrent networks and state-space models.
+///
+/// # Arguments
+///
+/// * `a` - The multiplicative coefficients (e.g. the discretized state transition).
+/// * `b` - The additive inputs, must have the same shape as `a`.
+/// * `dim` - The dimension to scan over (e.g. the sequence dimension).
+///
+/// # Returns
+///
+/// The hidden states `h`, with the same shape as the inputs.
+///
+/// # Example
+///
+/// ```rust
+/// use burn_tensor::backend::Backend;
+/// use burn_tensor::{Tensor, scan};
+///
+/// fn example<B: Backend>() {
+///     let device = B::Device::default();
+///     let a = Tensor::<B, 2>::from_floats([[0.5, 0.5, 0.5]], &device);
+///     let b = Tensor::<B, 2>::from_floats([[1.0, 2.0, 3.0]], &device);
+///     let h = scan::linear_scan(a, b, 1);
+///     println!("{h}");
+///     // [[1.0, 2.5, 4.25]]
+/// }
+/// ```
+pub fn linear_scan<B: Backend, const D: usize>(
+    a: Tensor<B, D>,
+    b: Tensor<B, D>,
+    dim: usize,
+) -> Tensor<B, D> {
+    assert_eq!(
+        a.dims(),
+        b.dims(),
+        "The coefficients and inputs of a linear scan must have the same shape"
+    );
+    assert!(dim < D, "The scan dimension {dim} is out of bounds");
+
+    Tensor::new(TensorPrimitive::Float(B::float_linear_scan(
+        a.primitive.tensor(),
+        b.primitive.tensor(),
+        dim,
+    )))
+}
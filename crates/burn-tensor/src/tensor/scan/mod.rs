@@ -0,0 +1,3 @@
+mod linear_scan;
+
+pub use linear_scan::*;
@@ -8,6 +8,7 @@ mod multi_threads;
 mod ops;
 mod primitive;
 mod quantization;
+mod scan;
 mod stats;
 
 pub use cubecl::prelude::{Float, Int, Numeric};
@@ -166,6 +167,9 @@ macro_rules! testgen_with_float_param {
         burn_tensor::testgen_vector_norm!();
         burn_tensor::testgen_cosine_similarity!();
 
+        // test scan
+        burn_tensor::testgen_linear_scan!();
+
         // test module
         burn_tensor::testgen_module_conv1d!();
         burn_tensor::testgen_module_conv2d!();
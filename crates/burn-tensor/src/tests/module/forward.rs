@@ -1,7 +1,11 @@
 #[burn_tensor_testgen::testgen(module_forward)]
 mod tests {
     use super::*;
-    use burn_tensor::{Int, Tensor, TensorData, backend::Backend, module::embedding};
+    use burn_tensor::{
+        Int, Tensor, TensorData,
+        backend::Backend,
+        module::{embedding, embedding_backward_sparse},
+    };
 
     #[test]
     fn test_embedding_forward() {
@@ -18,4 +22,21 @@ mod tests {
 
         output.into_data().assert_eq(&expected, false);
     }
+
+    #[test]
+    fn test_embedding_backward_sparse() {
+        let output_grad =
+            TestTensor::<3>::from([[[1.0, 1.0], [2.0, 2.0]], [[3.0, 3.0], [4.0, 4.0]]]);
+        let indices = TestTensorInt::<2>::from([[0, 5], [5, 1]]);
+
+        let (indices, values) = embedding_backward_sparse(output_grad, indices);
+
+        indices
+            .into_data()
+            .assert_eq(&TensorData::from([0, 5, 5, 1]), false);
+        values.into_data().assert_eq(
+            &TensorData::from([[1.0, 1.0], [2.0, 2.0], [3.0, 3.0], [4.0, 4.0]]),
+            false,
+        );
+    }
 }
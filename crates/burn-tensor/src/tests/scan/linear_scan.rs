@@ -0,0 +1,96 @@
+#[burn_tensor_testgen::testgen(linear_scan)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use burn_tensor::ops::linear_scan::float_linear_scan_hillis_steele;
+    use burn_tensor::{TensorPrimitive, scan};
+    use burn_tensor::{Tolerance, ops::FloatElem};
+    type FT = FloatElem<TestBackend>;
+
+    #[test]
+    fn test_linear_scan_1d() {
+        let a = TestTensor::<1>::from([0.5, 0.5, 0.5]);
+        let b = TestTensor::<1>::from([1.0, 2.0, 3.0]);
+
+        scan::linear_scan(a, b, 0)
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &TestTensor::<1>::from([1.0, 2.5, 4.25]).into_data(),
+                Tolerance::default(),
+            );
+    }
+
+    #[test]
+    fn test_linear_scan_should_match_sequential_recurrence() {
+        // Non power of two length to cover the padding of the last scan step.
+        let a = TestTensor::<3>::from([
+            [[0.9, 0.1], [0.5, -1.0], [0.2, 0.3], [1.0, 0.7], [-0.4, 0.6]],
+            [[0.0, 1.0], [0.8, 0.8], [0.3, -0.2], [0.6, 0.1], [0.5, 0.9]],
+        ]);
+        let b = TestTensor::<3>::from([
+            [
+                [1.0, -1.0],
+                [0.5, 2.0],
+                [-0.3, 0.1],
+                [0.2, 0.4],
+                [1.5, -0.5],
+            ],
+            [[0.7, 0.3], [-1.2, 0.6], [0.4, 0.4], [0.9, -0.8], [0.1, 0.2]],
+        ]);
+
+        let mut states = Vec::new();
+        let mut h = TestTensor::<3>::zeros([2, 1, 2], &Default::default());
+        for t in 0..5 {
+            let a_t = a.clone().narrow(1, t, 1);
+            let b_t = b.clone().narrow(1, t, 1);
+            h = a_t * h + b_t;
+            states.push(h.clone());
+        }
+        let expected = TestTensor::cat(states, 1).into_data();
+
+        scan::linear_scan(a, b, 1)
+            .into_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+
+    #[test]
+    fn test_linear_scan_single_step() {
+        let a = TestTensor::<2>::from([[0.5], [2.0]]);
+        let b = TestTensor::<2>::from([[1.0], [3.0]]);
+
+        scan::linear_scan(a, b.clone(), 1)
+            .into_data()
+            .assert_eq(&b.into_data(), true);
+    }
+
+    #[test]
+    fn test_linear_scan_first_dim_of_transposed_tensor() {
+        // Scans a non-contiguous tensor along a dimension that isn't the last one.
+        let a = TestTensor::<2>::from([[0.5, 0.1, 2.0], [1.0, 0.2, -1.0]]).transpose();
+        let b = TestTensor::<2>::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).transpose();
+
+        scan::linear_scan(a, b, 0)
+            .into_data()
+            .assert_approx_eq::<FT>(
+                &TestTensor::<2>::from([[1.0, 4.0], [2.1, 5.8], [7.2, 0.2]]).into_data(),
+                Tolerance::default(),
+            );
+    }
+
+    #[test]
+    fn test_linear_scan_default_implementation() {
+        let a = TestTensor::<2>::from([[0.9, 0.1, 0.5, -1.0, 0.2], [1.0, 0.7, -0.4, 0.6, 0.3]]);
+        let b = TestTensor::<2>::from([[1.0, -1.0, 0.5, 2.0, -0.3], [0.2, 0.4, 1.5, -0.5, 0.7]]);
+        let expected = scan::linear_scan(a.clone(), b.clone(), 1).into_data();
+
+        let output = float_linear_scan_hillis_steele::<TestBackend>(
+            a.into_primitive().tensor(),
+            b.into_primitive().tensor(),
+            1,
+        );
+
+        TestTensor::<2>::from_primitive(TensorPrimitive::Float(output))
+            .into_data()
+            .assert_approx_eq::<FT>(&expected, Tolerance::default());
+    }
+}
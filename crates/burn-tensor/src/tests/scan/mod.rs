@@ -0,0 +1 @@
+pub(crate) mod linear_scan;
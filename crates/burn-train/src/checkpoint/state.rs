@@ -0,0 +1,54 @@
+use burn_core::amp::GradScalerState;
+use burn_core::data::dataloader::DataLoaderState;
+use burn_core::record::{PrecisionSettings, Record};
+use burn_core::tensor::backend::Backend;
+use serde::{Deserialize, Serialize};
+
+/// The progress of the training saved with each checkpoint, used to resume the training where
+/// the checkpoint was taken.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrainingState {
+    /// The epoch of the checkpoint.
+    pub epoch: usize,
+    /// The number of iterations done in the epoch, 0 for epoch checkpoints.
+    pub iteration: usize,
+    /// The number of iterations done since the start of the training.
+    pub total_iterations: usize,
+    /// The state of the training data loader, for checkpoints taken in the middle of an epoch.
+    pub dataloader: Option<DataLoaderState>,
+    /// The seed of the backend random number generator, set when the checkpoint was taken.
+    ///
+    /// The state of the generator can't be saved, so it is seeded again with a new seed drawn
+    /// from it, which makes the random numbers after resuming the same as the ones of the
+    /// interrupted training.
+    pub seed: Option<u64>,
+    /// The state of the gradient scaler, when training with mixed precision.
+    #[serde(default)]
+    pub grad_scaler: Option<GradScalerState>,
+}
+
+impl TrainingState {
+    /// The state at the end of the given epoch.
+    pub fn epoch(epoch: usize, total_iterations: usize) -> Self {
+        Self {
+            epoch,
+            iteration: 0,
+            total_iterations,
+            dataloader: None,
+            seed: None,
+            grad_scaler: None,
+        }
+    }
+}
+
+impl<B: Backend> Record<B> for TrainingState {
+    type Item<S: PrecisionSettings> = Self;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        self
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, _device: &B::Device) -> Self {
+        item
+    }
+}
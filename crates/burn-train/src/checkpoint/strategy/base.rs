@@ -1,11 +1,13 @@
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 
+use crate::Checkpoint;
 use crate::metric::store::EventStoreClient;
 
 /// Action to be taken by a [checkpointer](crate::checkpoint::Checkpointer).
 #[derive(Clone, PartialEq, Debug)]
 pub enum CheckpointingAction {
-    /// Delete the given epoch.
+    /// Delete the given epoch, or the given iteration for
+    /// [iteration checkpoints](CheckpointingStrategy::checkpointing_iteration).
     Delete(usize),
     /// Save the current record.
     Save,
@@ -19,6 +21,34 @@ pub trait CheckpointingStrategy: Send {
         epoch: usize,
         collector: &EventStoreClient,
     ) -> Vec<CheckpointingAction>;
+
+    /// Based on the number of iterations since the start of the training, determine if a
+    /// checkpoint of the current iteration should be saved, which allows resuming the training in
+    /// the middle of an epoch.
+    ///
+    /// No iteration checkpoint is saved by default.
+    fn checkpointing_iteration(
+        &mut self,
+        _iteration: usize,
+        _collector: &EventStoreClient,
+    ) -> Vec<CheckpointingAction> {
+        Vec::new()
+    }
+
+    /// Whether the strategy may [checkpoint iterations](Self::checkpointing_iteration), which is
+    /// only supported by some learning strategies.
+    ///
+    /// Should be overridden by the strategies checkpointing iterations, `false` by default.
+    fn checkpoints_iterations(&self) -> bool {
+        false
+    }
+
+    /// Restore the state of the strategy when the training is resumed from the given checkpoint,
+    /// after `total_iterations` iterations since the start of the training, e.g. to delete the
+    /// checkpoints saved before resuming.
+    ///
+    /// Nothing is restored by default.
+    fn resume(&mut self, _checkpoint: Checkpoint, _total_iterations: usize) {}
 }
 
 // We make dyn box implement the checkpointing strategy so that it can be used with generic, but
@@ -31,4 +61,21 @@ impl CheckpointingStrategy for Box<dyn CheckpointingStrategy> {
     ) -> Vec<CheckpointingAction> {
         self.deref_mut().checkpointing(epoch, collector)
     }
+
+    fn checkpointing_iteration(
+        &mut self,
+        iteration: usize,
+        collector: &EventStoreClient,
+    ) -> Vec<CheckpointingAction> {
+        self.deref_mut()
+            .checkpointing_iteration(iteration, collector)
+    }
+
+    fn checkpoints_iterations(&self) -> bool {
+        self.deref().checkpoints_iterations()
+    }
+
+    fn resume(&mut self, checkpoint: Checkpoint, total_iterations: usize) {
+        self.deref_mut().resume(checkpoint, total_iterations)
+    }
 }
@@ -1,3 +1,4 @@
+use crate::Checkpoint;
 use crate::metric::store::EventStoreClient;
 
 use super::{CheckpointingAction, CheckpointingStrategy};
@@ -8,6 +9,7 @@ use std::collections::HashSet;
 pub struct ComposedCheckpointingStrategy {
     strategies: Vec<Box<dyn CheckpointingStrategy>>,
     deleted: Vec<HashSet<usize>>,
+    deleted_iterations: Vec<HashSet<usize>>,
 }
 
 /// Help building a [checkpointing strategy](CheckpointingStrategy) by combining multiple ones.
@@ -37,6 +39,7 @@ impl ComposedCheckpointingStrategy {
     fn new(strategies: Vec<Box<dyn CheckpointingStrategy>>) -> Self {
         Self {
             deleted: strategies.iter().map(|_| HashSet::new()).collect(),
+            deleted_iterations: strategies.iter().map(|_| HashSet::new()).collect(),
             strategies,
         }
     }
@@ -53,72 +56,94 @@ impl CheckpointingStrategy for ComposedCheckpointingStrategy {
         epoch: usize,
         collector: &EventStoreClient,
     ) -> Vec<CheckpointingAction> {
-        let mut saved = false;
-        let mut actions = Vec::new();
-        let mut epochs_to_check = Vec::new();
-
-        for (i, strategy) in self.strategies.iter_mut().enumerate() {
-            let actions = strategy.checkpointing(epoch, collector);
-            // We assume that the strategy would not want the current epoch to be saved.
-            // So we flag it as deleted.
-            if actions.is_empty() {
-                self.deleted
-                    .get_mut(i)
-                    .expect("As many 'deleted' as 'strategies'.")
-                    .insert(epoch);
-            }
+        let actions = self
+            .strategies
+            .iter_mut()
+            .map(|strategy| strategy.checkpointing(epoch, collector))
+            .collect();
 
-            for action in actions {
-                match action {
-                    CheckpointingAction::Delete(epoch) => {
-                        self.deleted
-                            .get_mut(i)
-                            .expect("As many 'deleted' as 'strategies'.")
-                            .insert(epoch);
-                        epochs_to_check.push(epoch);
-                    }
-                    CheckpointingAction::Save => saved = true,
-                }
-            }
+        compose(&mut self.deleted, actions, epoch)
+    }
+
+    fn checkpointing_iteration(
+        &mut self,
+        iteration: usize,
+        collector: &EventStoreClient,
+    ) -> Vec<CheckpointingAction> {
+        let actions = self
+            .strategies
+            .iter_mut()
+            .map(|strategy| strategy.checkpointing_iteration(iteration, collector))
+            .collect();
+
+        compose(&mut self.deleted_iterations, actions, iteration)
+    }
+
+    fn checkpoints_iterations(&self) -> bool {
+        self.strategies
+            .iter()
+            .any(|strategy| strategy.checkpoints_iterations())
+    }
+
+    fn resume(&mut self, checkpoint: Checkpoint, total_iterations: usize) {
+        for strategy in self.strategies.iter_mut() {
+            strategy.resume(checkpoint, total_iterations);
         }
+    }
+}
 
-        if saved {
-            actions.push(CheckpointingAction::Save);
+/// Combine the actions of each strategy for the given epoch or iteration, deleting a checkpoint
+/// only when every strategy flagged it.
+fn compose(
+    deleted: &mut [HashSet<usize>],
+    strategy_actions: Vec<Vec<CheckpointingAction>>,
+    current: usize,
+) -> Vec<CheckpointingAction> {
+    let saved = strategy_actions
+        .iter()
+        .any(|actions| actions.contains(&CheckpointingAction::Save));
+    let mut actions = Vec::new();
+    let mut to_check = Vec::new();
+
+    for (deleted, strategy_actions) in deleted.iter_mut().zip(strategy_actions) {
+        // We assume that the strategy would not want the current checkpoint to be saved.
+        // So we flag it as deleted.
+        if saved && strategy_actions.is_empty() {
+            deleted.insert(current);
         }
 
-        for epoch in epochs_to_check.into_iter() {
-            let mut num_true = 0;
-            for i in 0..self.strategies.len() {
-                if self
-                    .deleted
-                    .get(i)
-                    .expect("Ad many 'deleted' as 'strategies'.")
-                    .contains(&epoch)
-                {
-                    num_true += 1;
-                }
+        for action in strategy_actions {
+            if let CheckpointingAction::Delete(key) = action {
+                deleted.insert(key);
+                to_check.push(key);
             }
+        }
+    }
+
+    if saved {
+        actions.push(CheckpointingAction::Save);
+    }
 
-            if num_true == self.strategies.len() {
-                actions.push(CheckpointingAction::Delete(epoch));
+    for key in to_check.into_iter() {
+        if deleted.iter().all(|deleted| deleted.contains(&key)) {
+            actions.push(CheckpointingAction::Delete(key));
 
-                for i in 0..self.strategies.len() {
-                    self.deleted
-                        .get_mut(i)
-                        .expect("As many 'deleted' as 'strategies'.")
-                        .remove(&epoch);
-                }
+            for deleted in deleted.iter_mut() {
+                deleted.remove(&key);
             }
         }
-
-        actions
     }
+
+    actions
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{checkpoint::KeepLastNCheckpoints, metric::store::LogEventStore};
+    use crate::{
+        checkpoint::{IntervalCheckpointingStrategy, KeepLastNCheckpoints},
+        metric::store::LogEventStore,
+    };
 
     #[test]
     fn should_delete_when_both_deletes() {
@@ -143,4 +168,18 @@ mod tests {
             strategy.checkpointing(3, &store)
         );
     }
+
+    #[test]
+    fn should_checkpoint_iterations_when_any_strategy_does() {
+        let strategy = ComposedCheckpointingStrategy::builder()
+            .add(KeepLastNCheckpoints::new(1))
+            .build();
+        assert!(!strategy.checkpoints_iterations());
+
+        let strategy = ComposedCheckpointingStrategy::builder()
+            .add(KeepLastNCheckpoints::new(1))
+            .add(IntervalCheckpointingStrategy::every_iterations(100))
+            .build();
+        assert!(strategy.checkpoints_iterations());
+    }
 }
@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::CheckpointingStrategy;
+use crate::{Checkpoint, checkpoint::CheckpointingAction, metric::store::EventStoreClient};
+
+/// Save a checkpoint every N iterations or every given duration, keeping the last ones.
+///
+/// Useful for long trainings with slow epochs, where the training can be resumed in the middle of
+/// an epoch. It doesn't save any epoch checkpoint, so it is usually
+/// [composed](super::ComposedCheckpointingStrategy) with another strategy.
+pub struct IntervalCheckpointingStrategy {
+    interval: Interval,
+    num_keep: usize,
+    last_save: Instant,
+    saved: VecDeque<usize>,
+}
+
+enum Interval {
+    Iterations(usize),
+    Duration(Duration),
+}
+
+impl IntervalCheckpointingStrategy {
+    /// Save a checkpoint every `num_iterations` iterations.
+    ///
+    /// # Panics
+    ///
+    /// If the number of iterations is 0.
+    pub fn every_iterations(num_iterations: usize) -> Self {
+        assert!(
+            num_iterations > 0,
+            "The number of iterations between checkpoints should be positive"
+        );
+        Self::new(Interval::Iterations(num_iterations))
+    }
+
+    /// Save a checkpoint at the first iteration after each `duration`.
+    pub fn every_duration(duration: Duration) -> Self {
+        Self::new(Interval::Duration(duration))
+    }
+
+    /// Keep the last `num_keep` iteration checkpoints, 1 by default.
+    ///
+    /// # Panics
+    ///
+    /// If the number of checkpoints to keep is 0.
+    pub fn keep_last(mut self, num_keep: usize) -> Self {
+        assert!(num_keep > 0, "At least one checkpoint should be kept");
+        self.num_keep = num_keep;
+        self
+    }
+
+    fn new(interval: Interval) -> Self {
+        Self {
+            interval,
+            num_keep: 1,
+            last_save: Instant::now(),
+            saved: VecDeque::new(),
+        }
+    }
+
+    fn should_save(&self, iteration: usize) -> bool {
+        match self.interval {
+            Interval::Iterations(num_iterations) => iteration % num_iterations == 0,
+            Interval::Duration(duration) => self.last_save.elapsed() >= duration,
+        }
+    }
+}
+
+impl CheckpointingStrategy for IntervalCheckpointingStrategy {
+    fn checkpointing(
+        &mut self,
+        _epoch: usize,
+        _store: &EventStoreClient,
+    ) -> Vec<CheckpointingAction> {
+        Vec::new()
+    }
+
+    fn checkpointing_iteration(
+        &mut self,
+        iteration: usize,
+        _store: &EventStoreClient,
+    ) -> Vec<CheckpointingAction> {
+        if !self.should_save(iteration) {
+            return Vec::new();
+        }
+
+        self.last_save = Instant::now();
+        self.saved.push_back(iteration);
+
+        let mut actions = vec![CheckpointingAction::Save];
+
+        while self.saved.len() > self.num_keep {
+            let iteration = self.saved.pop_front().unwrap();
+            actions.push(CheckpointingAction::Delete(iteration));
+        }
+
+        actions
+    }
+
+    fn checkpoints_iterations(&self) -> bool {
+        true
+    }
+
+    /// Restore the iteration checkpoints to delete once newer ones are saved.
+    ///
+    /// With an interval of iterations, they are the last multiples of the interval before the
+    /// resumed iteration. With a duration, only the resumed iteration checkpoint is known, the
+    /// older ones are kept.
+    fn resume(&mut self, checkpoint: Checkpoint, total_iterations: usize) {
+        self.last_save = Instant::now();
+        self.saved = match self.interval {
+            Interval::Iterations(num_iterations) => {
+                let num_saved = total_iterations / num_iterations;
+                (num_saved.saturating_sub(self.num_keep) + 1..=num_saved)
+                    .map(|index| index * num_iterations)
+                    .collect()
+            }
+            Interval::Duration(_) => match checkpoint {
+                Checkpoint::Iteration(iteration) => VecDeque::from([iteration]),
+                Checkpoint::Epoch(_) => VecDeque::new(),
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::store::LogEventStore;
+
+    #[test]
+    fn should_save_every_n_iterations_and_keep_last() {
+        let mut strategy = IntervalCheckpointingStrategy::every_iterations(2).keep_last(2);
+        let store = EventStoreClient::new(LogEventStore::default());
+
+        assert!(strategy.checkpoints_iterations());
+        assert!(strategy.checkpointing(1, &store).is_empty());
+        assert!(strategy.checkpointing_iteration(1, &store).is_empty());
+        assert_eq!(
+            vec![CheckpointingAction::Save],
+            strategy.checkpointing_iteration(2, &store)
+        );
+        assert!(strategy.checkpointing_iteration(3, &store).is_empty());
+        assert_eq!(
+            vec![CheckpointingAction::Save],
+            strategy.checkpointing_iteration(4, &store)
+        );
+        assert_eq!(
+            vec![CheckpointingAction::Save, CheckpointingAction::Delete(2)],
+            strategy.checkpointing_iteration(6, &store)
+        );
+    }
+
+    #[test]
+    fn should_save_after_duration() {
+        let mut strategy = IntervalCheckpointingStrategy::every_duration(Duration::ZERO);
+        let store = EventStoreClient::new(LogEventStore::default());
+
+        assert_eq!(
+            vec![CheckpointingAction::Save],
+            strategy.checkpointing_iteration(1, &store)
+        );
+        assert_eq!(
+            vec![CheckpointingAction::Save, CheckpointingAction::Delete(1)],
+            strategy.checkpointing_iteration(2, &store)
+        );
+
+        let mut strategy = IntervalCheckpointingStrategy::every_duration(Duration::from_secs(3600));
+        assert!(strategy.checkpointing_iteration(1, &store).is_empty());
+    }
+
+    #[test]
+    fn should_delete_checkpoints_saved_before_resuming() {
+        let mut strategy = IntervalCheckpointingStrategy::every_iterations(2).keep_last(2);
+        let store = EventStoreClient::new(LogEventStore::default());
+
+        strategy.resume(Checkpoint::Iteration(6), 6);
+
+        assert!(strategy.checkpointing_iteration(7, &store).is_empty());
+        assert_eq!(
+            vec![CheckpointingAction::Save, CheckpointingAction::Delete(4)],
+            strategy.checkpointing_iteration(8, &store)
+        );
+        assert_eq!(
+            vec![CheckpointingAction::Save, CheckpointingAction::Delete(6)],
+            strategy.checkpointing_iteration(10, &store)
+        );
+
+        // Resuming from an epoch checkpoint between iteration checkpoints.
+        strategy.resume(Checkpoint::Epoch(1), 3);
+        assert_eq!(
+            vec![CheckpointingAction::Save, CheckpointingAction::Delete(2)],
+            strategy.checkpointing_iteration(4, &store)
+        );
+
+        let mut strategy = IntervalCheckpointingStrategy::every_duration(Duration::ZERO);
+        strategy.resume(Checkpoint::Iteration(5), 5);
+        assert_eq!(
+            vec![CheckpointingAction::Save, CheckpointingAction::Delete(5)],
+            strategy.checkpointing_iteration(6, &store)
+        );
+    }
+}
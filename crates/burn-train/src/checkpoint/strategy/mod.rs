@@ -1,9 +1,11 @@
 mod base;
 mod composed;
+mod interval;
 mod lastn;
 mod metric;
 
 pub use base::*;
 pub use composed::*;
+pub use interval::*;
 pub use lastn::*;
 pub use metric::*;
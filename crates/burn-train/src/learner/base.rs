@@ -1,11 +1,15 @@
-use crate::checkpoint::{Checkpointer, CheckpointingAction, CheckpointingStrategy};
+use crate::CallbackList;
+use crate::checkpoint::{Checkpointer, CheckpointingAction, CheckpointingStrategy, TrainingState};
 use crate::components::LearnerComponentTypes;
 use crate::metric::store::EventStoreClient;
+use crate::run::LearnerRun;
 use crate::{CloneEarlyStoppingStrategy, LearnerSummaryConfig, LearningStrategy};
+use burn_core::amp::MixedPrecisionConfig;
 use burn_core::lr_scheduler::LrScheduler;
-use burn_core::module::Module;
+use burn_core::module::{EmaConfig, EmaModule, EmaRecord, Module};
 use burn_core::optim::Optimizer;
-use burn_core::tensor::Device;
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{Device, Distribution, Int, Tensor};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -17,8 +21,11 @@ pub struct Learner<LC: LearnerComponentTypes> {
     pub(crate) optim: LC::Optimizer,
     pub(crate) lr_scheduler: LC::LrScheduler,
     pub(crate) num_epochs: usize,
-    pub(crate) checkpoint: Option<usize>,
+    pub(crate) checkpoint: Option<Checkpoint>,
     pub(crate) grad_accumulation: Option<usize>,
+    pub(crate) ema: Option<(EmaConfig, Option<Device<LC::Backend>>)>,
+    pub(crate) grad_norm: bool,
+    pub(crate) mixed_precision: Option<MixedPrecisionConfig>,
     pub(crate) checkpointer: Option<LearnerCheckpointer<LC>>,
     pub(crate) learning_strategy: LearningStrategy<LC::Backend>,
     pub(crate) interrupter: Interrupter,
@@ -26,56 +33,222 @@ pub struct Learner<LC: LearnerComponentTypes> {
     pub(crate) event_processor: LC::EventProcessor,
     pub(crate) event_store: Arc<EventStoreClient>,
     pub(crate) summary: Option<LearnerSummaryConfig>,
+    pub(crate) run: Option<LearnerRun>,
+    pub(crate) callbacks: CallbackList<LC::Model, LC::Optimizer>,
 }
 
 /// Cloneable reference to an early stopping strategy
 pub(crate) type EarlyStoppingStrategyRef = Box<dyn CloneEarlyStoppingStrategy>;
 
-#[derive(new)]
-pub(crate) struct LearnerCheckpointer<LC: LearnerComponentTypes> {
+/// Checkpointer of the [exponential moving average](EmaModule) of the model.
+pub(crate) type EmaCheckpointerRef<LC> = Box<
+    dyn Checkpointer<
+            EmaRecord<<LC as LearnerComponentTypes>::Backend, <LC as LearnerComponentTypes>::Model>,
+            <LC as LearnerComponentTypes>::Backend,
+        >,
+>;
+
+/// Checkpointer of the [training state](TrainingState).
+pub(crate) type StateCheckpointerRef<LC> =
+    Box<dyn Checkpointer<TrainingState, <LC as LearnerComponentTypes>::Backend>>;
+
+/// A checkpoint to resume the training from.
+#[derive(Clone, Copy, Debug)]
+pub enum Checkpoint {
+    /// The checkpoint saved at the end of an epoch.
+    Epoch(usize),
+    /// The checkpoint saved after a number of iterations since the start of the training.
+    Iteration(usize),
+}
+
+/// The checkpointers of all the records saved with a checkpoint.
+pub(crate) struct CheckpointerSet<LC: LearnerComponentTypes> {
     model: LC::CheckpointerModel,
     optim: LC::CheckpointerOptimizer,
     lr_scheduler: LC::CheckpointerLrScheduler,
+    ema: Option<EmaCheckpointerRef<LC>>,
+    state: Option<StateCheckpointerRef<LC>>,
+}
+
+impl<LC: LearnerComponentTypes> CheckpointerSet<LC> {
+    pub(crate) fn new(
+        model: LC::CheckpointerModel,
+        optim: LC::CheckpointerOptimizer,
+        lr_scheduler: LC::CheckpointerLrScheduler,
+    ) -> Self {
+        Self {
+            model,
+            optim,
+            lr_scheduler,
+            ema: None,
+            state: None,
+        }
+    }
+
+    /// Also checkpoint the exponential moving average of the model with the given checkpointer.
+    pub(crate) fn with_ema(mut self, checkpointer: EmaCheckpointerRef<LC>) -> Self {
+        self.ema = Some(checkpointer);
+        self
+    }
+
+    /// Also checkpoint the training state with the given checkpointer.
+    pub(crate) fn with_state(mut self, checkpointer: StateCheckpointerRef<LC>) -> Self {
+        self.state = Some(checkpointer);
+        self
+    }
+
+    fn save(
+        &self,
+        key: usize,
+        model: &LC::Model,
+        optim: &LC::Optimizer,
+        scheduler: &LC::LrScheduler,
+        ema: Option<&EmaModule<LC::Backend, LC::Model>>,
+        state: TrainingState,
+    ) {
+        self.model
+            .save(key, model.clone().into_record())
+            .expect("Can save model checkpoint.");
+        self.optim
+            .save(key, optim.to_record())
+            .expect("Can save optimizer checkpoint.");
+        self.lr_scheduler
+            .save(key, scheduler.to_record())
+            .expect("Can save learning rate scheduler checkpoint.");
+        if let (Some(checkpointer), Some(ema)) = (&self.ema, ema) {
+            checkpointer
+                .save(key, ema.clone().into_record())
+                .expect("Can save EMA model checkpoint.");
+        }
+        if let Some(checkpointer) = &self.state {
+            checkpointer
+                .save(key, state)
+                .expect("Can save training state checkpoint.");
+        }
+    }
+
+    fn delete(&self, key: usize) {
+        self.model
+            .delete(key)
+            .expect("Can delete model checkpoint.");
+        self.optim
+            .delete(key)
+            .expect("Can delete optimizer checkpoint.");
+        self.lr_scheduler
+            .delete(key)
+            .expect("Can delete learning rate scheduler checkpoint.");
+        if let Some(checkpointer) = &self.ema {
+            checkpointer
+                .delete(key)
+                .expect("Can delete EMA model checkpoint.");
+        }
+        if let Some(checkpointer) = &self.state {
+            checkpointer
+                .delete(key)
+                .expect("Can delete training state checkpoint.");
+        }
+    }
+}
+
+/// Save and restore the checkpoints of the training, at the end of epochs and, when the
+/// [strategy](CheckpointingStrategy::checkpointing_iteration) requests it, after iterations.
+pub(crate) struct LearnerCheckpointer<LC: LearnerComponentTypes> {
+    epochs: CheckpointerSet<LC>,
+    iterations: Option<CheckpointerSet<LC>>,
     strategy: LC::CheckpointerStrategy,
 }
 
 impl<LC: LearnerComponentTypes> LearnerCheckpointer<LC> {
+    pub(crate) fn new(epochs: CheckpointerSet<LC>, strategy: LC::CheckpointerStrategy) -> Self {
+        Self {
+            epochs,
+            iterations: None,
+            strategy,
+        }
+    }
+
+    /// Also save the checkpoints of iterations with the given checkpointers.
+    pub(crate) fn with_iterations(mut self, iterations: CheckpointerSet<LC>) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Checkpoint the training at the end of the [epoch](TrainingState::epoch) of the given
+    /// state, returning whether a checkpoint was saved.
     pub(crate) fn checkpoint(
         &mut self,
         model: &LC::Model,
         optim: &LC::Optimizer,
         scheduler: &LC::LrScheduler,
-        epoch: usize,
+        ema: Option<&EmaModule<LC::Backend, LC::Model>>,
+        state: TrainingState,
         store: &EventStoreClient,
-    ) {
+    ) -> bool {
+        let epoch = state.epoch;
         let actions = self.strategy.checkpointing(epoch, store);
+        let mut saved = false;
 
         for action in actions {
             match action {
-                CheckpointingAction::Delete(epoch) => {
-                    self.model
-                        .delete(epoch)
-                        .expect("Can delete model checkpoint.");
-                    self.optim
-                        .delete(epoch)
-                        .expect("Can delete optimizer checkpoint.");
-                    self.lr_scheduler
-                        .delete(epoch)
-                        .expect("Can delete learning rate scheduler checkpoint.");
+                CheckpointingAction::Delete(epoch) => self.epochs.delete(epoch),
+                CheckpointingAction::Save => {
+                    self.epochs
+                        .save(epoch, model, optim, scheduler, ema, state.clone());
+                    saved = true;
                 }
+            }
+        }
+
+        saved
+    }
+
+    /// Checkpoint the training in the middle of an epoch, keyed by the
+    /// [total number of iterations](TrainingState::total_iterations) of the given state.
+    ///
+    /// When a checkpoint is saved, the random number generator of the backend is seeded again to
+    /// be restored when resuming, and `true` is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn checkpoint_iteration(
+        &mut self,
+        model: &LC::Model,
+        optim: &LC::Optimizer,
+        scheduler: &LC::LrScheduler,
+        ema: Option<&EmaModule<LC::Backend, LC::Model>>,
+        mut state: TrainingState,
+        device: &Device<LC::Backend>,
+        store: &EventStoreClient,
+    ) -> bool {
+        let Some(iterations) = &self.iterations else {
+            return false;
+        };
+        let iteration = state.total_iterations;
+        let actions = self.strategy.checkpointing_iteration(iteration, store);
+        let mut saved = false;
+
+        for action in actions {
+            match action {
+                CheckpointingAction::Delete(iteration) => iterations.delete(iteration),
                 CheckpointingAction::Save => {
-                    self.model
-                        .save(epoch, model.clone().into_record())
-                        .expect("Can save model checkpoint.");
-                    self.optim
-                        .save(epoch, optim.to_record())
-                        .expect("Can save optimizer checkpoint.");
-                    self.lr_scheduler
-                        .save(epoch, scheduler.to_record())
-                        .expect("Can save learning rate scheduler checkpoint.");
+                    state.seed = Some(reseed::<LC::Backend>(device));
+                    iterations.save(iteration, model, optim, scheduler, ema, state.clone());
+                    saved = true;
                 }
             }
         }
+
+        saved
+    }
+
+    fn checkpointers(&self, checkpoint: Checkpoint) -> (&CheckpointerSet<LC>, usize) {
+        match checkpoint {
+            Checkpoint::Epoch(epoch) => (&self.epochs, epoch),
+            Checkpoint::Iteration(iteration) => (
+                self.iterations
+                    .as_ref()
+                    .expect("Iteration checkpoints should be enabled to resume from an iteration."),
+                iteration,
+            ),
+        }
     }
 
     pub(crate) fn load_checkpoint(
@@ -84,28 +257,99 @@ impl<LC: LearnerComponentTypes> LearnerCheckpointer<LC> {
         optim: LC::Optimizer,
         scheduler: LC::LrScheduler,
         device: &Device<LC::Backend>,
-        epoch: usize,
+        checkpoint: Checkpoint,
     ) -> (LC::Model, LC::Optimizer, LC::LrScheduler) {
-        let record = self
+        let (checkpointers, key) = self.checkpointers(checkpoint);
+
+        let record = checkpointers
             .model
-            .restore(epoch, device)
+            .restore(key, device)
             .expect("Can load model checkpoint.");
         let model = model.load_record(record);
 
-        let record = self
+        let record = checkpointers
             .optim
-            .restore(epoch, device)
+            .restore(key, device)
             .expect("Can load optimizer checkpoint.");
         let optim = optim.load_record(record);
 
-        let record = self
+        let record = checkpointers
             .lr_scheduler
-            .restore(epoch, device)
+            .restore(key, device)
             .expect("Can load learning rate scheduler checkpoint.");
         let scheduler = scheduler.load_record(record);
 
         (model, optim, scheduler)
     }
+
+    /// Restore the exponential moving average of the model on its device, the average is kept as
+    /// is when its checkpoint can't be found, e.g. when EMA was enabled after the checkpointed
+    /// epoch.
+    pub(crate) fn load_ema(
+        &self,
+        ema: EmaModule<LC::Backend, LC::Model>,
+        device: &Device<LC::Backend>,
+        checkpoint: Checkpoint,
+    ) -> EmaModule<LC::Backend, LC::Model> {
+        let (checkpointers, key) = self.checkpointers(checkpoint);
+        let Some(checkpointer) = &checkpointers.ema else {
+            return ema;
+        };
+
+        match checkpointer.restore(key, device) {
+            Ok(record) => ema.load_record(record),
+            Err(err) => {
+                log::warn!("Can't load EMA model checkpoint, starting from the model: {err:?}");
+                ema
+            }
+        }
+    }
+
+    /// Restore the training state, which is missing from checkpoints saved by previous versions.
+    ///
+    /// # Panics
+    ///
+    /// If the state of an iteration checkpoint can't be loaded, since the training can't be
+    /// resumed in the middle of an epoch without it.
+    pub(crate) fn load_state(
+        &self,
+        device: &Device<LC::Backend>,
+        checkpoint: Checkpoint,
+    ) -> Option<TrainingState> {
+        let (checkpointers, key) = self.checkpointers(checkpoint);
+        let checkpointer = checkpointers.state.as_ref()?;
+
+        match (checkpointer.restore(key, device), checkpoint) {
+            (Ok(state), _) => Some(state),
+            (Err(err), Checkpoint::Iteration(_)) => {
+                panic!("Can't load training state checkpoint: {err:?}")
+            }
+            (Err(err), Checkpoint::Epoch(_)) => {
+                log::warn!("Can't load training state checkpoint: {err:?}");
+                None
+            }
+        }
+    }
+
+    /// Restore the state of the strategy when the training is resumed from the given checkpoint,
+    /// so that the checkpoints saved before resuming are deleted.
+    pub(crate) fn resume(&mut self, checkpoint: Checkpoint, total_iterations: usize) {
+        self.strategy.resume(checkpoint, total_iterations);
+    }
+}
+
+/// Seed the random number generator of the backend with a seed drawn from it, returning the seed.
+fn reseed<B: Backend>(device: &B::Device) -> u64 {
+    let values =
+        Tensor::<B, 1, Int>::random([2], Distribution::Uniform(0.0, 2.0f64.powi(31)), device)
+            .into_data()
+            .convert::<i64>();
+    let seed = values
+        .iter::<i64>()
+        .fold(0u64, |seed, value| (seed << 31) | value as u64);
+
+    B::seed(device, seed);
+    seed
 }
 
 #[derive(Clone, Default)]
@@ -6,24 +6,29 @@ use std::sync::Arc;
 use super::Learner;
 use crate::checkpoint::{
     AsyncCheckpointer, CheckpointingStrategy, ComposedCheckpointingStrategy, FileCheckpointer,
-    KeepLastNCheckpoints, MetricCheckpointingStrategy,
+    KeepLastNCheckpoints, MetricCheckpointingStrategy, TrainingState,
 };
 use crate::components::{LearnerComponentsMarker, LearningDataMarker};
-use crate::learner::EarlyStoppingStrategy;
 use crate::learner::base::Interrupter;
+use crate::learner::{ComposedEarlyStoppingStrategy, EarlyStoppingStrategy};
 use crate::logger::{FileMetricLogger, MetricLogger};
 use crate::metric::processor::{
     AsyncProcessorTraining, FullEventProcessorTraining, ItemLazy, MetricsTraining,
 };
 use crate::metric::store::{Aggregate, Direction, EventStoreClient, LogEventStore, Split};
-use crate::metric::{Adaptor, LossMetric, Metric};
+use crate::metric::{Adaptor, GradientNormMetric, LossMetric, Metric};
 use crate::renderer::{MetricsRenderer, default_renderer};
+use crate::run::{LearnerRun, RunInfo, RunStore, RunStoreFormat};
 use crate::{
-    ApplicationLoggerInstaller, EarlyStoppingStrategyRef, FileApplicationLoggerInstaller,
-    LearnerCheckpointer, LearnerSummaryConfig, LearningStrategy, TrainStep, ValidStep,
+    ApplicationLoggerInstaller, Callback, CallbackList, Checkpoint, CheckpointerSet,
+    EarlyStoppingStrategyRef, FileApplicationLoggerInstaller, LearnerBuildError,
+    LearnerCheckpointer, LearnerFeatures, LearnerSummaryConfig, LearningStrategy, TrainStep,
+    ValidStep,
 };
+use burn_core::amp::MixedPrecisionConfig;
+use burn_core::config::Config;
 use burn_core::lr_scheduler::LrScheduler;
-use burn_core::module::AutodiffModule;
+use burn_core::module::{AutodiffModule, EmaConfig, EmaRecord};
 use burn_core::optim::Optimizer;
 use burn_core::record::FileRecorder;
 use burn_core::tensor::backend::AutodiffBackend;
@@ -53,10 +58,28 @@ where
         AsyncCheckpointer<O::Record, B>,
         AsyncCheckpointer<S::Record<B>, B>,
     )>,
+    #[allow(clippy::type_complexity)]
+    checkpointers_iteration: Option<(
+        AsyncCheckpointer<M::Record, B>,
+        AsyncCheckpointer<O::Record, B>,
+        AsyncCheckpointer<S::Record<B>, B>,
+    )>,
+    // The checkpointers of epochs and iterations.
+    checkpointer_ema: Option<(
+        AsyncCheckpointer<EmaRecord<B, M>, B>,
+        AsyncCheckpointer<EmaRecord<B, M>, B>,
+    )>,
+    checkpointer_state: Option<(
+        AsyncCheckpointer<TrainingState, B>,
+        AsyncCheckpointer<TrainingState, B>,
+    )>,
     num_epochs: usize,
-    checkpoint: Option<usize>,
+    checkpoint: Option<Checkpoint>,
     directory: PathBuf,
     grad_accumulation: Option<usize>,
+    ema: Option<(EmaConfig, Option<B::Device>)>,
+    grad_norm: bool,
+    mixed_precision: Option<MixedPrecisionConfig>,
     learning_strategy: LearningStrategy<B>,
     renderer: Option<Box<dyn MetricsRenderer + 'static>>,
     metrics: MetricsTraining<TO, VO>,
@@ -68,7 +91,11 @@ where
     early_stopping: Option<EarlyStoppingStrategyRef>,
     // Use BTreeSet instead of HashSet for consistent (alphabetical) iteration order
     summary_metrics: BTreeSet<String>,
+    summary_text_metrics: BTreeSet<String>,
     summary: bool,
+    run_store: Option<RunStoreFormat>,
+    run_info: Option<RunInfo>,
+    callbacks: CallbackList<M, O>,
     _p: PhantomData<(TI, VI, TO, VO)>,
 }
 
@@ -96,8 +123,14 @@ where
             num_epochs: 1,
             checkpoint: None,
             checkpointers: None,
+            checkpointers_iteration: None,
+            checkpointer_ema: None,
+            checkpointer_state: None,
             directory,
             grad_accumulation: None,
+            ema: None,
+            grad_norm: false,
+            mixed_precision: None,
             learning_strategy: LearningStrategy::default(),
             metrics: MetricsTraining::default(),
             event_store: LogEventStore::default(),
@@ -120,7 +153,11 @@ where
             ),
             early_stopping: None,
             summary_metrics: BTreeSet::new(),
+            summary_text_metrics: BTreeSet::new(),
             summary: false,
+            run_store: None,
+            run_info: None,
+            callbacks: CallbackList::default(),
             _p: PhantomData,
         }
     }
@@ -143,6 +180,9 @@ where
     }
 
     /// Update the checkpointing_strategy.
+    ///
+    /// Strategies [checkpointing iterations](CheckpointingStrategy::checkpoints_iterations) are
+    /// only supported on a single device, the learner fails to [build](Self::build) otherwise.
     pub fn with_checkpointing_strategy<CS>(mut self, strategy: CS) -> Self
     where
         CS: CheckpointingStrategy + 'static,
@@ -182,6 +222,7 @@ where
     where
         <TO as ItemLazy>::ItemSync: Adaptor<Me::Input>,
     {
+        self.summary_text_metrics.insert(metric.name().to_string());
         self.metrics.register_train_metric(metric);
         self
     }
@@ -191,6 +232,7 @@ where
     where
         <VO as ItemLazy>::ItemSync: Adaptor<Me::Input>,
     {
+        self.summary_text_metrics.insert(metric.name().to_string());
         self.metrics.register_valid_metric(metric);
         self
     }
@@ -210,6 +252,52 @@ where
         self
     }
 
+    /// Keep an [exponential moving average](burn_core::module::EmaModule) of the model weights, updated after each
+    /// optimizer step.
+    ///
+    /// # Notes
+    ///
+    /// When enabled, the validation is done with the averaged model, which is also checkpointed
+    /// and returned at the end of the training on the device of the model.
+    ///
+    /// The average is kept on the given `device`, e.g. to save the memory of the accelerator, or
+    /// on the device of the model when `None`, which is the main device with multiple devices.
+    /// It isn't supported with distributed data parallel training, the learner fails to
+    /// [build](Self::build).
+    pub fn with_ema(mut self, config: EmaConfig, device: Option<B::Device>) -> Self {
+        self.ema = Some((config, device));
+        self
+    }
+
+    /// Train with [automatic mixed precision](burn_core::amp), running the linear and
+    /// convolution layers in half precision and scaling the loss to avoid the underflow of the
+    /// gradients.
+    ///
+    /// # Notes
+    ///
+    /// The [training step](TrainStep) should compute the gradients from the
+    /// [scaled loss](burn_core::amp::scale_loss), otherwise its gradients are used as is, without
+    /// loss scaling. The iterations with non-finite gradients are skipped, and the state of the
+    /// loss scale is saved with the checkpoints. It is only supported with a single device, the
+    /// learner fails to [build](Self::build) with multiple devices.
+    pub fn mixed_precision(mut self, config: MixedPrecisionConfig) -> Self {
+        self.mixed_precision = Some(config);
+        self
+    }
+
+    /// Register the [gradient norm metric](GradientNormMetric), computing the L2 norm of all the
+    /// gradients of each training iteration before they are clipped.
+    ///
+    /// # Notes
+    ///
+    /// The norm is read back from the device at every iteration, which slows down training. It
+    /// isn't supported with distributed data parallel training, the learner fails to
+    /// [build](Self::build).
+    pub fn metric_train_grad_norm(mut self) -> Self {
+        self.grad_norm = true;
+        self.metric_train_numeric(GradientNormMetric::new())
+    }
+
     /// Register a [numeric](crate::metric::Numeric) training [metric](Metric).
     pub fn metric_train_numeric<Me>(mut self, metric: Me) -> Self
     where
@@ -248,7 +336,20 @@ where
 
     /// The epoch from which the training must resume.
     pub fn checkpoint(mut self, checkpoint: usize) -> Self {
-        self.checkpoint = Some(checkpoint);
+        self.checkpoint = Some(Checkpoint::Epoch(checkpoint));
+        self
+    }
+
+    /// The iteration, counted from the start of the training, from which the training must
+    /// resume in the middle of an epoch.
+    ///
+    /// The checkpoint must have been saved by a strategy
+    /// [checkpointing iterations](CheckpointingStrategy::checkpointing_iteration), e.g. the
+    /// [interval strategy](crate::checkpoint::IntervalCheckpointingStrategy). Iteration
+    /// checkpoints are only supported on a single device, the learner fails to
+    /// [build](Self::build) with multiple devices or distributed data parallel training.
+    pub fn checkpoint_iteration(mut self, iteration: usize) -> Self {
+        self.checkpoint = Some(Checkpoint::Iteration(iteration));
         self
     }
 
@@ -265,6 +366,9 @@ where
 
     /// Register an [early stopping strategy](EarlyStoppingStrategy) to stop the training when the
     /// conditions are meet.
+    ///
+    /// The strategy replaces the one registered before, multiple strategies can be combined with
+    /// a [composed strategy](crate::ComposedEarlyStoppingStrategy).
     pub fn early_stopping<Strategy>(mut self, strategy: Strategy) -> Self
     where
         Strategy: EarlyStoppingStrategy + Clone + Send + Sync + 'static,
@@ -273,6 +377,34 @@ where
         self
     }
 
+    /// Register an [early stopping strategy](EarlyStoppingStrategy) in addition to the one
+    /// registered before, if any, stopping the training when any of them stops it.
+    pub(crate) fn add_early_stopping<Strategy>(mut self, strategy: Strategy) -> Self
+    where
+        Strategy: EarlyStoppingStrategy + Clone + Send + Sync + 'static,
+    {
+        self.early_stopping = Some(match self.early_stopping.take() {
+            Some(existing) => Box::new(ComposedEarlyStoppingStrategy::new(vec![
+                existing,
+                Box::new(strategy),
+            ])),
+            None => Box::new(strategy),
+        });
+        self
+    }
+
+    /// Register a [callback](Callback) with hooks called during the training.
+    ///
+    /// The callbacks are called in their registration order. They aren't supported with
+    /// distributed data parallel training, the learner fails to [build](Self::build).
+    pub fn callback<C>(mut self, callback: C) -> Self
+    where
+        C: Callback<M, O> + 'static,
+    {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
     /// By default, Rust logs are captured and written into
     /// `experiment.log`. If disabled, standard Rust log handling
     /// will apply.
@@ -285,7 +417,10 @@ where
     }
 
     /// Register a checkpointer that will save the [optimizer](Optimizer), the
-    /// [model](AutodiffModule) and the [scheduler](LrScheduler) to different files.
+    /// [model](AutodiffModule), the [scheduler](LrScheduler) and the
+    /// [training state](TrainingState) to different files.
+    ///
+    /// The checkpoints of iterations are saved to files suffixed with `-iteration`.
     pub fn with_file_checkpointer<FR>(mut self, recorder: FR) -> Self
     where
         FR: FileRecorder<B> + 'static,
@@ -295,16 +430,26 @@ where
         S::Record<B>: 'static,
     {
         let checkpoint_dir = self.directory.join("checkpoint");
-        let checkpointer_model = FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "model");
-        let checkpointer_optimizer =
-            FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "optim");
-        let checkpointer_scheduler: FileCheckpointer<FR> =
-            FileCheckpointer::new(recorder, &checkpoint_dir, "scheduler");
+        let checkpointer =
+            |name: &str| FileCheckpointer::new(recorder.clone(), &checkpoint_dir, name);
 
         self.checkpointers = Some((
-            AsyncCheckpointer::new(checkpointer_model),
-            AsyncCheckpointer::new(checkpointer_optimizer),
-            AsyncCheckpointer::new(checkpointer_scheduler),
+            AsyncCheckpointer::new(checkpointer("model")),
+            AsyncCheckpointer::new(checkpointer("optim")),
+            AsyncCheckpointer::new(checkpointer("scheduler")),
+        ));
+        self.checkpointers_iteration = Some((
+            AsyncCheckpointer::new(checkpointer("model-iteration")),
+            AsyncCheckpointer::new(checkpointer("optim-iteration")),
+            AsyncCheckpointer::new(checkpointer("scheduler-iteration")),
+        ));
+        self.checkpointer_ema = Some((
+            AsyncCheckpointer::new(checkpointer("ema")),
+            AsyncCheckpointer::new(checkpointer("ema-iteration")),
+        ));
+        self.checkpointer_state = Some((
+            AsyncCheckpointer::new(checkpointer("state")),
+            AsyncCheckpointer::new(checkpointer("state-iteration")),
         ));
 
         self
@@ -318,9 +463,73 @@ where
         self
     }
 
+    /// Record the numeric metrics of the training and validation splits to a single metrics
+    /// file of the given format in the directory of the learner, along with the default metric
+    /// loggers.
+    ///
+    /// A `run.json` file describing the run is also saved, so that the runs saved in the
+    /// subdirectories of a directory can be listed and compared with a
+    /// [run registry](crate::run::RunRegistry).
+    pub fn run_store(mut self, format: RunStoreFormat) -> Self {
+        self.run_store = Some(format);
+        self.run_info.get_or_insert_with(RunInfo::new);
+        self
+    }
+
+    /// Save a config with the given name, e.g. `training`, in the `run.json` file describing the
+    /// run.
+    pub fn run_config<C: Config>(mut self, name: &str, config: &C) -> Self {
+        let info = self.run_info.take().unwrap_or_else(RunInfo::new);
+        self.run_info = Some(info.with_config(name, config));
+        self
+    }
+
+    /// Create the [learner](Learner) from a [model](AutodiffModule) and an [optimizer](Optimizer),
+    /// or return an error when the [learning strategy](LearningStrategy) doesn't support the
+    /// configuration of the builder.
+    ///
+    /// See [build](Self::build).
+    #[allow(clippy::type_complexity)]
+    pub fn try_build(
+        self,
+        model: M,
+        optim: O,
+        lr_scheduler: S,
+    ) -> Result<
+        Learner<
+            LearnerComponentsMarker<
+                B,
+                S,
+                M,
+                O,
+                AsyncCheckpointer<M::Record, B>,
+                AsyncCheckpointer<O::Record, B>,
+                AsyncCheckpointer<S::Record<B>, B>,
+                AsyncProcessorTraining<FullEventProcessorTraining<TO, VO>>,
+                Box<dyn CheckpointingStrategy>,
+                LearningDataMarker<TI, VI, TO, VO>,
+            >,
+        >,
+        LearnerBuildError,
+    >
+    where
+        M::Record: 'static,
+        O::Record: 'static,
+        S::Record<B>: 'static,
+    {
+        self.validate()?;
+
+        Ok(self.build(model, optim, lr_scheduler))
+    }
+
     /// Create the [learner](Learner) from a [model](AutodiffModule) and an [optimizer](Optimizer).
     /// The [learning rate scheduler](LrScheduler) can also be a simple
     /// [learning rate](burn_core::LearningRate).
+    ///
+    /// # Panics
+    ///
+    /// If the [learning strategy](LearningStrategy) doesn't support the configuration of the
+    /// builder, use [try_build](Self::try_build) to handle the error.
     #[allow(clippy::type_complexity)] // The goal for the builder is to handle all types and
     // creates a clean learner.
     pub fn build(
@@ -347,14 +556,22 @@ where
         O::Record: 'static,
         S::Record<B>: 'static,
     {
+        if let Err(err) = self.validate() {
+            panic!("Can't build the learner: {err}");
+        }
         if self.tracing_logger.is_some()
             && let Err(e) = self.tracing_logger.as_ref().unwrap().install()
         {
             log::warn!("Failed to install the experiment logger: {e}");
         }
-        let renderer = self
-            .renderer
-            .unwrap_or_else(|| default_renderer(self.interrupter.clone(), self.checkpoint));
+        let renderer = self.renderer.unwrap_or_else(|| {
+            // The epoch of an iteration checkpoint is only known once loaded.
+            let checkpoint = match self.checkpoint {
+                Some(Checkpoint::Epoch(epoch)) => Some(epoch),
+                _ => None,
+            };
+            default_renderer(self.interrupter.clone(), checkpoint)
+        });
 
         if self.num_loggers == 0 {
             self.event_store
@@ -363,6 +580,14 @@ where
                 .register_logger_valid(FileMetricLogger::new_train(self.directory.join("valid")));
         }
 
+        let mut run_info = self.run_info;
+        if let (Some(format), Some(info)) = (self.run_store, run_info.as_mut()) {
+            let store = RunStore::new(&self.directory, format);
+            self.event_store.register_logger_train(store.logger_train());
+            self.event_store.register_logger_valid(store.logger_valid());
+            info.metrics_file = Some(format.file_name().to_string());
+        }
+
         let event_store = Arc::new(EventStoreClient::new(self.event_store));
         let event_processor = AsyncProcessorTraining::new(FullEventProcessorTraining::new(
             self.metrics,
@@ -370,21 +595,52 @@ where
             event_store.clone(),
         ));
 
+        let ema = self.ema;
+        let checkpointer_ema = self.checkpointer_ema;
+        let checkpointer_state = self.checkpointer_state;
+        let checkpointers_iteration = self.checkpointers_iteration;
         let checkpointer = self.checkpointers.map(|(model, optim, scheduler)| {
-            LearnerCheckpointer::new(model, optim, scheduler, self.checkpointer_strategy)
+            let mut epochs = CheckpointerSet::new(model, optim, scheduler);
+            let mut iterations = checkpointers_iteration
+                .map(|(model, optim, scheduler)| CheckpointerSet::new(model, optim, scheduler));
+
+            if let (Some(_), Some((checkpointer_ema, checkpointer_ema_iteration))) =
+                (&ema, checkpointer_ema)
+            {
+                epochs = epochs.with_ema(Box::new(checkpointer_ema));
+                iterations = iterations
+                    .map(|iterations| iterations.with_ema(Box::new(checkpointer_ema_iteration)));
+            }
+            if let Some((checkpointer_state, checkpointer_state_iteration)) = checkpointer_state {
+                epochs = epochs.with_state(Box::new(checkpointer_state));
+                iterations = iterations.map(|iterations| {
+                    iterations.with_state(Box::new(checkpointer_state_iteration))
+                });
+            }
+
+            let checkpointer = LearnerCheckpointer::new(epochs, self.checkpointer_strategy);
+            match iterations {
+                Some(iterations) => checkpointer.with_iterations(iterations),
+                None => checkpointer,
+            }
+        });
+
+        let learning_strategy = Self::prepare_learning_strategy(self.learning_strategy);
+        let run = run_info.map(|info| {
+            let info = info.with_backend::<B>(&learning_strategy.devices());
+            LearnerRun::new(self.directory.clone(), info)
         });
 
         let summary = if self.summary {
             Some(LearnerSummaryConfig {
                 directory: self.directory,
                 metrics: self.summary_metrics.into_iter().collect::<Vec<_>>(),
+                text_metrics: self.summary_text_metrics.into_iter().collect::<Vec<_>>(),
             })
         } else {
             None
         };
 
-        let learning_strategy = Self::prepare_learning_strategy(self.learning_strategy);
-
         Learner {
             model,
             optim,
@@ -395,13 +651,34 @@ where
             event_store,
             checkpoint: self.checkpoint,
             grad_accumulation: self.grad_accumulation,
+            ema,
+            grad_norm: self.grad_norm,
+            mixed_precision: self.mixed_precision,
             learning_strategy,
             interrupter: self.interrupter,
             early_stopping: self.early_stopping,
             summary,
+            run,
+            callbacks: self.callbacks,
         }
     }
 
+    /// Checks that the learning strategy supports the features enabled on the builder.
+    fn validate(&self) -> Result<(), LearnerBuildError> {
+        let features = LearnerFeatures {
+            ema: self.ema.is_some(),
+            grad_norm: self.grad_norm,
+            mixed_precision: self.mixed_precision.is_some(),
+            callbacks: !self.callbacks.is_empty(),
+            iteration_checkpoints: self.checkpointers_iteration.is_some()
+                && self.checkpointer_strategy.checkpoints_iterations(),
+            iteration_resume: matches!(self.checkpoint, Some(Checkpoint::Iteration(_))),
+        };
+        let strategy = Self::prepare_learning_strategy(self.learning_strategy.clone());
+
+        features.validate(strategy.kind())
+    }
+
     fn prepare_learning_strategy(learning_strategy: LearningStrategy<B>) -> LearningStrategy<B> {
         if let LearningStrategy::MultiDeviceNaive(devices) = &learning_strategy
             && devices.len() == 1
@@ -0,0 +1,195 @@
+use burn_core::LearningRate;
+use burn_core::optim::GradientsParams;
+
+use crate::Checkpoint;
+use crate::metric::store::EventStoreClient;
+
+/// Hooks called by the [learner](crate::Learner) during the training, with mutable access to the
+/// model and the optimizer.
+///
+/// Callbacks are registered with [callback](crate::LearnerBuilder::callback) and called in their
+/// registration order. All the hooks do nothing by default, so only the relevant ones need to be
+/// implemented, e.g. to log the gradients, generate samples every few iterations or freeze a part
+/// of the model after some epochs.
+///
+/// # Notes
+///
+/// The callbacks aren't supported with distributed data parallel (DDP) training, the learner fails
+/// to [build](crate::LearnerBuilder::build). With multiple devices, the items of all the devices
+/// are computed in parallel before the step hooks of their iterations are called, so changes to
+/// the model in [before_step](Callback::before_step) only apply to the next items, and no
+/// iteration is checkpointed.
+pub trait Callback<M, O>: Send {
+    /// Called once before the first epoch of the training, including when resuming it.
+    fn on_train_start(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called once after the last epoch of the training, even when it was stopped early.
+    fn on_train_end(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called before the training of each epoch.
+    fn on_epoch_start(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called at the end of each epoch, after its validation and its checkpoint.
+    fn on_epoch_end(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called before the validation of each epoch.
+    fn on_valid_start(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called after the validation of each epoch, once its metrics are available in the
+    /// [store](CallbackContext::store).
+    fn on_valid_end(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called before each training iteration, with the
+    /// [learning rate](CallbackContext::lr) of the iteration, which can be changed.
+    fn before_step(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called with the gradients before each optimizer step, e.g. to log or clip them.
+    fn before_optimizer_step(
+        &mut self,
+        _grads: &mut GradientsParams,
+        _context: &mut CallbackContext<'_, M, O>,
+    ) {
+    }
+
+    /// Called after each training iteration, once its item is processed.
+    fn after_step(&mut self, _context: &mut CallbackContext<'_, M, O>) {}
+
+    /// Called after a checkpoint of the training is saved.
+    fn on_checkpoint(&mut self, _checkpoint: Checkpoint, _context: &mut CallbackContext<'_, M, O>) {
+    }
+}
+
+/// The state of the training given to the [callbacks](Callback).
+pub struct CallbackContext<'a, M, O> {
+    /// The trained model.
+    pub model: &'a mut M,
+    /// The optimizer.
+    pub optim: &'a mut O,
+    /// The event store, to read the metrics of the previous epochs.
+    pub store: &'a EventStoreClient,
+    /// The current epoch.
+    pub epoch: usize,
+    /// The current iteration in the epoch, or 0 outside of the training iterations.
+    pub iteration: usize,
+    /// The learning rate of the current iteration, only available in the step hooks.
+    pub lr: Option<LearningRate>,
+}
+
+impl<'a, M, O> CallbackContext<'a, M, O> {
+    /// Create the context of the given epoch, outside of the training iterations.
+    pub fn new(
+        model: &'a mut M,
+        optim: &'a mut O,
+        store: &'a EventStoreClient,
+        epoch: usize,
+    ) -> Self {
+        Self {
+            model,
+            optim,
+            store,
+            epoch,
+            iteration: 0,
+            lr: None,
+        }
+    }
+
+    /// Set the iteration and its learning rate.
+    pub fn with_iteration(mut self, iteration: usize, lr: LearningRate) -> Self {
+        self.iteration = iteration;
+        self.lr = Some(lr);
+        self
+    }
+}
+
+/// The callbacks registered on the learner, called in order.
+pub(crate) struct CallbackList<M, O> {
+    callbacks: Vec<Box<dyn Callback<M, O>>>,
+}
+
+impl<M, O> Default for CallbackList<M, O> {
+    fn default() -> Self {
+        Self {
+            callbacks: Vec::new(),
+        }
+    }
+}
+
+impl<M, O> CallbackList<M, O> {
+    pub(crate) fn push(&mut self, callback: Box<dyn Callback<M, O>>) {
+        self.callbacks.push(callback);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Call the hook of each callback with the context.
+    pub(crate) fn call<F>(&mut self, context: &mut CallbackContext<'_, M, O>, mut hook: F)
+    where
+        F: FnMut(&mut dyn Callback<M, O>, &mut CallbackContext<'_, M, O>),
+    {
+        for callback in self.callbacks.iter_mut() {
+            hook(callback.as_mut(), context);
+        }
+    }
+
+    /// Call the hook of each callback with the context of the given epoch, outside of the
+    /// training iterations.
+    pub(crate) fn call_epoch<F>(
+        &mut self,
+        model: &mut M,
+        optim: &mut O,
+        store: &EventStoreClient,
+        epoch: usize,
+        hook: F,
+    ) where
+        F: FnMut(&mut dyn Callback<M, O>, &mut CallbackContext<'_, M, O>),
+    {
+        if self.is_empty() {
+            return;
+        }
+
+        let mut context = CallbackContext::new(model, optim, store, epoch);
+        self.call(&mut context, hook);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::store::LogEventStore;
+
+    struct RecordingCallback(usize);
+
+    impl Callback<Vec<usize>, f64> for RecordingCallback {
+        fn on_epoch_end(&mut self, context: &mut CallbackContext<'_, Vec<usize>, f64>) {
+            context.model.push(self.0 * 10 + context.epoch);
+        }
+
+        fn before_step(&mut self, context: &mut CallbackContext<'_, Vec<usize>, f64>) {
+            context.lr = context.lr.map(|lr| lr * *context.optim);
+        }
+    }
+
+    #[test]
+    fn test_callbacks_are_called_in_order() {
+        let mut callbacks = CallbackList::default();
+        callbacks.push(Box::new(RecordingCallback(1)));
+        callbacks.push(Box::new(RecordingCallback(2)));
+        let store = EventStoreClient::new(LogEventStore::default());
+        let mut model = Vec::new();
+        let mut optim = 0.5;
+
+        callbacks.call_epoch(&mut model, &mut optim, &store, 2, |callback, context| {
+            callback.on_epoch_end(context)
+        });
+        assert_eq!(model, vec![12, 22]);
+
+        let mut context =
+            CallbackContext::new(&mut model, &mut optim, &store, 3).with_iteration(1, 1e-2);
+        callbacks.call(&mut context, |callback, context| {
+            callback.before_step(context)
+        });
+        assert_eq!(context.lr, Some(1e-2 * 0.25));
+    }
+}
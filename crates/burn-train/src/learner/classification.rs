@@ -1,7 +1,7 @@
 use crate::metric::TopKAccuracyInput;
 use crate::metric::{
-    AccuracyInput, Adaptor, ConfusionStatsInput, HammingScoreInput, LossInput, PerplexityInput,
-    processor::ItemLazy,
+    AccuracyInput, Adaptor, CalibrationInput, ConfusionMatrixInput, ConfusionStatsInput,
+    HammingScoreInput, LossInput, PerplexityInput, processor::ItemLazy,
 };
 use burn_core::tensor::backend::Backend;
 use burn_core::tensor::{Int, Tensor, Transaction};
@@ -83,6 +83,18 @@ impl<B: Backend> Adaptor<ConfusionStatsInput<B>> for ClassificationOutput<B> {
     }
 }
 
+impl<B: Backend> Adaptor<CalibrationInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> CalibrationInput<B> {
+        CalibrationInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<ConfusionMatrixInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> ConfusionMatrixInput<B> {
+        ConfusionMatrixInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
 /// Multi-label classification output adapted for multiple metrics.
 #[derive(new)]
 pub struct MultiLabelClassificationOutput<B: Backend> {
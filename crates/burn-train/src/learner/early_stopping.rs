@@ -107,6 +107,59 @@ impl EarlyStoppingStrategy for MetricEarlyStoppingStrategy {
     }
 }
 
+/// Compose multiple [early stopping strategies](EarlyStoppingStrategy), stopping the training
+/// when any of them stops it.
+///
+/// Every strategy is updated at the end of each epoch, even when another one already stops the
+/// training.
+#[derive(Clone)]
+pub struct ComposedEarlyStoppingStrategy {
+    strategies: Vec<Box<dyn CloneEarlyStoppingStrategy>>,
+}
+
+/// Help building an [early stopping strategy](EarlyStoppingStrategy) by combining multiple ones.
+#[derive(Default)]
+pub struct ComposedEarlyStoppingStrategyBuilder {
+    strategies: Vec<Box<dyn CloneEarlyStoppingStrategy>>,
+}
+
+impl ComposedEarlyStoppingStrategyBuilder {
+    /// Add a new [early stopping strategy](EarlyStoppingStrategy).
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<S>(mut self, strategy: S) -> Self
+    where
+        S: EarlyStoppingStrategy + Clone + Send + 'static,
+    {
+        self.strategies.push(Box::new(strategy));
+        self
+    }
+
+    /// Create a new [composed early stopping strategy](ComposedEarlyStoppingStrategy).
+    pub fn build(self) -> ComposedEarlyStoppingStrategy {
+        ComposedEarlyStoppingStrategy::new(self.strategies)
+    }
+}
+
+impl ComposedEarlyStoppingStrategy {
+    pub(crate) fn new(strategies: Vec<Box<dyn CloneEarlyStoppingStrategy>>) -> Self {
+        Self { strategies }
+    }
+
+    /// Create a new builder which help compose multiple
+    /// [early stopping strategies](EarlyStoppingStrategy).
+    pub fn builder() -> ComposedEarlyStoppingStrategyBuilder {
+        ComposedEarlyStoppingStrategyBuilder::default()
+    }
+}
+
+impl EarlyStoppingStrategy for ComposedEarlyStoppingStrategy {
+    fn should_stop(&mut self, epoch: usize, store: &EventStoreClient) -> bool {
+        self.strategies.iter_mut().fold(false, |stop, strategy| {
+            strategy.should_stop(epoch, store) || stop
+        })
+    }
+}
+
 impl MetricEarlyStoppingStrategy {
     /// Create a new [early stopping strategy](EarlyStoppingStrategy) based on a metrics collected
     /// during training or validation.
@@ -140,7 +193,10 @@ impl MetricEarlyStoppingStrategy {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
 
     use crate::{
         TestBackend,
@@ -211,6 +267,39 @@ mod tests {
         );
     }
 
+    /// Stops from the given epoch, counting its updates.
+    #[derive(Clone)]
+    struct StopFrom {
+        epoch: usize,
+        updates: Arc<AtomicUsize>,
+    }
+
+    impl EarlyStoppingStrategy for StopFrom {
+        fn should_stop(&mut self, epoch: usize, _store: &EventStoreClient) -> bool {
+            self.updates.fetch_add(1, Ordering::Relaxed);
+            epoch >= self.epoch
+        }
+    }
+
+    #[test]
+    fn composed_should_stop_when_any_strategy_stops() {
+        let updates = Arc::new(AtomicUsize::new(0));
+        let strategy = |epoch| StopFrom {
+            epoch,
+            updates: updates.clone(),
+        };
+        let mut early_stopping = ComposedEarlyStoppingStrategy::builder()
+            .add(strategy(2))
+            .add(strategy(3))
+            .build();
+        let store = EventStoreClient::new(LogEventStore::default());
+
+        assert!(!early_stopping.should_stop(1, &store));
+        assert!(early_stopping.should_stop(2, &store));
+        // Every strategy is updated, even after the first one stops.
+        assert_eq!(updates.load(Ordering::Relaxed), 4);
+    }
+
     fn test_early_stopping(n_epochs: usize, data: &[(&[f64], bool, &str)]) {
         let loss = LossMetric::<TestBackend>::new();
         let mut early_stopping = MetricEarlyStoppingStrategy::new(
@@ -1,22 +1,26 @@
 mod application_logger;
 mod base;
 mod builder;
+mod callback;
 mod classification;
 mod early_stopping;
 mod regression;
 mod step;
 mod strategies;
 mod summary;
+mod text_generation;
 mod train_val;
 
 pub use application_logger::*;
 pub use base::*;
 pub use builder::*;
+pub use callback::*;
 pub use classification::*;
 pub use early_stopping::*;
 pub use regression::*;
 pub use step::*;
 pub use strategies::*;
 pub use summary::*;
+pub use text_generation::*;
 pub use train::*;
 pub use train_val::*;
@@ -2,15 +2,21 @@ use std::sync::Arc;
 
 #[cfg(feature = "ddp")]
 use burn_collective::CollectiveConfig;
-use burn_core::{module::AutodiffModule, tensor::backend::AutodiffBackend};
+use burn_core::{
+    amp::MixedPrecisionConfig,
+    lr_scheduler::LrScheduler,
+    module::{AutodiffModule, EmaModule},
+    tensor::backend::AutodiffBackend,
+};
 
 use crate::{
-    EarlyStoppingStrategyRef, Interrupter, Learner, LearnerCheckpointer, TrainLoader,
-    TrainingResult, ValidLoader,
+    CallbackList, Checkpoint, EarlyStoppingStrategyRef, Interrupter, Learner, LearnerCheckpointer,
+    TrainLoader, TrainingResult, ValidLoader,
+    checkpoint::TrainingState,
     components::LearnerComponentTypes,
     metric::{
         processor::{EventProcessorTraining, LearnerEvent},
-        store::EventStoreClient,
+        store::{Aggregate, EventStoreClient},
     },
 };
 
@@ -45,12 +51,137 @@ pub fn ddp<B: AutodiffBackend>(
     LearningStrategy::DistributedDataParallel { devices, config }
 }
 
+impl<B: AutodiffBackend> LearningStrategy<B> {
+    /// The devices the model is trained on.
+    pub(crate) fn devices(&self) -> Vec<B::Device> {
+        match self {
+            Self::SingleDevice(device) => vec![device.clone()],
+            Self::MultiDeviceNaive(devices) => devices.clone(),
+            #[cfg(feature = "ddp")]
+            Self::DistributedDataParallel { devices, .. } => devices.clone(),
+        }
+    }
+}
+
 impl<B: AutodiffBackend> Default for LearningStrategy<B> {
     fn default() -> Self {
         Self::SingleDevice(Default::default())
     }
 }
 
+impl<B: AutodiffBackend> LearningStrategy<B> {
+    /// The kind of the strategy, without its devices.
+    pub(crate) fn kind(&self) -> LearningStrategyKind {
+        match self {
+            Self::SingleDevice(_) => LearningStrategyKind::SingleDevice,
+            Self::MultiDeviceNaive(_) => LearningStrategyKind::MultiDevice,
+            #[cfg(feature = "ddp")]
+            Self::DistributedDataParallel { .. } => LearningStrategyKind::DistributedDataParallel,
+        }
+    }
+}
+
+/// The kind of a [learning strategy](LearningStrategy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LearningStrategyKind {
+    SingleDevice,
+    MultiDevice,
+    #[cfg(feature = "ddp")]
+    DistributedDataParallel,
+}
+
+impl core::fmt::Display for LearningStrategyKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SingleDevice => f.write_str("a single device"),
+            Self::MultiDevice => f.write_str("multiple devices"),
+            #[cfg(feature = "ddp")]
+            Self::DistributedDataParallel => f.write_str("distributed data parallel"),
+        }
+    }
+}
+
+/// The error returned when [building](crate::LearnerBuilder::try_build) a learner with a
+/// configuration its [learning strategy](LearningStrategy) doesn't support.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LearnerBuildError {
+    /// The feature isn't supported by the learning strategy.
+    UnsupportedFeature {
+        /// The unsupported feature.
+        feature: &'static str,
+        /// The learning strategy.
+        strategy: String,
+    },
+}
+
+impl core::fmt::Display for LearnerBuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedFeature { feature, strategy } => {
+                write!(f, "{feature} isn't supported when training with {strategy}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LearnerBuildError {}
+
+/// The features of the learner that aren't supported by every
+/// [learning strategy](LearningStrategy).
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct LearnerFeatures {
+    /// An [exponential moving average](EmaModule) of the model is kept.
+    pub ema: bool,
+    /// The [gradient norm](crate::metric::GradientNormMetric) is computed.
+    pub grad_norm: bool,
+    /// The model is trained with [mixed precision](burn_core::amp).
+    pub mixed_precision: bool,
+    /// [Callbacks](crate::Callback) are registered.
+    pub callbacks: bool,
+    /// The checkpointing strategy
+    /// [checkpoints iterations](crate::checkpoint::CheckpointingStrategy::checkpoints_iterations).
+    pub iteration_checkpoints: bool,
+    /// The training is resumed from an [iteration checkpoint](Checkpoint::Iteration).
+    pub iteration_resume: bool,
+}
+
+impl LearnerFeatures {
+    /// Returns an error with the first enabled feature the strategy doesn't support.
+    pub(crate) fn validate(&self, strategy: LearningStrategyKind) -> Result<(), LearnerBuildError> {
+        let unsupported: &[(bool, &'static str)] = match strategy {
+            LearningStrategyKind::SingleDevice => &[],
+            LearningStrategyKind::MultiDevice => &[
+                (self.mixed_precision, "Mixed precision"),
+                (self.iteration_checkpoints, "Checkpointing iterations"),
+                (
+                    self.iteration_resume,
+                    "Resuming from an iteration checkpoint",
+                ),
+            ],
+            #[cfg(feature = "ddp")]
+            LearningStrategyKind::DistributedDataParallel => &[
+                (self.ema, "The exponential moving average of the model"),
+                (self.grad_norm, "The gradient norm metric"),
+                (self.mixed_precision, "Mixed precision"),
+                (self.callbacks, "Registering callbacks"),
+                (self.iteration_checkpoints, "Checkpointing iterations"),
+                (
+                    self.iteration_resume,
+                    "Resuming from an iteration checkpoint",
+                ),
+            ],
+        };
+
+        match unsupported.iter().find(|(enabled, _)| *enabled) {
+            Some((_, feature)) => Err(LearnerBuildError::UnsupportedFeature {
+                feature,
+                strategy: strategy.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Provides the `fit` function for any learning strategy
 pub(crate) trait LearningMethod<LC: LearnerComponentTypes> {
     /// The dataloaders after being prepared for this trainin strategy
@@ -70,26 +201,55 @@ pub(crate) trait LearningMethod<LC: LearnerComponentTypes> {
         dataloader_valid: ValidLoader<LC>,
     ) -> TrainingResult<LC::InnerModel> {
         let mut model = learner.model;
+        // Counted before training, since the returned model may not track gradients.
+        let num_params = (model.num_params(), model.num_trainable_params());
         let mut optim = learner.optim;
         let mut lr_scheduler = learner.lr_scheduler;
         let checkpoint = learner.checkpoint;
 
-        let starting_epoch = match checkpoint {
-            Some(checkpoint) => {
-                if let Some(checkpointer) = &mut learner.checkpointer {
-                    (model, optim, lr_scheduler) = checkpointer.load_checkpoint(
-                        model,
-                        optim,
-                        lr_scheduler,
-                        &Default::default(), // Load the checkpoint on the default device.
-                        checkpoint,
-                    );
-                }
-                checkpoint + 1
+        // Load the checkpoint on the default device.
+        let device = Default::default();
+        let mut state = None;
+
+        if let (Some(checkpoint), Some(checkpointer)) = (checkpoint, &mut learner.checkpointer) {
+            (model, optim, lr_scheduler) =
+                checkpointer.load_checkpoint(model, optim, lr_scheduler, &device, checkpoint);
+            state = checkpointer.load_state(&device, checkpoint);
+            if let Some(state) = &state {
+                checkpointer.resume(checkpoint, state.total_iterations);
             }
-            None => 1,
+        }
+
+        let starting_epoch = match (checkpoint, &state) {
+            // The epoch of an iteration checkpoint is resumed.
+            (Some(Checkpoint::Iteration(_)), Some(state)) => state.epoch,
+            (Some(Checkpoint::Iteration(iteration)), None) => {
+                panic!("The training state of iteration {iteration} is required to resume from it.")
+            }
+            (Some(Checkpoint::Epoch(epoch)), _) => epoch + 1,
+            _ => 1,
         };
 
+        // The average starts from the restored model when its checkpoint is missing, and is kept
+        // on the main device unless another device is configured.
+        let ema = learner.ema.as_ref().map(|(config, ema_device)| {
+            let ema_device = ema_device
+                .clone()
+                .unwrap_or_else(|| learner.learning_strategy.devices()[0].clone());
+            let ema = config.init(&model).to_device(&ema_device);
+            match (checkpoint, &learner.checkpointer) {
+                (Some(checkpoint), Some(checkpointer)) => {
+                    checkpointer.load_ema(ema, &ema_device, checkpoint)
+                }
+                _ => ema,
+            }
+        });
+
+        let mut run = learner.run;
+        if let Some(run) = run.as_mut() {
+            run.start();
+        }
+
         let dataloaders = self.prepare_dataloaders(dataloader_train, dataloader_valid);
         let model = self.prepare_model(model);
 
@@ -100,10 +260,15 @@ pub(crate) trait LearningMethod<LC: LearnerComponentTypes> {
             num_epochs: learner.num_epochs,
             checkpointer: learner.checkpointer,
             grad_accumulation: learner.grad_accumulation,
+            ema,
+            grad_norm: learner.grad_norm,
+            mixed_precision: learner.mixed_precision,
             interrupter: learner.interrupter,
             early_stopping: learner.early_stopping,
             event_processor: learner.event_processor,
             event_store: learner.event_store,
+            state,
+            callbacks: learner.callbacks,
         };
         let (model, mut event_processor) =
             self.learn(model, dataloaders, starting_epoch, components);
@@ -111,10 +276,18 @@ pub(crate) trait LearningMethod<LC: LearnerComponentTypes> {
         // Signal training end. For the TUI renderer, this handles the exit & return to main screen.
         event_processor.process_train(LearnerEvent::End);
 
+        if let Some(run) = run {
+            run.end();
+        }
+
         let summary = learner.summary.and_then(|summary| {
             summary
                 .init()
-                .map(|summary| summary.with_model(model.to_string()))
+                .map(|summary| {
+                    summary
+                        .with_model(model.to_string())
+                        .with_num_params(num_params.0, num_params.1)
+                })
                 .ok()
         });
 
@@ -157,9 +330,231 @@ pub(crate) struct LearnerComponents<LC: LearnerComponentTypes> {
     pub lr_scheduler: LC::LrScheduler,
     pub num_epochs: usize,
     pub grad_accumulation: Option<usize>,
+    pub ema: Option<EmaModule<LC::Backend, LC::Model>>,
+    pub grad_norm: bool,
+    pub mixed_precision: Option<MixedPrecisionConfig>,
     pub checkpointer: Option<LearnerCheckpointer<LC>>,
     pub interrupter: Interrupter,
     pub early_stopping: Option<EarlyStoppingStrategyRef>,
     pub event_processor: LC::EventProcessor,
     pub event_store: Arc<EventStoreClient>,
+    /// The training state of the loaded checkpoint, if any.
+    pub state: Option<TrainingState>,
+    pub callbacks: CallbackList<LC::Model, LC::Optimizer>,
+}
+
+/// The number of iterations done before the starting epoch, for the strategies that don't
+/// checkpoint iterations, which can only be resumed from an epoch checkpoint.
+pub(crate) fn resumed_iterations(state: Option<TrainingState>) -> usize {
+    state.map_or(0, |state| state.total_iterations)
+}
+
+/// Provide the value of the metric observed by the [learning rate scheduler](LrScheduler), if any,
+/// aggregated over the given epoch.
+///
+/// # Notes
+///
+/// The metric should be registered, otherwise no data is collected and the scheduler isn't updated.
+pub(crate) fn observe_lr_scheduler<S: LrScheduler>(
+    scheduler: &mut S,
+    epoch: usize,
+    store: &EventStoreClient,
+) {
+    let Some(metric) = scheduler.observed_metric() else {
+        return;
+    };
+
+    match store.find_metric(&metric.name, epoch, Aggregate::Mean, metric.split.into()) {
+        Some(value) => scheduler.observe(value),
+        None => log::warn!(
+            "Can't find metric {} for the learning rate scheduler.",
+            metric.name
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use burn_core::lr_scheduler::{
+        MetricSplit, SchedulerMetric,
+        plateau::{ReduceLrOnPlateau, ReduceLrOnPlateauConfig},
+    };
+
+    use crate::{
+        TestBackend,
+        logger::InMemoryMetricLogger,
+        metric::{
+            LossMetric, Metric,
+            processor::{
+                MetricsTraining, MinimalEventProcessor,
+                test_utils::{end_epoch, process_train},
+            },
+            store::LogEventStore,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn single_device_should_support_all_features() {
+        let features = LearnerFeatures {
+            ema: true,
+            grad_norm: true,
+            mixed_precision: true,
+            callbacks: true,
+            iteration_checkpoints: true,
+            iteration_resume: true,
+        };
+
+        assert_eq!(
+            features.validate(LearningStrategyKind::SingleDevice),
+            Ok(())
+        );
+    }
+
+    #[cfg(feature = "ddp")]
+    #[test]
+    fn ddp_should_reject_ema() {
+        let features = LearnerFeatures {
+            ema: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            features.validate(LearningStrategyKind::DistributedDataParallel),
+            Err(LearnerBuildError::UnsupportedFeature {
+                feature: "The exponential moving average of the model",
+                strategy: "distributed data parallel".into(),
+            })
+        );
+        assert_eq!(
+            LearnerFeatures::default().validate(LearningStrategyKind::DistributedDataParallel),
+            Ok(())
+        );
+    }
+
+    #[cfg(feature = "ddp")]
+    #[test]
+    fn ddp_should_reject_grad_norm() {
+        let features = LearnerFeatures {
+            grad_norm: true,
+            ..Default::default()
+        };
+
+        assert!(
+            features
+                .validate(LearningStrategyKind::DistributedDataParallel)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn multi_device_should_reject_mixed_precision() {
+        let features = LearnerFeatures {
+            mixed_precision: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            features.validate(LearningStrategyKind::MultiDevice),
+            Err(LearnerBuildError::UnsupportedFeature {
+                feature: "Mixed precision",
+                strategy: "multiple devices".into(),
+            })
+        );
+        #[cfg(feature = "ddp")]
+        assert!(
+            features
+                .validate(LearningStrategyKind::DistributedDataParallel)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn multi_device_should_reject_iteration_checkpoints() {
+        let checkpoints = LearnerFeatures {
+            iteration_checkpoints: true,
+            ..Default::default()
+        };
+        let resume = LearnerFeatures {
+            iteration_resume: true,
+            ..Default::default()
+        };
+        let callbacks = LearnerFeatures {
+            callbacks: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            checkpoints.validate(LearningStrategyKind::MultiDevice),
+            Err(LearnerBuildError::UnsupportedFeature {
+                feature: "Checkpointing iterations",
+                strategy: "multiple devices".into(),
+            })
+        );
+        assert!(resume.validate(LearningStrategyKind::MultiDevice).is_err());
+        assert_eq!(
+            callbacks.validate(LearningStrategyKind::MultiDevice),
+            Ok(())
+        );
+    }
+
+    #[cfg(feature = "ddp")]
+    #[test]
+    fn ddp_should_reject_callbacks_and_iteration_checkpoints() {
+        let features = [
+            LearnerFeatures {
+                callbacks: true,
+                ..Default::default()
+            },
+            LearnerFeatures {
+                iteration_checkpoints: true,
+                ..Default::default()
+            },
+            LearnerFeatures {
+                iteration_resume: true,
+                ..Default::default()
+            },
+        ];
+
+        for features in features {
+            assert!(
+                features
+                    .validate(LearningStrategyKind::DistributedDataParallel)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn lr_scheduler_should_observe_epoch_metric() {
+        let loss = LossMetric::<TestBackend>::new();
+        let metric = SchedulerMetric::new(loss.name().to_string()).with_split(MetricSplit::Train);
+        let mut scheduler: ReduceLrOnPlateau = ReduceLrOnPlateauConfig::new(0.1, metric)
+            .with_patience(0)
+            .init()
+            .unwrap();
+        let mut store = LogEventStore::default();
+        let mut metrics = MetricsTraining::<f64, f64>::default();
+
+        store.register_logger_train(InMemoryMetricLogger::default());
+        metrics.register_train_metric_numeric(loss);
+
+        let store = Arc::new(EventStoreClient::new(store));
+        let mut processor = MinimalEventProcessor::new(metrics, store.clone());
+
+        let mut lrs = Vec::new();
+        for (epoch, points) in [[1.0, 0.5], [0.5, 0.3], [0.5, 0.5]].iter().enumerate() {
+            let epoch = epoch + 1;
+            for point in points {
+                process_train(&mut processor, *point, epoch);
+            }
+            end_epoch(&mut processor, epoch);
+
+            observe_lr_scheduler(&mut scheduler, epoch, &store);
+            lrs.push(scheduler.step());
+        }
+
+        assert_eq!(lrs, [0.1, 0.1, 0.1 * 0.1]);
+    }
 }
@@ -6,6 +6,7 @@ use std::marker::PhantomData;
 use std::sync::mpsc::{Receiver, SyncSender};
 use std::sync::{Arc, Mutex};
 
+use crate::checkpoint::TrainingState;
 use crate::metric::processor::{EventProcessorTraining, LearnerEvent, LearnerItem};
 use crate::{TrainLoader, TrainStep, ValidLoader, ValidStep};
 use crate::{components::LearnerComponentTypes, learner::base::Interrupter};
@@ -25,6 +26,8 @@ pub struct DdpTrainEpoch<LC: LearnerComponentTypes> {
     epoch: usize,
     epoch_total: usize,
     grad_accumulation: Option<usize>,
+    #[new(default)]
+    total_iterations: usize,
 }
 
 impl<LC: LearnerComponentTypes> DdpValidEpoch<LC> {
@@ -72,6 +75,18 @@ impl<LC: LearnerComponentTypes> DdpValidEpoch<LC> {
 }
 
 impl<LC: LearnerComponentTypes> DdpTrainEpoch<LC> {
+    /// Resume the count of iterations after the given number of iterations done before the
+    /// starting epoch.
+    pub fn resume(mut self, total_iterations: usize) -> Self {
+        self.total_iterations = total_iterations;
+        self
+    }
+
+    /// The state of the training at the end of the given epoch.
+    pub fn epoch_state(&self, epoch: usize) -> TrainingState {
+        TrainingState::epoch(epoch, self.total_iterations)
+    }
+
     /// Runs the training epoch.
     ///
     /// # Arguments
@@ -109,6 +124,7 @@ impl<LC: LearnerComponentTypes> DdpTrainEpoch<LC> {
             let mut lr = 0.;
             for _ in 0..peer_count {
                 iteration += 1;
+                self.total_iterations += 1;
                 lr = scheduler.step();
             }
             log::info!("Iteration {iteration}");
@@ -6,6 +6,7 @@ use burn_collective::CollectiveConfig;
 use burn_core::prelude::Backend;
 
 use crate::learner::strategies::ddp::DdpWorker;
+use crate::learner::strategies::resumed_iterations;
 use crate::{LearnerComponents, LearningMethod, TrainLoader, ValidLoader};
 use burn_core::data::dataloader::split::split_dataloader;
 use burn_core::module::Module;
@@ -61,6 +62,8 @@ impl<LC: LearnerComponentTypes + Send + 'static> LearningMethod<LC> for DdpLearn
     ) -> (LC::Model, LC::EventProcessor) {
         let (mut dataloaders_train, dataloader_valid) = dataloaders;
         let model: LC::Model = model;
+        // Iterations aren't checkpointed with DDP.
+        let total_iterations = resumed_iterations(components.state);
 
         // The reference model is always on the first device provided.
         let main_device = self.devices[0].clone();
@@ -84,6 +87,7 @@ impl<LC: LearnerComponentTypes + Send + 'static> LearningMethod<LC> for DdpLearn
             Some(dataloader_valid),
             self.config.clone(),
             starting_epoch,
+            total_iterations,
             components.num_epochs,
             components.grad_accumulation,
             peer_count,
@@ -109,6 +113,7 @@ impl<LC: LearnerComponentTypes + Send + 'static> LearningMethod<LC> for DdpLearn
                 None,
                 self.config.clone(),
                 starting_epoch,
+                total_iterations,
                 components.num_epochs,
                 components.grad_accumulation,
                 peer_count,
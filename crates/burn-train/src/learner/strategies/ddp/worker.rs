@@ -1,6 +1,6 @@
 use crate::components::{LearnerComponentTypes, TrainBackend};
 use crate::ddp::epoch::DdpValidEpoch;
-use crate::learner::strategies::ddp;
+use crate::learner::strategies::{ddp, observe_lr_scheduler};
 use crate::metric::store::EventStoreClient;
 use crate::{EarlyStoppingStrategyRef, Interrupter, LearnerCheckpointer, TrainLoader, ValidLoader};
 use burn_collective::{self, CollectiveConfig, PeerId};
@@ -30,6 +30,7 @@ where
     dataloader_valid: Option<ValidLoader<LC>>,
     collective_config: CollectiveConfig,
     starting_epoch: usize,
+    total_iterations: usize,
     num_epochs: usize,
     grad_accumulation: Option<usize>,
     peer_count: usize,
@@ -58,6 +59,7 @@ where
         dataloader_valid: Option<ValidLoader<LC>>,
         collective_config: CollectiveConfig,
         starting_epoch: usize,
+        total_iterations: usize,
         num_epochs: usize,
         grad_accumulation: Option<usize>,
         peer_count: usize,
@@ -78,6 +80,7 @@ where
             dataloader_valid,
             collective_config,
             starting_epoch,
+            total_iterations,
             num_epochs,
             grad_accumulation,
             peer_count,
@@ -103,7 +106,8 @@ where
             self.starting_epoch,
             self.num_epochs,
             self.grad_accumulation,
-        );
+        )
+        .resume(self.total_iterations);
 
         for epoch in self.starting_epoch..self.num_epochs + 1 {
             (self.model, self.optim) = epoch_train.run(
@@ -129,12 +133,15 @@ where
                 epoch_valid.run(&self.model, &mut event_processor, &self.interrupter);
             }
 
+            observe_lr_scheduler(&mut self.lr_scheduler, epoch, &self.event_store);
+
             if let Some(checkpointer) = &mut self.checkpointer {
                 checkpointer.checkpoint(
                     &self.model,
                     &self.optim,
                     &self.lr_scheduler,
-                    epoch,
+                    None,
+                    epoch_train.epoch_state(epoch),
                     &self.event_store,
                 );
             }
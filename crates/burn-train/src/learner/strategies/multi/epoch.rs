@@ -1,7 +1,12 @@
+use crate::checkpoint::TrainingState;
 use crate::metric::processor::{EventProcessorTraining, LearnerEvent, LearnerItem};
-use crate::{MultiDevicesTrainStep, TrainLoader, TrainStep};
+use crate::metric::store::EventStoreClient;
+use crate::{CallbackContext, CallbackList, MultiDevicesTrainStep, TrainLoader, TrainStep};
 use crate::{components::LearnerComponentTypes, learner::base::Interrupter};
-use burn_core::{lr_scheduler::LrScheduler, optim::GradientsAccumulator, tensor::backend::Backend};
+use burn_core::{
+    lr_scheduler::LrScheduler, module::EmaModule, optim::GradientsAccumulator,
+    tensor::backend::Backend,
+};
 
 /// A training epoch.
 #[derive(new)]
@@ -10,9 +15,24 @@ pub struct MultiDeviceTrainEpoch<LC: LearnerComponentTypes> {
     epoch: usize,
     epoch_total: usize,
     grad_accumulation: Option<usize>,
+    grad_norm: bool,
+    #[new(default)]
+    total_iterations: usize,
 }
 
 impl<LC: LearnerComponentTypes> MultiDeviceTrainEpoch<LC> {
+    /// Resume the count of iterations after the given number of iterations done before the
+    /// starting epoch.
+    pub fn resume(mut self, total_iterations: usize) -> Self {
+        self.total_iterations = total_iterations;
+        self
+    }
+
+    /// The state of the training at the end of the given epoch.
+    pub fn epoch_state(&self, epoch: usize) -> TrainingState {
+        TrainingState::epoch(epoch, self.total_iterations)
+    }
+
     /// Runs the training epoch on multiple devices.
     ///
     /// # Arguments
@@ -22,10 +42,20 @@ impl<LC: LearnerComponentTypes> MultiDeviceTrainEpoch<LC> {
     /// * `lr_scheduler` - The learning rate scheduler to use.
     /// * `processor` - The event processor to use.
     /// * `devices` - The devices to use.
+    /// * `ema` - The moving average of the model to update after each optimizer step, if any.
+    /// * `callbacks` - The callbacks called around each iteration.
+    /// * `store` - The event store given to the callbacks.
+    ///
+    /// # Notes
+    ///
+    /// The items of all the devices are computed in parallel before their iterations are
+    /// processed, so the step hooks of an iteration are called after its gradients are computed,
+    /// and changes to the model in `before_step` only affect the items of the next devices steps.
     ///
     /// # Returns
     ///
     /// The trained model and the optimizer.
+    #[allow(clippy::too_many_arguments)]
     pub fn run(
         &mut self,
         mut model: LC::Model,
@@ -33,7 +63,10 @@ impl<LC: LearnerComponentTypes> MultiDeviceTrainEpoch<LC> {
         lr_scheduler: &mut LC::LrScheduler,
         processor: &mut LC::EventProcessor,
         devices: Vec<<LC::Backend as Backend>::Device>,
+        mut ema: Option<&mut EmaModule<LC::Backend, LC::Model>>,
         interrupter: &Interrupter,
+        callbacks: &mut CallbackList<LC::Model, LC::Optimizer>,
+        store: &EventStoreClient,
     ) -> (LC::Model, LC::Optimizer) {
         log::info!(
             "Executing training step for epoch {} on devices {:?}",
@@ -65,20 +98,46 @@ impl<LC: LearnerComponentTypes> MultiDeviceTrainEpoch<LC> {
 
             for item in items {
                 iteration += 1;
-                let lr = lr_scheduler.step();
+                self.total_iterations += 1;
+                let mut lr = lr_scheduler.step();
+
+                if !callbacks.is_empty() {
+                    let mut context =
+                        CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                            .with_iteration(iteration, lr);
+                    callbacks.call(&mut context, |callback, context| {
+                        callback.before_step(context)
+                    });
+                    lr = context.lr.unwrap_or(lr);
+                }
 
                 let grads = item.grads.to_device(&device_main, &model);
+                let grad_norm = self
+                    .grad_norm
+                    .then(|| grads.l2_norm::<LC::Backend, LC::Model>(&model));
 
                 accumulator.accumulate(&model, grads);
                 accumulation_current += 1;
 
                 if accumulation <= accumulation_current {
-                    let grads = accumulator.grads();
+                    let mut grads = accumulator.grads();
+                    if !callbacks.is_empty() {
+                        let mut context =
+                            CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                                .with_iteration(iteration, lr);
+                        callbacks.call(&mut context, |callback, context| {
+                            callback.before_optimizer_step(&mut grads, context)
+                        });
+                    }
                     model = model.optimize(&mut optim, lr, grads);
                     accumulation_current = 0;
+
+                    if let Some(ema) = ema.as_deref_mut() {
+                        ema.update(&model);
+                    }
                 }
 
-                let item = LearnerItem::new(
+                let mut item = LearnerItem::new(
                     item.item,
                     progress.clone(),
                     self.epoch,
@@ -86,9 +145,19 @@ impl<LC: LearnerComponentTypes> MultiDeviceTrainEpoch<LC> {
                     iteration,
                     Some(lr),
                 );
+                item.grad_norm = grad_norm;
 
                 processor.process_train(LearnerEvent::ProcessedItem(item));
 
+                if !callbacks.is_empty() {
+                    let mut context =
+                        CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                            .with_iteration(iteration, lr);
+                    callbacks.call(&mut context, |callback, context| {
+                        callback.after_step(context)
+                    });
+                }
+
                 if interrupter.should_stop() {
                     log::info!("Training interrupted.");
                     interrupted = true;
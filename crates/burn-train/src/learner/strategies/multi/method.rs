@@ -1,6 +1,9 @@
 use crate::{
-    LearnerComponents, LearningMethod, TrainLoader, ValidLoader, components::LearnerComponentTypes,
-    learner::strategies::single::epoch::SingleDeviceValidEpoch,
+    Checkpoint, LearnerComponents, LearningMethod, TrainLoader, ValidLoader,
+    components::LearnerComponentTypes,
+    learner::strategies::{
+        observe_lr_scheduler, resumed_iterations, single::epoch::SingleDeviceValidEpoch,
+    },
     multi::epoch::MultiDeviceTrainEpoch,
 };
 use burn_core::{data::dataloader::split::split_dataloader, module::Module, prelude::Backend};
@@ -56,50 +59,249 @@ impl<LC: LearnerComponentTypes> LearningMethod<LC> for MultiDeviceLearningStrate
             starting_epoch,
             components.num_epochs,
             components.grad_accumulation,
+            components.grad_norm,
+        )
+        // Iterations aren't checkpointed with multiple devices.
+        .resume(resumed_iterations(components.state.take()));
+
+        let main_device = self.devices.first().unwrap();
+        let mut ema = components.ema.take();
+        let callbacks = &mut components.callbacks;
+        let store = &components.event_store;
+
+        callbacks.call_epoch(
+            &mut model,
+            &mut components.optim,
+            store,
+            starting_epoch,
+            |callback, context| callback.on_train_start(context),
         );
 
+        let mut last_epoch = starting_epoch;
         for epoch in starting_epoch..components.num_epochs + 1 {
+            last_epoch = epoch;
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_epoch_start(context),
+            );
+
             (model, components.optim) = epoch_train.run(
                 model,
                 components.optim,
                 &mut components.lr_scheduler,
                 &mut components.event_processor,
                 self.devices.to_vec(),
+                ema.as_mut(),
                 &components.interrupter,
+                callbacks,
+                store,
             );
 
             if components.interrupter.should_stop() {
                 break;
             }
 
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_valid_start(context),
+            );
+
             let epoch_valid = SingleDeviceValidEpoch::<LC>::new(
                 dataloader_valid.clone(),
                 epoch,
                 components.num_epochs,
             );
+            // The average can be kept on another device than the model.
+            let ema_model = ema
+                .as_ref()
+                .map(|ema| ema.module().clone().to_device(main_device));
             epoch_valid.run(
-                &model,
+                ema_model.as_ref().unwrap_or(&model),
                 &mut components.event_processor,
                 &components.interrupter,
             );
 
-            if let Some(checkpointer) = &mut components.checkpointer {
-                checkpointer.checkpoint(
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_valid_end(context),
+            );
+
+            observe_lr_scheduler(&mut components.lr_scheduler, epoch, store);
+
+            if let Some(checkpointer) = &mut components.checkpointer
+                && checkpointer.checkpoint(
                     &model,
                     &components.optim,
                     &components.lr_scheduler,
+                    ema.as_ref(),
+                    epoch_train.epoch_state(epoch),
+                    store,
+                )
+            {
+                callbacks.call_epoch(
+                    &mut model,
+                    &mut components.optim,
+                    store,
                     epoch,
-                    &components.event_store,
+                    |callback, context| callback.on_checkpoint(Checkpoint::Epoch(epoch), context),
                 );
             }
 
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_epoch_end(context),
+            );
+
             if let Some(early_stopping) = &mut components.early_stopping
-                && early_stopping.should_stop(epoch, &components.event_store)
+                && early_stopping.should_stop(epoch, store)
             {
                 break;
             }
         }
 
+        callbacks.call_epoch(
+            &mut model,
+            &mut components.optim,
+            store,
+            last_epoch,
+            |callback, context| callback.on_train_end(context),
+        );
+
+        let model = match ema {
+            Some(ema) => ema.into_module().to_device(main_device),
+            None => model,
+        };
+
         (model, components.event_processor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        LearnerBuilder, LearningStrategy, RegressionOutput, TestBackend, TrainOutput, TrainStep,
+        ValidStep,
+        checkpoint::{Checkpointer, FileCheckpointer, TrainingState},
+        renderer::cli::CliMetricsRenderer,
+    };
+    use burn_autodiff::Autodiff;
+    use burn_core as burn;
+    use burn_core::{
+        data::{
+            dataloader::{DataLoaderBuilder, batcher::Batcher},
+            dataset::InMemDataset,
+        },
+        nn::{Linear, LinearConfig},
+        optim::SgdConfig,
+        record::CompactRecorder,
+        tensor::{Tensor, TensorData, backend::AutodiffBackend},
+    };
+    use std::path::Path;
+
+    type TestAutodiffBackend = Autodiff<TestBackend>;
+
+    #[derive(Module, Debug)]
+    struct TestModel<B: Backend> {
+        linear: Linear<B>,
+    }
+
+    impl<B: Backend> TestModel<B> {
+        fn forward(&self, inputs: Tensor<B, 2>) -> RegressionOutput<B> {
+            let output = self.linear.forward(inputs);
+            let loss = output.clone().powi_scalar(2).mean();
+            RegressionOutput::new(loss, output.clone(), output)
+        }
+    }
+
+    impl<B: AutodiffBackend> TrainStep<Tensor<B, 2>, RegressionOutput<B>> for TestModel<B> {
+        fn step(&self, inputs: Tensor<B, 2>) -> TrainOutput<RegressionOutput<B>> {
+            let item = self.forward(inputs);
+            TrainOutput::new(self, item.loss.backward(), item)
+        }
+    }
+
+    impl<B: Backend> ValidStep<Tensor<B, 2>, RegressionOutput<B>> for TestModel<B> {
+        fn step(&self, inputs: Tensor<B, 2>) -> RegressionOutput<B> {
+            self.forward(inputs)
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestBatcher;
+
+    impl<B: Backend> Batcher<B, [f32; 2], Tensor<B, 2>> for TestBatcher {
+        fn batch(&self, items: Vec<[f32; 2]>, device: &B::Device) -> Tensor<B, 2> {
+            let num_items = items.len();
+            Tensor::from_data(TensorData::new(items.concat(), [num_items, 2]), device)
+        }
+    }
+
+    fn fit(directory: &Path, num_epochs: usize, checkpoint: Option<usize>) {
+        let device = Default::default();
+        let items = (0..8).map(|i| [i as f32, 1.0]).collect::<Vec<_>>();
+        let dataloader_train = DataLoaderBuilder::<TestAutodiffBackend, _, _>::new(TestBatcher)
+            .batch_size(2)
+            .build(InMemDataset::new(items.clone()));
+        let dataloader_valid = DataLoaderBuilder::<TestBackend, _, _>::new(TestBatcher)
+            .batch_size(2)
+            .build(InMemDataset::new(items));
+
+        let mut builder = LearnerBuilder::new(directory)
+            .with_file_checkpointer(CompactRecorder::new())
+            .learning_strategy(LearningStrategy::MultiDeviceNaive(vec![device, device]))
+            .renderer(CliMetricsRenderer::new())
+            .num_epochs(num_epochs);
+        if let Some(checkpoint) = checkpoint {
+            builder = builder.checkpoint(checkpoint);
+        }
+        let learner = builder.build(
+            TestModel {
+                linear: LinearConfig::new(2, 1).init(&device),
+            },
+            SgdConfig::new().init(),
+            1e-3,
+        );
+
+        learner.fit(dataloader_train, dataloader_valid);
+    }
+
+    fn state(directory: &Path, epoch: usize) -> TrainingState {
+        let checkpointer = FileCheckpointer::new(
+            CompactRecorder::new(),
+            directory.join("checkpoint"),
+            "state",
+        );
+        Checkpointer::<TrainingState, TestAutodiffBackend>::restore(
+            &checkpointer,
+            epoch,
+            &Default::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_count_iterations_when_resuming_on_multiple_devices() {
+        let directory = tempfile::tempdir().unwrap();
+
+        fit(directory.path(), 2, None);
+        let iterations_per_epoch = state(directory.path(), 1).total_iterations;
+        assert_eq!(iterations_per_epoch, 4);
+        assert_eq!(state(directory.path(), 2).total_iterations, 8);
+
+        fit(directory.path(), 3, Some(2));
+        assert_eq!(state(directory.path(), 3).total_iterations, 12);
+    }
+}
@@ -1,11 +1,21 @@
+use burn_core::amp::{AutocastPolicy, GradScaler, MixedPrecisionConfig};
 use burn_core::data::dataloader::DataLoader;
 use burn_core::tensor::backend::AutodiffBackend;
-use burn_core::{lr_scheduler::LrScheduler, module::AutodiffModule, optim::GradientsAccumulator};
+use burn_core::{
+    lr_scheduler::LrScheduler,
+    module::{AutodiffModule, EmaModule},
+    optim::GradientsAccumulator,
+};
 use std::sync::Arc;
 
+use crate::checkpoint::TrainingState;
 use crate::components::OutputTrain;
 use crate::metric::processor::{EventProcessorTraining, LearnerEvent, LearnerItem};
-use crate::{TrainStep, ValidLoader, ValidStep};
+use crate::metric::store::EventStoreClient;
+use crate::{
+    CallbackContext, CallbackList, Checkpoint, LearnerCheckpointer, TrainStep, ValidLoader,
+    ValidStep,
+};
 use crate::{components::LearnerComponentTypes, learner::base::Interrupter};
 
 /// A validation epoch.
@@ -23,6 +33,17 @@ pub struct SingleDeviceTrainEpoch<B: AutodiffBackend, TI> {
     epoch: usize,
     epoch_total: usize,
     grad_accumulation: Option<usize>,
+    grad_norm: bool,
+    #[new(default)]
+    total_iterations: usize,
+    #[new(default)]
+    resume: Option<TrainingState>,
+    #[new(default)]
+    autocast: Option<AutocastPolicy>,
+    #[new(default)]
+    scaler: Option<GradScaler>,
+    #[new(default)]
+    warned_unscaled_loss: bool,
 }
 
 impl<LC: LearnerComponentTypes> SingleDeviceValidEpoch<LC> {
@@ -70,6 +91,37 @@ impl<LC: LearnerComponentTypes> SingleDeviceValidEpoch<LC> {
 }
 
 impl<B: AutodiffBackend, TI> SingleDeviceTrainEpoch<B, TI> {
+    /// Resume the training from the state of a checkpoint, in the middle of its epoch when it
+    /// was taken after an iteration.
+    ///
+    /// The state of the gradient scaler is restored when training with
+    /// [mixed precision](Self::mixed_precision), which should be enabled first.
+    pub fn resume(mut self, state: TrainingState) -> Self {
+        self.total_iterations = state.total_iterations;
+        if let (Some(scaler), Some(scaler_state)) = (self.scaler.as_mut(), &state.grad_scaler) {
+            scaler.load_state(scaler_state);
+        }
+        if state.iteration > 0 {
+            self.resume = Some(state);
+        }
+        self
+    }
+
+    /// Train with automatic mixed precision, autocasting the training steps and scaling their
+    /// loss.
+    pub fn mixed_precision(mut self, config: &MixedPrecisionConfig) -> Self {
+        self.autocast = Some(config.policy());
+        self.scaler = Some(config.scaler.init());
+        self
+    }
+
+    /// The state of the training at the end of the given epoch.
+    pub fn epoch_state(&self, epoch: usize) -> TrainingState {
+        let mut state = TrainingState::epoch(epoch, self.total_iterations);
+        state.grad_scaler = self.scaler.as_ref().map(GradScaler::state);
+        state
+    }
+
     /// Runs the training epoch.
     ///
     /// # Arguments
@@ -78,17 +130,28 @@ impl<B: AutodiffBackend, TI> SingleDeviceTrainEpoch<B, TI> {
     /// * `optim` - The optimizer to use.
     /// * `scheduler` - The learning rate scheduler to use.
     /// * `processor` - The event processor to use.
+    /// * `ema` - The moving average of the model to update after each optimizer step, if any.
+    /// * `checkpointer` - The checkpointer saving the iterations requested by its strategy, if any.
+    /// * `callbacks` - The callbacks called around each iteration.
+    /// * `store` - The event store given to the checkpointing strategy.
+    /// * `device` - The device of the training.
     ///
     /// # Returns
     ///
     /// The trained model and the optimizer.
+    #[allow(clippy::too_many_arguments)]
     pub fn run<LC: LearnerComponentTypes<Backend = B>>(
         &mut self,
         mut model: LC::Model,
         mut optim: LC::Optimizer,
         scheduler: &mut LC::LrScheduler,
         processor: &mut LC::EventProcessor,
+        mut ema: Option<&mut EmaModule<B, LC::Model>>,
         interrupter: &Interrupter,
+        mut checkpointer: Option<&mut LearnerCheckpointer<LC>>,
+        callbacks: &mut CallbackList<LC::Model, LC::Optimizer>,
+        store: &EventStoreClient,
+        device: &B::Device,
     ) -> (LC::Model, LC::Optimizer)
     where
         LC::Model: TrainStep<TI, OutputTrain<LC>>,
@@ -96,34 +159,121 @@ impl<B: AutodiffBackend, TI> SingleDeviceTrainEpoch<B, TI> {
         log::info!("Executing training step for epoch {}", self.epoch,);
 
         // Single device / dataloader
-        let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
+        let (mut iterator, mut iteration) = match self.resume.take() {
+            Some(TrainingState {
+                iteration,
+                dataloader: Some(dataloader),
+                seed,
+                ..
+            }) => {
+                log::info!("Resuming epoch {} after iteration {iteration}", self.epoch);
+                if let Some(seed) = seed {
+                    B::seed(device, seed);
+                }
+                (self.dataloader.resume(&dataloader), iteration)
+            }
+            Some(_) => {
+                log::warn!(
+                    "The data loader state is missing, starting epoch {} again.",
+                    self.epoch
+                );
+                (self.dataloader.iter(), 0)
+            }
+            None => (self.dataloader.iter(), 0),
+        };
         let mut accumulator = GradientsAccumulator::new();
         let mut accumulation_current = 0;
 
         while let Some(item) = iterator.next() {
             iteration += 1;
-            let lr = scheduler.step();
+            self.total_iterations += 1;
+            let mut lr = scheduler.step();
             log::info!("Iteration {iteration}");
 
+            if !callbacks.is_empty() {
+                let mut context = CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                    .with_iteration(iteration, lr);
+                callbacks.call(&mut context, |callback, context| {
+                    callback.before_step(context)
+                });
+                lr = context.lr.unwrap_or(lr);
+            }
+
             let progress = iterator.progress();
-            let item = model.step(item);
+            let (item, loss_scaled) = {
+                let _autocast = self.autocast.map(AutocastPolicy::enable);
+                let loss_scale = self.scaler.as_ref().map(GradScaler::enable);
+                let item = model.step(item);
+                (item, loss_scale.is_some_and(|guard| guard.is_applied()))
+            };
 
-            match self.grad_accumulation {
-                Some(accumulation) => {
-                    accumulator.accumulate(&model, item.grads);
-                    accumulation_current += 1;
+            if self.scaler.is_some() && !loss_scaled && !self.warned_unscaled_loss {
+                self.warned_unscaled_loss = true;
+                log::warn!(
+                    "The training step doesn't scale its loss with `burn::amp::scale_loss`, its gradients are used without loss scaling."
+                );
+            }
 
-                    if accumulation <= accumulation_current {
-                        let grads = accumulator.grads();
-                        model = model.optimize(&mut optim, lr, grads);
-                        accumulation_current = 0;
+            // The gradients are only unscaled when the step scaled its loss, and the iterations
+            // with non-finite gradients are skipped, with a smaller loss scale.
+            let grads = match self.scaler.as_mut().filter(|_| loss_scaled) {
+                Some(scaler) => {
+                    let grads = scaler.unscale::<B, LC::Model>(&model, item.grads);
+                    if grads.is_none() {
+                        scaler.update(false);
+                        log::warn!(
+                            "Skipping iteration {iteration} with non-finite gradients, the loss scale is now {}",
+                            scaler.scale_value()
+                        );
+                    }
+                    grads
+                }
+                None => Some(item.grads),
+            };
+            let grad_norm = grads.as_ref().and_then(|grads| {
+                self.grad_norm
+                    .then(|| grads.l2_norm::<B, LC::Model>(&model))
+            });
+
+            if let Some(grads) = grads {
+                let grads = match self.grad_accumulation {
+                    Some(accumulation) => {
+                        accumulator.accumulate(&model, grads);
+                        accumulation_current += 1;
+
+                        if accumulation <= accumulation_current {
+                            accumulation_current = 0;
+                            Some(accumulator.grads())
+                        } else {
+                            None
+                        }
+                    }
+                    None => Some(grads),
+                };
+
+                if let Some(mut grads) = grads {
+                    if !callbacks.is_empty() {
+                        let mut context =
+                            CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                                .with_iteration(iteration, lr);
+                        callbacks.call(&mut context, |callback, context| {
+                            callback.before_optimizer_step(&mut grads, context)
+                        });
+                    }
+                    model = model.optimize(&mut optim, lr, grads);
+
+                    if let Some(scaler) = self.scaler.as_mut().filter(|_| loss_scaled) {
+                        scaler.update(true);
+                    }
+                    if let Some(ema) = ema.as_deref_mut() {
+                        ema.update(&model);
                     }
                 }
-                None => model = model.optimize(&mut optim, lr, item.grads),
             }
+            // Accumulated gradients aren't saved, so checkpoints are only taken after a step.
+            let checkpointable = accumulation_current == 0;
 
-            let item = LearnerItem::new(
+            let mut item = LearnerItem::new(
                 item.item,
                 progress,
                 self.epoch,
@@ -131,9 +281,48 @@ impl<B: AutodiffBackend, TI> SingleDeviceTrainEpoch<B, TI> {
                 iteration,
                 Some(lr),
             );
+            item.grad_norm = grad_norm;
 
             processor.process_train(LearnerEvent::ProcessedItem(item));
 
+            if !callbacks.is_empty() {
+                let mut context = CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                    .with_iteration(iteration, lr);
+                callbacks.call(&mut context, |callback, context| {
+                    callback.after_step(context)
+                });
+            }
+
+            if checkpointable && let Some(checkpointer) = checkpointer.as_deref_mut() {
+                let state = TrainingState {
+                    epoch: self.epoch,
+                    iteration,
+                    total_iterations: self.total_iterations,
+                    dataloader: iterator.state(),
+                    seed: None,
+                    grad_scaler: self.scaler.as_ref().map(GradScaler::state),
+                };
+                let saved = checkpointer.checkpoint_iteration(
+                    &model,
+                    &optim,
+                    scheduler,
+                    ema.as_deref(),
+                    state,
+                    device,
+                    store,
+                );
+
+                if saved && !callbacks.is_empty() {
+                    let checkpoint = Checkpoint::Iteration(self.total_iterations);
+                    let mut context =
+                        CallbackContext::new(&mut model, &mut optim, store, self.epoch)
+                            .with_iteration(iteration, lr);
+                    callbacks.call(&mut context, |callback, context| {
+                        callback.on_checkpoint(checkpoint, context)
+                    });
+                }
+            }
+
             if interrupter.should_stop() {
                 log::info!("Training interrupted.");
                 break;
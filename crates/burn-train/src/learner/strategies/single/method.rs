@@ -1,6 +1,7 @@
 use crate::{
-    LearnerComponents, LearningMethod, TrainLoader, ValidLoader,
+    Checkpoint, LearnerComponents, LearningMethod, TrainLoader, ValidLoader,
     components::LearnerComponentTypes,
+    learner::strategies::observe_lr_scheduler,
     learner::strategies::single::epoch::{SingleDeviceTrainEpoch, SingleDeviceValidEpoch},
 };
 use burn_core::{module::Module, tensor::Device};
@@ -54,49 +55,135 @@ impl<LC: LearnerComponentTypes> LearningMethod<LC> for SingleDeviceLearningStrat
             starting_epoch,
             components.num_epochs,
             components.grad_accumulation,
+            components.grad_norm,
+        );
+        if let Some(config) = &components.mixed_precision {
+            epoch_train = epoch_train.mixed_precision(config);
+        }
+        if let Some(state) = components.state.take() {
+            epoch_train = epoch_train.resume(state);
+        }
+
+        let mut ema = components.ema.take();
+        let callbacks = &mut components.callbacks;
+        let store = &components.event_store;
+
+        callbacks.call_epoch(
+            &mut model,
+            &mut components.optim,
+            store,
+            starting_epoch,
+            |callback, context| callback.on_train_start(context),
         );
 
+        let mut last_epoch = starting_epoch;
         for epoch in starting_epoch..components.num_epochs + 1 {
+            last_epoch = epoch;
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_epoch_start(context),
+            );
+
             (model, components.optim) = epoch_train.run::<LC>(
                 model,
                 components.optim,
                 &mut components.lr_scheduler,
                 &mut components.event_processor,
+                ema.as_mut(),
                 &components.interrupter,
+                components.checkpointer.as_mut(),
+                callbacks,
+                store,
+                &self.device,
             );
 
             if components.interrupter.should_stop() {
                 break;
             }
 
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_valid_start(context),
+            );
+
             let epoch_valid = SingleDeviceValidEpoch::<LC>::new(
                 dataloader_valid.clone(),
                 epoch,
                 components.num_epochs,
             );
+            // The average can be kept on another device than the model.
+            let ema_model = ema
+                .as_ref()
+                .map(|ema| ema.module().clone().to_device(&self.device));
             epoch_valid.run(
-                &model,
+                ema_model.as_ref().unwrap_or(&model),
                 &mut components.event_processor,
                 &components.interrupter,
             );
 
-            if let Some(checkpointer) = &mut components.checkpointer {
-                checkpointer.checkpoint(
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_valid_end(context),
+            );
+
+            observe_lr_scheduler(&mut components.lr_scheduler, epoch, store);
+
+            if let Some(checkpointer) = &mut components.checkpointer
+                && checkpointer.checkpoint(
                     &model,
                     &components.optim,
                     &components.lr_scheduler,
+                    ema.as_ref(),
+                    epoch_train.epoch_state(epoch),
+                    store,
+                )
+            {
+                callbacks.call_epoch(
+                    &mut model,
+                    &mut components.optim,
+                    store,
                     epoch,
-                    &components.event_store,
+                    |callback, context| callback.on_checkpoint(Checkpoint::Epoch(epoch), context),
                 );
             }
 
+            callbacks.call_epoch(
+                &mut model,
+                &mut components.optim,
+                store,
+                epoch,
+                |callback, context| callback.on_epoch_end(context),
+            );
+
             if let Some(early_stopping) = &mut components.early_stopping
-                && early_stopping.should_stop(epoch, &components.event_store)
+                && early_stopping.should_stop(epoch, store)
             {
                 break;
             }
         }
 
+        callbacks.call_epoch(
+            &mut model,
+            &mut components.optim,
+            store,
+            last_epoch,
+            |callback, context| callback.on_train_end(context),
+        );
+
+        let model = match ema {
+            Some(ema) => ema.into_module().to_device(&self.device),
+            None => model,
+        };
+
         (model, components.event_processor)
     }
 }
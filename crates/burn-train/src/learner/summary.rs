@@ -6,7 +6,10 @@ use std::{
 
 use crate::{
     logger::FileMetricLogger,
-    metric::store::{Aggregate, EventStore, LogEventStore, Split},
+    metric::{
+        ConfusionMatrix,
+        store::{Aggregate, EventStore, LogEventStore, Split},
+    },
 };
 
 /// Contains the metric value at a given time.
@@ -59,14 +62,38 @@ pub struct SummaryMetrics {
     pub valid: Vec<MetricSummary>,
 }
 
+/// Contains the confusion matrix of the last epoch of a split.
+pub struct ConfusionMatrixSummary {
+    /// The metric name.
+    pub name: String,
+    /// The epoch of the confusion matrix.
+    pub epoch: usize,
+    /// The confusion matrix accumulated over the epoch.
+    pub matrix: ConfusionMatrix,
+}
+
+/// Contains the confusion matrices recorded for the training and validation steps.
+#[derive(Default)]
+pub struct SummaryConfusionMatrices {
+    /// Training confusion matrices.
+    pub train: Vec<ConfusionMatrixSummary>,
+    /// Validation confusion matrices.
+    pub valid: Vec<ConfusionMatrixSummary>,
+}
+
 /// Detailed training summary.
 pub struct LearnerSummary {
     /// The number of epochs completed.
     pub epochs: usize,
     /// The summary of recorded metrics during training.
     pub metrics: SummaryMetrics,
+    /// The confusion matrices of the last epoch.
+    pub confusion_matrices: SummaryConfusionMatrices,
     /// The model name (only recorded within the learner).
     pub(crate) model: Option<String>,
+    /// The total and trainable number of parameters of the model (only recorded within the
+    /// learner).
+    pub(crate) num_params: Option<(usize, usize)>,
 }
 
 impl LearnerSummary {
@@ -124,14 +151,59 @@ impl LearnerSummary {
                 train: train_summary,
                 valid: valid_summary,
             },
+            confusion_matrices: Default::default(),
             model: None,
+            num_params: None,
         })
     }
 
+    /// Collects the confusion matrices of the last epoch among the given metrics, skipping the
+    /// metrics that aren't [confusion matrices](crate::metric::ConfusionMatrixMetric).
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory containing the training artifacts (checkpoints and logs).
+    /// * `metrics` - The list of metrics to collect for the summary.
+    pub fn with_confusion_matrices<S: AsRef<str>>(
+        mut self,
+        directory: impl AsRef<Path>,
+        metrics: &[S],
+    ) -> Self {
+        let directory = directory.as_ref();
+        let epoch = self.epochs;
+
+        let collect = |split: &str| {
+            let logger = FileMetricLogger::new_train(directory.join(split));
+            metrics
+                .iter()
+                .filter_map(|metric| {
+                    let entry = logger.read_last(metric.as_ref(), epoch)?;
+                    let matrix = ConfusionMatrix::deserialize(&entry).ok()?;
+                    Some(ConfusionMatrixSummary {
+                        name: metric.as_ref().to_string(),
+                        epoch,
+                        matrix,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        self.confusion_matrices = SummaryConfusionMatrices {
+            train: collect("train"),
+            valid: collect("valid"),
+        };
+        self
+    }
+
     pub(crate) fn with_model(mut self, name: String) -> Self {
         self.model = Some(name);
         self
     }
+
+    pub(crate) fn with_num_params(mut self, num_params: usize, num_trainable: usize) -> Self {
+        self.num_params = Some((num_params, num_trainable));
+        self
+    }
 }
 
 impl Display for LearnerSummary {
@@ -160,6 +232,13 @@ impl Display for LearnerSummary {
         if let Some(model) = &self.model {
             writeln!(f, "Model:\n{model}")?;
         }
+        if let Some((num_params, num_trainable)) = self.num_params {
+            writeln!(
+                f,
+                "Parameters: {num_params} ({num_trainable} trainable, {} non-trainable)",
+                num_params - num_trainable
+            )?;
+        }
         writeln!(f, "Total Epochs: {epochs}\n\n", epochs = self.epochs)?;
 
         // Metrics table header
@@ -232,6 +311,19 @@ impl Display for LearnerSummary {
         write_metrics_summary(&self.metrics.train, split_train)?;
         write_metrics_summary(&self.metrics.valid, split_valid)?;
 
+        for (matrices, split) in [
+            (&self.confusion_matrices.train, split_train),
+            (&self.confusion_matrices.valid, split_valid),
+        ] {
+            for summary in matrices.iter() {
+                writeln!(
+                    f,
+                    "\n{split} {} (epoch {}):\n{}",
+                    summary.name, summary.epoch, summary.matrix
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -239,11 +331,13 @@ impl Display for LearnerSummary {
 pub(crate) struct LearnerSummaryConfig {
     pub(crate) directory: PathBuf,
     pub(crate) metrics: Vec<String>,
+    pub(crate) text_metrics: Vec<String>,
 }
 
 impl LearnerSummaryConfig {
     pub fn init(&self) -> Result<LearnerSummary, String> {
         LearnerSummary::new(&self.directory, &self.metrics[..])
+            .map(|summary| summary.with_confusion_matrices(&self.directory, &self.text_metrics[..]))
     }
 }
 
@@ -322,4 +416,38 @@ mod tests {
 
         std::fs::remove_dir_all(dir).unwrap();
     }
+
+    #[test]
+    fn test_summary_should_collect_confusion_matrices() {
+        let dir = Path::new("/tmp/test-learner-summary-confusion-matrix");
+        let train_dir = dir.join("train/epoch-1");
+        let valid_dir = dir.join("valid/epoch-1");
+        std::fs::create_dir_all(&train_dir).unwrap();
+        std::fs::create_dir_all(&valid_dir).unwrap();
+
+        let mut matrix = ConfusionMatrix::new(2);
+        matrix.update(&[0, 1], &[0, 0]);
+        let first = matrix.serialize();
+        matrix.update(&[1], &[1]);
+        std::fs::write(
+            valid_dir.join("Confusion_Matrix.log"),
+            format!("{first}\n{}\n", matrix.serialize()),
+        )
+        .expect("Unable to write file");
+        std::fs::write(train_dir.join("Loss.log"), "1.0").expect("Unable to write file");
+
+        let summary = LearnerSummary::new(dir.to_str().unwrap(), &["Loss"])
+            .expect("Summary artifacts should exist")
+            .with_confusion_matrices(dir, &["Confusion Matrix", "Loss"]);
+
+        // The loss isn't a confusion matrix, and the matrix of the epoch is the last entry.
+        assert_eq!(summary.confusion_matrices.train.len(), 0);
+        assert_eq!(summary.confusion_matrices.valid.len(), 1);
+        let valid = &summary.confusion_matrices.valid[0];
+        assert_eq!(valid.name, "Confusion Matrix");
+        assert_eq!(valid.epoch, 1);
+        assert_eq!(valid.matrix, matrix);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -0,0 +1,45 @@
+use crate::metric::processor::ItemLazy;
+use crate::metric::text::TextGenerationInput;
+use crate::metric::{Adaptor, LossInput};
+use burn_core::tensor::Tensor;
+use burn_core::tensor::backend::Backend;
+use burn_ndarray::NdArray;
+
+/// Simple text generation output adapted for the loss and the text generation metrics.
+#[derive(new)]
+pub struct TextGenerationOutput<B: Backend> {
+    /// The loss.
+    pub loss: Tensor<B, 1>,
+
+    /// The generated sequences, decoded to text.
+    pub predictions: Vec<String>,
+
+    /// The reference texts.
+    pub references: Vec<String>,
+}
+
+impl<B: Backend> ItemLazy for TextGenerationOutput<B> {
+    type ItemSync = TextGenerationOutput<NdArray>;
+
+    fn sync(self) -> Self::ItemSync {
+        let device = &Default::default();
+
+        TextGenerationOutput {
+            loss: Tensor::from_data(self.loss.into_data(), device),
+            predictions: self.predictions,
+            references: self.references,
+        }
+    }
+}
+
+impl<B: Backend> Adaptor<LossInput<B>> for TextGenerationOutput<B> {
+    fn adapt(&self) -> LossInput<B> {
+        LossInput::new(self.loss.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<TextGenerationInput> for TextGenerationOutput<B> {
+    fn adapt(&self) -> TextGenerationInput {
+        TextGenerationInput::new(self.predictions.clone(), self.references.clone())
+    }
+}
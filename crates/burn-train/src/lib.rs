@@ -20,6 +20,12 @@ pub mod logger;
 /// The metric module.
 pub mod metric;
 
+/// The run module, to record the metrics and descriptions of training runs and compare them.
+pub mod run;
+
+/// The sweep module, to search the hyperparameters of a config.
+pub mod sweep;
+
 mod learner;
 
 pub use learner::*;
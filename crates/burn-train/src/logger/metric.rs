@@ -169,6 +169,24 @@ impl FileMetricLogger {
     }
 }
 
+impl FileMetricLogger {
+    /// Read the last logged item of a metric for an epoch, e.g. the state of a metric accumulated
+    /// over the epoch.
+    pub(crate) fn read_last(&self, name: &str, epoch: usize) -> Option<String> {
+        if let Some(value) = self.loggers.get(name) {
+            value.sync()
+        }
+
+        let file_path = self.file_path(None, name, Some(epoch));
+
+        std::fs::read_to_string(file_path)
+            .ok()?
+            .lines()
+            .rfind(|line| !line.is_empty())
+            .map(|line| line.to_string())
+    }
+}
+
 impl MetricLogger for FileMetricLogger {
     fn log(&mut self, item: &MetricEntry) {
         match item.tags.is_empty() {
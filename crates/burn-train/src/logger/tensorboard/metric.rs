@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::TensorBoardWriter;
+use crate::logger::{InMemoryMetricLogger, MetricLogger};
+use crate::metric::{MetricEntry, NumericEntry};
+
+/// Metric logger writing the numeric metrics to a [TensorBoard](TensorBoardWriter) event file.
+///
+/// Each value is written as a scalar tagged with the name of the metric and the split, e.g.
+/// `Loss/train`, with the number of values logged for the metric as step. At the end of each
+/// epoch, the value of the epoch is written with the `/epoch` suffix, e.g. `Loss/train/epoch`,
+/// with the epoch as step. It's the mean of the values of the epoch, or the last one for the
+/// [cumulative](NumericEntry::Cumulative) metrics.
+///
+/// The values are also kept in memory to be read back by the checkpointing and early stopping
+/// strategies, so the train and valid loggers can replace the default file loggers.
+pub struct TensorBoardMetricLogger {
+    writer: TensorBoardWriter,
+    split: String,
+    steps: BTreeMap<String, usize>,
+    epoch_values: BTreeMap<String, (f64, usize)>,
+    values: InMemoryMetricLogger,
+}
+
+impl TensorBoardMetricLogger {
+    /// Create a new TensorBoard metric logger for the training split.
+    pub fn new_train(directory: impl AsRef<Path>) -> Self {
+        Self::new(directory, "train")
+    }
+
+    /// Create a new TensorBoard metric logger for the validation split.
+    pub fn new_valid(directory: impl AsRef<Path>) -> Self {
+        Self::new(directory, "valid")
+    }
+
+    /// Create a new TensorBoard metric logger writing an event file in the given directory, with
+    /// the split appended to the tags of the metrics.
+    ///
+    /// # Panics
+    ///
+    /// If the event file can't be created.
+    pub fn new(directory: impl AsRef<Path>, split: &str) -> Self {
+        Self {
+            writer: TensorBoardWriter::with_suffix(directory, &format!(".{split}")),
+            split: split.to_string(),
+            steps: BTreeMap::new(),
+            epoch_values: BTreeMap::new(),
+            values: InMemoryMetricLogger::new(),
+        }
+    }
+
+    /// The writer of the event file, to log histograms or hyperparameters along the metrics.
+    pub fn writer(&mut self) -> &mut TensorBoardWriter {
+        &mut self.writer
+    }
+
+    fn tag(&self, item: &MetricEntry) -> String {
+        let mut tag = format!("{}/{}", item.name, self.split);
+        for item_tag in item.tags.iter() {
+            tag.push('/');
+            tag.push_str(item_tag);
+        }
+        tag
+    }
+}
+
+impl MetricLogger for TensorBoardMetricLogger {
+    fn log(&mut self, item: &MetricEntry) {
+        self.values.log(item);
+
+        // Only numeric metrics are written.
+        let Ok(entry) = NumericEntry::deserialize(&item.serialize) else {
+            return;
+        };
+        let value = entry.current();
+
+        let tag = self.tag(item);
+        let step = self.steps.entry(tag.clone()).or_default();
+        *step += 1;
+        self.writer.add_scalar(&tag, value, *step);
+
+        let (sum, num) = self.epoch_values.entry(tag).or_default();
+        match entry {
+            NumericEntry::Value(_) => {
+                *sum += value;
+                *num += 1;
+            }
+            NumericEntry::Aggregated { count, .. } => {
+                *sum += value * count as f64;
+                *num += count;
+            }
+            // The last entry is the value of the epoch.
+            NumericEntry::Cumulative(_) => {
+                *sum = value;
+                *num = 1;
+            }
+        }
+    }
+
+    fn end_epoch(&mut self, epoch: usize) {
+        for (tag, (sum, num)) in std::mem::take(&mut self.epoch_values) {
+            if num > 0 {
+                self.writer
+                    .add_scalar(&format!("{tag}/epoch"), sum / num as f64, epoch);
+            }
+        }
+        self.writer.flush();
+        self.values.end_epoch(epoch);
+    }
+
+    fn read_numeric(&mut self, name: &str, epoch: usize) -> Result<Vec<NumericEntry>, String> {
+        self.values.read_numeric(name, epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::tensorboard::proto::ProtoField;
+    use crate::logger::tensorboard::writer::tests::summary_values;
+    use std::sync::Arc;
+
+    fn entry(name: &str, serialize: &str) -> MetricEntry {
+        MetricEntry::new(
+            Arc::new(name.to_string()),
+            serialize.to_string(),
+            serialize.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_log_iterations_and_epochs() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut logger = TensorBoardMetricLogger::new_valid(directory.path());
+
+        logger.log(&entry("Loss", "1.0,2"));
+        logger.log(&entry("Loss", "4.0,1"));
+        logger.log(&entry("Learning Rate", "0.1"));
+        // Non-numeric metrics aren't written.
+        logger.log(&entry("Status", "running"));
+        logger.end_epoch(1);
+        logger.log(&entry("Loss", "0.5,1"));
+        logger.end_epoch(2);
+
+        let scalars = summary_values(logger.writer().path())
+            .into_iter()
+            .map(|(step, value)| {
+                let tag = value.iter().find(|(field, _)| *field == 1).unwrap();
+                let scalar = value.iter().find(|(field, _)| *field == 2).unwrap();
+                let ProtoField::Fixed32(scalar) = scalar.1 else {
+                    panic!("Expected a simple value");
+                };
+                (tag.1.as_str().to_string(), step, f32::from_bits(scalar))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            scalars,
+            vec![
+                ("Loss/valid".to_string(), 1, 1.0),
+                ("Loss/valid".to_string(), 2, 4.0),
+                ("Learning Rate/valid".to_string(), 1, 0.1),
+                ("Learning Rate/valid/epoch".to_string(), 1, 0.1),
+                // The mean is weighted by the number of items of each value.
+                ("Loss/valid/epoch".to_string(), 1, 2.0),
+                ("Loss/valid".to_string(), 3, 0.5),
+                ("Loss/valid/epoch".to_string(), 2, 0.5),
+            ]
+        );
+
+        let values = logger.read_numeric("Loss", 1).unwrap();
+        assert_eq!(values.len(), 2);
+    }
+}
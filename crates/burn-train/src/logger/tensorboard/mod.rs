@@ -0,0 +1,6 @@
+mod metric;
+mod proto;
+mod writer;
+
+pub use metric::*;
+pub use writer::*;
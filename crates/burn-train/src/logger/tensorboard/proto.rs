@@ -0,0 +1,208 @@
+//! Minimal protocol buffers encoding of the TensorBoard event messages.
+//!
+//! Only the fields written by the [writer](super::TensorBoardWriter) are encoded, following
+//! `tensorflow/core/util/event.proto`, `tensorflow/core/framework/summary.proto` and
+//! `tensorboard/plugins/hparams/plugin_data.proto`.
+
+const WIRE_VARINT: u32 = 0;
+const WIRE_FIXED64: u32 = 1;
+const WIRE_BYTES: u32 = 2;
+const WIRE_FIXED32: u32 = 5;
+
+/// Encodes the fields of a protocol buffers message.
+#[derive(Default)]
+pub(crate) struct ProtoEncoder {
+    buffer: Vec<u8>,
+}
+
+impl ProtoEncoder {
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    pub(crate) fn int64(&mut self, field: u32, value: i64) -> &mut Self {
+        self.key(field, WIRE_VARINT);
+        self.varint(value as u64);
+        self
+    }
+
+    pub(crate) fn double(&mut self, field: u32, value: f64) -> &mut Self {
+        self.key(field, WIRE_FIXED64);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn float(&mut self, field: u32, value: f32) -> &mut Self {
+        self.key(field, WIRE_FIXED32);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn bool(&mut self, field: u32, value: bool) -> &mut Self {
+        self.int64(field, value as i64)
+    }
+
+    pub(crate) fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Self {
+        self.key(field, WIRE_BYTES);
+        self.varint(value.len() as u64);
+        self.buffer.extend_from_slice(value);
+        self
+    }
+
+    pub(crate) fn string(&mut self, field: u32, value: &str) -> &mut Self {
+        self.bytes(field, value.as_bytes())
+    }
+
+    pub(crate) fn message(
+        &mut self,
+        field: u32,
+        encode: impl FnOnce(&mut ProtoEncoder),
+    ) -> &mut Self {
+        let mut message = ProtoEncoder::default();
+        encode(&mut message);
+        self.bytes(field, &message.buffer)
+    }
+
+    pub(crate) fn packed_doubles(&mut self, field: u32, values: &[f64]) -> &mut Self {
+        let bytes = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.bytes(field, &bytes)
+    }
+
+    fn key(&mut self, field: u32, wire_type: u32) {
+        self.varint(((field << 3) | wire_type) as u64);
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buffer.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buffer.push(value as u8);
+    }
+}
+
+/// A decoded field, used to verify the written events.
+#[cfg(test)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ProtoField {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(Vec<u8>),
+    Fixed32(u32),
+}
+
+#[cfg(test)]
+impl ProtoField {
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        match self {
+            ProtoField::Bytes(bytes) => bytes,
+            field => panic!("Expected a length-delimited field, got {field:?}"),
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        std::str::from_utf8(self.as_bytes()).unwrap()
+    }
+
+    pub(crate) fn as_double(&self) -> f64 {
+        match self {
+            ProtoField::Fixed64(value) => f64::from_bits(*value),
+            field => panic!("Expected a double field, got {field:?}"),
+        }
+    }
+
+    pub(crate) fn as_float(&self) -> f32 {
+        match self {
+            ProtoField::Fixed32(value) => f32::from_bits(*value),
+            field => panic!("Expected a float field, got {field:?}"),
+        }
+    }
+
+    pub(crate) fn as_varint(&self) -> u64 {
+        match self {
+            ProtoField::Varint(value) => *value,
+            field => panic!("Expected a varint field, got {field:?}"),
+        }
+    }
+}
+
+/// Decode the fields of a protocol buffers message, in order.
+#[cfg(test)]
+pub(crate) fn decode(mut bytes: &[u8]) -> Vec<(u32, ProtoField)> {
+    fn varint(bytes: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = bytes[0];
+            *bytes = &bytes[1..];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte < 0x80 {
+                return value;
+            }
+            shift += 7;
+        }
+    }
+
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let key = varint(&mut bytes);
+        let field = match key & 0x7 {
+            0 => ProtoField::Varint(varint(&mut bytes)),
+            1 => {
+                let (value, rest) = bytes.split_at(8);
+                bytes = rest;
+                ProtoField::Fixed64(u64::from_le_bytes(value.try_into().unwrap()))
+            }
+            2 => {
+                let len = varint(&mut bytes) as usize;
+                let (value, rest) = bytes.split_at(len);
+                bytes = rest;
+                ProtoField::Bytes(value.to_vec())
+            }
+            5 => {
+                let (value, rest) = bytes.split_at(4);
+                bytes = rest;
+                ProtoField::Fixed32(u32::from_le_bytes(value.try_into().unwrap()))
+            }
+            wire_type => panic!("Unsupported wire type {wire_type}"),
+        };
+        fields.push(((key >> 3) as u32, field));
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_encode_and_decode_fields() {
+        let mut encoder = ProtoEncoder::default();
+        encoder
+            .int64(1, 300)
+            .double(2, 1.5)
+            .float(3, -2.0)
+            .string(4, "tag")
+            .message(5, |message| {
+                message.bool(1, true);
+            });
+        let bytes = encoder.into_bytes();
+
+        // The varint 300 is encoded in two bytes.
+        assert_eq!(&bytes[..3], &[0x08, 0xac, 0x02]);
+
+        let fields = decode(&bytes);
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields[0], (1, ProtoField::Varint(300)));
+        assert_eq!(fields[1].1.as_double(), 1.5);
+        assert_eq!(fields[2].1.as_float(), -2.0);
+        assert_eq!(fields[3].1.as_str(), "tag");
+        assert_eq!(
+            decode(fields[4].1.as_bytes()),
+            vec![(1, ProtoField::Varint(1))]
+        );
+    }
+}
@@ -0,0 +1,685 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use burn_core::config::Config;
+use burn_core::module::{AutodiffModule, Module, ModuleVisitor, ParamId};
+use burn_core::optim::GradientsParams;
+use burn_core::tensor::Tensor;
+use burn_core::tensor::backend::{AutodiffBackend, Backend};
+
+use super::proto::ProtoEncoder;
+
+/// The number of buckets of the histograms.
+const HISTOGRAM_BUCKETS: usize = 30;
+
+/// Writes scalars, histograms and hyperparameters to a
+/// [TensorBoard](https://www.tensorflow.org/tensorboard) event file.
+///
+/// The file is written in the `tfevents` format read by TensorBoard, without any dependency on
+/// TensorFlow, and is named after the creation time so that multiple writers can share a
+/// directory.
+pub struct TensorBoardWriter {
+    file: BufWriter<File>,
+    path: PathBuf,
+}
+
+impl TensorBoardWriter {
+    /// Create a new event file in the given directory.
+    ///
+    /// # Panics
+    ///
+    /// If the directory or the file can't be created.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self::with_suffix(directory, "")
+    }
+
+    /// Create a new event file in the given directory, with the given suffix appended to its name.
+    ///
+    /// # Panics
+    ///
+    /// If the directory or the file can't be created.
+    pub fn with_suffix(directory: impl AsRef<Path>, suffix: &str) -> Self {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory).unwrap_or_else(|err| {
+            panic!(
+                "Should be able to create the directory '{}': {err}",
+                directory.display()
+            )
+        });
+
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        let path = directory.join(format!(
+            "events.out.tfevents.{}.{host}.{}{suffix}",
+            time.as_secs(),
+            std::process::id()
+        ));
+
+        let file = File::create(&path).unwrap_or_else(|err| {
+            panic!(
+                "Should be able to create the new file '{}': {err}",
+                path.display()
+            )
+        });
+
+        let mut writer = Self {
+            file: BufWriter::new(file),
+            path,
+        };
+        writer.write_event(0, |event| {
+            event.string(EVENT_FILE_VERSION, "brain.Event:2");
+        });
+        writer.flush();
+        writer
+    }
+
+    /// The path of the event file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write a scalar value.
+    pub fn add_scalar(&mut self, tag: &str, value: f64, step: usize) {
+        self.write_summary(step, |summary| {
+            summary
+                .string(VALUE_TAG, tag)
+                .float(VALUE_SIMPLE_VALUE, value as f32);
+        });
+    }
+
+    /// Write the histogram of the values of a tensor.
+    pub fn add_histogram<B: Backend, const D: usize>(
+        &mut self,
+        tag: &str,
+        tensor: Tensor<B, D>,
+        step: usize,
+    ) {
+        let values = tensor.into_data().iter::<f64>().collect::<Vec<_>>();
+        self.add_histogram_values(tag, &values, step);
+    }
+
+    /// Write the histogram of the given values, with buckets of the same width between their
+    /// minimum and maximum. Non-finite values are ignored.
+    pub fn add_histogram_values(&mut self, tag: &str, values: &[f64], step: usize) {
+        let histogram = Histogram::new(values);
+        self.write_summary(step, |summary| {
+            summary
+                .string(VALUE_TAG, tag)
+                .message(VALUE_HISTO, |histo| histogram.encode(histo));
+        });
+    }
+
+    /// Write the histograms of all the parameters of a module.
+    ///
+    /// The tags are the paths of the parameters in the module, e.g. `weights/layers/0/weight`.
+    pub fn add_weight_histograms<B: Backend, M: Module<B>>(&mut self, module: &M, step: usize) {
+        let mut visitor = WeightsVisitor {
+            writer: self,
+            path: Vec::new(),
+            step,
+        };
+        module.visit(&mut visitor);
+    }
+
+    /// Write the histograms of the gradients of all the parameters of a module.
+    ///
+    /// The tags are the paths of the parameters in the module, e.g. `gradients/layers/0/weight`.
+    pub fn add_gradient_histograms<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &mut self,
+        module: &M,
+        grads: &GradientsParams,
+        step: usize,
+    ) {
+        let mut visitor = GradientsVisitor {
+            writer: self,
+            grads,
+            path: Vec::new(),
+            step,
+        };
+        module.visit(&mut visitor);
+    }
+
+    /// Write the hyperparameters of a configuration for the hparams dashboard.
+    ///
+    /// Nested fields are flattened with their path joined by dots, and the given metric tags are
+    /// shown next to the hyperparameters, e.g. `Loss/valid/epoch`.
+    pub fn add_hparams<C: Config>(&mut self, config: &C, metrics: &[&str]) {
+        let value = serde_json::to_value(config).expect("Config should be serializable to JSON");
+        let mut hparams = Vec::new();
+        flatten_hparams(String::new(), value, &mut hparams);
+
+        let experiment = plugin_data(|data| {
+            data.message(HPARAMS_EXPERIMENT, |experiment| {
+                for (name, value) in hparams.iter() {
+                    experiment.message(EXPERIMENT_HPARAM_INFOS, |info| {
+                        info.string(HPARAM_INFO_NAME, name)
+                            .int64(HPARAM_INFO_TYPE, value.data_type());
+                    });
+                }
+                for metric in metrics {
+                    experiment.message(EXPERIMENT_METRIC_INFOS, |info| {
+                        info.message(METRIC_INFO_NAME, |name| {
+                            name.string(METRIC_NAME_TAG, metric);
+                        });
+                    });
+                }
+            });
+        });
+        let session_start = plugin_data(|data| {
+            data.message(HPARAMS_SESSION_START_INFO, |session| {
+                for (name, value) in hparams.iter() {
+                    // Maps are encoded as repeated entries with a key and a value.
+                    session.message(SESSION_START_HPARAMS, |entry| {
+                        entry
+                            .string(MAP_ENTRY_KEY, name)
+                            .message(MAP_ENTRY_VALUE, |proto_value| value.encode(proto_value));
+                    });
+                }
+                session.double(SESSION_START_TIME, wall_time());
+            });
+        });
+
+        for (tag, content) in [
+            ("_hparams_/experiment", experiment),
+            ("_hparams_/session_start_info", session_start),
+        ] {
+            self.write_summary(0, |summary| {
+                summary
+                    .string(VALUE_TAG, tag)
+                    .message(VALUE_METADATA, |metadata| {
+                        metadata.message(METADATA_PLUGIN_DATA, |plugin| {
+                            plugin
+                                .string(PLUGIN_NAME, "hparams")
+                                .bytes(PLUGIN_CONTENT, &content);
+                        });
+                    });
+            });
+        }
+        self.flush();
+    }
+
+    /// Flush the written events to the file.
+    pub fn flush(&mut self) {
+        self.file.flush().expect("Can flush the event file.");
+    }
+
+    fn write_summary(&mut self, step: usize, encode_value: impl FnOnce(&mut ProtoEncoder)) {
+        self.write_event(step, |event| {
+            event.message(EVENT_SUMMARY, |summary| {
+                summary.message(SUMMARY_VALUE, encode_value);
+            });
+        });
+    }
+
+    fn write_event(&mut self, step: usize, encode: impl FnOnce(&mut ProtoEncoder)) {
+        let mut event = ProtoEncoder::default();
+        event
+            .double(EVENT_WALL_TIME, wall_time())
+            .int64(EVENT_STEP, step as i64);
+        encode(&mut event);
+
+        write_record(&mut self.file, &event.into_bytes()).expect("Can write an event.");
+    }
+}
+
+impl Drop for TensorBoardWriter {
+    fn drop(&mut self) {
+        self.file.flush().ok();
+    }
+}
+
+// Field numbers of `Event`.
+const EVENT_WALL_TIME: u32 = 1;
+const EVENT_STEP: u32 = 2;
+const EVENT_FILE_VERSION: u32 = 3;
+const EVENT_SUMMARY: u32 = 5;
+// Field numbers of `Summary` and `Summary.Value`.
+const SUMMARY_VALUE: u32 = 1;
+const VALUE_TAG: u32 = 1;
+const VALUE_SIMPLE_VALUE: u32 = 2;
+const VALUE_HISTO: u32 = 5;
+const VALUE_METADATA: u32 = 9;
+// Field numbers of `SummaryMetadata` and `SummaryMetadata.PluginData`.
+const METADATA_PLUGIN_DATA: u32 = 1;
+const PLUGIN_NAME: u32 = 1;
+const PLUGIN_CONTENT: u32 = 2;
+// Field numbers of `HistogramProto`.
+const HISTO_MIN: u32 = 1;
+const HISTO_MAX: u32 = 2;
+const HISTO_NUM: u32 = 3;
+const HISTO_SUM: u32 = 4;
+const HISTO_SUM_SQUARES: u32 = 5;
+const HISTO_BUCKET_LIMIT: u32 = 6;
+const HISTO_BUCKET: u32 = 7;
+// Field numbers of the messages of the hparams plugin.
+const HPARAMS_EXPERIMENT: u32 = 2;
+const HPARAMS_SESSION_START_INFO: u32 = 3;
+const EXPERIMENT_HPARAM_INFOS: u32 = 4;
+const EXPERIMENT_METRIC_INFOS: u32 = 5;
+const HPARAM_INFO_NAME: u32 = 1;
+const HPARAM_INFO_TYPE: u32 = 4;
+const METRIC_INFO_NAME: u32 = 1;
+const METRIC_NAME_TAG: u32 = 2;
+const SESSION_START_HPARAMS: u32 = 1;
+const SESSION_START_TIME: u32 = 5;
+const MAP_ENTRY_KEY: u32 = 1;
+const MAP_ENTRY_VALUE: u32 = 2;
+// Field numbers of `google.protobuf.Value`.
+const PROTO_VALUE_NUMBER: u32 = 2;
+const PROTO_VALUE_STRING: u32 = 3;
+const PROTO_VALUE_BOOL: u32 = 4;
+
+fn wall_time() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs_f64())
+        .unwrap_or_default()
+}
+
+fn plugin_data(encode: impl FnOnce(&mut ProtoEncoder)) -> Vec<u8> {
+    let mut data = ProtoEncoder::default();
+    encode(&mut data);
+    data.into_bytes()
+}
+
+/// Write a record in the TFRecord format: the length of the data and its checksum, followed by
+/// the data and its checksum.
+fn write_record(writer: &mut impl Write, data: &[u8]) -> std::io::Result<()> {
+    let len = (data.len() as u64).to_le_bytes();
+    writer.write_all(&len)?;
+    writer.write_all(&masked_crc32c(&len).to_le_bytes())?;
+    writer.write_all(data)?;
+    writer.write_all(&masked_crc32c(data).to_le_bytes())
+}
+
+fn masked_crc32c(data: &[u8]) -> u32 {
+    let crc = crc32c(data);
+    crc.rotate_right(15).wrapping_add(0xa282ead8)
+}
+
+/// The CRC-32C (Castagnoli) checksum of the data.
+fn crc32c(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0x82f63b78
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+
+    !data.iter().fold(!0, |crc, byte| {
+        TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Read the data of the records of an event file, checking their checksums.
+#[cfg(test)]
+pub(crate) fn read_records(path: &Path) -> Vec<Vec<u8>> {
+    let bytes = std::fs::read(path).unwrap();
+    let mut records = Vec::new();
+    let mut rest = bytes.as_slice();
+
+    while !rest.is_empty() {
+        let (len, tail) = rest.split_at(8);
+        let (len_crc, tail) = tail.split_at(4);
+        assert_eq!(masked_crc32c(len).to_le_bytes(), len_crc);
+
+        let len = u64::from_le_bytes(len.try_into().unwrap()) as usize;
+        let (data, tail) = tail.split_at(len);
+        let (data_crc, tail) = tail.split_at(4);
+        assert_eq!(masked_crc32c(data).to_le_bytes(), data_crc);
+
+        records.push(data.to_vec());
+        rest = tail;
+    }
+
+    records
+}
+
+struct Histogram {
+    min: f64,
+    max: f64,
+    num: f64,
+    sum: f64,
+    sum_squares: f64,
+    bucket_limits: Vec<f64>,
+    buckets: Vec<f64>,
+}
+
+impl Histogram {
+    fn new(values: &[f64]) -> Self {
+        let values = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        if values.is_empty() {
+            return Self {
+                min: 0.0,
+                max: 0.0,
+                num: 0.0,
+                sum: 0.0,
+                sum_squares: 0.0,
+                bucket_limits: Vec::new(),
+                buckets: Vec::new(),
+            };
+        }
+
+        // All the values fall in the same bucket when they are equal.
+        let num_buckets = if min < max { HISTOGRAM_BUCKETS } else { 1 };
+        let width = (max - min) / num_buckets as f64;
+        let bucket_limits = (1..=num_buckets)
+            .map(|i| match i == num_buckets {
+                true => max,
+                false => min + width * i as f64,
+            })
+            .collect::<Vec<_>>();
+        let mut buckets = vec![0.0; num_buckets];
+
+        for value in values.iter() {
+            let index = match width > 0.0 {
+                true => (((value - min) / width) as usize).min(num_buckets - 1),
+                false => 0,
+            };
+            buckets[index] += 1.0;
+        }
+
+        Self {
+            min,
+            max,
+            num: values.len() as f64,
+            sum: values.iter().sum(),
+            sum_squares: values.iter().map(|value| value * value).sum(),
+            bucket_limits,
+            buckets,
+        }
+    }
+
+    fn encode(&self, histo: &mut ProtoEncoder) {
+        histo
+            .double(HISTO_MIN, self.min)
+            .double(HISTO_MAX, self.max)
+            .double(HISTO_NUM, self.num)
+            .double(HISTO_SUM, self.sum)
+            .double(HISTO_SUM_SQUARES, self.sum_squares)
+            .packed_doubles(HISTO_BUCKET_LIMIT, &self.bucket_limits)
+            .packed_doubles(HISTO_BUCKET, &self.buckets);
+    }
+}
+
+enum HParamValue {
+    Number(f64),
+    String(String),
+    Bool(bool),
+}
+
+impl HParamValue {
+    /// The `DataType` of the hparams plugin.
+    fn data_type(&self) -> i64 {
+        match self {
+            HParamValue::String(_) => 1,
+            HParamValue::Bool(_) => 2,
+            HParamValue::Number(_) => 3,
+        }
+    }
+
+    fn encode(&self, value: &mut ProtoEncoder) {
+        match self {
+            HParamValue::Number(number) => value.double(PROTO_VALUE_NUMBER, *number),
+            HParamValue::String(string) => value.string(PROTO_VALUE_STRING, string),
+            HParamValue::Bool(boolean) => value.bool(PROTO_VALUE_BOOL, *boolean),
+        };
+    }
+}
+
+fn flatten_hparams(
+    name: String,
+    value: serde_json::Value,
+    hparams: &mut Vec<(String, HParamValue)>,
+) {
+    let join = |key: &str| match name.is_empty() {
+        true => key.to_string(),
+        false => format!("{name}.{key}"),
+    };
+
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                flatten_hparams(join(&key), value, hparams);
+            }
+        }
+        serde_json::Value::Number(number) => hparams.push((
+            name,
+            HParamValue::Number(number.as_f64().unwrap_or_default()),
+        )),
+        serde_json::Value::Bool(boolean) => hparams.push((name, HParamValue::Bool(boolean))),
+        serde_json::Value::String(string) => hparams.push((name, HParamValue::String(string))),
+        // Lists are shown as they are serialized, unset optional fields are skipped.
+        value @ serde_json::Value::Array(_) => {
+            hparams.push((name, HParamValue::String(value.to_string())))
+        }
+        serde_json::Value::Null => {}
+    }
+}
+
+struct WeightsVisitor<'a> {
+    writer: &'a mut TensorBoardWriter,
+    path: Vec<String>,
+    step: usize,
+}
+
+impl<B: Backend> ModuleVisitor<B> for WeightsVisitor<'_> {
+    fn enter_module(&mut self, name: &str, _container_type: &str) {
+        self.path.push(name.to_string());
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, _id: ParamId, tensor: &Tensor<B, D>) {
+        let tag = format!("weights/{}", self.path.join("/"));
+        self.writer.add_histogram(&tag, tensor.clone(), self.step);
+    }
+}
+
+struct GradientsVisitor<'a> {
+    writer: &'a mut TensorBoardWriter,
+    grads: &'a GradientsParams,
+    path: Vec<String>,
+    step: usize,
+}
+
+impl<B: AutodiffBackend> ModuleVisitor<B> for GradientsVisitor<'_> {
+    fn enter_module(&mut self, name: &str, _container_type: &str) {
+        self.path.push(name.to_string());
+    }
+
+    fn exit_module(&mut self, _name: &str, _container_type: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        if let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) {
+            let tag = format!("gradients/{}", self.path.join("/"));
+            self.writer.add_histogram(&tag, grad, self.step);
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use crate::logger::tensorboard::proto::{ProtoField, decode};
+    use burn_core as burn;
+    use burn_core::nn::{Linear, LinearConfig};
+    use std::collections::HashMap;
+
+    /// The summary values of the events of a file, as `(step, value fields)`.
+    pub(crate) fn summary_values(path: &Path) -> Vec<(u64, Vec<(u32, ProtoField)>)> {
+        read_records(path)
+            .iter()
+            .filter_map(|record| {
+                let event = decode(record);
+                let step = event
+                    .iter()
+                    .find(|(field, _)| *field == EVENT_STEP)
+                    .map(|(_, step)| step.as_varint())
+                    .unwrap_or_default();
+                let summary = event.iter().find(|(field, _)| *field == EVENT_SUMMARY)?;
+                let value = decode(summary.1.as_bytes()).remove(0);
+                Some((step, decode(value.1.as_bytes())))
+            })
+            .collect()
+    }
+
+    fn field(fields: &[(u32, ProtoField)], number: u32) -> &ProtoField {
+        &fields.iter().find(|(field, _)| *field == number).unwrap().1
+    }
+
+    #[test]
+    fn test_crc32c() {
+        // Check value of the CRC-32C algorithm.
+        assert_eq!(crc32c(b"123456789"), 0xe3069283);
+    }
+
+    #[test]
+    fn test_write_file_version_and_scalars() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut writer = TensorBoardWriter::new(directory.path());
+        writer.add_scalar("Loss/train", 0.5, 3);
+        writer.flush();
+
+        let records = read_records(writer.path());
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            field(&decode(&records[0]), EVENT_FILE_VERSION).as_str(),
+            "brain.Event:2"
+        );
+
+        let values = summary_values(writer.path());
+        assert_eq!(values.len(), 1);
+        let (step, value) = &values[0];
+        assert_eq!(*step, 3);
+        assert_eq!(field(value, VALUE_TAG).as_str(), "Loss/train");
+        assert_eq!(field(value, VALUE_SIMPLE_VALUE).as_float(), 0.5);
+    }
+
+    #[test]
+    fn test_write_histogram() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut writer = TensorBoardWriter::new(directory.path());
+        let tensor =
+            Tensor::<TestBackend, 1>::from_floats([0.0, 1.0, 1.0, 3.0], &Default::default());
+        writer.add_histogram("values", tensor, 1);
+        writer.flush();
+
+        let values = summary_values(writer.path());
+        let histo = decode(field(&values[0].1, VALUE_HISTO).as_bytes());
+        assert_eq!(field(&histo, HISTO_MIN).as_double(), 0.0);
+        assert_eq!(field(&histo, HISTO_MAX).as_double(), 3.0);
+        assert_eq!(field(&histo, HISTO_NUM).as_double(), 4.0);
+        assert_eq!(field(&histo, HISTO_SUM).as_double(), 5.0);
+        assert_eq!(field(&histo, HISTO_SUM_SQUARES).as_double(), 11.0);
+
+        let buckets = field(&histo, HISTO_BUCKET)
+            .as_bytes()
+            .chunks(8)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(buckets.len(), HISTOGRAM_BUCKETS);
+        assert_eq!(buckets.iter().sum::<f64>(), 4.0);
+        assert_eq!(buckets[0], 1.0);
+        assert_eq!(buckets[HISTOGRAM_BUCKETS - 1], 1.0);
+    }
+
+    #[test]
+    fn test_write_weight_histograms() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut writer = TensorBoardWriter::new(directory.path());
+        let linear: Linear<TestBackend> = LinearConfig::new(2, 2).init(&Default::default());
+
+        writer.add_weight_histograms(&linear, 2);
+        writer.flush();
+
+        let tags = summary_values(writer.path())
+            .iter()
+            .map(|(_, value)| field(value, VALUE_TAG).as_str().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec!["weights/weight", "weights/bias"]);
+    }
+
+    #[derive(Config, Debug)]
+    struct TestConfig {
+        learning_rate: f64,
+        optim: TestOptimConfig,
+    }
+
+    #[derive(Config, Debug)]
+    struct TestOptimConfig {
+        name: String,
+        #[config(default = true)]
+        nesterov: bool,
+    }
+
+    #[test]
+    fn test_write_hparams() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut writer = TensorBoardWriter::new(directory.path());
+        let config = TestConfig::new(0.01, TestOptimConfig::new("sgd".to_string()));
+        writer.add_hparams(&config, &["Loss/valid/epoch"]);
+
+        let values = summary_values(writer.path());
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            field(&values[0].1, VALUE_TAG).as_str(),
+            "_hparams_/experiment"
+        );
+
+        let metadata = decode(field(&values[1].1, VALUE_METADATA).as_bytes());
+        let plugin = decode(field(&metadata, METADATA_PLUGIN_DATA).as_bytes());
+        assert_eq!(field(&plugin, PLUGIN_NAME).as_str(), "hparams");
+
+        let data = decode(field(&plugin, PLUGIN_CONTENT).as_bytes());
+        let session = decode(field(&data, HPARAMS_SESSION_START_INFO).as_bytes());
+        let hparams = session
+            .iter()
+            .filter(|(field, _)| *field == SESSION_START_HPARAMS)
+            .map(|(_, entry)| {
+                let entry = decode(entry.as_bytes());
+                let key = field(&entry, MAP_ENTRY_KEY).as_str().to_string();
+                let value = decode(field(&entry, MAP_ENTRY_VALUE).as_bytes()).remove(0);
+                (key, value)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(hparams.len(), 3);
+        let hparams = hparams.into_iter().collect::<HashMap<_, _>>();
+        assert_eq!(hparams["learning_rate"].1.as_double(), 0.01);
+        assert_eq!(
+            hparams["optim.nesterov"],
+            (PROTO_VALUE_BOOL, ProtoField::Varint(1))
+        );
+        assert_eq!(hparams["optim.name"].1.as_str(), "sgd");
+    }
+}
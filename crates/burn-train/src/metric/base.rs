@@ -3,6 +3,9 @@ use std::sync::Arc;
 use burn_core::{LearningRate, data::dataloader::Progress};
 
 /// Metric metadata that can be used when computing metrics.
+///
+/// Should be created with [new](MetricMetadata::new), since new fields can be added.
+#[non_exhaustive]
 pub struct MetricMetadata {
     /// The current progress.
     pub progress: Progress,
@@ -18,9 +21,36 @@ pub struct MetricMetadata {
 
     /// The current learning rate.
     pub lr: Option<LearningRate>,
+
+    /// The L2 norm of all the gradients of the current iteration, if tracked.
+    pub grad_norm: Option<f64>,
 }
 
 impl MetricMetadata {
+    /// Create the metadata of an item, without [gradient norm](MetricMetadata::grad_norm).
+    pub fn new(
+        progress: Progress,
+        epoch: usize,
+        epoch_total: usize,
+        iteration: usize,
+        lr: Option<LearningRate>,
+    ) -> Self {
+        Self {
+            progress,
+            epoch,
+            epoch_total,
+            iteration,
+            lr,
+            grad_norm: None,
+        }
+    }
+
+    /// Set the L2 norm of the gradients of the iteration.
+    pub fn with_grad_norm(mut self, grad_norm: Option<f64>) -> Self {
+        self.grad_norm = grad_norm;
+        self
+    }
+
     /// Fake metric metadata
     #[cfg(test)]
     pub fn fake() -> Self {
@@ -33,6 +63,7 @@ impl MetricMetadata {
             epoch_total: 1,
             iteration: 0,
             lr: None,
+            grad_norm: None,
         }
     }
 }
@@ -109,6 +140,17 @@ impl MetricEntry {
             tags: Vec::new(),
         }
     }
+
+    /// Create an empty entry, for an item the metric has no value for. Empty entries are
+    /// neither logged nor displayed.
+    pub fn empty(name: Arc<String>) -> Self {
+        Self::new(name, String::new(), String::new())
+    }
+
+    /// Whether the entry is [empty](MetricEntry::empty).
+    pub fn is_empty(&self) -> bool {
+        self.serialize.is_empty()
+    }
 }
 
 /// Numeric metric entry.
@@ -125,6 +167,11 @@ pub enum NumericEntry {
         /// The current aggregated value.
         current: f64,
     },
+    /// Value computed from all the items of the epoch so far, e.g. from statistics accumulated
+    /// since the start of the epoch.
+    ///
+    /// The value of the metric for an epoch is its last entry, not the mean of its entries.
+    Cumulative(f64),
 }
 
 impl NumericEntry {
@@ -133,15 +180,20 @@ impl NumericEntry {
         match self {
             NumericEntry::Value(val) => *val,
             NumericEntry::Aggregated { current, .. } => *current,
+            NumericEntry::Cumulative(val) => *val,
         }
     }
 }
 
+/// Tag following the value of a serialized [cumulative](NumericEntry::Cumulative) entry.
+const CUMULATIVE_TAG: &str = "cumulative";
+
 impl NumericEntry {
     pub(crate) fn serialize(&self) -> String {
         match self {
             Self::Value(v) => v.to_string(),
             Self::Aggregated { sum, count, .. } => format!("{sum},{count}"),
+            Self::Cumulative(v) => format!("{v},{CUMULATIVE_TAG}"),
         }
     }
 
@@ -157,8 +209,15 @@ impl NumericEntry {
                 Err(err) => Err(err.to_string()),
             }
         } else if num_values == 2 {
-            // Aggregated numeric (value, number of elements)
             let (value, numel) = (values[0], values[1]);
+            if numel == CUMULATIVE_TAG {
+                return match value.parse::<f64>() {
+                    Ok(value) => Ok(NumericEntry::Cumulative(value)),
+                    Err(err) => Err(err.to_string()),
+                };
+            }
+
+            // Aggregated numeric (value, number of elements)
             match value.parse::<f64>() {
                 Ok(value) => match numel.parse::<usize>() {
                     Ok(numel) => Ok(NumericEntry::Aggregated {
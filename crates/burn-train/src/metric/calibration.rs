@@ -0,0 +1,351 @@
+use core::marker::PhantomData;
+
+use super::state::{AccumulatedMetricState, FormatOptions, NumericMetricState};
+use super::{MetricEntry, MetricMetadata};
+use crate::metric::{Metric, MetricName, Numeric};
+use burn_core::tensor::activation::{log_sigmoid, log_softmax};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{ElementConversion, Int, Tensor};
+
+/// Input type of the calibration metrics, e.g. the [BrierScoreMetric].
+///
+/// The outputs are the logits of the classes, or a single logit of the positive class for binary
+/// classification.
+#[derive(new)]
+pub struct CalibrationInput<B: Backend> {
+    outputs: Tensor<B, 2>,
+    targets: Tensor<B, 1, Int>,
+}
+
+impl<B: Backend> CalibrationInput<B> {
+    /// The log-probabilities of the classes, with two classes for a single logit.
+    fn log_probabilities(&self) -> Tensor<B, 2> {
+        let [_, num_classes] = self.outputs.dims();
+
+        match num_classes {
+            1 => Tensor::cat(
+                vec![
+                    log_sigmoid(self.outputs.clone().neg()),
+                    log_sigmoid(self.outputs.clone()),
+                ],
+                1,
+            ),
+            _ => log_softmax(self.outputs.clone(), 1),
+        }
+    }
+}
+
+/// The expected calibration error (ECE) of a classification.
+///
+/// The predictions are grouped in bins of equal width by their confidence, i.e. the probability
+/// of the predicted class, and the ECE is the mean of the absolute difference between the
+/// accuracy and the mean confidence of each bin, weighted by the number of predictions in the
+/// bin. The bins are accumulated over the epoch, so the value is the ECE of all the predictions of
+/// the epoch.
+#[derive(Clone)]
+pub struct CalibrationErrorMetric<B: Backend> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    /// The number of predictions, the sum of their confidences and the number of correct
+    /// predictions of each bin.
+    bins: Vec<(u64, f64, u64)>,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for CalibrationErrorMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> CalibrationErrorMetric<B> {
+    /// Creates the metric with 15 bins.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("ECE".to_string()),
+            state: Default::default(),
+            bins: vec![(0, 0.0, 0); 15],
+            _b: PhantomData,
+        }
+    }
+
+    /// Sets the number of bins of the confidences.
+    pub fn with_num_bins(mut self, num_bins: usize) -> Self {
+        assert!(num_bins > 0, "The number of bins must be at least 1.");
+        self.bins = vec![(0, 0.0, 0); num_bins];
+        self
+    }
+
+    fn calibration_error(&self) -> f64 {
+        let total = self.bins.iter().map(|(count, _, _)| count).sum::<u64>();
+        if total == 0 {
+            return 0.0;
+        }
+
+        // The gap of each bin weighted by its number of predictions.
+        let gaps = self
+            .bins
+            .iter()
+            .map(|&(_, confidence, correct)| (correct as f64 - confidence).abs())
+            .sum::<f64>();
+
+        gaps / total as f64
+    }
+}
+
+impl<B: Backend> Metric for CalibrationErrorMetric<B> {
+    type Input = CalibrationInput<B>;
+
+    fn update(&mut self, input: &CalibrationInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, _] = input.outputs.dims();
+        let probabilities = input.log_probabilities().exp();
+
+        let (confidences, predictions) = probabilities.max_dim_with_indices(1);
+        let confidences = confidences.to_data().iter::<f64>().collect::<Vec<_>>();
+        let predictions = predictions.reshape([batch_size]).to_data();
+        let targets = input.targets.to_data();
+
+        let num_bins = self.bins.len();
+        for ((confidence, prediction), target) in confidences
+            .into_iter()
+            .zip(predictions.iter::<i64>())
+            .zip(targets.iter::<i64>())
+        {
+            let bin = ((confidence * num_bins as f64) as usize).min(num_bins - 1);
+            let (count, confidences, correct) = &mut self.bins[bin];
+            *count += 1;
+            *confidences += confidence;
+            *correct += u64::from(prediction == target);
+        }
+
+        self.state.update(
+            100.0 * self.calibration_error(),
+            FormatOptions::new(self.name()).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.bins.fill((0, 0.0, 0));
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for CalibrationErrorMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The Brier score of a classification, i.e. the mean squared difference between the predicted
+/// probabilities and the one-hot targets, summed over the classes.
+///
+/// For binary classification with a single logit, only the probability of the positive class is
+/// compared to the target.
+#[derive(Clone)]
+pub struct BrierScoreMetric<B: Backend> {
+    name: MetricName,
+    state: NumericMetricState,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for BrierScoreMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> BrierScoreMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("Brier Score".to_string()),
+            state: Default::default(),
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for BrierScoreMetric<B> {
+    type Input = CalibrationInput<B>;
+
+    fn update(&mut self, input: &CalibrationInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, num_classes] = input.outputs.dims();
+        let probabilities = input.log_probabilities().exp();
+
+        let errors = match num_classes {
+            1 => {
+                let positives = probabilities.slice([0..batch_size, 1..2]);
+                positives - input.targets.clone().reshape([batch_size, 1]).float()
+            }
+            _ => probabilities - input.targets.clone().one_hot::<2>(num_classes).float(),
+        };
+        let score = errors.powi_scalar(2).sum().into_scalar().elem::<f64>() / batch_size as f64;
+
+        self.state.update(
+            score,
+            batch_size,
+            FormatOptions::new(self.name()).precision(4),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for BrierScoreMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The log-loss of a classification, i.e. the mean negative log-probability of the target
+/// classes.
+#[derive(Clone)]
+pub struct LogLossMetric<B: Backend> {
+    name: MetricName,
+    state: NumericMetricState,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for LogLossMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> LogLossMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("Log Loss".to_string()),
+            state: Default::default(),
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for LogLossMetric<B> {
+    type Input = CalibrationInput<B>;
+
+    fn update(&mut self, input: &CalibrationInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, _] = input.outputs.dims();
+        let targets = input.targets.clone().reshape([batch_size, 1]);
+
+        let log_likelihood = input
+            .log_probabilities()
+            .gather(1, targets)
+            .sum()
+            .into_scalar()
+            .elem::<f64>();
+
+        self.state.update(
+            -log_likelihood / batch_size as f64,
+            batch_size,
+            FormatOptions::new(self.name()).precision(4),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for LogLossMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn input(outputs: [[f32; 2]; 4], targets: [i64; 4]) -> CalibrationInput<TestBackend> {
+        let device = Default::default();
+        // The logits are the log-probabilities, so the probabilities are the outputs.
+        CalibrationInput::new(
+            Tensor::<TestBackend, 2>::from_data(outputs, &device).log(),
+            Tensor::from_data(targets, &device),
+        )
+    }
+
+    #[test]
+    fn test_calibration_error() {
+        let input = input(
+            [[0.95, 0.05], [0.85, 0.15], [0.25, 0.75], [0.35, 0.65]],
+            [0, 1, 1, 1],
+        );
+        let mut metric = CalibrationErrorMetric::<TestBackend>::new().with_num_bins(2);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // All the predictions are in the upper bin, with a mean confidence of 0.8 and an accuracy
+        // of 0.75.
+        assert!((metric.value().current() - 5.0).abs() < 1e-4);
+
+        let mut metric = CalibrationErrorMetric::<TestBackend>::new().with_num_bins(10);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        // Each prediction is in its own bin, with gaps of 0.05, 0.85, 0.25 and 0.35.
+        let expected = 100.0 * (0.05 + 0.85 + 0.25 + 0.35) / 4.0;
+        assert!((metric.value().current() - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_brier_score() {
+        let input = input(
+            [[0.95, 0.05], [0.85, 0.15], [0.25, 0.75], [0.35, 0.65]],
+            [0, 1, 1, 1],
+        );
+        let mut metric = BrierScoreMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        let expected = 2.0 * (0.0025 + 0.7225 + 0.0625 + 0.1225) / 4.0;
+        assert!((metric.value().current() - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_log_loss() {
+        let input = input(
+            [[0.95, 0.05], [0.85, 0.15], [0.25, 0.75], [0.35, 0.65]],
+            [0, 1, 1, 1],
+        );
+        let mut metric = LogLossMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        let expected = -(0.95_f64.ln() + 0.15_f64.ln() + 0.75_f64.ln() + 0.65_f64.ln()) / 4.0;
+        assert!((metric.value().current() - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_binary_log_loss_and_brier_score() {
+        let device = Default::default();
+        let input = CalibrationInput::<TestBackend>::new(
+            Tensor::from_data([[0.0], [2.0]], &device),
+            Tensor::from_data([1, 0], &device),
+        );
+
+        let mut metric = LogLossMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        let positive = 1.0 / (1.0 + (-2.0_f64).exp());
+        let expected = -(0.5_f64.ln() + (1.0 - positive).ln()) / 2.0;
+        assert!((metric.value().current() - expected).abs() < 1e-5);
+
+        let mut metric = BrierScoreMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        let expected = (0.25 + positive * positive) / 2.0;
+        assert!((metric.value().current() - expected).abs() < 1e-5);
+    }
+}
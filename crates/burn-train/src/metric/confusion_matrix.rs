@@ -0,0 +1,253 @@
+use core::marker::PhantomData;
+
+use super::{MetricEntry, MetricMetadata};
+use crate::metric::{Metric, MetricName};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{Int, Tensor};
+use serde::{Deserialize, Serialize};
+
+/// The number of items of each target class predicted as each class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfusionMatrix {
+    num_classes: usize,
+    /// The counts indexed by `target * num_classes + prediction`.
+    counts: Vec<u64>,
+}
+
+impl ConfusionMatrix {
+    /// Creates an empty confusion matrix of the given number of classes.
+    pub fn new(num_classes: usize) -> Self {
+        Self {
+            num_classes,
+            counts: vec![0; num_classes * num_classes],
+        }
+    }
+
+    /// The number of classes.
+    pub fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+
+    /// The number of items of the target class predicted as the given class.
+    pub fn count(&self, target: usize, prediction: usize) -> u64 {
+        self.counts[target * self.num_classes + prediction]
+    }
+
+    /// Count the predictions of the targets.
+    ///
+    /// # Panics
+    /// If a class is outside of `[0, num_classes)`.
+    pub fn update(&mut self, predictions: &[i64], targets: &[i64]) {
+        for (&prediction, &target) in predictions.iter().zip(targets) {
+            let prediction = self.class(prediction);
+            let target = self.class(target);
+            self.counts[target * self.num_classes + prediction] += 1;
+        }
+    }
+
+    fn class(&self, class: i64) -> usize {
+        assert!(
+            class >= 0 && (class as usize) < self.num_classes,
+            "Class {class} is out of range for {} classes.",
+            self.num_classes
+        );
+        class as usize
+    }
+
+    fn reset(&mut self) {
+        self.counts.fill(0);
+    }
+
+    /// Serialize the confusion matrix on a single line, to be logged.
+    pub fn serialize(&self) -> String {
+        serde_json::to_string(self).expect("Can serialize the confusion matrix")
+    }
+
+    /// Deserialize a confusion matrix logged with [serialize](Self::serialize).
+    pub fn deserialize(entry: &str) -> Result<Self, String> {
+        let matrix: Self = serde_json::from_str(entry).map_err(|err| err.to_string())?;
+        if matrix.counts.len() != matrix.num_classes * matrix.num_classes {
+            return Err(format!(
+                "Invalid confusion matrix of {} classes with {} counts.",
+                matrix.num_classes,
+                matrix.counts.len()
+            ));
+        }
+        Ok(matrix)
+    }
+}
+
+impl core::fmt::Display for ConfusionMatrix {
+    /// Formats the matrix as a table, with a row for each target class and a column for each
+    /// predicted class.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let header = "Target \\ Predicted";
+        let classes = (0..self.num_classes)
+            .map(|class| class.to_string())
+            .collect::<Vec<_>>();
+        let widths = (0..self.num_classes)
+            .map(|prediction| {
+                (0..self.num_classes)
+                    .map(|target| self.count(target, prediction).to_string().len())
+                    .chain(Some(classes[prediction].len()))
+                    .max()
+                    .unwrap_or(1)
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "| {header} |")?;
+        for (class, width) in classes.iter().zip(widths.iter()) {
+            write!(f, " {class:>width$} |")?;
+        }
+        write!(f, "\n|{:-<width$}|", "", width = header.len() + 2)?;
+        for width in widths.iter() {
+            write!(f, "{:-<width$}|", "", width = width + 2)?;
+        }
+
+        for (target, class) in classes.iter().enumerate() {
+            write!(f, "\n| {class:<width$} |", width = header.len())?;
+            for (prediction, width) in widths.iter().enumerate() {
+                write!(f, " {:>width$} |", self.count(target, prediction))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The [confusion matrix metric](ConfusionMatrixMetric) input type.
+#[derive(new)]
+pub struct ConfusionMatrixInput<B: Backend> {
+    outputs: Tensor<B, 2>,
+    targets: Tensor<B, 1, Int>,
+}
+
+/// The confusion matrix of a classification, accumulated over the epoch.
+///
+/// The predicted class is the one with the highest output, or the positive class when the
+/// outputs have a single logit. The matrix isn't numeric, so the metric is registered with
+/// [metric_train](crate::LearnerBuilder::metric_train) or
+/// [metric_valid](crate::LearnerBuilder::metric_valid). The matrix of the last epoch is displayed
+/// as a table in the renderer and in the [summary](crate::LearnerSummary).
+#[derive(Clone)]
+pub struct ConfusionMatrixMetric<B: Backend> {
+    name: MetricName,
+    matrix: ConfusionMatrix,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> ConfusionMatrixMetric<B> {
+    /// Creates the metric for the given number of classes, which is 2 for binary classification.
+    pub fn new(num_classes: usize) -> Self {
+        Self {
+            name: MetricName::new("Confusion Matrix".to_string()),
+            matrix: ConfusionMatrix::new(num_classes),
+            _b: PhantomData,
+        }
+    }
+
+    /// The confusion matrix accumulated since the start of the epoch.
+    pub fn matrix(&self) -> &ConfusionMatrix {
+        &self.matrix
+    }
+}
+
+impl<B: Backend> Metric for ConfusionMatrixMetric<B> {
+    type Input = ConfusionMatrixInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ConfusionMatrixInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        let [batch_size, num_classes] = input.outputs.dims();
+
+        let predictions = match num_classes {
+            1 => input.outputs.clone().greater_elem(0.0).int(),
+            _ => input.outputs.clone().argmax(1),
+        }
+        .reshape([batch_size]);
+
+        let predictions = predictions.to_data().iter::<i64>().collect::<Vec<_>>();
+        let targets = input.targets.to_data().iter::<i64>().collect::<Vec<_>>();
+        self.matrix.update(&predictions, &targets);
+
+        MetricEntry::new(
+            self.name(),
+            self.matrix.to_string(),
+            self.matrix.serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.matrix.reset();
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_confusion_matrix() {
+        let device = Default::default();
+        let mut metric = ConfusionMatrixMetric::<TestBackend>::new(3);
+        let input = ConfusionMatrixInput::new(
+            Tensor::from_data(
+                [
+                    [0.9, 0.1, 0.0],
+                    [0.2, 0.7, 0.1],
+                    [0.1, 0.8, 0.1],
+                    [0.1, 0.1, 0.8],
+                ],
+                &device,
+            ),
+            Tensor::from_data([0, 1, 2, 2], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        let entry = metric.update(&input, &MetricMetadata::fake());
+
+        let matrix = metric.matrix();
+        assert_eq!(matrix.count(0, 0), 2);
+        assert_eq!(matrix.count(1, 1), 2);
+        assert_eq!(matrix.count(2, 1), 2);
+        assert_eq!(matrix.count(2, 2), 2);
+        assert_eq!(matrix.count(0, 1), 0);
+
+        assert_eq!(
+            entry.formatted,
+            "| Target \\ Predicted | 0 | 1 | 2 |\n\
+             |--------------------|---|---|---|\n\
+             | 0                  | 2 | 0 | 0 |\n\
+             | 1                  | 0 | 2 | 0 |\n\
+             | 2                  | 0 | 2 | 2 |"
+        );
+        assert_eq!(
+            ConfusionMatrix::deserialize(&entry.serialize).unwrap(),
+            *matrix
+        );
+    }
+
+    #[test]
+    fn test_binary_confusion_matrix() {
+        let device = Default::default();
+        let mut metric = ConfusionMatrixMetric::<TestBackend>::new(2);
+        let input = ConfusionMatrixInput::new(
+            Tensor::from_data([[1.5], [-0.5], [0.5]], &device),
+            Tensor::from_data([1, 1, 0], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        let matrix = metric.matrix();
+        assert_eq!(matrix.count(1, 1), 1);
+        assert_eq!(matrix.count(1, 0), 1);
+        assert_eq!(matrix.count(0, 1), 1);
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use super::{
+    MetricMetadata, Numeric,
+    state::{FormatOptions, NumericMetricState},
+};
+use crate::metric::{Metric, MetricEntry, MetricName};
+
+/// Track the L2 norm of all the gradients of the model across iterations, before clipping.
+///
+/// The norm is only computed by the learner when the metric is registered with
+/// [metric_train_grad_norm](crate::LearnerBuilder::metric_train_grad_norm).
+#[derive(Clone)]
+pub struct GradientNormMetric {
+    name: MetricName,
+    state: NumericMetricState,
+}
+
+impl GradientNormMetric {
+    /// Creates a new gradient norm metric.
+    pub fn new() -> Self {
+        Self {
+            name: Arc::new("Gradient Norm".to_string()),
+            state: NumericMetricState::new(),
+        }
+    }
+}
+
+impl Default for GradientNormMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metric for GradientNormMetric {
+    type Input = ();
+
+    fn update(&mut self, _item: &(), metadata: &MetricMetadata) -> MetricEntry {
+        // The norm isn't computed for the iterations skipped with non-finite gradients.
+        let Some(grad_norm) = metadata.grad_norm else {
+            return MetricEntry::empty(self.name());
+        };
+
+        self.state
+            .update(grad_norm, 1, FormatOptions::new(self.name()).precision(3))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl Numeric for GradientNormMetric {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_grad_norm_should_not_update() {
+        let mut metric = GradientNormMetric::new();
+
+        let entry = metric.update(&(), &MetricMetadata::fake().with_grad_norm(Some(2.0)));
+        assert!(!entry.is_empty());
+
+        let entry = metric.update(&(), &MetricMetadata::fake());
+        assert!(entry.is_empty());
+        assert_eq!(metric.value().current(), 2.0);
+    }
+}
@@ -2,6 +2,8 @@
 pub mod state;
 /// Module responsible to save and exposes data collected during training.
 pub mod store;
+/// Metrics module for text generation tasks.
+pub mod text;
 /// Metrics module for vision tasks.
 pub mod vision;
 
@@ -27,29 +29,39 @@ pub use memory_use::*;
 mod acc;
 mod auroc;
 mod base;
+mod calibration;
+mod confusion_matrix;
 mod confusion_stats;
 mod fbetascore;
+mod grad_norm;
 mod hamming;
 mod iteration;
 mod learning_rate;
 mod loss;
 mod perplexity;
 mod precision;
+mod privacy;
 mod recall;
+mod regression;
 mod top_k_acc;
 
 pub use acc::*;
 pub use auroc::*;
 pub use base::*;
+pub use calibration::*;
+pub use confusion_matrix::*;
 pub use confusion_stats::ConfusionStatsInput;
 pub use fbetascore::*;
+pub use grad_norm::*;
 pub use hamming::*;
 pub use iteration::*;
 pub use learning_rate::*;
 pub use loss::*;
 pub use perplexity::*;
 pub use precision::*;
+pub use privacy::*;
 pub use recall::*;
+pub use regression::*;
 pub use top_k_acc::*;
 
 pub(crate) mod classification;
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use burn_core::optim::privacy::PrivacyAccountant;
+
+use super::{
+    MetricMetadata, Numeric,
+    state::{AccumulatedMetricState, FormatOptions},
+};
+use crate::metric::{Metric, MetricEntry, MetricName};
+
+/// Track the privacy budget `ε` spent by a [DP-SGD](burn_core::optim::privacy::DpSgd) optimizer
+/// for a given `δ`.
+///
+/// The budget spent since the start of the training only grows, so the value of the metric for an
+/// epoch is the last budget of the epoch rather than the mean of its budgets.
+#[derive(Clone)]
+pub struct PrivacyBudgetMetric {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    accountant: PrivacyAccountant,
+    delta: f64,
+}
+
+impl PrivacyBudgetMetric {
+    /// Creates a new privacy budget metric reading the given accountant, e.g. the
+    /// [accountant](burn_core::optim::privacy::DpSgd::accountant) of the optimizer.
+    pub fn new(accountant: PrivacyAccountant, delta: f64) -> Self {
+        Self {
+            name: Arc::new(format!("Epsilon (δ={delta:e})")),
+            state: AccumulatedMetricState::new(),
+            accountant,
+            delta,
+        }
+    }
+}
+
+impl Metric for PrivacyBudgetMetric {
+    type Input = ();
+
+    fn update(&mut self, _item: &(), _metadata: &MetricMetadata) -> MetricEntry {
+        let epsilon = self.accountant.epsilon(self.delta);
+
+        self.state
+            .update(epsilon, FormatOptions::new(self.name()).precision(3))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl Numeric for PrivacyBudgetMetric {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::NumericEntry;
+    use burn_core::optim::privacy::RdpAccountant;
+
+    #[test]
+    fn test_privacy_budget_follows_accountant() {
+        let accountant = PrivacyAccountant::new(RdpAccountant::new(1.0, 0.01));
+        let mut metric = PrivacyBudgetMetric::new(accountant.clone(), 1e-5);
+
+        metric.update(&(), &MetricMetadata::fake());
+        assert_eq!(metric.value().current(), 0.0);
+
+        accountant.set_steps(1000);
+        let entry = metric.update(&(), &MetricMetadata::fake());
+        assert!((metric.value().current() - 2.1078).abs() < 1e-3);
+        // The last budget is the value of the epoch.
+        assert!(matches!(metric.value(), NumericEntry::Cumulative(_)));
+        assert!(entry.serialize.ends_with(",cumulative"));
+    }
+}
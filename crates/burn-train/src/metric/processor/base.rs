@@ -79,6 +79,10 @@ pub struct LearnerItem<T> {
 
     /// The learning rate.
     pub lr: Option<LearningRate>,
+
+    /// The L2 norm of all the gradients, if tracked.
+    #[new(default)]
+    pub grad_norm: Option<f64>,
 }
 
 impl<T: ItemLazy> ItemLazy for LearnerItem<T> {
@@ -92,6 +96,7 @@ impl<T: ItemLazy> ItemLazy for LearnerItem<T> {
             epoch_total: self.epoch_total,
             iteration: self.iteration,
             lr: self.lr,
+            grad_norm: self.grad_norm,
         }
     }
 }
@@ -70,12 +70,16 @@ impl<T: ItemLazy> MetricsEvaluation<T> {
 
         for metric in self.test.iter_mut() {
             let state = metric.update(item, metadata);
-            entries.push(state);
+            if !state.is_empty() {
+                entries.push(state);
+            }
         }
 
         for metric in self.test_numeric.iter_mut() {
             let (state, value) = metric.update(item, metadata);
-            entries_numeric.push((state, value));
+            if !state.is_empty() {
+                entries_numeric.push((state, value));
+            }
         }
 
         MetricsUpdate::new(entries, entries_numeric)
@@ -134,12 +138,16 @@ impl<T: ItemLazy, V: ItemLazy> MetricsTraining<T, V> {
 
         for metric in self.train.iter_mut() {
             let state = metric.update(item, metadata);
-            entries.push(state);
+            if !state.is_empty() {
+                entries.push(state);
+            }
         }
 
         for metric in self.train_numeric.iter_mut() {
             let (state, value) = metric.update(item, metadata);
-            entries_numeric.push((state, value));
+            if !state.is_empty() {
+                entries_numeric.push((state, value));
+            }
         }
 
         MetricsUpdate::new(entries, entries_numeric)
@@ -156,12 +164,16 @@ impl<T: ItemLazy, V: ItemLazy> MetricsTraining<T, V> {
 
         for metric in self.valid.iter_mut() {
             let state = metric.update(item, metadata);
-            entries.push(state);
+            if !state.is_empty() {
+                entries.push(state);
+            }
         }
 
         for metric in self.valid_numeric.iter_mut() {
             let (state, value) = metric.update(item, metadata);
-            entries_numeric.push((state, value));
+            if !state.is_empty() {
+                entries_numeric.push((state, value));
+            }
         }
 
         MetricsUpdate::new(entries, entries_numeric)
@@ -210,13 +222,14 @@ impl<T> From<&LearnerItem<T>> for EvaluationProgress {
 
 impl<T> From<&LearnerItem<T>> for MetricMetadata {
     fn from(item: &LearnerItem<T>) -> Self {
-        Self {
-            progress: item.progress.clone(),
-            epoch: item.epoch,
-            epoch_total: item.epoch_total,
-            iteration: item.iteration,
-            lr: item.lr,
-        }
+        Self::new(
+            item.progress.clone(),
+            item.epoch,
+            item.epoch_total,
+            item.iteration,
+            item.lr,
+        )
+        .with_grad_norm(item.grad_norm)
     }
 }
 
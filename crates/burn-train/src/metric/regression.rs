@@ -0,0 +1,442 @@
+use core::marker::PhantomData;
+
+use super::state::{AccumulatedMetricState, FormatOptions, NumericMetricState};
+use super::{MetricEntry, MetricMetadata};
+use crate::metric::{Metric, MetricName, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{ElementConversion, Tensor};
+
+/// Input type of the regression metrics, e.g. the [MaeMetric].
+///
+/// The outputs and the targets have the dimensions `[batch_size, num_outputs]`.
+#[derive(new)]
+pub struct RegressionInput<B: Backend> {
+    outputs: Tensor<B, 2>,
+    targets: Tensor<B, 2>,
+}
+
+impl<B: Backend> RegressionInput<B> {
+    fn errors(&self) -> Tensor<B, 2> {
+        assert_eq!(
+            self.outputs.dims(),
+            self.targets.dims(),
+            "Outputs and targets must have the same dimensions."
+        );
+        self.targets.clone() - self.outputs.clone()
+    }
+}
+
+/// The mean and the sum of the squared deviations from the mean of each output, accumulated over
+/// the batches with the parallel algorithm of Chan et al.
+#[derive(Clone, Debug, Default)]
+struct RunningMoments {
+    count: usize,
+    mean: Vec<f64>,
+    m2: Vec<f64>,
+}
+
+impl RunningMoments {
+    fn update<B: Backend>(&mut self, values: Tensor<B, 2>) {
+        let [batch_size, num_outputs] = values.dims();
+        if batch_size == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.mean = vec![0.0; num_outputs];
+            self.m2 = vec![0.0; num_outputs];
+        }
+
+        let batch_mean = values.clone().mean_dim(0);
+        let batch_m2 = (values - batch_mean.clone()).powi_scalar(2).sum_dim(0);
+        let batch_mean = batch_mean.to_data().iter::<f64>().collect::<Vec<_>>();
+        let batch_m2 = batch_m2.to_data().iter::<f64>().collect::<Vec<_>>();
+
+        let count = self.count + batch_size;
+        let weight = batch_size as f64 / count as f64;
+        for i in 0..num_outputs {
+            let delta = batch_mean[i] - self.mean[i];
+            self.mean[i] += delta * weight;
+            self.m2[i] += batch_m2[i] + delta * delta * self.count as f64 * weight;
+        }
+        self.count = count;
+    }
+}
+
+/// Uniform average over the outputs of `1 - residual / total`, which is 1 for an output without
+/// variance when its residual is 0, and 0 otherwise.
+fn average_score(residuals: impl Iterator<Item = f64>, totals: &[f64]) -> f64 {
+    let scores = residuals
+        .zip(totals)
+        .map(|(residual, &total)| match total > 0.0 {
+            true => 1.0 - residual / total,
+            false if residual == 0.0 => 1.0,
+            false => 0.0,
+        })
+        .collect::<Vec<_>>();
+
+    match scores.is_empty() {
+        true => 0.0,
+        false => scores.iter().sum::<f64>() / scores.len() as f64,
+    }
+}
+
+/// The mean absolute error (MAE) of a regression.
+#[derive(Clone)]
+pub struct MaeMetric<B: Backend> {
+    name: MetricName,
+    state: NumericMetricState,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for MaeMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> MaeMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("MAE".to_string()),
+            state: Default::default(),
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for MaeMetric<B> {
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let errors = input.errors();
+        let num_elements = errors.shape().num_elements();
+        let mae = errors.abs().mean().into_scalar().elem::<f64>();
+
+        self.state.update(
+            mae,
+            num_elements,
+            FormatOptions::new(self.name()).precision(4),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for MaeMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The root mean squared error (RMSE) of a regression, over all the outputs of the epoch.
+#[derive(Clone)]
+pub struct RmseMetric<B: Backend> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    sum_squared_errors: f64,
+    count: usize,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for RmseMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> RmseMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("RMSE".to_string()),
+            state: Default::default(),
+            sum_squared_errors: 0.0,
+            count: 0,
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for RmseMetric<B> {
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let errors = input.errors();
+        self.count += errors.shape().num_elements();
+        self.sum_squared_errors += errors.powi_scalar(2).sum().into_scalar().elem::<f64>();
+
+        let rmse = match self.count {
+            0 => 0.0,
+            count => (self.sum_squared_errors / count as f64).sqrt(),
+        };
+
+        self.state
+            .update(rmse, FormatOptions::new(self.name()).precision(4))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.sum_squared_errors = 0.0;
+        self.count = 0;
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for RmseMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The mean absolute percentage error (MAPE) of a regression.
+///
+/// The targets close to zero are clamped to the machine epsilon, so their error is large instead
+/// of infinite.
+#[derive(Clone)]
+pub struct MapeMetric<B: Backend> {
+    name: MetricName,
+    state: NumericMetricState,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for MapeMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> MapeMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("MAPE".to_string()),
+            state: Default::default(),
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for MapeMetric<B> {
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let errors = input.errors();
+        let num_elements = errors.shape().num_elements();
+        let targets = input.targets.clone().abs().clamp_min(f32::EPSILON);
+        let mape = (errors.abs() / targets).mean().into_scalar().elem::<f64>();
+
+        self.state.update(
+            100.0 * mape,
+            num_elements,
+            FormatOptions::new(self.name()).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for MapeMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The coefficient of determination (R²) of a regression, i.e. the fraction of the variance of
+/// the targets explained by the outputs.
+///
+/// The sums of squares are accumulated over the epoch, and the score is averaged over the outputs.
+#[derive(Clone)]
+pub struct R2ScoreMetric<B: Backend> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    targets: RunningMoments,
+    errors: RunningMoments,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for R2ScoreMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> R2ScoreMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("R2".to_string()),
+            state: Default::default(),
+            targets: Default::default(),
+            errors: Default::default(),
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for R2ScoreMetric<B> {
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        self.errors.update(input.errors());
+        self.targets.update(input.targets.clone());
+
+        // The sum of the squared errors, from their mean and their sum of squared deviations.
+        let count = self.errors.count as f64;
+        let residuals = self
+            .errors
+            .m2
+            .iter()
+            .zip(self.errors.mean.iter())
+            .map(|(m2, mean)| m2 + count * mean * mean);
+        let score = average_score(residuals, &self.targets.m2);
+
+        self.state
+            .update(score, FormatOptions::new(self.name()).precision(4))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.targets = Default::default();
+        self.errors = Default::default();
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for R2ScoreMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The explained variance of a regression, i.e. one minus the variance of the errors over the
+/// variance of the targets, which unlike the [R²](R2ScoreMetric) ignores a constant bias of the
+/// outputs.
+///
+/// The variances are accumulated over the epoch, and the score is averaged over the outputs.
+#[derive(Clone)]
+pub struct ExplainedVarianceMetric<B: Backend> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    targets: RunningMoments,
+    errors: RunningMoments,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for ExplainedVarianceMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> ExplainedVarianceMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("Explained Variance".to_string()),
+            state: Default::default(),
+            targets: Default::default(),
+            errors: Default::default(),
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for ExplainedVarianceMetric<B> {
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        self.errors.update(input.errors());
+        self.targets.update(input.targets.clone());
+
+        let score = average_score(self.errors.m2.iter().copied(), &self.targets.m2);
+
+        self.state
+            .update(score, FormatOptions::new(self.name()).precision(4))
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.targets = Default::default();
+        self.errors = Default::default();
+    }
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+}
+
+impl<B: Backend> Numeric for ExplainedVarianceMetric<B> {
+    fn value(&self) -> super::NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn input(outputs: [[f32; 1]; 4], targets: [[f32; 1]; 4]) -> RegressionInput<TestBackend> {
+        let device = Default::default();
+        RegressionInput::new(
+            Tensor::from_data(outputs, &device),
+            Tensor::from_data(targets, &device),
+        )
+    }
+
+    #[test]
+    fn test_mae_rmse_mape() {
+        let input = input([[1.0], [2.0], [5.0], [4.0]], [[2.0], [2.0], [4.0], [4.0]]);
+
+        let mut metric = MaeMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 0.5).abs() < 1e-6);
+
+        let mut metric = RmseMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 0.5_f64.sqrt()).abs() < 1e-6);
+
+        let mut metric = MapeMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0 * (0.5 + 0.25) / 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_r2_and_explained_variance_are_accumulated_over_batches() {
+        let mut r2 = R2ScoreMetric::<TestBackend>::new();
+        let mut explained_variance = ExplainedVarianceMetric::<TestBackend>::new();
+
+        // The targets have a mean of 2.5 and a sum of squares of 5, the outputs have a constant
+        // bias of 1.
+        let batches = [
+            input([[2.0], [3.0], [4.0], [5.0]], [[1.0], [2.0], [3.0], [4.0]]),
+            input([[2.0], [3.0], [4.0], [5.0]], [[1.0], [2.0], [3.0], [4.0]]),
+        ];
+        for batch in batches.iter() {
+            let _entry = r2.update(batch, &MetricMetadata::fake());
+            let _entry = explained_variance.update(batch, &MetricMetadata::fake());
+        }
+
+        assert!((r2.value().current() - (1.0 - 8.0 / 10.0)).abs() < 1e-6);
+        assert!((explained_variance.value().current() - 1.0).abs() < 1e-6);
+    }
+}
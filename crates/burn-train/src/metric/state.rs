@@ -127,3 +127,59 @@ impl Default for NumericMetricState {
         Self::new()
     }
 }
+
+/// Useful utility to implement numeric metrics computed from statistics accumulated over the
+/// epoch, e.g. a confusion matrix, whose value isn't the mean of their values on each batch.
+///
+/// # Notes
+///
+/// Each entry is the value of the metric on all the items of the epoch so far, logged as a
+/// [cumulative](NumericEntry::Cumulative) entry, so the value of the metric for the epoch is its
+/// last entry.
+#[derive(Clone)]
+pub struct AccumulatedMetricState {
+    current: f64,
+}
+
+impl AccumulatedMetricState {
+    /// Create a new [accumulated metric state](AccumulatedMetricState).
+    pub fn new() -> Self {
+        Self { current: f64::NAN }
+    }
+
+    /// Reset the state.
+    pub fn reset(&mut self) {
+        self.current = f64::NAN;
+    }
+
+    /// Update the state with the value of the metric computed from the statistics accumulated
+    /// since the start of the epoch.
+    pub fn update(&mut self, value: f64, format: FormatOptions) -> MetricEntry {
+        self.current = value;
+
+        let serialized = NumericEntry::Cumulative(value).serialize();
+
+        let formatted = match format.precision {
+            Some(precision) => format_float(value, precision),
+            None => format!("{value}"),
+        };
+        let formatted = match format.unit {
+            Some(unit) => format!("epoch {formatted} {unit}"),
+            None => format!("epoch {formatted}"),
+        };
+
+        MetricEntry::new(format.name, formatted, serialized)
+    }
+}
+
+impl Numeric for AccumulatedMetricState {
+    fn value(&self) -> NumericEntry {
+        NumericEntry::Cumulative(self.current)
+    }
+}
+
+impl Default for AccumulatedMetricState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -48,24 +48,33 @@ impl NumericMetricsAggregate {
             return None;
         }
 
+        // The last cumulative entry is already computed from all the items of the epoch.
+        let value = match points.last() {
+            Some(NumericEntry::Cumulative(value)) => *value,
+            _ => Self::mean(points, aggregate),
+        };
+
+        self.value_for_each_epoch.insert(key, value);
+        Some(value)
+    }
+
+    fn mean(points: Vec<NumericEntry>, aggregate: Aggregate) -> f64 {
         // Accurately compute the aggregated value based on the *actual* number of points
         // since not all mini-batches are guaranteed to have the specified batch size
         let (sum, num_points) = points
             .into_iter()
             .map(|entry| match entry {
-                NumericEntry::Value(v) => (v, 1),
+                NumericEntry::Value(v) | NumericEntry::Cumulative(v) => (v, 1),
                 // Right now the mean is the only aggregate available, so we can assume that the sum
                 // of an entry corresponds to (value * number of elements)
                 NumericEntry::Aggregated { sum, count, .. } => (sum * count as f64, count),
             })
             .reduce(|(acc_v, acc_n), (v, n)| (acc_v + v, acc_n + n))
             .unwrap();
-        let value = match aggregate {
-            Aggregate::Mean => sum / num_points as f64,
-        };
 
-        self.value_for_each_epoch.insert(key, value);
-        Some(value)
+        match aggregate {
+            Aggregate::Mean => sum / num_points as f64,
+        }
     }
 
     pub(crate) fn find_epoch(
@@ -214,4 +223,31 @@ mod tests {
         // Average should be (0.5 + 1.25 * 2) / 3 = 1.0, not (0.5 + 1.25) / 2 = 0.875
         assert_eq!(value, 1.0);
     }
+
+    #[test]
+    fn should_aggregate_cumulative_entries_to_the_last_one() {
+        let mut logger = InMemoryMetricLogger::default();
+        let mut aggregate = NumericMetricsAggregate::default();
+        let metric_name = Arc::new("BLEU".to_string());
+
+        for value in [20.0, 35.0, 30.0] {
+            let entry = MetricEntry::new(
+                metric_name.clone(),
+                value.to_string(),
+                NumericEntry::Cumulative(value).serialize(),
+            );
+            logger.log(&entry);
+        }
+
+        let value = aggregate
+            .aggregate(
+                metric_name.as_str(),
+                1,
+                Aggregate::Mean,
+                &mut [Box::new(logger)],
+            )
+            .unwrap();
+
+        assert_eq!(value, 30.0);
+    }
 }
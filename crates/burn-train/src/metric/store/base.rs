@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use burn_core::lr_scheduler::MetricSplit;
+
 use crate::metric::{MetricEntry, NumericEntry};
 
 /// Event happening during the training/validation process.
@@ -75,6 +77,15 @@ pub enum Split {
     Test,
 }
 
+impl From<MetricSplit> for Split {
+    fn from(split: MetricSplit) -> Self {
+        match split {
+            MetricSplit::Train => Split::Train,
+            MetricSplit::Valid => Split::Valid,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 /// The direction of the query.
 pub enum Direction {
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Input type for the text generation metrics, e.g. [BleuMetric](super::BleuMetric).
+///
+/// The predictions are the generated sequences decoded to text, and the references are the
+/// expected texts. The words of the texts are separated by whitespaces, so the texts should be
+/// tokenized and normalized the same way before being given to the metrics.
+#[derive(Clone, Debug)]
+pub struct TextGenerationInput {
+    predictions: Vec<String>,
+    references: Vec<String>,
+}
+
+impl TextGenerationInput {
+    /// Creates a new text generation input with the prediction and the reference of each item
+    /// of the batch.
+    ///
+    /// # Panics
+    /// If the number of predictions and references differ.
+    pub fn new(predictions: Vec<String>, references: Vec<String>) -> Self {
+        assert_eq!(
+            predictions.len(),
+            references.len(),
+            "Each prediction must have a reference."
+        );
+        Self {
+            predictions,
+            references,
+        }
+    }
+
+    /// The number of items in the batch.
+    pub(crate) fn len(&self) -> usize {
+        self.predictions.len()
+    }
+
+    /// The prediction and the reference of each item.
+    pub(crate) fn pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.predictions
+            .iter()
+            .zip(self.references.iter())
+            .map(|(prediction, reference)| (prediction.as_str(), reference.as_str()))
+    }
+}
+
+/// The whitespace separated words of the text.
+pub(crate) fn words(text: &str) -> Vec<&str> {
+    text.split_whitespace().collect()
+}
+
+/// The count of each n-gram of the sequence.
+pub(crate) fn ngrams<T: Eq + Hash>(sequence: &[T], n: usize) -> HashMap<&[T], usize> {
+    let mut counts = HashMap::new();
+    if n == 0 {
+        return counts;
+    }
+    for ngram in sequence.windows(n) {
+        *counts.entry(ngram).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// The number of n-grams of the prediction found in the reference, where each n-gram of the
+/// reference is matched at most once.
+pub(crate) fn ngram_matches<T: Eq + Hash>(prediction: &[T], reference: &[T], n: usize) -> usize {
+    let references = ngrams(reference, n);
+    ngrams(prediction, n)
+        .into_iter()
+        .map(|(ngram, count)| count.min(references.get(ngram).copied().unwrap_or(0)))
+        .sum()
+}
+
+/// The number of n-grams of the sequence.
+pub(crate) fn ngram_total<T>(sequence: &[T], n: usize) -> usize {
+    match n {
+        0 => 0,
+        n => (sequence.len() + 1).saturating_sub(n),
+    }
+}
+
+/// The Levenshtein distance between the sequences, i.e. the minimum number of substitutions,
+/// deletions and insertions to change one into the other.
+pub(crate) fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, item_a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, item_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(item_a != item_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// The length of the longest common subsequence of the sequences.
+pub(crate) fn longest_common_subsequence<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut previous = vec![0; b.len() + 1];
+    let mut current = vec![0; b.len() + 1];
+
+    for item_a in a.iter() {
+        for (j, item_b) in b.iter().enumerate() {
+            current[j + 1] = match item_a == item_b {
+                true => previous[j] + 1,
+                false => previous[j + 1].max(current[j]),
+            };
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ngram_matches_are_clipped() {
+        let prediction = words("the the the cat");
+        let reference = words("the cat on the mat");
+
+        assert_eq!(ngram_matches(&prediction, &reference, 1), 3);
+        assert_eq!(ngram_matches(&prediction, &reference, 2), 1);
+        assert_eq!(ngram_total(&prediction, 2), 3);
+        assert_eq!(ngram_total(&prediction, 5), 0);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let kitten = "kitten".chars().collect::<Vec<_>>();
+        let sitting = "sitting".chars().collect::<Vec<_>>();
+
+        assert_eq!(edit_distance(&kitten, &sitting), 3);
+        assert_eq!(edit_distance(&kitten, &[]), 6);
+        assert_eq!(edit_distance::<char>(&[], &sitting), 7);
+    }
+
+    #[test]
+    fn test_longest_common_subsequence() {
+        let a = words("the cat sat on the mat");
+        let b = words("the cat was on a mat");
+
+        assert_eq!(longest_common_subsequence(&a, &b), 4);
+    }
+}
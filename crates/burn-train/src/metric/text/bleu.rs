@@ -0,0 +1,216 @@
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, MetricName, Numeric, NumericEntry,
+    state::{AccumulatedMetricState, FormatOptions},
+};
+use super::{TextGenerationInput, ngram_matches, ngram_total, words};
+
+/// The smoothing of the n-gram precisions of the [BleuMetric], to avoid a score of zero when an
+/// order has no match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BleuSmoothing {
+    /// No smoothing, the score is zero when an order has no match.
+    None,
+    /// The orders without match have the given number of matches instead.
+    Floor(f64),
+    /// The given value is added to the matches and to the total of the orders above 1.
+    AddK(f64),
+    /// The precision of the k-th order without match is `1 / (2^k * total)`, as in the NIST
+    /// `mteval` script.
+    Exponential,
+}
+
+/// The BLEU statistics of the corpus.
+#[derive(Clone, Debug, Default)]
+struct BleuStatistics {
+    matches: Vec<usize>,
+    totals: Vec<usize>,
+    prediction_length: usize,
+    reference_length: usize,
+}
+
+impl BleuStatistics {
+    fn new(max_order: usize) -> Self {
+        Self {
+            matches: vec![0; max_order],
+            totals: vec![0; max_order],
+            prediction_length: 0,
+            reference_length: 0,
+        }
+    }
+
+    fn update(&mut self, prediction: &[&str], reference: &[&str]) {
+        for n in 1..=self.matches.len() {
+            self.matches[n - 1] += ngram_matches(prediction, reference, n);
+            self.totals[n - 1] += ngram_total(prediction, n);
+        }
+        self.prediction_length += prediction.len();
+        self.reference_length += reference.len();
+    }
+
+    fn bleu(&self, smoothing: BleuSmoothing) -> f64 {
+        if self.prediction_length == 0 {
+            return 0.0;
+        }
+
+        let mut exponential = 1.0;
+        let mut sum_log_precisions = 0.0;
+
+        for (n, (&matches, &total)) in self.matches.iter().zip(self.totals.iter()).enumerate() {
+            let (mut matches, mut total) = (matches as f64, total as f64);
+            match smoothing {
+                BleuSmoothing::AddK(k) if n > 0 => {
+                    matches += k;
+                    total += k;
+                }
+                BleuSmoothing::Floor(floor) if matches == 0.0 => matches = floor,
+                BleuSmoothing::Exponential if matches == 0.0 => {
+                    exponential *= 2.0;
+                    matches = 1.0 / exponential;
+                }
+                _ => {}
+            }
+
+            if matches <= 0.0 || total <= 0.0 {
+                return 0.0;
+            }
+            sum_log_precisions += (matches / total).ln();
+        }
+
+        let brevity_penalty = match self.prediction_length < self.reference_length {
+            true => (1.0 - self.reference_length as f64 / self.prediction_length as f64).exp(),
+            false => 1.0,
+        };
+
+        brevity_penalty * (sum_log_precisions / self.matches.len() as f64).exp()
+    }
+}
+
+/// The corpus-level BLEU score of generated texts.
+///
+/// The clipped n-gram matches and the lengths are accumulated over the epoch, so the value is the
+/// BLEU score of the whole corpus of the epoch rather than the mean of the score of each batch or
+/// sentence. The words are the whitespace separated tokens of the
+/// [texts](TextGenerationInput), with a single reference per prediction.
+#[derive(Clone)]
+pub struct BleuMetric {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    statistics: BleuStatistics,
+    smoothing: BleuSmoothing,
+}
+
+impl Default for BleuMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BleuMetric {
+    /// Creates the BLEU metric up to 4-grams, with the [exponential](BleuSmoothing::Exponential)
+    /// smoothing.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("BLEU".to_string()),
+            state: Default::default(),
+            statistics: BleuStatistics::new(4),
+            smoothing: BleuSmoothing::Exponential,
+        }
+    }
+
+    /// Sets the maximum order of the n-grams.
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        assert!(max_order > 0, "The maximum order must be at least 1.");
+        self.statistics = BleuStatistics::new(max_order);
+        self
+    }
+
+    /// Sets the smoothing of the precisions.
+    pub fn with_smoothing(mut self, smoothing: BleuSmoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+}
+
+impl Metric for BleuMetric {
+    type Input = TextGenerationInput;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        for (prediction, reference) in item.pairs() {
+            self.statistics
+                .update(&words(prediction), &words(reference));
+        }
+
+        self.state.update(
+            100.0 * self.statistics.bleu(self.smoothing),
+            FormatOptions::new(self.name()).precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.statistics = BleuStatistics::new(self.statistics.matches.len());
+    }
+}
+
+impl Numeric for BleuMetric {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(prediction: &str, reference: &str) -> TextGenerationInput {
+        TextGenerationInput::new(vec![prediction.to_string()], vec![reference.to_string()])
+    }
+
+    #[test]
+    fn test_bleu() {
+        let mut metric = BleuMetric::new();
+        let _entry = metric.update(
+            &input("the cat sat on the mat", "the cat sat on the mat"),
+            &MetricMetadata::fake(),
+        );
+        assert!((metric.value().current() - 100.0).abs() < 1e-9);
+
+        let mut metric = BleuMetric::new();
+        let _entry = metric.update(
+            &input("the cat sat on the mat", "the cat sat on a mat"),
+            &MetricMetadata::fake(),
+        );
+        let expected = 100.0 * (5.0 / 6.0 * 3.0 / 5.0 * 2.0 / 4.0 * 1.0 / 3.0_f64).powf(0.25);
+        assert!((metric.value().current() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bleu_smoothing() {
+        let input = input("the cat is on a mat", "the cat sat on a mat");
+
+        let mut metric = BleuMetric::new().with_smoothing(BleuSmoothing::None);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.value().current(), 0.0);
+
+        // The 4-grams have no match, so their precision is 1 / (2 * 3).
+        let mut metric = BleuMetric::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        let expected = 100.0 * (5.0 / 6.0 * 3.0 / 5.0 * 1.0 / 4.0 * 1.0 / 6.0_f64).powf(0.25);
+        assert!((metric.value().current() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bleu_is_accumulated_over_batches() {
+        let mut metric = BleuMetric::new().with_max_order(1);
+        let _entry = metric.update(&input("a b", "a b"), &MetricMetadata::fake());
+        let _entry = metric.update(&input("c d", "c e f g"), &MetricMetadata::fake());
+
+        // 3 of the 4 words are matched, with a brevity penalty of exp(1 - 6 / 4).
+        let expected = 100.0 * 0.75 * (1.0 - 6.0 / 4.0_f64).exp();
+        assert!((metric.value().current() - expected).abs() < 1e-9);
+    }
+}
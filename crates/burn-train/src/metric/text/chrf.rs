@@ -0,0 +1,162 @@
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, MetricName, Numeric, NumericEntry,
+    state::{AccumulatedMetricState, FormatOptions},
+};
+use super::{TextGenerationInput, ngram_matches, ngram_total};
+
+/// The character n-gram statistics of the corpus for each order.
+#[derive(Clone, Debug, Default)]
+struct ChrFStatistics {
+    matches: Vec<usize>,
+    predictions: Vec<usize>,
+    references: Vec<usize>,
+}
+
+impl ChrFStatistics {
+    fn new(char_order: usize) -> Self {
+        Self {
+            matches: vec![0; char_order],
+            predictions: vec![0; char_order],
+            references: vec![0; char_order],
+        }
+    }
+
+    fn update(&mut self, prediction: &str, reference: &str) {
+        let prediction = chars(prediction);
+        let reference = chars(reference);
+
+        for n in 1..=self.matches.len() {
+            self.matches[n - 1] += ngram_matches(&prediction, &reference, n);
+            self.predictions[n - 1] += ngram_total(&prediction, n);
+            self.references[n - 1] += ngram_total(&reference, n);
+        }
+    }
+
+    /// The F-score averaged over the orders found in both the predictions and the references.
+    fn chrf(&self, beta: f64) -> f64 {
+        let factor = beta * beta;
+        let mut sum = 0.0;
+        let mut orders = 0;
+
+        for n in 0..self.matches.len() {
+            if self.predictions[n] == 0 || self.references[n] == 0 {
+                continue;
+            }
+            orders += 1;
+
+            let precision = self.matches[n] as f64 / self.predictions[n] as f64;
+            let recall = self.matches[n] as f64 / self.references[n] as f64;
+            let denominator = factor * precision + recall;
+            if denominator > 0.0 {
+                sum += (1.0 + factor) * precision * recall / denominator;
+            }
+        }
+
+        match orders {
+            0 => 0.0,
+            orders => sum / orders as f64,
+        }
+    }
+}
+
+/// The characters of the text, without the whitespaces.
+fn chars(text: &str) -> Vec<char> {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// The corpus-level chrF score of generated texts, i.e. the F-score of their character n-grams.
+///
+/// The n-gram statistics are accumulated over the epoch, so the value is the chrF score of the
+/// whole corpus of the epoch rather than the mean of the score of each batch or sentence. The
+/// whitespaces are ignored.
+#[derive(Clone)]
+pub struct ChrFMetric {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    statistics: ChrFStatistics,
+    beta: f64,
+}
+
+impl Default for ChrFMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChrFMetric {
+    /// Creates the chrF metric up to character 6-grams, with a beta of 2 weighting the recall
+    /// twice as much as the precision.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("chrF".to_string()),
+            state: Default::default(),
+            statistics: ChrFStatistics::new(6),
+            beta: 2.0,
+        }
+    }
+
+    /// Sets the maximum order of the character n-grams.
+    pub fn with_char_order(mut self, char_order: usize) -> Self {
+        assert!(char_order > 0, "The character order must be at least 1.");
+        self.statistics = ChrFStatistics::new(char_order);
+        self
+    }
+
+    /// Sets the importance of the recall relative to the precision.
+    pub fn with_beta(mut self, beta: f64) -> Self {
+        self.beta = beta;
+        self
+    }
+}
+
+impl Metric for ChrFMetric {
+    type Input = TextGenerationInput;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        for (prediction, reference) in item.pairs() {
+            self.statistics.update(prediction, reference);
+        }
+
+        self.state.update(
+            100.0 * self.statistics.chrf(self.beta),
+            FormatOptions::new(self.name()).precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.statistics = ChrFStatistics::new(self.statistics.matches.len());
+    }
+}
+
+impl Numeric for ChrFMetric {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(prediction: &str, reference: &str) -> TextGenerationInput {
+        TextGenerationInput::new(vec![prediction.to_string()], vec![reference.to_string()])
+    }
+
+    #[test]
+    fn test_chrf() {
+        let mut metric = ChrFMetric::new();
+        let _entry = metric.update(&input("a cat", "acat"), &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0).abs() < 1e-9);
+
+        let mut metric = ChrFMetric::new().with_char_order(2).with_beta(1.0);
+        let _entry = metric.update(&input("abc", "abd"), &MetricMetadata::fake());
+        // 2 of the 3 unigrams and 1 of the 2 bigrams are matched.
+        let expected = 100.0 * (2.0 / 3.0 + 1.0 / 2.0) / 2.0;
+        assert!((metric.value().current() - expected).abs() < 1e-9);
+    }
+}
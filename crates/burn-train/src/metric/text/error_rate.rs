@@ -0,0 +1,156 @@
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, MetricName, Numeric, NumericEntry,
+    state::{FormatOptions, NumericMetricState},
+};
+use super::{TextGenerationInput, edit_distance, words};
+
+/// The total edit distance and reference length of the batch, at the level of the words or of
+/// the characters.
+fn edits(item: &TextGenerationInput, words_level: bool) -> (usize, usize) {
+    item.pairs()
+        .map(|(prediction, reference)| match words_level {
+            true => {
+                let reference = words(reference);
+                (
+                    edit_distance(&words(prediction), &reference),
+                    reference.len(),
+                )
+            }
+            false => {
+                let reference = reference.chars().collect::<Vec<_>>();
+                let prediction = prediction.chars().collect::<Vec<_>>();
+                (edit_distance(&prediction, &reference), reference.len())
+            }
+        })
+        .fold((0, 0), |(edits, length), (e, l)| (edits + e, length + l))
+}
+
+/// Update the state with the error rate of the batch, weighted by the length of its references so
+/// that the value of the epoch is the total edit distance over the total reference length.
+fn update_error_rate(
+    state: &mut NumericMetricState,
+    (edits, length): (usize, usize),
+    name: MetricName,
+) -> MetricEntry {
+    let length = length.max(1);
+    state.update(
+        100.0 * edits as f64 / length as f64,
+        length,
+        FormatOptions::new(name).unit("%").precision(2),
+    )
+}
+
+/// The character error rate (CER) of generated texts, i.e. the edit distance between the
+/// characters of the predictions and of the references over the number of characters of the
+/// references.
+#[derive(Clone)]
+pub struct CharErrorRateMetric {
+    name: MetricName,
+    state: NumericMetricState,
+}
+
+impl Default for CharErrorRateMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharErrorRateMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("CER".to_string()),
+            state: Default::default(),
+        }
+    }
+}
+
+impl Metric for CharErrorRateMetric {
+    type Input = TextGenerationInput;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        update_error_rate(&mut self.state, edits(item, false), self.name())
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+    }
+}
+
+impl Numeric for CharErrorRateMetric {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The word error rate (WER) of generated texts, i.e. the edit distance between the whitespace
+/// separated words of the predictions and of the references over the number of words of the
+/// references.
+#[derive(Clone)]
+pub struct WordErrorRateMetric {
+    name: MetricName,
+    state: NumericMetricState,
+}
+
+impl Default for WordErrorRateMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WordErrorRateMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("WER".to_string()),
+            state: Default::default(),
+        }
+    }
+}
+
+impl Metric for WordErrorRateMetric {
+    type Input = TextGenerationInput;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        update_error_rate(&mut self.state, edits(item, true), self.name())
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+    }
+}
+
+impl Numeric for WordErrorRateMetric {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_rates() {
+        let input = TextGenerationInput::new(
+            vec!["the cat sat".to_string(), "hello".to_string()],
+            vec!["the cat sat down".to_string(), "hallo".to_string()],
+        );
+
+        let mut metric = WordErrorRateMetric::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0 * 2.0 / 5.0).abs() < 1e-9);
+
+        let mut metric = CharErrorRateMetric::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0 * 6.0 / 21.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,11 @@
+mod base;
+mod bleu;
+mod chrf;
+mod error_rate;
+mod rouge;
+
+pub use base::*;
+pub use bleu::*;
+pub use chrf::*;
+pub use error_rate::*;
+pub use rouge::*;
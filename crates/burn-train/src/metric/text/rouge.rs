@@ -0,0 +1,135 @@
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, MetricName, Numeric, NumericEntry,
+    state::{FormatOptions, NumericMetricState},
+};
+use super::{TextGenerationInput, longest_common_subsequence, ngram_matches, ngram_total, words};
+
+/// The overlap between the predictions and the references measured by the [RougeMetric].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RougeVariant {
+    /// The overlap of the n-grams, e.g. ROUGE-1 for the words and ROUGE-2 for the bigrams.
+    N(usize),
+    /// The longest common subsequence of the words.
+    L,
+}
+
+/// The ROUGE F1 score of generated texts, e.g. ROUGE-1, ROUGE-2 or ROUGE-L.
+///
+/// The score of each prediction is computed against its reference, and the value of the epoch is
+/// the mean of the scores of all its predictions. The words are the whitespace separated tokens
+/// of the [texts](TextGenerationInput).
+#[derive(Clone)]
+pub struct RougeMetric {
+    name: MetricName,
+    state: NumericMetricState,
+    variant: RougeVariant,
+}
+
+impl RougeMetric {
+    /// Creates the metric of the given variant.
+    pub fn new(variant: RougeVariant) -> Self {
+        let name = match variant {
+            RougeVariant::N(n) => {
+                assert!(n > 0, "The n-grams must have at least 1 word.");
+                format!("ROUGE-{n}")
+            }
+            RougeVariant::L => "ROUGE-L".to_string(),
+        };
+
+        Self {
+            name: MetricName::new(name),
+            state: Default::default(),
+            variant,
+        }
+    }
+
+    /// The F1 score of the prediction.
+    fn score(&self, prediction: &str, reference: &str) -> f64 {
+        let prediction = words(prediction);
+        let reference = words(reference);
+
+        let (matches, predictions, references) = match self.variant {
+            RougeVariant::N(n) => (
+                ngram_matches(&prediction, &reference, n),
+                ngram_total(&prediction, n),
+                ngram_total(&reference, n),
+            ),
+            RougeVariant::L => (
+                longest_common_subsequence(&prediction, &reference),
+                prediction.len(),
+                reference.len(),
+            ),
+        };
+
+        if matches == 0 {
+            return 0.0;
+        }
+        let precision = matches as f64 / predictions as f64;
+        let recall = matches as f64 / references as f64;
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+impl Metric for RougeMetric {
+    type Input = TextGenerationInput;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        let batch_size = item.len();
+        let sum = item
+            .pairs()
+            .map(|(prediction, reference)| self.score(prediction, reference))
+            .sum::<f64>();
+        let score = match batch_size {
+            0 => 0.0,
+            _ => sum / batch_size as f64,
+        };
+
+        self.state.update(
+            100.0 * score,
+            batch_size.max(1),
+            FormatOptions::new(self.name()).precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+    }
+}
+
+impl Numeric for RougeMetric {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rouge() {
+        let input = TextGenerationInput::new(
+            vec!["the cat sat on the mat".to_string()],
+            vec!["the cat was on a mat".to_string()],
+        );
+
+        // 4 of the 6 words are matched.
+        let mut metric = RougeMetric::new(RougeVariant::N(1));
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0 * 4.0 / 6.0).abs() < 1e-9);
+
+        // Only "the cat" is matched among the 5 bigrams.
+        let mut metric = RougeMetric::new(RougeVariant::N(2));
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0 / 5.0).abs() < 1e-9);
+
+        let mut metric = RougeMetric::new(RougeVariant::L);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.name().as_str(), "ROUGE-L");
+        assert!((metric.value().current() - 100.0 * 4.0 / 6.0).abs() < 1e-9);
+    }
+}
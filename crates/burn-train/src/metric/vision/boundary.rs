@@ -0,0 +1,258 @@
+use crate::metric::MetricName;
+
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, Numeric, NumericEntry,
+    state::{AccumulatedMetricState, FormatOptions},
+};
+use super::SegmentationInput;
+use burn_core::prelude::Backend;
+use core::marker::PhantomData;
+
+/// The boundary pixels of the outputs and of the targets, and how many of them are matched,
+/// accumulated over the batches.
+#[derive(Clone, Debug, Default)]
+struct BoundaryCounts {
+    outputs: u64,
+    matched_outputs: u64,
+    targets: u64,
+    matched_targets: u64,
+}
+
+impl BoundaryCounts {
+    fn f1(&self) -> f64 {
+        if self.outputs == 0 && self.targets == 0 {
+            return 1.0;
+        }
+        if self.outputs == 0 || self.targets == 0 {
+            return 0.0;
+        }
+
+        let precision = self.matched_outputs as f64 / self.outputs as f64;
+        let recall = self.matched_targets as f64 / self.targets as f64;
+        if precision + recall == 0.0 {
+            return 0.0;
+        }
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+/// The class of each boundary pixel of a mask of shape `[height, width]`, i.e. of each pixel with
+/// a 4-neighbour of another class.
+fn boundaries(mask: &[i64], height: usize, width: usize, ignored: &[bool]) -> Vec<Option<i64>> {
+    let mut boundaries = vec![None; mask.len()];
+
+    for y in 0..height {
+        for x in 0..width {
+            let index = y * width + x;
+            if ignored[index] {
+                continue;
+            }
+
+            let class = mask[index];
+            let right = x + 1 < width && mask[index + 1] != class;
+            let left = x > 0 && mask[index - 1] != class;
+            let down = y + 1 < height && mask[index + width] != class;
+            let up = y > 0 && mask[index - width] != class;
+
+            if right || left || down || up {
+                boundaries[index] = Some(class);
+            }
+        }
+    }
+
+    boundaries
+}
+
+/// Count the boundary pixels of `boundaries` and how many of them have a boundary pixel of the
+/// same class in `others` within the tolerance.
+fn count_matches(
+    boundaries: &[Option<i64>],
+    others: &[Option<i64>],
+    height: usize,
+    width: usize,
+    tolerance: usize,
+) -> (u64, u64) {
+    let mut total = 0;
+    let mut matched = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let Some(class) = boundaries[y * width + x] else {
+                continue;
+            };
+            total += 1;
+
+            let mut rows = y.saturating_sub(tolerance)..(y + tolerance + 1).min(height);
+            let is_matched = rows.any(|y| {
+                let mut cols = x.saturating_sub(tolerance)..(x + tolerance + 1).min(width);
+                cols.any(|x| others[y * width + x] == Some(class))
+            });
+            if is_matched {
+                matched += 1;
+            }
+        }
+    }
+
+    (total, matched)
+}
+
+/// The boundary F1 score (BF score) of semantic segmentation masks.
+///
+/// The boundary pixels are the pixels with a 4-neighbour of another class. A predicted boundary
+/// pixel is correct when a target boundary pixel of the same class is within the tolerance,
+/// measured in pixels along each axis, and conversely for the recall. The matches are accumulated
+/// over the epoch, so the value is the F1 score of all the boundaries of the epoch.
+///
+/// The [input](SegmentationInput) masks have the dimensions `[B, H, W]`, and are read from the
+/// device to find their boundaries.
+#[derive(Clone)]
+pub struct BoundaryF1Metric<B: Backend> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    counts: BoundaryCounts,
+    tolerance: usize,
+    ignore_index: Option<usize>,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for BoundaryF1Metric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> BoundaryF1Metric<B> {
+    /// Creates the metric with a tolerance of 2 pixels.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("Boundary F1".to_string()),
+            state: Default::default(),
+            counts: Default::default(),
+            tolerance: 2,
+            ignore_index: None,
+            _b: PhantomData,
+        }
+    }
+
+    /// Sets the distance in pixels within which the boundaries are matched.
+    pub fn with_tolerance(mut self, tolerance: usize) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Ignores the pixels whose target is the given class, e.g. the unlabeled pixels.
+    pub fn with_ignore_index(mut self, index: usize) -> Self {
+        self.ignore_index = Some(index);
+        self
+    }
+}
+
+impl<B: Backend> Metric for BoundaryF1Metric<B> {
+    type Input = SegmentationInput<B, 3>;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, height, width] = item.outputs().dims();
+        let (outputs, targets) = item.classes();
+        let size = height * width;
+
+        for i in 0..batch_size {
+            let outputs = &outputs[i * size..(i + 1) * size];
+            let targets = &targets[i * size..(i + 1) * size];
+            let ignored = targets
+                .iter()
+                .map(|&target| {
+                    self.ignore_index
+                        .is_some_and(|index| index as i64 == target)
+                })
+                .collect::<Vec<_>>();
+
+            let output_boundaries = boundaries(outputs, height, width, &ignored);
+            let target_boundaries = boundaries(targets, height, width, &ignored);
+
+            let (total, matched) = count_matches(
+                &output_boundaries,
+                &target_boundaries,
+                height,
+                width,
+                self.tolerance,
+            );
+            self.counts.outputs += total;
+            self.counts.matched_outputs += matched;
+
+            let (total, matched) = count_matches(
+                &target_boundaries,
+                &output_boundaries,
+                height,
+                width,
+                self.tolerance,
+            );
+            self.counts.targets += total;
+            self.counts.matched_targets += matched;
+        }
+
+        self.state.update(
+            100.0 * self.counts.f1(),
+            FormatOptions::new(self.name()).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.counts = Default::default();
+    }
+}
+
+impl<B: Backend> Numeric for BoundaryF1Metric<B> {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    fn input(
+        outputs: [[[i64; 4]; 4]; 1],
+        targets: [[[i64; 4]; 4]; 1],
+    ) -> SegmentationInput<TestBackend> {
+        let device = Default::default();
+        SegmentationInput::new(
+            Tensor::from_data(outputs, &device),
+            Tensor::from_data(targets, &device),
+        )
+    }
+
+    #[test]
+    fn test_boundary_f1_within_tolerance() {
+        let targets = [[[0, 0, 1, 1], [0, 0, 1, 1], [0, 0, 1, 1], [0, 0, 1, 1]]];
+        let outputs = [[[0, 1, 1, 1], [0, 1, 1, 1], [0, 1, 1, 1], [0, 1, 1, 1]]];
+
+        let mut metric = BoundaryF1Metric::<TestBackend>::new().with_tolerance(1);
+        let _entry = metric.update(&input(outputs, targets), &MetricMetadata::fake());
+        assert_eq!(metric.value().current(), 100.0);
+
+        // Without tolerance, the boundaries shifted by one pixel aren't matched.
+        let mut metric = BoundaryF1Metric::<TestBackend>::new().with_tolerance(0);
+        let _entry = metric.update(&input(outputs, targets), &MetricMetadata::fake());
+        assert_eq!(metric.value().current(), 0.0);
+    }
+
+    #[test]
+    fn test_boundary_f1_without_boundaries() {
+        let mut metric = BoundaryF1Metric::<TestBackend>::new();
+        let uniform = [[[1; 4]; 4]];
+        let _entry = metric.update(&input(uniform, uniform), &MetricMetadata::fake());
+        assert_eq!(metric.value().current(), 100.0);
+
+        let targets = [[[0, 0, 1, 1], [0, 0, 1, 1], [0, 0, 1, 1], [0, 0, 1, 1]]];
+        let _entry = metric.update(&input(uniform, targets), &MetricMetadata::fake());
+        assert_eq!(metric.value().current(), 0.0);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::metric::MetricName;
+
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, Numeric, NumericEntry,
+    state::{AccumulatedMetricState, FormatOptions},
+};
+use burn_core::{
+    prelude::{Backend, Tensor},
+    tensor::Int,
+};
+use core::marker::PhantomData;
+use std::collections::BTreeMap;
+
+/// The detections and the target boxes of an image, for the [DetectionInput].
+pub struct ImageDetections<B: Backend> {
+    boxes: Tensor<B, 2>,
+    scores: Tensor<B, 1>,
+    labels: Tensor<B, 1, Int>,
+    target_boxes: Tensor<B, 2>,
+    target_labels: Tensor<B, 1, Int>,
+}
+
+impl<B: Backend> ImageDetections<B> {
+    /// Creates the detections of an image.
+    ///
+    /// The boxes have the dimensions `[N, 4]` and the `(x_min, y_min, x_max, y_max)` coordinates,
+    /// where `N` is the number of detections, or the number of targets for the target boxes.
+    ///
+    /// # Panics
+    /// - If the boxes don't have 4 coordinates.
+    /// - If the number of boxes, scores and labels of the detections or of the targets differ.
+    pub fn new(
+        boxes: Tensor<B, 2>,
+        scores: Tensor<B, 1>,
+        labels: Tensor<B, 1, Int>,
+        target_boxes: Tensor<B, 2>,
+        target_labels: Tensor<B, 1, Int>,
+    ) -> Self {
+        let [num_detections, coordinates] = boxes.dims();
+        assert_eq!(coordinates, 4, "Detection boxes must have 4 coordinates.");
+        assert!(
+            scores.dims() == [num_detections] && labels.dims() == [num_detections],
+            "Detections must have the same length. Got {:?}, {:?} and {:?}",
+            boxes.dims(),
+            scores.dims(),
+            labels.dims()
+        );
+
+        let [num_targets, coordinates] = target_boxes.dims();
+        assert_eq!(coordinates, 4, "Target boxes must have 4 coordinates.");
+        assert!(
+            target_labels.dims() == [num_targets],
+            "Target boxes and labels must have the same length. Got {:?} and {:?}",
+            target_boxes.dims(),
+            target_labels.dims()
+        );
+
+        Self {
+            boxes,
+            scores,
+            labels,
+            target_boxes,
+            target_labels,
+        }
+    }
+
+    fn boxes(boxes: &Tensor<B, 2>) -> Vec<[f32; 4]> {
+        boxes
+            .to_data()
+            .iter::<f32>()
+            .collect::<Vec<_>>()
+            .chunks_exact(4)
+            .map(|coordinates| {
+                [
+                    coordinates[0],
+                    coordinates[1],
+                    coordinates[2],
+                    coordinates[3],
+                ]
+            })
+            .collect()
+    }
+
+    /// The detections and the targets, read from the device.
+    fn read(&self) -> (Vec<Detection>, Vec<Target>) {
+        let scores = self.scores.to_data().iter::<f32>().collect::<Vec<_>>();
+        let labels = self.labels.to_data().iter::<i64>().collect::<Vec<_>>();
+        let detections = Self::boxes(&self.boxes)
+            .into_iter()
+            .zip(scores)
+            .zip(labels)
+            .map(|((bbox, score), label)| Detection { bbox, score, label })
+            .collect();
+
+        let labels = self
+            .target_labels
+            .to_data()
+            .iter::<i64>()
+            .collect::<Vec<_>>();
+        let targets = Self::boxes(&self.target_boxes)
+            .into_iter()
+            .zip(labels)
+            .map(|(bbox, label)| Target { bbox, label })
+            .collect();
+
+        (detections, targets)
+    }
+}
+
+/// Input type for the [MeanAveragePrecisionMetric], with the detections of each image of the
+/// batch.
+pub struct DetectionInput<B: Backend> {
+    images: Vec<ImageDetections<B>>,
+}
+
+impl<B: Backend> DetectionInput<B> {
+    /// Creates a new detection input with the detections of each image.
+    pub fn new(images: Vec<ImageDetections<B>>) -> Self {
+        Self { images }
+    }
+}
+
+struct Detection {
+    bbox: [f32; 4],
+    score: f32,
+    label: i64,
+}
+
+struct Target {
+    bbox: [f32; 4],
+    label: i64,
+}
+
+/// The intersection over union of two `(x_min, y_min, x_max, y_max)` boxes.
+fn box_iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let area = |bbox: &[f32; 4]| (bbox[2] - bbox[0]).max(0.0) * (bbox[3] - bbox[1]).max(0.0);
+
+    let width = (a[2].min(b[2]) - a[0].max(b[0])).max(0.0);
+    let height = (a[3].min(b[3]) - a[1].max(b[1])).max(0.0);
+    let intersection = width * height;
+    let union = area(a) + area(b) - intersection;
+
+    match union > 0.0 {
+        true => intersection / union,
+        false => 0.0,
+    }
+}
+
+/// The detections of a class, with whether they are true positives at each IoU threshold,
+/// accumulated over the batches.
+#[derive(Clone, Debug, Default)]
+struct ClassDetections {
+    /// The score of each detection and whether it matched a target at each threshold.
+    detections: Vec<(f32, Vec<bool>)>,
+    num_targets: usize,
+}
+
+impl ClassDetections {
+    /// The COCO average precision at the threshold, interpolated on 101 recall points.
+    fn average_precision(&self, threshold: usize) -> f64 {
+        let mut detections = self
+            .detections
+            .iter()
+            .map(|(score, matches)| (*score, matches[threshold]))
+            .collect::<Vec<_>>();
+        detections.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut true_positives = 0;
+        let mut recalls = Vec::with_capacity(detections.len());
+        let mut precisions = Vec::with_capacity(detections.len());
+        for (i, (_, matched)) in detections.iter().enumerate() {
+            if *matched {
+                true_positives += 1;
+            }
+            recalls.push(true_positives as f64 / self.num_targets as f64);
+            precisions.push(true_positives as f64 / (i + 1) as f64);
+        }
+
+        // The precision at a recall is the best precision at any higher recall.
+        for i in (1..precisions.len()).rev() {
+            precisions[i - 1] = precisions[i - 1].max(precisions[i]);
+        }
+
+        let sum = (0..=100)
+            .map(|point| {
+                let recall = point as f64 / 100.0;
+                let index = recalls.partition_point(|r| *r < recall);
+                precisions.get(index).copied().unwrap_or(0.0)
+            })
+            .sum::<f64>();
+
+        sum / 101.0
+    }
+}
+
+/// The COCO mean average precision (mAP) of object detections, averaged over the IoU thresholds
+/// from 0.5 to 0.95 with a step of 0.05 (mAP@[.5:.95]) or at a single IoU threshold, e.g. mAP@.5.
+///
+/// Each detection is matched, from the highest score to the lowest, to the unmatched target of
+/// the same class with the highest IoU above the threshold. The matches are accumulated over the
+/// epoch, so the value is the mAP of all the images of the epoch rather than the mean of the mAP
+/// of each batch. The average precision of each class is interpolated on 101 recall points, and
+/// the classes without targets are skipped from the mean.
+#[derive(Clone)]
+pub struct MeanAveragePrecisionMetric<B: Backend> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    thresholds: Vec<f32>,
+    max_detections: usize,
+    classes: BTreeMap<i64, ClassDetections>,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> Default for MeanAveragePrecisionMetric<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> MeanAveragePrecisionMetric<B> {
+    /// Creates the mAP@[.5:.95] metric, keeping the 100 detections with the highest scores of
+    /// each image.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("mAP@[.5:.95]".to_string()),
+            state: Default::default(),
+            thresholds: (0..10).map(|i| 0.5 + 0.05 * i as f32).collect(),
+            max_detections: 100,
+            classes: BTreeMap::new(),
+            _b: PhantomData,
+        }
+    }
+
+    /// Reports the mAP at the given IoU threshold only, e.g. 0.5 for mAP@.5.
+    pub fn with_iou_threshold(mut self, threshold: f32) -> Self {
+        self.name = MetricName::new(format!("mAP@{threshold}"));
+        self.thresholds = vec![threshold];
+        self
+    }
+
+    /// Sets the maximum number of detections of each image, keeping the highest scores.
+    pub fn with_max_detections(mut self, max_detections: usize) -> Self {
+        self.max_detections = max_detections;
+        self
+    }
+
+    fn update_image(&mut self, mut detections: Vec<Detection>, targets: Vec<Target>) {
+        detections.sort_by(|a, b| b.score.total_cmp(&a.score));
+        detections.truncate(self.max_detections);
+
+        for target in targets.iter() {
+            self.classes.entry(target.label).or_default().num_targets += 1;
+        }
+
+        let mut matched = vec![vec![false; targets.len()]; self.thresholds.len()];
+        for detection in detections {
+            let ious = targets
+                .iter()
+                .map(|target| match target.label == detection.label {
+                    true => box_iou(&detection.bbox, &target.bbox),
+                    false => -1.0,
+                })
+                .collect::<Vec<_>>();
+
+            let matches = self
+                .thresholds
+                .iter()
+                .zip(matched.iter_mut())
+                .map(|(threshold, matched)| {
+                    let best = ious
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, iou)| !matched[*i] && **iou >= *threshold)
+                        .max_by(|a, b| a.1.total_cmp(b.1));
+
+                    match best {
+                        Some((i, _)) => {
+                            matched[i] = true;
+                            true
+                        }
+                        None => false,
+                    }
+                })
+                .collect();
+
+            self.classes
+                .entry(detection.label)
+                .or_default()
+                .detections
+                .push((detection.score, matches));
+        }
+    }
+
+    /// The mAP accumulated since the start of the epoch.
+    fn mean_average_precision(&self) -> f64 {
+        let classes = self
+            .classes
+            .values()
+            .filter(|class| class.num_targets > 0)
+            .collect::<Vec<_>>();
+        if classes.is_empty() {
+            return 0.0;
+        }
+
+        let sum = (0..self.thresholds.len())
+            .map(|threshold| {
+                classes
+                    .iter()
+                    .map(|class| class.average_precision(threshold))
+                    .sum::<f64>()
+                    / classes.len() as f64
+            })
+            .sum::<f64>();
+
+        sum / self.thresholds.len() as f64
+    }
+}
+
+impl<B: Backend> Metric for MeanAveragePrecisionMetric<B> {
+    type Input = DetectionInput<B>;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        for image in item.images.iter() {
+            let (detections, targets) = image.read();
+            self.update_image(detections, targets);
+        }
+
+        self.state.update(
+            100.0 * self.mean_average_precision(),
+            FormatOptions::new(self.name()).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.classes.clear();
+    }
+}
+
+impl<B: Backend> Numeric for MeanAveragePrecisionMetric<B> {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn image(
+        boxes: Vec<[f32; 4]>,
+        scores: Vec<f32>,
+        labels: Vec<i64>,
+        targets: Vec<([f32; 4], i64)>,
+    ) -> ImageDetections<TestBackend> {
+        let device = Default::default();
+        let num_detections = boxes.len();
+        let num_targets = targets.len();
+        let (target_boxes, target_labels): (Vec<_>, Vec<_>) = targets.into_iter().unzip();
+
+        ImageDetections::new(
+            Tensor::<TestBackend, 1>::from_floats(boxes.concat().as_slice(), &device)
+                .reshape([num_detections, 4]),
+            Tensor::from_floats(scores.as_slice(), &device),
+            Tensor::from_ints(labels.as_slice(), &device),
+            Tensor::<TestBackend, 1>::from_floats(target_boxes.concat().as_slice(), &device)
+                .reshape([num_targets, 4]),
+            Tensor::from_ints(target_labels.as_slice(), &device),
+        )
+    }
+
+    #[test]
+    fn test_box_iou() {
+        assert_eq!(
+            box_iou(&[0.0, 0.0, 2.0, 2.0], &[1.0, 0.0, 3.0, 2.0]),
+            2.0 / 6.0
+        );
+        assert_eq!(box_iou(&[0.0, 0.0, 1.0, 1.0], &[2.0, 2.0, 3.0, 3.0]), 0.0);
+    }
+
+    #[test]
+    fn test_map_perfect_detections() {
+        let mut metric = MeanAveragePrecisionMetric::<TestBackend>::new();
+        let input = DetectionInput::new(vec![image(
+            vec![[0.0, 0.0, 10.0, 10.0], [20.0, 20.0, 30.0, 30.0]],
+            vec![0.9, 0.8],
+            vec![0, 1],
+            vec![([0.0, 0.0, 10.0, 10.0], 0), ([20.0, 20.0, 30.0, 30.0], 1)],
+        )]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert!((metric.value().current() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_map_iou_thresholds() {
+        // The detection has an IoU of 0.57 with the target, so it's only matched at 0.5 and 0.55.
+        let input = DetectionInput::new(vec![image(
+            vec![[0.0, 0.0, 10.0, 5.7]],
+            vec![0.9],
+            vec![0],
+            vec![([0.0, 0.0, 10.0, 10.0], 0)],
+        )]);
+
+        let mut metric = MeanAveragePrecisionMetric::<TestBackend>::new().with_iou_threshold(0.5);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.name().as_str(), "mAP@0.5");
+        assert!((metric.value().current() - 100.0).abs() < 1e-9);
+
+        let mut metric = MeanAveragePrecisionMetric::<TestBackend>::new();
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_map_is_accumulated_over_batches() {
+        let mut metric = MeanAveragePrecisionMetric::<TestBackend>::new().with_iou_threshold(0.5);
+
+        // A false positive with a high score, then a true positive on another batch.
+        let _entry = metric.update(
+            &DetectionInput::new(vec![image(
+                vec![[50.0, 50.0, 60.0, 60.0]],
+                vec![0.9],
+                vec![0],
+                vec![([0.0, 0.0, 10.0, 10.0], 0)],
+            )]),
+            &MetricMetadata::fake(),
+        );
+        assert_eq!(metric.value().current(), 0.0);
+
+        let _entry = metric.update(
+            &DetectionInput::new(vec![image(
+                vec![[0.0, 0.0, 10.0, 10.0]],
+                vec![0.8],
+                vec![0],
+                vec![([0.0, 0.0, 10.0, 10.0], 0)],
+            )]),
+            &MetricMetadata::fake(),
+        );
+
+        // The precision is 0.5 at the recall of 0.5, which is the highest recall, so the 51
+        // recall points up to 0.5 have a precision of 0.5 and the others a precision of 0.
+        let expected = 100.0 * 0.5 * 51.0 / 101.0;
+        assert!((metric.value().current() - expected).abs() < 1e-9);
+    }
+}
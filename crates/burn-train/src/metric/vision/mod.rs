@@ -1,3 +1,9 @@
+mod boundary;
+mod detection;
 mod dice;
+mod segmentation;
 
+pub use boundary::*;
+pub use detection::*;
 pub use dice::*;
+pub use segmentation::*;
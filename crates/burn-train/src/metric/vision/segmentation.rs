@@ -0,0 +1,399 @@
+use crate::metric::MetricName;
+
+use super::super::{
+    Metric, MetricEntry, MetricMetadata, Numeric, NumericEntry,
+    state::{AccumulatedMetricState, FormatOptions, NumericMetricState},
+};
+use burn_core::{
+    prelude::{Backend, Tensor},
+    tensor::{ElementConversion, Int},
+};
+use core::marker::PhantomData;
+
+/// Input type for the semantic segmentation metrics, e.g. [IouMetric].
+///
+/// # Type Parameters
+/// - `B`: Backend type.
+/// - `D`: Number of dimensions, including the batch dimension (default 3).
+pub struct SegmentationInput<B: Backend, const D: usize = 3> {
+    /// The predicted class of each pixel.
+    outputs: Tensor<B, D, Int>,
+    /// The target class of each pixel.
+    targets: Tensor<B, D, Int>,
+}
+
+impl<B: Backend, const D: usize> SegmentationInput<B, D> {
+    /// Creates a new segmentation input with the given outputs and targets.
+    ///
+    /// Inputs are expected to have the dimensions `[B, ...]`, e.g. `[B, H, W]` for images, with
+    /// the class index of each pixel. Use `argmax` on the class dimension of the model outputs
+    /// to get the predicted classes.
+    ///
+    /// # Panics
+    /// - If `D` is less than 2.
+    /// - If `outputs` and `targets` do not have the same shape.
+    pub fn new(outputs: Tensor<B, D, Int>, targets: Tensor<B, D, Int>) -> Self {
+        assert!(D >= 2, "SegmentationInput requires at least 2 dimensions.");
+        assert!(
+            outputs.dims() == targets.dims(),
+            "Outputs and targets must have the same dimensions. Got {:?} and {:?}",
+            outputs.dims(),
+            targets.dims()
+        );
+        Self { outputs, targets }
+    }
+
+    /// The predicted classes.
+    pub(crate) fn outputs(&self) -> &Tensor<B, D, Int> {
+        &self.outputs
+    }
+
+    /// The target classes.
+    pub(crate) fn targets(&self) -> &Tensor<B, D, Int> {
+        &self.targets
+    }
+
+    /// The predicted and target classes of the pixels, read from the device.
+    pub(crate) fn classes(&self) -> (Vec<i64>, Vec<i64>) {
+        let outputs = self.outputs.to_data().iter::<i64>().collect();
+        let targets = self.targets.to_data().iter::<i64>().collect();
+        (outputs, targets)
+    }
+}
+
+/// The number of pixels of each target class predicted as each class, accumulated over the
+/// batches.
+#[derive(Clone, Debug)]
+struct SegmentationConfusionMatrix {
+    num_classes: usize,
+    ignore_index: Option<usize>,
+    /// The counts indexed by `target * num_classes + prediction`.
+    counts: Vec<u64>,
+}
+
+impl SegmentationConfusionMatrix {
+    fn new(num_classes: usize, ignore_index: Option<usize>) -> Self {
+        Self {
+            num_classes,
+            ignore_index,
+            counts: vec![0; num_classes * num_classes],
+        }
+    }
+
+    fn reset(&mut self) {
+        self.counts.fill(0);
+    }
+
+    /// Count the pixels, ignoring the targets equal to the ignore index.
+    ///
+    /// # Panics
+    /// If a class is outside of `[0, num_classes)`.
+    fn update(&mut self, outputs: &[i64], targets: &[i64]) {
+        for (&output, &target) in outputs.iter().zip(targets) {
+            if self
+                .ignore_index
+                .is_some_and(|index| index as i64 == target)
+            {
+                continue;
+            }
+            let output = self.class(output);
+            let target = self.class(target);
+            self.counts[target * self.num_classes + output] += 1;
+        }
+    }
+
+    fn class(&self, class: i64) -> usize {
+        assert!(
+            class >= 0 && (class as usize) < self.num_classes,
+            "Class {class} is out of range for {} classes.",
+            self.num_classes
+        );
+        class as usize
+    }
+
+    /// The intersection over union of the class, which is undefined when the class is neither
+    /// predicted nor in the targets.
+    fn iou(&self, class: usize) -> Option<f64> {
+        let intersection = self.counts[class * self.num_classes + class];
+        let targets = self.counts[class * self.num_classes..(class + 1) * self.num_classes]
+            .iter()
+            .sum::<u64>();
+        let predictions = (0..self.num_classes)
+            .map(|target| self.counts[target * self.num_classes + class])
+            .sum::<u64>();
+        let union = targets + predictions - intersection;
+
+        (union > 0).then(|| intersection as f64 / union as f64)
+    }
+
+    /// The mean of the intersection over union of the classes that are defined, skipping the
+    /// ignored class.
+    fn mean_iou(&self) -> f64 {
+        let ious = (0..self.num_classes)
+            .filter(|class| self.ignore_index != Some(*class))
+            .filter_map(|class| self.iou(class))
+            .collect::<Vec<_>>();
+
+        match ious.is_empty() {
+            true => 0.0,
+            false => ious.iter().sum::<f64>() / ious.len() as f64,
+        }
+    }
+}
+
+/// The intersection over union (IoU), or Jaccard index, of semantic segmentation masks, either of
+/// a single class or averaged over the classes (mIoU).
+///
+/// The IoU is computed from a confusion matrix accumulated over the epoch, so its value is the
+/// IoU of all the pixels of the epoch rather than the mean of the IoU of each batch. The classes
+/// that are neither predicted nor in the targets are skipped from the mean.
+///
+/// # Type Parameters
+/// - `B`: Backend type.
+/// - `D`: Number of dimensions of the [input](SegmentationInput) (default 3).
+#[derive(Clone)]
+pub struct IouMetric<B: Backend, const D: usize = 3> {
+    name: MetricName,
+    state: AccumulatedMetricState,
+    confusion: SegmentationConfusionMatrix,
+    class: Option<usize>,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend, const D: usize> IouMetric<B, D> {
+    /// Creates the mean IoU metric over the given number of classes.
+    pub fn new(num_classes: usize) -> Self {
+        Self {
+            name: MetricName::new("Mean IoU".to_string()),
+            state: Default::default(),
+            confusion: SegmentationConfusionMatrix::new(num_classes, None),
+            class: None,
+            _b: PhantomData,
+        }
+    }
+
+    /// Ignores the pixels whose target is the given class, e.g. the unlabeled pixels.
+    pub fn with_ignore_index(mut self, index: usize) -> Self {
+        self.confusion.ignore_index = Some(index);
+        self
+    }
+
+    /// Reports the IoU of the given class instead of the mean IoU.
+    pub fn with_class(mut self, class: usize) -> Self {
+        assert!(
+            class < self.confusion.num_classes,
+            "Class {class} is out of range for {} classes.",
+            self.confusion.num_classes
+        );
+        self.name = MetricName::new(format!("IoU (class {class})"));
+        self.class = Some(class);
+        self
+    }
+
+    /// The IoU of each class accumulated since the start of the epoch, which is undefined for
+    /// the classes that are neither predicted nor in the targets.
+    pub fn iou_per_class(&self) -> Vec<Option<f64>> {
+        (0..self.confusion.num_classes)
+            .map(|class| self.confusion.iou(class))
+            .collect()
+    }
+}
+
+impl<B: Backend, const D: usize> Metric for IouMetric<B, D> {
+    type Input = SegmentationInput<B, D>;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        let (outputs, targets) = item.classes();
+        self.confusion.update(&outputs, &targets);
+
+        let iou = match self.class {
+            Some(class) => self.confusion.iou(class).unwrap_or(0.0),
+            None => self.confusion.mean_iou(),
+        };
+
+        self.state.update(
+            100.0 * iou,
+            FormatOptions::new(self.name()).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.confusion.reset();
+    }
+}
+
+impl<B: Backend, const D: usize> Numeric for IouMetric<B, D> {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+/// The pixel accuracy of semantic segmentation masks, i.e. the fraction of the pixels whose class
+/// is correctly predicted.
+///
+/// # Type Parameters
+/// - `B`: Backend type.
+/// - `D`: Number of dimensions of the [input](SegmentationInput) (default 3).
+#[derive(Clone)]
+pub struct PixelAccuracyMetric<B: Backend, const D: usize = 3> {
+    name: MetricName,
+    state: NumericMetricState,
+    ignore_index: Option<usize>,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend, const D: usize> Default for PixelAccuracyMetric<B, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend, const D: usize> PixelAccuracyMetric<B, D> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            name: MetricName::new("Pixel Accuracy".to_string()),
+            state: Default::default(),
+            ignore_index: None,
+            _b: PhantomData,
+        }
+    }
+
+    /// Ignores the pixels whose target is the given class, e.g. the unlabeled pixels.
+    pub fn with_ignore_index(mut self, index: usize) -> Self {
+        self.ignore_index = Some(index);
+        self
+    }
+}
+
+impl<B: Backend, const D: usize> Metric for PixelAccuracyMetric<B, D> {
+    type Input = SegmentationInput<B, D>;
+
+    fn name(&self) -> MetricName {
+        self.name.clone()
+    }
+
+    fn update(&mut self, item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        let outputs = item.outputs().clone();
+        let targets = item.targets().clone();
+
+        let (correct, total) = match self.ignore_index {
+            Some(index) => {
+                let mask = targets.clone().not_equal_elem(index as i64);
+                let total = mask.clone().int().sum().into_scalar().elem::<i64>();
+                (outputs.equal(targets).bool_and(mask), total as usize)
+            }
+            None => {
+                let total = targets.shape().num_elements();
+                (outputs.equal(targets), total)
+            }
+        };
+        let correct = correct.int().sum().into_scalar().elem::<i64>() as usize;
+
+        // The accuracy of the batch is weighted by its number of pixels, so the mean of the epoch
+        // is the accuracy of all its pixels.
+        let accuracy = match total {
+            0 => 0.0,
+            _ => correct as f64 / total as f64,
+        };
+
+        self.state.update(
+            100.0 * accuracy,
+            total.max(1),
+            FormatOptions::new(self.name()).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+    }
+}
+
+impl<B: Backend, const D: usize> Numeric for PixelAccuracyMetric<B, D> {
+    fn value(&self) -> NumericEntry {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn input(
+        outputs: [[[i64; 3]; 2]; 1],
+        targets: [[[i64; 3]; 2]; 1],
+    ) -> SegmentationInput<TestBackend> {
+        let device = Default::default();
+        SegmentationInput::new(
+            Tensor::from_data(outputs, &device),
+            Tensor::from_data(targets, &device),
+        )
+    }
+
+    #[test]
+    fn test_mean_iou() {
+        let mut metric = IouMetric::<TestBackend>::new(3);
+        let input = input([[[0, 0, 1], [1, 2, 2]]], [[[0, 1, 1], [1, 2, 0]]]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // IoU of class 0: 1 / 3, class 1: 2 / 3, class 2: 1 / 2.
+        let ious = metric.iou_per_class();
+        assert_eq!(ious, vec![Some(1.0 / 3.0), Some(2.0 / 3.0), Some(0.5)]);
+        let expected = 100.0 * (1.0 / 3.0 + 2.0 / 3.0 + 0.5) / 3.0;
+        assert!((metric.value().current() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_iou_is_accumulated_over_batches() {
+        let mut metric = IouMetric::<TestBackend>::new(2).with_class(1);
+        let _entry = metric.update(
+            &input([[[1, 1, 1], [1, 1, 1]]], [[[1, 1, 1], [1, 1, 1]]]),
+            &MetricMetadata::fake(),
+        );
+        assert_eq!(metric.value().current(), 100.0);
+
+        let entry = metric.update(
+            &input([[[1, 1, 0], [0, 0, 0]]], [[[0, 0, 0], [0, 0, 0]]]),
+            &MetricMetadata::fake(),
+        );
+        // 6 pixels in the intersection and 8 in the union.
+        assert_eq!(metric.value().current(), 75.0);
+        // The logged entry is the accumulated value, not its difference with the previous one.
+        assert_eq!(entry.serialize, "75,cumulative");
+    }
+
+    #[test]
+    fn test_iou_ignore_index() {
+        let mut metric = IouMetric::<TestBackend>::new(3).with_ignore_index(2);
+        let input = input([[[0, 1, 1], [1, 0, 0]]], [[[0, 1, 1], [1, 2, 2]]]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert_eq!(metric.value().current(), 100.0);
+    }
+
+    #[test]
+    fn test_pixel_accuracy() {
+        let mut metric = PixelAccuracyMetric::<TestBackend>::new();
+        let input = input([[[0, 0, 1], [1, 2, 2]]], [[[0, 1, 1], [1, 2, 0]]]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 100.0 * 4.0 / 6.0).abs() < 1e-9);
+
+        let mut metric = PixelAccuracyMetric::<TestBackend>::new().with_ignore_index(0);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value().current() - 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Class 3 is out of range for 3 classes.")]
+    fn test_iou_class_out_of_range() {
+        let mut metric = IouMetric::<TestBackend>::new(3);
+        let input = input([[[0, 0, 3], [1, 2, 2]]], [[[0, 1, 1], [1, 2, 0]]]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+    }
+}
@@ -159,6 +159,12 @@ impl FullHistoryPoints {
                 self.avg_counter = count as f64;
                 current
             }
+            NumericEntry::Cumulative(val) => {
+                // The last value is already computed from all the items so far.
+                self.avg_sum = val;
+                self.avg_counter = 1.0;
+                val
+            }
         };
 
         if x > self.max_x {
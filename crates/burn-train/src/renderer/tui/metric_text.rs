@@ -96,7 +96,19 @@ impl TextMetricView {
 
             for (name, group) in entry.groups.iter() {
                 for (split, entry) in group.splits.iter() {
-                    lines.push(format_line(name, split, &entry.formatted));
+                    match entry.formatted.contains('\n') {
+                        false => lines.push(format_line(name, split, &entry.formatted)),
+                        true => {
+                            // Multi-line entries, e.g. tables, are displayed below their split.
+                            lines.push(format_line(name, split, ""));
+                            lines.extend(
+                                entry
+                                    .formatted
+                                    .lines()
+                                    .map(|line| vec![Span::from(format!(" {line}"))]),
+                            );
+                        }
+                    }
                 }
             }
 
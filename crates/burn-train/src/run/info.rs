@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use burn_core::config::Config;
+use burn_core::tensor::backend::Backend;
+use serde::{Deserialize, Serialize};
+
+/// The name of the file describing a run in its directory.
+pub const RUN_INFO_FILE: &str = "run.json";
+
+/// The description of a training run, saved to the `run.json` file of its directory.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunInfo {
+    /// The serialized configs of the run, by name.
+    pub configs: BTreeMap<String, serde_json::Value>,
+    /// The hash of the current commit of the git repository the run was started from.
+    pub git_hash: Option<String>,
+    /// The name of the backend.
+    pub backend: Option<String>,
+    /// The devices the run is trained on.
+    pub devices: Vec<String>,
+    /// The number of seconds since the Unix epoch when the run started.
+    pub start_time: Option<u64>,
+    /// The number of seconds since the Unix epoch when the run ended.
+    pub end_time: Option<u64>,
+    /// The name of the metrics file in the directory of the run, if any.
+    pub metrics_file: Option<String>,
+}
+
+impl RunInfo {
+    /// Create a new run description with the git hash of the working directory.
+    pub fn new() -> Self {
+        Self {
+            git_hash: git_hash(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a config to the run with the given name, e.g. `training` or `model`.
+    pub fn with_config<C: Config>(mut self, name: &str, config: &C) -> Self {
+        match serde_json::to_value(config) {
+            Ok(value) => {
+                self.configs.insert(name.to_string(), value);
+            }
+            Err(err) => log::warn!("Failed to serialize the config {name}: {err}"),
+        }
+        self
+    }
+
+    /// Set the backend and devices of the run.
+    pub fn with_backend<B: Backend>(mut self, devices: &[B::Device]) -> Self {
+        self.backend = devices.first().map(|device| B::name(device));
+        self.devices = devices.iter().map(|device| format!("{device:?}")).collect();
+        self
+    }
+
+    /// The duration of the run in seconds, if it has ended.
+    pub fn duration(&self) -> Option<u64> {
+        Some(self.end_time?.saturating_sub(self.start_time?))
+    }
+
+    /// Save the run description to the `run.json` file of the directory.
+    pub fn save(&self, directory: impl AsRef<Path>) -> std::io::Result<()> {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(directory.join(RUN_INFO_FILE), content)
+    }
+
+    /// Load the run description from the `run.json` file of the directory.
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self, String> {
+        let path = directory.as_ref().join(RUN_INFO_FILE);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|err| format!("Can't read {}: {err}", path.display()))?;
+
+        serde_json::from_str(&content).map_err(|err| format!("Invalid {}: {err}", path.display()))
+    }
+}
+
+fn git_hash() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8(output.stdout).ok()?;
+    Some(hash.trim().to_string())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Records the start and end of a run trained by the learner.
+pub(crate) struct LearnerRun {
+    directory: PathBuf,
+    info: RunInfo,
+}
+
+impl LearnerRun {
+    pub(crate) fn new(directory: PathBuf, info: RunInfo) -> Self {
+        Self { directory, info }
+    }
+
+    pub(crate) fn start(&mut self) {
+        // The start of a resumed run is kept.
+        let start_time = RunInfo::load(&self.directory)
+            .ok()
+            .and_then(|info| info.start_time);
+        self.info.start_time = Some(start_time.unwrap_or_else(now));
+        self.info.end_time = None;
+        self.save();
+    }
+
+    pub(crate) fn end(mut self) {
+        self.info.end_time = Some(now());
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(err) = self.info.save(&self.directory) {
+            log::warn!("Failed to save the run description: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core as burn;
+
+    #[derive(Config)]
+    struct TestConfig {
+        learning_rate: f64,
+        #[config(default = 32)]
+        batch_size: usize,
+    }
+
+    #[test]
+    fn test_save_and_load_run_info() {
+        let directory = tempfile::tempdir().unwrap();
+        let info = RunInfo::new()
+            .with_config("training", &TestConfig::new(0.01))
+            .with_backend::<TestBackend>(&[Default::default()]);
+        info.save(directory.path()).unwrap();
+
+        let loaded = RunInfo::load(directory.path()).unwrap();
+        assert_eq!(loaded, info);
+        assert_eq!(
+            loaded.configs["training"],
+            serde_json::json!({ "learning_rate": 0.01, "batch_size": 32 })
+        );
+        assert_eq!(loaded.backend.as_deref(), Some("ndarray"));
+        assert_eq!(loaded.devices.len(), 1);
+    }
+
+    #[test]
+    fn test_learner_run_records_times() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut run = LearnerRun::new(directory.path().to_path_buf(), RunInfo::default());
+
+        run.start();
+        let info = RunInfo::load(directory.path()).unwrap();
+        assert!(info.start_time.is_some());
+        assert!(info.end_time.is_none());
+
+        run.end();
+        let info = RunInfo::load(directory.path()).unwrap();
+        assert!(info.duration().is_some());
+    }
+}
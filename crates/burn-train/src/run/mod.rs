@@ -0,0 +1,7 @@
+mod info;
+mod registry;
+mod store;
+
+pub use info::*;
+pub use registry::*;
+pub use store::*;
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::{MetricRecord, RUN_INFO_FILE, RunInfo, read_metric_records, split_name};
+use crate::metric::store::{Direction, Split};
+
+/// Lists the runs saved in the subdirectories of a directory, e.g. one run per directory of a
+/// hyperparameter sweep.
+pub struct RunRegistry {
+    directory: PathBuf,
+}
+
+impl RunRegistry {
+    /// Create a new registry of the runs in the given directory.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The runs of the directory, i.e. the subdirectories with a `run.json` file, sorted by start
+    /// time.
+    pub fn runs(&self) -> Result<Vec<Run>, String> {
+        let entries = std::fs::read_dir(&self.directory)
+            .map_err(|err| format!("Can't read {}: {err}", self.directory.display()))?;
+
+        let mut runs = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|err| err.to_string())?.path();
+            if path.join(RUN_INFO_FILE).is_file() {
+                runs.push(Run::load(path)?);
+            }
+        }
+        runs.sort_by(|a, b| {
+            (a.info.start_time, &a.directory).cmp(&(b.info.start_time, &b.directory))
+        });
+
+        Ok(runs)
+    }
+
+    /// Compare the runs of the directory on the mean of a metric for each epoch, with the best
+    /// runs first.
+    ///
+    /// Runs without values for the metric are last.
+    pub fn compare(
+        &self,
+        metric: &str,
+        split: Split,
+        direction: Direction,
+    ) -> Result<Vec<RunComparison>, String> {
+        let mut comparisons = self
+            .runs()?
+            .into_iter()
+            .map(|run| {
+                let epochs = run.epoch_means(metric, split)?;
+                let best = epochs.iter().copied().reduce(|best, current| {
+                    let better = match direction {
+                        Direction::Lowest => current.1 < best.1,
+                        Direction::Highest => current.1 > best.1,
+                    };
+                    if better { current } else { best }
+                });
+
+                Ok(RunComparison {
+                    name: run.name(),
+                    best,
+                    last: epochs.last().copied(),
+                    run,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        comparisons.sort_by(|a, b| match (a.best, b.best) {
+            (Some((_, a)), Some((_, b))) => match direction {
+                Direction::Lowest => a.total_cmp(&b),
+                Direction::Highest => b.total_cmp(&a),
+            },
+            (Some(_), None) => core::cmp::Ordering::Less,
+            (None, Some(_)) => core::cmp::Ordering::Greater,
+            (None, None) => core::cmp::Ordering::Equal,
+        });
+
+        Ok(comparisons)
+    }
+}
+
+/// A run saved in a directory.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// The directory of the run.
+    pub directory: PathBuf,
+    /// The description of the run.
+    pub info: RunInfo,
+}
+
+impl Run {
+    /// Load the run saved in the given directory.
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self, String> {
+        let directory = directory.as_ref().to_path_buf();
+        let info = RunInfo::load(&directory)?;
+
+        Ok(Self { directory, info })
+    }
+
+    /// The name of the run, i.e. the name of its directory.
+    pub fn name(&self) -> String {
+        self.directory
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// The numeric metric values recorded during the run, empty if it has no metrics file.
+    pub fn records(&self) -> Result<Vec<MetricRecord>, String> {
+        match &self.info.metrics_file {
+            Some(file) => read_metric_records(self.directory.join(file)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The mean of the values of a metric for each epoch of the split, weighted by the number of
+    /// items of each value.
+    pub fn epoch_means(&self, metric: &str, split: Split) -> Result<Vec<(usize, f64)>, String> {
+        let split = split_name(split);
+        let mut epochs = BTreeMap::<usize, (f64, usize)>::new();
+
+        for record in self.records()? {
+            if record.name != metric || record.split != split || !record.tags.is_empty() {
+                continue;
+            }
+            let (sum, count) = epochs.entry(record.epoch).or_default();
+            *sum += record.value * record.count as f64;
+            *count += record.count;
+        }
+
+        Ok(epochs
+            .into_iter()
+            .filter(|(_, (_, count))| *count > 0)
+            .map(|(epoch, (sum, count))| (epoch, sum / count as f64))
+            .collect())
+    }
+}
+
+/// The values of a metric for a run, compared by the [registry](RunRegistry::compare).
+#[derive(Clone, Debug)]
+pub struct RunComparison {
+    /// The name of the run.
+    pub name: String,
+    /// The epoch and the mean of the metric of the best epoch.
+    pub best: Option<(usize, f64)>,
+    /// The epoch and the mean of the metric of the last epoch.
+    pub last: Option<(usize, f64)>,
+    /// The compared run.
+    pub run: Run,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::MetricLogger;
+    use crate::metric::MetricEntry;
+    use crate::run::{RunStore, RunStoreFormat};
+    use std::sync::Arc;
+
+    fn save_run(directory: &Path, name: &str, start_time: u64, losses: &[&[&str]]) {
+        let directory = directory.join(name);
+        let store = RunStore::new(&directory, RunStoreFormat::Jsonl);
+        let mut logger = store.logger_valid();
+
+        for (epoch, losses) in losses.iter().enumerate() {
+            for loss in losses.iter() {
+                logger.log(&MetricEntry::new(
+                    Arc::new("Loss".to_string()),
+                    loss.to_string(),
+                    loss.to_string(),
+                ));
+            }
+            logger.end_epoch(epoch + 1);
+        }
+
+        let info = RunInfo {
+            start_time: Some(start_time),
+            metrics_file: Some(RunStoreFormat::Jsonl.file_name().to_string()),
+            ..Default::default()
+        };
+        info.save(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_list_and_compare_runs() {
+        let directory = tempfile::tempdir().unwrap();
+        save_run(directory.path(), "a", 2, &[&["2.0,1", "1.0,3"], &["1.5,1"]]);
+        save_run(directory.path(), "b", 1, &[&["0.5,1"], &["1.0,1"]]);
+        save_run(directory.path(), "c", 3, &[]);
+        // Directories without a run description are ignored.
+        std::fs::create_dir(directory.path().join("other")).unwrap();
+
+        let registry = RunRegistry::new(directory.path());
+        let runs = registry.runs().unwrap();
+        let names = runs.iter().map(Run::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["b", "a", "c"]);
+
+        // The mean of the first epoch is weighted by the number of items.
+        assert_eq!(
+            runs[1].epoch_means("Loss", Split::Valid).unwrap(),
+            vec![(1, 1.25), (2, 1.5)]
+        );
+        assert!(
+            runs[1]
+                .epoch_means("Loss", Split::Train)
+                .unwrap()
+                .is_empty()
+        );
+
+        let comparisons = registry
+            .compare("Loss", Split::Valid, Direction::Lowest)
+            .unwrap();
+        let results = comparisons
+            .iter()
+            .map(|comparison| (comparison.name.as_str(), comparison.best, comparison.last))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                ("b", Some((1, 0.5)), Some((2, 1.0))),
+                ("a", Some((1, 1.25)), Some((2, 1.5))),
+                ("c", None, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::logger::MetricLogger;
+use crate::metric::store::Split;
+use crate::metric::{MetricEntry, NumericEntry};
+
+/// The format of the metrics file of a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunStoreFormat {
+    /// One JSON object per line.
+    Jsonl,
+    /// Comma-separated values with a header.
+    Csv,
+}
+
+impl RunStoreFormat {
+    /// The name of the metrics file in the directory of the run.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            RunStoreFormat::Jsonl => "metrics.jsonl",
+            RunStoreFormat::Csv => "metrics.csv",
+        }
+    }
+
+    /// The format of the given metrics file, from its extension.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "jsonl" => Some(RunStoreFormat::Jsonl),
+            "csv" => Some(RunStoreFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "epoch,iteration,split,name,tags,value,count,timestamp";
+
+/// A numeric metric value recorded during a run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricRecord {
+    /// The epoch of the value, starting at 1.
+    pub epoch: usize,
+    /// The number of values of the metric recorded in the epoch, starting at 1.
+    pub iteration: usize,
+    /// The split of the value, i.e. `train`, `valid` or `test`.
+    pub split: String,
+    /// The name of the metric.
+    pub name: String,
+    /// The tags of the metric, e.g. the name of a validation dataset.
+    pub tags: Vec<String>,
+    /// The value of the metric for the iteration.
+    ///
+    /// JSON has no representation of the non-finite numbers, so they're serialized as the strings
+    /// `NaN`, `inf` and `-inf` in the JSONL format.
+    #[serde(with = "non_finite")]
+    pub value: f64,
+    /// The number of items the value is computed on, or 0 when the value is computed on all the
+    /// items of the epoch so far, in which case the last value of the epoch is the value of the
+    /// epoch.
+    pub count: usize,
+    /// The number of seconds since the Unix epoch when the value was recorded.
+    pub timestamp: f64,
+}
+
+impl MetricRecord {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            self.epoch,
+            self.iteration,
+            csv_field(&self.split),
+            csv_field(&self.name),
+            csv_field(&self.tags.join("|")),
+            self.value,
+            self.count,
+            self.timestamp
+        )
+    }
+
+    fn from_csv(line: &str) -> Result<Self, String> {
+        let fields = parse_csv_line(line);
+        let [epoch, iteration, split, name, tags, value, count, timestamp] =
+            <[String; 8]>::try_from(fields)
+                .map_err(|fields| format!("Expected 8 fields, got {}", fields.len()))?;
+        let parse_err = |field: &str| format!("Invalid {field} in record: {line}");
+
+        Ok(Self {
+            epoch: epoch.parse().map_err(|_| parse_err("epoch"))?,
+            iteration: iteration.parse().map_err(|_| parse_err("iteration"))?,
+            split,
+            name,
+            tags: match tags.is_empty() {
+                true => Vec::new(),
+                false => tags.split('|').map(ToString::to_string).collect(),
+            },
+            value: value.parse().map_err(|_| parse_err("value"))?,
+            count: count.parse().map_err(|_| parse_err("count"))?,
+            timestamp: timestamp.parse().map_err(|_| parse_err("timestamp"))?,
+        })
+    }
+}
+
+/// Serializes the non-finite floats as strings, since JSON would turn them into `null`.
+mod non_finite {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Float {
+        Number(f64),
+        Text(String),
+    }
+
+    pub(super) fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        match value.is_finite() {
+            true => serializer.serialize_f64(*value),
+            false => serializer.serialize_str(&value.to_string()),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        match Float::deserialize(deserializer)? {
+            Float::Number(value) => Ok(value),
+            Float::Text(text) => text
+                .parse()
+                .map_err(|_| D::Error::custom(format!("Invalid float: {text}"))),
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(core::mem::take(&mut field)),
+            (c, _) => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Read the metric records of a metrics file written by a [run store](RunStore).
+pub fn read_metric_records(path: impl AsRef<Path>) -> Result<Vec<MetricRecord>, String> {
+    let path = path.as_ref();
+    let format = RunStoreFormat::from_path(path)
+        .ok_or_else(|| format!("Unknown metrics file format: {}", path.display()))?;
+    let file = File::open(path).map_err(|err| format!("Can't open {}: {err}", path.display()))?;
+
+    let mut records = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|err| err.to_string())?;
+        if line.is_empty() || (format == RunStoreFormat::Csv && index == 0) {
+            continue;
+        }
+
+        let record = match format {
+            RunStoreFormat::Jsonl => serde_json::from_str(&line).map_err(|err| err.to_string()),
+            RunStoreFormat::Csv => MetricRecord::from_csv(&line),
+        };
+        records.push(record?);
+    }
+
+    Ok(records)
+}
+
+struct RunStoreWriter {
+    file: BufWriter<File>,
+    path: PathBuf,
+    format: RunStoreFormat,
+}
+
+impl RunStoreWriter {
+    fn write(&mut self, record: &MetricRecord) {
+        let line = match self.format {
+            RunStoreFormat::Jsonl => serde_json::to_string(record).unwrap(),
+            RunStoreFormat::Csv => record.to_csv(),
+        };
+        if let Err(err) = writeln!(self.file, "{line}") {
+            log::error!("Failed to write to {}: {err}", self.path.display());
+        }
+    }
+}
+
+/// Stores the numeric metrics of the training and validation splits of a run in a single
+/// [JSONL or CSV](RunStoreFormat) file.
+///
+/// The metrics are logged with the [loggers](RunMetricLogger) of each split, which can be
+/// registered with [metric_loggers](crate::LearnerBuilder::metric_loggers), or directly with
+/// [run_store](crate::LearnerBuilder::run_store).
+#[derive(Clone)]
+pub struct RunStore {
+    writer: Arc<Mutex<RunStoreWriter>>,
+}
+
+impl RunStore {
+    /// Create a new run store, writing the metrics file of the format in the given directory.
+    ///
+    /// Values are appended to an existing metrics file, e.g. when resuming from a checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// If the metrics file can't be created.
+    pub fn new(directory: impl AsRef<Path>, format: RunStoreFormat) -> Self {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory).ok();
+
+        let path = directory.join(format.file_name());
+        let exists = path.exists();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap_or_else(|err| {
+                panic!("Can't create the metrics file {}: {err}", path.display())
+            });
+        let mut file = BufWriter::new(file);
+
+        if format == RunStoreFormat::Csv && !exists {
+            writeln!(file, "{CSV_HEADER}").unwrap();
+        }
+
+        Self {
+            writer: Arc::new(Mutex::new(RunStoreWriter { file, path, format })),
+        }
+    }
+
+    /// The path of the metrics file.
+    pub fn path(&self) -> PathBuf {
+        self.writer.lock().unwrap().path.clone()
+    }
+
+    /// Create a metric logger for the training split.
+    pub fn logger_train(&self) -> RunMetricLogger {
+        self.logger(Split::Train, Some(1))
+    }
+
+    /// Create a metric logger for the validation split.
+    pub fn logger_valid(&self) -> RunMetricLogger {
+        self.logger(Split::Valid, Some(1))
+    }
+
+    /// Create a metric logger for the testing split, which has no epochs.
+    pub fn logger_test(&self) -> RunMetricLogger {
+        self.logger(Split::Test, None)
+    }
+
+    fn logger(&self, split: Split, epoch: Option<usize>) -> RunMetricLogger {
+        RunMetricLogger {
+            store: self.clone(),
+            split: split_name(split),
+            epoch,
+            iterations: Default::default(),
+        }
+    }
+
+    fn flush(&self) {
+        let mut writer = self.writer.lock().unwrap();
+        if let Err(err) = writer.file.flush() {
+            log::error!("Failed to flush {}: {err}", writer.path.display());
+        }
+    }
+}
+
+/// The name of the split in the metrics file.
+pub(crate) fn split_name(split: Split) -> &'static str {
+    match split {
+        Split::Train => "train",
+        Split::Valid => "valid",
+        Split::Test => "test",
+    }
+}
+
+/// Metric logger writing the numeric metrics of a split to a [run store](RunStore).
+pub struct RunMetricLogger {
+    store: RunStore,
+    split: &'static str,
+    epoch: Option<usize>,
+    iterations: std::collections::HashMap<(String, Vec<String>), usize>,
+}
+
+impl MetricLogger for RunMetricLogger {
+    fn log(&mut self, item: &MetricEntry) {
+        // Only numeric metrics are recorded.
+        let Ok(entry) = NumericEntry::deserialize(&item.serialize) else {
+            return;
+        };
+        let (value, count) = match entry {
+            NumericEntry::Value(value) => (value, 1),
+            NumericEntry::Aggregated { current, count, .. } => (current, count),
+            NumericEntry::Cumulative(value) => (value, 0),
+        };
+
+        let name = item.name.to_string();
+        let tags = item
+            .tags
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect::<Vec<_>>();
+        let iteration = self
+            .iterations
+            .entry((name.clone(), tags.clone()))
+            .or_default();
+        *iteration += 1;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+
+        let record = MetricRecord {
+            epoch: self.epoch.unwrap_or(1),
+            iteration: *iteration,
+            split: self.split.to_string(),
+            name,
+            tags,
+            value,
+            count,
+            timestamp,
+        };
+        self.store.writer.lock().unwrap().write(&record);
+    }
+
+    fn end_epoch(&mut self, epoch: usize) {
+        self.store.flush();
+        self.iterations.clear();
+        if let Some(current) = self.epoch.as_mut() {
+            *current = epoch + 1;
+        }
+    }
+
+    fn read_numeric(&mut self, name: &str, epoch: usize) -> Result<Vec<NumericEntry>, String> {
+        self.store.flush();
+
+        let records = read_metric_records(self.store.path())?;
+        Ok(records
+            .into_iter()
+            .filter(|record| {
+                record.split == self.split
+                    && record.epoch == epoch
+                    && record.name == name
+                    && record.tags.is_empty()
+            })
+            .map(|record| match record.count {
+                0 => NumericEntry::Cumulative(record.value),
+                count => NumericEntry::Aggregated {
+                    sum: record.value,
+                    count,
+                    current: record.value,
+                },
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, serialize: &str) -> MetricEntry {
+        MetricEntry::new(
+            Arc::new(name.to_string()),
+            serialize.to_string(),
+            serialize.to_string(),
+        )
+    }
+
+    fn log_run(format: RunStoreFormat) -> Vec<MetricRecord> {
+        let directory = tempfile::tempdir().unwrap();
+        let store = RunStore::new(directory.path(), format);
+        let mut train = store.logger_train();
+        let mut valid = store.logger_valid();
+
+        train.log(&entry("Loss", "1.5,2"));
+        train.log(&entry("Loss", "0.5,1"));
+        // Non-numeric metrics aren't recorded.
+        train.log(&entry("Status", "running"));
+        train.end_epoch(1);
+        let mut tagged = entry("Accuracy", "80.0");
+        tagged.tags.push(Arc::new("dataset, \"a\"".to_string()));
+        valid.log(&tagged);
+        valid.end_epoch(1);
+        train.log(&entry("Loss", "0.25,2"));
+        train.end_epoch(2);
+
+        let values = train.read_numeric("Loss", 1).unwrap();
+        assert_eq!(values.len(), 2);
+
+        read_metric_records(store.path()).unwrap()
+    }
+
+    fn assert_records(records: Vec<MetricRecord>) {
+        let records = records
+            .into_iter()
+            .map(|record| {
+                assert!(record.timestamp > 0.0);
+                (
+                    record.epoch,
+                    record.iteration,
+                    record.split,
+                    record.name,
+                    record.tags,
+                    record.value,
+                    record.count,
+                )
+            })
+            .collect::<Vec<_>>();
+        let record = |epoch, iteration, split: &str, name: &str, tags: &[&str], value, count| {
+            (
+                epoch,
+                iteration,
+                split.to_string(),
+                name.to_string(),
+                tags.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                value,
+                count,
+            )
+        };
+
+        assert_eq!(
+            records,
+            vec![
+                record(1, 1, "train", "Loss", &[], 1.5, 2),
+                record(1, 2, "train", "Loss", &[], 0.5, 1),
+                record(1, 1, "valid", "Accuracy", &["dataset, \"a\""], 80.0, 1),
+                record(2, 1, "train", "Loss", &[], 0.25, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_store_jsonl() {
+        assert_records(log_run(RunStoreFormat::Jsonl));
+    }
+
+    #[test]
+    fn test_run_store_csv() {
+        assert_records(log_run(RunStoreFormat::Csv));
+    }
+
+    #[test]
+    fn test_run_store_cumulative_entries() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = RunStore::new(directory.path(), RunStoreFormat::Jsonl);
+        let mut train = store.logger_train();
+
+        train.log(&entry("BLEU", "20,cumulative"));
+        train.log(&entry("BLEU", "30,cumulative"));
+
+        let values = train.read_numeric("BLEU", 1).unwrap();
+        assert!(matches!(
+            values.as_slice(),
+            [
+                NumericEntry::Cumulative(20.0),
+                NumericEntry::Cumulative(30.0)
+            ]
+        ));
+        assert_eq!(read_metric_records(store.path()).unwrap()[1].count, 0);
+    }
+
+    fn assert_non_finite_values(format: RunStoreFormat) {
+        let directory = tempfile::tempdir().unwrap();
+        let store = RunStore::new(directory.path(), format);
+        let mut train = store.logger_train();
+
+        train.log(&entry("Loss", "NaN,2"));
+        train.log(&entry("Loss", "inf,2"));
+        train.log(&entry("Loss", "-inf,2"));
+
+        let values = read_metric_records(store.path())
+            .unwrap()
+            .into_iter()
+            .map(|record| record.value)
+            .collect::<Vec<_>>();
+        assert!(values[0].is_nan());
+        assert_eq!(values[1..], [f64::INFINITY, f64::NEG_INFINITY]);
+    }
+
+    #[test]
+    fn test_run_store_jsonl_non_finite_values() {
+        assert_non_finite_values(RunStoreFormat::Jsonl);
+    }
+
+    #[test]
+    fn test_run_store_csv_non_finite_values() {
+        assert_non_finite_values(RunStoreFormat::Csv);
+    }
+}
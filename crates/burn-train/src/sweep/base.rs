@@ -0,0 +1,594 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use burn_core::config::Config;
+use burn_core::lr_scheduler::LrScheduler;
+use burn_core::module::AutodiffModule;
+use burn_core::optim::Optimizer;
+use burn_core::tensor::backend::AutodiffBackend;
+use serde::{Deserialize, Serialize};
+
+use super::{Pruner, RandomSampler, Sampler, SearchSpace, TrialParams, TrialPruning};
+use crate::metric::Metric;
+use crate::metric::processor::ItemLazy;
+use crate::metric::store::{Direction, Split};
+use crate::run::RunStoreFormat;
+use crate::{Interrupter, LearnerBuilder, LearningStrategy, TrainStep, ValidStep};
+
+/// The name of the file saving the trials in the directory of a sweep.
+pub const SWEEP_FILE: &str = "sweep.json";
+
+/// The state of a trial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrialState {
+    /// The trial is training, or has been interrupted and will be trained again when the sweep
+    /// is resumed.
+    Running,
+    /// The trial has been trained.
+    Complete,
+    /// The trial has been stopped early by the pruner.
+    Pruned,
+    /// The trial couldn't be created or didn't report any objective.
+    Failed,
+}
+
+/// A trial of a sweep, saved in the sweep file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrialRecord {
+    /// The id of the trial.
+    pub id: usize,
+    /// The values of the searched parameters.
+    pub params: TrialParams,
+    /// The state of the trial.
+    pub state: TrialState,
+    /// The best objective of the epochs of the trial, once complete or pruned.
+    pub value: Option<f64>,
+    /// The objective reported for each epoch.
+    pub intermediate_values: Vec<(usize, f64)>,
+}
+
+impl TrialRecord {
+    /// The objective reported for the epoch.
+    pub fn intermediate_value(&self, epoch: usize) -> Option<f64> {
+        self.intermediate_values
+            .iter()
+            .find(|(current, _)| *current == epoch)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// The trials of a sweep, shared by the workers and the pruning strategies of the trials.
+pub(crate) struct SweepState {
+    path: PathBuf,
+    pub(crate) trials: Vec<TrialRecord>,
+    pruner: Option<Box<dyn Pruner>>,
+    direction: Direction,
+}
+
+impl SweepState {
+    pub(crate) fn report(&mut self, trial: usize, epoch: usize, value: f64) -> bool {
+        let record = &mut self.trials[trial];
+        record
+            .intermediate_values
+            .retain(|(current, _)| *current != epoch);
+        record.intermediate_values.push((epoch, value));
+
+        let pruned = match &self.pruner {
+            Some(pruner) => pruner.should_prune(trial, epoch, value, &self.trials, self.direction),
+            None => false,
+        };
+        if pruned {
+            log::info!("Pruning trial {trial} at epoch {epoch} with objective {value}");
+            self.trials[trial].state = TrialState::Pruned;
+        }
+
+        self.save();
+        pruned
+    }
+
+    fn finish(&mut self, trial: usize) {
+        let direction = self.direction;
+        let record = &mut self.trials[trial];
+        record.value = record
+            .intermediate_values
+            .iter()
+            .map(|(_, value)| *value)
+            .reduce(|best, value| match direction {
+                Direction::Lowest => best.min(value),
+                Direction::Highest => best.max(value),
+            });
+
+        record.state = match (record.state, record.value) {
+            (_, None) => TrialState::Failed,
+            (TrialState::Pruned, _) => TrialState::Pruned,
+            _ => TrialState::Complete,
+        };
+        self.save();
+    }
+
+    fn fail(&mut self, trial: usize) {
+        self.trials[trial].state = TrialState::Failed;
+        self.save();
+    }
+
+    fn save(&self) {
+        let content = serde_json::to_string_pretty(&self.trials).unwrap();
+        if let Err(err) = std::fs::write(&self.path, content) {
+            log::error!("Failed to save the sweep to {}: {err}", self.path.display());
+        }
+    }
+}
+
+/// A trial of a [sweep](Sweep), with the config to train.
+pub struct Trial<C, D> {
+    /// The id of the trial.
+    pub id: usize,
+    /// The config of the trial, with the sampled parameters.
+    pub config: C,
+    /// The values of the sampled parameters.
+    pub params: TrialParams,
+    /// The device to train the trial on.
+    pub device: D,
+    directory: PathBuf,
+    pruning: TrialPruning,
+    interrupter: Interrupter,
+}
+
+impl<C: Config, D: Clone> Trial<C, D> {
+    /// The directory of the trial, where the learner should save its artifacts.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The [early stopping strategy](crate::EarlyStoppingStrategy) reporting the objective of
+    /// each epoch to the sweep, which stops the training when the trial is pruned.
+    ///
+    /// It can be combined with other strategies using a
+    /// [composed strategy](crate::ComposedEarlyStoppingStrategy).
+    pub fn pruning(&self) -> TrialPruning {
+        self.pruning.clone()
+    }
+
+    /// Report the objective of an epoch when training without a learner, returning whether the
+    /// trial is pruned and its training should be stopped.
+    pub fn report(&self, epoch: usize, value: f64) -> bool {
+        self.pruning.report(epoch, value)
+    }
+
+    /// The handle stopping the sweep, which should also stop the training of the trial.
+    pub fn interrupter(&self) -> Interrupter {
+        self.interrupter.clone()
+    }
+
+    /// Configure the learner builder to train the trial: on the device of the trial, reporting
+    /// the objectives to the sweep with an early stopping strategy, and stopped with the sweep.
+    ///
+    /// The [pruning](Self::pruning) is composed with the early stopping strategy already
+    /// registered in the builder. A strategy registered afterwards replaces both, so it should
+    /// be [composed](crate::ComposedEarlyStoppingStrategy) with the pruning of the trial.
+    ///
+    /// The metrics of the trial are also recorded in a [run store](crate::run::RunStore), along
+    /// with its config.
+    #[allow(clippy::type_complexity)]
+    pub fn learner<B, M, O, S, TI, VI, TO, VO>(
+        &self,
+        builder: LearnerBuilder<B, M, O, S, TI, VI, TO, VO>,
+    ) -> LearnerBuilder<B, M, O, S, TI, VI, TO, VO>
+    where
+        B: AutodiffBackend<Device = D>,
+        M: AutodiffModule<B> + TrainStep<TI, TO> + core::fmt::Display + 'static,
+        M::InnerModule: ValidStep<VI, VO>,
+        O: Optimizer<M, B>,
+        S: LrScheduler,
+        TI: Send + 'static,
+        VI: Send + 'static,
+        TO: ItemLazy + 'static,
+        VO: ItemLazy + 'static,
+    {
+        builder
+            .learning_strategy(LearningStrategy::SingleDevice(self.device.clone()))
+            .add_early_stopping(self.pruning())
+            .with_interrupter(self.interrupter())
+            .run_store(RunStoreFormat::Jsonl)
+            .run_config("config", &self.config)
+    }
+}
+
+/// The objective optimized by a sweep: the mean of a metric for an epoch.
+#[derive(Clone)]
+struct SweepObjective {
+    metric: String,
+    split: Split,
+    direction: Direction,
+}
+
+/// Searches the hyperparameters of a config, training a trial for each sampled set of
+/// parameters.
+///
+/// The trials are saved in the `sweep.json` file of the directory of the sweep, so that running
+/// the sweep again in the same directory resumes it: the finished trials are kept, and the
+/// trials that were interrupted are trained again with the same parameters.
+pub struct Sweep<C: Config> {
+    directory: PathBuf,
+    base: C,
+    space: SearchSpace,
+    sampler: Box<dyn Sampler>,
+    pruner: Option<Box<dyn Pruner>>,
+    objective: SweepObjective,
+    num_trials: usize,
+    interrupter: Interrupter,
+}
+
+impl<C: Config> Sweep<C> {
+    /// Create a new sweep of the search space around the base config, saved in the given
+    /// directory.
+    ///
+    /// By default, 10 trials are sampled randomly, without pruning, to minimize the validation
+    /// loss.
+    pub fn new(directory: impl AsRef<Path>, base: C, space: SearchSpace) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+            base,
+            space,
+            sampler: Box::new(RandomSampler::new(0)),
+            pruner: None,
+            objective: SweepObjective {
+                metric: "Loss".to_string(),
+                split: Split::Valid,
+                direction: Direction::Lowest,
+            },
+            num_trials: 10,
+            interrupter: Interrupter::new(),
+        }
+    }
+
+    /// Set the sampler of the parameters of the trials.
+    pub fn with_sampler<Sa: Sampler + 'static>(mut self, sampler: Sa) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    /// Set the pruner stopping the unpromising trials early.
+    pub fn with_pruner<P: Pruner + 'static>(mut self, pruner: P) -> Self {
+        self.pruner = Some(Box::new(pruner));
+        self
+    }
+
+    /// Set the objective of the sweep: the mean of the metric for an epoch of the split, with
+    /// the best epoch of each trial compared.
+    pub fn with_objective<Me: Metric>(
+        mut self,
+        metric: &Me,
+        split: Split,
+        direction: Direction,
+    ) -> Self {
+        self.objective = SweepObjective {
+            metric: metric.name().to_string(),
+            split,
+            direction,
+        };
+        self
+    }
+
+    /// Set the number of trials of the sweep, including the ones of a resumed sweep.
+    pub fn with_num_trials(mut self, num_trials: usize) -> Self {
+        self.num_trials = num_trials;
+        self
+    }
+
+    /// The handle to stop the sweep, interrupting the running trials.
+    pub fn interrupter(&self) -> Interrupter {
+        self.interrupter.clone()
+    }
+
+    /// Run the sweep, training the trials with the given function.
+    ///
+    /// A trial is trained on each device at a time, so the trials are trained sequentially with a
+    /// single device. The function should train the config of the trial with a learner
+    /// configured with [Trial::learner], or report the objective of each epoch with
+    /// [Trial::report].
+    ///
+    /// # Panics
+    ///
+    /// If the sweep file can't be read, or no device is given.
+    pub fn run<D, F>(self, devices: Vec<D>, train: F) -> SweepResult
+    where
+        C: Sync,
+        D: Clone + Send,
+        F: Fn(&Trial<C, D>) + Sync,
+    {
+        assert!(!devices.is_empty(), "At least one device is required");
+        std::fs::create_dir_all(&self.directory).ok();
+
+        let path = self.directory.join(SWEEP_FILE);
+        let mut trials = match path.exists() {
+            true => load_trials(&path).unwrap_or_else(|err| panic!("{err}")),
+            false => Vec::new(),
+        };
+
+        // The interrupted trials are trained again from scratch.
+        let mut pending = VecDeque::new();
+        for record in trials.iter_mut() {
+            if record.state == TrialState::Running {
+                record.intermediate_values.clear();
+                pending.push_back(record.id);
+                std::fs::remove_dir_all(trial_directory(&self.directory, record.id)).ok();
+            }
+        }
+        if !trials.is_empty() {
+            log::info!(
+                "Resuming the sweep with {} trials, {} interrupted",
+                trials.len(),
+                pending.len()
+            );
+        }
+
+        let state = Arc::new(Mutex::new(SweepState {
+            path,
+            trials,
+            pruner: self.pruner,
+            direction: self.objective.direction,
+        }));
+        let scheduler = Mutex::new((self.sampler, pending));
+        self.interrupter.reset();
+
+        std::thread::scope(|scope| {
+            for device in devices {
+                let worker = SweepWorker {
+                    directory: &self.directory,
+                    base: &self.base,
+                    space: &self.space,
+                    objective: &self.objective,
+                    num_trials: self.num_trials,
+                    interrupter: &self.interrupter,
+                    state: &state,
+                    scheduler: &scheduler,
+                };
+                let train = &train;
+                scope.spawn(move || worker.run(device, train));
+            }
+        });
+
+        let trials = state.lock().unwrap().trials.clone();
+        SweepResult {
+            trials,
+            direction: self.objective.direction,
+        }
+    }
+}
+
+fn trial_directory(directory: &Path, trial: usize) -> PathBuf {
+    directory.join(format!("trial-{trial}"))
+}
+
+fn load_trials(path: &Path) -> Result<Vec<TrialRecord>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| format!("Can't read the sweep {}: {err}", path.display()))?;
+    serde_json::from_str(&content).map_err(|err| format!("Invalid sweep {}: {err}", path.display()))
+}
+
+type SweepScheduler = Mutex<(Box<dyn Sampler>, VecDeque<usize>)>;
+
+struct SweepWorker<'a, C> {
+    directory: &'a Path,
+    base: &'a C,
+    space: &'a SearchSpace,
+    objective: &'a SweepObjective,
+    num_trials: usize,
+    interrupter: &'a Interrupter,
+    state: &'a Arc<Mutex<SweepState>>,
+    scheduler: &'a SweepScheduler,
+}
+
+impl<C: Config> SweepWorker<'_, C> {
+    fn run<D: Clone, F: Fn(&Trial<C, D>)>(self, device: D, train: &F) {
+        while !self.interrupter.should_stop() {
+            let Some((id, params)) = self.next_trial() else {
+                break;
+            };
+
+            let config = match self.space.apply(self.base, &params) {
+                Ok(config) => config,
+                Err(err) => {
+                    log::error!("Failed to create the config of trial {id}: {err}");
+                    self.state.lock().unwrap().fail(id);
+                    continue;
+                }
+            };
+
+            log::info!("Training trial {id} with {params:?}");
+            let trial = Trial {
+                id,
+                config,
+                params,
+                device: device.clone(),
+                directory: trial_directory(self.directory, id),
+                pruning: TrialPruning::new(
+                    self.state.clone(),
+                    id,
+                    self.objective.metric.clone(),
+                    self.objective.split,
+                ),
+                interrupter: self.interrupter.clone(),
+            };
+            train(&trial);
+
+            // An interrupted trial is kept running, to be trained again when resuming.
+            if !self.interrupter.should_stop() {
+                self.state.lock().unwrap().finish(id);
+            }
+        }
+    }
+
+    fn next_trial(&self) -> Option<(usize, TrialParams)> {
+        let mut scheduler = self.scheduler.lock().unwrap();
+        let (sampler, pending) = &mut *scheduler;
+
+        if let Some(id) = pending.pop_front() {
+            let state = self.state.lock().unwrap();
+            return Some((id, state.trials[id].params.clone()));
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let id = state.trials.len();
+        if id >= self.num_trials {
+            return None;
+        }
+
+        let params = sampler.sample(id, self.space, &state.trials, self.objective.direction)?;
+        state.trials.push(TrialRecord {
+            id,
+            params: params.clone(),
+            state: TrialState::Running,
+            value: None,
+            intermediate_values: Vec::new(),
+        });
+        state.save();
+
+        Some((id, params))
+    }
+}
+
+/// The trials of a finished or interrupted [sweep](Sweep).
+#[derive(Clone, Debug)]
+pub struct SweepResult {
+    /// The trials of the sweep.
+    pub trials: Vec<TrialRecord>,
+    direction: Direction,
+}
+
+impl SweepResult {
+    /// Load the trials of the sweep saved in the given directory, with the direction of its
+    /// objective.
+    pub fn load(directory: impl AsRef<Path>, direction: Direction) -> Result<Self, String> {
+        let trials = load_trials(&directory.as_ref().join(SWEEP_FILE))?;
+        Ok(Self { trials, direction })
+    }
+
+    /// The trial with the best objective, among the completed ones.
+    pub fn best(&self) -> Option<&TrialRecord> {
+        self.trials
+            .iter()
+            .filter(|trial| trial.state == TrialState::Complete)
+            .filter_map(|trial| Some((trial.value?, trial)))
+            .reduce(|best, current| match self.direction {
+                Direction::Lowest if current.0 < best.0 => current,
+                Direction::Highest if current.0 > best.0 => current,
+                _ => best,
+            })
+            .map(|(_, trial)| trial)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweep::{GridSampler, MedianPruner};
+    use burn_core as burn;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Config, Debug)]
+    pub struct TestConfig {
+        x: f64,
+    }
+
+    fn sweep(directory: &Path, num_trials: usize) -> Sweep<TestConfig> {
+        Sweep::new(
+            directory,
+            TestConfig::new(0.0),
+            SearchSpace::new().choice("x", [1.0, 2.0, 3.0, 4.0]),
+        )
+        .with_sampler(GridSampler::new())
+        .with_num_trials(num_trials)
+    }
+
+    // The objective decreases with the epochs, and is the lowest for x = 3.
+    fn train(trial: &Trial<TestConfig, ()>) {
+        for epoch in 1..=3 {
+            let value = (trial.config.x - 3.0).abs() + 1.0 / epoch as f64;
+            if trial.report(epoch, value) {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_and_resume_sweep() {
+        let directory = tempfile::tempdir().unwrap();
+        let count = AtomicUsize::new(0);
+        let train = |trial: &Trial<TestConfig, ()>| {
+            count.fetch_add(1, Ordering::Relaxed);
+            train(trial);
+        };
+
+        let result = sweep(directory.path(), 2).run(vec![()], train);
+        assert_eq!(result.trials.len(), 2);
+        assert_eq!(result.best().unwrap().id, 1);
+
+        // The finished trials aren't trained again.
+        let result = sweep(directory.path(), 4).run(vec![()], train);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+
+        let best = result.best().unwrap();
+        assert_eq!(best.id, 2);
+        assert_eq!(best.value, Some(1.0 / 3.0));
+        assert_eq!(best.intermediate_values.len(), 3);
+
+        let loaded = SweepResult::load(directory.path(), Direction::Lowest).unwrap();
+        assert_eq!(loaded.trials.len(), 4);
+        assert_eq!(loaded.best().unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_resume_interrupted_trial() {
+        let directory = tempfile::tempdir().unwrap();
+        let sweep = sweep(directory.path(), 4);
+        let interrupter = sweep.interrupter();
+
+        let result = sweep.run(vec![()], |trial| {
+            trial.report(1, 1.0);
+            if trial.id == 1 {
+                interrupter.stop();
+            }
+        });
+        let states = result
+            .trials
+            .iter()
+            .map(|trial| trial.state)
+            .collect::<Vec<_>>();
+        assert_eq!(states, vec![TrialState::Complete, TrialState::Running]);
+
+        let ids = Mutex::new(Vec::new());
+        let result = self::sweep(directory.path(), 4).run(vec![()], |trial| {
+            ids.lock().unwrap().push(trial.id);
+            train(trial);
+        });
+
+        assert_eq!(ids.into_inner().unwrap(), vec![1, 2, 3]);
+        assert_eq!(result.trials[1].params["x"], 2.0);
+        assert!(
+            result
+                .trials
+                .iter()
+                .all(|trial| trial.state == TrialState::Complete)
+        );
+    }
+
+    #[test]
+    fn test_run_sweep_on_devices() {
+        let directory = tempfile::tempdir().unwrap();
+        let result = Sweep::new(
+            directory.path(),
+            TestConfig::new(0.0),
+            SearchSpace::new().choice("x", [3.0, 2.5, 2.0, 6.0]),
+        )
+        .with_sampler(GridSampler::new())
+        .with_pruner(MedianPruner::new().with_startup_trials(3))
+        .with_num_trials(4)
+        .run(vec![(), ()], train);
+
+        assert_eq!(result.trials.len(), 4);
+        assert_eq!(result.best().unwrap().params["x"], 3.0);
+    }
+}
@@ -0,0 +1,9 @@
+mod base;
+mod pruner;
+mod sampler;
+mod space;
+
+pub use base::*;
+pub use pruner::*;
+pub use sampler::*;
+pub use space::*;
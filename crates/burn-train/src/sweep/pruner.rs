@@ -0,0 +1,311 @@
+use std::sync::{Arc, Mutex};
+
+use super::{SweepState, TrialRecord, TrialState};
+use crate::EarlyStoppingStrategy;
+use crate::metric::store::{Aggregate, Direction, EventStoreClient, Split};
+
+/// Decides whether a trial of a [sweep](super::Sweep) should be stopped early, from the
+/// objectives of its epochs compared to the other trials.
+pub trait Pruner: Send {
+    /// Whether the trial should be pruned after reporting the objective of the epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `trial` - The id of the trial.
+    /// * `epoch` - The epoch of the objective.
+    /// * `value` - The objective of the epoch.
+    /// * `history` - All the trials, including the running ones, with the objectives reported
+    ///   for each epoch.
+    /// * `direction` - Whether lower or higher objectives are better.
+    fn should_prune(
+        &self,
+        trial: usize,
+        epoch: usize,
+        value: f64,
+        history: &[TrialRecord],
+        direction: Direction,
+    ) -> bool;
+}
+
+fn is_better(a: f64, b: f64, direction: Direction) -> bool {
+    match direction {
+        Direction::Lowest => a < b,
+        Direction::Highest => a > b,
+    }
+}
+
+/// Prunes the trials whose objective is worse than the median of the objectives of the completed
+/// trials at the same epoch.
+#[derive(Clone, Debug)]
+pub struct MedianPruner {
+    startup_trials: usize,
+    warmup_epochs: usize,
+}
+
+impl MedianPruner {
+    /// Create a new median pruner, waiting for 5 completed trials and not pruning during the
+    /// first epoch.
+    pub fn new() -> Self {
+        Self {
+            startup_trials: 5,
+            warmup_epochs: 1,
+        }
+    }
+
+    /// Set the number of completed trials before pruning.
+    pub fn with_startup_trials(mut self, startup_trials: usize) -> Self {
+        self.startup_trials = startup_trials;
+        self
+    }
+
+    /// Set the number of epochs of each trial before pruning it.
+    pub fn with_warmup_epochs(mut self, warmup_epochs: usize) -> Self {
+        self.warmup_epochs = warmup_epochs;
+        self
+    }
+}
+
+impl Default for MedianPruner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pruner for MedianPruner {
+    fn should_prune(
+        &self,
+        trial: usize,
+        epoch: usize,
+        value: f64,
+        history: &[TrialRecord],
+        direction: Direction,
+    ) -> bool {
+        if epoch <= self.warmup_epochs {
+            return false;
+        }
+
+        let completed = history
+            .iter()
+            .filter(|record| record.id != trial && record.state == TrialState::Complete)
+            .collect::<Vec<_>>();
+        if completed.len() < self.startup_trials {
+            return false;
+        }
+
+        let mut values = completed
+            .iter()
+            .filter_map(|record| record.intermediate_value(epoch))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return false;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let middle = values.len() / 2;
+        let median = match values.len() % 2 {
+            0 => (values[middle - 1] + values[middle]) / 2.0,
+            _ => values[middle],
+        };
+
+        is_better(median, value, direction)
+    }
+}
+
+/// Prunes the trials with asynchronous successive halving (ASHA).
+///
+/// The trials are compared at the rungs of `min_epochs * reduction_factor^k` epochs, where only
+/// the best `1 / reduction_factor` of the trials that reached the rung are kept. Since the rungs
+/// are evaluated when each trial reaches them, the trials don't wait for each other.
+#[derive(Clone, Debug)]
+pub struct AshaPruner {
+    min_epochs: usize,
+    reduction_factor: usize,
+}
+
+impl AshaPruner {
+    /// Create a new ASHA pruner, with a first rung after the first epoch and keeping a third of
+    /// the trials at each rung.
+    pub fn new() -> Self {
+        Self {
+            min_epochs: 1,
+            reduction_factor: 3,
+        }
+    }
+
+    /// Set the number of epochs of the first rung.
+    pub fn with_min_epochs(mut self, min_epochs: usize) -> Self {
+        assert!(
+            min_epochs > 0,
+            "The first rung must have at least one epoch"
+        );
+        self.min_epochs = min_epochs;
+        self
+    }
+
+    /// Set the inverse of the fraction of the trials kept at each rung.
+    pub fn with_reduction_factor(mut self, reduction_factor: usize) -> Self {
+        assert!(
+            reduction_factor > 1,
+            "The reduction factor must be at least 2"
+        );
+        self.reduction_factor = reduction_factor;
+        self
+    }
+
+    fn is_rung(&self, epoch: usize) -> bool {
+        let mut rung = self.min_epochs;
+        while rung < epoch {
+            rung *= self.reduction_factor;
+        }
+        rung == epoch
+    }
+}
+
+impl Default for AshaPruner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pruner for AshaPruner {
+    fn should_prune(
+        &self,
+        trial: usize,
+        epoch: usize,
+        value: f64,
+        history: &[TrialRecord],
+        direction: Direction,
+    ) -> bool {
+        if !self.is_rung(epoch) {
+            return false;
+        }
+
+        let mut values = history
+            .iter()
+            .filter(|record| record.id != trial)
+            .filter_map(|record| record.intermediate_value(epoch))
+            .chain([value])
+            .collect::<Vec<_>>();
+        if values.len() < 2 {
+            return false;
+        }
+        values.sort_by(|a, b| match direction {
+            Direction::Lowest => a.total_cmp(b),
+            Direction::Highest => b.total_cmp(a),
+        });
+
+        let num_promoted = (values.len() / self.reduction_factor).max(1);
+        is_better(values[num_promoted - 1], value, direction)
+    }
+}
+
+/// An [early stopping strategy](EarlyStoppingStrategy) reporting the objective of each epoch of
+/// a trial to its sweep, which stops the training when the trial is pruned.
+#[derive(Clone)]
+pub struct TrialPruning {
+    state: Arc<Mutex<SweepState>>,
+    trial: usize,
+    metric: String,
+    split: Split,
+}
+
+impl TrialPruning {
+    pub(crate) fn new(
+        state: Arc<Mutex<SweepState>>,
+        trial: usize,
+        metric: String,
+        split: Split,
+    ) -> Self {
+        Self {
+            state,
+            trial,
+            metric,
+            split,
+        }
+    }
+
+    /// Report the objective of an epoch, returning whether the trial is pruned.
+    pub fn report(&self, epoch: usize, value: f64) -> bool {
+        self.state.lock().unwrap().report(self.trial, epoch, value)
+    }
+
+    /// Whether the trial has been pruned.
+    pub fn is_pruned(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.trials[self.trial].state == TrialState::Pruned
+    }
+}
+
+impl EarlyStoppingStrategy for TrialPruning {
+    fn should_stop(&mut self, epoch: usize, store: &EventStoreClient) -> bool {
+        match store.find_metric(&self.metric, epoch, Aggregate::Mean, self.split) {
+            Some(value) => self.report(epoch, value),
+            None => {
+                log::warn!("Can't find the objective {} of the trial.", self.metric);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweep::TrialParams;
+
+    fn trial(id: usize, state: TrialState, values: &[f64]) -> TrialRecord {
+        TrialRecord {
+            id,
+            params: TrialParams::new(),
+            state,
+            value: None,
+            intermediate_values: values
+                .iter()
+                .enumerate()
+                .map(|(epoch, value)| (epoch + 1, *value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_median_pruner() {
+        let history = vec![
+            trial(0, TrialState::Complete, &[3.0, 1.0]),
+            trial(1, TrialState::Complete, &[3.0, 2.0]),
+            trial(2, TrialState::Pruned, &[3.0, 0.5]),
+            trial(3, TrialState::Complete, &[3.0, 3.0]),
+            trial(4, TrialState::Running, &[5.0, 2.5]),
+        ];
+        let pruner = MedianPruner::new().with_startup_trials(3);
+
+        // Not pruned during the warmup.
+        assert!(!pruner.should_prune(4, 1, 5.0, &history, Direction::Lowest));
+        // The median of the completed trials is 2.0.
+        assert!(pruner.should_prune(4, 2, 2.5, &history, Direction::Lowest));
+        assert!(!pruner.should_prune(4, 2, 1.5, &history, Direction::Lowest));
+        assert!(!pruner.should_prune(4, 2, 2.5, &history, Direction::Highest));
+
+        let pruner = MedianPruner::new().with_startup_trials(4);
+        assert!(!pruner.should_prune(4, 2, 2.5, &history, Direction::Lowest));
+    }
+
+    #[test]
+    fn test_asha_pruner() {
+        let history = vec![
+            trial(0, TrialState::Complete, &[1.0, 1.0, 1.0]),
+            trial(1, TrialState::Pruned, &[2.0]),
+            trial(2, TrialState::Running, &[3.0, 3.0]),
+            trial(3, TrialState::Pruned, &[4.0]),
+        ];
+        let pruner = AshaPruner::new().with_reduction_factor(2);
+
+        // The best 2 of the 5 trials at the first rung are kept.
+        assert!(pruner.should_prune(4, 1, 3.5, &history, Direction::Lowest));
+        assert!(!pruner.should_prune(4, 1, 1.5, &history, Direction::Lowest));
+        // The best of the 3 trials at the second rung is kept.
+        assert!(pruner.should_prune(4, 2, 2.0, &history, Direction::Lowest));
+        assert!(!pruner.should_prune(4, 2, 0.5, &history, Direction::Lowest));
+        // The trials are only compared at the rungs.
+        assert!(!pruner.should_prune(4, 3, 10.0, &history, Direction::Lowest));
+    }
+}
@@ -0,0 +1,469 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+
+use super::{ParamDistribution, SearchSpace, TrialParams, TrialRecord};
+use crate::metric::store::Direction;
+
+/// Samples the parameters of the trials of a [sweep](super::Sweep).
+pub trait Sampler: Send {
+    /// Sample the parameters of the given trial, or return `None` when the search space is
+    /// exhausted.
+    ///
+    /// # Arguments
+    ///
+    /// * `trial` - The id of the trial, the number of trials sampled before it.
+    /// * `space` - The search space.
+    /// * `history` - The previous trials, with their objective when completed.
+    /// * `direction` - Whether lower or higher objectives are better.
+    fn sample(
+        &mut self,
+        trial: usize,
+        space: &SearchSpace,
+        history: &[TrialRecord],
+        direction: Direction,
+    ) -> Option<TrialParams>;
+}
+
+/// Samples every combination of the parameters, with a number of evenly spaced values for the
+/// numeric distributions.
+#[derive(Clone, Debug)]
+pub struct GridSampler {
+    steps: usize,
+}
+
+impl GridSampler {
+    /// Create a new grid sampler, with 5 values for the numeric distributions.
+    pub fn new() -> Self {
+        Self { steps: 5 }
+    }
+
+    /// Set the number of evenly spaced values of the numeric distributions.
+    pub fn with_steps(mut self, steps: usize) -> Self {
+        assert!(steps > 0, "The grid must have at least one step");
+        self.steps = steps;
+        self
+    }
+
+    fn values(&self, distribution: &ParamDistribution) -> Vec<Value> {
+        if let ParamDistribution::Choice(values) = distribution {
+            return values.clone();
+        }
+
+        let (low, high) = distribution.bounds().unwrap();
+        let mut values = (0..self.steps)
+            .map(|step| match self.steps {
+                1 => (low + high) / 2.0,
+                steps => low + (high - low) * step as f64 / (steps - 1) as f64,
+            })
+            .map(|x| distribution.value(x))
+            .collect::<Vec<_>>();
+        values.dedup();
+        values
+    }
+}
+
+impl Default for GridSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sampler for GridSampler {
+    fn sample(
+        &mut self,
+        trial: usize,
+        space: &SearchSpace,
+        _history: &[TrialRecord],
+        _direction: Direction,
+    ) -> Option<TrialParams> {
+        let grid = space
+            .params()
+            .iter()
+            .map(|(path, distribution)| (path, self.values(distribution)))
+            .collect::<Vec<_>>();
+        let size = grid
+            .iter()
+            .map(|(_, values)| values.len())
+            .product::<usize>();
+        if trial >= size {
+            return None;
+        }
+
+        // The last parameters vary the fastest.
+        let mut index = trial;
+        let mut params = TrialParams::new();
+        for (path, values) in grid.iter().rev() {
+            params.insert(path.to_string(), values[index % values.len()].clone());
+            index /= values.len();
+        }
+
+        Some(params)
+    }
+}
+
+/// Samples the parameters independently and uniformly.
+#[derive(Clone, Debug)]
+pub struct RandomSampler {
+    seed: u64,
+}
+
+impl RandomSampler {
+    /// Create a new random sampler.
+    ///
+    /// The parameters of a trial only depend on the seed and its id, so that a resumed sweep
+    /// samples the same trials.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Sampler for RandomSampler {
+    fn sample(
+        &mut self,
+        trial: usize,
+        space: &SearchSpace,
+        _history: &[TrialRecord],
+        _direction: Direction,
+    ) -> Option<TrialParams> {
+        let mut rng = trial_rng(self.seed, trial);
+        Some(sample_uniform(&mut rng, space))
+    }
+}
+
+fn trial_rng(seed: u64, trial: usize) -> StdRng {
+    StdRng::seed_from_u64(seed.wrapping_add((trial as u64).wrapping_mul(0x9e3779b97f4a7c15)))
+}
+
+fn sample_uniform(rng: &mut StdRng, space: &SearchSpace) -> TrialParams {
+    space
+        .params()
+        .iter()
+        .map(|(path, distribution)| {
+            let value = match distribution {
+                ParamDistribution::Choice(values) => {
+                    values[rng.random_range(0..values.len())].clone()
+                }
+                ParamDistribution::Int { low, high } => Value::from(rng.random_range(*low..=*high)),
+                distribution => {
+                    let (low, high) = distribution.bounds().unwrap();
+                    distribution.value(low + (high - low) * rng.random::<f64>())
+                }
+            };
+            (path.clone(), value)
+        })
+        .collect()
+}
+
+/// Samples the parameters with the tree-structured Parzen estimator (TPE), a Bayesian
+/// optimization method.
+///
+/// The completed trials are split between the best ones, a fraction `gamma` of them, and the
+/// others. For each parameter, candidates are sampled from a density estimated on the values of
+/// the best trials, and the candidate maximizing the ratio of this density with the density of
+/// the other trials is chosen. The first trials are sampled randomly.
+#[derive(Clone, Debug)]
+pub struct TpeSampler {
+    seed: u64,
+    startup_trials: usize,
+    gamma: f64,
+    candidates: usize,
+}
+
+impl TpeSampler {
+    /// Create a new TPE sampler, with 10 random startup trials, the best quarter of the trials
+    /// used to estimate the density of the good parameters, and 24 candidates.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            startup_trials: 10,
+            gamma: 0.25,
+            candidates: 24,
+        }
+    }
+
+    /// Set the number of completed trials sampled randomly before estimating the densities.
+    pub fn with_startup_trials(mut self, startup_trials: usize) -> Self {
+        self.startup_trials = startup_trials;
+        self
+    }
+
+    /// Set the fraction of the completed trials considered good.
+    pub fn with_gamma(mut self, gamma: f64) -> Self {
+        assert!(gamma > 0.0 && gamma < 1.0, "Gamma must be between 0 and 1");
+        self.gamma = gamma;
+        self
+    }
+
+    /// Set the number of candidates sampled for each parameter.
+    pub fn with_candidates(mut self, candidates: usize) -> Self {
+        assert!(candidates > 0, "At least one candidate must be sampled");
+        self.candidates = candidates;
+        self
+    }
+
+    fn sample_categorical(
+        &self,
+        rng: &mut StdRng,
+        num_values: usize,
+        below: &[f64],
+        above: &[f64],
+    ) -> f64 {
+        // The frequencies of the values, with a uniform prior.
+        let density = |positions: &[f64]| {
+            let mut counts = vec![1.0; num_values];
+            positions
+                .iter()
+                .for_each(|position| counts[*position as usize] += 1.0);
+            let total = counts.iter().sum::<f64>();
+            counts.iter().map(|count| count / total).collect::<Vec<_>>()
+        };
+        let l = density(below);
+        let g = density(above);
+
+        (0..self.candidates)
+            .map(|_| {
+                let mut threshold = rng.random::<f64>();
+                l.iter()
+                    .position(|probability| {
+                        threshold -= probability;
+                        threshold <= 0.0
+                    })
+                    .unwrap_or(num_values - 1)
+            })
+            .max_by(|a, b| (l[*a] / g[*a]).total_cmp(&(l[*b] / g[*b])))
+            .unwrap() as f64
+    }
+
+    fn sample_numeric(
+        &self,
+        rng: &mut StdRng,
+        bounds: (f64, f64),
+        below: &[f64],
+        above: &[f64],
+    ) -> f64 {
+        let l = ParzenEstimator::new(bounds, below);
+        let g = ParzenEstimator::new(bounds, above);
+
+        (0..self.candidates)
+            .map(|_| l.sample(rng))
+            .max_by(|a, b| {
+                let ratio = |x: f64| l.log_pdf(x) - g.log_pdf(x);
+                ratio(*a).total_cmp(&ratio(*b))
+            })
+            .unwrap()
+    }
+}
+
+impl Sampler for TpeSampler {
+    fn sample(
+        &mut self,
+        trial: usize,
+        space: &SearchSpace,
+        history: &[TrialRecord],
+        direction: Direction,
+    ) -> Option<TrialParams> {
+        let mut rng = trial_rng(self.seed, trial);
+        let mut completed = history
+            .iter()
+            .filter_map(|record| Some((record.value?, &record.params)))
+            .collect::<Vec<_>>();
+
+        if completed.len() < self.startup_trials.max(2) {
+            return Some(sample_uniform(&mut rng, space));
+        }
+
+        completed.sort_by(|(a, _), (b, _)| match direction {
+            Direction::Lowest => a.total_cmp(b),
+            Direction::Highest => b.total_cmp(a),
+        });
+        let num_below = ((self.gamma * completed.len() as f64).ceil() as usize).max(1);
+
+        let params = space
+            .params()
+            .iter()
+            .map(|(path, distribution)| {
+                let positions = |trials: &[(f64, &TrialParams)]| {
+                    trials
+                        .iter()
+                        .filter_map(|(_, params)| distribution.position(params.get(path)?))
+                        .collect::<Vec<_>>()
+                };
+                let below = positions(&completed[..num_below]);
+                let above = positions(&completed[num_below..]);
+
+                let x = match distribution {
+                    ParamDistribution::Choice(values) => {
+                        self.sample_categorical(&mut rng, values.len(), &below, &above)
+                    }
+                    distribution => {
+                        let bounds = distribution.bounds().unwrap();
+                        self.sample_numeric(&mut rng, bounds, &below, &above)
+                    }
+                };
+                (path.clone(), distribution.value(x))
+            })
+            .collect();
+
+        Some(params)
+    }
+}
+
+/// A mixture of truncated gaussians centered on the observations, with a wide prior centered
+/// on the middle of the bounds.
+struct ParzenEstimator {
+    low: f64,
+    high: f64,
+    mus: Vec<f64>,
+    sigmas: Vec<f64>,
+}
+
+impl ParzenEstimator {
+    fn new((low, high): (f64, f64), observations: &[f64]) -> Self {
+        let range = (high - low).max(f64::EPSILON);
+        let mut mus = vec![(low + high) / 2.0];
+        let mut sigmas = vec![range];
+
+        // The bandwidth of each observation is the distance to its farthest neighbor, clipped so
+        // that the density isn't degenerate.
+        let mut sorted = observations.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let min_sigma = range / (sorted.len() as f64 + 1.0).min(100.0);
+        for (index, mu) in sorted.iter().enumerate() {
+            let left = if index == 0 { low } else { sorted[index - 1] };
+            let right = sorted.get(index + 1).copied().unwrap_or(high);
+            let sigma = (mu - left).max(right - mu);
+
+            mus.push(*mu);
+            sigmas.push(sigma.clamp(min_sigma, range));
+        }
+
+        Self {
+            low,
+            high,
+            mus,
+            sigmas,
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        let component = rng.random_range(0..self.mus.len());
+        let (mu, sigma) = (self.mus[component], self.sigmas[component]);
+
+        // Rejection sampling of the truncated gaussian, clamped after a number of attempts.
+        for _ in 0..100 {
+            // Box-Muller transform.
+            let u1 = 1.0 - rng.random::<f64>();
+            let u2 = rng.random::<f64>();
+            let normal = (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos();
+            let x = mu + sigma * normal;
+
+            if (self.low..=self.high).contains(&x) {
+                return x;
+            }
+        }
+
+        mu.clamp(self.low, self.high)
+    }
+
+    fn log_pdf(&self, x: f64) -> f64 {
+        let pdf = self
+            .mus
+            .iter()
+            .zip(self.sigmas.iter())
+            .map(|(mu, sigma)| {
+                let z = (x - mu) / sigma;
+                (-0.5 * z * z).exp() / sigma
+            })
+            .sum::<f64>()
+            / self.mus.len() as f64;
+
+        pdf.max(f64::MIN_POSITIVE).ln()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweep::TrialState;
+
+    fn space() -> SearchSpace {
+        SearchSpace::new()
+            .choice("activation", ["relu", "gelu"])
+            .uniform("dropout", 0.0, 0.5)
+            .int("layers", 1, 3)
+    }
+
+    #[test]
+    fn test_grid_sampler() {
+        let space = space();
+        let mut sampler = GridSampler::new().with_steps(3);
+
+        let trials = (0..20)
+            .map_while(|trial| sampler.sample(trial, &space, &[], Direction::Lowest))
+            .collect::<Vec<_>>();
+
+        assert_eq!(trials.len(), 2 * 3 * 3);
+        assert_eq!(trials[0]["activation"], Value::from("relu"));
+        assert_eq!(trials[0]["dropout"], Value::from(0.0));
+        assert_eq!(trials[0]["layers"], Value::from(1));
+        assert_eq!(trials[1]["layers"], Value::from(2));
+        assert_eq!(trials[3]["dropout"], Value::from(0.25));
+        assert_eq!(trials[17]["activation"], Value::from("gelu"));
+        assert_eq!(trials[17]["dropout"], Value::from(0.5));
+        assert_eq!(trials[17]["layers"], Value::from(3));
+    }
+
+    #[test]
+    fn test_random_sampler_is_deterministic() {
+        let space = space();
+        let mut sampler = RandomSampler::new(42);
+
+        let first = sampler.sample(3, &space, &[], Direction::Lowest).unwrap();
+        let second = sampler.sample(3, &space, &[], Direction::Lowest).unwrap();
+        assert_eq!(first, second);
+
+        for trial in 0..20 {
+            let params = sampler
+                .sample(trial, &space, &[], Direction::Lowest)
+                .unwrap();
+            let dropout = params["dropout"].as_f64().unwrap();
+            let layers = params["layers"].as_i64().unwrap();
+            assert!((0.0..=0.5).contains(&dropout));
+            assert!((1..=3).contains(&layers));
+        }
+    }
+
+    #[test]
+    fn test_tpe_sampler_samples_near_best_trials() {
+        let space = SearchSpace::new()
+            .uniform("x", 0.0, 1.0)
+            .choice("activation", ["relu", "gelu"]);
+        let history = (0..20)
+            .map(|id| {
+                let x = id as f64 / 20.0;
+                let activation = if x > 0.7 { "gelu" } else { "relu" };
+                TrialRecord {
+                    id,
+                    params: TrialParams::from([
+                        ("x".to_string(), Value::from(x)),
+                        ("activation".to_string(), Value::from(activation)),
+                    ]),
+                    state: TrialState::Complete,
+                    // The best trials have the highest x.
+                    value: Some((x - 0.9).abs()),
+                    intermediate_values: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut sampler = TpeSampler::new(0);
+
+        for trial in 20..30 {
+            let params = sampler
+                .sample(trial, &space, &history, Direction::Lowest)
+                .unwrap();
+            assert!(params["x"].as_f64().unwrap() > 0.5, "{params:?}");
+            assert_eq!(params["activation"], Value::from("gelu"));
+        }
+    }
+}
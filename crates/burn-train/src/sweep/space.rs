@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+
+use burn_core::config::Config;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The values of the parameters of a trial, by path of the config field.
+pub type TrialParams = BTreeMap<String, Value>;
+
+/// The distribution of the values of a parameter of the [search space](SearchSpace).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ParamDistribution {
+    /// One of the given values.
+    Choice(Vec<Value>),
+    /// A float uniformly distributed between the bounds.
+    Uniform {
+        /// The lower bound.
+        low: f64,
+        /// The upper bound.
+        high: f64,
+    },
+    /// A float whose logarithm is uniformly distributed between the logarithms of the bounds,
+    /// e.g. a learning rate.
+    LogUniform {
+        /// The lower bound, must be positive.
+        low: f64,
+        /// The upper bound.
+        high: f64,
+    },
+    /// An integer uniformly distributed between the bounds, inclusively.
+    Int {
+        /// The lower bound.
+        low: i64,
+        /// The upper bound.
+        high: i64,
+    },
+}
+
+impl ParamDistribution {
+    /// The bounds of the values of a numeric distribution, in the space they are uniformly
+    /// distributed, i.e. the logarithms of the bounds of a log-uniform distribution.
+    pub(crate) fn bounds(&self) -> Option<(f64, f64)> {
+        match self {
+            ParamDistribution::Choice(_) => None,
+            ParamDistribution::Uniform { low, high } => Some((*low, *high)),
+            ParamDistribution::LogUniform { low, high } => Some((low.ln(), high.ln())),
+            ParamDistribution::Int { low, high } => Some((*low as f64, *high as f64)),
+        }
+    }
+
+    /// Convert a value of the space the values are uniformly distributed in to a parameter.
+    pub(crate) fn value(&self, x: f64) -> Value {
+        match self {
+            ParamDistribution::Choice(values) => values[x as usize].clone(),
+            ParamDistribution::Uniform { low, high } => Value::from(x.clamp(*low, *high)),
+            ParamDistribution::LogUniform { low, high } => Value::from(x.exp().clamp(*low, *high)),
+            ParamDistribution::Int { low, high } => {
+                Value::from((x.round() as i64).clamp(*low, *high))
+            }
+        }
+    }
+
+    /// Convert a parameter to the space the values are uniformly distributed in, i.e. the index
+    /// of a choice.
+    pub(crate) fn position(&self, value: &Value) -> Option<f64> {
+        match self {
+            ParamDistribution::Choice(values) => values
+                .iter()
+                .position(|choice| choice == value)
+                .map(|index| index as f64),
+            ParamDistribution::LogUniform { .. } => value.as_f64().map(f64::ln),
+            _ => value.as_f64(),
+        }
+    }
+}
+
+/// The hyperparameters to search, as distributions of the values of config fields.
+///
+/// The fields are identified by their path in the serialized config, with the names of the
+/// nested configs separated by dots, e.g. `optimizer.weight_decay.penalty`.
+#[derive(Clone, Debug, Default)]
+pub struct SearchSpace {
+    params: Vec<(String, ParamDistribution)>,
+}
+
+impl SearchSpace {
+    /// Create an empty search space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Search the field among the given values.
+    pub fn choice<V: Serialize>(self, path: &str, values: impl IntoIterator<Item = V>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|value| serde_json::to_value(value).expect("Can serialize the value"))
+            .collect::<Vec<_>>();
+        assert!(!values.is_empty(), "The choices of {path} can't be empty");
+
+        self.param(path, ParamDistribution::Choice(values))
+    }
+
+    /// Search the float field uniformly between the bounds.
+    pub fn uniform(self, path: &str, low: f64, high: f64) -> Self {
+        assert!(low <= high, "The bounds of {path} must be increasing");
+        self.param(path, ParamDistribution::Uniform { low, high })
+    }
+
+    /// Search the float field on a logarithmic scale between the bounds.
+    pub fn log_uniform(self, path: &str, low: f64, high: f64) -> Self {
+        assert!(
+            low > 0.0 && low <= high,
+            "The bounds of {path} must be positive and increasing"
+        );
+        self.param(path, ParamDistribution::LogUniform { low, high })
+    }
+
+    /// Search the integer field between the bounds, inclusively.
+    pub fn int(self, path: &str, low: i64, high: i64) -> Self {
+        assert!(low <= high, "The bounds of {path} must be increasing");
+        self.param(path, ParamDistribution::Int { low, high })
+    }
+
+    /// Search the field with the given distribution, replacing the previous one of the field.
+    pub fn param(mut self, path: &str, distribution: ParamDistribution) -> Self {
+        self.params.retain(|(name, _)| name != path);
+        self.params.push((path.to_string(), distribution));
+        self
+    }
+
+    /// The searched fields and their distribution.
+    pub fn params(&self) -> &[(String, ParamDistribution)] {
+        &self.params
+    }
+
+    /// Create the config of a trial, replacing the fields of the base config with the values of
+    /// the parameters.
+    pub fn apply<C: Config>(&self, base: &C, params: &TrialParams) -> Result<C, String> {
+        let mut config = serde_json::to_value(base).map_err(|err| err.to_string())?;
+
+        for (path, value) in params.iter() {
+            let mut field = &mut config;
+            for name in path.split('.') {
+                field = field
+                    .as_object_mut()
+                    .and_then(|object| object.get_mut(name))
+                    .ok_or_else(|| format!("The config has no field {path}"))?;
+            }
+            *field = value.clone();
+        }
+
+        serde_json::from_value(config).map_err(|err| format!("Invalid trial config: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core as burn;
+
+    #[derive(Config, Debug)]
+    pub struct TestOptimConfig {
+        learning_rate: f64,
+    }
+
+    #[derive(Config, Debug)]
+    pub struct TestConfig {
+        optimizer: TestOptimConfig,
+        batch_size: usize,
+        activation: String,
+    }
+
+    #[test]
+    fn test_apply_params() {
+        let base = TestConfig::new(TestOptimConfig::new(0.1), 32, "relu".to_string());
+        let space = SearchSpace::new()
+            .log_uniform("optimizer.learning_rate", 1e-4, 1e-1)
+            .int("batch_size", 8, 64)
+            .choice("activation", ["relu", "gelu"]);
+        assert_eq!(space.params().len(), 3);
+
+        let params = TrialParams::from([
+            ("optimizer.learning_rate".to_string(), Value::from(0.01)),
+            ("batch_size".to_string(), Value::from(16)),
+            ("activation".to_string(), Value::from("gelu")),
+        ]);
+        let config = space.apply(&base, &params).unwrap();
+
+        assert_eq!(config.optimizer.learning_rate, 0.01);
+        assert_eq!(config.batch_size, 16);
+        assert_eq!(config.activation, "gelu");
+    }
+
+    #[test]
+    fn test_apply_unknown_field() {
+        let base = TestConfig::new(TestOptimConfig::new(0.1), 32, "relu".to_string());
+        let params = TrialParams::from([("optimizer.momentum".to_string(), Value::from(0.9))]);
+
+        let result = SearchSpace::new().apply(&base, &params);
+        assert_eq!(
+            result.unwrap_err(),
+            "The config has no field optimizer.momentum"
+        );
+    }
+
+    #[test]
+    fn test_distribution_positions() {
+        let distribution = ParamDistribution::LogUniform {
+            low: 1e-4,
+            high: 1.0,
+        };
+        let position = distribution.position(&Value::from(0.01)).unwrap();
+        assert_eq!(position, 0.01f64.ln());
+        let value = distribution.value(position).as_f64().unwrap();
+        assert!((value - 0.01).abs() < 1e-12);
+
+        let distribution = ParamDistribution::Choice(vec![Value::from("a"), Value::from("b")]);
+        assert_eq!(distribution.position(&Value::from("b")), Some(1.0));
+        assert_eq!(distribution.value(1.0), Value::from("b"));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::ir::{ArgType, Node};
+
+/// Configuration for LpNormalization operations
+#[derive(Debug, Clone, PartialEq)]
+pub struct LpNormalizationConfig {
+    /// The order of the norm (only 1 and 2 are supported by ONNX)
+    pub p: f64,
+    /// The axis to normalize over
+    pub axis: usize,
+}
+
+impl LpNormalizationConfig {
+    /// Create a new LpNormalizationConfig
+    pub fn new(p: f64, axis: usize) -> Self {
+        Self { p, axis }
+    }
+}
+
+/// Create a LpNormalizationConfig from the attributes of the node
+pub fn lp_normalization_config(node: &Node) -> LpNormalizationConfig {
+    let mut axis: i64 = -1;
+    let mut p: i64 = 2;
+
+    let tensor = match &node.inputs.first().unwrap().ty {
+        ArgType::Tensor(tensor) => tensor,
+        _ => panic!("LpNormalization: only tensor input is valid"),
+    };
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "axis" => axis = value.clone().into_i64(),
+            "p" => p = value.clone().into_i64(),
+            _ => panic!("Unexpected attribute for LpNormalization: {key}"),
+        }
+    }
+
+    assert!(
+        p == 1 || p == 2,
+        "LpNormalization: only p = 1 or p = 2 is supported (got {p})"
+    );
+
+    // if axis is negative, it is counted from the end
+    if axis < 0 {
+        axis += tensor.rank as i64;
+    }
+
+    LpNormalizationConfig::new(p as f64, axis as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::NodeType;
+    use crate::node::test_utils::NodeBuilder;
+
+    fn create_test_node(axis: Option<i64>, p: Option<i64>) -> Node {
+        let mut builder = NodeBuilder::new(NodeType::LpNormalization, "test_lp_normalization")
+            .input_tensor_f32("input", 3, None)
+            .output_tensor_f32("output", 3, None);
+
+        if let Some(axis) = axis {
+            builder = builder.attr_int("axis", axis);
+        }
+        if let Some(p) = p {
+            builder = builder.attr_int("p", p);
+        }
+
+        builder.build()
+    }
+
+    #[test]
+    fn test_lp_normalization_config_defaults() {
+        let node = create_test_node(None, None);
+        let config = lp_normalization_config(&node);
+        assert_eq!(config, LpNormalizationConfig::new(2.0, 2));
+    }
+
+    #[test]
+    fn test_lp_normalization_config_with_attrs() {
+        let node = create_test_node(Some(1), Some(1));
+        let config = lp_normalization_config(&node);
+        assert_eq!(config, LpNormalizationConfig::new(1.0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "only p = 1 or p = 2 is supported")]
+    fn test_lp_normalization_config_invalid_p() {
+        let node = create_test_node(None, Some(3));
+        let _ = lp_normalization_config(&node);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::ir::Node;
+
+/// Configuration for LRN operations
+#[derive(Debug, Clone, PartialEq)]
+pub struct LrnConfig {
+    /// The number of channels to sum over
+    pub size: usize,
+    /// Scaling parameter
+    pub alpha: f64,
+    /// The exponent
+    pub beta: f64,
+    /// The additive bias
+    pub bias: f64,
+}
+
+impl LrnConfig {
+    /// Create a new LrnConfig
+    pub fn new(size: usize, alpha: f64, beta: f64, bias: f64) -> Self {
+        Self {
+            size,
+            alpha,
+            beta,
+            bias,
+        }
+    }
+}
+
+/// Create a LrnConfig from the attributes of the node
+pub fn lrn_config(node: &Node) -> LrnConfig {
+    let mut size = None;
+    let mut alpha = 1e-4;
+    let mut beta = 0.75;
+    let mut bias = 1.0;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "size" => size = Some(value.clone().into_i64() as usize),
+            "alpha" => alpha = value.clone().into_f32() as f64,
+            "beta" => beta = value.clone().into_f32() as f64,
+            "bias" => bias = value.clone().into_f32() as f64,
+            _ => panic!("Unexpected attribute for LRN: {key}"),
+        }
+    }
+
+    let size = size.expect("LRN: size must be provided");
+    assert!(size > 0, "LRN: size must be greater than 0");
+    // ONNX sums over `[c - floor((size - 1) / 2), c + ceil((size - 1) / 2)]` while the burn module
+    // uses the PyTorch window `[c - floor(size / 2), c + floor((size - 1) / 2)]`, which only match
+    // for odd sizes.
+    assert!(
+        size % 2 == 1,
+        "LRN: only odd sizes are supported (got {size})"
+    );
+
+    LrnConfig::new(size, alpha, beta, bias)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::NodeType;
+    use crate::node::test_utils::NodeBuilder;
+
+    fn create_test_node(size: i64) -> Node {
+        NodeBuilder::new(NodeType::LRN, "test_lrn")
+            .input_tensor_f32("X", 4, None)
+            .output_tensor_f32("Y", 4, None)
+            .attr_int("size", size)
+            .build()
+    }
+
+    #[test]
+    fn test_lrn_config_defaults() {
+        let node = create_test_node(5);
+        let config = lrn_config(&node);
+        assert_eq!(config, LrnConfig::new(5, 1e-4, 0.75, 1.0));
+    }
+
+    #[test]
+    fn test_lrn_config_with_attrs() {
+        let node = NodeBuilder::new(NodeType::LRN, "test_lrn")
+            .input_tensor_f32("X", 4, None)
+            .output_tensor_f32("Y", 4, None)
+            .attr_int("size", 3)
+            .attr_float("alpha", 0.5)
+            .attr_float("beta", 1.0)
+            .attr_float("bias", 2.0)
+            .build();
+        let config = lrn_config(&node);
+        assert_eq!(config, LrnConfig::new(3, 0.5, 1.0, 2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "only odd sizes are supported")]
+    fn test_lrn_config_even_size() {
+        let node = create_test_node(4);
+        let _ = lrn_config(&node);
+    }
+}
@@ -45,6 +45,8 @@ pub mod layer_norm;
 pub mod leaky_relu;
 pub mod linear;
 pub mod log_softmax;
+pub mod lp_normalization;
+pub mod lrn;
 pub mod matmul;
 pub mod matmulinteger;
 pub mod max_pool1d;
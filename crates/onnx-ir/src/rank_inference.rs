@@ -80,6 +80,8 @@ pub fn rank_inference(node: &mut Node) {
         NodeType::Linear => linear_update_outputs(node),
         NodeType::Log => same_as_input(node),
         NodeType::LogSoftmax => same_as_input(node),
+        NodeType::LpNormalization => same_as_input(node),
+        NodeType::LRN => same_as_input(node),
         NodeType::MatMul => matmul_update_outputs(node),
         NodeType::MatMulInteger => matmulinteger_update_outputs(node),
         NodeType::Max => same_as_input_broadcast(node),